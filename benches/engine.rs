@@ -0,0 +1,271 @@
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use drone_network::backend::connections::{ConnectionGraph, Topology};
+use drone_network::backend::device::{
+    Device, DeviceBuilder, BROADCAST_ID, device_map_from_slice
+};
+use drone_network::backend::device::systems::{
+    MovementSystem, PowerSystem, RXModule, TRXSystem, TXModule
+};
+use drone_network::backend::malware::{Malware, MalwareType};
+use drone_network::backend::mathphysics::{
+    Float, Frequency, Megahertz, Meter, Millisecond, Point3D, PowerUnit
+};
+use drone_network::backend::networkmodel::{NetworkModel, NetworkModelBuilder};
+use drone_network::backend::networkmodel::attack::{AttackType, AttackerDevice};
+use drone_network::backend::parameters::SimulationParameters;
+use drone_network::backend::networkmodel::gps::GPS;
+use drone_network::backend::signal::{
+    Data, FreqToStrengthMap, Signal, SignalQueue, SignalStrength,
+    GREEN_SIGNAL_STRENGTH
+};
+use drone_network::backend::device::IdToDelayMap;
+use drone_network::backend::task::{Scenario, Task};
+
+
+const DEVICE_MAX_POWER: PowerUnit   = PowerUnit::new(100_000);
+const FLEET_SIZES: [usize; 3]       = [10, 50, 100];
+const QUEUE_SIZES: [usize; 2]       = [100, 1_000];
+// About half of the signals in filled queues are older than that.
+const QUEUE_PROBE_TIME: Millisecond = Millisecond::new(480);
+const SWARM_SIDE: Float             = 100.0;
+
+
+fn tx_module(frequency: Frequency, radius: Meter) -> TXModule {
+    let tx_signal_strength = SignalStrength::from_area_radius(
+        radius,
+        Frequency::Control as Megahertz
+    );
+
+    TXModule::new(FreqToStrengthMap::from([(frequency, tx_signal_strength)]))
+}
+
+fn rx_module() -> RXModule {
+    RXModule::new(
+        FreqToStrengthMap::from([
+            (Frequency::Control, SignalStrength::new(10_000.0)),
+            (Frequency::GPS, GREEN_SIGNAL_STRENGTH),
+        ])
+    )
+}
+
+fn power_system() -> PowerSystem {
+    PowerSystem::build(DEVICE_MAX_POWER, DEVICE_MAX_POWER)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+fn drone(position: Point3D) -> Device {
+    DeviceBuilder::new()
+        .set_real_position(position)
+        .set_power_system(power_system())
+        .set_movement_system(
+            MovementSystem::build(
+                SimulationParameters::default().max_drone_speed()
+            )
+                .unwrap_or_else(|error| panic!("{}", error))
+        )
+        .set_trx_system(
+            TRXSystem::new(
+                tx_module(Frequency::Control, Meter::new(50.0)),
+                rx_module()
+            )
+        )
+        .build()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn swarm_model(drone_count: usize, topology: Topology) -> NetworkModel {
+    let command_center = DeviceBuilder::new()
+        .set_real_position(Point3D::new(SWARM_SIDE, SWARM_SIDE, 0.0))
+        .set_power_system(power_system())
+        .set_trx_system(
+            TRXSystem::new(
+                tx_module(Frequency::Control, Meter::new(300.0)),
+                rx_module()
+            )
+        )
+        .build();
+    let command_center_id = command_center.id();
+
+    // Drones are placed on a deterministic grid, so every run measures
+    // the same network.
+    let side = (drone_count as Float).sqrt().ceil() as usize;
+    let step = SWARM_SIDE / side as Float;
+    let mut devices: Vec<Device> = (0..drone_count)
+        .map(|index| {
+            let x = (index % side) as Float * step;
+            let y = (index / side) as Float * step;
+
+            drone(Point3D::new(x, y, 25.0))
+        })
+        .collect();
+    devices.insert(0, command_center);
+
+    let gps = GPS::new(
+        DeviceBuilder::new()
+            .set_real_position(Point3D::new(50.0, 50.0, 200.0))
+            .set_power_system(power_system())
+            .set_trx_system(
+                TRXSystem::new(
+                    tx_module(Frequency::GPS, Meter::new(350.0)),
+                    RXModule::default()
+                )
+            )
+            .build()
+    );
+
+    let malware = Malware::new(
+        MalwareType::Indicator,
+        Millisecond::new(1_000),
+        Some(Millisecond::new(500)),
+        None
+    );
+    let attacker = DeviceBuilder::new()
+        .set_real_position(Point3D::new(-10.0, 2.0, 0.0))
+        .set_power_system(power_system())
+        .set_trx_system(
+            TRXSystem::new(
+                tx_module(Frequency::Control, Meter::new(30.0)),
+                RXModule::default()
+            )
+        )
+        .build();
+
+    NetworkModelBuilder::new()
+        .set_command_center_id(command_center_id)
+        .set_device_map(device_map_from_slice(&devices))
+        .set_attacker_devices(vec![
+            AttackerDevice::new(
+                attacker,
+                AttackType::MalwareDistribution(malware)
+            )
+        ])
+        .set_gps(gps)
+        .set_topology(topology)
+        .set_scenario(
+            Scenario::from([
+                (
+                    Millisecond::ZERO,
+                    BROADCAST_ID,
+                    Task::Reposition(Point3D::default())
+                )
+            ])
+        )
+        .set_delay_multiplier(1.0)
+        .build()
+}
+
+fn bench_network_model_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("network_model_update");
+
+    for topology in [Topology::Star, Topology::Mesh] {
+        for drone_count in FLEET_SIZES {
+            let model = swarm_model(drone_count, topology);
+
+            group.bench_with_input(
+                BenchmarkId::new(format!("{topology:?}"), drone_count),
+                &model,
+                |b, model| b.iter_batched(
+                    || model.clone(),
+                    |mut model| model.update(),
+                    BatchSize::SmallInput,
+                )
+            );
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_connection_graph_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("connection_graph_update");
+
+    for topology in [Topology::Star, Topology::Mesh] {
+        for drone_count in FLEET_SIZES {
+            let model = swarm_model(drone_count, topology);
+            let mut connections = ConnectionGraph::new(topology);
+
+            group.bench_function(
+                BenchmarkId::new(format!("{topology:?}"), drone_count),
+                |b| b.iter(|| connections.update(
+                    model.command_device_id(),
+                    model.device_map()
+                ))
+            );
+        }
+    }
+
+    group.finish();
+}
+
+fn filled_signal_queue(queue_size: usize) -> SignalQueue {
+    let signal = Signal::new(
+        1,
+        2,
+        Data::Noise,
+        Frequency::Control,
+        GREEN_SIGNAL_STRENGTH
+    );
+    let mut signal_queue = SignalQueue::new();
+
+    for time in 0..queue_size {
+        // Entries arrive slightly out of order, like delayed malware signals
+        // do.
+        let time = i32::try_from(time % 97 * 10).unwrap_or_default();
+
+        signal_queue.add_entry(
+            Millisecond::new(time),
+            signal,
+            IdToDelayMap::from([(2, Millisecond::new(time % 30))])
+        );
+    }
+
+    signal_queue
+}
+
+fn bench_signal_queue(c: &mut Criterion) {
+    let mut group = c.benchmark_group("signal_queue");
+
+    for queue_size in QUEUE_SIZES {
+        group.bench_with_input(
+            BenchmarkId::new("add_entry", queue_size),
+            &queue_size,
+            |b, queue_size| b.iter(|| filled_signal_queue(*queue_size))
+        );
+
+        let signal_queue = filled_signal_queue(queue_size);
+
+        group.bench_with_input(
+            BenchmarkId::new("get_current_signals_for", queue_size),
+            &signal_queue,
+            |b, signal_queue| b.iter(||
+                signal_queue
+                    .get_current_signals_for(2, QUEUE_PROBE_TIME)
+                    .count()
+            )
+        );
+        group.bench_with_input(
+            BenchmarkId::new("remove_old_signals", queue_size),
+            &signal_queue,
+            |b, signal_queue| b.iter_batched(
+                || signal_queue.clone(),
+                |mut signal_queue| {
+                    signal_queue.remove_old_signals(QUEUE_PROBE_TIME);
+                    signal_queue
+                },
+                BatchSize::SmallInput,
+            )
+        );
+    }
+
+    group.finish();
+}
+
+
+criterion_group!(
+    benches,
+    bench_network_model_update,
+    bench_connection_graph_update,
+    bench_signal_queue
+);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+fn main() {
+    #[cfg(feature = "grpc")]
+    compile_protos();
+}
+
+// `protox` is used instead of `protoc`, so no system dependencies are
+// required to build the gRPC server.
+#[cfg(feature = "grpc")]
+fn compile_protos() {
+    const PROTO_PATH: &str = "proto/simulation.proto";
+
+    println!("cargo:rerun-if-changed={PROTO_PATH}");
+
+    let file_descriptors = protox::compile([PROTO_PATH], ["proto"])
+        .expect("Failed to parse protobuf definitions");
+
+    tonic_build::configure()
+        .compile_fds(file_descriptors)
+        .expect("Failed to generate gRPC server");
+}
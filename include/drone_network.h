@@ -0,0 +1,53 @@
+/* C API of the `ffi` feature, see `src/ffi.rs`. */
+#ifndef DRONE_NETWORK_H
+#define DRONE_NETWORK_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct DnNetworkModel DnNetworkModel;
+
+typedef struct DnDeviceState {
+    size_t id;
+    float x;
+    float y;
+    float z;
+    float yaw;
+    float battery_level;
+    bool infected;
+    bool shut_down;
+} DnDeviceState;
+
+/* Returns NULL on failure, the reason is read with `dn_last_error`. */
+DnNetworkModel *dn_model_from_json(const char *json);
+void dn_model_destroy(DnNetworkModel *model);
+
+/* Returns 0 on success and -1 on failure. */
+int32_t dn_model_update(DnNetworkModel *model, uint32_t iterations);
+int32_t dn_model_current_time(const DnNetworkModel *model);
+size_t dn_model_command_device_id(const DnNetworkModel *model);
+size_t dn_model_device_count(const DnNetworkModel *model);
+/* Writes up to `capacity` states ordered by ID, returns the written count. */
+size_t dn_model_device_states(
+    const DnNetworkModel *model,
+    DnDeviceState *states,
+    size_t capacity
+);
+
+/* The string is freed with `dn_string_free`. */
+char *dn_model_to_json(const DnNetworkModel *model);
+void dn_string_free(char *string);
+
+const char *dn_last_error(void);
+void dn_set_seed(uint64_t seed);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
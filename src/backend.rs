@@ -1,14 +1,28 @@
 use mathphysics::{Meter, Millisecond};
 
 
+pub mod aggregation;
+pub mod collision;
 pub mod connections;
+pub mod coverage;
 pub mod device;
+pub mod epidemic;
+pub mod event;
+pub mod fault;
+pub mod landing;
+pub mod linkloss;
 pub mod malware;
 pub mod mathphysics;
+pub mod metrics;
 pub mod networkmodel;
+pub mod parameters;
+pub mod provenance;
+pub mod random;
+pub mod recon;
 pub mod signal;
 pub mod task;
+pub mod weather;
 
 
-pub const DESTINATION_RADIUS: Meter   = 5.0;
-pub const ITERATION_TIME: Millisecond = 50;
+pub const DESTINATION_RADIUS: Meter   = Meter::new(5.0);
+pub const ITERATION_TIME: Millisecond = Millisecond::new(50);
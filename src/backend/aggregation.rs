@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::ITERATION_TIME;
+use super::device::DeviceId;
+use super::device::systems::{ReconRecord, ReconSummary};
+use super::mathphysics::Millisecond;
+
+
+// Relays collect reconnaissance records heading to the command center and
+// send one summary per target every `interval` instead of a signal per
+// record. Records are kept by the last relay before the command center,
+// which they reach after the delay along the path to it. Earlier hops are
+// not transmitted, so they spend no energy and load no channel. By default
+// records are not aggregated, because the interval is 0.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct Aggregator {
+    interval: Millisecond,
+    // Records by the relays collecting them, with the times they reach
+    // the relays.
+    #[serde(default)]
+    pending_records: BTreeMap<DeviceId, Vec<(Millisecond, ReconRecord)>>,
+    // Records and summaries since aggregation started.
+    #[serde(default)]
+    record_count: usize,
+    #[serde(default)]
+    summary_count: usize,
+}
+
+impl Aggregator {
+    #[must_use]
+    pub fn new(interval: Millisecond) -> Self {
+        Self { interval, ..Default::default() }
+    }
+
+    #[must_use]
+    pub fn interval(&self) -> Millisecond {
+        self.interval
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.interval > Millisecond::ZERO
+    }
+
+    #[must_use]
+    pub fn flushes_at(&self, time: Millisecond) -> bool {
+        self.is_enabled() && time % self.interval < ITERATION_TIME
+    }
+
+    pub fn add_record(
+        &mut self,
+        relay_id: DeviceId,
+        record: ReconRecord,
+        arrival_time: Millisecond
+    ) {
+        self.pending_records
+            .entry(relay_id)
+            .or_default()
+            .push((arrival_time, record));
+        self.record_count += 1;
+    }
+
+    // Summaries of records that reached their relays by `current_time`, by
+    // the relays that send them. Records still on their way wait for the
+    // next flush.
+    pub fn take_summaries(
+        &mut self,
+        current_time: Millisecond
+    ) -> Vec<(DeviceId, ReconSummary)> {
+        let mut summaries = Vec::new();
+
+        for (relay_id, records) in &mut self.pending_records {
+            let (arrived_records, records_on_the_way): (Vec<_>, Vec<_>) =
+                std::mem::take(records)
+                    .into_iter()
+                    .partition(|(arrival_time, _)|
+                        *arrival_time <= current_time
+                    );
+            let arrived_records: Vec<ReconRecord> = arrived_records
+                .into_iter()
+                .map(|(_, record)| record)
+                .collect();
+
+            *records = records_on_the_way;
+            summaries.extend(
+                ReconSummary::per_target(&arrived_records)
+                    .into_iter()
+                    .map(|summary| (*relay_id, summary))
+            );
+        }
+
+        self.pending_records.retain(|_, records| !records.is_empty());
+        self.summary_count += summaries.len();
+
+        summaries
+    }
+
+    // Records per summary sent, `None` until the first summary.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn ratio(&self) -> Option<f32> {
+        (self.summary_count > 0)
+            .then(|| self.record_count as f32 / self.summary_count as f32)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::mathphysics::Point3D;
+
+    use super::*;
+
+
+    const FLUSH_TIME: Millisecond = Millisecond::new(500);
+
+
+    fn record(
+        time: i32,
+        observer_id: DeviceId,
+        target_id: DeviceId
+    ) -> ReconRecord {
+        ReconRecord::new(
+            Millisecond::new(time),
+            observer_id,
+            target_id,
+            Point3D::default()
+        )
+    }
+
+
+    #[test]
+    fn relays_send_one_summary_per_target_and_interval() {
+        let mut aggregator = Aggregator::new(FLUSH_TIME);
+        let arrival_time = Millisecond::new(200);
+
+        aggregator.add_record(1, record(100, 1, 7), arrival_time);
+        aggregator.add_record(1, record(200, 2, 7), arrival_time);
+        aggregator.add_record(1, record(150, 3, 7), arrival_time);
+        aggregator.add_record(1, record(100, 3, 8), arrival_time);
+        aggregator.add_record(4, record(100, 4, 7), arrival_time);
+
+        assert!(!aggregator.flushes_at(Millisecond::new(300)));
+        assert!(aggregator.flushes_at(FLUSH_TIME));
+        assert_eq!(aggregator.ratio(), None);
+
+        let summaries = aggregator.take_summaries(FLUSH_TIME);
+
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(summaries[0].0, 1);
+        assert_eq!(summaries[0].1.record_count(), 3);
+        assert_eq!(summaries[0].1.latest().observer_id(), 2);
+        assert_eq!(summaries[1].0, 1);
+        assert_eq!(summaries[1].1.latest().target_id(), 8);
+        assert_eq!(summaries[2].0, 4);
+        assert!(
+            aggregator.ratio()
+                .is_some_and(|ratio| (ratio - 5.0 / 3.0).abs() < 1e-6)
+        );
+        assert!(aggregator.take_summaries(FLUSH_TIME).is_empty());
+    }
+
+    #[test]
+    fn records_on_the_way_to_relay_wait_for_next_flush() {
+        let mut aggregator = Aggregator::new(FLUSH_TIME);
+
+        aggregator.add_record(1, record(450, 2, 7), Millisecond::new(550));
+
+        assert!(aggregator.take_summaries(FLUSH_TIME).is_empty());
+
+        let summaries = aggregator.take_summaries(FLUSH_TIME * 2);
+
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].1.latest().observer_id(), 2);
+    }
+
+    #[test]
+    fn default_aggregator_is_disabled() {
+        let aggregator = Aggregator::default();
+
+        assert!(!aggregator.is_enabled());
+        assert!(!aggregator.flushes_at(Millisecond::ZERO));
+    }
+}
@@ -0,0 +1,102 @@
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::mathphysics::Meter;
+use super::random;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub enum CollisionOutcome {
+    Destroyed,
+    // Health lost by each device.
+    Damaged(f32),
+    // Devices bounce off each other unharmed.
+    Deflected,
+}
+
+
+// By default the collision distance is 0.0, so collisions are not detected.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct CollisionPolicy {
+    collision_distance: Meter,
+    destroy_probability: f64,
+    damage_probability: f64,
+    damage: f32,
+}
+
+impl CollisionPolicy {
+    // Collisions that neither destroy nor damage devices deflect them.
+    #[must_use]
+    pub fn new(
+        collision_distance: Meter,
+        destroy_probability: f64,
+        damage_probability: f64,
+        damage: f32,
+    ) -> Self {
+        Self {
+            collision_distance,
+            destroy_probability: destroy_probability.clamp(0.0, 1.0),
+            damage_probability: damage_probability.clamp(0.0, 1.0),
+            damage: damage.clamp(0.0, 1.0),
+        }
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.collision_distance > Meter::ZERO
+    }
+
+    #[must_use]
+    pub fn collision_distance(&self) -> Meter {
+        self.collision_distance
+    }
+
+    #[must_use]
+    pub fn draw_outcome(&self) -> CollisionOutcome {
+        let draw = random::with_rng(|rng| rng.random::<f64>());
+
+        if draw < self.destroy_probability {
+            CollisionOutcome::Destroyed
+        } else if draw < self.destroy_probability + self.damage_probability {
+            CollisionOutcome::Damaged(self.damage)
+        } else {
+            CollisionOutcome::Deflected
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn default_collision_policy_is_disabled() {
+        assert!(!CollisionPolicy::default().is_enabled());
+    }
+
+    #[test]
+    fn outcome_follows_probabilities() {
+        let distance = Meter::new(1.0);
+        let destroying_policy = CollisionPolicy::new(distance, 1.0, 0.0, 0.5);
+        let damaging_policy = CollisionPolicy::new(distance, 0.0, 1.0, 0.5);
+        let deflecting_policy = CollisionPolicy::new(distance, 0.0, 0.0, 0.5);
+
+        for _ in 0..100 {
+            assert_eq!(
+                destroying_policy.draw_outcome(),
+                CollisionOutcome::Destroyed
+            );
+            assert_eq!(
+                damaging_policy.draw_outcome(),
+                CollisionOutcome::Damaged(0.5)
+            );
+            assert_eq!(
+                deflecting_policy.draw_outcome(),
+                CollisionOutcome::Deflected
+            );
+        }
+    }
+}
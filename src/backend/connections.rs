@@ -1,5 +1,7 @@
+use std::borrow::Cow;
 use std::fmt;
 
+use schemars::{JsonSchema, Schema, SchemaGenerator};
 use serde::{self, Serialize};
 use serde::ser::{Serializer, SerializeStruct};
 use serde::de::{self, Deserialize, Deserializer, Visitor, SeqAccess, MapAccess};
@@ -14,7 +16,7 @@ use rustworkx_core::shortest_path::{astar, dijkstra};
 use super::device::{
     Device, DeviceId, IdToDelayMap, IdToDeviceMap, BROADCAST_ID
 };
-use super::mathphysics::{delay_to, Frequency, Meter, Position};
+use super::mathphysics::{delay_to, Meter, Millisecond, Position};
 use super::signal::SignalStrength;
 
 
@@ -23,6 +25,21 @@ type SerdeEdge      = (DeviceId, DeviceId, (Meter, SignalStrength));
 type ConnectionMap  = GraphMap<DeviceId, (Meter, SignalStrength), Directed>;
 
 
+// Devices on the path after the source process the signal before passing
+// it on or acting on it.
+fn processing_delay_along(
+    path: &[DeviceId],
+    device_map: &IdToDeviceMap
+) -> Millisecond {
+    path
+        .iter()
+        .skip(1)
+        .filter_map(|device_id| device_map.get(device_id))
+        .map(Device::processing_delay)
+        .sum()
+}
+
+
 #[derive(Error, Debug)]
 pub enum ShortestPathError {
     #[error("Shortest path was not found")]
@@ -32,13 +49,29 @@ pub enum ShortestPathError {
 }
     
 
-#[derive(Clone, Copy, Debug, Default, Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Copy, Debug, Default, JsonSchema, Serialize, serde::Deserialize
+)]
 pub enum Topology {
     Mesh,
     #[default]
     Star,
 }
 
+impl Topology {
+    // Every topology, so runs can be compared across all of them.
+    pub const ALL: [Self; 2] = [Self::Mesh, Self::Star];
+}
+
+
+// Format of the serialized `ConnectionGraph`, it only describes the schema.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+struct SerdeConnectionGraph {
+    edges: Vec<SerdeEdge>,
+    topology: Topology,
+}
+
 
 #[derive(Clone, Debug, Default)]
 pub struct ConnectionGraph {
@@ -60,6 +93,11 @@ impl ConnectionGraph {
         &self.graph_map
     }
 
+    #[must_use]
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
     // Currently, it considers only distances between devices while building the 
     // most efficient paths. It ignores signal qualities of devices.
     pub fn update(
@@ -79,6 +117,15 @@ impl ConnectionGraph {
         }
     }
 
+    pub fn remove_connection(
+        &mut self,
+        device_id1: DeviceId,
+        device_id2: DeviceId
+    ) {
+        self.graph_map.remove_edge(device_id1, device_id2);
+        self.graph_map.remove_edge(device_id2, device_id1);
+    }
+
     fn create_star(
         &mut self,
         central_device: &Device,
@@ -90,8 +137,10 @@ impl ConnectionGraph {
     }
 
     fn create_mesh(&mut self, device_map: &IdToDeviceMap) {
-        for tx in device_map.values() {
-            for rx in device_map.values() {
+        // `connect_devices` links both directions, so every pair of devices
+        // is visited only once.
+        for (index, tx) in device_map.values().enumerate() {
+            for rx in device_map.values().skip(index + 1) {
                 self.connect_devices(tx, rx);    
             }
         }
@@ -116,9 +165,16 @@ impl ConnectionGraph {
         device2: &Device,
         distance: Meter,
     ) {
+        let frequency = device1.control_frequency();
+
+        // Devices on different control channels do not hear each other.
+        if device2.control_frequency() != frequency {
+            return;
+        }
+
         if let Some(tx_signal_strength_from_1) = device1.tx_signal_strength_at(
             device2, 
-            Frequency::Control
+            frequency
         ) {
             if tx_signal_strength_from_1.is_black() {
                 return;
@@ -144,6 +200,7 @@ impl ConnectionGraph {
             self.delay_map_from_inside_network(
                 source_device.id(), 
                 destination_id,
+                device_map,
                 delay_multiplier
             )
         } else {
@@ -156,19 +213,33 @@ impl ConnectionGraph {
         }
     }
 
+    // Signals are relayed along the shortest paths, so every relay adds its
+    // processing delay.
     fn delay_map_from_inside_network(
         &self,
         source: DeviceId,
         destination: DeviceId,
+        device_map: &IdToDeviceMap,
         delay_multiplier: f32,
     ) -> IdToDelayMap {
-        let distance_map = self.dijkstra(source, destination)
+        let mut path_map = DictMap::default();
+        let distance_map = self.dijkstra_with_paths(
+            source, 
+            destination, 
+            Some(&mut path_map)
+        )
             .unwrap_or_else(|error| panic!("{}", error));
 
         distance_map
             .iter()
             .map(|(device_id, distance)| {
-                let delay = delay_to(*distance, delay_multiplier);
+                let processing_delay = path_map
+                    .get(device_id)
+                    .map_or(Millisecond::ZERO, |path| 
+                        processing_delay_along(path, device_map)
+                    );
+                let delay = delay_to(*distance, delay_multiplier) 
+                    + processing_delay;
             
                 (*device_id, delay)
             })
@@ -182,25 +253,23 @@ impl ConnectionGraph {
         device_map: &IdToDeviceMap,
         delay_multiplier: f32,
     ) -> IdToDelayMap {
-        let destination_ids: Vec<DeviceId> = if destination_id == BROADCAST_ID {
-            self.graph_map.nodes().collect()
-        } else if self.graph_map.contains_node(destination_id) {
-            vec![destination_id]
-        } else {
-            Vec::new()
-        };
-
-        destination_ids
-            .iter()
+        self.graph_map
+            .nodes()
+            .filter(|node_id| 
+                destination_id == BROADCAST_ID || *node_id == destination_id
+            )
             .filter_map(|destination_id| {
-                let destination_device = device_map.get(destination_id)?; 
+                let destination_device = device_map.get(&destination_id)?; 
                 
                 let delay = delay_to(
                     source_device.distance_to(destination_device), 
                     delay_multiplier
+                ) + processing_delay_along(
+                    &[source_device.id(), destination_id], 
+                    device_map
                 );
 
-                Some((*destination_id, delay))
+                Some((destination_id, delay))
             })
             .collect()
     }
@@ -213,7 +282,17 @@ impl ConnectionGraph {
         &self,
         source: DeviceId,
         destination: DeviceId,
-    ) -> rustworkx_core::Result<DictMap<DeviceId, f32>> {
+    ) -> rustworkx_core::Result<DictMap<DeviceId, Meter>> {
+        self.dijkstra_with_paths(source, destination, None)
+    }
+
+    // Fills `path_map` with the shortest paths starting with `source`.
+    fn dijkstra_with_paths(
+        &self,
+        source: DeviceId,
+        destination: DeviceId,
+        path_map: Option<&mut DictMap<DeviceId, Vec<DeviceId>>>
+    ) -> rustworkx_core::Result<DictMap<DeviceId, Meter>> {
         let destination = if destination == BROADCAST_ID {
             None
         } else {
@@ -225,7 +304,7 @@ impl ConnectionGraph {
             source,
             destination,
             |edge| Ok(edge.weight().0),
-            None
+            path_map
         )
     }
 
@@ -246,7 +325,7 @@ impl ConnectionGraph {
                 Ok(finish == destination)
             },
             |edge| Ok(edge.weight().0),
-            |_| Ok(0.0)
+            |_| Ok(Meter::ZERO)
         ) else {
             return Err(ShortestPathError::NoPathFound);
         };
@@ -257,6 +336,55 @@ impl ConnectionGraph {
         
         Ok((distance, path))
     }
+
+    // Delay along the shortest path and signal strength of its last hop, so
+    // relayed signals can be delivered to the destination.
+    #[must_use]
+    pub fn relay_route(
+        &self,
+        source: DeviceId,
+        destination: DeviceId,
+        device_map: &IdToDeviceMap,
+        delay_multiplier: f32,
+    ) -> Option<(Millisecond, SignalStrength)> {
+        let (distance, path) = self
+            .find_shortest_path_from_to(source, destination)
+            .ok()?;
+        let last_hop = path[path.len() - 2];
+        let (_, signal_strength) = self.graph_map.edge_weight(
+            last_hop, 
+            destination
+        )?;
+        let delay = delay_to(distance, delay_multiplier)
+            + processing_delay_along(&path, device_map);
+
+        Some((delay, *signal_strength))
+    }
+
+    // The device that relays signals along the shortest path directly to the
+    // destination. It is the source itself if they are connected directly.
+    #[must_use]
+    pub fn last_hop(
+        &self,
+        source: DeviceId,
+        destination: DeviceId
+    ) -> Option<DeviceId> {
+        let (_, path) = self
+            .find_shortest_path_from_to(source, destination)
+            .ok()?;
+
+        Some(path[path.len() - 2])
+    }
+}
+
+impl JsonSchema for ConnectionGraph {
+    fn schema_name() -> Cow<'static, str> {
+        "ConnectionGraph".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        SerdeConnectionGraph::json_schema(generator)
+    }
 }
 
 impl Serialize for ConnectionGraph {
@@ -366,7 +494,9 @@ mod tests {
     use crate::backend::device::systems::{
         PowerSystem, RXModule, TRXSystem, TXModule, 
     };
-    use crate::backend::mathphysics::{Megahertz, Point3D, PowerUnit};
+    use crate::backend::mathphysics::{
+        Frequency, Megahertz, Point3D, PowerUnit
+    };
     use crate::backend::signal::{
         FreqToStrengthMap, GREEN_SIGNAL_STRENGTH, SignalStrength
     };
@@ -374,9 +504,9 @@ mod tests {
     use super::*;
     
 
-    const CC_TX_CONTROL_RADIUS: Meter    = 300.0;
-    const DEVICE_MAX_POWER: PowerUnit    = 1_000;
-    const DRONE_TX_CONTROL_RADIUS: Meter = 10.0;
+    const CC_TX_CONTROL_RADIUS: Meter    = Meter::new(300.0);
+    const DEVICE_MAX_POWER: PowerUnit    = PowerUnit::new(1_000);
+    const DRONE_TX_CONTROL_RADIUS: Meter = Meter::new(10.0);
     
 
     fn device_power_system() -> PowerSystem {
@@ -533,4 +663,35 @@ mod tests {
         assert!(connections.graph_map.contains_edge(drone_c_id, drone_e_id));
         assert!(connections.graph_map.contains_edge(drone_e_id, drone_c_id));
     }
+
+    #[test]
+    fn relays_add_processing_delay() {
+        let processing_delay = Millisecond::new(100);
+        let drone = |x| DeviceBuilder::new()
+            .set_real_position(Point3D::new(x, 0.0, 0.0))
+            .set_power_system(device_power_system())
+            .set_trx_system(control_trx_system(DRONE_TX_CONTROL_RADIUS))
+            .set_processing_delay(processing_delay)
+            .build();
+        let command_center = drone_with_trx_system_set(Point3D::default());
+        let devices = [command_center, drone(7.0), drone(14.0)];
+        let device_map = device_map_from_slice(&devices);
+
+        let mut connections = ConnectionGraph::new(Topology::Mesh);
+        
+        connections.update(devices[0].id(), &device_map);
+
+        let delay_map = connections.delay_map(
+            &devices[0], 
+            BROADCAST_ID, 
+            &device_map, 
+            1.0
+        );
+
+        assert_eq!(delay_map.get(&devices[1].id()), Some(&processing_delay));
+        assert_eq!(
+            delay_map.get(&devices[2].id()),
+            Some(&(processing_delay + processing_delay))
+        );
+    }
 }
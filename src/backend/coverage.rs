@@ -0,0 +1,282 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::device::{Device, BROADCAST_ID};
+use super::mathphysics::{Float, Point3D, Position};
+use super::networkmodel::NetworkModel;
+use super::networkmodel::attack::AttackType;
+
+
+// The area is sampled at the centers of a grid with this many cells along
+// each side, so the cost does not depend on the area size.
+const CELLS_PER_SIDE: usize = 50;
+
+
+// Fractions of the mission area covered at one iteration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Coverage {
+    // Seen by a sensor of a working drone.
+    sensor: f32,
+    // Reached by a control signal of a device connected to the command
+    // device that is stronger than the signal of any jammer.
+    control: f32,
+}
+
+impl Coverage {
+    #[must_use]
+    pub fn sensor(&self) -> f32 {
+        self.sensor
+    }
+
+    #[must_use]
+    pub fn control(&self) -> f32 {
+        self.control
+    }
+}
+
+
+// Ground rectangle of a mission, e.g. the area to search.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct CoverageArea {
+    min_x: Float,
+    min_y: Float,
+    max_x: Float,
+    max_y: Float,
+}
+
+impl CoverageArea {
+    // Corners may be given in any order.
+    #[must_use]
+    pub fn new(
+        corner: (Float, Float),
+        opposite_corner: (Float, Float)
+    ) -> Self {
+        Self {
+            min_x: corner.0.min(opposite_corner.0),
+            min_y: corner.1.min(opposite_corner.1),
+            max_x: corner.0.max(opposite_corner.0),
+            max_y: corner.1.max(opposite_corner.1),
+        }
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn coverage(&self, network_model: &NetworkModel) -> Coverage {
+        let drones: Vec<&Device> = network_model
+            .drones()
+            .filter(|drone| !drone.is_shut_down())
+            .collect();
+
+        // Dijkstra never fails; missing command device means no device is
+        // connected.
+        let connected_ids = network_model
+            .connections()
+            .dijkstra(network_model.command_device_id(), BROADCAST_ID)
+            .unwrap_or_default();
+        let connected_devices: Vec<&Device> = network_model
+            .device_map()
+            .iter()
+            .filter(|(device_id, device)|
+                connected_ids.contains_key(device_id)
+                    && !device.is_shut_down()
+            )
+            .map(|(_, device)| device)
+            .collect();
+        let jammers: Vec<&Device> = network_model
+            .attacker_devices()
+            .iter()
+            .filter(|attacker| matches!(
+                attacker.attack_type(),
+                AttackType::ElectronicWarfare
+            ))
+            .map(|attacker| attacker.device())
+            .collect();
+
+        let cells = self.cell_centers();
+        let cell_count = cells.len() as f32;
+        let sensor_count = cells
+            .iter()
+            .filter(|cell|
+                drones.iter().any(|drone|
+                    drone.sensor_system().detects(drone.position(), cell)
+                )
+            )
+            .count();
+        let control_count = cells
+            .iter()
+            .filter(|cell|
+                connected_devices.iter().any(|device|
+                    has_control_at(device, cell, &jammers)
+                )
+            )
+            .count();
+
+        Coverage {
+            sensor: sensor_count as f32 / cell_count,
+            control: control_count as f32 / cell_count,
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn cell_centers(&self) -> Vec<Point3D> {
+        let cells_per_side = CELLS_PER_SIDE as Float;
+        let cell_width     = (self.max_x - self.min_x) / cells_per_side;
+        let cell_height    = (self.max_y - self.min_y) / cells_per_side;
+
+        (0..CELLS_PER_SIDE)
+            .flat_map(|column|
+                (0..CELLS_PER_SIDE).map(move |row| (column, row))
+            )
+            .map(|(column, row)|
+                Point3D::new(
+                    self.min_x + (column as Float + 0.5) * cell_width,
+                    self.min_y + (row as Float + 0.5) * cell_height,
+                    0.0
+                )
+            )
+            .collect()
+    }
+}
+
+
+fn has_control_at(
+    device: &Device,
+    point: &Point3D,
+    jammers: &[&Device]
+) -> bool {
+    let frequency = device.control_frequency();
+    let Some(signal_strength) = device.tx_signal_strength_at(
+        point,
+        frequency
+    ) else {
+        return false;
+    };
+
+    !signal_strength.is_black()
+        && jammers
+            .iter()
+            .filter_map(|jammer|
+                jammer.tx_signal_strength_at(point, frequency)
+            )
+            .all(|jamming_strength| signal_strength > jamming_strength)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+    use std::slice;
+
+    use crate::backend::connections::Topology;
+    use crate::backend::device::{DeviceBuilder, device_map_from_slice};
+    use crate::backend::device::systems::{
+        PowerSystem, RXModule, SensorSystem, TRXSystem, TXModule
+    };
+    use crate::backend::mathphysics::{
+        Frequency, Megahertz, Meter, Millisecond, PowerUnit
+    };
+    use crate::backend::networkmodel::NetworkModelBuilder;
+    use crate::backend::networkmodel::attack::AttackerDevice;
+    use crate::backend::signal::{FreqToStrengthMap, SignalStrength};
+
+    use super::*;
+
+
+    const MAX_POWER: PowerUnit = PowerUnit::new(100);
+
+
+    fn power_system() -> PowerSystem {
+        PowerSystem::build(MAX_POWER, MAX_POWER)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn control_trx_system(area_radius: Float) -> TRXSystem {
+        let tx_signal_strength = SignalStrength::from_area_radius(
+            Meter::new(area_radius),
+            Frequency::Control as Megahertz
+        );
+
+        TRXSystem::new(
+            TXModule::new(
+                FreqToStrengthMap::from([
+                    (Frequency::Control, tx_signal_strength)
+                ])
+            ),
+            RXModule::default()
+        )
+    }
+
+    fn command_center(position: Point3D) -> Device {
+        DeviceBuilder::new()
+            .set_real_position(position)
+            .set_power_system(power_system())
+            .set_trx_system(control_trx_system(1000.0))
+            .build()
+    }
+
+
+    #[test]
+    fn sensor_coverage_is_footprint_share() {
+        let command_center = command_center(Point3D::new(5000.0, 0.0, 0.0));
+        let command_center_id = command_center.id();
+        // The sensor sees the whole area from above its center.
+        let drone = DeviceBuilder::new()
+            .set_real_position(Point3D::new(50.0, 50.0, 100.0))
+            .set_power_system(power_system())
+            .set_sensor_system(
+                SensorSystem::new(
+                    FRAC_PI_2,
+                    Meter::new(1000.0),
+                    Millisecond::ZERO
+                )
+            )
+            .build();
+        let network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center_id)
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .set_topology(Topology::Star)
+            .build();
+
+        let full_area = CoverageArea::new((0.0, 0.0), (100.0, 100.0));
+        let far_area = CoverageArea::new((2000.0, 2000.0), (2100.0, 2100.0));
+
+        let full_coverage = full_area.coverage(&network_model);
+        let far_coverage = far_area.coverage(&network_model);
+
+        assert!((full_coverage.sensor() - 1.0).abs() < 1e-6);
+        assert_eq!(far_coverage.sensor(), 0.0);
+        assert_eq!(far_coverage.control(), 0.0);
+    }
+
+    #[test]
+    fn jamming_reduces_control_coverage() {
+        let command_center = command_center(Point3D::new(0.0, 0.0, 0.0));
+        let command_center_id = command_center.id();
+        let jammer = DeviceBuilder::new()
+            .set_real_position(Point3D::new(100.0, 0.0, 0.0))
+            .set_power_system(power_system())
+            .set_trx_system(control_trx_system(1000.0))
+            .build();
+        let network_model = |attacker_devices| NetworkModelBuilder::new()
+            .set_command_center_id(command_center_id)
+            .set_device_map(
+                device_map_from_slice(slice::from_ref(&command_center))
+            )
+            .set_attacker_devices(attacker_devices)
+            .set_topology(Topology::Star)
+            .build();
+        let area = CoverageArea::new((-100.0, -100.0), (100.0, 100.0));
+
+        let control = area.coverage(&network_model(Vec::new())).control();
+        let jammed_control = area
+            .coverage(
+                &network_model(vec![
+                    AttackerDevice::new(jammer, AttackType::ElectronicWarfare)
+                ])
+            )
+            .control();
+
+        assert!((control - 1.0).abs() < 1e-6);
+        assert!(jammed_control > 0.0 && jammed_control < 1.0);
+    }
+}
@@ -1,42 +1,64 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
 use log::trace;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use super::{DESTINATION_RADIUS, ITERATION_TIME};
+use super::ITERATION_TIME;
+use super::collision::CollisionOutcome;
+use super::event::{Event, EventKind};
+use super::landing::nearest_reachable_zone;
 use super::malware::{InfectionMap, Malware, MalwareType};
 use super::mathphysics::{
-    equation_of_motion_3d, millis_to_secs, Frequency, Meter, MeterPerSecond, 
-    Millisecond, Point3D, Position, PowerUnit
+    equation_of_motion_3d, float_to_f32, millis_to_secs, Float, Frequency, 
+    Joule, Megahertz, Meter, MeterPerSecond, Millisecond, Orientation, 
+    Point3D, Position, Vector3D, Watt
 };
+use super::parameters::SimulationParameters;
 use super::signal::{
-    Data, FreqToStrengthMap, Signal, SignalStrength, BLACK_SIGNAL_STRENGTH, 
+    Data, FreqToStrengthMap, Propagation, PropagationModel, Signal, 
+    SignalStrength, BLACK_SIGNAL_STRENGTH, GREEN_SIGNAL_STRENGTH_VALUE, 
 };
-use super::task::Task;
+use super::task::{Mission, Task};
 
 use id::generate_device_id;
 use systems::{
-    MovementSystem, PowerSystem, PowerSystemError, SecuritySystem, TRXSystem, 
-    TRXSystemError
+    power_units_to_joules, ChannelSystem, ClockSystem, EnergyConsumer, 
+    EnergyUsage, FirmwareSystem, FirmwareUpdate, FlockingSystem, GossipSystem, 
+    HardwareComponent, IntegritySystem, MovementSystem, NavigationSystem, 
+    Neighbor, PowerSystem, PowerSystemError, ReconRecord, ReliabilitySystem, 
+    Rumor, SecuritySystem, SensorSystem, SpoofingEvidence, TRXSystem, 
+    TRXSystemError, ThermalSystem
 };
 
 
 pub use id::{
-    DeviceId, IdToDelayMap, IdToDeviceMap, IdToTaskMap, BROADCAST_ID,
-    device_map_from_slice
+    DeviceId, IdToDelayMap, IdToTaskMap, BROADCAST_ID, device_map_from_slice
 };
+pub use payload::Payload;
+pub use storage::IdToDeviceMap;
+pub use template::{DeviceTemplates, TemplateError};
+pub use world::{NeighborView, WorldView};
 
 
 pub mod systems;
 
 mod id;
+mod payload;
+mod storage;
+mod template;
+mod world;
 
 
 pub const MAX_DRONE_SPEED: MeterPerSecond = 25.0;
 
 
-const MOVEMENT_POWER_CONSUMPTION: PowerUnit   = 5; 
-const PASSIVE_POWER_CONSUMPTION: PowerUnit    = 1; 
-const PROCESSING_POWER_CONSUMPTION: PowerUnit = 5; 
+// Drones continuing their mission without the command center keep at least
+// this distance from each other.
+const AUTONOMOUS_SEPARATION: Meter = Meter::new(5.0);
+// Fixes on different GPS bands farther apart than this are inconsistent.
+const MAX_GPS_BAND_DISAGREEMENT: Meter = Meter::new(1.0);
 
 
 #[derive(Debug, Error)]
@@ -48,7 +70,9 @@ pub enum DeviceError {
 }
 
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize
+)]
 pub enum SignalLossResponse {
     Ascend,
     #[default]
@@ -56,6 +80,12 @@ pub enum SignalLossResponse {
     Hover,
     ReturnToHome(Point3D), // `Point3D` - a home point
     Shutdown,
+    // The device hovers while waiting for the control signal. After 
+    // `Millisecond` it continues its mission on its own.
+    ContinueMission(Millisecond),
+    // The device hovers while waiting for the control signal. After
+    // `Millisecond` it lands at the nearest reachable landing zone.
+    Land(Millisecond),
 }
 
 
@@ -67,7 +97,20 @@ pub struct DeviceBuilder {
     movement_system: Option<MovementSystem>,
     trx_system: Option<TRXSystem>,
     security_system: Option<SecuritySystem>,
+    reliability_system: Option<ReliabilitySystem>,
+    thermal_system: Option<ThermalSystem>,
+    sensor_system: Option<SensorSystem>,
+    flocking_system: Option<FlockingSystem>,
+    navigation_system: Option<NavigationSystem>,
+    channel_system: Option<ChannelSystem>,
+    gossip_system: Option<GossipSystem>,
+    clock_system: Option<ClockSystem>,
+    firmware_system: Option<FirmwareSystem>,
+    integrity_system: Option<IntegritySystem>,
+    payload: Option<Payload>,
+    mission: Option<Mission>,
     signal_loss_response: Option<SignalLossResponse>,
+    processing_delay: Option<Millisecond>,
 }
 
 impl DeviceBuilder {
@@ -80,7 +123,20 @@ impl DeviceBuilder {
             movement_system: None,
             trx_system: None,
             security_system: None,
+            reliability_system: None,
+            thermal_system: None,
+            sensor_system: None,
+            flocking_system: None,
+            navigation_system: None,
+            channel_system: None,
+            gossip_system: None,
+            clock_system: None,
+            firmware_system: None,
+            integrity_system: None,
+            payload: None,
+            mission: None,
             signal_loss_response: None,
+            processing_delay: None,
         }
     }
 
@@ -129,6 +185,93 @@ impl DeviceBuilder {
         self
     }
 
+    #[must_use]
+    pub fn set_reliability_system(
+        mut self, 
+        reliability_system: ReliabilitySystem
+    ) -> Self {
+        self.reliability_system = Some(reliability_system);
+        self
+    }
+
+    #[must_use]
+    pub fn set_thermal_system(mut self, thermal_system: ThermalSystem) -> Self {
+        self.thermal_system = Some(thermal_system);
+        self
+    }
+
+    #[must_use]
+    pub fn set_sensor_system(mut self, sensor_system: SensorSystem) -> Self {
+        self.sensor_system = Some(sensor_system);
+        self
+    }
+
+    #[must_use]
+    pub fn set_flocking_system(
+        mut self, 
+        flocking_system: FlockingSystem
+    ) -> Self {
+        self.flocking_system = Some(flocking_system);
+        self
+    }
+
+    #[must_use]
+    pub fn set_navigation_system(
+        mut self, 
+        navigation_system: NavigationSystem
+    ) -> Self {
+        self.navigation_system = Some(navigation_system);
+        self
+    }
+
+    #[must_use]
+    pub fn set_channel_system(mut self, channel_system: ChannelSystem) -> Self {
+        self.channel_system = Some(channel_system);
+        self
+    }
+
+    #[must_use]
+    pub fn set_gossip_system(mut self, gossip_system: GossipSystem) -> Self {
+        self.gossip_system = Some(gossip_system);
+        self
+    }
+
+    #[must_use]
+    pub fn set_clock_system(mut self, clock_system: ClockSystem) -> Self {
+        self.clock_system = Some(clock_system);
+        self
+    }
+
+    #[must_use]
+    pub fn set_firmware_system(
+        mut self,
+        firmware_system: FirmwareSystem
+    ) -> Self {
+        self.firmware_system = Some(firmware_system);
+        self
+    }
+
+    #[must_use]
+    pub fn set_integrity_system(
+        mut self,
+        integrity_system: IntegritySystem
+    ) -> Self {
+        self.integrity_system = Some(integrity_system);
+        self
+    }
+
+    #[must_use]
+    pub fn set_payload(mut self, payload: Payload) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    #[must_use]
+    pub fn set_mission(mut self, mission: Mission) -> Self {
+        self.mission = Some(mission);
+        self
+    }
+
     #[must_use]
     pub fn set_signal_loss_response(
         mut self,
@@ -137,9 +280,23 @@ impl DeviceBuilder {
         self.signal_loss_response = Some(signal_loss_response);
         self
     }
+
+    #[must_use]
+    pub fn set_processing_delay(
+        mut self,
+        processing_delay: Millisecond
+    ) -> Self {
+        self.processing_delay = Some(processing_delay);
+        self
+    }
    
+    // Clock drift is drawn for every built device.
     #[must_use]
     pub fn build(self) -> Device {
+        let mut clock_system = self.clock_system.unwrap_or_default();
+
+        clock_system.draw_drift();
+
         Device::new(
             generate_device_id(),
             self.real_position_in_meters.unwrap_or_default(),
@@ -148,28 +305,102 @@ impl DeviceBuilder {
             self.movement_system.unwrap_or_default(),
             self.trx_system.unwrap_or_default(),
             self.security_system.unwrap_or_default(),
+            self.reliability_system.unwrap_or_default(),
+            self.thermal_system.unwrap_or_default(),
+            self.sensor_system.unwrap_or_default(),
+            self.flocking_system.unwrap_or_default(),
+            self.navigation_system.unwrap_or_default(),
+            self.channel_system.unwrap_or_default(),
+            self.gossip_system.unwrap_or_default(),
+            clock_system,
+            self.firmware_system.unwrap_or_default(),
+            self.integrity_system.unwrap_or_default(),
+            self.payload,
+            self.mission.unwrap_or_default(),
             self.signal_loss_response.unwrap_or_default(),
+            self.processing_delay.unwrap_or_default(),
         )
     }
 }
 
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
 pub struct Device {
     id: DeviceId,
     current_time: Millisecond,
     real_position_in_meters: Point3D,
+    #[serde(default)]
+    orientation: Orientation,
     task: Task,
     power_system: PowerSystem,
     movement_system: MovementSystem,
     trx_system: TRXSystem,
     security_system: SecuritySystem,
+    #[serde(default)]
+    reliability_system: ReliabilitySystem,
+    #[serde(default)]
+    thermal_system: ThermalSystem,
+    #[serde(default)]
+    sensor_system: SensorSystem,
+    #[serde(default)]
+    flocking_system: FlockingSystem,
+    #[serde(default)]
+    navigation_system: NavigationSystem,
+    #[serde(default)]
+    channel_system: ChannelSystem,
+    #[serde(default)]
+    gossip_system: GossipSystem,
+    #[serde(default)]
+    clock_system: ClockSystem,
+    #[serde(default)]
+    firmware_system: FirmwareSystem,
+    #[serde(default)]
+    integrity_system: IntegritySystem,
+    #[serde(default)]
+    payload: Option<Payload>,
+    // Share of health lost in collisions.
+    #[serde(default)]
+    damage: f32,
+    #[serde(default)]
+    energy_usage: EnergyUsage,
     infection_map: InfectionMap,
+    #[serde(default)]
+    mission: Mission,
     signal_loss_response: SignalLossResponse,
+    // Time between receiving a signal and acting on it or passing it on.
+    #[serde(default)]
+    processing_delay: Millisecond,
+    #[serde(default)]
+    control_signal_lost: bool,
+    // Time the control signal was lost at.
+    #[serde(default)]
+    control_signal_lost_at: Millisecond,
+    // Executing the mission without the command center.
+    #[serde(default)]
+    autonomous: bool,
+    // Point the device is landing at in an emergency.
+    #[serde(default)]
+    landing_site: Option<Point3D>,
+    #[serde(default)]
+    landed: bool,
+    // Moved to positions received from another simulator instead of by the
+    // movement system.
+    #[serde(default)]
+    externally_driven: bool,
+    // Position received since the last update.
+    #[serde(skip)]
+    external_position: Option<Point3D>,
+    // Parameters of the model from the world view of the last update.
+    #[serde(skip)]
+    parameters: SimulationParameters,
+    // Events since the last `take_events` call.
+    #[serde(skip)]
+    events: Vec<Event>,
 }
 
 impl Device {
     #[must_use]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: DeviceId,
         real_position_in_meters: Point3D,
@@ -178,19 +409,57 @@ impl Device {
         movement_system: MovementSystem,
         trx_system: TRXSystem,
         security_system: SecuritySystem,
+        reliability_system: ReliabilitySystem,
+        thermal_system: ThermalSystem,
+        sensor_system: SensorSystem,
+        flocking_system: FlockingSystem,
+        navigation_system: NavigationSystem,
+        channel_system: ChannelSystem,
+        gossip_system: GossipSystem,
+        clock_system: ClockSystem,
+        firmware_system: FirmwareSystem,
+        integrity_system: IntegritySystem,
+        payload: Option<Payload>,
+        mission: Mission,
         signal_loss_response: SignalLossResponse,
+        processing_delay: Millisecond,
     ) -> Self {
         Self {
             id,
-            current_time: 0,
+            current_time: Millisecond::ZERO,
             real_position_in_meters,
+            orientation: Orientation::default(),
             task,
             power_system,
             movement_system,
             trx_system,
             security_system,
+            reliability_system,
+            thermal_system,
+            sensor_system,
+            flocking_system,
+            navigation_system,
+            channel_system,
+            gossip_system,
+            clock_system,
+            firmware_system,
+            integrity_system,
+            payload,
+            damage: 0.0,
+            energy_usage: EnergyUsage::default(),
             infection_map: InfectionMap::default(),
+            mission,
             signal_loss_response,
+            processing_delay,
+            control_signal_lost: false,
+            control_signal_lost_at: Millisecond::ZERO,
+            autonomous: false,
+            landing_site: None,
+            landed: false,
+            externally_driven: false,
+            external_position: None,
+            parameters: SimulationParameters::default(),
+            events: Vec::new(),
         }
     }
 
@@ -209,20 +478,156 @@ impl Device {
         self.movement_system.position()
     }
     
+    #[must_use]
+    pub fn orientation(&self) -> &Orientation {
+        &self.orientation
+    }
+    
+    #[must_use]
+    pub fn velocity(&self) -> &Vector3D {
+        self.movement_system.velocity()
+    }
+
+    #[must_use]
+    pub fn max_speed(&self) -> MeterPerSecond {
+        self.movement_system.max_speed()
+    }
+    
+    #[must_use]
+    pub fn power_system(&self) -> &PowerSystem {
+        &self.power_system
+    }
+    
+    #[must_use]
+    pub fn reliability_system(&self) -> &ReliabilitySystem {
+        &self.reliability_system
+    }
+    
+    #[must_use]
+    pub fn thermal_system(&self) -> &ThermalSystem {
+        &self.thermal_system
+    }
+    
+    #[must_use]
+    pub fn sensor_system(&self) -> &SensorSystem {
+        &self.sensor_system
+    }
+    
+    #[must_use]
+    pub fn flocking_system(&self) -> &FlockingSystem {
+        &self.flocking_system
+    }
+
+    #[must_use]
+    pub fn navigation_system(&self) -> &NavigationSystem {
+        &self.navigation_system
+    }
+
+    #[must_use]
+    pub fn channel_system(&self) -> &ChannelSystem {
+        &self.channel_system
+    }
+
+    #[must_use]
+    pub fn gossip_system(&self) -> &GossipSystem {
+        &self.gossip_system
+    }
+
+    #[must_use]
+    pub fn clock_system(&self) -> &ClockSystem {
+        &self.clock_system
+    }
+
+    #[must_use]
+    pub fn firmware_system(&self) -> &FirmwareSystem {
+        &self.firmware_system
+    }
+
+    #[must_use]
+    pub fn integrity_system(&self) -> &IntegritySystem {
+        &self.integrity_system
+    }
+
+    // Time on the device clock. Timing decisions of the device rest on it,
+    // while events are recorded at the model time.
+    #[must_use]
+    pub fn local_time(&self) -> Millisecond {
+        self.clock_system.local_time(self.current_time)
+    }
+
+    // Frequency the device currently sends and receives control data on.
+    #[must_use]
+    pub fn control_frequency(&self) -> Frequency {
+        self.channel_system.control_frequency()
+    }
+    
+    #[must_use]
+    pub fn payload(&self) -> Option<&Payload> {
+        self.payload.as_ref()
+    }
+    
+    // Health is 1.0 for an intact device and 0.0 for a destroyed one.
+    #[must_use]
+    pub fn health(&self) -> f32 {
+        1.0 - self.damage
+    }
+    
+    #[must_use]
+    pub fn energy_usage(&self) -> &EnergyUsage {
+        &self.energy_usage
+    }
+    
     #[must_use]
     pub fn infection_map(&self) -> &InfectionMap {
         &self.infection_map
     }
     
+    #[must_use]
+    pub fn mission(&self) -> &Mission {
+        &self.mission
+    }
+    
+    #[must_use]
+    pub fn parameters(&self) -> &SimulationParameters {
+        &self.parameters
+    }
+    
+    #[must_use]
+    pub fn is_autonomous(&self) -> bool {
+        self.autonomous
+    }
+    
+    #[must_use]
+    pub fn landing_site(&self) -> Option<&Point3D> {
+        self.landing_site.as_ref()
+    }
+
+    #[must_use]
+    pub fn has_landed(&self) -> bool {
+        self.landed
+    }
+
     #[must_use]
     pub fn signal_loss_response(&self) -> &SignalLossResponse {
         &self.signal_loss_response
     }
 
+    // Signals are delivered on iterations, so the delay is rounded down to
+    // the iteration time like propagation delays.
+    #[must_use]
+    pub fn processing_delay(&self) -> Millisecond {
+        self.processing_delay - self.processing_delay % ITERATION_TIME
+    }
+
     #[must_use]
     pub fn tx_signal_strength_map(&self) -> &FreqToStrengthMap {
         self.trx_system.tx_signal_strength_map()
     }
+
+    #[must_use]
+    pub fn rx_max_signal_strength_map(&self) -> &FreqToStrengthMap {
+        self.trx_system.rx_max_signal_strength_map()
+    }
     
     #[must_use]
     pub fn tx_signal_strength_on(
@@ -232,9 +637,18 @@ impl Device {
         self.trx_system.tx_signal_strength_on(frequency)
     }
     
+    // An overheated device transmits with throttled strength.
     #[must_use]
     pub fn area_radius_on(&self, frequency: Frequency) -> Meter {
-        self.trx_system.area_radius_on(frequency)
+        self.trx_system
+            .tx_signal_strength_on(&frequency)
+            .map_or(Meter::ZERO, |tx_signal_strength|
+                self.trx_system.propagation().area_radius(
+                    *tx_signal_strength
+                        * Float::from(self.thermal_system.throttle_factor()),
+                    frequency as Megahertz
+                )
+            )
     }
 
     #[must_use]
@@ -254,7 +668,12 @@ impl Device {
     ) -> Option<SignalStrength> {
         let distance_to_rx = self.distance_to(receiver);
 
-        self.trx_system.tx_signal_strength_at(distance_to_rx, frequency)
+        self.trx_system
+            .tx_signal_strength_at(distance_to_rx, frequency)
+            .map(|signal_strength|
+                signal_strength
+                    * Float::from(self.thermal_system.throttle_factor())
+            )
     }
 
     /// # Errors
@@ -288,6 +707,70 @@ impl Device {
         Ok(signal)
     }
 
+    // Update the device pushes to the network at the current time.
+    #[must_use]
+    pub fn firmware_update(&self) -> Option<FirmwareUpdate> {
+        if self.is_shut_down() {
+            return None;
+        }
+
+        self.firmware_system.update_at(self.local_time())
+    }
+
+    // Local time the device sends in its beacon at the current time.
+    #[must_use]
+    pub fn time_beacon(&self) -> Option<Millisecond> {
+        let local_time = self.local_time();
+
+        (!self.is_shut_down() && self.clock_system.sends_beacon_at(local_time))
+            .then_some(local_time)
+    }
+
+    // Rumor the device sends to its gossip peers at the current time.
+    #[must_use]
+    pub fn gossip(&self) -> Option<Rumor> {
+        if self.is_shut_down() 
+            || !self.gossip_system.exchanges_at(self.local_time()) 
+        {
+            return None;
+        }
+
+        self.gossip_system.random_rumor()
+    }
+
+    // Records of the devices that are detected by the sensor at the current 
+    // time. A shut down device does not sample.
+    #[must_use]
+    pub fn scan<'a, I>(&self, targets: I) -> Vec<ReconRecord> 
+    where 
+        I: IntoIterator<Item = &'a Device>
+    {
+        if self.is_shut_down() 
+            || !self.sensor_system.samples_at(self.local_time()) 
+        {
+            return Vec::new();
+        }
+
+        targets
+            .into_iter()
+            .filter(|target| target.id() != self.id)
+            .filter(|target| 
+                self.sensor_system.detects(
+                    &self.real_position_in_meters, 
+                    target.position()
+                )
+            )
+            .map(|target| 
+                ReconRecord::new(
+                    self.local_time(),
+                    self.id,
+                    target.id(),
+                    *target.position()
+                )
+            )
+            .collect()
+    }
+
     #[must_use]
     pub fn receives_signal_on(&self, frequency: &Frequency) -> bool {
         self.trx_system.receives_signal_on(frequency)
@@ -320,6 +803,36 @@ impl Device {
             )
     }
 
+    // Feeds the state that is compared between deterministic runs: position,
+    // power and infections.
+    pub fn hash_state<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        for coordinate in [
+            self.real_position_in_meters.x,
+            self.real_position_in_meters.y,
+            self.real_position_in_meters.z,
+        ] {
+            coordinate.to_bits().hash(state);
+        }
+        self.power_system.energy().value().to_bits().hash(state);
+        self.thermal_system.temperature().to_bits().hash(state);
+        self.damage.to_bits().hash(state);
+
+        // Infection map iteration order is random, so entries are combined
+        // in an order independent way.
+        let infection_hash = self.infection_map
+            .iter()
+            .map(|entry| {
+                let mut hasher = DefaultHasher::new();
+                entry.hash(&mut hasher);
+
+                hasher.finish()
+            })
+            .fold(0, u64::wrapping_add);
+
+        infection_hash.hash(state);
+    }
+
     #[must_use]
     pub fn is_infected(&self) -> bool {
         !self.infection_map.is_empty()
@@ -332,78 +845,468 @@ impl Device {
 
     #[must_use]
     pub fn is_shut_down(&self) -> bool {
-        self.power_system.power() == 0
+        self.power_system.is_drained()
     }  
 
+    // The device neither moves nor heads to a destination.
+    #[must_use]
+    pub fn is_at_rest(&self) -> bool {
+        self.velocity().displacement() == Point3D::default()
+            && self.task.destination().is_none()
+    }
+
+    #[must_use]
+    pub fn is_externally_driven(&self) -> bool {
+        self.externally_driven
+    }
+
+    // Signals are charged after they were queued, so running out of energy
+    // does not take back the transmission.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn draw_tx_energy(&mut self, signal_count: usize) {
+        let _ = self.try_consume_energy(
+            signal_count as f32 * self.parameters.tx_energy_consumption(),
+            EnergyConsumer::TX
+        );
+    }
+
+    pub fn take_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
+    }
+
+    // Devices within this distance are listed in the world view of the
+    // device. It is the farthest of the flocking neighbor radius, the sensor
+    // range and the control signal radius.
+    #[must_use]
+    pub fn detection_range(&self) -> Meter {
+        let control_radius = self.trx_system
+            .area_radius_on(self.control_frequency());
+
+        self.flocking_system
+            .neighbor_radius()
+            .max(self.sensor_system.range())
+            .max(control_radius)
+    }
+
+    // `away` points from the other device to this one.
+    pub fn collide(
+        &mut self, 
+        other_id: DeviceId, 
+        outcome: CollisionOutcome,
+        away: Vector3D
+    ) {
+        let was_shut_down = self.is_shut_down();
+
+        match outcome {
+            CollisionOutcome::Destroyed       => self.take_damage(1.0),
+            CollisionOutcome::Damaged(damage) => self.take_damage(damage),
+            CollisionOutcome::Deflected       => self.deflect(away),
+        }
+
+        self.trace_collision(other_id, outcome);
+        self.push_event(EventKind::Collided { other_id, outcome });
+
+        if !was_shut_down && self.is_shut_down() {
+            self.push_event(EventKind::ShutDown);
+        }
+    }
+
+    // Charges the battery after a sortie. Each charge wears the battery out,
+    // so endurance declines across sorties.
+    pub fn recharge(&mut self) {
+        self.power_system.recharge();
+        self.trace_recharge();
+    }
+
+    pub fn set_mission(&mut self, mission: Mission) {
+        self.mission = mission;
+    }
+
+    pub fn set_propagation(&mut self, propagation: Propagation) {
+        self.trx_system.set_propagation(propagation);
+    }
+
+    pub fn set_parameters(&mut self, parameters: SimulationParameters) {
+        self.parameters = parameters;
+    }
+
+    // Max speed of intact motors, damaged ones reach only a part of it.
+    pub fn set_max_speed(&mut self, max_speed: MeterPerSecond) {
+        self.movement_system.set_max_speed(
+            max_speed * Float::from(self.health())
+        );
+    }
+
+    pub fn set_externally_driven(&mut self, externally_driven: bool) {
+        self.externally_driven = externally_driven;
+    }
+
+    // The device is moved to the position on its next update, if it is
+    // externally driven.
+    pub fn set_external_position(&mut self, position: Point3D) {
+        self.external_position = Some(position);
+    }
+
     /// # Errors
     ///
     /// Will return `Err` if all power is consumed or the movement system is
     /// disabled.
-    pub fn update(&mut self) -> Result<(), DeviceError> {
+    pub fn update(
+        &mut self,
+        world_view: &WorldView
+    ) -> Result<(), DeviceError> {
+        self.parameters = *world_view.parameters();
         self.trace_control_signal_strength();
 
-        self.try_consume_power(PASSIVE_POWER_CONSUMPTION)?;
-        self.handle_malware_infections();
-        self.process_received_signals()?;
-        if self.receives_signal_on(&Frequency::Control) {
-            self.process_task();
-        } else {
-            self.handle_signal_loss();
+        let was_shut_down = self.is_shut_down();
+        let result = self.update_systems(world_view);
+
+        if !was_shut_down && self.is_shut_down() {
+            self.push_event(EventKind::ShutDown);
         }
-        self.trx_system.clear_received_signals();
-        self.update_real_position()?;
+
+        result?;
 
         self.current_time += ITERATION_TIME;
+        self.clock_system.advance(ITERATION_TIME);
 
         Ok(())
     }
-    
-    fn process_received_signals(&mut self,) -> Result<(), DeviceError> {
-        for (_, signal) in self.trx_system.received_signals() {
-            self.process_data(signal.data())?; 
+
+    fn update_systems(
+        &mut self,
+        world_view: &WorldView
+    ) -> Result<(), DeviceError> {
+        let previous_velocity = *self.movement_system.velocity();
+
+        self.steer_by_neighbors(world_view);
+
+        self.try_draw_power(
+            self.parameters.passive_power_draw(),
+            EnergyConsumer::Passive
+        )?;
+        self.handle_hardware_failures();
+        self.handle_malware_infections();
+        self.install_firmware()?;
+        self.process_received_signals(world_view)?;
+        self.detect_jamming();
+
+        let control_signal_lost = !self.receives_signal_on(
+            &self.control_frequency()
+        );
+
+        self.set_control_signal_lost(control_signal_lost);
+        if self.power_system.is_critical() {
+            self.start_emergency_landing(world_view);
+        }
+        if self.landing_site.is_some() {
+            self.land();
+        } else if control_signal_lost {
+            self.handle_signal_loss(world_view);
+        } else {
+            self.process_task();
         }
+        self.trx_system.clear_received_signals();
+        self.apply_flocking();
+        self.movement_system.limit_velocity_change(&previous_velocity);
+        self.update_thermal_state();
+        self.update_real_position()?;
 
         Ok(())
     }
-     
-    fn process_data(&mut self, data: &Data) -> Result<(), DeviceError> {
-        self.try_consume_power(PROCESSING_POWER_CONSUMPTION)?;
 
-        match data {
-            Data::GPS(gps_position) => self.movement_system.set_position(
-                *gps_position
-            ),
-            Data::Malware(malware)  => self.process_malware(malware),
-            Data::SetTask(task)     => self.task = *task,
-            Data::Noise             => ()
+    fn push_event(&mut self, event_kind: EventKind) {
+        self.events.push(Event::new(self.current_time, self.id, event_kind));
+    }
+
+    fn set_control_signal_lost(&mut self, control_signal_lost: bool) {
+        if self.control_signal_lost == control_signal_lost {
+            return;
         }
 
-        Ok(())
-    }
+        self.control_signal_lost = control_signal_lost;
 
-    fn process_malware(&mut self, malware: &Malware) {
-        if !self.infection_map.contains_key(malware) 
-            && !self.security_system.patches(malware) 
-        {
-            self.infection_map.insert(*malware, self.current_time);
-            self.trace_infected(malware);
+        if control_signal_lost {
+            self.control_signal_lost_at = self.local_time();
+            self.push_event(EventKind::ControlSignalLost);
+        } else {
+            self.autonomous = false;
+            self.push_event(EventKind::ControlSignalRestored);
         }
     }
-   
-    fn try_consume_power(
+    
+    // Noise on the control channel is taken for jamming once it lasts for
+    // the noise tolerance of the channel system.
+    fn detect_jamming(&mut self) {
+        let received_data = self.trx_system
+            .received_signal_on(&self.channel_system.control_frequency())
+            .map(|(_, signal)| signal.data());
+
+        if let Some(backup_frequency) = self.channel_system.detect_jamming(
+            received_data,
+            self.local_time()
+        ) {
+            self.switch_control_channel(backup_frequency);
+        }
+    }
+
+    // The radio is retuned, so signals on the previous frequency are not
+    // received anymore. Peers are notified by the network model.
+    fn switch_control_channel(&mut self, frequency: Frequency) {
+        let previous_frequency = self.control_frequency();
+
+        self.trx_system.retune(previous_frequency, frequency);
+        self.channel_system.switch_to(frequency);
+        self.trace_control_channel_switch(frequency);
+        self.push_event(
+            EventKind::ControlChannelSwitched { 
+                from: previous_frequency, 
+                to: frequency 
+            }
+        );
+    }
+    
+    fn process_received_signals(
+        &mut self,
+        world_view: &WorldView
+    ) -> Result<(), DeviceError> {
+        // Signals are read by index, so the RX buffer is not copied while 
+        // the device is mutated by processing.
+        let mut signal_index = 0;
+
+        while let Some((_, signal)) = self.trx_system
+            .received_signals()
+            .get(signal_index)
+        {
+            let data = *signal.data();
+            let source_id = signal.source_id();
+            let is_corrupted = data != Data::Noise
+                && self.integrity_system.is_corrupted(*signal.strength());
+            let data = if is_corrupted {
+                self.decode_corrupted(&data)
+            } else {
+                Some(data)
+            };
+
+            if let Some(data) = data {
+                self.process_data(&data, source_id, world_view)?; 
+            }
+            signal_index += 1;
+        }
+
+        Ok(())
+    }
+     
+    fn process_data(
+        &mut self,
+        data: &Data,
+        source_id: DeviceId,
+        world_view: &WorldView
+    ) -> Result<(), DeviceError> {
+        self.try_consume_energy(
+            self.parameters.processing_energy_consumption(),
+            EnergyConsumer::Processing
+        )?;
+
+        match data {
+            Data::FirmwareUpdate(update)   => 
+                self.process_firmware_update(*update),
+            Data::GPS(gps_position)        => 
+                self.process_gps_fix(*gps_position, world_view),
+            Data::Gossip(rumor)            => self.process_rumor(*rumor),
+            Data::Malware(malware)         => 
+                self.process_malware(malware, source_id),
+            Data::Recon(record)            => 
+                self.push_event(EventKind::ReconReceived(*record)),
+            Data::ReconSummary(summary)    => 
+                self.push_event(EventKind::ReconReceived(*summary.latest())),
+            Data::SetTask(task)            => 
+                self.set_commanded_task(*task, source_id),
+            Data::SwitchChannel(frequency) => 
+                self.process_channel_switch(*frequency),
+            Data::TimeBeacon(time)         => self.process_time_beacon(*time),
+            Data::Noise                    => ()
+        }
+
+        Ok(())
+    }
+
+    // Corrupted data the device can decode is accepted as is.
+    fn decode_corrupted(&mut self, data: &Data) -> Option<Data> {
+        let decoded_data = self.integrity_system.corrupt(data);
+
+        if decoded_data.is_some() {
+            self.trace_corrupted_data(data, "Accepted");
+            self.push_event(EventKind::CorruptedDataAccepted);
+        } else {
+            self.trace_corrupted_data(data, "Discarded");
+            self.push_event(EventKind::CorruptedDataDiscarded);
+        }
+
+        decoded_data
+    }
+
+    // Commanded tasks take priority over the mission, so the tasks skipped
+    // by the command center are not returned to. The device commanding the
+    // task is taken for the leader.
+    fn set_commanded_task(&mut self, task: Task, source_id: DeviceId) {
+        let time = self.local_time();
+
+        self.mission.skip_to(&task);
+        self.task = task;
+        self.note(Rumor::Leader { leader_id: source_id, time });
+        self.note(Rumor::Task { task, time });
+    }
+
+    // Facts the device finds out itself are kept only if it gossips.
+    fn note(&mut self, rumor: Rumor) {
+        if self.gossip_system.is_enabled() {
+            self.gossip_system.learn(rumor);
+        }
+    }
+
+    fn process_rumor(&mut self, rumor: Rumor) {
+        if self.gossip_system.learn(rumor) {
+            self.trace_rumor_learned(rumor);
+            self.push_event(EventKind::RumorLearned(rumor));
+        }
+    }
+
+    // A rejected fix is replaced by the dead-reckoned position. Multi-band
+    // receivers also compare the fix with ones received on other bands, as
+    // spoofers rarely reproduce all of them consistently.
+    fn process_gps_fix(&mut self, fix: Point3D, world_view: &WorldView) {
+        let evidence = if self.gps_bands_disagree_with(&fix) {
+            Some(SpoofingEvidence::BandMismatch)
+        } else {
+            self.navigation_system.check_fix(&fix, world_view.neighbors())
+        };
+        let Some(evidence) = evidence else {
+            self.navigation_system.accept_fix(fix);
+            self.movement_system.set_position(fix);
+            return;
+        };
+
+        self.trace_gps_spoofing(evidence);
+        self.push_event(EventKind::GPSSpoofingDetected(evidence));
+
+        if !self.navigation_system.rejects_spoofed_fixes() {
+            self.movement_system.set_position(fix);
+        } else if let Some(estimated_position) = self.navigation_system
+            .estimated_position()
+        {
+            self.movement_system.set_position(*estimated_position);
+        }
+    }
+
+    fn gps_bands_disagree_with(&self, fix: &Point3D) -> bool {
+        self.trx_system
+            .received_signals()
+            .iter()
+            .filter(|(_, signal)| signal.frequency().is_gps())
+            .any(|(_, signal)| match signal.data() {
+                Data::GPS(band_fix) => 
+                    band_fix.distance_to(fix) > MAX_GPS_BAND_DISAGREEMENT,
+                _                   => false,
+            })
+    }
+
+    // Peers that switched their control channel are followed, so the
+    // network moves to the backup frequency together.
+    fn process_channel_switch(&mut self, frequency: Frequency) {
+        if self.channel_system.follows_switch_to(frequency) {
+            self.switch_control_channel(frequency);
+        }
+    }
+
+    fn process_time_beacon(&mut self, beacon_time: Millisecond) {
+        if self.clock_system.synchronize(beacon_time, self.current_time) {
+            self.trace_clock_synchronized();
+        }
+    }
+
+    fn process_firmware_update(&mut self, update: FirmwareUpdate) {
+        if self.firmware_system.start_install(update) {
+            self.trace_firmware_install_started(update);
+            self.push_event(
+                EventKind::FirmwareInstallStarted(update.version())
+            );
+        }
+    }
+
+    // Installation draws power until the new version is running.
+    fn install_firmware(&mut self) -> Result<(), PowerSystemError> {
+        let Some(update) = self.firmware_system.installing().copied() else {
+            return Ok(());
+        };
+
+        self.try_draw_power(
+            update.install_power(),
+            EnergyConsumer::Processing
+        )?;
+
+        if let Some(version) = self.firmware_system.advance(ITERATION_TIME) {
+            self.trace_firmware_updated();
+            self.push_event(EventKind::FirmwareUpdated(version));
+        }
+
+        Ok(())
+    }
+
+    // Updates protect only from later infections, so malware the device is
+    // already infected with keeps running.
+    fn process_malware(&mut self, malware: &Malware, source_id: DeviceId) {
+        if !self.infection_map.contains_key(malware) 
+            && !self.security_system.patches(malware) 
+            && malware.targets(self.firmware_system.version())
+        {
+            self.infection_map.insert(*malware, self.current_time);
+            self.note(
+                Rumor::Infection { device_id: self.id, time: self.local_time() }
+            );
+            self.trace_infected(malware);
+            self.push_event(
+                EventKind::Infected { malware: *malware, source_id }
+            );
+        }
+    }
+   
+    // The energy that was actually consumed is accounted to the consumer,
+    // so a drained battery is not overcounted.
+    fn try_consume_energy(
         &mut self, 
-        power: PowerUnit
+        energy: Joule,
+        consumer: EnergyConsumer
+    ) -> Result<(), PowerSystemError> {
+        let energy_before = self.power_system.energy();
+        let result = self.power_system.consume_energy(energy);
+
+        self.energy_usage.record(
+            consumer,
+            energy_before - self.power_system.energy()
+        );
+
+        result.inspect_err(|_| self.selfdestruction())
+    }
+
+    // Power is drawn during the whole iteration.
+    fn try_draw_power(
+        &mut self,
+        power: Watt,
+        consumer: EnergyConsumer
     ) -> Result<(), PowerSystemError> {
-        self.power_system
-            .consume_power(power)
-            .inspect_err(|_| self.selfdestruction())
+        self.try_consume_energy(
+            power * millis_to_secs(ITERATION_TIME),
+            consumer
+        )
     }
 
     fn process_task(&mut self) {
-        let gps_is_connected = self.receives_signal_on(&Frequency::GPS); 
+        let gps_is_connected = self.has_gps_fix();
 
         match self.task {
             Task::Attack(destination) 
+                | Task::DropPayload(destination)
                 | Task::Reconnect(destination)
                 | Task::Reposition(destination)
                 if gps_is_connected   => {
@@ -411,13 +1314,23 @@ impl Device {
                 self.try_complete_task();
             },
             Task::Attack(_) 
+                | Task::DropPayload(_)
                 | Task::Reconnect(_)
                 | Task::Reposition(_) =>
                 self.set_horizontal_velocity(),
+            Task::KeepFormation(velocity) => 
+                self.movement_system.set_velocity(velocity),
             Task::Undefined           => ()
         }
     }
     
+    // Multi-band receivers keep the fix while any band is not denied.
+    fn has_gps_fix(&self) -> bool {
+        Frequency::GPS_BANDS
+            .iter()
+            .any(|band| self.receives_signal_on(band))
+    }
+    
     fn set_horizontal_velocity(&mut self) {
         let mut velocity = *self.movement_system.velocity();
 
@@ -428,46 +1341,357 @@ impl Device {
         self.movement_system.set_velocity(velocity);
     }
 
-    fn handle_signal_loss(&mut self) {
+    fn handle_signal_loss(&mut self, world_view: &WorldView) {
         match self.signal_loss_response {
-            SignalLossResponse::Ascend                   => {
+            SignalLossResponse::Ascend                    => {
                 let mut point_above = self.real_position_in_meters;
                 point_above.z += 1.0;
 
                 self.movement_system.set_direction(point_above);
                 self.task = Task::Reconnect(point_above);
             },
-            SignalLossResponse::Hover                    => {
+            SignalLossResponse::Hover                     => {
                 self.task = Task::Reconnect(self.real_position_in_meters);
                 self.process_task();
             },
-            SignalLossResponse::Ignore                   =>
+            SignalLossResponse::Ignore                    =>
                 self.process_task(),
-            SignalLossResponse::ReturnToHome(home_point) => {
+            SignalLossResponse::ReturnToHome(home_point)  => {
                 self.task = Task::Reconnect(home_point);
                 self.process_task();
             },
-            SignalLossResponse::Shutdown                 =>
+            SignalLossResponse::Shutdown                  =>
                 self.selfdestruction(),
+            SignalLossResponse::ContinueMission(patience) =>
+                self.wait_or_continue_mission(patience, world_view),
+            SignalLossResponse::Land(patience)            =>
+                self.wait_or_land(patience, world_view),
+        }
+    }
+
+    // A lasting loss of the control signal is taken as unrecoverable.
+    fn wait_or_land(&mut self, patience: Millisecond, world_view: &WorldView) {
+        if self.local_time() - self.control_signal_lost_at < patience {
+            self.task = Task::Reconnect(self.real_position_in_meters);
+            self.process_task();
+        } else {
+            self.start_emergency_landing(world_view);
+            self.land();
+        }
+    }
+
+    // The device heads for the nearest landing zone it has the energy to
+    // reach, or lands right below if it is above the zone already. Without
+    // one or without a GPS fix to navigate by, it lands right below too.
+    // Landing is not called off once started.
+    fn start_emergency_landing(&mut self, world_view: &WorldView) {
+        if self.landing_site.is_some() {
+            return;
+        }
+
+        let landing_site = nearest_reachable_zone(
+            world_view.landing_zones(),
+            &self.real_position_in_meters,
+            self.flight_range()
+        )
+            .filter(|_| self.has_gps_fix())
+            .map_or(
+                Point3D::new(
+                    self.real_position_in_meters.x,
+                    self.real_position_in_meters.y,
+                    0.0
+                ),
+                |landing_zone|
+                    landing_zone.landing_site_for(&self.real_position_in_meters)
+            );
+
+        self.landing_site = Some(landing_site);
+        self.trace_emergency_landing(&landing_site);
+        self.push_event(EventKind::EmergencyLandingStarted(landing_site));
+    }
+
+    // The device flies straight at the landing site, so it touches down
+    // there once it descends to the site altitude. A device below the site
+    // climbs to it first. It stays on the ground afterwards.
+    fn land(&mut self) {
+        let Some(landing_site) = self.landing_site else {
+            return;
+        };
+
+        if self.landed {
+            self.movement_system.set_velocity(Vector3D::default());
+            return;
+        }
+
+        let altitude = self.real_position_in_meters.z;
+        // Altitude before the last move, so a device touches down only if
+        // it was above or at the site altitude.
+        let previous_altitude = altitude
+            - self.movement_system.velocity().displacement().z
+                * Float::from(millis_to_secs(ITERATION_TIME));
+
+        if altitude <= landing_site.z && previous_altitude >= landing_site.z {
+            self.landed = true;
+            self.real_position_in_meters.z = landing_site.z;
+            self.movement_system.set_velocity(Vector3D::default());
+            self.trace_landed();
+            self.push_event(EventKind::Landed);
+        } else if self.has_gps_fix() {
+            self.movement_system.set_direction(landing_site);
+        } else {
+            let max_speed = self.movement_system.max_speed();
+            let vertical_speed = if altitude < landing_site.z {
+                max_speed
+            } else {
+                -max_speed
+            };
+
+            self.movement_system.set_velocity(
+                Vector3D::new(
+                    Point3D::default(),
+                    Point3D::new(0.0, 0.0, vertical_speed)
+                )
+            );
         }
     }
 
+    // Distance the device can fly in level flight at its max speed on the
+    // energy left.
+    fn flight_range(&self) -> Meter {
+        let parameters = &self.parameters;
+        let max_speed = self.movement_system.max_speed();
+        let speed_fraction = float_to_f32(
+            max_speed / parameters.max_drone_speed()
+        );
+        let power_draw = parameters.passive_power_draw() 
+            + self.mass_factor() * (
+                parameters.hover_power_draw()
+                    + speed_fraction.powi(2) * parameters.speed_power_draw()
+            );
+        let flight_time = self.power_system.energy() / power_draw;
+
+        Meter::new(Float::from(flight_time) * max_speed)
+    }
+
+    // The device hovers waiting for the control signal until the patience
+    // runs out.
+    fn wait_or_continue_mission(
+        &mut self, 
+        patience: Millisecond, 
+        world_view: &WorldView
+    ) {
+        if self.local_time() - self.control_signal_lost_at < patience {
+            self.task = Task::Reconnect(self.real_position_in_meters);
+            self.process_task();
+        } else {
+            self.continue_mission(world_view);
+        }
+    }
+
+    // The device executes the rest of its mission and hovers once it is
+    // complete. Nearby devices doing the same are kept at a distance.
+    fn continue_mission(&mut self, world_view: &WorldView) {
+        if !self.autonomous {
+            self.autonomous = true;
+            self.trace_mission_continued();
+            self.push_event(EventKind::MissionContinued);
+        }
+
+        self.task = self.mission
+            .current_task()
+            .copied()
+            .unwrap_or(Task::Reconnect(self.real_position_in_meters));
+        self.process_task();
+        self.keep_separation(world_view);
+    }
+
+    fn keep_separation(&mut self, world_view: &WorldView) {
+        let mut push = Point3D::default();
+
+        for neighbor in world_view.neighbors_within(AUTONOMOUS_SEPARATION) {
+            let distance = neighbor.distance();
+
+            if distance <= Meter::ZERO {
+                continue;
+            }
+
+            // Closer neighbors push harder.
+            let away = self.real_position_in_meters - *neighbor.position();
+            push = push 
+                + away * ((AUTONOMOUS_SEPARATION - distance) / distance);
+        }
+
+        let velocity = *self.movement_system.velocity();
+
+        self.movement_system.set_velocity(
+            Vector3D::new(
+                velocity.initial_point, 
+                velocity.terminal_point + push
+            )
+        );
+    }
+
+    fn steer_by_neighbors(&mut self, world_view: &WorldView) {
+        if !self.flocking_system.is_enabled() {
+            return;
+        }
+
+        let neighbors: Vec<Neighbor> = world_view
+            .neighbors()
+            .iter()
+            .map(|neighbor| (*neighbor.position(), *neighbor.velocity()))
+            .collect();
+
+        self.flocking_system.steer(
+            &self.real_position_in_meters,
+            self.movement_system.velocity(),
+            &neighbors
+        );
+    }
+
+    fn apply_flocking(&mut self) {
+        if !self.flocking_system.is_enabled() || self.landed {
+            return;
+        }
+
+        let velocity = self.flocking_system.apply(
+            self.movement_system.velocity()
+        );
+        self.movement_system.set_velocity(velocity);
+    }
+
+    // Load is the largest fraction of max speed or max TX strength in use.
+    fn thermal_load(&self) -> f32 {
+        let max_speed = self.movement_system.max_speed();
+        let speed_load = if max_speed > 0.0 {
+            float_to_f32(self.movement_system.velocity().size() / max_speed)
+        } else {
+            0.0
+        };
+        let tx_load = self.trx_system
+            .tx_signal_strength_map()
+            .values()
+            .map(|signal_strength| 
+                float_to_f32(
+                    signal_strength.value() / GREEN_SIGNAL_STRENGTH_VALUE
+                )
+            )
+            .fold(0.0, f32::max);
+
+        speed_load.max(tx_load)
+    }
+
+    fn update_thermal_state(&mut self) {
+        let was_overheated = self.thermal_system.is_overheated();
+
+        self.thermal_system.update(self.thermal_load());
+
+        match (was_overheated, self.thermal_system.is_overheated()) {
+            (false, true) => {
+                self.trace_thermal_state();
+                self.push_event(EventKind::Overheated);
+            },
+            (true, false) => {
+                self.trace_thermal_state();
+                self.push_event(EventKind::CooledDown);
+            },
+            _             => (),
+        }
+
+        let mut velocity = *self.movement_system.velocity();
+        velocity.truncate(
+            self.movement_system.max_speed() 
+                * Float::from(self.thermal_system.throttle_factor())
+        );
+        self.movement_system.set_velocity(velocity);
+    }
+
+    // Hovering costs the base power, horizontal flight grows with the speed
+    // squared (drag) and climbing grows with the climb rate. Everything is
+    // scaled by the total mass, so payload shortens endurance.
+    fn movement_power_draw(&self) -> Watt {
+        let parameters = &self.parameters;
+        let velocity = self.movement_system.velocity().displacement();
+        let speed_fraction = float_to_f32(
+            velocity.x.hypot(velocity.y) / parameters.max_drone_speed()
+        );
+        let climb_fraction = float_to_f32(
+            velocity.z.max(0.0) / parameters.max_drone_speed()
+        );
+
+        self.mass_factor() * (
+            parameters.hover_power_draw()
+                + speed_fraction.powi(2) * parameters.speed_power_draw()
+                + climb_fraction * parameters.climb_power_draw()
+        )
+    }
+
+    fn mass_factor(&self) -> f32 {
+        let drone_mass = self.parameters.drone_mass();
+        let payload_mass = self.payload
+            .as_ref()
+            .map_or(0.0, Payload::mass);
+
+        (drone_mass + payload_mass) / drone_mass
+    }
+
+    // Landed devices stay on the ground with their motors stopped.
     fn update_real_position(&mut self) -> Result<(), DeviceError> {
-        if self.movement_system.is_disabled() {
+        if self.movement_system.is_disabled() || self.landed {
             return Ok(());
         }
+        if self.externally_driven {
+            return self.follow_external_position();
+        }
 
-        self.try_consume_power(MOVEMENT_POWER_CONSUMPTION)?;
+        self.try_draw_power(
+            self.movement_power_draw(),
+            EnergyConsumer::Movement
+        )?;
+        self.orientation.follow_velocity(
+            self.movement_system.velocity(),
+            millis_to_secs(ITERATION_TIME)
+        );
         
         self.real_position_in_meters = equation_of_motion_3d(
             &self.real_position_in_meters,
             &self.movement_system.velocity().displacement(),
             millis_to_secs(ITERATION_TIME),
         );
+        self.navigation_system.dead_reckon(
+            self.movement_system.velocity(),
+            millis_to_secs(ITERATION_TIME)
+        );
         
         Ok(())
     }
 
+    // The device keeps its position until a new one is received. Its own
+    // velocity is ignored, so the orientation follows the displacement.
+    fn follow_external_position(&mut self) -> Result<(), DeviceError> {
+        let Some(external_position) = self.external_position.take() else {
+            return Ok(());
+        };
+
+        self.try_draw_power(
+            self.movement_power_draw(),
+            EnergyConsumer::Movement
+        )?;
+
+        let iteration_time = millis_to_secs(ITERATION_TIME);
+        let velocity = Vector3D::new(
+            Point3D::default(),
+            (external_position - self.real_position_in_meters)
+                / Float::from(iteration_time)
+        );
+
+        self.orientation.follow_velocity(&velocity, iteration_time);
+        self.navigation_system.dead_reckon(&velocity, iteration_time);
+        self.real_position_in_meters = external_position;
+
+        Ok(())
+    }
+
     // Device can check if it has reached the task only if it knows
     // its current position (if it has GPS connection).
     fn try_complete_task(&mut self) {
@@ -475,20 +1699,40 @@ impl Device {
             Task::Attack(destination) 
                 if self.at_destination(&destination) => { 
                 self.trace_reached_destination();
+                self.complete_task();
                 self.selfdestruction();
             },
+            Task::DropPayload(destination) 
+                if self.at_destination(&destination) => { 
+                self.trace_reached_destination();
+                self.drop_payload();
+                self.complete_task();
+                self.task = Task::Undefined;
+            },
             Task::Reposition(destination) 
                 if self.at_destination(&destination) => { 
                 self.trace_reached_destination();
+                self.complete_task();
                 self.task = Task::Undefined;
             },
             _ => (),
         }
     }
 
+    fn complete_task(&mut self) {
+        self.mission.complete(&self.task);
+        self.push_event(EventKind::TaskCompleted(self.task));
+    }
+
+    fn drop_payload(&mut self) {
+        if let Some(payload) = self.payload.take() {
+            self.trace_dropped_payload(&payload);
+        }
+    }
+
     #[must_use]
     pub fn at_destination(&self, destination: &Point3D) -> bool {
-        self.distance_to(destination) <= DESTINATION_RADIUS 
+        self.distance_to(destination) <= self.parameters.destination_radius()
     }
 
     fn selfdestruction(&mut self) {
@@ -498,11 +1742,49 @@ impl Device {
         self.security_system = SecuritySystem::default();
     }
 
+    // Max speed drops along with health.
+    fn take_damage(&mut self, damage: f32) {
+        let health = self.health();
+
+        self.damage = (self.damage + damage).min(1.0);
+
+        if self.health() <= 0.0 {
+            self.selfdestruction();
+        } else {
+            self.movement_system.scale_max_speed(self.health() / health);
+        }
+    }
+
+    fn deflect(&mut self, away: Vector3D) {
+        let mut velocity = away;
+
+        velocity.scale_to(self.movement_system.velocity().size());
+        self.movement_system.set_velocity(velocity);
+    }
+
+    fn handle_hardware_failures(&mut self) {
+        for component in self.reliability_system.fail_components() {
+            match component {
+                HardwareComponent::Motors      =>
+                    self.movement_system.disable(),
+                HardwareComponent::Radio       =>
+                    self.trx_system = TRXSystem::default(),
+                HardwareComponent::GPSReceiver =>
+                    for band in Frequency::GPS_BANDS {
+                        self.trx_system.stop_receiving_on(band);
+                    },
+            }
+
+            self.trace_hardware_failure(component);
+            self.push_event(EventKind::HardwareFailure(component));
+        }
+    }
+
     fn handle_malware_infections(&mut self) {
         let malware_infections: Vec<Malware> = self.infection_map
             .iter()
             .filter_map(|(malware, infection_time)| {
-                let malicious_payload_execution_time = infection_time 
+                let malicious_payload_execution_time = *infection_time 
                     + malware.infection_delay();
 
                 if self.current_time == malicious_payload_execution_time {
@@ -516,7 +1798,10 @@ impl Device {
         for malware in malware_infections {
             match malware.malware_type() {
                 MalwareType::DoS(lost_power) => {
-                    let _ = self.try_consume_power(*lost_power);
+                    let _ = self.try_consume_energy(
+                        power_units_to_joules(*lost_power),
+                        EnergyConsumer::Processing
+                    );
                 },
                 MalwareType::Indicator       => (),
             }
@@ -530,7 +1815,7 @@ impl Device {
             self.current_time,
             self.id,
             self.trx_system
-                .received_signal_on(&Frequency::Control)
+                .received_signal_on(&self.control_frequency())
                 .map_or(BLACK_SIGNAL_STRENGTH, |(_, signal)| *signal.strength())
         );
     }
@@ -553,6 +1838,145 @@ impl Device {
         );
     }
 
+    fn trace_thermal_state(&self) {
+        trace!(
+            "Current time: {}, Id: {}, Temperature: {}, Throttle factor: {}",
+            self.current_time,
+            self.id,
+            self.thermal_system.temperature(),
+            self.thermal_system.throttle_factor()
+        );
+    }
+
+    fn trace_control_channel_switch(&self, frequency: Frequency) {
+        trace!(
+            "Current time: {}, Id: {}, Switched control channel to {:?}",
+            self.current_time,
+            self.id,
+            frequency
+        );
+    }
+
+    fn trace_clock_synchronized(&self) {
+        trace!(
+            "Current time: {}, Id: {}, Set clock to {}",
+            self.current_time,
+            self.id,
+            self.local_time()
+        );
+    }
+
+    fn trace_mission_continued(&self) {
+        trace!(
+            "Current time: {}, Id: {}, Continuing mission without control",
+            self.current_time,
+            self.id
+        );
+    }
+
+    fn trace_emergency_landing(&self, landing_site: &Point3D) {
+        trace!(
+            "Current time: {}, Id: {}, Landing in emergency at {:?}",
+            self.current_time,
+            self.id,
+            landing_site
+        );
+    }
+
+    fn trace_landed(&self) {
+        trace!(
+            "Current time: {}, Id: {}, Landed",
+            self.current_time,
+            self.id
+        );
+    }
+
+    fn trace_firmware_install_started(&self, update: FirmwareUpdate) {
+        trace!(
+            "Current time: {}, Id: {}, Installing firmware version {}",
+            self.current_time,
+            self.id,
+            update.version()
+        );
+    }
+
+    fn trace_corrupted_data(&self, data: &Data, action: &str) {
+        trace!(
+            "Current time: {}, Id: {}, {} corrupted data {:?}",
+            self.current_time,
+            self.id,
+            action,
+            data
+        );
+    }
+
+    fn trace_firmware_updated(&self) {
+        trace!(
+            "Current time: {}, Id: {}, Updated firmware to version {}",
+            self.current_time,
+            self.id,
+            self.firmware_system.version()
+        );
+    }
+
+    fn trace_rumor_learned(&self, rumor: Rumor) {
+        trace!(
+            "Current time: {}, Id: {}, Learned rumor {:?}",
+            self.current_time,
+            self.id,
+            rumor
+        );
+    }
+
+    fn trace_gps_spoofing(&self, evidence: SpoofingEvidence) {
+        trace!(
+            "Current time: {}, Id: {}, GPS fix looks spoofed ({:?})",
+            self.current_time,
+            self.id,
+            evidence
+        );
+    }
+
+    fn trace_hardware_failure(&self, component: HardwareComponent) {
+        trace!(
+            "Current time: {}, Id: {}, {:?} failed",
+            self.current_time,
+            self.id,
+            component
+        );
+    }
+
+    fn trace_collision(&self, other_id: DeviceId, outcome: CollisionOutcome) {
+        trace!(
+            "Current time: {}, Id: {}, Collided with {} ({:?}), Health: {}",
+            self.current_time,
+            self.id,
+            other_id,
+            outcome,
+            self.health()
+        );
+    }
+
+    fn trace_recharge(&self) {
+        trace!(
+            "Current time: {}, Id: {}, Recharged to {} (cycle {})",
+            self.current_time,
+            self.id,
+            self.power_system.max_power(),
+            self.power_system.cycle_count()
+        );
+    }
+
+    fn trace_dropped_payload(&self, payload: &Payload) {
+        trace!(
+            "Current time: {}, Id: {}, Dropped {} ({} kg)",
+            self.current_time,
+            self.id,
+            payload.item(),
+            payload.mass()
+        );
+    }
+
     fn trace_reached_destination(&self) {
         trace!(
             "Current time: {}, Id: {}, Reached destination",
@@ -566,15 +1990,40 @@ impl Default for Device {
     fn default() -> Self {
         Self {
             id: generate_device_id(),
-            current_time: 0,
+            current_time: Millisecond::ZERO,
             real_position_in_meters: Point3D::default(),
+            orientation: Orientation::default(),
             task: Task::Undefined,
             power_system: PowerSystem::default(),
             movement_system: MovementSystem::default(),
             trx_system: TRXSystem::default(),
             security_system: SecuritySystem::default(),
+            reliability_system: ReliabilitySystem::default(),
+            thermal_system: ThermalSystem::default(),
+            sensor_system: SensorSystem::default(),
+            flocking_system: FlockingSystem::default(),
+            navigation_system: NavigationSystem::default(),
+            channel_system: ChannelSystem::default(),
+            gossip_system: GossipSystem::default(),
+            clock_system: ClockSystem::default(),
+            firmware_system: FirmwareSystem::default(),
+            integrity_system: IntegritySystem::default(),
+            payload: None,
+            damage: 0.0,
+            energy_usage: EnergyUsage::default(),
             infection_map: InfectionMap::default(),
+            mission: Mission::default(),
             signal_loss_response: SignalLossResponse::default(),
+            processing_delay: Millisecond::ZERO,
+            control_signal_lost: false,
+            control_signal_lost_at: Millisecond::ZERO,
+            autonomous: false,
+            landing_site: None,
+            landed: false,
+            externally_driven: false,
+            external_position: None,
+            parameters: SimulationParameters::default(),
+            events: Vec::new(),
         }
     }
 }
@@ -588,17 +2037,21 @@ impl Position for Device {
 
 #[cfg(test)]
 mod tests {
-    use crate::backend::device::systems::{RXModule, TXModule};
-    use crate::backend::mathphysics::Megahertz;
+    use std::sync::Arc;
+
+    use crate::backend::device::systems::{RXModule, TXModule, VersionRange};
+    use crate::backend::landing::LandingZone;
+    use crate::backend::mathphysics::{Megahertz, PowerUnit, Vector3D};
     use crate::backend::signal::{
-        GREEN_SIGNAL_STRENGTH, MAX_RED_SIGNAL_STRENGTH
+        GREEN_SIGNAL_STRENGTH, MAX_BLACK_SIGNAL_STRENGTH, 
+        MAX_RED_SIGNAL_STRENGTH
     };
 
     use super::*;
 
 
-    const DRONE_TX_CONTROL_RADIUS: Meter = 10.0;
-    const DEVICE_MAX_POWER: PowerUnit    = 10_000;
+    const DRONE_TX_CONTROL_RADIUS: Meter = Meter::new(10.0);
+    const DEVICE_MAX_POWER: PowerUnit    = PowerUnit::new(10_000);
     const MAX_ITER_COUNT: usize          = 10_000;
     const SOME_DEVICE_ID: DeviceId       = 5;
     
@@ -608,6 +2061,14 @@ mod tests {
             .unwrap_or_else(|error| panic!("{}", error))
     }
 
+    // Energy a hovering drone without payload draws in one iteration.
+    fn hovering_energy_per_iteration() -> Joule {
+        let parameters = SimulationParameters::default();
+
+        (parameters.passive_power_draw() + parameters.hover_power_draw())
+            * millis_to_secs(ITERATION_TIME)
+    }
+
     fn control_tx_module(radius: Meter) -> TXModule {
         let tx_signal_strength  = SignalStrength::from_area_radius(
             radius, 
@@ -630,7 +2091,7 @@ mod tests {
     }
      
     fn drone_movement_system() -> MovementSystem {
-        MovementSystem::build(MAX_DRONE_SPEED)
+        MovementSystem::build(SimulationParameters::default().max_drone_speed())
             .unwrap_or_else(|error| panic!("{}", error))
     }
 
@@ -641,8 +2102,16 @@ mod tests {
         )
     }
 
+    fn iteration_times(
+        end_time: Millisecond
+    ) -> impl Iterator<Item = Millisecond> {
+        (0..end_time.value())
+            .step_by(ITERATION_TIME.value() as usize)
+            .map(Millisecond::new)
+    }
+
     fn indicator_malware() -> Malware {
-        Malware::new(MalwareType::Indicator, 0, None)
+        Malware::new(MalwareType::Indicator, Millisecond::ZERO, None, None)
     }
 
     fn send_signal_until_it_is_received(
@@ -688,9 +2157,9 @@ mod tests {
     #[test]
     fn device_selfdestructs_after_consuming_all_power() {
         let task  = Task::Attack(Point3D::new(5.0, 5.0, 5.0));
-        let power = PASSIVE_POWER_CONSUMPTION + MOVEMENT_POWER_CONSUMPTION;
+        let energy = hovering_energy_per_iteration();
         
-        let power_system    = PowerSystem::build(power, power)
+        let power_system    = PowerSystem::build_in_joules(energy, energy)
             .unwrap_or_else(|error| panic!("{}", error));
         let movement_system = MovementSystem::build(25.0)
             .unwrap_or_else(|error| panic!("{}", error));
@@ -711,7 +2180,7 @@ mod tests {
         
         assert!(
             matches!(
-                device.update(), 
+                device.update(&WorldView::default()), 
                 Err(
                     DeviceError::PowerSystemError(
                         PowerSystemError::NoPowerLeft
@@ -722,6 +2191,54 @@ mod tests {
         assert!(device.is_shut_down());
     }
 
+    #[test]
+    fn energy_usage_does_not_exceed_drained_energy() {
+        let energy = hovering_energy_per_iteration();
+        let power_system = PowerSystem::build_in_joules(energy, energy)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        let mut device = DeviceBuilder::new()
+            .set_task(Task::Attack(Point3D::new(5.0, 5.0, 5.0)))
+            .set_power_system(power_system)
+            .set_movement_system(drone_movement_system())
+            .set_trx_system(drone_green_trx_system())
+            .build();
+
+        let _ = device.update(&WorldView::default());
+
+        let energy_usage = *device.energy_usage();
+
+        assert!(
+            (energy_usage.of(EnergyConsumer::Passive)
+                - SimulationParameters::default().passive_power_draw() 
+                    * millis_to_secs(ITERATION_TIME)).value().abs()
+                < 0.001
+        );
+        assert!(energy_usage.of(EnergyConsumer::Movement) > Joule::ZERO);
+        assert!((energy_usage.total() - energy).value().abs() < 0.001);
+    }
+
+    #[test]
+    fn transmission_is_charged_per_signal() {
+        let mut device = DeviceBuilder::new()
+            .set_power_system(device_power_system())
+            .build();
+
+        let tx_energy_consumption = SimulationParameters::default()
+            .tx_energy_consumption();
+
+        device.draw_tx_energy(4);
+
+        assert!(
+            (device.energy_usage().of(EnergyConsumer::TX)
+                - 4.0 * tx_energy_consumption).value().abs() < 0.001
+        );
+        assert!(
+            (device.energy_usage().total()
+                - 4.0 * tx_energy_consumption).value().abs() < 0.001
+        );
+    }
+
     #[test]
     fn ascending_on_signal_loss() {
         let signal_loss_response = SignalLossResponse::Ascend;
@@ -738,7 +2255,7 @@ mod tests {
         let original_position = device_without_signal.real_position_in_meters;
 
         let many_iterations = ITERATION_TIME * 10;
-        for time in (0..many_iterations).step_by(ITERATION_TIME as usize) {
+        for time in iteration_times(many_iterations) {
             let gps_signal = Signal::new(
                 SOME_DEVICE_ID,
                 device_without_signal.id(),
@@ -748,7 +2265,7 @@ mod tests {
             );
 
             let _ = device_without_signal.receive_signal(gps_signal, time);
-            let _ = device_without_signal.update();
+            let _ = device_without_signal.update(&WorldView::default());
         }
 
         assert_eq!(
@@ -778,7 +2295,7 @@ mod tests {
         let original_position = device_without_signal.real_position_in_meters;
 
         let many_iterations = ITERATION_TIME * 500;
-        for time in (0..many_iterations).step_by(ITERATION_TIME as usize) {
+        for time in iteration_times(many_iterations) {
             let gps_signal = Signal::new(
                 SOME_DEVICE_ID,
                 device_without_signal.id(),
@@ -788,7 +2305,7 @@ mod tests {
             );
 
             let _ = device_without_signal.receive_signal(gps_signal, time);
-            let _ = device_without_signal.update();
+            let _ = device_without_signal.update(&WorldView::default());
         }
 
         assert_eq!(
@@ -829,7 +2346,7 @@ mod tests {
             .build();
 
         let many_iterations = ITERATION_TIME * 500;
-        for time in (0..many_iterations).step_by(ITERATION_TIME as usize) {
+        for time in iteration_times(many_iterations) {
             let gps_signal = Signal::new(
                 SOME_DEVICE_ID,
                 device_without_signal.id(),
@@ -843,39 +2360,220 @@ mod tests {
                 gps_signal,
                 time
             );
-            let _ = device_without_signal.update();
+            let _ = device_without_signal.update(&WorldView::default());
+        }
+
+        assert!(device_without_signal.at_destination(&home_point));
+    }
+    
+    #[test]
+    fn shutting_down_on_signal_loss() {
+        let signal_loss_response = SignalLossResponse::Shutdown;
+        let destination_point = Point3D::new(5.0, 5.0, 5.0);
+        let task = Task::Reposition(destination_point);
+        
+        let mut device_without_signal = DeviceBuilder::new()
+            .set_task(task)
+            .set_power_system(device_power_system())
+            .set_signal_loss_response(signal_loss_response)
+            .build();
+
+        let many_iterations = Millisecond::new(500);
+        for time in iteration_times(many_iterations) {
+            let gps_signal = Signal::new(
+                SOME_DEVICE_ID,
+                device_without_signal.id(),
+                Data::GPS(*device_without_signal.position()), 
+                Frequency::GPS,
+                MAX_RED_SIGNAL_STRENGTH,
+            );
+
+            let _ = device_without_signal.receive_signal(gps_signal, time);
+            let _ = device_without_signal.update(&WorldView::default());
+        }
+
+        assert!(device_without_signal.is_shut_down());
+    }
+
+    #[test]
+    fn continuing_mission_after_patience_on_signal_loss() {
+        let patience = Millisecond::new(500);
+        let first_destination = Point3D::new(MAX_DRONE_SPEED, 0.0, 0.0);
+        let last_destination = Point3D::new(
+            MAX_DRONE_SPEED, 
+            MAX_DRONE_SPEED, 
+            0.0
+        );
+        let mission = Mission::new(vec![
+            Task::Reposition(first_destination),
+            Task::Reposition(last_destination),
+        ]);
+        let trx_system = TRXSystem::new( 
+            TXModule::default(), 
+            rx_module() 
+        );
+
+        let mut device = DeviceBuilder::new()
+            .set_power_system(device_power_system())
+            .set_movement_system(drone_movement_system())
+            .set_trx_system(trx_system)
+            .set_mission(mission)
+            .set_signal_loss_response(
+                SignalLossResponse::ContinueMission(patience)
+            )
+            .build();
+
+        let many_iterations = Millisecond::new(5000);
+        for time in iteration_times(many_iterations) {
+            let gps_signal = Signal::new(
+                SOME_DEVICE_ID,
+                device.id(),
+                Data::GPS(*device.position()), 
+                Frequency::GPS,
+                MAX_RED_SIGNAL_STRENGTH,
+            );
+            
+            send_signal_until_it_is_received(&mut device, gps_signal, time);
+            assert!(device.update(&WorldView::default()).is_ok());
+        }
+
+        let events = device.take_events();
+        let mission_continued_times: Vec<Millisecond> = events
+            .iter()
+            .filter(|event| *event.kind() == EventKind::MissionContinued)
+            .map(Event::time)
+            .collect();
+
+        assert_eq!(mission_continued_times.len(), 1);
+        assert!(mission_continued_times[0] >= patience);
+        assert!(device.is_autonomous());
+        assert!(device.mission().is_complete());
+        assert!(device.at_destination(&last_destination));
+    }
+    
+    #[test]
+    fn landing_at_nearest_reachable_zone_after_patience_on_signal_loss() {
+        let patience = Millisecond::new(500);
+        let near_zone = LandingZone::new(
+            Point3D::new(30.0, 0.0, 0.0),
+            Meter::new(5.0)
+        );
+        let farther_zone = LandingZone::new(
+            Point3D::new(-60.0, 0.0, 0.0),
+            Meter::new(5.0)
+        );
+        let world_view = WorldView::default()
+            .set_landing_zones(Arc::from([farther_zone, near_zone]));
+        let trx_system = TRXSystem::new( 
+            TXModule::default(), 
+            rx_module() 
+        );
+
+        let mut device = DeviceBuilder::new()
+            .set_real_position(Point3D::new(0.0, 0.0, 20.0))
+            .set_power_system(device_power_system())
+            .set_movement_system(drone_movement_system())
+            .set_trx_system(trx_system)
+            .set_signal_loss_response(SignalLossResponse::Land(patience))
+            .build();
+
+        let many_iterations = Millisecond::new(5000);
+        for time in iteration_times(many_iterations) {
+            let gps_signal = Signal::new(
+                SOME_DEVICE_ID,
+                device.id(),
+                Data::GPS(*device.position()), 
+                Frequency::GPS,
+                MAX_RED_SIGNAL_STRENGTH,
+            );
+            
+            send_signal_until_it_is_received(&mut device, gps_signal, time);
+            assert!(device.update(&world_view).is_ok());
+        }
+
+        let events = device.take_events();
+        let landing_events: Vec<&EventKind> = events
+            .iter()
+            .map(Event::kind)
+            .filter(|kind| matches!(
+                kind,
+                EventKind::EmergencyLandingStarted(_) | EventKind::Landed
+            ))
+            .collect();
+
+        assert_eq!(
+            landing_events,
+            vec![
+                &EventKind::EmergencyLandingStarted(*near_zone.center()),
+                &EventKind::Landed
+            ]
+        );
+        assert!(device.has_landed());
+        assert!(near_zone.contains(device.position()));
+        assert_eq!(device.position().z, 0.0);
+    }
+
+    #[test]
+    fn landing_in_place_on_critical_battery() {
+        let device_position = Point3D::new(5.0, 5.0, 10.0);
+        let power_system = PowerSystem::build_in_joules(
+            Joule::new(1_000.0),
+            Joule::new(150.0)
+        )
+            .unwrap_or_else(|error| panic!("{}", error))
+            .set_critical_level(0.2);
+
+        let mut device = DeviceBuilder::new()
+            .set_real_position(device_position)
+            .set_power_system(power_system)
+            .set_movement_system(drone_movement_system())
+            .set_signal_loss_response(SignalLossResponse::Ascend)
+            .build();
+
+        for _ in iteration_times(Millisecond::new(1000)) {
+            assert!(device.update(&WorldView::default()).is_ok());
         }
 
-        assert!(device_without_signal.at_destination(&home_point));
+        let landed_position = *device.position();
+
+        assert!(device.has_landed());
+        assert_eq!(
+            device.landing_site(),
+            Some(&Point3D::new(device_position.x, device_position.y, 0.0))
+        );
+        assert_eq!(landed_position, *device.landing_site().unwrap());
+
+        let _ = device.update(&WorldView::default());
+
+        assert_eq!(*device.position(), landed_position);
     }
     
     #[test]
-    fn shutting_down_on_signal_loss() {
-        let signal_loss_response = SignalLossResponse::Shutdown;
-        let destination_point = Point3D::new(5.0, 5.0, 5.0);
-        let task = Task::Reposition(destination_point);
-        
-        let mut device_without_signal = DeviceBuilder::new()
-            .set_task(task)
+    fn device_below_landing_site_climbs_before_landing() {
+        let landing_site = Point3D::new(0.0, 0.0, 30.0);
+        let mut device = DeviceBuilder::new()
+            .set_real_position(Point3D::new(0.0, 0.0, 10.0))
             .set_power_system(device_power_system())
-            .set_signal_loss_response(signal_loss_response)
+            .set_movement_system(drone_movement_system())
             .build();
 
-        let many_iterations = 500;
-        for time in (0..many_iterations).step_by(ITERATION_TIME as usize) {
-            let gps_signal = Signal::new(
-                SOME_DEVICE_ID,
-                device_without_signal.id(),
-                Data::GPS(*device_without_signal.position()), 
-                Frequency::GPS,
-                MAX_RED_SIGNAL_STRENGTH,
-            );
+        device.landing_site = Some(landing_site);
+        device.land();
 
-            let _ = device_without_signal.receive_signal(gps_signal, time);
-            let _ = device_without_signal.update();
+        assert!(!device.has_landed());
+        assert_eq!(device.position().z, 10.0);
+
+        for _ in iteration_times(Millisecond::new(5000)) {
+            assert!(device.update(&WorldView::default()).is_ok());
+
+            if device.has_landed() {
+                break;
+            }
+            assert!(device.position().z < landing_site.z + 5.0);
         }
 
-        assert!(device_without_signal.is_shut_down());
+        assert!(device.has_landed());
+        assert_eq!(*device.position(), landing_site);
     }
     
     #[test]
@@ -898,8 +2596,8 @@ mod tests {
             device_position
         );
 
-        for _ in (0..1000).step_by(ITERATION_TIME as usize) {
-            let _ = device.update();
+        for _ in iteration_times(Millisecond::new(1000)) {
+            let _ = device.update(&WorldView::default());
 
             assert_eq!(
                 *device.gps_position(), 
@@ -923,8 +2621,8 @@ mod tests {
             .set_movement_system(drone_movement_system())
             .build();
 
-        for _ in (0..1000).step_by(ITERATION_TIME as usize) {
-            let _ = device_without_gps.update();
+        for _ in iteration_times(Millisecond::new(1000)) {
+            let _ = device_without_gps.update(&WorldView::default());
         }
 
         assert_eq!(
@@ -953,8 +2651,8 @@ mod tests {
             .set_trx_system(trx_system)
             .build();
             
-        let many_iterations = 1000;
-        for time in (0..many_iterations).step_by(ITERATION_TIME as usize) {
+        let many_iterations = Millisecond::new(1000);
+        for time in iteration_times(many_iterations) {
             let gps_signal = Signal::new(
                 SOME_DEVICE_ID,
                 device.id(),
@@ -964,12 +2662,75 @@ mod tests {
             );
             
             send_signal_until_it_is_received(&mut device, gps_signal, time);
-            assert!(device.update().is_ok());
+            assert!(device.update(&WorldView::default()).is_ok());
         }
 
         assert!(device.at_destination(&destination_point));
     }
 
+    #[test]
+    fn payload_is_dropped_at_destination() {
+        let destination_point = Point3D::new(MAX_DRONE_SPEED, 0.0, 0.0);
+        let task = Task::DropPayload(destination_point);
+        let trx_system = TRXSystem::new( 
+            TXModule::default(), 
+            rx_module() 
+        );
+        
+        let mut device = DeviceBuilder::new()
+            .set_task(task)
+            .set_power_system(device_power_system())
+            .set_movement_system(drone_movement_system())
+            .set_trx_system(trx_system)
+            .set_payload(Payload::new("cargo", 1.0))
+            .build();
+            
+        let many_iterations = Millisecond::new(1000);
+        for time in iteration_times(many_iterations) {
+            let gps_signal = Signal::new(
+                SOME_DEVICE_ID,
+                device.id(),
+                Data::GPS(*device.position()), 
+                Frequency::GPS,
+                MAX_RED_SIGNAL_STRENGTH,
+            );
+            
+            send_signal_until_it_is_received(&mut device, gps_signal, time);
+            assert!(device.update(&WorldView::default()).is_ok());
+        }
+
+        assert!(device.payload().is_none());
+        assert_eq!(device.task, Task::Undefined);
+        assert!(
+            device
+                .take_events()
+                .iter()
+                .any(|event| *event.kind() == EventKind::TaskCompleted(task))
+        );
+    }
+
+    #[test]
+    fn flocking_device_moves_away_from_close_neighbor() {
+        let mut device = DeviceBuilder::new()
+            .set_power_system(device_power_system())
+            .set_movement_system(drone_movement_system())
+            .set_flocking_system(
+                FlockingSystem::new(Meter::new(10.0), 20.0, 0.0, 0.0)
+            )
+            .set_signal_loss_response(SignalLossResponse::Ignore)
+            .build();
+        let neighbor = DeviceBuilder::new()
+            .set_real_position(Point3D::new(2.0, 0.0, 0.0))
+            .set_power_system(device_power_system())
+            .build();
+        let world_view = WorldView::observed_by(&device, [&neighbor]);
+
+        assert!(device.update(&world_view).is_ok());
+
+        assert!(device.position().x < 0.0);
+        assert_eq!(device.position().y, 0.0);
+    }
+
     #[test]
     fn device_selfdestruction() {
         let task = Task::Attack(Point3D::new(5.0, 5.0, 5.0));
@@ -1010,11 +2771,11 @@ mod tests {
             Frequency::Control, 
             MAX_RED_SIGNAL_STRENGTH, 
         );
-        let time = 0;
+        let time = Millisecond::ZERO;
 
         send_signal_until_it_is_received(&mut device, signal, time);
 
-        assert!(device.process_received_signals().is_ok());
+        assert!(device.process_received_signals(&WorldView::default()).is_ok());
         assert_eq!(task, device.task);
     }
     
@@ -1039,15 +2800,100 @@ mod tests {
             Frequency::GPS,
             MAX_RED_SIGNAL_STRENGTH,
         );
-        let time = 0;
+        let time = Millisecond::ZERO;
 
         send_signal_until_it_is_received(&mut device, gps_signal, time);
 
-        assert!(device.process_received_signals().is_ok());
+        assert!(device.process_received_signals(&WorldView::default()).is_ok());
         assert_eq!(device.real_position_in_meters, global_position);
         assert_eq!(*device.gps_position(), gps_position);
     }
 
+    #[test]
+    fn multi_band_receiver_keeps_fix_while_any_band_is_not_denied() {
+        let max_rx_signal_strength_map = FreqToStrengthMap::from([
+            (Frequency::GPS, GREEN_SIGNAL_STRENGTH),
+            (Frequency::GPSL5, GREEN_SIGNAL_STRENGTH)
+        ]);
+        let mut device = DeviceBuilder::new()
+            .set_power_system(device_power_system())
+            .set_trx_system(TRXSystem::new(
+                TXModule::default(),
+                RXModule::new(max_rx_signal_strength_map)
+            ))
+            .build();
+        let time = Millisecond::ZERO;
+        let jamming_signal = Signal::new(
+            SOME_DEVICE_ID,
+            device.id(),
+            Data::Noise,
+            Frequency::GPS,
+            SignalStrength::new(2.0 * GREEN_SIGNAL_STRENGTH_VALUE),
+        );
+        let gps_signal = Signal::new(
+            SOME_DEVICE_ID,
+            device.id(),
+            Data::GPS(Point3D::default()),
+            Frequency::GPSL5,
+            MAX_RED_SIGNAL_STRENGTH,
+        );
+
+        for _ in 0..MAX_ITER_COUNT {
+            let _ = device.receive_signal(jamming_signal, time);
+        }
+
+        assert!(!device.has_gps_fix());
+
+        send_signal_until_it_is_received(&mut device, gps_signal, time);
+
+        assert!(device.has_gps_fix());
+    }
+
+    #[test]
+    fn mismatched_band_fixes_are_detected() {
+        let max_rx_signal_strength_map = FreqToStrengthMap::from([
+            (Frequency::GPS, GREEN_SIGNAL_STRENGTH),
+            (Frequency::GPSL5, GREEN_SIGNAL_STRENGTH)
+        ]);
+        let mut device = DeviceBuilder::new()
+            .set_power_system(device_power_system())
+            .set_trx_system(TRXSystem::new(
+                TXModule::default(),
+                RXModule::new(max_rx_signal_strength_map)
+            ))
+            .build();
+        let time = Millisecond::ZERO;
+        let spoofing_signal = Signal::new(
+            SOME_DEVICE_ID,
+            device.id(),
+            Data::GPS(Point3D::new(100.0, 0.0, 0.0)),
+            Frequency::GPS,
+            MAX_RED_SIGNAL_STRENGTH,
+        );
+        let gps_signal = Signal::new(
+            SOME_DEVICE_ID,
+            device.id(),
+            Data::GPS(Point3D::default()),
+            Frequency::GPSL5,
+            MAX_RED_SIGNAL_STRENGTH,
+        );
+
+        send_signal_until_it_is_received(&mut device, spoofing_signal, time);
+        send_signal_until_it_is_received(&mut device, gps_signal, time);
+
+        assert!(device.process_received_signals(&WorldView::default()).is_ok());
+        assert!(
+            device
+                .take_events()
+                .iter()
+                .any(|event| 
+                    *event.kind() == EventKind::GPSSpoofingDetected(
+                        SpoofingEvidence::BandMismatch
+                    )
+                )
+        );
+    }
+
     #[test]
     fn receive_and_process_broadcast_signal() {
         let task = Task::Attack(Point3D::new(5.0, 0.0, 0.0));
@@ -1064,11 +2910,11 @@ mod tests {
             Frequency::Control, 
             MAX_RED_SIGNAL_STRENGTH, 
         );
-        let time = 0;
+        let time = Millisecond::ZERO;
 
         send_signal_until_it_is_received(&mut device, signal, time);
         
-        assert!(device.process_received_signals().is_ok());
+        assert!(device.process_received_signals(&WorldView::default()).is_ok());
         assert_eq!(task, device.task);
     }
 
@@ -1090,7 +2936,7 @@ mod tests {
 
         assert!(
             matches!(
-                device.receive_signal(signal, 0),
+                device.receive_signal(signal, Millisecond::ZERO),
                 Err(TRXSystemError::WrongSignalDestination)
             )
         );
@@ -1112,7 +2958,7 @@ mod tests {
             Frequency::Control, 
             MAX_RED_SIGNAL_STRENGTH, 
         );
-        let time = 0;
+        let time = Millisecond::ZERO;
 
         assert!(!device.is_infected());
         assert!(!device.is_infected_with(&malware));
@@ -1138,16 +2984,256 @@ mod tests {
             Frequency::Control,
             MAX_RED_SIGNAL_STRENGTH, 
         );
-        let time = 0;
+        let time = Millisecond::ZERO;
 
         assert!(!device.is_infected());
         assert!(!device.is_infected_with(&malware));
         assert!(!device.security_system.patches(&malware));
 
         send_signal_until_it_is_received(&mut device, signal, time);
-        assert!(device.process_received_signals().is_ok());
+        assert!(device.process_received_signals(&WorldView::default()).is_ok());
 
         assert!(device.is_infected());
         assert!(device.is_infected_with(&malware));
     }
+
+    #[test]
+    fn updated_device_is_not_infected_by_targeted_malware() {
+        let malware = Malware::new(
+            MalwareType::Indicator,
+            Millisecond::ZERO,
+            None,
+            Some(VersionRange::new(1, 1))
+        );
+        let mut device = DeviceBuilder::new()
+            .set_power_system(device_power_system())
+            .set_trx_system(drone_green_trx_system())
+            .set_firmware_system(FirmwareSystem::new(2, None))
+            .build(); 
+        
+        let signal = Signal::new(
+            SOME_DEVICE_ID,
+            BROADCAST_ID,
+            Data::Malware(malware), 
+            Frequency::Control, 
+            MAX_RED_SIGNAL_STRENGTH, 
+        );
+
+        send_signal_until_it_is_received(
+            &mut device,
+            signal,
+            Millisecond::ZERO
+        );
+        assert!(device.update(&WorldView::default()).is_ok());
+        assert!(!device.is_infected_with(&malware));
+    }
+
+    #[test]
+    fn corrupted_task_is_discarded_by_integrity_check() {
+        let task = Task::Reposition(Point3D::new(10.0, 10.0, 5.0));
+        let mut device = DeviceBuilder::new()
+            .set_power_system(device_power_system())
+            .set_trx_system(drone_green_trx_system())
+            .set_integrity_system(IntegritySystem::new(true, true))
+            .build(); 
+        
+        let signal = Signal::new(
+            SOME_DEVICE_ID,
+            BROADCAST_ID,
+            Data::SetTask(task), 
+            Frequency::Control, 
+            MAX_BLACK_SIGNAL_STRENGTH, 
+        );
+        send_signal_until_it_is_received(
+            &mut device,
+            signal,
+            Millisecond::ZERO
+        );
+
+        // Corruption is random, so the signal is processed until the
+        // corrupted copy is discarded.
+        for _ in 0..MAX_ITER_COUNT {
+            device.task = Task::Undefined;
+            assert!(
+                device.process_received_signals(&WorldView::default()).is_ok()
+            );
+
+            if device
+                .take_events()
+                .iter()
+                .any(|event| *event.kind() == EventKind::CorruptedDataDiscarded)
+            {
+                break;
+            }
+
+            assert_eq!(device.task, task);
+        }
+
+        assert_eq!(device.task, Task::Undefined);
+    }
+
+    #[test]
+    fn firmware_update_is_installed_over_time() {
+        let install_time = ITERATION_TIME + ITERATION_TIME;
+        let update       = FirmwareUpdate::new(
+            2,
+            install_time,
+            Watt::new(5.0)
+        );
+        let mut device = DeviceBuilder::new()
+            .set_power_system(device_power_system())
+            .set_trx_system(drone_green_trx_system())
+            .set_firmware_system(FirmwareSystem::new(1, None))
+            .build(); 
+        
+        let signal = Signal::new(
+            SOME_DEVICE_ID,
+            BROADCAST_ID,
+            Data::FirmwareUpdate(update), 
+            Frequency::Control, 
+            MAX_RED_SIGNAL_STRENGTH, 
+        );
+
+        send_signal_until_it_is_received(
+            &mut device,
+            signal,
+            Millisecond::ZERO
+        );
+        assert!(device.update(&WorldView::default()).is_ok());
+        assert_eq!(device.firmware_system().installing(), Some(&update));
+
+        for _ in 0..2 {
+            assert!(device.update(&WorldView::default()).is_ok());
+        }
+
+        let event_kinds: Vec<EventKind> = device
+            .take_events()
+            .iter()
+            .map(|event| *event.kind())
+            .collect();
+
+        assert_eq!(device.firmware_system().version(), 2);
+        assert!(event_kinds.contains(&EventKind::FirmwareInstallStarted(2)));
+        assert!(event_kinds.contains(&EventKind::FirmwareUpdated(2)));
+    }
+
+    #[test]
+    fn infection_event_records_source() {
+        let malware    = indicator_malware(); 
+        let mut device = DeviceBuilder::new()
+            .set_power_system(device_power_system())
+            .set_trx_system(drone_green_trx_system())
+            .build(); 
+        
+        let signal = Signal::new(
+            SOME_DEVICE_ID,
+            BROADCAST_ID,
+            Data::Malware(malware), 
+            Frequency::Control,
+            MAX_RED_SIGNAL_STRENGTH, 
+        );
+
+        send_signal_until_it_is_received(
+            &mut device,
+            signal,
+            Millisecond::ZERO
+        );
+        assert!(device.update(&WorldView::default()).is_ok());
+
+        let events = device.take_events();
+
+        assert_eq!(
+            events.first().map(Event::kind),
+            Some(&EventKind::Infected { malware, source_id: SOME_DEVICE_ID })
+        );
+        assert!(device.take_events().is_empty());
+    }
+
+    #[test]
+    fn movement_power_draw_grows_with_speed_and_payload() {
+        let mut device = DeviceBuilder::new()
+            .set_movement_system(drone_movement_system())
+            .build();
+
+        let hover_power_draw = device.movement_power_draw();
+
+        device.movement_system.set_velocity(
+            Vector3D::new(
+                Point3D::default(), 
+                Point3D::new(MAX_DRONE_SPEED, 0.0, 0.0)
+            )
+        );
+        let flight_power_draw = device.movement_power_draw();
+
+        device.payload = Some(
+            Payload::new("cargo", SimulationParameters::default().drone_mass())
+        );
+        let payload_flight_power_draw = device.movement_power_draw();
+
+        assert!(
+            (hover_power_draw 
+                - SimulationParameters::default().hover_power_draw())
+                .value()
+                .abs() 
+                < f32::EPSILON
+        );
+        assert!(hover_power_draw < flight_power_draw);
+        assert!(
+            (payload_flight_power_draw - 2.0 * flight_power_draw)
+                .value()
+                .abs() 
+                < f32::EPSILON
+        );
+    }
+
+    #[test]
+    fn shutdown_and_signal_loss_events_are_emitted_once() {
+        let energy = hovering_energy_per_iteration();
+        let mut device = DeviceBuilder::new()
+            .set_task(Task::Reposition(Point3D::new(5.0, 5.0, 5.0)))
+            .set_power_system(
+                PowerSystem::build_in_joules(energy, energy)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_movement_system(drone_movement_system())
+            .set_trx_system(drone_green_trx_system())
+            .build();
+
+        for _ in 0..3 {
+            let _ = device.update(&WorldView::default());
+        }
+
+        let event_kinds: Vec<EventKind> = device
+            .take_events()
+            .iter()
+            .map(|event| *event.kind())
+            .collect();
+
+        assert_eq!(
+            event_kinds,
+            vec![EventKind::ControlSignalLost, EventKind::ShutDown]
+        );
+    }
+
+    #[test]
+    fn externally_driven_device_follows_external_position() {
+        let external_position = Point3D::new(1.0, 2.0, 3.0);
+        let mut device = DeviceBuilder::new()
+            .set_task(Task::Reposition(Point3D::new(-50.0, 0.0, 0.0)))
+            .set_power_system(device_power_system())
+            .set_movement_system(drone_movement_system())
+            .set_trx_system(drone_green_trx_system())
+            .build();
+
+        device.set_externally_driven(true);
+        device.set_external_position(external_position);
+        let _ = device.update(&WorldView::default());
+
+        assert_eq!(device.position(), &external_position);
+
+        // The position is kept until a new one is received.
+        let _ = device.update(&WorldView::default());
+
+        assert_eq!(device.position(), &external_position);
+    }
 }
@@ -5,11 +5,11 @@ use crate::backend::mathphysics::Millisecond;
 use crate::backend::task::Task;
 
 use super::Device;
+use super::storage::IdToDeviceMap;
 
 
 pub type DeviceId = usize;
 pub type IdToDelayMap  = HashMap<DeviceId, Millisecond>;
-pub type IdToDeviceMap = HashMap<DeviceId, Device>;
 pub type IdToTaskMap   = HashMap<DeviceId, Task>;
 
 
@@ -26,6 +26,6 @@ pub fn generate_device_id() -> DeviceId {
 pub fn device_map_from_slice(devices: &[Device]) -> IdToDeviceMap {
     devices
         .iter()
-        .map(|device| (device.id(), device.clone()))
+        .cloned()
         .collect()
 }
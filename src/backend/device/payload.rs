@@ -0,0 +1,29 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::mathphysics::Kilogram;
+
+
+// Cargo carried by a device until it is dropped.
+#[derive(Clone, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct Payload {
+    item: String,
+    mass: Kilogram,
+}
+
+impl Payload {
+    #[must_use]
+    pub fn new(item: &str, mass: Kilogram) -> Self {
+        Self { item: item.to_string(), mass: mass.max(0.0) }
+    }
+
+    #[must_use]
+    pub fn item(&self) -> &str {
+        &self.item
+    }
+
+    #[must_use]
+    pub fn mass(&self) -> Kilogram {
+        self.mass
+    }
+}
@@ -0,0 +1,333 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use slotmap::{new_key_type, SlotMap};
+use slotmap::basic::{Values, ValuesMut};
+
+use crate::backend::mathphysics::{Meter, Position};
+
+use super::{Device, DeviceId};
+
+
+new_key_type! {
+    pub struct DeviceKey;
+}
+
+
+type Iter<'a> = std::iter::Map<
+    Values<'a, DeviceKey, Device>, 
+    fn(&'a Device) -> (DeviceId, &'a Device)
+>;
+type IterMut<'a> = std::iter::Map<
+    ValuesMut<'a, DeviceKey, Device>, 
+    fn(&'a mut Device) -> (DeviceId, &'a mut Device)
+>;
+
+
+// Devices are stored in a generational arena, so iteration order is stable
+// and slots of removed devices are reused. The ID index maps public device
+// IDs to arena keys.
+#[derive(Clone, Debug, Default)]
+pub struct IdToDeviceMap {
+    devices: SlotMap<DeviceKey, Device>,
+    id_index: HashMap<DeviceId, DeviceKey>,
+}
+
+impl IdToDeviceMap {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            devices: SlotMap::with_key(),
+            id_index: HashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains_key(&self, device_id: &DeviceId) -> bool {
+        self.id_index.contains_key(device_id)
+    }
+
+    #[must_use]
+    pub fn get(&self, device_id: &DeviceId) -> Option<&Device> {
+        let key = self.id_index.get(device_id)?;
+
+        self.devices.get(*key)
+    }
+
+    #[must_use]
+    pub fn get_mut(&mut self, device_id: &DeviceId) -> Option<&mut Device> {
+        let key = self.id_index.get(device_id)?;
+
+        self.devices.get_mut(*key)
+    }
+
+    // Returns the replaced device if a device with the same ID was already
+    // stored.
+    pub fn insert(&mut self, device: Device) -> Option<Device> {
+        if let Some(stored_device) = self.get_mut(&device.id()) {
+            return Some(std::mem::replace(stored_device, device));
+        }
+
+        let device_id = device.id();
+        let key = self.devices.insert(device);
+
+        self.id_index.insert(device_id, key);
+
+        None
+    }
+
+    pub fn remove(&mut self, device_id: &DeviceId) -> Option<Device> {
+        let key = self.id_index.remove(device_id)?;
+
+        self.devices.remove(key)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = DeviceId> + '_ {
+        self.devices.values().map(Device::id)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Device> {
+        self.devices.values()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Device> {
+        self.devices.values_mut()
+    }
+
+    pub fn devices_where<F>(
+        &self,
+        predicate: F
+    ) -> impl Iterator<Item = &Device>
+    where
+        F: Fn(&Device) -> bool
+    {
+        self.values().filter(move |device| predicate(device))
+    }
+
+    // Devices at most `radius` away from `center`, which is a point or
+    // another device.
+    pub fn devices_within<P: Position>(
+        &self,
+        center: &P,
+        radius: Meter
+    ) -> impl Iterator<Item = &Device> {
+        self.devices_where(move |device| device.distance_to(center) <= radius)
+    }
+
+    pub fn active_devices(&self) -> impl Iterator<Item = &Device> {
+        self.devices_where(|device| !device.is_shut_down())
+    }
+
+    pub fn infected_devices(&self) -> impl Iterator<Item = &Device> {
+        self.devices_where(Device::is_infected)
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        self.devices
+            .values()
+            .map(|device| (device.id(), device))
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        self.devices
+            .values_mut()
+            .map(|device| (device.id(), device))
+    }
+}
+
+impl FromIterator<Device> for IdToDeviceMap {
+    fn from_iter<I: IntoIterator<Item = Device>>(iter: I) -> Self {
+        let mut device_map = Self::new();
+
+        for device in iter {
+            device_map.insert(device);
+        }
+
+        device_map
+    }
+}
+
+impl<'a> IntoIterator for &'a IdToDeviceMap {
+    type Item = (DeviceId, &'a Device);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut IdToDeviceMap {
+    type Item = (DeviceId, &'a mut Device);
+    type IntoIter = IterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// The map is serialized as an ID to device map, so the format is the same as
+// for a `HashMap`.
+impl Serialize for IdToDeviceMap {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl JsonSchema for IdToDeviceMap {
+    fn schema_name() -> Cow<'static, str> {
+        "IdToDeviceMap".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        BTreeMap::<DeviceId, Device>::json_schema(generator)
+    }
+}
+
+impl<'de> Deserialize<'de> for IdToDeviceMap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Devices are inserted in ID order to get the same iteration order
+        // on every deserialization.
+        let devices = BTreeMap::<DeviceId, Device>::deserialize(deserializer)?;
+
+        Ok(devices.into_values().collect())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::device::DeviceBuilder;
+    use crate::backend::mathphysics::Point3D;
+
+    use super::*;
+
+
+    fn devices() -> Vec<Device> {
+        (0..5)
+            .map(|_| DeviceBuilder::new().build())
+            .collect()
+    }
+
+
+    #[test]
+    fn getting_devices_by_id() {
+        let devices = devices();
+        let device_map: IdToDeviceMap = devices.iter().cloned().collect();
+
+        assert_eq!(device_map.len(), devices.len());
+
+        for device in &devices {
+            assert!(device_map.contains_key(&device.id()));
+            assert_eq!(
+                device_map.get(&device.id()).map(Device::id),
+                Some(device.id())
+            );
+        }
+    }
+
+    #[test]
+    fn iterating_in_insertion_order() {
+        let devices = devices();
+        let device_map: IdToDeviceMap = devices.iter().cloned().collect();
+
+        let expected_ids: Vec<DeviceId> = devices
+            .iter()
+            .map(Device::id)
+            .collect();
+        let ids: Vec<DeviceId> = device_map.ids().collect();
+
+        assert_eq!(ids, expected_ids);
+    }
+
+    #[test]
+    fn inserting_device_with_same_id_replaces_it() {
+        let device = DeviceBuilder::new().build();
+        let mut device_map = IdToDeviceMap::new();
+
+        assert!(device_map.insert(device.clone()).is_none());
+        assert!(device_map.insert(device).is_some());
+        assert_eq!(device_map.len(), 1);
+    }
+
+    #[test]
+    fn removed_device_slot_is_reused() {
+        let devices = devices();
+        let removed_id = devices[2].id();
+        let mut device_map: IdToDeviceMap = devices.into_iter().collect();
+
+        assert!(device_map.remove(&removed_id).is_some());
+        assert!(device_map.get(&removed_id).is_none());
+        assert!(device_map.remove(&removed_id).is_none());
+
+        let new_device = DeviceBuilder::new().build();
+        let new_id = new_device.id();
+        device_map.insert(new_device);
+
+        let ids: Vec<DeviceId> = device_map.ids().collect();
+
+        assert_eq!(ids[2], new_id);
+    }
+
+    #[test]
+    fn querying_devices_by_predicate_and_distance() {
+        let devices: Vec<Device> = [0.0, 5.0, 10.0, 20.0]
+            .into_iter()
+            .map(|x|
+                DeviceBuilder::new()
+                    .set_real_position(Point3D::new(x, 0.0, 0.0))
+                    .build()
+            )
+            .collect();
+        let device_map: IdToDeviceMap = devices.iter().cloned().collect();
+
+        let nearby_ids: Vec<DeviceId> = device_map
+            .devices_within(&devices[1], Meter::new(5.0))
+            .map(Device::id)
+            .collect();
+        let far_ids: Vec<DeviceId> = device_map
+            .devices_where(|device| device.position().x > 5.0)
+            .map(Device::id)
+            .collect();
+
+        assert_eq!(
+            nearby_ids,
+            vec![devices[0].id(), devices[1].id(), devices[2].id()]
+        );
+        assert_eq!(far_ids, vec![devices[2].id(), devices[3].id()]);
+        assert_eq!(device_map.infected_devices().count(), 0);
+    }
+
+    #[test]
+    fn serialization_roundtrip_keeps_devices() {
+        let device_map: IdToDeviceMap = devices().into_iter().collect();
+
+        let json = serde_json::to_string(&device_map)
+            .unwrap_or_else(|error| panic!("{}", error));
+        let deserialized_map: IdToDeviceMap = serde_json::from_str(&json)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        let mut ids: Vec<DeviceId> = device_map.ids().collect();
+        ids.sort_unstable();
+        let deserialized_ids: Vec<DeviceId> = deserialized_map.ids().collect();
+
+        assert_eq!(deserialized_ids, ids);
+    }
+}
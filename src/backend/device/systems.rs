@@ -1,10 +1,30 @@
+pub use channel::*;
+pub use clock::*;
+pub use firmware::*;
+pub use flocking::*;
+pub use gossip::*;
+pub use integrity::*;
 pub use movement::*;
+pub use navigation::*;
 pub use power::*;
+pub use reliability::*;
 pub use security::*;
+pub use sensor::*;
+pub use thermal::*;
 pub use trx::*;
 
 
+pub mod channel;
+pub mod clock;
+pub mod firmware;
+pub mod flocking;
+pub mod gossip;
+pub mod integrity;
 pub mod movement;
+pub mod navigation;
 pub mod power;
+pub mod reliability;
 pub mod security;
+pub mod sensor;
+pub mod thermal;
 pub mod trx;
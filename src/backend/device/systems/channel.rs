@@ -0,0 +1,179 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::mathphysics::{Frequency, Millisecond};
+use crate::backend::signal::Data;
+
+
+fn default_control_frequency() -> Frequency {
+    Frequency::Control
+}
+
+
+// Keeps track of the frequency the device is controlled on. Noise received
+// on it without decodable data in between for `noise_tolerance` is taken
+// for jamming, so the device switches to the backup frequency. By default
+// there is no backup frequency and the device stays on `Frequency::Control`.
+#[derive(Clone, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct ChannelSystem {
+    backup_frequency: Option<Frequency>,
+    noise_tolerance: Millisecond,
+    #[serde(default = "default_control_frequency")]
+    control_frequency: Frequency,
+    // Time the current noise was first received at.
+    #[serde(default)]
+    noise_start: Option<Millisecond>,
+}
+
+impl Default for ChannelSystem {
+    fn default() -> Self {
+        Self {
+            backup_frequency: None,
+            noise_tolerance: Millisecond::ZERO,
+            control_frequency: Frequency::Control,
+            noise_start: None,
+        }
+    }
+}
+
+impl ChannelSystem {
+    #[must_use]
+    pub fn new(
+        backup_frequency: Frequency,
+        noise_tolerance: Millisecond
+    ) -> Self {
+        Self {
+            backup_frequency: Some(backup_frequency),
+            noise_tolerance,
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn backup_frequency(&self) -> Option<Frequency> {
+        self.backup_frequency
+    }
+
+    #[must_use]
+    pub fn noise_tolerance(&self) -> Millisecond {
+        self.noise_tolerance
+    }
+
+    #[must_use]
+    pub fn control_frequency(&self) -> Frequency {
+        self.control_frequency
+    }
+
+    // `received_data` is the data received on the control frequency at
+    // `time`. Returns the frequency to switch to once the noise lasts long
+    // enough.
+    pub fn detect_jamming(
+        &mut self,
+        received_data: Option<&Data>,
+        time: Millisecond
+    ) -> Option<Frequency> {
+        let backup_frequency = self.backup_frequency?;
+
+        if self.control_frequency == backup_frequency {
+            return None;
+        }
+
+        match received_data {
+            Some(Data::Noise) => (),
+            Some(_)           => {
+                self.noise_start = None;
+                return None;
+            },
+            None              => return None,
+        }
+
+        let noise_start = *self.noise_start.get_or_insert(time);
+
+        (time - noise_start >= self.noise_tolerance)
+            .then_some(backup_frequency)
+    }
+
+    // Peers that switched are only followed to the backup frequency.
+    #[must_use]
+    pub fn follows_switch_to(&self, frequency: Frequency) -> bool {
+        self.backup_frequency == Some(frequency)
+            && self.control_frequency != frequency
+    }
+
+    pub fn switch_to(&mut self, frequency: Frequency) {
+        self.control_frequency = frequency;
+        self.noise_start = None;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::task::Task;
+
+    use super::*;
+
+
+    const NOISE: Option<&Data>         = Some(&Data::Noise);
+    const NOISE_TOLERANCE: Millisecond = Millisecond::new(200);
+
+
+    #[test]
+    fn sustained_noise_switches_to_backup_frequency() {
+        let mut channel_system = ChannelSystem::new(
+            Frequency::Backup,
+            NOISE_TOLERANCE
+        );
+
+        assert_eq!(
+            channel_system.detect_jamming(NOISE, Millisecond::new(0)),
+            None
+        );
+        // Decodable data in between restarts the count, while iterations
+        // without any signal do not.
+        assert_eq!(
+            channel_system.detect_jamming(
+                Some(&Data::SetTask(Task::Undefined)),
+                Millisecond::new(100)
+            ),
+            None
+        );
+        assert_eq!(
+            channel_system.detect_jamming(NOISE, Millisecond::new(150)),
+            None
+        );
+        assert_eq!(
+            channel_system.detect_jamming(None, Millisecond::new(250)),
+            None
+        );
+        assert_eq!(
+            channel_system.detect_jamming(NOISE, Millisecond::new(350)),
+            Some(Frequency::Backup)
+        );
+
+        channel_system.switch_to(Frequency::Backup);
+
+        assert_eq!(channel_system.control_frequency(), Frequency::Backup);
+        assert_eq!(
+            channel_system.detect_jamming(NOISE, Millisecond::new(1000)),
+            None
+        );
+        assert!(!channel_system.follows_switch_to(Frequency::Backup));
+    }
+
+    #[test]
+    fn default_channel_system_never_switches() {
+        let mut channel_system = ChannelSystem::default();
+
+        assert_eq!(
+            channel_system.detect_jamming(NOISE, Millisecond::new(0)),
+            None
+        );
+        assert_eq!(
+            channel_system.detect_jamming(NOISE, Millisecond::new(10_000)),
+            None
+        );
+        assert!(!channel_system.follows_switch_to(Frequency::Backup));
+        assert_eq!(channel_system.control_frequency(), Frequency::Control);
+    }
+}
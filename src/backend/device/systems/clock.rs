@@ -0,0 +1,174 @@
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::ITERATION_TIME;
+use crate::backend::mathphysics::{millis_to_secs, Millisecond};
+use crate::backend::random;
+
+
+// Local clock of the device. It gains `drift` milliseconds per second (loses
+// them if the drift is negative) and is set to the time of received beacons.
+// The drift is drawn from [-max_drift, max_drift] when the device is built,
+// so devices built from one template drift apart. A device with
+// `beacon_period` sends beacons with its local time. By default the clock
+// keeps the model time and no beacons are sent.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct ClockSystem {
+    max_drift: f32,
+    beacon_period: Millisecond,
+    #[serde(default)]
+    drift: f32,
+    // Local time minus model time in milliseconds.
+    #[serde(default)]
+    offset: f32,
+    // Time of the last beacon the clock was set to.
+    #[serde(default)]
+    last_beacon_time: Option<Millisecond>,
+}
+
+impl ClockSystem {
+    #[must_use]
+    pub fn new(max_drift: f32, beacon_period: Millisecond) -> Self {
+        Self {
+            max_drift,
+            beacon_period,
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn max_drift(&self) -> f32 {
+        self.max_drift
+    }
+
+    #[must_use]
+    pub fn drift(&self) -> f32 {
+        self.drift
+    }
+
+    pub fn draw_drift(&mut self) {
+        if self.max_drift > 0.0 {
+            self.drift = random::with_rng(|rng|
+                rng.random_range(-self.max_drift..=self.max_drift)
+            );
+        }
+    }
+
+    #[must_use]
+    pub fn beacon_period(&self) -> Millisecond {
+        self.beacon_period
+    }
+
+    #[must_use]
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn local_time(&self, model_time: Millisecond) -> Millisecond {
+        model_time + Millisecond::new(self.offset.round() as i32)
+    }
+
+    pub fn advance(&mut self, elapsed: Millisecond) {
+        self.offset += self.drift * millis_to_secs(elapsed);
+    }
+
+    // The clock is read once per iteration, so the beacon is sent on the
+    // first iteration of each period.
+    #[must_use]
+    pub fn sends_beacon_at(&self, local_time: Millisecond) -> bool {
+        self.beacon_period > Millisecond::ZERO
+            && local_time % self.beacon_period < ITERATION_TIME
+    }
+
+    // Beacons not newer than the last one are replayed or reordered, so they
+    // are ignored. Returns `true` if the clock was set.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn synchronize(
+        &mut self,
+        beacon_time: Millisecond,
+        model_time: Millisecond
+    ) -> bool {
+        if self.last_beacon_time.is_some_and(|last_beacon_time|
+            beacon_time <= last_beacon_time
+        ) {
+            return false;
+        }
+
+        self.offset = (beacon_time - model_time).value() as f32;
+        self.last_beacon_time = Some(beacon_time);
+
+        true
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    const MAX_DRIFT: f32 = 10.0;
+
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn drifting_clock_is_set_by_beacons() {
+        let mut clock_system = ClockSystem::new(MAX_DRIFT, Millisecond::ZERO);
+
+        clock_system.draw_drift();
+
+        assert!(clock_system.drift().abs() <= MAX_DRIFT);
+
+        for _ in 0..20 {
+            clock_system.advance(ITERATION_TIME);
+        }
+
+        let model_time = Millisecond::new(1000);
+
+        assert_eq!(
+            clock_system.local_time(model_time),
+            model_time + Millisecond::new(clock_system.drift().round() as i32)
+        );
+        assert!(clock_system.synchronize(model_time, model_time));
+        assert_eq!(clock_system.local_time(model_time), model_time);
+    }
+
+    #[test]
+    fn replayed_beacon_is_ignored() {
+        let mut clock_system = ClockSystem::default();
+
+        assert!(
+            clock_system.synchronize(
+                Millisecond::new(500),
+                Millisecond::new(500)
+            )
+        );
+        assert!(
+            !clock_system.synchronize(
+                Millisecond::new(500),
+                Millisecond::new(1000)
+            )
+        );
+        assert_eq!(
+            clock_system.local_time(Millisecond::new(1000)),
+            Millisecond::new(1000)
+        );
+    }
+
+    #[test]
+    fn default_clock_keeps_model_time_without_beacons() {
+        let mut clock_system = ClockSystem::default();
+
+        clock_system.draw_drift();
+        clock_system.advance(Millisecond::new(1000));
+
+        assert_eq!(
+            clock_system.local_time(Millisecond::new(1000)),
+            Millisecond::new(1000)
+        );
+        assert!(!clock_system.sends_beacon_at(Millisecond::ZERO));
+    }
+}
@@ -0,0 +1,245 @@
+use std::fmt;
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::backend::mathphysics::{Millisecond, Watt};
+
+
+pub type FirmwareVersion = u32;
+
+
+const VERSION_RANGE_DELIMITER: &str = "..";
+
+
+#[derive(Debug, Error)]
+pub enum VersionRangeParseError {
+    #[error("Expected `<first>..<last>`")]
+    MissingDelimiter,
+    #[error("Failed to parse firmware version")]
+    FailedToParse,
+    #[error("First version is greater than the last one")]
+    Empty,
+}
+
+
+// Firmware versions from `first` to `last` inclusive.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, PartialEq, JsonSchema, Serialize, Deserialize
+)]
+pub struct VersionRange {
+    first: FirmwareVersion,
+    last: FirmwareVersion,
+}
+
+impl VersionRange {
+    #[must_use]
+    pub fn new(first: FirmwareVersion, last: FirmwareVersion) -> Self {
+        Self { first, last }
+    }
+
+    #[must_use]
+    pub fn first(&self) -> FirmwareVersion {
+        self.first
+    }
+
+    #[must_use]
+    pub fn last(&self) -> FirmwareVersion {
+        self.last
+    }
+
+    #[must_use]
+    pub fn contains(&self, version: FirmwareVersion) -> bool {
+        (self.first..=self.last).contains(&version)
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.first, VERSION_RANGE_DELIMITER, self.last)
+    }
+}
+
+impl FromStr for VersionRange {
+    type Err = VersionRangeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, last) = s
+            .split_once(VERSION_RANGE_DELIMITER)
+            .ok_or(VersionRangeParseError::MissingDelimiter)?;
+        let first: FirmwareVersion = first
+            .parse()
+            .map_err(|_| VersionRangeParseError::FailedToParse)?;
+        let last: FirmwareVersion = last
+            .parse()
+            .map_err(|_| VersionRangeParseError::FailedToParse)?;
+
+        if first > last {
+            return Err(VersionRangeParseError::Empty);
+        }
+
+        Ok(Self::new(first, last))
+    }
+}
+
+
+// Over-the-air update to `version`. Installing it takes `install_time`,
+// during which the device draws `install_power`.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct FirmwareUpdate {
+    version: FirmwareVersion,
+    install_time: Millisecond,
+    install_power: Watt,
+}
+
+impl FirmwareUpdate {
+    #[must_use]
+    pub fn new(
+        version: FirmwareVersion,
+        install_time: Millisecond,
+        install_power: Watt
+    ) -> Self {
+        Self { version, install_time, install_power }
+    }
+
+    #[must_use]
+    pub fn version(&self) -> FirmwareVersion {
+        self.version
+    }
+
+    #[must_use]
+    pub fn install_time(&self) -> Millisecond {
+        self.install_time
+    }
+
+    #[must_use]
+    pub fn install_power(&self) -> Watt {
+        self.install_power
+    }
+}
+
+
+// Firmware the device runs. A device with a rollout pushes the update to
+// the network from the rollout time on. Updates not newer than the running
+// or the installed firmware are ignored.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct FirmwareSystem {
+    version: FirmwareVersion,
+    rollout: Option<(Millisecond, FirmwareUpdate)>,
+    // Update being installed with the time left to install it.
+    #[serde(default)]
+    installing: Option<(FirmwareUpdate, Millisecond)>,
+}
+
+impl FirmwareSystem {
+    #[must_use]
+    pub fn new(
+        version: FirmwareVersion,
+        rollout: Option<(Millisecond, FirmwareUpdate)>
+    ) -> Self {
+        Self {
+            version,
+            rollout,
+            installing: None,
+        }
+    }
+
+    #[must_use]
+    pub fn version(&self) -> FirmwareVersion {
+        self.version
+    }
+
+    #[must_use]
+    pub fn rollout(&self) -> Option<&(Millisecond, FirmwareUpdate)> {
+        self.rollout.as_ref()
+    }
+
+    #[must_use]
+    pub fn installing(&self) -> Option<&FirmwareUpdate> {
+        self.installing.as_ref().map(|(update, _)| update)
+    }
+
+    #[must_use]
+    pub fn update_at(&self, time: Millisecond) -> Option<FirmwareUpdate> {
+        self.rollout
+            .filter(|(rollout_time, _)| time >= *rollout_time)
+            .map(|(_, update)| update)
+    }
+
+    // Returns `true` if the installation was started.
+    pub fn start_install(&mut self, update: FirmwareUpdate) -> bool {
+        let newest_version = self.installing()
+            .map_or(self.version, FirmwareUpdate::version);
+
+        if update.version <= newest_version {
+            return false;
+        }
+
+        self.installing = Some((update, update.install_time));
+
+        true
+    }
+
+    // Returns the installed version once the installation is finished.
+    pub fn advance(&mut self, elapsed: Millisecond) -> Option<FirmwareVersion> {
+        let (update, time_left) = self.installing.as_mut()?;
+
+        *time_left -= elapsed;
+
+        if *time_left > Millisecond::ZERO {
+            return None;
+        }
+
+        self.version = update.version;
+        self.installing = None;
+
+        Some(self.version)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    fn update(version: FirmwareVersion) -> FirmwareUpdate {
+        FirmwareUpdate::new(version, Millisecond::new(200), Watt::new(5.0))
+    }
+
+
+    #[test]
+    fn update_is_installed_after_install_time() {
+        let mut firmware_system = FirmwareSystem::new(1, None);
+
+        assert!(firmware_system.start_install(update(2)));
+        assert_eq!(firmware_system.advance(Millisecond::new(100)), None);
+        assert_eq!(firmware_system.version(), 1);
+        assert_eq!(firmware_system.advance(Millisecond::new(100)), Some(2));
+        assert_eq!(firmware_system.version(), 2);
+        assert!(firmware_system.installing().is_none());
+    }
+
+    #[test]
+    fn older_updates_are_ignored() {
+        let mut firmware_system = FirmwareSystem::new(2, None);
+
+        assert!(!firmware_system.start_install(update(2)));
+        assert!(firmware_system.start_install(update(4)));
+        assert!(!firmware_system.start_install(update(3)));
+        assert_eq!(firmware_system.installing(), Some(&update(4)));
+    }
+
+    #[test]
+    fn version_range_is_parsed() {
+        let version_range: VersionRange = "1..3".parse().unwrap();
+
+        assert!(version_range.contains(1));
+        assert!(version_range.contains(3));
+        assert!(!version_range.contains(4));
+        assert_eq!(version_range.to_string(), "1..3");
+        assert!("3..1".parse::<VersionRange>().is_err());
+    }
+}
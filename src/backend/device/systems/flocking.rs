@@ -0,0 +1,160 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::mathphysics::{
+    Float, Meter, Point3D, Position, Vector3D
+};
+
+
+// Position and velocity of a neighbor device.
+pub type Neighbor = (Point3D, Vector3D);
+
+
+// By default the system has no neighbor radius, so it never steers.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct FlockingSystem {
+    neighbor_radius: Meter,
+    separation_weight: f32,
+    alignment_weight: f32,
+    cohesion_weight: f32,
+    // Velocity correction found from the last known neighbors.
+    #[serde(default)]
+    steering: Vector3D,
+}
+
+impl FlockingSystem {
+    #[must_use]
+    pub fn new(
+        neighbor_radius: Meter,
+        separation_weight: f32,
+        alignment_weight: f32,
+        cohesion_weight: f32,
+    ) -> Self {
+        Self {
+            neighbor_radius,
+            separation_weight,
+            alignment_weight,
+            cohesion_weight,
+            steering: Vector3D::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.neighbor_radius > Meter::ZERO
+    }
+
+    #[must_use]
+    pub fn neighbor_radius(&self) -> Meter {
+        self.neighbor_radius
+    }
+
+    #[must_use]
+    pub fn steering(&self) -> &Vector3D {
+        &self.steering
+    }
+
+    // Separation pushes the device away from close neighbors, alignment
+    // matches their average velocity and cohesion pulls the device to their
+    // center.
+    pub fn steer(
+        &mut self,
+        position: &Point3D,
+        velocity: &Vector3D,
+        neighbors: &[Neighbor]
+    ) {
+        self.steering = Vector3D::default();
+
+        let neighbors: Vec<&Neighbor> = neighbors
+            .iter()
+            .filter(|(neighbor_position, _)| {
+                let distance = position.distance_to(neighbor_position);
+
+                distance > Meter::ZERO && distance <= self.neighbor_radius
+            })
+            .collect();
+
+        if neighbors.is_empty() {
+            return;
+        }
+
+        let neighbor_count = neighbors.len() as Float;
+        let mut separation = Point3D::default();
+        let mut average_velocity = Point3D::default();
+        let mut center = Point3D::default();
+
+        for (neighbor_position, neighbor_velocity) in neighbors {
+            let away = *position - *neighbor_position;
+            let distance = Vector3D::new(*neighbor_position, *position).size();
+
+            // Closer neighbors push harder.
+            separation = separation + away / distance.powi(2);
+            average_velocity = average_velocity
+                + neighbor_velocity.displacement();
+            center = center + *neighbor_position;
+        }
+
+        let alignment = average_velocity / neighbor_count
+            - velocity.displacement();
+        let cohesion = center / neighbor_count - *position;
+
+        self.steering = Vector3D::new(
+            Point3D::default(),
+            separation * Float::from(self.separation_weight)
+                + alignment * Float::from(self.alignment_weight)
+                + cohesion * Float::from(self.cohesion_weight)
+        );
+    }
+
+    #[must_use]
+    pub fn apply(&self, velocity: &Vector3D) -> Vector3D {
+        Vector3D::new(
+            velocity.initial_point,
+            velocity.terminal_point + self.steering.displacement()
+        )
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    fn flocking_system() -> FlockingSystem {
+        FlockingSystem::new(Meter::new(10.0), 50.0, 0.0, 0.0)
+    }
+
+
+    #[test]
+    fn default_flocking_system_does_not_steer() {
+        let mut flocking_system = FlockingSystem::default();
+
+        flocking_system.steer(
+            &Point3D::default(),
+            &Vector3D::default(),
+            &[(Point3D::new(1.0, 0.0, 0.0), Vector3D::default())]
+        );
+
+        assert!(!flocking_system.is_enabled());
+        assert_eq!(*flocking_system.steering(), Vector3D::default());
+    }
+
+    #[test]
+    fn close_neighbors_push_device_away() {
+        let mut flocking_system = flocking_system();
+
+        flocking_system.steer(
+            &Point3D::default(),
+            &Vector3D::default(),
+            &[
+                (Point3D::new(2.0, 0.0, 0.0), Vector3D::default()),
+                // Out of the neighbor radius.
+                (Point3D::new(-20.0, 0.0, 0.0), Vector3D::default()),
+            ]
+        );
+
+        assert!(flocking_system.steering().displacement().x < 0.0);
+        assert_eq!(flocking_system.steering().displacement().y, 0.0);
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::ITERATION_TIME;
+use crate::backend::device::DeviceId;
+use crate::backend::mathphysics::Millisecond;
+use crate::backend::random;
+use crate::backend::task::Task;
+
+
+// A fact about the network spread by gossip. `time` is when the fact was
+// first heard of, so newer leaders and tasks replace older ones.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub enum Rumor {
+    Infection { device_id: DeviceId, time: Millisecond },
+    Leader { leader_id: DeviceId, time: Millisecond },
+    Task { task: Task, time: Millisecond },
+}
+
+
+// Summary of what the device knows about the network. Every `period` the
+// device sends an entry of the summary to `fanout` random peers. A radio
+// receives one signal per frequency at a time, so each exchange carries a
+// single random entry instead of the whole summary. By default the device
+// does not gossip, because its period is 0.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct GossipSystem {
+    period: Millisecond,
+    fanout: usize,
+    // Devices known to be infected with the earliest known infection time.
+    #[serde(default)]
+    infections: BTreeMap<DeviceId, Millisecond>,
+    #[serde(default)]
+    leader: Option<(DeviceId, Millisecond)>,
+    #[serde(default)]
+    task: Option<(Task, Millisecond)>,
+}
+
+impl GossipSystem {
+    #[must_use]
+    pub fn new(period: Millisecond, fanout: usize) -> Self {
+        Self {
+            period,
+            fanout,
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn period(&self) -> Millisecond {
+        self.period
+    }
+
+    #[must_use]
+    pub fn fanout(&self) -> usize {
+        self.fanout
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.period > Millisecond::ZERO && self.fanout > 0
+    }
+
+    #[must_use]
+    pub fn exchanges_at(&self, time: Millisecond) -> bool {
+        self.is_enabled() && time % self.period < ITERATION_TIME
+    }
+
+    #[must_use]
+    pub fn infections(&self) -> &BTreeMap<DeviceId, Millisecond> {
+        &self.infections
+    }
+
+    #[must_use]
+    pub fn leader_id(&self) -> Option<DeviceId> {
+        self.leader.map(|(leader_id, _)| leader_id)
+    }
+
+    #[must_use]
+    pub fn task(&self) -> Option<&Task> {
+        self.task.as_ref().map(|(task, _)| task)
+    }
+
+    #[must_use]
+    pub fn summary(&self) -> Vec<Rumor> {
+        let infections = self.infections
+            .iter()
+            .map(|(device_id, time)|
+                Rumor::Infection { device_id: *device_id, time: *time }
+            );
+        let leader = self.leader
+            .map(|(leader_id, time)| Rumor::Leader { leader_id, time });
+        let task = self.task
+            .map(|(task, time)| Rumor::Task { task, time });
+
+        infections
+            .chain(leader)
+            .chain(task)
+            .collect()
+    }
+
+    #[must_use]
+    pub fn random_rumor(&self) -> Option<Rumor> {
+        let summary = self.summary();
+
+        if summary.is_empty() {
+            return None;
+        }
+
+        let index = random::with_rng(|rng| rng.random_range(0..summary.len()));
+
+        Some(summary[index])
+    }
+
+    // Returns `true` if the rumor was news to the device.
+    pub fn learn(&mut self, rumor: Rumor) -> bool {
+        match rumor {
+            Rumor::Infection { device_id, time } =>
+                match self.infections.entry(device_id) {
+                    Entry::Vacant(entry)       => {
+                        entry.insert(time);
+                        true
+                    },
+                    Entry::Occupied(mut entry) => {
+                        if time < *entry.get() {
+                            entry.insert(time);
+                        }
+                        false
+                    },
+                },
+            Rumor::Leader { leader_id, time }    =>
+                learn_newer(&mut self.leader, leader_id, time),
+            Rumor::Task { task, time }           =>
+                learn_newer(&mut self.task, task, time),
+        }
+    }
+}
+
+
+// A repeated fact keeps the time it was first heard at.
+fn learn_newer<T: PartialEq>(
+    known: &mut Option<(T, Millisecond)>,
+    fact: T,
+    time: Millisecond
+) -> bool {
+    match known {
+        Some((known_fact, known_time))
+            if *known_fact == fact || *known_time >= time => false,
+        _ => {
+            *known = Some((fact, time));
+            true
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    const SOME_DEVICE_ID: DeviceId = 5;
+
+
+    #[test]
+    fn newer_leader_replaces_older_one() {
+        let mut gossip_system = GossipSystem::default();
+
+        assert!(gossip_system.learn(
+            Rumor::Leader { leader_id: 1, time: Millisecond::new(100) }
+        ));
+        assert!(!gossip_system.learn(
+            Rumor::Leader { leader_id: 2, time: Millisecond::new(50) }
+        ));
+        assert!(!gossip_system.learn(
+            Rumor::Leader { leader_id: 1, time: Millisecond::new(200) }
+        ));
+        assert!(gossip_system.learn(
+            Rumor::Leader { leader_id: 2, time: Millisecond::new(150) }
+        ));
+        assert_eq!(gossip_system.leader_id(), Some(2));
+    }
+
+    #[test]
+    fn infections_are_learned_once() {
+        let mut gossip_system = GossipSystem::default();
+        let infection = Rumor::Infection {
+            device_id: SOME_DEVICE_ID,
+            time: Millisecond::new(100)
+        };
+        let earlier_infection = Rumor::Infection {
+            device_id: SOME_DEVICE_ID,
+            time: Millisecond::new(50)
+        };
+
+        assert!(gossip_system.learn(infection));
+        assert!(!gossip_system.learn(infection));
+        assert!(!gossip_system.learn(earlier_infection));
+        assert_eq!(gossip_system.summary(), [earlier_infection]);
+    }
+
+    #[test]
+    fn default_gossip_system_never_exchanges() {
+        let gossip_system = GossipSystem::default();
+
+        assert!(!gossip_system.exchanges_at(Millisecond::ZERO));
+        assert!(gossip_system.random_rumor().is_none());
+    }
+}
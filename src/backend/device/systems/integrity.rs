@@ -0,0 +1,161 @@
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::device::systems::ReconRecord;
+use crate::backend::mathphysics::{Float, Point3D, Vector3D};
+use crate::backend::random;
+use crate::backend::signal::{
+    Data, SignalStrength, MAX_BLACK_SIGNAL_STRENGTH, MAX_RED_SIGNAL_STRENGTH,
+    MAX_YELLOW_SIGNAL_STRENGTH
+};
+use crate::backend::task::Task;
+
+
+const CORRUPT_GREEN_SIGNAL: f64  = 0.0;
+const CORRUPT_YELLOW_SIGNAL: f64 = 0.02;
+const CORRUPT_RED_SIGNAL: f64    = 0.1;
+const CORRUPT_BLACK_SIGNAL: f64  = 0.3;
+
+// Largest error of a corrupted coordinate in meters.
+const MAX_COORDINATE_ERROR: Float = 50.0;
+
+
+fn corruption_probability(signal_strength: SignalStrength) -> f64 {
+    if signal_strength > MAX_YELLOW_SIGNAL_STRENGTH {
+        CORRUPT_GREEN_SIGNAL
+    } else if signal_strength > MAX_RED_SIGNAL_STRENGTH {
+        CORRUPT_YELLOW_SIGNAL
+    } else if signal_strength > MAX_BLACK_SIGNAL_STRENGTH {
+        CORRUPT_RED_SIGNAL
+    } else {
+        CORRUPT_BLACK_SIGNAL
+    }
+}
+
+fn corrupt_point(point: Point3D) -> Point3D {
+    let error = || random::with_rng(|rng|
+        rng.random_range(-MAX_COORDINATE_ERROR..=MAX_COORDINATE_ERROR)
+    );
+
+    Point3D::new(point.x + error(), point.y + error(), point.z)
+}
+
+fn corrupt_record(record: &ReconRecord) -> ReconRecord {
+    record.with_target_position(corrupt_point(*record.target_position()))
+}
+
+fn corrupt_task(task: Task) -> Task {
+    match task {
+        Task::Attack(point)           => Task::Attack(corrupt_point(point)),
+        Task::DropPayload(point)      =>
+            Task::DropPayload(corrupt_point(point)),
+        Task::KeepFormation(velocity) => Task::KeepFormation(
+            Vector3D {
+                terminal_point: corrupt_point(velocity.terminal_point),
+                ..velocity
+            }
+        ),
+        Task::Reconnect(point)        => Task::Reconnect(corrupt_point(point)),
+        Task::Reposition(point)       =>
+            Task::Reposition(corrupt_point(point)),
+        Task::Undefined               => Task::Undefined,
+    }
+}
+
+
+// Received data is corrupted by bit errors with a probability that grows as
+// the signal weakens. A device checking integrity discards corrupted data.
+// Otherwise corrupted positions are accepted subtly wrong and the rest of
+// the data cannot be decoded. By default data is never corrupted.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct IntegritySystem {
+    bit_errors: bool,
+    checks_integrity: bool,
+}
+
+impl IntegritySystem {
+    #[must_use]
+    pub fn new(bit_errors: bool, checks_integrity: bool) -> Self {
+        Self { bit_errors, checks_integrity }
+    }
+
+    #[must_use]
+    pub fn has_bit_errors(&self) -> bool {
+        self.bit_errors
+    }
+
+    #[must_use]
+    pub fn checks_integrity(&self) -> bool {
+        self.checks_integrity
+    }
+
+    #[must_use]
+    pub fn is_corrupted(&self, signal_strength: SignalStrength) -> bool {
+        self.bit_errors
+            && random::random_bool(corruption_probability(signal_strength))
+    }
+
+    // Returns the data as the device decodes it or `None` if the corrupted
+    // data is unusable.
+    #[must_use]
+    pub fn corrupt(&self, data: &Data) -> Option<Data> {
+        if self.checks_integrity {
+            return None;
+        }
+
+        match data {
+            Data::GPS(position) => Some(Data::GPS(corrupt_point(*position))),
+            Data::Recon(record) => Some(Data::Recon(corrupt_record(record))),
+            Data::ReconSummary(summary) => Some(
+                Data::ReconSummary(
+                    summary.with_latest(corrupt_record(summary.latest()))
+                )
+            ),
+            Data::SetTask(task) => Some(Data::SetTask(corrupt_task(*task))),
+            _                   => None,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::backend::signal::GREEN_SIGNAL_STRENGTH;
+
+
+    #[test]
+    fn green_signals_are_never_corrupted() {
+        let integrity_system = IntegritySystem::new(true, false);
+
+        for _ in 0..1000 {
+            assert!(!integrity_system.is_corrupted(GREEN_SIGNAL_STRENGTH));
+        }
+    }
+
+    #[test]
+    fn checked_data_is_discarded() {
+        let integrity_system = IntegritySystem::new(true, true);
+        let task = Task::Reposition(Point3D::new(10.0, 10.0, 5.0));
+
+        assert_eq!(integrity_system.corrupt(&Data::SetTask(task)), None);
+    }
+
+    #[test]
+    fn unchecked_task_is_subtly_wrong() {
+        let integrity_system = IntegritySystem::new(true, false);
+        let point = Point3D::new(10.0, 10.0, 5.0);
+        let Some(Data::SetTask(Task::Reposition(corrupted_point))) =
+            integrity_system.corrupt(&Data::SetTask(Task::Reposition(point)))
+        else {
+            panic!("Corrupted task is not decoded");
+        };
+
+        assert!((corrupted_point.x - point.x).abs() <= MAX_COORDINATE_ERROR);
+        assert!((corrupted_point.y - point.y).abs() <= MAX_COORDINATE_ERROR);
+        assert_eq!(corrupted_point.z, point.z);
+        assert_eq!(integrity_system.corrupt(&Data::Noise), None);
+    }
+}
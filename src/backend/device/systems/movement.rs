@@ -1,7 +1,12 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::backend::mathphysics::{MeterPerSecond, Point3D, Vector3D};
+use crate::backend::ITERATION_TIME;
+use crate::backend::mathphysics::{
+    millis_to_secs, wrap_angle, Float, MeterPerSecond, Point3D, 
+    RadianPerSecond, Vector3D
+};
 
 
 #[derive(Error, Debug)]
@@ -11,12 +16,56 @@ pub enum MovementSystemBuildError {
 }
 
 
+// Limits of velocity change per second. Limits equal to 0.0 are not applied,
+// so by default the velocity changes instantly.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize
+)]
+pub struct MovementLimits {
+    max_turn_rate: RadianPerSecond,
+    max_climb_rate: MeterPerSecond,
+    max_descent_rate: MeterPerSecond,
+}
+
+impl MovementLimits {
+    #[must_use]
+    pub fn new(
+        max_turn_rate: RadianPerSecond,
+        max_climb_rate: MeterPerSecond,
+        max_descent_rate: MeterPerSecond,
+    ) -> Self {
+        Self {
+            max_turn_rate: max_turn_rate.max(0.0),
+            max_climb_rate: max_climb_rate.max(0.0),
+            max_descent_rate: max_descent_rate.max(0.0),
+        }
+    }
+
+    #[must_use]
+    pub fn max_turn_rate(&self) -> RadianPerSecond {
+        self.max_turn_rate
+    }
+
+    #[must_use]
+    pub fn max_climb_rate(&self) -> MeterPerSecond {
+        self.max_climb_rate
+    }
+
+    #[must_use]
+    pub fn max_descent_rate(&self) -> MeterPerSecond {
+        self.max_descent_rate
+    }
+}
+
+
 // By default the system can not move, because its maximum speed is 0.0.
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
 pub struct MovementSystem {
     position_in_meters: Point3D,
     max_speed: MeterPerSecond,
     velocity_in_mps: Vector3D,
+    #[serde(default)]
+    limits: MovementLimits,
 }
 
 impl MovementSystem {
@@ -35,7 +84,8 @@ impl MovementSystem {
             // The position should be provided by GPS (from TRXSystem).
             position_in_meters: Point3D::default(),
             max_speed,
-            velocity_in_mps: Vector3D::default()
+            velocity_in_mps: Vector3D::default(),
+            limits: MovementLimits::default(),
         };
 
         Ok(movement_system)
@@ -56,11 +106,42 @@ impl MovementSystem {
         &self.velocity_in_mps
     }
 
+    #[must_use]
+    pub fn limits(&self) -> &MovementLimits {
+        &self.limits
+    }
+
     #[must_use]
     pub fn is_disabled(&self) -> bool {
         self.max_speed == 0.0
     }
     
+    // The system keeps its position, but can not move anymore.
+    pub fn disable(&mut self) {
+        self.max_speed = 0.0;
+        self.velocity_in_mps = Vector3D::default();
+    }
+
+    pub fn set_limits(&mut self, limits: MovementLimits) {
+        self.limits = limits;
+    }
+
+    // A disabled system stays disabled.
+    pub fn set_max_speed(&mut self, max_speed: MeterPerSecond) {
+        if self.is_disabled() {
+            return;
+        }
+
+        self.max_speed = max_speed.max(0.0);
+        self.velocity_in_mps.truncate(self.max_speed);
+    }
+
+    // Damaged motors can not reach the previous max speed.
+    pub fn scale_max_speed(&mut self, factor: f32) {
+        self.max_speed *= Float::from(factor.clamp(0.0, 1.0));
+        self.velocity_in_mps.truncate(self.max_speed);
+    }
+
     pub fn set_position(&mut self, position_in_meters: Point3D) {
         self.position_in_meters = position_in_meters;
     }
@@ -86,6 +167,42 @@ impl MovementSystem {
         
         self.velocity_in_mps.scale_to(self.max_speed);
     }
+
+    // Velocity may be set several times during an iteration, so the limits 
+    // are applied once to the change from the velocity of the previous 
+    // iteration. A hovering system can start moving in any direction.
+    pub fn limit_velocity_change(&mut self, previous_velocity: &Vector3D) {
+        let iteration_time = millis_to_secs(ITERATION_TIME);
+        let previous = previous_velocity.displacement();
+        let mut current = self.velocity_in_mps.displacement();
+        let previous_horizontal_speed = previous.x.hypot(previous.y);
+        let horizontal_speed = current.x.hypot(current.y);
+
+        if self.limits.max_turn_rate > 0.0 
+            && previous_horizontal_speed > 0.0 
+            && horizontal_speed > 0.0
+        {
+            let max_heading_change = self.limits.max_turn_rate 
+                * Float::from(iteration_time);
+            let previous_heading = previous.y.atan2(previous.x);
+            let heading_change = wrap_angle(
+                current.y.atan2(current.x) - previous_heading
+            ).clamp(-max_heading_change, max_heading_change);
+            let heading = previous_heading + heading_change;
+
+            current.x = horizontal_speed * heading.cos();
+            current.y = horizontal_speed * heading.sin();
+        }
+        if self.limits.max_climb_rate > 0.0 {
+            current.z = current.z.min(self.limits.max_climb_rate);
+        }
+        if self.limits.max_descent_rate > 0.0 {
+            current.z = current.z.max(-self.limits.max_descent_rate);
+        }
+
+        self.velocity_in_mps.terminal_point = self.velocity_in_mps
+            .initial_point + current;
+    }
 }
 
 
@@ -139,4 +256,53 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn turn_rate_limits_heading_change() {
+        let mut movement_system = MovementSystem::build(10.0).unwrap();
+        // 1 radian per second is 0.05 radians per iteration.
+        movement_system.set_limits(MovementLimits::new(1.0, 0.0, 0.0));
+
+        let previous_velocity = Vector3D::new(
+            Point3D::default(), 
+            Point3D::new(10.0, 0.0, 0.0)
+        );
+        movement_system.set_velocity(previous_velocity);
+        movement_system.set_direction(Point3D::new(0.0, 100.0, 0.0));
+        movement_system.limit_velocity_change(&previous_velocity);
+
+        let velocity = movement_system.velocity().displacement();
+        let heading = velocity.y.atan2(velocity.x);
+
+        assert!((heading - 0.05).abs() < 1e-4);
+        assert!((movement_system.velocity().size() - 10.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn climb_and_descent_rates_are_limited() {
+        let mut movement_system = MovementSystem::build(10.0).unwrap();
+        movement_system.set_limits(MovementLimits::new(0.0, 2.0, 1.0));
+
+        movement_system.set_direction(Point3D::new(0.0, 0.0, 100.0));
+        movement_system.limit_velocity_change(&Vector3D::default());
+        assert_eq!(movement_system.velocity().displacement().z, 2.0);
+
+        movement_system.set_direction(Point3D::new(0.0, 0.0, -100.0));
+        movement_system.limit_velocity_change(&Vector3D::default());
+        assert_eq!(movement_system.velocity().displacement().z, -1.0);
+    }
+
+    #[test]
+    fn hovering_system_turns_instantly() {
+        let mut movement_system = MovementSystem::build(10.0).unwrap();
+        movement_system.set_limits(MovementLimits::new(1.0, 0.0, 0.0));
+
+        movement_system.set_direction(Point3D::new(0.0, 100.0, 0.0));
+        movement_system.limit_velocity_change(&Vector3D::default());
+
+        let velocity = movement_system.velocity().displacement();
+
+        assert!(velocity.x.abs() < 1e-4);
+        assert!((velocity.y - 10.0).abs() < 1e-4);
+    }
 }
@@ -0,0 +1,248 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::device::NeighborView;
+use crate::backend::mathphysics::{
+    equation_of_motion_3d, Float, Meter, Point3D, Position, Second, Vector3D
+};
+
+
+// Why a GPS fix looks spoofed.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, JsonSchema, Serialize, Deserialize
+)]
+pub enum SpoofingEvidence {
+    // The fix is too far from the dead-reckoned position.
+    Jump,
+    // Ranges to neighbors computed from the fix do not match the measured
+    // ones.
+    RangeMismatch,
+    // Fixes received on different GPS bands do not match.
+    BandMismatch,
+}
+
+
+// Dead reckons the position from the last plausible GPS fix and checks new
+// fixes against it and against ranges measured to neighbors, which report
+// their positions. Fixes that look spoofed are flagged and, if the system
+// rejects them, replaced by the dead-reckoned position. By default the
+// system checks nothing, because its thresholds are 0.0.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct NavigationSystem {
+    max_jump: Meter,
+    // Largest mean difference of ranges to neighbors.
+    max_range_error: Meter,
+    rejects_spoofed_fixes: bool,
+    #[serde(default)]
+    estimated_position: Option<Point3D>,
+}
+
+impl NavigationSystem {
+    #[must_use]
+    pub fn new(
+        max_jump: Meter,
+        max_range_error: Meter,
+        rejects_spoofed_fixes: bool
+    ) -> Self {
+        Self {
+            max_jump,
+            max_range_error,
+            rejects_spoofed_fixes,
+            estimated_position: None,
+        }
+    }
+
+    #[must_use]
+    pub fn max_jump(&self) -> Meter {
+        self.max_jump
+    }
+
+    #[must_use]
+    pub fn max_range_error(&self) -> Meter {
+        self.max_range_error
+    }
+
+    #[must_use]
+    pub fn rejects_spoofed_fixes(&self) -> bool {
+        self.rejects_spoofed_fixes
+    }
+
+    // Unknown until the first plausible fix is received.
+    #[must_use]
+    pub fn estimated_position(&self) -> Option<&Point3D> {
+        self.estimated_position.as_ref()
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.max_jump > Meter::ZERO || self.max_range_error > Meter::ZERO
+    }
+
+    #[must_use]
+    pub fn check_fix(
+        &self,
+        fix: &Point3D,
+        neighbors: &[NeighborView]
+    ) -> Option<SpoofingEvidence> {
+        if self.is_jump(fix) {
+            return Some(SpoofingEvidence::Jump);
+        }
+        if self.mismatches_ranges(fix, neighbors) {
+            return Some(SpoofingEvidence::RangeMismatch);
+        }
+
+        None
+    }
+
+    // Only plausible fixes are followed, so a spoofed fix that was not
+    // rejected does not hide the following ones. A disabled system keeps no
+    // estimate.
+    pub fn accept_fix(&mut self, fix: Point3D) {
+        if self.is_enabled() {
+            self.estimated_position = Some(fix);
+        }
+    }
+
+    pub fn dead_reckon(&mut self, velocity: &Vector3D, time: Second) {
+        if let Some(estimated_position) = self.estimated_position.as_mut() {
+            *estimated_position = equation_of_motion_3d(
+                estimated_position,
+                &velocity.displacement(),
+                time
+            );
+        }
+    }
+
+    fn is_jump(&self, fix: &Point3D) -> bool {
+        self.max_jump > Meter::ZERO
+            && self.estimated_position.is_some_and(|estimated_position|
+                estimated_position.distance_to(fix) > self.max_jump
+            )
+    }
+
+    fn mismatches_ranges(
+        &self,
+        fix: &Point3D,
+        neighbors: &[NeighborView]
+    ) -> bool {
+        if self.max_range_error <= Meter::ZERO || neighbors.is_empty() {
+            return false;
+        }
+
+        let range_error: Meter = neighbors
+            .iter()
+            .map(|neighbor|
+                (fix.distance_to(neighbor) - neighbor.distance()).abs()
+            )
+            .sum();
+
+        range_error / neighbors.len() as Float > self.max_range_error
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::device::{DeviceBuilder, WorldView};
+    use crate::backend::device::systems::{PowerSystem, SensorSystem};
+    use crate::backend::mathphysics::{Millisecond, PowerUnit};
+
+    use super::*;
+
+
+    const MAX_JUMP: Meter        = Meter::new(20.0);
+    const MAX_RANGE_ERROR: Meter = Meter::new(5.0);
+
+
+    #[test]
+    fn default_navigation_system_accepts_every_fix() {
+        let mut navigation_system = NavigationSystem::default();
+        navigation_system.accept_fix(Point3D::default());
+
+        assert!(!navigation_system.is_enabled());
+        assert_eq!(navigation_system.estimated_position(), None);
+        assert_eq!(
+            navigation_system.check_fix(&Point3D::new(1e4, 0.0, 0.0), &[]),
+            None
+        );
+    }
+
+    #[test]
+    fn fix_far_from_dead_reckoned_position_is_a_jump() {
+        let mut navigation_system = NavigationSystem::new(
+            MAX_JUMP,
+            Meter::ZERO,
+            true
+        );
+        let velocity = Vector3D::new(
+            Point3D::default(),
+            Point3D::new(10.0, 0.0, 0.0)
+        );
+
+        // The first fix has nothing to be compared with.
+        assert_eq!(
+            navigation_system.check_fix(&Point3D::new(1e4, 0.0, 0.0), &[]),
+            None
+        );
+
+        navigation_system.accept_fix(Point3D::default());
+        navigation_system.dead_reckon(&velocity, 5.0);
+
+        assert_eq!(
+            navigation_system.estimated_position(),
+            Some(&Point3D::new(50.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            navigation_system.check_fix(&Point3D::new(55.0, 0.0, 0.0), &[]),
+            None
+        );
+        assert_eq!(
+            navigation_system.check_fix(&Point3D::new(0.0, 0.0, 0.0), &[]),
+            Some(SpoofingEvidence::Jump)
+        );
+    }
+
+    #[test]
+    fn fix_inconsistent_with_neighbor_ranges_is_flagged() {
+        let power_system = PowerSystem::build(
+            PowerUnit::new(1000),
+            PowerUnit::new(1000)
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+        let observer = DeviceBuilder::new()
+            .set_power_system(power_system.clone())
+            .set_sensor_system(
+                SensorSystem::new(
+                    1.0,
+                    Meter::new(100.0),
+                    Millisecond::new(1000)
+                )
+            )
+            .build();
+        let neighbor = DeviceBuilder::new()
+            .set_real_position(Point3D::new(30.0, 0.0, 0.0))
+            .set_power_system(power_system)
+            .build();
+        let world_view = WorldView::observed_by(&observer, [&neighbor]);
+        let navigation_system = NavigationSystem::new(
+            Meter::ZERO,
+            MAX_RANGE_ERROR,
+            false
+        );
+
+        assert_eq!(
+            navigation_system.check_fix(
+                &Point3D::new(0.0, 2.0, 0.0),
+                world_view.neighbors()
+            ),
+            None
+        );
+        assert_eq!(
+            navigation_system.check_fix(
+                &Point3D::new(-40.0, 0.0, 0.0),
+                world_view.neighbors()
+            ),
+            Some(SpoofingEvidence::RangeMismatch)
+        );
+    }
+}
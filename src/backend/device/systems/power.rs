@@ -1,7 +1,12 @@
+use std::collections::BTreeMap;
+
+use derive_more::{Add, Sum};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::backend::mathphysics::PowerUnit;
+use crate::backend::device::DeviceId;
+use crate::backend::mathphysics::{Joule, PowerUnit, Second, Watt};
 
 
 #[derive(Error, Debug)]
@@ -17,11 +22,36 @@ pub enum PowerSystemBuildError {
 }
 
 
-// By default the system can supply any power, because its maximum power is 0.0.
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+// Converts integer power units of the older model. One unit is one Joule.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn power_units_to_joules(power: PowerUnit) -> Joule {
+    Joule::new(power.value() as f32)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn joules_to_power_units(energy: Joule) -> PowerUnit {
+    PowerUnit::new(energy.value().ceil() as u32)
+}
+
+
+// Battery energy in Joules. By default the system can supply any power, 
+// because its maximum energy is 0.0.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
 pub struct PowerSystem {
-    max_power: PowerUnit,
-    power: PowerUnit,
+    #[serde(alias = "max_power")]
+    max_energy: Joule,
+    #[serde(alias = "power")]
+    energy: Joule,
+    // Fraction of max energy lost on each charge cycle.
+    #[serde(default)]
+    capacity_fade: f32,
+    #[serde(default)]
+    cycle_count: u32,
+    // Level at or below which the device lands in an emergency, 0.0 if it
+    // never does.
+    #[serde(default)]
+    critical_level: f32,
 }
 
 impl PowerSystem {
@@ -32,21 +62,103 @@ impl PowerSystem {
         max_power: PowerUnit, 
         power: PowerUnit
     ) -> Result<Self, PowerSystemBuildError> {
-        if power > max_power {
+        Self::build_in_joules(
+            power_units_to_joules(max_power), 
+            power_units_to_joules(power)
+        )
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if provided energy is higher than provided max 
+    /// energy.
+    pub fn build_in_joules(
+        max_energy: Joule,
+        energy: Joule
+    ) -> Result<Self, PowerSystemBuildError> {
+        if energy > max_energy {
             return Err(PowerSystemBuildError::PowerIsGreaterThanMax);
         }
 
-        Ok(Self { max_power, power })
+        Ok(
+            Self { 
+                max_energy, 
+                energy: energy.max(Joule::ZERO), 
+                capacity_fade: 0.0, 
+                cycle_count: 0,
+                critical_level: 0.0,
+            }
+        )
+    }
+
+    #[must_use]
+    pub fn set_capacity_fade(mut self, capacity_fade: f32) -> Self {
+        self.capacity_fade = capacity_fade.clamp(0.0, 1.0);
+        self
+    }
+
+    #[must_use]
+    pub fn set_critical_level(mut self, critical_level: f32) -> Self {
+        self.critical_level = critical_level.clamp(0.0, 1.0);
+        self
     }
 
     #[must_use]
     pub fn max_power(&self) -> PowerUnit {
-        self.max_power
+        joules_to_power_units(self.max_energy)
     }
 
+    // Any energy left counts as at least one power unit, so the power is 0
+    // only for a drained system.
     #[must_use]
     pub fn power(&self) -> PowerUnit {
-        self.power
+        joules_to_power_units(self.energy)
+    }
+
+    #[must_use]
+    pub fn max_energy(&self) -> Joule {
+        self.max_energy
+    }
+
+    #[must_use]
+    pub fn energy(&self) -> Joule {
+        self.energy
+    }
+
+    #[must_use]
+    pub fn capacity_fade(&self) -> f32 {
+        self.capacity_fade
+    }
+
+    #[must_use]
+    pub fn cycle_count(&self) -> u32 {
+        self.cycle_count
+    }
+
+    #[must_use]
+    pub fn critical_level(&self) -> f32 {
+        self.critical_level
+    }
+
+    #[must_use]
+    pub fn is_drained(&self) -> bool {
+        self.energy <= Joule::ZERO
+    }
+
+    // Fraction of max energy that is left. A system without max energy has 
+    // no charge to measure, so its level is 0.0.
+    #[must_use]
+    pub fn level(&self) -> f32 {
+        if self.max_energy <= Joule::ZERO {
+            return 0.0;
+        }
+
+        self.energy / self.max_energy
+    }
+
+    #[must_use]
+    pub fn is_critical(&self) -> bool {
+        self.critical_level > 0.0 && self.level() <= self.critical_level
     }
 
     /// # Errors
@@ -56,14 +168,120 @@ impl PowerSystem {
         &mut self, 
         power_to_consume: PowerUnit
     ) -> Result<(), PowerSystemError> {
-        self.power = self.power.saturating_sub(power_to_consume);
+        self.consume_energy(power_units_to_joules(power_to_consume))
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the system consume all energy.
+    pub fn consume_energy(
+        &mut self,
+        energy_to_consume: Joule
+    ) -> Result<(), PowerSystemError> {
+        self.energy = (self.energy - energy_to_consume).max(Joule::ZERO);
 
-        if self.power == 0 {
+        if self.is_drained() {
             return Err(PowerSystemError::NoPowerLeft)
         }
 
         Ok(())
     }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the system consume all energy.
+    pub fn draw_power(
+        &mut self,
+        power: Watt,
+        duration: Second
+    ) -> Result<(), PowerSystemError> {
+        self.consume_energy(power * duration)
+    }
+
+    // Completes a charge cycle: the battery wears out by the capacity fade
+    // and is charged to the reduced max energy.
+    pub fn recharge(&mut self) {
+        self.max_energy  -= self.max_energy * self.capacity_fade;
+        self.energy       = self.max_energy;
+        self.cycle_count += 1;
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EnergyConsumer {
+    Movement,
+    Processing,
+    TX,
+    // Drain of the device being on, e.g. its flight controller.
+    Passive,
+}
+
+
+// Energy consumed by device subsystems since the device was built.
+#[derive(
+    Clone, Copy, Debug, Default, Add, PartialEq, Sum, JsonSchema, Serialize,
+    Deserialize
+)]
+pub struct EnergyUsage {
+    movement: Joule,
+    processing: Joule,
+    tx: Joule,
+    passive: Joule,
+}
+
+impl EnergyUsage {
+    pub fn record(&mut self, consumer: EnergyConsumer, energy: Joule) {
+        match consumer {
+            EnergyConsumer::Movement   => self.movement += energy,
+            EnergyConsumer::Processing => self.processing += energy,
+            EnergyConsumer::TX         => self.tx += energy,
+            EnergyConsumer::Passive    => self.passive += energy,
+        }
+    }
+
+    #[must_use]
+    pub fn of(&self, consumer: EnergyConsumer) -> Joule {
+        match consumer {
+            EnergyConsumer::Movement   => self.movement,
+            EnergyConsumer::Processing => self.processing,
+            EnergyConsumer::TX         => self.tx,
+            EnergyConsumer::Passive    => self.passive,
+        }
+    }
+
+    #[must_use]
+    pub fn total(&self) -> Joule {
+        self.movement + self.processing + self.tx + self.passive
+    }
+}
+
+
+// Energy usage of devices at run end, written to run summaries.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnergyReport {
+    total: EnergyUsage,
+    per_device: BTreeMap<DeviceId, EnergyUsage>,
+}
+
+impl EnergyReport {
+    #[must_use]
+    pub fn new(per_device: BTreeMap<DeviceId, EnergyUsage>) -> Self {
+        Self {
+            total: per_device.values().copied().sum(),
+            per_device,
+        }
+    }
+
+    #[must_use]
+    pub fn total(&self) -> &EnergyUsage {
+        &self.total
+    }
+
+    #[must_use]
+    pub fn per_device(&self) -> &BTreeMap<DeviceId, EnergyUsage> {
+        &self.per_device
+    }
 }
 
 
@@ -76,13 +294,13 @@ mod tests {
     fn default_power_system_does_not_supply_power() {
         let default_power_system = PowerSystem::default();
 
-        assert_eq!(default_power_system.max_power(), 0);
+        assert_eq!(default_power_system.max_power(), PowerUnit::new(0));
     }
 
     #[test]
     fn building_power_system_with_power_greater_than_max_is_impossible() {
-        let max_power      = 50;
-        let too_high_power = max_power * 2;
+        let max_power      = PowerUnit::new(50);
+        let too_high_power = max_power + max_power;
 
         let result = PowerSystem::build(max_power, too_high_power);
 
@@ -93,7 +311,7 @@ mod tests {
 
     #[test]
     fn error_on_consuming_all_power() {
-        let max_power = 10;
+        let max_power = PowerUnit::new(10);
         let power     = max_power;
         
         let mut power_system = PowerSystem::build(max_power, power)
@@ -105,6 +323,114 @@ mod tests {
                 Err(PowerSystemError::NoPowerLeft)
             )
         );
-        assert_eq!(power_system.power, 0);
+        assert_eq!(power_system.power(), PowerUnit::new(0));
+    }
+
+    #[test]
+    fn recharge_reduces_max_power_by_capacity_fade() {
+        let mut power_system = PowerSystem::build(
+            PowerUnit::new(1000),
+            PowerUnit::new(100)
+        )
+            .unwrap_or_else(|error| panic!("{}", error))
+            .set_capacity_fade(0.1);
+
+        power_system.recharge();
+
+        assert!((power_system.max_energy().value() - 900.0).abs() < 0.01);
+        assert!((power_system.energy().value() - 900.0).abs() < 0.01);
+        
+        power_system.recharge();
+
+        assert!((power_system.max_energy().value() - 810.0).abs() < 0.01);
+        assert_eq!(power_system.cycle_count(), 2);
+    }
+
+    #[test]
+    fn recharge_without_capacity_fade_restores_max_power() {
+        let mut power_system = PowerSystem::build(
+            PowerUnit::new(1000),
+            PowerUnit::new(0)
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        power_system.recharge();
+
+        assert_eq!(power_system.power(), PowerUnit::new(1000));
+        assert_eq!(power_system.max_power(), PowerUnit::new(1000));
+    }
+
+    #[test]
+    fn drawing_power_consumes_energy_over_time() {
+        let mut power_system = PowerSystem::build_in_joules(
+            Joule::new(100.0),
+            Joule::new(100.0)
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        power_system.draw_power(Watt::new(20.0), 0.5)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        assert!((power_system.energy().value() - 90.0).abs() < f32::EPSILON);
+        assert_eq!(power_system.power(), PowerUnit::new(90));
+    }
+
+    #[test]
+    fn power_units_are_rounded_up_until_drained() {
+        let mut power_system = PowerSystem::build(
+            PowerUnit::new(10),
+            PowerUnit::new(10)
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        power_system.consume_energy(Joule::new(9.5))
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        assert_eq!(power_system.power(), PowerUnit::new(1));
+        assert!(!power_system.is_drained());
+    }
+
+    #[test]
+    fn level_is_fraction_of_max_power() {
+        let power_system = PowerSystem::build(
+            PowerUnit::new(200),
+            PowerUnit::new(50)
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        assert!((power_system.level() - 0.25).abs() < f32::EPSILON);
+        assert!(PowerSystem::default().level().abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn level_at_or_below_critical_level_is_critical() {
+        let power_system = PowerSystem::build(
+            PowerUnit::new(200),
+            PowerUnit::new(50)
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        assert!(!power_system.is_critical());
+        assert!(power_system.clone().set_critical_level(0.25).is_critical());
+        assert!(!power_system.set_critical_level(0.2).is_critical());
+    }
+
+    #[test]
+    fn energy_usage_is_summed_per_consumer() {
+        let mut first_usage = EnergyUsage::default();
+        let mut second_usage = EnergyUsage::default();
+
+        first_usage.record(EnergyConsumer::Movement, Joule::new(10.0));
+        first_usage.record(EnergyConsumer::TX, Joule::new(1.0));
+        second_usage.record(EnergyConsumer::Movement, Joule::new(5.0));
+        second_usage.record(EnergyConsumer::Passive, Joule::new(2.0));
+
+        let total_usage: EnergyUsage = [first_usage, second_usage]
+            .into_iter()
+            .sum();
+
+        assert_eq!(total_usage.of(EnergyConsumer::Movement), Joule::new(15.0));
+        assert_eq!(total_usage.of(EnergyConsumer::Processing), Joule::new(0.0));
+        assert_eq!(total_usage.total(), Joule::new(18.0));
     }
 }    
@@ -0,0 +1,138 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::ITERATION_TIME;
+use crate::backend::mathphysics::Millisecond;
+use crate::backend::random;
+
+
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, PartialEq, JsonSchema, Serialize, Deserialize
+)]
+pub enum HardwareComponent {
+    Motors,
+    Radio,
+    GPSReceiver,
+}
+
+
+fn failure_probability(mtbf: Millisecond) -> f64 {
+    if mtbf <= Millisecond::ZERO {
+        return 1.0;
+    }
+
+    // Failures are exponentially distributed, so the probability to fail
+    // during an iteration does not depend on the device age.
+    1.0 - (
+        -f64::from(ITERATION_TIME.value()) / f64::from(mtbf.value())
+    ).exp()
+}
+
+
+// Mean times between failures of device components. A component without
+// MTBF never fails. By default nothing fails.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct ReliabilitySystem {
+    motors_mtbf: Option<Millisecond>,
+    radio_mtbf: Option<Millisecond>,
+    gps_receiver_mtbf: Option<Millisecond>,
+    failed_components: Vec<HardwareComponent>,
+}
+
+impl ReliabilitySystem {
+    #[must_use]
+    pub fn new(
+        motors_mtbf: Option<Millisecond>,
+        radio_mtbf: Option<Millisecond>,
+        gps_receiver_mtbf: Option<Millisecond>,
+    ) -> Self {
+        Self {
+            motors_mtbf,
+            radio_mtbf,
+            gps_receiver_mtbf,
+            failed_components: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn mtbf(&self, component: HardwareComponent) -> Option<Millisecond> {
+        match component {
+            HardwareComponent::Motors      => self.motors_mtbf,
+            HardwareComponent::Radio       => self.radio_mtbf,
+            HardwareComponent::GPSReceiver => self.gps_receiver_mtbf,
+        }
+    }
+
+    #[must_use]
+    pub fn failed_components(&self) -> &[HardwareComponent] {
+        &self.failed_components
+    }
+
+    #[must_use]
+    pub fn has_failed(&self, component: HardwareComponent) -> bool {
+        self.failed_components.contains(&component)
+    }
+
+    // Returns components that failed during the iteration. Failed components
+    // are not repaired.
+    pub fn fail_components(&mut self) -> Vec<HardwareComponent> {
+        let new_failures: Vec<HardwareComponent> = [
+            HardwareComponent::Motors,
+            HardwareComponent::Radio,
+            HardwareComponent::GPSReceiver,
+        ]
+            .into_iter()
+            .filter(|component| !self.has_failed(*component))
+            .filter(|component|
+                self.mtbf(*component).is_some_and(|mtbf|
+                    random::random_bool(failure_probability(mtbf))
+                )
+            )
+            .collect();
+
+        self.failed_components.extend_from_slice(&new_failures);
+
+        new_failures
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn components_without_mtbf_never_fail() {
+        let mut reliability_system = ReliabilitySystem::default();
+
+        for _ in 0..1000 {
+            assert!(reliability_system.fail_components().is_empty());
+        }
+    }
+
+    #[test]
+    fn component_fails_only_once() {
+        let mut reliability_system = ReliabilitySystem::new(
+            Some(Millisecond::ZERO),
+            None,
+            None
+        );
+
+        assert_eq!(
+            reliability_system.fail_components(),
+            vec![HardwareComponent::Motors]
+        );
+        assert!(reliability_system.fail_components().is_empty());
+        assert!(reliability_system.has_failed(HardwareComponent::Motors));
+    }
+
+    #[test]
+    fn failure_probability_grows_with_shorter_mtbf() {
+        let second = Millisecond::new(1_000);
+        let minute = Millisecond::new(60_000);
+
+        assert!(failure_probability(second) > failure_probability(minute));
+        assert!(failure_probability(minute) > 0.0);
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::ITERATION_TIME;
+use crate::backend::device::DeviceId;
+use crate::backend::mathphysics::{
+    Float, Meter, Millisecond, Point3D, Position
+};
+
+
+// A device detected by a sensor.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct ReconRecord {
+    time: Millisecond,
+    observer_id: DeviceId,
+    target_id: DeviceId,
+    target_position: Point3D,
+}
+
+impl ReconRecord {
+    #[must_use]
+    pub fn new(
+        time: Millisecond,
+        observer_id: DeviceId,
+        target_id: DeviceId,
+        target_position: Point3D,
+    ) -> Self {
+        Self { time, observer_id, target_id, target_position }
+    }
+
+    #[must_use]
+    pub fn time(&self) -> Millisecond {
+        self.time
+    }
+
+    #[must_use]
+    pub fn observer_id(&self) -> DeviceId {
+        self.observer_id
+    }
+
+    #[must_use]
+    pub fn target_id(&self) -> DeviceId {
+        self.target_id
+    }
+
+    #[must_use]
+    pub fn target_position(&self) -> &Point3D {
+        &self.target_position
+    }
+
+    #[must_use]
+    pub fn with_target_position(&self, target_position: Point3D) -> Self {
+        Self { target_position, ..*self }
+    }
+}
+
+
+// Records of a target a relay combined into one signal. Only the latest of
+// them is delivered, the rest are counted.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct ReconSummary {
+    latest: ReconRecord,
+    record_count: usize,
+}
+
+impl ReconSummary {
+    // One summary per target of the records, ordered by target IDs.
+    #[must_use]
+    pub fn per_target(records: &[ReconRecord]) -> Vec<Self> {
+        let mut summaries: BTreeMap<DeviceId, Self> = BTreeMap::new();
+
+        for record in records {
+            summaries
+                .entry(record.target_id())
+                .and_modify(|summary| {
+                    summary.record_count += 1;
+
+                    if record.time() >= summary.latest.time() {
+                        summary.latest = *record;
+                    }
+                })
+                .or_insert(Self { latest: *record, record_count: 1 });
+        }
+
+        summaries.into_values().collect()
+    }
+
+    #[must_use]
+    pub fn latest(&self) -> &ReconRecord {
+        &self.latest
+    }
+
+    #[must_use]
+    pub fn record_count(&self) -> usize {
+        self.record_count
+    }
+
+    #[must_use]
+    pub fn with_latest(&self, latest: ReconRecord) -> Self {
+        Self { latest, ..*self }
+    }
+}
+
+
+// The sensor looks straight down and sees targets in the cone with 
+// `field_of_view` apex angle (in radians) up to `range` away. By default 
+// the system detects nothing, because its range is 0.0.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct SensorSystem {
+    field_of_view: f32,
+    range: Meter,
+    sampling_period: Millisecond,
+}
+
+impl SensorSystem {
+    #[must_use]
+    pub fn new(
+        field_of_view: f32,
+        range: Meter,
+        sampling_period: Millisecond
+    ) -> Self {
+        Self { field_of_view, range, sampling_period }
+    }
+
+    #[must_use]
+    pub fn field_of_view(&self) -> f32 {
+        self.field_of_view
+    }
+
+    #[must_use]
+    pub fn range(&self) -> Meter {
+        self.range
+    }
+
+    #[must_use]
+    pub fn sampling_period(&self) -> Millisecond {
+        self.sampling_period
+    }
+
+    #[must_use]
+    pub fn samples_at(&self, time: Millisecond) -> bool {
+        self.range > Meter::ZERO 
+            && self.sampling_period > Millisecond::ZERO 
+            && time % self.sampling_period < ITERATION_TIME
+    }
+
+    #[must_use]
+    pub fn detects(&self, sensor_position: &Point3D, target: &Point3D) -> bool {
+        let distance = sensor_position.distance_to(target);
+        let depth = sensor_position.z - target.z;
+
+        if distance > self.range || depth <= 0.0 {
+            return false;
+        }
+
+        // Angle between the nadir and the direction to the target.
+        let angle = (depth / distance.value()).acos();
+
+        angle <= Float::from(self.field_of_view) / 2.0
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+
+    #[test]
+    fn default_sensor_system_does_not_sample() {
+        let sensor_system = SensorSystem::default();
+
+        assert!(!sensor_system.samples_at(Millisecond::ZERO));
+        assert!(
+            !sensor_system.detects(
+                &Point3D::new(0.0, 0.0, 10.0), 
+                &Point3D::default()
+            )
+        );
+    }
+
+    #[test]
+    fn only_targets_beneath_in_range_are_detected() {
+        let sensor_system = SensorSystem::new(
+            FRAC_PI_2,
+            Meter::new(20.0),
+            Millisecond::new(1000)
+        );
+        let sensor_position = Point3D::new(0.0, 0.0, 10.0);
+
+        assert!(
+            sensor_system.detects(&sensor_position, &Point3D::new(5.0, 0.0, 0.0))
+        );
+        // Outside of the field of view.
+        assert!(
+            !sensor_system.detects(
+                &sensor_position, 
+                &Point3D::new(15.0, 0.0, 0.0)
+            )
+        );
+        // Out of range.
+        assert!(
+            !sensor_system.detects(
+                &sensor_position, 
+                &Point3D::new(0.0, 0.0, -15.0)
+            )
+        );
+        // Above the sensor.
+        assert!(
+            !sensor_system.detects(
+                &sensor_position, 
+                &Point3D::new(0.0, 0.0, 15.0)
+            )
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::ITERATION_TIME;
+use crate::backend::mathphysics::{millis_to_secs, Celsius};
+
+
+// By default the system never heats up, so the device is never throttled.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct ThermalSystem {
+    temperature: Celsius,
+    ambient_temperature: Celsius,
+    // Temperature growth per second under full load.
+    heating_rate: Celsius,
+    // Fraction of the difference with the ambient temperature that is lost
+    // per second.
+    cooling_rate: f32,
+    throttle_temperature: Celsius,
+    // Max speed and TX strength multiplier of an overheated device.
+    throttle_factor: f32,
+}
+
+impl ThermalSystem {
+    #[must_use]
+    pub fn new(
+        ambient_temperature: Celsius,
+        heating_rate: Celsius,
+        cooling_rate: f32,
+        throttle_temperature: Celsius,
+        throttle_factor: f32,
+    ) -> Self {
+        Self {
+            temperature: ambient_temperature,
+            ambient_temperature,
+            heating_rate,
+            cooling_rate,
+            throttle_temperature,
+            throttle_factor: throttle_factor.clamp(0.0, 1.0),
+        }
+    }
+
+    #[must_use]
+    pub fn temperature(&self) -> Celsius {
+        self.temperature
+    }
+
+    #[must_use]
+    pub fn is_overheated(&self) -> bool {
+        self.heating_rate > 0.0 
+            && self.temperature > self.throttle_temperature
+    }
+
+    #[must_use]
+    pub fn throttle_factor(&self) -> f32 {
+        if self.is_overheated() {
+            self.throttle_factor
+        } else {
+            1.0
+        }
+    }
+
+    // Load is the fraction of the device capacity in use: 0.0 for an idle
+    // device and 1.0 for max speed or max TX strength.
+    pub fn update(&mut self, load: f32) {
+        let iteration_time = millis_to_secs(ITERATION_TIME);
+        let heating = self.heating_rate * load.clamp(0.0, 1.0);
+        let cooling = self.cooling_rate 
+            * (self.temperature - self.ambient_temperature);
+
+        self.temperature += (heating - cooling) * iteration_time;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    fn thermal_system() -> ThermalSystem {
+        ThermalSystem::new(20.0, 10.0, 0.05, 60.0, 0.5)
+    }
+
+
+    #[test]
+    fn default_thermal_system_does_not_throttle() {
+        let mut thermal_system = ThermalSystem::default();
+
+        for _ in 0..1000 {
+            thermal_system.update(1.0);
+        }
+
+        assert!((thermal_system.throttle_factor() - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn sustained_full_load_throttles_device() {
+        let mut thermal_system = thermal_system();
+
+        for _ in 0..1000 {
+            thermal_system.update(1.0);
+        }
+
+        assert!(thermal_system.is_overheated());
+        assert!((thermal_system.throttle_factor() - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn idle_device_cools_down() {
+        let mut thermal_system = thermal_system();
+
+        for _ in 0..1000 {
+            thermal_system.update(1.0);
+        }
+        for _ in 0..2000 {
+            thermal_system.update(0.0);
+        }
+
+        assert!(!thermal_system.is_overheated());
+        assert!(thermal_system.temperature() < 25.0);
+    }
+}
@@ -1,8 +1,11 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::backend::mathphysics::{Frequency, Megahertz, Meter, Millisecond};
-use crate::backend::signal::{FreqToStrengthMap, Signal, SignalStrength};
+use crate::backend::mathphysics::{Frequency, Meter, Millisecond};
+use crate::backend::signal::{
+    FreqToStrengthMap, Propagation, Signal, SignalStrength
+};
 
 pub use rx::{SignalRecord, RXError, RXModule};
 pub use tx::TXModule;
@@ -25,7 +28,7 @@ pub enum TRXSystemError {
 }
 
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
 pub struct TRXSystem {
     tx_module: TXModule, 
     rx_module: RXModule,
@@ -42,6 +45,11 @@ impl TRXSystem {
         self.tx_module.signal_strength_map() 
     }
 
+    #[must_use]
+    pub fn rx_max_signal_strength_map(&self) -> &FreqToStrengthMap {
+        self.rx_module.max_signal_strength_map()
+    }
+
     #[must_use]
     pub fn tx_signal_strength_on(
         &self, 
@@ -50,15 +58,14 @@ impl TRXSystem {
         self.tx_module.signal_strength_on(frequency) 
     }
 
+    #[must_use]
+    pub fn propagation(&self) -> &Propagation {
+        self.tx_module.propagation()
+    }
+
     #[must_use]
     pub fn area_radius_on(&self, frequency: Frequency) -> Meter {
-        self.tx_module
-            .signal_strength_on(&frequency)
-            .map_or(
-                0.0, 
-                |tx_signal_strength| 
-                    tx_signal_strength.area_radius_on(frequency as Megahertz)
-            )
+        self.tx_module.area_radius_on(frequency)
     }
 
     #[must_use]
@@ -85,7 +92,7 @@ impl TRXSystem {
     }
 
     #[must_use]
-    pub fn received_signals(&self) -> Vec<SignalRecord> {
+    pub fn received_signals(&self) -> &[SignalRecord] {
         self.rx_module.received_signals()
     }
     
@@ -110,6 +117,21 @@ impl TRXSystem {
         Ok(())
     }
 
+    // Signals are sent and received on `to` instead of `from` with the same
+    // strengths.
+    pub fn retune(&mut self, from: Frequency, to: Frequency) {
+        self.tx_module.retune(from, to);
+        self.rx_module.retune(from, to);
+    }
+
+    pub fn set_propagation(&mut self, propagation: Propagation) {
+        self.tx_module.set_propagation(propagation);
+    }
+
+    pub fn stop_receiving_on(&mut self, frequency: Frequency) {
+        self.rx_module.stop_listening_on(frequency);
+    }
+
     pub fn clear_received_signals(&mut self) {
         self.rx_module.clear_signals();
     }
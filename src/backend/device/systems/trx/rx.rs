@@ -1,7 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::backend::mathphysics::{Frequency, Millisecond};
+use crate::backend::random;
 use crate::backend::signal::{
     Data, FreqToStrengthMap, Signal, SignalStrength, MAX_BLACK_SIGNAL_STRENGTH,
     MAX_RED_SIGNAL_STRENGTH, MAX_YELLOW_SIGNAL_STRENGTH
@@ -20,7 +22,7 @@ const RECEIVE_BLACK_SIGNAL: f64  = 0.1;
 
 
 fn signal_reached_rx(signal_strength: SignalStrength) -> bool {
-    rand::random_bool(
+    random::random_bool(
         signal_reach_rx_probability(signal_strength)
     )
 }
@@ -52,7 +54,7 @@ pub enum RXError {
 
 
 // By default we create a non-functioning RXModule.
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
 pub struct RXModule {
     max_signal_strength_map: FreqToStrengthMap,
     received_signals: Vec<SignalRecord>,
@@ -78,8 +80,13 @@ impl RXModule {
     }
 
     #[must_use]
-    pub fn received_signals(&self) -> Vec<SignalRecord> {
-        self.received_signals.clone()
+    pub fn max_signal_strength_map(&self) -> &FreqToStrengthMap {
+        &self.max_signal_strength_map
+    }
+
+    #[must_use]
+    pub fn received_signals(&self) -> &[SignalRecord] {
+        &self.received_signals
     }
 
     #[must_use]
@@ -112,10 +119,8 @@ impl RXModule {
 
         if let Some((_, current_signal)) = self.received_signal_on(
             &signal.frequency()
-        ) {
-            if current_signal.strength() > signal.strength() {
-                return Err(RXError::SignalTooWeak);
-            }
+        ) && current_signal.strength() > signal.strength() {
+            return Err(RXError::SignalTooWeak);
         }
 
         self.remove_current_received_signal_on(signal.frequency());
@@ -157,6 +162,21 @@ impl RXModule {
         self.received_signals.remove(current_signal_index);
     }
     
+    // Signals already received on `from` are kept until they are cleared,
+    // so they can still be processed.
+    pub fn retune(&mut self, from: Frequency, to: Frequency) {
+        if let Some(max_signal_strength) = self.max_signal_strength_map
+            .remove(&from)
+        {
+            self.max_signal_strength_map.insert(to, max_signal_strength);
+        }
+    }
+    
+    pub fn stop_listening_on(&mut self, frequency: Frequency) {
+        self.max_signal_strength_map.remove(&frequency);
+        self.remove_current_received_signal_on(frequency);
+    }
+
     pub fn clear_signals(&mut self) {
         self.received_signals.clear();
     }
@@ -1,13 +1,20 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::backend::mathphysics::{Frequency, Megahertz, Meter};
-use crate::backend::signal::{FreqToStrengthMap, SignalStrength};
+use crate::backend::signal::{
+    FreqToStrengthMap, Propagation, PropagationModel, SignalStrength
+};
 
 
 // By default we create a non-functioning `TXModule` based on signal strength.
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+// Signals weaken with distance as the propagation model of the network
+// model describes.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
 pub struct TXModule {
-    signal_strength_map: FreqToStrengthMap
+    signal_strength_map: FreqToStrengthMap,
+    #[serde(default)]
+    propagation: Propagation,
 }
 
 impl TXModule {
@@ -15,7 +22,16 @@ impl TXModule {
     pub fn new(
         signal_strength_map: FreqToStrengthMap
     ) -> Self {
-        Self { signal_strength_map }
+        Self { signal_strength_map, propagation: Propagation::default() }
+    }
+
+    #[must_use]
+    pub fn propagation(&self) -> &Propagation {
+        &self.propagation
+    }
+
+    pub fn set_propagation(&mut self, propagation: Propagation) {
+        self.propagation = propagation;
     }
 
     #[must_use]
@@ -40,7 +56,29 @@ impl TXModule {
         self
             .signal_strength_on(&frequency)
             .map(|signal_strength| 
-                signal_strength.at(frequency as Megahertz, distance)
+                self.propagation.received_strength(
+                    *signal_strength,
+                    frequency as Megahertz,
+                    distance
+                )
             )
     }
+
+    #[must_use]
+    pub fn area_radius_on(&self, frequency: Frequency) -> Meter {
+        self.signal_strength_on(&frequency)
+            .map_or(
+                Meter::ZERO,
+                |signal_strength| self.propagation.area_radius(
+                    *signal_strength,
+                    frequency as Megahertz
+                )
+            )
+    }
+
+    pub fn retune(&mut self, from: Frequency, to: Frequency) {
+        if let Some(signal_strength) = self.signal_strength_map.remove(&from) {
+            self.signal_strength_map.insert(to, signal_strength);
+        }
+    }
 }
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::backend::mathphysics::Point3D;
+
+use super::{Device, DeviceBuilder};
+
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum TemplateError {
+    #[error("Device template `{0}` does not exist")]
+    UnknownTemplate(String),
+}
+
+
+// Named device builders, so a kind of device is described once and placed
+// many times. Each instance gets a new ID and the given position, while
+// the rest is taken from the template.
+#[derive(Clone, Debug, Default)]
+pub struct DeviceTemplates {
+    device_builders: BTreeMap<String, DeviceBuilder>,
+}
+
+impl DeviceTemplates {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // A template with the same name is replaced.
+    #[must_use]
+    pub fn set_template(
+        mut self,
+        name: impl Into<String>,
+        device_builder: DeviceBuilder
+    ) -> Self {
+        self.device_builders.insert(name.into(), device_builder);
+        self
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&DeviceBuilder> {
+        self.device_builders.get(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.device_builders.keys().map(String::as_str)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if there is no template named `name`.
+    pub fn instantiate(
+        &self,
+        name: &str,
+        position: Point3D
+    ) -> Result<Device, TemplateError> {
+        self.get(name)
+            .map(|device_builder|
+                device_builder
+                    .clone()
+                    .set_real_position(position)
+                    .build()
+            )
+            .ok_or_else(|| TemplateError::UnknownTemplate(name.to_string()))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::device::SignalLossResponse;
+    use crate::backend::device::systems::PowerSystem;
+    use crate::backend::mathphysics::{Position, PowerUnit};
+
+    use super::*;
+
+
+    #[test]
+    fn instances_share_template_but_not_position_or_id() {
+        let templates = DeviceTemplates::new()
+            .set_template(
+                "drone",
+                DeviceBuilder::new()
+                    .set_power_system(
+                        PowerSystem::build(
+                            PowerUnit::new(1000),
+                            PowerUnit::new(500)
+                        )
+                            .unwrap_or_else(|error| panic!("{}", error))
+                    )
+                    .set_signal_loss_response(SignalLossResponse::Hover)
+            );
+        let first_position = Point3D::new(1.0, 2.0, 3.0);
+        let second_position = Point3D::new(-1.0, 0.0, 5.0);
+
+        let first = templates
+            .instantiate("drone", first_position)
+            .unwrap_or_else(|error| panic!("{}", error));
+        let second = templates
+            .instantiate("drone", second_position)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        assert_ne!(first.id(), second.id());
+        assert_eq!(*first.position(), first_position);
+        assert_eq!(*second.position(), second_position);
+        assert_eq!(first.power_system(), second.power_system());
+        assert_eq!(
+            first.signal_loss_response(),
+            second.signal_loss_response()
+        );
+    }
+
+    #[test]
+    fn unknown_template_is_rejected() {
+        let templates = DeviceTemplates::new()
+            .set_template("cc", DeviceBuilder::new());
+
+        assert_eq!(
+            templates.instantiate("drone", Point3D::default()).err(),
+            Some(TemplateError::UnknownTemplate("drone".to_string()))
+        );
+        assert_eq!(templates.names().collect::<Vec<_>>(), vec!["cc"]);
+    }
+}
@@ -0,0 +1,204 @@
+use std::sync::Arc;
+
+use crate::backend::landing::LandingZone;
+use crate::backend::mathphysics::{Meter, Point3D, Position, Vector3D};
+use crate::backend::parameters::SimulationParameters;
+
+use super::{Device, DeviceId};
+
+
+// Another device as it is seen by the device the view is built for.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NeighborView {
+    id: DeviceId,
+    position: Point3D,
+    velocity: Vector3D,
+    distance: Meter,
+}
+
+impl NeighborView {
+    #[must_use]
+    pub fn id(&self) -> DeviceId {
+        self.id
+    }
+
+    #[must_use]
+    pub fn velocity(&self) -> &Vector3D {
+        &self.velocity
+    }
+
+    #[must_use]
+    pub fn distance(&self) -> Meter {
+        self.distance
+    }
+}
+
+impl Position for NeighborView {
+    fn position(&self) -> &Point3D {
+        &self.position
+    }
+}
+
+
+// Read-only surroundings of a device passed into its update. Only active
+// devices within the detection range of the device are listed, closest
+// first, and shut down devices see nothing. Decentralized behaviors like
+// flocking, local avoidance or distributed routing then do not need the
+// whole device map. Landing zones and simulation parameters of the model
+// are known to every device, the zones are shared by all views.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WorldView {
+    neighbors: Vec<NeighborView>,
+    landing_zones: Arc<[LandingZone]>,
+    parameters: SimulationParameters,
+}
+
+impl WorldView {
+    // `devices` may contain the observer itself, which is skipped.
+    #[must_use]
+    pub fn observed_by<'a, I>(observer: &Device, devices: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Device>
+    {
+        let detection_range = observer.detection_range();
+
+        if observer.is_shut_down() || detection_range <= Meter::ZERO {
+            return Self::default();
+        }
+
+        let mut neighbors: Vec<NeighborView> = devices
+            .into_iter()
+            .filter(|device|
+                device.id() != observer.id() && !device.is_shut_down()
+            )
+            .map(|device| NeighborView {
+                id: device.id(),
+                position: *device.position(),
+                velocity: *device.velocity(),
+                distance: observer.distance_to(device),
+            })
+            .filter(|neighbor| neighbor.distance <= detection_range)
+            .collect();
+
+        neighbors.sort_by(|neighbor1, neighbor2|
+            neighbor1.distance.total_cmp(&neighbor2.distance)
+        );
+
+        Self { neighbors, ..Default::default() }
+    }
+
+    #[must_use]
+    pub fn set_landing_zones(
+        mut self,
+        landing_zones: Arc<[LandingZone]>
+    ) -> Self {
+        self.landing_zones = landing_zones;
+        self
+    }
+
+    #[must_use]
+    pub fn set_parameters(mut self, parameters: SimulationParameters) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    #[must_use]
+    pub fn neighbors(&self) -> &[NeighborView] {
+        &self.neighbors
+    }
+
+    pub fn neighbors_within(
+        &self,
+        radius: Meter
+    ) -> impl Iterator<Item = &NeighborView> {
+        self.neighbors
+            .iter()
+            .take_while(move |neighbor| neighbor.distance <= radius)
+    }
+
+    #[must_use]
+    pub fn nearest_neighbor(&self) -> Option<&NeighborView> {
+        self.neighbors.first()
+    }
+
+    #[must_use]
+    pub fn landing_zones(&self) -> &[LandingZone] {
+        &self.landing_zones
+    }
+
+    #[must_use]
+    pub fn parameters(&self) -> &SimulationParameters {
+        &self.parameters
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::device::DeviceBuilder;
+    use crate::backend::device::systems::{PowerSystem, SensorSystem};
+    use crate::backend::mathphysics::{Float, Millisecond, PowerUnit};
+
+    use super::*;
+
+
+    const DEVICE_MAX_POWER: PowerUnit = PowerUnit::new(1000);
+
+
+    fn device_at(x: Float) -> Device {
+        DeviceBuilder::new()
+            .set_real_position(Point3D::new(x, 0.0, 0.0))
+            .set_power_system(
+                PowerSystem::build(DEVICE_MAX_POWER, DEVICE_MAX_POWER)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .build()
+    }
+
+
+    #[test]
+    fn view_lists_active_devices_in_range_closest_first() {
+        let observer = DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(DEVICE_MAX_POWER, DEVICE_MAX_POWER)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_sensor_system(
+                SensorSystem::new(1.0, Meter::new(10.0), Millisecond::new(1000))
+            )
+            .build();
+        let far = device_at(8.0);
+        let near = device_at(-3.0);
+        let out_of_range = device_at(20.0);
+        let shut_down = DeviceBuilder::new().build();
+
+        let world_view = WorldView::observed_by(
+            &observer,
+            [&observer, &far, &near, &out_of_range, &shut_down]
+        );
+        let neighbor_ids: Vec<DeviceId> = world_view
+            .neighbors()
+            .iter()
+            .map(NeighborView::id)
+            .collect();
+
+        assert_eq!(neighbor_ids, vec![near.id(), far.id()]);
+        assert_eq!(
+            world_view.nearest_neighbor().map(NeighborView::distance),
+            Some(Meter::new(3.0))
+        );
+        assert_eq!(world_view.neighbors_within(Meter::new(5.0)).count(), 1);
+    }
+
+    #[test]
+    fn device_without_range_sees_nothing() {
+        let observer = device_at(0.0);
+        let neighbor = device_at(1.0);
+
+        assert!(
+            WorldView::observed_by(&observer, [&neighbor])
+                .neighbors()
+                .is_empty()
+        );
+    }
+}
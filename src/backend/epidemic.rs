@@ -0,0 +1,192 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::device::DeviceId;
+use super::event::{Event, EventKind};
+use super::mathphysics::Millisecond;
+
+
+// Malware spread among drones estimated from the infection events of a run.
+// A drone counts as infected from its first infection, whatever malware it
+// is infected with.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EpidemicStats {
+    population: usize,
+    infected_count: usize,
+    // Infected drone count after each time new drones were infected.
+    infection_curve: Vec<(Millisecond, usize)>,
+    time_to_full_compromise: Option<Millisecond>,
+    reproduction_number: Option<f32>,
+}
+
+impl EpidemicStats {
+    // Events are expected in the order they happened. Infections of devices
+    // other than `drone_ids` are ignored.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_events<'a>(
+        events: impl IntoIterator<Item = &'a Event>,
+        drone_ids: &BTreeSet<DeviceId>
+    ) -> Self {
+        let mut infection_sources: BTreeMap<DeviceId, DeviceId> =
+            BTreeMap::new();
+        let mut infection_curve: Vec<(Millisecond, usize)> = Vec::new();
+
+        for event in events {
+            let EventKind::Infected { source_id, .. } = event.kind() else {
+                continue;
+            };
+
+            if !drone_ids.contains(&event.device_id())
+                || infection_sources.contains_key(&event.device_id())
+            {
+                continue;
+            }
+
+            infection_sources.insert(event.device_id(), *source_id);
+
+            let infected_count = infection_sources.len();
+
+            match infection_curve.last_mut() {
+                Some((time, count)) if *time == event.time() =>
+                    *count = infected_count,
+                _                                            =>
+                    infection_curve.push((event.time(), infected_count)),
+            }
+        }
+
+        let population = drone_ids.len();
+        let infected_count = infection_sources.len();
+        let time_to_full_compromise = infection_curve
+            .last()
+            .filter(|(_, infected_count)| *infected_count == population)
+            .map(|(time, _)| *time);
+
+        // Drones infected from outside the swarm, e.g. by an attacker, are
+        // the index cases. R0 is estimated as the mean number of drones
+        // each of them infected.
+        let index_case_ids: Vec<DeviceId> = infection_sources
+            .iter()
+            .filter(|(_, source_id)| !drone_ids.contains(source_id))
+            .map(|(device_id, _)| *device_id)
+            .collect();
+        let secondary_infection_count = infection_sources
+            .values()
+            .filter(|source_id| index_case_ids.contains(source_id))
+            .count();
+        let reproduction_number = (!index_case_ids.is_empty()).then(||
+            secondary_infection_count as f32 / index_case_ids.len() as f32
+        );
+
+        Self {
+            population,
+            infected_count,
+            infection_curve,
+            time_to_full_compromise,
+            reproduction_number,
+        }
+    }
+
+    #[must_use]
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    #[must_use]
+    pub fn infected_count(&self) -> usize {
+        self.infected_count
+    }
+
+    #[must_use]
+    pub fn infection_curve(&self) -> &[(Millisecond, usize)] {
+        &self.infection_curve
+    }
+
+    #[must_use]
+    pub fn time_to_full_compromise(&self) -> Option<Millisecond> {
+        self.time_to_full_compromise
+    }
+
+    #[must_use]
+    pub fn reproduction_number(&self) -> Option<f32> {
+        self.reproduction_number
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::malware::{Malware, MalwareType};
+
+    use super::*;
+
+
+    const ATTACKER_ID: DeviceId = 100;
+
+
+    fn infected(time: i32, device_id: DeviceId, source_id: DeviceId) -> Event {
+        let malware = Malware::new(
+            MalwareType::Indicator,
+            Millisecond::ZERO,
+            None,
+            None
+        );
+
+        Event::new(
+            Millisecond::new(time),
+            device_id,
+            EventKind::Infected { malware, source_id }
+        )
+    }
+
+
+    #[test]
+    fn spread_through_whole_swarm() {
+        let drone_ids = BTreeSet::from([1, 2, 3, 4]);
+        let events = [
+            infected(0, 1, ATTACKER_ID),
+            infected(50, 2, 1),
+            infected(50, 3, 1),
+            infected(100, 4, 2),
+            infected(150, 4, 3),
+        ];
+
+        let epidemic_stats = EpidemicStats::from_events(&events, &drone_ids);
+
+        assert_eq!(epidemic_stats.infected_count(), 4);
+        assert_eq!(
+            epidemic_stats.infection_curve(),
+            [
+                (Millisecond::new(0), 1),
+                (Millisecond::new(50), 3),
+                (Millisecond::new(100), 4)
+            ]
+        );
+        assert_eq!(
+            epidemic_stats.time_to_full_compromise(),
+            Some(Millisecond::new(100))
+        );
+        assert_eq!(epidemic_stats.reproduction_number(), Some(2.0));
+    }
+
+    #[test]
+    fn partial_spread_is_not_full_compromise() {
+        let drone_ids = BTreeSet::from([1, 2, 3]);
+        let events = [
+            infected(0, 1, ATTACKER_ID),
+            infected(0, 2, ATTACKER_ID),
+            infected(0, ATTACKER_ID, 1),
+        ];
+
+        let epidemic_stats = EpidemicStats::from_events(&events, &drone_ids);
+
+        assert_eq!(epidemic_stats.infected_count(), 2);
+        assert_eq!(epidemic_stats.time_to_full_compromise(), None);
+        assert_eq!(epidemic_stats.reproduction_number(), Some(0.0));
+        assert_eq!(
+            EpidemicStats::from_events(&[], &drone_ids).reproduction_number(),
+            None
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::collision::CollisionOutcome;
+use super::device::DeviceId;
+use super::device::systems::{
+    FirmwareVersion, HardwareComponent, ReconRecord, Rumor, SpoofingEvidence
+};
+use super::malware::Malware;
+use super::mathphysics::{Frequency, Millisecond, Point3D};
+use super::task::Task;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub enum EventKind {
+    Collided { other_id: DeviceId, outcome: CollisionOutcome },
+    ControlChannelSwitched { from: Frequency, to: Frequency },
+    ControlSignalLost,
+    ControlSignalRestored,
+    CooledDown,
+    CorruptedDataAccepted,
+    CorruptedDataDiscarded,
+    EmergencyLandingStarted(Point3D),
+    FirmwareInstallStarted(FirmwareVersion),
+    FirmwareUpdated(FirmwareVersion),
+    GPSSpoofingDetected(SpoofingEvidence),
+    HardwareFailure(HardwareComponent),
+    Infected { malware: Malware, source_id: DeviceId },
+    Landed,
+    MissionContinued,
+    Overheated,
+    ReconReceived(ReconRecord),
+    RumorLearned(Rumor),
+    ShutDown,
+    TaskCompleted(Task),
+}
+
+impl EventKind {
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Collided { .. }               => "collided",
+            Self::ControlChannelSwitched { .. } => "control_channel_switched",
+            Self::ControlSignalLost             => "control_signal_lost",
+            Self::ControlSignalRestored         => "control_signal_restored",
+            Self::CooledDown                    => "cooled_down",
+            Self::CorruptedDataAccepted         => "corrupted_data_accepted",
+            Self::CorruptedDataDiscarded        => "corrupted_data_discarded",
+            Self::EmergencyLandingStarted(_)    => "emergency_landing_started",
+            Self::FirmwareInstallStarted(_)     => "firmware_install_started",
+            Self::FirmwareUpdated(_)            => "firmware_updated",
+            Self::GPSSpoofingDetected(_)        => "gps_spoofing_detected",
+            Self::HardwareFailure(_)            => "hardware_failure",
+            Self::Infected { .. }               => "infected",
+            Self::Landed                        => "landed",
+            Self::MissionContinued              => "mission_continued",
+            Self::Overheated                    => "overheated",
+            Self::ReconReceived(_)              => "recon_received",
+            Self::RumorLearned(_)               => "rumor_learned",
+            Self::ShutDown                      => "shut_down",
+            Self::TaskCompleted(_)              => "task_completed",
+        }
+    }
+
+    // Event specific data in a human readable form.
+    #[must_use]
+    pub fn details(&self) -> Option<String> {
+        match self {
+            Self::Collided { other_id, outcome }      =>
+                Some(format!("{outcome:?} with {other_id}")),
+            Self::ControlChannelSwitched { from, to } =>
+                Some(format!("{from:?} to {to:?}")),
+            Self::EmergencyLandingStarted(site)       =>
+                Some(format!("at {site:?}")),
+            Self::FirmwareInstallStarted(version)
+                | Self::FirmwareUpdated(version)      =>
+                Some(format!("version {version}")),
+            Self::GPSSpoofingDetected(evidence)       =>
+                Some(format!("{evidence:?}")),
+            Self::HardwareFailure(component)          =>
+                Some(format!("{component:?}")),
+            Self::Infected { malware, source_id }     =>
+                Some(format!("{malware} from {source_id}")),
+            Self::ReconReceived(record)               =>
+                Some(
+                    format!(
+                        "{} seen by {} at {:?}",
+                        record.target_id(),
+                        record.observer_id(),
+                        record.target_position()
+                    )
+                ),
+            Self::RumorLearned(rumor)                 =>
+                Some(format!("{rumor:?}")),
+            Self::TaskCompleted(task)                 =>
+                Some(format!("{task:?}")),
+            _                                         => None,
+        }
+    }
+}
+
+
+// Something notable that happened to a device during an iteration.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct Event {
+    time: Millisecond,
+    device_id: DeviceId,
+    kind: EventKind,
+}
+
+impl Event {
+    #[must_use]
+    pub fn new(time: Millisecond, device_id: DeviceId, kind: EventKind) -> Self {
+        Self { time, device_id, kind }
+    }
+
+    #[must_use]
+    pub fn time(&self) -> Millisecond {
+        self.time
+    }
+
+    #[must_use]
+    pub fn device_id(&self) -> DeviceId {
+        self.device_id
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> &EventKind {
+        &self.kind
+    }
+}
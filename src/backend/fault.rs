@@ -0,0 +1,238 @@
+use std::fs;
+use std::path::Path;
+
+use rand::seq::IndexedRandom;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::connections::ConnectionGraph;
+use super::device::{Device, DeviceId, IdToDeviceMap};
+use super::mathphysics::Millisecond;
+use super::random;
+
+
+#[derive(Debug, Error)]
+pub enum FaultPlanError {
+    #[error("Failed to read fault plan: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to deserialize fault plan: {0}")]
+    Deserialization(#[from] serde_json::Error),
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub enum FaultKind {
+    // Every delivered signal is dropped with the probability.
+    SignalDrop(f64),
+    // The number of random network devices stop being updated.
+    DeviceFreeze(usize),
+    // Devices are disconnected in both directions.
+    EdgeCut(DeviceId, DeviceId),
+}
+
+
+// A fault that is active from `start_time` until `end_time`, or until the end
+// of the run without it.
+#[derive(Clone, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct Fault {
+    kind: FaultKind,
+    start_time: Millisecond,
+    #[serde(default)]
+    end_time: Option<Millisecond>,
+    // Devices are chosen when the fault becomes active.
+    #[serde(default)]
+    frozen_devices: Option<Vec<DeviceId>>,
+}
+
+impl Fault {
+    #[must_use]
+    pub fn new(
+        kind: FaultKind,
+        start_time: Millisecond,
+        end_time: Option<Millisecond>
+    ) -> Self {
+        Self { kind, start_time, end_time, frozen_devices: None }
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> &FaultKind {
+        &self.kind
+    }
+
+    #[must_use]
+    pub fn is_active_at(&self, time: Millisecond) -> bool {
+        self.start_time <= time
+            && self.end_time.is_none_or(|end_time| time < end_time)
+    }
+}
+
+
+// Injects scheduled faults into the network model, so robustness can be
+// tested without attacker devices.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct FaultInjector {
+    faults: Vec<Fault>,
+}
+
+impl FaultInjector {
+    #[must_use]
+    pub fn new(faults: Vec<Fault>) -> Self {
+        Self { faults }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the file can not be read or it does not contain
+    /// a JSON list of faults.
+    pub fn from_json(fault_plan_path: &Path) -> Result<Self, FaultPlanError> {
+        let json_string = fs::read_to_string(fault_plan_path)?;
+
+        Ok(Self::new(serde_json::from_str(&json_string)?))
+    }
+
+    #[must_use]
+    pub fn faults(&self) -> &[Fault] {
+        &self.faults
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.faults.is_empty()
+    }
+
+    // Chooses devices of freeze faults that become active. The command
+    // device is never frozen.
+    pub fn activate(
+        &mut self,
+        current_time: Millisecond,
+        device_map: &IdToDeviceMap,
+        command_device_id: DeviceId
+    ) {
+        for fault in &mut self.faults {
+            let FaultKind::DeviceFreeze(device_count) = fault.kind else {
+                continue;
+            };
+
+            if fault.frozen_devices.is_some()
+                || !fault.is_active_at(current_time)
+            {
+                continue;
+            }
+
+            let device_ids: Vec<DeviceId> = device_map
+                .values()
+                .map(Device::id)
+                .filter(|device_id| *device_id != command_device_id)
+                .collect();
+
+            fault.frozen_devices = Some(
+                random::with_rng(|rng|
+                    device_ids
+                        .choose_multiple(rng, device_count)
+                        .copied()
+                        .collect()
+                )
+            );
+        }
+    }
+
+    #[must_use]
+    pub fn drops_signal(&self, current_time: Millisecond) -> bool {
+        self.active_faults(current_time)
+            .filter_map(|fault| match fault.kind {
+                FaultKind::SignalDrop(drop_probability) =>
+                    Some(drop_probability.clamp(0.0, 1.0)),
+                _                                       => None,
+            })
+            .any(random::random_bool)
+    }
+
+    #[must_use]
+    pub fn is_frozen(
+        &self,
+        device_id: DeviceId,
+        current_time: Millisecond
+    ) -> bool {
+        self.active_faults(current_time)
+            .filter_map(|fault| fault.frozen_devices.as_ref())
+            .any(|frozen_devices| frozen_devices.contains(&device_id))
+    }
+
+    pub fn cut_edges(
+        &self,
+        connections: &mut ConnectionGraph,
+        current_time: Millisecond
+    ) {
+        for fault in self.active_faults(current_time) {
+            if let FaultKind::EdgeCut(device_id1, device_id2) = fault.kind {
+                connections.remove_connection(device_id1, device_id2);
+            }
+        }
+    }
+
+    fn active_faults(
+        &self,
+        current_time: Millisecond
+    ) -> impl Iterator<Item = &Fault> {
+        self.faults
+            .iter()
+            .filter(move |fault| fault.is_active_at(current_time))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::device::{DeviceBuilder, device_map_from_slice};
+
+    use super::*;
+
+
+    #[test]
+    fn fault_is_active_only_in_its_window() {
+        let fault = Fault::new(
+            FaultKind::SignalDrop(1.0),
+            Millisecond::new(100),
+            Some(Millisecond::new(200))
+        );
+
+        assert!(!fault.is_active_at(Millisecond::new(50)));
+        assert!(fault.is_active_at(Millisecond::new(100)));
+        assert!(!fault.is_active_at(Millisecond::new(200)));
+    }
+
+    #[test]
+    fn certain_signal_drop_drops_signals() {
+        let fault_injector = FaultInjector::new(vec![
+            Fault::new(FaultKind::SignalDrop(1.0), Millisecond::ZERO, None)
+        ]);
+
+        assert!(fault_injector.drops_signal(Millisecond::ZERO));
+        assert!(!FaultInjector::default().drops_signal(Millisecond::ZERO));
+    }
+
+    #[test]
+    fn device_freeze_does_not_freeze_command_device() {
+        let command_device = DeviceBuilder::new().build();
+        let drone = DeviceBuilder::new().build();
+        let device_map = device_map_from_slice(&[
+            command_device.clone(),
+            drone.clone()
+        ]);
+        let mut fault_injector = FaultInjector::new(vec![
+            Fault::new(FaultKind::DeviceFreeze(2), Millisecond::ZERO, None)
+        ]);
+
+        fault_injector.activate(
+            Millisecond::ZERO,
+            &device_map,
+            command_device.id()
+        );
+
+        assert!(
+            !fault_injector.is_frozen(command_device.id(), Millisecond::ZERO)
+        );
+        assert!(fault_injector.is_frozen(drone.id(), Millisecond::ZERO));
+    }
+}
@@ -0,0 +1,135 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::mathphysics::{Meter, Point3D, Position};
+
+
+// Designated area where drones land in an emergency, e.g. on critical
+// battery or after losing the command center for good. Drones above it land
+// right below, others aim at its center, which is on the ground.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct LandingZone {
+    center: Point3D,
+    radius: Meter,
+}
+
+impl LandingZone {
+    // Negative radius is treated as zero.
+    #[must_use]
+    pub fn new(center: Point3D, radius: Meter) -> Self {
+        Self { center, radius: radius.max(Meter::ZERO) }
+    }
+
+    #[must_use]
+    pub fn center(&self) -> &Point3D {
+        &self.center
+    }
+
+    #[must_use]
+    pub fn radius(&self) -> Meter {
+        self.radius
+    }
+
+    // Points above or below the zone are inside it too.
+    #[must_use]
+    pub fn contains(&self, point: &Point3D) -> bool {
+        let horizontal_distance = (point.x - self.center.x)
+            .hypot(point.y - self.center.y);
+
+        Meter::new(horizontal_distance) <= self.radius
+    }
+
+    // Point on the ground of the zone a drone at `position` lands on.
+    #[must_use]
+    pub fn landing_site_for(&self, position: &Point3D) -> Point3D {
+        if self.contains(position) {
+            Point3D::new(position.x, position.y, self.center.z)
+        } else {
+            self.center
+        }
+    }
+}
+
+impl Position for LandingZone {
+    fn position(&self) -> &Point3D {
+        &self.center
+    }
+}
+
+
+// The closest zone to `position` whose center is within `range`.
+#[must_use]
+pub fn nearest_reachable_zone<'a>(
+    landing_zones: &'a [LandingZone],
+    position: &Point3D,
+    range: Meter
+) -> Option<&'a LandingZone> {
+    landing_zones
+        .iter()
+        .map(|landing_zone|
+            (landing_zone, position.distance_to(landing_zone))
+        )
+        .filter(|(_, distance)| *distance <= range)
+        .min_by(|(_, distance1), (_, distance2)|
+            distance1.total_cmp(distance2)
+        )
+        .map(|(landing_zone, _)| landing_zone)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn zone_contains_points_above_it() {
+        let landing_zone = LandingZone::new(
+            Point3D::new(10.0, 0.0, 0.0),
+            Meter::new(5.0)
+        );
+
+        assert!(landing_zone.contains(&Point3D::new(13.0, 4.0, 50.0)));
+        assert!(!landing_zone.contains(&Point3D::new(16.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn drone_above_zone_lands_right_below() {
+        let landing_zone = LandingZone::new(
+            Point3D::new(10.0, 0.0, 0.0),
+            Meter::new(5.0)
+        );
+
+        assert_eq!(
+            landing_zone.landing_site_for(&Point3D::new(13.0, 4.0, 50.0)),
+            Point3D::new(13.0, 4.0, 0.0)
+        );
+        assert_eq!(
+            landing_zone.landing_site_for(&Point3D::new(16.0, 0.0, 50.0)),
+            *landing_zone.center()
+        );
+    }
+
+    #[test]
+    fn nearest_zone_within_range_is_chosen() {
+        let near = LandingZone::new(
+            Point3D::new(30.0, 0.0, 0.0),
+            Meter::new(5.0)
+        );
+        let far = LandingZone::new(
+            Point3D::new(-80.0, 0.0, 0.0),
+            Meter::new(5.0)
+        );
+        let landing_zones = [far, near];
+        let origin = Point3D::default();
+
+        assert_eq!(
+            nearest_reachable_zone(&landing_zones, &origin, Meter::new(100.0)),
+            Some(&near)
+        );
+        assert_eq!(
+            nearest_reachable_zone(&landing_zones, &origin, Meter::new(10.0)),
+            None
+        );
+    }
+}
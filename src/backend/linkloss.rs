@@ -0,0 +1,337 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::mathphysics::{Float, Point3D, Vector3D};
+use super::signal::{Signal, SignalStrength};
+
+
+const METERS_PER_KILOMETER: Float = 1_000.0;
+
+
+// Axis-aligned box of space in meters.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct Region {
+    min: Point3D,
+    max: Point3D,
+}
+
+impl Region {
+    // Corners may be given in any order.
+    #[must_use]
+    pub fn new(corner: Point3D, opposite_corner: Point3D) -> Self {
+        Self {
+            min: Point3D::new(
+                corner.x.min(opposite_corner.x),
+                corner.y.min(opposite_corner.y),
+                corner.z.min(opposite_corner.z),
+            ),
+            max: Point3D::new(
+                corner.x.max(opposite_corner.x),
+                corner.y.max(opposite_corner.y),
+                corner.z.max(opposite_corner.z),
+            ),
+        }
+    }
+
+    #[must_use]
+    pub fn min(&self) -> &Point3D {
+        &self.min
+    }
+
+    #[must_use]
+    pub fn max(&self) -> &Point3D {
+        &self.max
+    }
+
+    // Length of the segment part inside the region, found by clipping the
+    // segment with the slabs between region faces.
+    #[must_use]
+    pub fn crossed_length(&self, start: &Point3D, end: &Point3D) -> Float {
+        let slabs = [
+            (start.x, end.x, self.min.x, self.max.x),
+            (start.y, end.y, self.min.y, self.max.y),
+            (start.z, end.z, self.min.z, self.max.z),
+        ];
+        let mut entry: Float = 0.0;
+        let mut exit: Float = 1.0;
+
+        for (start, end, min, max) in slabs {
+            let direction = end - start;
+
+            if direction == 0.0 {
+                if start < min || start > max {
+                    return 0.0;
+                }
+                continue;
+            }
+
+            let near = (min - start) / direction;
+            let far = (max - start) / direction;
+
+            entry = entry.max(near.min(far));
+            exit = exit.min(near.max(far));
+        }
+
+        if exit <= entry {
+            return 0.0;
+        }
+
+        (exit - entry) * Vector3D::new(*start, *end).size()
+    }
+}
+
+
+// Building, terrain or other object that adds a fixed loss to every link
+// passing through it.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct Obstruction {
+    region: Region,
+    // Decibels.
+    loss: Float,
+}
+
+impl Obstruction {
+    #[must_use]
+    pub fn new(region: Region, loss: Float) -> Self {
+        Self { region, loss: loss.max(0.0) }
+    }
+
+    #[must_use]
+    pub fn region(&self) -> &Region {
+        &self.region
+    }
+
+    #[must_use]
+    pub fn loss(&self) -> Float {
+        self.loss
+    }
+}
+
+
+// Vegetation, smoke or other medium that attenuates links in proportion to
+// the path length inside it.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct AttenuationZone {
+    region: Region,
+    // Decibels per kilometer.
+    attenuation: Float,
+}
+
+impl AttenuationZone {
+    #[must_use]
+    pub fn new(region: Region, attenuation: Float) -> Self {
+        Self { region, attenuation: attenuation.max(0.0) }
+    }
+
+    #[must_use]
+    pub fn region(&self) -> &Region {
+        &self.region
+    }
+
+    #[must_use]
+    pub fn attenuation(&self) -> Float {
+        self.attenuation
+    }
+}
+
+
+// Additional losses of links between devices computed from the path of
+// each signal. They are applied on top of the strength given by the
+// transmitter, so they work with any propagation model.
+#[derive(
+    Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize
+)]
+pub struct LinkLoss {
+    #[serde(default)]
+    obstructions: Vec<Obstruction>,
+    #[serde(default)]
+    attenuation_zones: Vec<AttenuationZone>,
+    // Decibels per meter of altitude difference between link ends.
+    #[serde(default)]
+    altitude_loss: Float,
+}
+
+impl LinkLoss {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn set_obstructions(mut self, obstructions: Vec<Obstruction>) -> Self {
+        self.obstructions = obstructions;
+        self
+    }
+
+    #[must_use]
+    pub fn set_attenuation_zones(
+        mut self,
+        attenuation_zones: Vec<AttenuationZone>
+    ) -> Self {
+        self.attenuation_zones = attenuation_zones;
+        self
+    }
+
+    // Negative values are treated as zero.
+    #[must_use]
+    pub fn set_altitude_loss(mut self, altitude_loss: Float) -> Self {
+        self.altitude_loss = altitude_loss.max(0.0);
+        self
+    }
+
+    #[must_use]
+    pub fn obstructions(&self) -> &[Obstruction] {
+        &self.obstructions
+    }
+
+    #[must_use]
+    pub fn attenuation_zones(&self) -> &[AttenuationZone] {
+        &self.attenuation_zones
+    }
+
+    #[must_use]
+    pub fn altitude_loss(&self) -> Float {
+        self.altitude_loss
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.obstructions.is_empty()
+            && self.attenuation_zones.is_empty()
+            && self.altitude_loss <= 0.0
+    }
+
+    // Total loss in decibels of the path between two points.
+    #[must_use]
+    pub fn loss_between(&self, start: &Point3D, end: &Point3D) -> Float {
+        let obstruction_loss: Float = self.obstructions
+            .iter()
+            .filter(|obstruction|
+                obstruction.region.crossed_length(start, end) > 0.0
+            )
+            .map(Obstruction::loss)
+            .sum();
+        let zone_loss: Float = self.attenuation_zones
+            .iter()
+            .map(|zone|
+                zone.attenuation
+                    * zone.region.crossed_length(start, end)
+                    / METERS_PER_KILOMETER
+            )
+            .sum();
+        let altitude_loss = self.altitude_loss * (end.z - start.z).abs();
+
+        obstruction_loss + zone_loss + altitude_loss
+    }
+
+    // Strength of the signal that travelled from `start` to `end`.
+    #[must_use]
+    pub fn attenuate(
+        &self,
+        signal: Signal,
+        start: &Point3D,
+        end: &Point3D
+    ) -> Signal {
+        let loss_in_db = self.loss_between(start, end);
+
+        if loss_in_db <= 0.0 {
+            return signal;
+        }
+
+        let strength_value = signal.strength().value()
+            * Float::powf(10.0, -loss_in_db / 10.0);
+
+        signal.with_strength(SignalStrength::new(strength_value))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::mathphysics::Frequency;
+    use crate::backend::signal::{Data, GREEN_SIGNAL_STRENGTH};
+
+    use super::*;
+
+
+    fn building() -> Region {
+        Region::new(
+            Point3D::new(10.0, -10.0, 0.0),
+            Point3D::new(20.0, 10.0, 30.0)
+        )
+    }
+
+
+    #[test]
+    fn crossed_length_is_clipped_by_region() {
+        let region = building();
+
+        assert_eq!(
+            region.crossed_length(
+                &Point3D::new(0.0, 0.0, 10.0),
+                &Point3D::new(40.0, 0.0, 10.0)
+            ),
+            10.0
+        );
+        assert_eq!(
+            region.crossed_length(
+                &Point3D::new(0.0, 0.0, 40.0),
+                &Point3D::new(40.0, 0.0, 40.0)
+            ),
+            0.0
+        );
+        assert_eq!(
+            region.crossed_length(
+                &Point3D::new(0.0, 0.0, 10.0),
+                &Point3D::new(5.0, 0.0, 10.0)
+            ),
+            0.0
+        );
+    }
+
+    #[test]
+    fn losses_are_summed() {
+        let link_loss = LinkLoss::new()
+            .set_obstructions(vec![Obstruction::new(building(), 20.0)])
+            .set_attenuation_zones(vec![
+                AttenuationZone::new(building(), 1_000.0)
+            ])
+            .set_altitude_loss(0.5);
+        let loss = link_loss.loss_between(
+            &Point3D::new(0.0, 0.0, 10.0),
+            &Point3D::new(40.0, 0.0, 10.0)
+        );
+        let climbing_loss = link_loss.loss_between(
+            &Point3D::new(0.0, 0.0, 40.0),
+            &Point3D::new(0.0, 0.0, 50.0)
+        );
+
+        assert!((loss - 30.0).abs() < 1e-3);
+        assert!((climbing_loss - 5.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn obstructed_signal_is_weaker() {
+        let link_loss = LinkLoss::new()
+            .set_obstructions(vec![Obstruction::new(building(), 10.0)]);
+        let signal = Signal::new(
+            0,
+            1,
+            Data::Noise,
+            Frequency::Control,
+            GREEN_SIGNAL_STRENGTH
+        );
+        let start = Point3D::new(0.0, 0.0, 10.0);
+
+        assert_eq!(
+            link_loss.attenuate(signal, &start, &Point3D::new(5.0, 0.0, 10.0)),
+            signal
+        );
+        assert!(
+            *link_loss
+                .attenuate(signal, &start, &Point3D::new(40.0, 0.0, 10.0))
+                .strength()
+                < GREEN_SIGNAL_STRENGTH
+        );
+    }
+}
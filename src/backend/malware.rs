@@ -1,11 +1,14 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
 
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
 use serde::{self, Serialize};
 use serde::ser::Serializer;
 use serde::de::{self, Deserialize, Deserializer};
 use thiserror::Error;
 
+use super::device::systems::{FirmwareVersion, VersionRange};
 use super::mathphysics::{Millisecond, PowerUnit};
 
 
@@ -17,12 +20,18 @@ pub type InfectionMap = HashMap<Malware, Millisecond>;
 const MALWARE_DISPLAY_DELIMITER: &str         = "-";
 const MALWARE_DISPLAY_SPREAD_DELAY_NONE: &str = "None";
 
+// Malware is serialized as "<type>-<infection delay>-<spread delay>" followed
+// by "-<first>..<last>" if it targets only these firmware versions.
+const MALWARE_SCHEMA_PATTERN: &str = 
+    r"^(Indicator|DoS\([^)]+\))-[0-9]+-([0-9]+|None)(-[0-9]+\.\.[0-9]+)?$";
+
 const ERR_MISSING_MW_TYPE: &str      = "Missing malware type";
 const ERR_MISSING_INF_DELAY: &str    = "Missing infection delay";
 const ERR_MISSING_SPREAD_DELAY: &str = "Missing spread delay";
 const ERR_PARSE_MW_TYPE: &str        = "Failed to parse malware type";
 const ERR_PARSE_INF_DELAY: &str      = "Failed to parse infection delay";
 const ERR_PARSE_SPREAD_DELAY: &str   = "Failed to parse spread delay";
+const ERR_PARSE_VERSIONS: &str       = "Failed to parse target versions";
 
 
 #[derive(Debug, Error)]
@@ -87,6 +96,8 @@ pub struct Malware {
     malware_type: MalwareType,
     infection_delay: Millisecond,
     spread_delay: Option<Millisecond>, // If `None`, malware does not spread.
+    // If `None`, malware infects devices with any firmware version.
+    target_versions: Option<VersionRange>,
 }
 
 impl Malware {
@@ -95,11 +106,13 @@ impl Malware {
         malware_type: MalwareType,
         infection_delay: Millisecond,
         spread_delay: Option<Millisecond>,
+        target_versions: Option<VersionRange>,
     ) -> Self {
         Self {
             malware_type,
             infection_delay,
             spread_delay,
+            target_versions,
         }
     }
 
@@ -122,6 +135,17 @@ impl Malware {
     pub fn spread_delay(&self) -> Option<Millisecond> {
         self.spread_delay
     }
+
+    #[must_use]
+    pub fn target_versions(&self) -> Option<&VersionRange> {
+        self.target_versions.as_ref()
+    }
+
+    #[must_use]
+    pub fn targets(&self, version: FirmwareVersion) -> bool {
+        self.target_versions
+            .is_none_or(|target_versions| target_versions.contains(version))
+    }
 }
 
 impl fmt::Display for Malware {
@@ -131,7 +155,7 @@ impl fmt::Display for Malware {
             None               => MALWARE_DISPLAY_SPREAD_DELAY_NONE,
         };
 
-        let malware_string = format!(
+        let mut malware_string = format!(
             "{}{}{}{}{}",
             self.malware_type,
             MALWARE_DISPLAY_DELIMITER,
@@ -140,6 +164,11 @@ impl fmt::Display for Malware {
             spread_delay_str,
         );
 
+        if let Some(target_versions) = self.target_versions {
+            malware_string.push_str(MALWARE_DISPLAY_DELIMITER);
+            malware_string.push_str(&target_versions.to_string());
+        }
+
         write!(f, "{malware_string}")
     }
 }
@@ -153,12 +182,25 @@ impl Serialize for Malware {
     }
 }
 
+impl JsonSchema for Malware {
+    fn schema_name() -> Cow<'static, str> {
+        "Malware".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": MALWARE_SCHEMA_PATTERN,
+        })
+    }
+}
+
 impl<'de> Deserialize<'de> for Malware {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let data = <&str>::deserialize(deserializer)?;
+        let data = String::deserialize(deserializer)?;
         let mut parts = data.split(MALWARE_DISPLAY_DELIMITER);
         
         let malware_type = parts
@@ -183,6 +225,19 @@ impl<'de> Deserialize<'de> for Malware {
                     .map_err(|_| de::Error::custom(ERR_PARSE_SPREAD_DELAY))
             )?;
 
-        Ok(Self { malware_type, infection_delay, spread_delay })
+        let target_versions = parts
+            .next()
+            .map(|target_versions_str| target_versions_str
+                .parse::<VersionRange>()
+                .map_err(|_| de::Error::custom(ERR_PARSE_VERSIONS))
+            )
+            .transpose()?;
+
+        Ok(Self { 
+            malware_type, 
+            infection_delay, 
+            spread_delay, 
+            target_versions 
+        })
     }
 }
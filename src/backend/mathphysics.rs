@@ -1,13 +1,17 @@
+use float_consts::{PI, TAU};
+
 use super::ITERATION_TIME;
 
 
 pub use frequency::Frequency;
+pub use orientation::Orientation;
 pub use point::Point3D;
 pub use unit::*;
 pub use vector::Vector3D;
 
 
 pub mod frequency;
+pub mod orientation;
 pub mod point;
 pub mod unit;
 pub mod vector;
@@ -16,11 +20,11 @@ pub mod vector;
 #[must_use]
 pub fn delay_to(distance: Meter, multiplier: f32) -> Millisecond {    
     if multiplier == 0.0 {
-        return 0;
+        return Millisecond::ZERO;
     }
 
     let delay = time_in_millis_from_distance_and_speed(
-        distance * multiplier as Meter,
+        distance * Float::from(multiplier),
         kmps_to_mpms(SPEED_OF_LIGHT) 
     );
     let reminder = delay % ITERATION_TIME;
@@ -28,13 +32,19 @@ pub fn delay_to(distance: Meter, multiplier: f32) -> Millisecond {
     delay - reminder
 }
 
+// Angle in range [-PI, PI).
+#[must_use]
+pub fn wrap_angle(angle: Radian) -> Radian {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
 #[must_use]
 pub fn equation_of_motion_1d(
     start_position: Meter,
     velocity: MeterPerSecond,
     time: Second
 ) -> Meter {
-    velocity.mul_add(time, start_position)
+    Meter::new(velocity.mul_add(Float::from(time), start_position.value()))
 }
 
 #[must_use]
@@ -43,10 +53,13 @@ pub fn equation_of_motion_3d(
     velocity: &Point3D,
     time: Second
 ) -> Point3D {
+    let coordinate = |start: Float, velocity: MeterPerSecond|
+        equation_of_motion_1d(Meter::new(start), velocity, time).value();
+
     Point3D::new(
-        equation_of_motion_1d(start_position.x, velocity.x, time),
-        equation_of_motion_1d(start_position.y, velocity.y, time),
-        equation_of_motion_1d(start_position.z, velocity.z, time),
+        coordinate(start_position.x, velocity.x),
+        coordinate(start_position.y, velocity.y),
+        coordinate(start_position.z, velocity.z),
     )
 }
 
@@ -54,10 +67,10 @@ pub fn equation_of_motion_3d(
 pub trait Position {
     fn position(&self) -> &Point3D;
 
-    fn distance_to<P: Position>(&self, other: &P) -> f32 {
+    fn distance_to<P: Position>(&self, other: &P) -> Meter {
         let vector = Vector3D::new(*self.position(), *other.position());
         
-        vector.size()
+        Meter::new(vector.size())
     }
 }
 
@@ -72,7 +85,16 @@ mod tests {
         let origin = Point3D::default();
         let some_point = Point3D::new(5.0, 0.0, 0.0);
 
-        assert_eq!(0.0, origin.distance_to(&origin));
-        assert_eq!(5.0, origin.distance_to(&some_point));
+        assert_eq!(Meter::new(0.0), origin.distance_to(&origin));
+        assert_eq!(Meter::new(5.0), origin.distance_to(&some_point));
+    }
+
+    #[cfg(feature = "f64")]
+    #[test]
+    fn distance_between_far_points_keeps_precision() {
+        let far_point = Point3D::new(1.0e7, 0.0, 0.0);
+        let nearby_point = Point3D::new(1.0e7 + 0.25, 0.0, 0.0);
+
+        assert_eq!(Meter::new(0.25), far_point.distance_to(&nearby_point));
     }
 }
@@ -1,11 +1,29 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 
 // The representation type needs to be updated if the `Megahertz` type is 
 // changed.
 #[repr(u32)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, 
+    JsonSchema, Serialize
+)]
 pub enum Frequency {
     Control = 2_400,
+    // GPS L1 band.
     GPS     = 1_575,
+    // Control channel devices switch to when `Control` is jammed.
+    Backup  = 5_800,
+    GPSL2   = 1_227,
+    GPSL5   = 1_176,
+}
+
+impl Frequency {
+    pub const GPS_BANDS: [Self; 3] = [Self::GPS, Self::GPSL2, Self::GPSL5];
+
+    #[must_use]
+    pub fn is_gps(self) -> bool {
+        Self::GPS_BANDS.contains(&self)
+    }
 }
@@ -0,0 +1,149 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::point::Point3D;
+use super::wrap_angle;
+use super::unit::{Float, MeterPerSecond, Radian, Second};
+use super::vector::Vector3D;
+
+
+const GRAVITATIONAL_ACCELERATION: Float = 9.81;
+// Slower devices hover, so they keep their heading and stay level.
+const MIN_ORIENTATION_SPEED: MeterPerSecond = 0.1;
+
+
+// Yaw is measured counterclockwise from `x` axis, pitch is positive when
+// climbing and roll is positive when banking into a counterclockwise turn.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize
+)]
+pub struct Orientation {
+    pub yaw: Radian,
+    pub pitch: Radian,
+    pub roll: Radian,
+}
+
+impl Orientation {
+    #[must_use]
+    pub fn new(yaw: Radian, pitch: Radian, roll: Radian) -> Self {
+        Self { yaw, pitch, roll }
+    }
+
+    // Unit vector the device is pointed along.
+    #[must_use]
+    pub fn forward(&self) -> Point3D {
+        Point3D::new(
+            self.pitch.cos() * self.yaw.cos(),
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+        )
+    }
+
+    // The nose follows the velocity, while roll is the bank angle of a
+    // coordinated turn with the yaw rate over `time`.
+    pub fn follow_velocity(&mut self, velocity: &Vector3D, time: Second) {
+        let displacement = velocity.displacement();
+        let horizontal_speed = displacement.x.hypot(displacement.y);
+
+        if velocity.size() < MIN_ORIENTATION_SPEED {
+            self.pitch = 0.0;
+            self.roll = 0.0;
+            return;
+        }
+
+        self.pitch = displacement.z.atan2(horizontal_speed);
+
+        if horizontal_speed < MIN_ORIENTATION_SPEED {
+            self.roll = 0.0;
+            return;
+        }
+
+        let yaw = displacement.y.atan2(displacement.x);
+        let yaw_rate = if time > 0.0 {
+            wrap_angle(yaw - self.yaw) / Float::from(time)
+        } else {
+            0.0
+        };
+
+        self.yaw = yaw;
+        self.roll = (horizontal_speed * yaw_rate / GRAVITATIONAL_ACCELERATION)
+            .atan();
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::mathphysics::float_consts::FRAC_PI_2;
+
+    use super::*;
+
+
+    const ITERATION_SECONDS: Second = 0.05;
+
+
+    fn velocity(x: Float, y: Float, z: Float) -> Vector3D {
+        Vector3D::new(Point3D::default(), Point3D::new(x, y, z))
+    }
+
+
+    #[test]
+    fn level_flight_along_y() {
+        let mut orientation = Orientation::new(FRAC_PI_2, 0.0, 0.0);
+
+        orientation.follow_velocity(
+            &velocity(0.0, 10.0, 0.0), 
+            ITERATION_SECONDS
+        );
+
+        assert!((orientation.yaw - FRAC_PI_2).abs() < 1e-6);
+        assert_eq!(orientation.pitch, 0.0);
+        assert_eq!(orientation.roll, 0.0);
+    }
+
+    #[test]
+    fn climbing_pitches_nose_up() {
+        let mut orientation = Orientation::default();
+
+        orientation.follow_velocity(
+            &velocity(5.0, 0.0, 5.0), 
+            ITERATION_SECONDS
+        );
+
+        assert!((orientation.pitch - FRAC_PI_2 / 2.0).abs() < 1e-6);
+        assert!(orientation.forward().z > 0.0);
+    }
+
+    #[test]
+    fn counterclockwise_turn_rolls_positive() {
+        let mut orientation = Orientation::default();
+
+        orientation.follow_velocity(
+            &velocity(10.0, 0.0, 0.0), 
+            ITERATION_SECONDS
+        );
+        orientation.follow_velocity(
+            &velocity(10.0, 1.0, 0.0), 
+            ITERATION_SECONDS
+        );
+
+        assert!(orientation.roll > 0.0);
+    }
+
+    #[test]
+    fn hovering_keeps_heading() {
+        let mut orientation = Orientation::default();
+
+        orientation.follow_velocity(
+            &velocity(0.0, 10.0, 0.0), 
+            ITERATION_SECONDS
+        );
+        orientation.follow_velocity(
+            &Vector3D::default(), 
+            ITERATION_SECONDS
+        );
+
+        assert!((orientation.yaw - FRAC_PI_2).abs() < 1e-6);
+        assert_eq!(orientation.roll, 0.0);
+    }
+}
@@ -1,29 +1,31 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use derive_more::{Add, Div, DivAssign, Mul, MulAssign, Sub};
 
 use super::Position;
+use super::unit::{float_to_f64, Float};
 
 
 #[derive(
     Copy, Clone, PartialEq, Add, Sub, Mul, MulAssign, Div, DivAssign, Debug, 
-    Default, Serialize, Deserialize,
+    Default, JsonSchema, Serialize, Deserialize,
 )]
 pub struct Point3D { 
-    pub x: f32, 
-    pub y: f32, 
-    pub z: f32, 
+    pub x: Float, 
+    pub y: Float, 
+    pub z: Float, 
 }
 
 impl Point3D {
     #[must_use]
-    pub fn new(x: f32, y: f32, z: f32) -> Self {
+    pub fn new(x: Float, y: Float, z: Float) -> Self {
         Self { x, y, z }
     }
 }
 
-impl From<(f32, f32, f32)> for Point3D {
-    fn from(value: (f32, f32, f32)) -> Self {
+impl From<(Float, Float, Float)> for Point3D {
+    fn from(value: (Float, Float, Float)) -> Self {
         Self { 
             x: value.0, 
             y: value.1, 
@@ -35,9 +37,9 @@ impl From<(f32, f32, f32)> for Point3D {
 impl From<Point3D> for (f64, f64, f64) {
     fn from(point: Point3D) -> Self {
         (
-            f64::from(point.x), 
-            f64::from(point.y), 
-            f64::from(point.z), 
+            float_to_f64(point.x), 
+            float_to_f64(point.y), 
+            float_to_f64(point.z), 
         )
     }
 }
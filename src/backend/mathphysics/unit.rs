@@ -1,22 +1,312 @@
-pub type Millisecond = i32;
+use std::ops::{Div, Rem};
+
+use derive_more::{
+    Add, AddAssign, Display, FromStr, Mul, Neg, Sub, SubAssign, Sum
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+
+// Precision of coordinates, distances and the motion and signal math on
+// them. The `f64` feature keeps long simulations over large areas from
+// accumulating rounding error in positions.
+#[cfg(not(feature = "f64"))]
+pub type Float = f32;
+#[cfg(feature = "f64")]
+pub type Float = f64;
+
+#[cfg(not(feature = "f64"))]
+pub use std::f32::consts as float_consts;
+#[cfg(feature = "f64")]
+pub use std::f64::consts as float_consts;
+
 pub type Second = f32;
-pub type Meter = f32;
-pub type KilometerPerSecond = f32;
-pub type MeterPerMillisecond = f32;
-pub type MeterPerSecond = f32;
+pub type Celsius = f32;
+pub type Kilogram = f32;
+pub type KilometerPerSecond = Float;
+pub type MeterPerMillisecond = Float;
+pub type MeterPerSecond = Float;
 pub type Megahertz = u32;
-pub type PowerUnit = u32;
+pub type Radian = Float;
+pub type RadianPerSecond = Float;
+
+
+// Distances, radii and ranges. Unlike the aliases above, it is a distinct
+// type, so it cannot be silently passed as a speed or a plain factor.
+#[derive(
+    Clone, Copy, Debug, Display, Default, Add, Sub, Mul, Neg, AddAssign,
+    SubAssign, Sum, FromStr, PartialEq, PartialOrd, JsonSchema, Serialize,
+    Deserialize
+)]
+#[display("{_0}")]
+pub struct Meter(Float);
+
+impl Meter {
+    pub const ZERO: Self = Self(0.0);
+
+    #[must_use]
+    pub const fn new(value: Float) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> Float {
+        self.0
+    }
+
+    #[must_use]
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    #[must_use]
+    pub fn total_cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Mul<Meter> for Float {
+    type Output = Meter;
+
+    fn mul(self, meters: Meter) -> Meter {
+        Meter(self * meters.0)
+    }
+}
+
+impl Div<Float> for Meter {
+    type Output = Self;
+
+    fn div(self, divisor: Float) -> Self {
+        Self(self.0 / divisor)
+    }
+}
+
+// Ratio of two distances, which has no unit.
+impl Div for Meter {
+    type Output = Float;
+
+    fn div(self, divisor: Self) -> Float {
+        self.0 / divisor.0
+    }
+}
+
+
+// Model time and delays.
+#[derive(
+    Clone, Copy, Debug, Display, Default, Add, Sub, Mul, Neg, AddAssign,
+    SubAssign, Sum, FromStr, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema,
+    Serialize, Deserialize
+)]
+#[display("{_0}")]
+pub struct Millisecond(i32);
+
+impl Millisecond {
+    pub const ZERO: Self = Self(0);
+
+    #[must_use]
+    pub const fn new(value: i32) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> i32 {
+        self.0
+    }
+}
+
+impl Mul<Millisecond> for i32 {
+    type Output = Millisecond;
+
+    fn mul(self, millis: Millisecond) -> Millisecond {
+        Millisecond(self * millis.0)
+    }
+}
+
+// Number of whole periods, e.g. `time / ITERATION_TIME` iterations.
+impl Div for Millisecond {
+    type Output = i32;
+
+    fn div(self, period: Self) -> i32 {
+        self.0 / period.0
+    }
+}
+
+// Time within a period, e.g. `time % ITERATION_TIME`. It is not negative
+// for times before 0 either.
+impl Rem for Millisecond {
+    type Output = Self;
+
+    fn rem(self, period: Self) -> Self {
+        Self(self.0.rem_euclid(period.0))
+    }
+}
+
+
+// Energy, e.g. battery charge or energy drawn by a consumer.
+#[derive(
+    Clone, Copy, Debug, Display, Default, Add, Sub, Mul, Neg, AddAssign,
+    SubAssign, Sum, FromStr, PartialEq, PartialOrd, JsonSchema, Serialize,
+    Deserialize
+)]
+#[display("{_0}")]
+pub struct Joule(f32);
+
+impl Joule {
+    pub const ZERO: Self = Self(0.0);
+
+    #[must_use]
+    pub const fn new(value: f32) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> f32 {
+        self.0
+    }
+
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    #[must_use]
+    pub fn min(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+}
+
+impl Mul<Joule> for f32 {
+    type Output = Joule;
+
+    fn mul(self, energy: Joule) -> Joule {
+        Joule(self * energy.0)
+    }
+}
+
+impl Div<f32> for Joule {
+    type Output = Self;
+
+    fn div(self, divisor: f32) -> Self {
+        Self(self.0 / divisor)
+    }
+}
+
+// Ratio of two energies, e.g. the battery level.
+impl Div for Joule {
+    type Output = f32;
+
+    fn div(self, divisor: Self) -> f32 {
+        self.0 / divisor.0
+    }
+}
+
+// Time the energy lasts for at the power.
+impl Div<Watt> for Joule {
+    type Output = Second;
+
+    fn div(self, power: Watt) -> Second {
+        self.0 / power.0
+    }
+}
+
+
+// Power drawn by a consumer. It is scaled from the left, as multiplying it by
+// `Second` gives energy.
+#[derive(
+    Clone, Copy, Debug, Display, Default, Add, Sub, Neg, AddAssign,
+    SubAssign, Sum, FromStr, PartialEq, PartialOrd, JsonSchema, Serialize,
+    Deserialize
+)]
+#[display("{_0}")]
+pub struct Watt(f32);
+
+impl Watt {
+    pub const ZERO: Self = Self(0.0);
+
+    #[must_use]
+    pub const fn new(value: f32) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> f32 {
+        self.0
+    }
+}
+
+impl Mul<Watt> for f32 {
+    type Output = Watt;
+
+    fn mul(self, power: Watt) -> Watt {
+        Watt(self * power.0)
+    }
+}
+
+// Energy drawn over the time.
+impl Mul<Second> for Watt {
+    type Output = Joule;
+
+    fn mul(self, time: Second) -> Joule {
+        Joule(self.0 * time)
+    }
+}
+
+
+// Integer battery charge of the older power model. One unit is one Joule.
+#[derive(
+    Clone, Copy, Debug, Display, Default, Add, Sub, AddAssign, SubAssign, Sum,
+    FromStr, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema, Serialize,
+    Deserialize
+)]
+#[display("{_0}")]
+pub struct PowerUnit(u32);
+
+impl PowerUnit {
+    #[must_use]
+    pub const fn new(value: u32) -> Self {
+        Self(value)
+    }
+
+    #[must_use]
+    pub const fn value(&self) -> u32 {
+        self.0
+    }
+}
 
 
 // Const for conversion from km / (s * MHz) to m / (s * Hz).
-const CONVERSION_CONST: f32 = 1_000.0;
+const CONVERSION_CONST: Float = 1_000.0;
 
 pub const SPEED_OF_LIGHT: KilometerPerSecond = 300_000.0;
 
 
+// Leave the model precision for the `f32` math of power and heat or for
+// outputs with a fixed precision. Conversions to the same type do nothing.
+#[must_use]
+#[allow(clippy::unnecessary_cast)]
+pub fn float_to_f32(value: Float) -> f32 {
+    value as f32
+}
+
+#[must_use]
+#[allow(clippy::useless_conversion)]
+pub fn float_to_f64(value: Float) -> f64 {
+    f64::from(value)
+}
+
 #[must_use]
 pub fn millis_to_secs(millis: Millisecond) -> Second {
-    millis as Second / 1_000.0 
+    millis.value() as Second / 1_000.0 
 }
 
 #[must_use]
@@ -29,10 +319,12 @@ pub fn time_in_millis_from_distance_and_speed(
     distance: Meter, 
     speed: MeterPerMillisecond
 ) -> Millisecond {
-    (distance / speed).round() as Millisecond
+    Millisecond((distance.value() / speed).round() as i32)
 }
 
 #[must_use]
 pub fn wave_length_in_meters(frequency: Megahertz) -> Meter {
-    SPEED_OF_LIGHT / (frequency as KilometerPerSecond * CONVERSION_CONST) 
+    Meter(
+        SPEED_OF_LIGHT / (frequency as KilometerPerSecond * CONVERSION_CONST)
+    )
 }
@@ -1,12 +1,14 @@
 use derive_more::{Add, Sub, Mul, Div, MulAssign, DivAssign};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::point::Point3D;
+use super::unit::Float;
 
 
 #[derive(
     Copy, Clone, PartialEq, Add, Sub, Mul, Div, MulAssign, DivAssign, Debug, 
-    Default, Serialize, Deserialize
+    Default, JsonSchema, Serialize, Deserialize
 )]
 pub struct Vector3D {
     pub initial_point: Point3D,
@@ -25,7 +27,7 @@ impl Vector3D {
     }
 
     #[must_use]
-    pub fn size(&self) -> f32 {
+    pub fn size(&self) -> Float {
         let displacement = self.displacement();
         
         (
@@ -43,13 +45,13 @@ impl Vector3D {
         }
     }
 
-    pub fn truncate(&mut self, truncated_size: f32) { 
+    pub fn truncate(&mut self, truncated_size: Float) { 
         if truncated_size < self.size() {
             self.scale_to(truncated_size);
         }
     }
 
-    pub fn scale_to(&mut self, scaled_size: f32) {
+    pub fn scale_to(&mut self, scaled_size: Float) {
         if scaled_size < 0.0 {
             return; 
         } else if scaled_size == 0.0 {
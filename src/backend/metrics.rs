@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+
+use super::coverage::Coverage;
+use super::device::{Device, BROADCAST_ID};
+use super::mathphysics::Millisecond;
+use super::networkmodel::NetworkModel;
+
+
+pub const CSV_HEADER: &str = 
+    "time,alive_count,infected_count,mean_battery_level,reachable_fraction,\
+    sensor_coverage,control_coverage,aggregation_ratio";
+
+
+// Aggregated state of network drones at one iteration. The command device is
+// not counted as a drone. Coverage is only computed if the model has a
+// coverage area, the aggregation ratio once relays sent a summary.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Metrics {
+    time: Millisecond,
+    alive_count: usize,
+    infected_count: usize,
+    mean_battery_level: f32,
+    reachable_fraction: f32,
+    sensor_coverage: Option<f32>,
+    control_coverage: Option<f32>,
+    aggregation_ratio: Option<f32>,
+}
+
+impl Metrics {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_model(network_model: &NetworkModel) -> Self {
+        let drones: Vec<&Device> = network_model.drones().collect();
+        let coverage = network_model
+            .coverage_area()
+            .map(|coverage_area| coverage_area.coverage(network_model));
+        let sensor_coverage = coverage.as_ref().map(Coverage::sensor);
+        let control_coverage = coverage.as_ref().map(Coverage::control);
+        let aggregation_ratio = network_model.aggregator().ratio();
+
+        if drones.is_empty() {
+            return Self {
+                time: network_model.current_time(),
+                sensor_coverage,
+                control_coverage,
+                aggregation_ratio,
+                ..Default::default()
+            };
+        }
+
+        let drone_count = drones.len() as f32;
+
+        let alive_count = drones
+            .iter()
+            .filter(|drone| !drone.is_shut_down())
+            .count();
+        let infected_count = drones
+            .iter()
+            .filter(|drone| drone.is_infected())
+            .count();
+        let mean_battery_level = drones
+            .iter()
+            .map(|drone| drone.power_system().level())
+            .sum::<f32>() / drone_count;
+
+        // Dijkstra never fails; missing command device means nothing is
+        // reachable.
+        let reachable_ids = network_model
+            .connections()
+            .dijkstra(network_model.command_device_id(), BROADCAST_ID)
+            .unwrap_or_default();
+        let reachable_count = drones
+            .iter()
+            .filter(|drone| reachable_ids.contains_key(&drone.id()))
+            .count();
+
+        Self {
+            time: network_model.current_time(),
+            alive_count,
+            infected_count,
+            mean_battery_level,
+            reachable_fraction: reachable_count as f32 / drone_count,
+            sensor_coverage,
+            control_coverage,
+            aggregation_ratio,
+        }
+    }
+
+    #[must_use]
+    pub fn time(&self) -> Millisecond {
+        self.time
+    }
+
+    #[must_use]
+    pub fn alive_count(&self) -> usize {
+        self.alive_count
+    }
+
+    #[must_use]
+    pub fn infected_count(&self) -> usize {
+        self.infected_count
+    }
+
+    #[must_use]
+    pub fn mean_battery_level(&self) -> f32 {
+        self.mean_battery_level
+    }
+
+    #[must_use]
+    pub fn reachable_fraction(&self) -> f32 {
+        self.reachable_fraction
+    }
+
+    #[must_use]
+    pub fn sensor_coverage(&self) -> Option<f32> {
+        self.sensor_coverage
+    }
+
+    #[must_use]
+    pub fn control_coverage(&self) -> Option<f32> {
+        self.control_coverage
+    }
+
+    // Reconnaissance records per summary relays sent.
+    #[must_use]
+    pub fn aggregation_ratio(&self) -> Option<f32> {
+        self.aggregation_ratio
+    }
+
+    // Fields are written in the order of `CSV_HEADER`, missing values are
+    // left empty.
+    #[must_use]
+    pub fn to_csv_record(&self) -> String {
+        let optional = |value: Option<f32>| value
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+
+        format!(
+            "{},{},{},{},{},{},{},{}",
+            self.time,
+            self.alive_count,
+            self.infected_count,
+            self.mean_battery_level,
+            self.reachable_fraction,
+            optional(self.sensor_coverage),
+            optional(self.control_coverage),
+            optional(self.aggregation_ratio)
+        )
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::connections::Topology;
+    use crate::backend::coverage::CoverageArea;
+    use crate::backend::device::{DeviceBuilder, device_map_from_slice};
+    use crate::backend::device::systems::{
+        PowerSystem, RXModule, TRXSystem, TXModule
+    };
+    use crate::backend::mathphysics::{
+        Frequency, Megahertz, Meter, Point3D, PowerUnit
+    };
+    use crate::backend::networkmodel::NetworkModelBuilder;
+    use crate::backend::signal::{FreqToStrengthMap, SignalStrength};
+
+    use super::*;
+
+
+    const MAX_POWER: PowerUnit = PowerUnit::new(100);
+
+
+    fn drone(position: Point3D, power: PowerUnit) -> Device {
+        DeviceBuilder::new()
+            .set_real_position(position)
+            .set_power_system(
+                PowerSystem::build(MAX_POWER, power)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .build()
+    }
+
+    fn network_model() -> NetworkModel {
+        let tx_signal_strength = SignalStrength::from_area_radius(
+            Meter::new(50.0),
+            Frequency::Control as Megahertz
+        );
+        let command_center = DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(MAX_POWER, MAX_POWER)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_trx_system(
+                TRXSystem::new(
+                    TXModule::new(
+                        FreqToStrengthMap::from([
+                            (Frequency::Control, tx_signal_strength)
+                        ])
+                    ),
+                    RXModule::default()
+                )
+            )
+            .build();
+        let command_center_id = command_center.id();
+
+        let devices = [
+            command_center,
+            drone(Point3D::new(10.0, 0.0, 0.0), MAX_POWER),
+            drone(Point3D::new(0.0, 10.0, 0.0), PowerUnit::new(50)),
+            drone(Point3D::new(500.0, 0.0, 0.0), PowerUnit::new(0)),
+        ];
+
+        NetworkModelBuilder::new()
+            .set_command_center_id(command_center_id)
+            .set_device_map(device_map_from_slice(&devices))
+            .set_topology(Topology::Star)
+            .build()
+    }
+
+
+    #[test]
+    fn metrics_describe_drones() {
+        let metrics = Metrics::from_model(&network_model());
+
+        assert_eq!(metrics.time(), Millisecond::ZERO);
+        assert_eq!(metrics.alive_count(), 2);
+        assert_eq!(metrics.infected_count(), 0);
+        assert!((metrics.mean_battery_level() - 0.5).abs() < f32::EPSILON);
+        assert!((metrics.reachable_fraction() - 2.0 / 3.0).abs() < 1e-6);
+        assert_eq!(metrics.sensor_coverage(), None);
+    }
+
+    #[test]
+    fn csv_record_matches_header() {
+        let mut network_model = network_model();
+        let metrics = Metrics::from_model(&network_model);
+
+        assert_eq!(
+            metrics.to_csv_record().split(',').count(),
+            CSV_HEADER.split(',').count()
+        );
+
+        network_model.set_coverage_area(
+            CoverageArea::new((-20.0, -20.0), (20.0, 20.0))
+        );
+
+        let metrics = Metrics::from_model(&network_model);
+
+        assert_eq!(metrics.control_coverage(), Some(1.0));
+        assert_eq!(
+            metrics.to_csv_record().split(',').count(),
+            CSV_HEADER.split(',').count()
+        );
+    }
+}
@@ -1,22 +1,73 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::Path;
+use std::sync::Arc;
 
-use serde::{Deserialize, Serialize};
+use rand::seq::IndexedRandom;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
 
-use super::ITERATION_TIME;
+use super::{random, ITERATION_TIME};
+use super::aggregation::Aggregator;
+use super::collision::CollisionPolicy;
 use super::connections::{ConnectionGraph, Topology};
-use super::device::{Device, DeviceId, IdToDeviceMap};
+use super::coverage::CoverageArea;
+use super::device::{
+    Device, DeviceId, IdToDelayMap, IdToDeviceMap, WorldView, BROADCAST_ID
+};
+use super::event::{Event, EventKind};
+use super::fault::FaultInjector;
+use super::landing::LandingZone;
+use super::linkloss::LinkLoss;
 use super::malware::Malware;
-use super::mathphysics::{Frequency, Millisecond};
-use super::signal::{Data, SignalQueue};
-use super::task::Scenario;
+use super::mathphysics::{
+    delay_to, Frequency, Megahertz, Millisecond, Point3D, Position, Vector3D
+};
+use super::parameters::SimulationParameters;
+use super::provenance::Provenance;
+use super::recon::RecognizedPicture;
+use super::signal::{
+    Data, DeliveryStats, HeardTransmissions, Interference, LatencyStats, 
+    PacketEvent, PacketEventKind, Propagation, PropagationModel, Signal, 
+    SignalQueue, SignalStrength
+};
+use super::task::{Scenario, Task};
+use super::weather::Weather;
 
-use attack::{add_malware_signals_to_queue, AttackerDevice};
+use attack::{add_malware_signals_to_queue, AttackError, AttackerDevice};
+use formation::Formation;
 use gps::GPS;
+use run::Run;
+use snapshot::ModelChanges;
 
 
 pub mod attack;
+pub mod formation;
 pub mod gps;
+pub mod run;
+pub mod snapshot;
+pub mod validation;
+
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum BuildError {
+    #[error("Command center ID is not set")]
+    MissingCommandCenterId,
+    #[error("Command center ID is the broadcast ID")]
+    BroadcastCommandCenterId,
+    #[error("Device map is not set")]
+    MissingDeviceMap,
+    #[error("Command center {0} is missing from the device map")]
+    MissingCommandCenter(DeviceId),
+    #[error("Scenario entry at {0} ms refers to missing device {1}")]
+    DanglingScenarioDevice(Millisecond, DeviceId),
+    #[error("Formation refers to missing device {0}")]
+    DanglingFormationDevice(DeviceId),
+    #[error("Attacker device {0} has the ID of another device")]
+    DuplicateAttackerId(DeviceId),
+}
 
 
 #[derive(Clone, Default)]
@@ -27,7 +78,11 @@ pub struct NetworkModelBuilder {
     gps: Option<GPS>,
     topology: Option<Topology>,
     scenario: Option<Scenario>,
+    formations: Option<Vec<Formation>>,
+    collision_policy: Option<CollisionPolicy>,
     delay_multiplier: Option<f32>,
+    parameters: Option<SimulationParameters>,
+    propagation: Option<Propagation>,
 }
 
 impl NetworkModelBuilder {
@@ -40,7 +95,11 @@ impl NetworkModelBuilder {
             gps: None,
             topology: None,
             scenario: None,
+            formations: None,
+            collision_policy: None,
             delay_multiplier: None,
+            parameters: None,
+            propagation: None,
         }
     }
 
@@ -86,15 +145,44 @@ impl NetworkModelBuilder {
         self
     }
     
+    #[must_use]
+    pub fn set_formations(mut self, formations: Vec<Formation>) -> Self {
+        self.formations = Some(formations);
+        self
+    }
+    
+    #[must_use]
+    pub fn set_collision_policy(
+        mut self, 
+        collision_policy: CollisionPolicy
+    ) -> Self {
+        self.collision_policy = Some(collision_policy);
+        self
+    }
+    
     #[must_use]
     pub fn set_delay_multiplier(mut self, delay_multiplier: f32) -> Self {
         self.delay_multiplier = Some(delay_multiplier);
         self
     }
 
+    #[must_use]
+    pub fn set_parameters(mut self, parameters: SimulationParameters) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
+    #[must_use]
+    pub fn set_propagation(mut self, propagation: Propagation) -> Self {
+        self.propagation = Some(propagation);
+        self
+    }
+
+    // Missing parts are defaulted, so the model may lack a command center.
+    // `try_build` checks the parts instead.
     #[must_use]
     pub fn build(self) -> NetworkModel {
-        NetworkModel::new(
+        let mut network_model = NetworkModel::new(
             self.command_center_id.unwrap_or_default(),
             self.device_map.unwrap_or_default(),
             self.attacker_devices.unwrap_or_default(),
@@ -102,12 +190,100 @@ impl NetworkModelBuilder {
             self.scenario.unwrap_or_default(),
             self.topology.unwrap_or_default(),
             self.delay_multiplier.unwrap_or_default(),
-        )
+        );
+
+        if let Some(formations) = self.formations {
+            network_model.set_formations(formations);
+        }
+        if let Some(collision_policy) = self.collision_policy {
+            network_model.set_collision_policy(collision_policy);
+        }
+        if let Some(parameters) = self.parameters {
+            network_model.set_parameters(parameters);
+        }
+        if let Some(propagation) = self.propagation {
+            network_model.set_propagation(propagation);
+        }
+
+        network_model
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the command center or the device map is not set,
+    /// the command center is not in the device map, the scenario or a
+    /// formation refers to a missing device, or an attacker device shares
+    /// its ID with another device.
+    pub fn try_build(self) -> Result<NetworkModel, BuildError> {
+        self.check_references()?;
+
+        Ok(self.build())
+    }
+
+    fn check_references(&self) -> Result<(), BuildError> {
+        let command_center_id = self.command_center_id
+            .ok_or(BuildError::MissingCommandCenterId)?;
+
+        if command_center_id == BROADCAST_ID {
+            return Err(BuildError::BroadcastCommandCenterId);
+        }
+
+        let device_map = self.device_map
+            .as_ref()
+            .ok_or(BuildError::MissingDeviceMap)?;
+
+        if !device_map.contains_key(&command_center_id) {
+            return Err(BuildError::MissingCommandCenter(command_center_id));
+        }
+
+        // Broadcast entries are sent to every device.
+        let scenario_entries = self.scenario
+            .iter()
+            .flat_map(Scenario::iter);
+
+        for (time, device_id, _) in scenario_entries {
+            let is_known = *device_id == BROADCAST_ID
+                || device_map.contains_key(device_id);
+
+            if !is_known {
+                return Err(
+                    BuildError::DanglingScenarioDevice(*time, *device_id)
+                );
+            }
+        }
+
+        for formation in self.formations.iter().flatten() {
+            let formation_ids = std::iter::once(formation.leader_id())
+                .chain(formation.member_ids().iter().copied());
+
+            for device_id in formation_ids {
+                if !device_map.contains_key(&device_id) {
+                    return Err(BuildError::DanglingFormationDevice(device_id));
+                }
+            }
+        }
+
+        let mut attacker_ids = BTreeSet::new();
+
+        for attacker_device in self.attacker_devices.iter().flatten() {
+            let attacker_id = attacker_device.device().id();
+
+            if device_map.contains_key(&attacker_id)
+                || !attacker_ids.insert(attacker_id)
+            {
+                return Err(BuildError::DuplicateAttackerId(attacker_id));
+            }
+        }
+
+        Ok(())
     }
 }
 
 
-#[derive(Clone, Serialize, Deserialize)]
+// Serialization is derived as inherent functions, so deserialization can
+// hand model-wide settings to devices afterwards.
+#[derive(Clone, JsonSchema, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub struct NetworkModel {
     current_time: Millisecond,
     command_device_id: DeviceId,
@@ -116,8 +292,94 @@ pub struct NetworkModel {
     gps: GPS,
     connections: ConnectionGraph,
     delay_multiplier: f32,
+    #[serde(default)]
+    parameters: SimulationParameters,
+    #[serde(default)]
+    propagation: Propagation,
     scenario: Scenario,
+    #[serde(default)]
+    formations: Vec<Formation>,
     signal_queue: SignalQueue,
+    #[serde(default)]
+    fault_injector: FaultInjector,
+    #[serde(default)]
+    recognized_picture: RecognizedPicture,
+    #[serde(default)]
+    collision_policy: CollisionPolicy,
+    // Mission area whose coverage is included in metrics.
+    #[serde(default)]
+    coverage_area: Option<CoverageArea>,
+    #[serde(default)]
+    weather: Weather,
+    #[serde(default)]
+    link_loss: LinkLoss,
+    // Concurrent transmissions degrade each other's links, `None` unless
+    // interference is enabled.
+    #[serde(default)]
+    interference: Option<Interference>,
+    // Sites drones land at in an emergency.
+    #[serde(default)]
+    landing_zones: Arc<[LandingZone]>,
+    #[serde(default)]
+    aggregator: Aggregator,
+    // Pairs of devices that were in contact after the last update. A pair
+    // collides only once until the devices separate.
+    #[serde(default)]
+    colliding_pairs: BTreeSet<(DeviceId, DeviceId)>,
+    // Metadata of the run that plays the model, so its snapshots are
+    // attributable.
+    #[serde(default)]
+    provenance: Option<Provenance>,
+    // Events of network devices from the last update.
+    #[serde(skip)]
+    events: Vec<Event>,
+    // Transmissions, receptions and drops of signals from the last update,
+    // `None` unless packet tracing is enabled.
+    #[serde(skip)]
+    packet_events: Option<Vec<PacketEvent>>,
+    // Latencies of signals received since tracking was enabled, `None`
+    // unless latency tracking is enabled.
+    #[serde(skip)]
+    latency_stats: Option<LatencyStats>,
+    // Signals other than noise that reached devices since the model was
+    // built or loaded.
+    #[serde(skip)]
+    delivery_stats: DeliveryStats,
+    // Failures of devices and attacks from the last update. They are shared,
+    // so the model stays cloneable.
+    #[serde(skip)]
+    errors: Vec<Arc<crate::Error>>,
+    // Devices and signal queue entries changed by the last update, `None`
+    // unless change tracking is enabled.
+    #[serde(skip)]
+    changes: Option<ModelChanges>,
+    // Devices changed since the last update.
+    #[serde(skip)]
+    changed_device_ids: BTreeSet<DeviceId>,
+}
+
+impl Serialize for NetworkModel {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        Self::serialize(self, serializer)
+    }
+}
+
+// Devices and their TX modules keep their own copies of the parameters and
+// the propagation model, which may be missing from the JSON of devices.
+impl<'de> Deserialize<'de> for NetworkModel {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Self, D::Error> {
+        let mut network_model = Self::deserialize(deserializer)?;
+
+        network_model.set_parameters(network_model.parameters);
+        network_model.set_propagation(network_model.propagation);
+
+        Ok(network_model)
+    }
 }
 
 impl NetworkModel {
@@ -132,15 +394,36 @@ impl NetworkModel {
         delay_multiplier: f32
     ) -> Self {
         let mut network_model = Self {
-            current_time: 0,
+            current_time: Millisecond::ZERO,
             command_device_id,
             attacker_devices,
             device_map,
             gps,
             connections: ConnectionGraph::new(topology),
             delay_multiplier,
+            parameters: SimulationParameters::default(),
+            propagation: Propagation::default(),
             scenario,
+            formations: Vec::new(),
             signal_queue: SignalQueue::new(),
+            fault_injector: FaultInjector::default(),
+            recognized_picture: RecognizedPicture::default(),
+            collision_policy: CollisionPolicy::default(),
+            coverage_area: None,
+            weather: Weather::default(),
+            link_loss: LinkLoss::default(),
+            interference: None,
+            landing_zones: Arc::from([]),
+            aggregator: Aggregator::default(),
+            colliding_pairs: BTreeSet::new(),
+            provenance: None,
+            events: Vec::new(),
+            packet_events: None,
+            latency_stats: None,
+            delivery_stats: DeliveryStats::default(),
+            errors: Vec::new(),
+            changes: None,
+            changed_device_ids: BTreeSet::new(),
         };
 
         network_model.set_initial_state();
@@ -148,6 +431,11 @@ impl NetworkModel {
         network_model
     }
     
+    #[must_use]
+    pub fn current_time(&self) -> Millisecond {
+        self.current_time
+    }
+
     #[must_use]
     pub fn command_device_id(&self) -> DeviceId {
         self.command_device_id
@@ -163,67 +451,465 @@ impl NetworkModel {
         &self.device_map
     }
 
+    // Devices other than the command device.
+    pub fn drones(&self) -> impl Iterator<Item = &Device> {
+        let command_device_id = self.command_device_id;
+
+        self.device_map
+            .devices_where(move |device| device.id() != command_device_id)
+    }
+
     #[must_use]
     pub fn attacker_devices(&self) -> &[AttackerDevice] {
         self.attacker_devices.as_slice()
     }   
 
+    #[must_use]
+    pub fn gps(&self) -> &GPS {
+        &self.gps
+    }
+
     #[must_use]
     pub fn connections(&self) -> &ConnectionGraph {
         &self.connections
     }
 
+    #[must_use]
+    pub fn parameters(&self) -> &SimulationParameters {
+        &self.parameters
+    }
+
+    // Devices keep a copy of the parameters, so they are in effect before
+    // the first update. Drones fly at the max drone speed of the parameters,
+    // including drones of JSON models.
+    pub fn set_parameters(&mut self, parameters: SimulationParameters) {
+        self.parameters = parameters;
+        self.mark_all_devices_changed();
+
+        for (device_id, device) in self.device_map.iter_mut() {
+            device.set_parameters(parameters);
+
+            if device_id != self.command_device_id {
+                device.set_max_speed(parameters.max_drone_speed());
+            }
+        }
+        for attacker_device in &mut self.attacker_devices {
+            attacker_device.device_mut().set_parameters(parameters);
+        }
+        self.gps.device_mut().set_parameters(parameters);
+    }
+
+    #[must_use]
+    pub fn propagation(&self) -> &Propagation {
+        &self.propagation
+    }
+
+    // Transmitters of all devices, attackers and GPS included, follow the
+    // propagation model, so connections are rebuilt with it.
+    pub fn set_propagation(&mut self, propagation: Propagation) {
+        self.propagation = propagation;
+        self.mark_all_devices_changed();
+
+        for device in self.device_map.values_mut() {
+            device.set_propagation(propagation);
+        }
+        for attacker_device in &mut self.attacker_devices {
+            attacker_device.device_mut().set_propagation(propagation);
+        }
+        self.gps.device_mut().set_propagation(propagation);
+        self.update_connections_graph();
+    }
+
     #[must_use]
     pub fn signal_queue(&self) -> &SignalQueue {
         &self.signal_queue
     }
 
+    #[must_use]
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    // Routine outcomes are not failures: attacks on targets out of reach and
+    // power draws of devices that were already shut down are left out.
+    pub fn errors(&self) -> impl Iterator<Item = &crate::Error> {
+        self.errors.iter().map(AsRef::as_ref)
+    }
+
+    #[must_use]
+    pub fn packet_events(&self) -> &[PacketEvent] {
+        self.packet_events.as_deref().unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn latency_stats(&self) -> Option<&LatencyStats> {
+        self.latency_stats.as_ref()
+    }
+
+    #[must_use]
+    pub fn delivery_stats(&self) -> &DeliveryStats {
+        &self.delivery_stats
+    }
+
+    #[must_use]
+    pub fn fault_injector(&self) -> &FaultInjector {
+        &self.fault_injector
+    }
+
+    pub fn set_fault_injector(&mut self, fault_injector: FaultInjector) {
+        self.fault_injector = fault_injector;
+    }
+
+    #[must_use]
+    pub fn coverage_area(&self) -> Option<&CoverageArea> {
+        self.coverage_area.as_ref()
+    }
+
+    pub fn set_coverage_area(&mut self, coverage_area: CoverageArea) {
+        self.coverage_area = Some(coverage_area);
+    }
+
+    #[must_use]
+    pub fn weather(&self) -> &Weather {
+        &self.weather
+    }
+
+    pub fn set_weather(&mut self, weather: Weather) {
+        self.weather = weather;
+    }
+
+    #[must_use]
+    pub fn link_loss(&self) -> &LinkLoss {
+        &self.link_loss
+    }
+
+    pub fn set_link_loss(&mut self, link_loss: LinkLoss) {
+        self.link_loss = link_loss;
+    }
+
+    #[must_use]
+    pub fn interference(&self) -> Option<&Interference> {
+        self.interference.as_ref()
+    }
+
+    pub fn set_interference(&mut self, interference: Interference) {
+        self.interference = Some(interference);
+    }
+
+    #[must_use]
+    pub fn landing_zones(&self) -> &[LandingZone] {
+        &self.landing_zones
+    }
+
+    pub fn set_landing_zones(&mut self, landing_zones: Vec<LandingZone>) {
+        self.landing_zones = landing_zones.into();
+    }
+
+    #[must_use]
+    pub fn aggregator(&self) -> &Aggregator {
+        &self.aggregator
+    }
+
+    pub fn set_aggregator(&mut self, aggregator: Aggregator) {
+        self.aggregator = aggregator;
+    }
+
+    #[must_use]
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    pub fn set_provenance(&mut self, provenance: Provenance) {
+        self.provenance = Some(provenance);
+    }
+
+    // Returns whether the device is in the device map.
+    pub fn set_externally_driven(&mut self, device_id: DeviceId) -> bool {
+        self.mark_device_changed(device_id);
+        self.device_map
+            .get_mut(&device_id)
+            .map(|device| device.set_externally_driven(true))
+            .is_some()
+    }
+
+    // Returns whether the device is externally driven, since positions of
+    // other devices are ignored.
+    pub fn set_external_position(
+        &mut self,
+        device_id: DeviceId,
+        position: Point3D
+    ) -> bool {
+        self.mark_device_changed(device_id);
+        self.device_map
+            .get_mut(&device_id)
+            .filter(|device| device.is_externally_driven())
+            .map(|device| device.set_external_position(position))
+            .is_some()
+    }
+
+    #[must_use]
+    pub fn scenario(&self) -> &Scenario {
+        &self.scenario
+    }
+
+    // The task is recorded into the scenario at the current time, so it is
+    // sent by the command center on the next update and the scenario replays
+    // the run. Tasks for missing devices are ignored.
+    pub fn issue_task(&mut self, device_id: DeviceId, task: Task) -> bool {
+        if device_id != BROADCAST_ID 
+            && !self.device_map.contains_key(&device_id)
+        {
+            return false;
+        }
+
+        self.scenario.add_entry(self.current_time, device_id, task);
+
+        true
+    }
+
+    #[must_use]
+    pub fn formations(&self) -> &[Formation] {
+        self.formations.as_slice()
+    }
+
+    pub fn set_formations(&mut self, formations: Vec<Formation>) {
+        self.formations = formations;
+        self.add_formation_signals_to_queue();
+    }
+
+    #[must_use]
+    pub fn collision_policy(&self) -> &CollisionPolicy {
+        &self.collision_policy
+    }
+
+    pub fn set_collision_policy(&mut self, collision_policy: CollisionPolicy) {
+        self.collision_policy = collision_policy;
+    }
+
+    #[must_use]
+    pub fn recognized_picture(&self) -> &RecognizedPicture {
+        &self.recognized_picture
+    }
+
+    // Hash of the simulation state that should be equal for every run with
+    // the same seed. It is stable within one build only.
+    #[must_use]
+    pub fn state_checksum(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        self.current_time.hash(&mut hasher);
+        for device in self.device_map.values() {
+            device.hash_state(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
     /// # Errors
     ///
     /// Will return `Err` if serialization fails.
     pub fn to_json(&self) -> serde_json::Result<String> {
         serde_json::to_string(&self)
     }
-    
+
     /// # Errors
     ///
     /// Will return `Err` if serialization fails.
-    /// 
-    /// # Panics
+    pub fn to_json_value(&self) -> serde_json::Result<serde_json::Value> {
+        // `serde_json::to_value` widens `f32` fields to `f64`, which makes
+        // them print with extra digits, so the value is parsed from the
+        // string instead.
+        serde_json::from_str(&self.to_json()?)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if deserialization fails.
+    pub fn from_json_value(
+        value: serde_json::Value
+    ) -> serde_json::Result<Self> {
+        serde_json::from_value(value)
+    }
+    
+    /// # Errors
     ///
-    /// Will panic if it fails to read the file at `model_path`.
-    pub fn from_json(model_path: &Path) -> serde_json::Result<Self> {
+    /// Will return `Err` if the file at `model_path` can not be read or
+    /// deserialized.
+    pub fn from_json(model_path: &Path) -> crate::Result<Self> {
+        let context = || format!("Failed to load `{}`", model_path.display());
         let json_string = fs::read_to_string(model_path)
-            .expect("Failed to read `.json` file");
+            .map_err(|error| crate::Error::io(context(), error))?;
 
         serde_json::from_str(&json_string)
+            .map_err(|error| crate::Error::json(context(), error))
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if serialization fails.
+    pub fn to_binary(&self) -> postcard::Result<Vec<u8>> {
+        postcard::to_stdvec(self)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if deserialization fails.
+    pub fn from_binary(bytes: &[u8]) -> postcard::Result<Self> {
+        postcard::from_bytes(bytes)
+    }
+
+    // Packet events are recorded from the next update on.
+    pub fn set_packet_tracing(&mut self, packet_tracing: bool) {
+        self.packet_events = packet_tracing.then(Vec::new);
+        self.signal_queue.set_tracing(packet_tracing);
+    }
+
+    // Changes are recorded from the next update on, so deltas of model
+    // states can be built without comparing whole models.
+    pub fn set_change_tracking(&mut self, change_tracking: bool) {
+        self.changes = change_tracking.then(ModelChanges::default);
+        self.changed_device_ids.clear();
+        self.signal_queue.set_change_tracking(change_tracking);
+    }
+
+    // Unlike packet events, latencies are kept across updates.
+    pub fn set_latency_tracking(&mut self, latency_tracking: bool) {
+        self.latency_stats = latency_tracking.then(LatencyStats::default);
+    }
+
+    // Adds the signal to the queue, so it is received by its destination on
+    // the next update.
+    pub fn inject_signal(&mut self, signal: Signal) {
+        self.signal_queue.add_entry(
+            self.current_time,
+            signal,
+            IdToDelayMap::new()
+        );
     }
 
+    // With the `tracing` feature, the update and each of its phases are
+    // recorded as spans.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            skip_all,
+            fields(time = self.current_time.value())
+        )
+    )]
     pub fn update(&mut self) {
+        let previous_time = self.current_time;
+
+        self.events.clear();
+        self.errors.clear();
+        if let Some(ref mut packet_events) = self.packet_events {
+            packet_events.clear();
+        }
+        self.fault_injector.activate(
+            self.current_time,
+            &self.device_map,
+            self.command_device_id
+        );
+
         self.spread_malware();
-        self.update_devices();
+
+        let world_views = self.world_views();
+
+        self.update_devices(&world_views);
+        self.handle_collisions();
+        self.update_recognized_picture();
         self.update_connections_graph();
-        self.signal_queue.remove_old_signals(self.current_time);
+        self.remove_old_signals();
      
         self.current_time += ITERATION_TIME;
         
-        self.add_scenario_signals_to_queue();
-        self.add_gps_signals_to_queue();
+        self.queue_signals();
+        self.record_transmissions();
+        self.draw_tx_energy();
+        self.record_changes(previous_time);
+    }
+
+    // The model is updated lazily, once per advance of the iterator, until
+    // its time reaches `end_time`, so `for state in model.run(end_time)`
+    // replaces a loop of updates.
+    pub fn run(&mut self, end_time: Millisecond) -> Run<'_> {
+        Run::new(self, end_time)
+    }
+
+    // Once the model is settled, an update can only advance time, so the
+    // model takes a single step of up to `max_step` instead of several
+    // regular ones. Returns the time the model advanced by.
+    pub fn update_adaptive(&mut self, max_step: Millisecond) -> Millisecond {
+        let step = if self.is_settled() {
+            max_step - max_step % ITERATION_TIME
+        } else {
+            ITERATION_TIME
+        }.max(ITERATION_TIME);
+        let previous_time = self.current_time;
+        let mut step_errors = Vec::new();
+
+        // Attackers and GPS keep drawing power and attacking, so they are
+        // advanced on every iteration of the step.
+        for _ in 1..step / ITERATION_TIME {
+            self.update_attackers_and_gps();
+            self.remove_old_signals();
+            step_errors.append(&mut self.errors);
+            self.current_time += ITERATION_TIME;
+        }
+
+        // The last iteration of the step is done as usual, so the signal
+        // queue is left in the same state as after regular updates.
+        self.update();
+        self.errors.splice(0..0, step_errors);
+
+        // Only time advances before the last iteration of the step.
+        if let Some(ref mut changes) = self.changes {
+            changes.since = previous_time;
+        }
+
+        step
+    }
+
+    // Only time advances between regular updates of a settled model: no
+    // signal is queued for a running device, e.g. GPS fixes are still sent
+    // to shut down ones, network devices are shut down and no device moves
+    // or heads to a destination.
+    #[must_use]
+    pub fn is_settled(&self) -> bool {
+        let is_running = |device_id| self.device_map
+            .get(&device_id)
+            .is_none_or(|device| !device.is_shut_down());
+
+        !self.signal_queue.destination_ids().any(is_running)
+            && self.device_map
+                .values()
+                .all(Device::is_shut_down)
+            && self.attacker_devices
+                .iter()
+                .map(AttackerDevice::device)
+                .chain(std::iter::once(self.gps.device()))
+                .all(|device| device.is_shut_down() || device.is_at_rest())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     fn spread_malware(&mut self) {
-        for (device_id, device) in &self.device_map {
-            let malware_list: Vec<Malware> = device.infection_map()
-                .keys()
-                .copied()
-                .collect();
+        // The buffer is shared between devices to avoid allocating a new 
+        // malware list for every infected device.
+        let mut malware_list: Vec<Malware> = Vec::new();
+
+        for device in self.device_map.infected_devices() {
+            malware_list.clear();
+            malware_list.extend(
+                device.infection_map()
+                    .keys()
+                    .filter(|malware| malware.spreads())
+            );
 
             if malware_list.is_empty() {
                 continue;
             }
 
             for (neighbor_id, neighbor_device) in &self.device_map {
-                if neighbor_id == device_id {
+                if neighbor_id == device.id() {
                     continue;
                 }
 
@@ -239,39 +925,346 @@ impl NetworkModel {
         }
     }
 
-    fn update_devices(&mut self) {
-        self.attacker_devices
-            .iter_mut()
-            .for_each(|attacker_device| { 
-                let _ = attacker_device.device_mut().update(); 
-            });
+    // Views are taken before any device moves, so they do not depend on the
+    // update order. They are in the iteration order of the device map.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn world_views(&self) -> Vec<WorldView> {
+        self.device_map
+            .values()
+            .map(|device|
+                WorldView::observed_by(device, self.device_map.values())
+                    .set_landing_zones(Arc::clone(&self.landing_zones))
+                    .set_parameters(self.parameters)
+            )
+            .collect()
+    }
 
-        let _ = self.gps.device_mut().update();
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn update_devices(&mut self, world_views: &[WorldView]) {
+        self.update_attackers_and_gps();
+
+        // Transmissions on air are taken after the attacks, so the noise of
+        // attackers interferes with the signals received in this update.
+        let transmitter_positions = self.transmitter_positions();
+        let heard_transmissions = self.heard_transmissions();
+        let gps_id = self.gps.device().id();
         
-        for (device_id, device) in &mut self.device_map {
-            for attacker_device in &self.attacker_devices {
-                let _ = attacker_device.execute_attack(
-                    device, 
-                    &mut self.signal_queue,
-                    self.current_time,
-                    self.delay_multiplier
-                );
+        for ((device_id, device), world_view) in self.device_map
+            .iter_mut()
+            .zip(world_views)
+        {
+            if self.fault_injector.is_frozen(device_id, self.current_time) {
+                continue;
             }
-
-            for signal in self.signal_queue.get_current_signals_for(
-                *device_id,
-                self.current_time
-            ) {
-                let _ = device.receive_signal(*signal, self.current_time);
+            if self.changes.is_some() {
+                self.changed_device_ids.insert(device_id);
             }
 
-            let _ = device.update();
-        }
-    }
+            for (creation_time, signal) in self.signal_queue
+                .get_current_signals_for(device_id, self.current_time)
+            {
+                let signal = &transmitter_positions
+                    .get(&signal.source_id())
+                    .map_or(*signal, |position|
+                        attenuate_on_path(
+                            &self.weather,
+                            &self.link_loss,
+                            gps_id,
+                            *signal,
+                            position,
+                            device.position()
+                        )
+                    );
+                let interfered = self.interference
+                    .as_ref()
+                    .zip(heard_transmissions.get(&device_id))
+                    .is_some_and(|(interference, heard_transmissions)|
+                        !interference.allows(signal, heard_transmissions)
+                    );
+                let received = !interfered
+                    && !self.fault_injector.drops_signal(self.current_time)
+                    && device
+                        .receive_signal(*signal, self.current_time)
+                        .is_ok();
 
-    fn update_connections_graph(&mut self) {
-        self.connections.update(self.command_device_id, &self.device_map);
-    }
+                if *signal.data() != Data::Noise {
+                    self.delivery_stats.record(received);
+                }
+                if let Some(latency_stats) = self.latency_stats
+                    .as_mut()
+                    .filter(|_| received)
+                {
+                    latency_stats.record(
+                        device_id,
+                        signal,
+                        self.current_time - creation_time
+                    );
+                }
+
+                if let Some(ref mut packet_events) = self.packet_events {
+                    let kind = if received {
+                        PacketEventKind::Received
+                    } else {
+                        PacketEventKind::Dropped
+                    };
+
+                    packet_events.push(
+                        PacketEvent::new(
+                            self.current_time,
+                            device_id,
+                            kind,
+                            *signal
+                        )
+                    );
+                }
+            }
+
+            record_device_error(&mut self.errors, device, world_view);
+
+            self.events.append(&mut device.take_events());
+        }
+    }
+
+    fn update_attackers_and_gps(&mut self) {
+        // Attackers and GPS do not react to other devices.
+        let empty_world_view = WorldView::default()
+            .set_parameters(self.parameters);
+
+        for attacker_device in &mut self.attacker_devices {
+            record_device_error(
+                &mut self.errors,
+                attacker_device.device_mut(),
+                &empty_world_view
+            );
+        }
+
+        record_device_error(
+            &mut self.errors,
+            self.gps.device_mut(),
+            &empty_world_view
+        );
+
+        self.execute_attacks();
+    }
+
+    fn execute_attacks(&mut self) {
+        for (device_id, device) in &self.device_map {
+            if self.fault_injector.is_frozen(device_id, self.current_time) {
+                continue;
+            }
+
+            for attacker_device in &self.attacker_devices {
+                let result = attacker_device.execute_attack(
+                    device, 
+                    &mut self.signal_queue,
+                    self.current_time,
+                    self.delay_multiplier
+                );
+
+                match result {
+                    Ok(()) | Err(AttackError::TargetOutOfRange) => (),
+                    Err(source) => self.errors.push(Arc::new(
+                        crate::Error::Attack { target_id: device_id, source }
+                    )),
+                }
+            }
+        }
+    }
+
+    // Positions of devices, attackers and GPS, so signals lose strength to
+    // the weather and link losses along the path they travelled. It is
+    // empty in clear weather without link losses.
+    fn transmitter_positions(&self) -> HashMap<DeviceId, Point3D> {
+        if self.weather.is_clear() && self.link_loss.is_empty() {
+            return HashMap::new();
+        }
+
+        self.device_map
+            .values()
+            .chain(
+                self.attacker_devices
+                    .iter()
+                    .map(AttackerDevice::device)
+            )
+            .chain(std::iter::once(self.gps.device()))
+            .map(|device| (device.id(), *device.position()))
+            .collect()
+    }
+
+    // Strengths of transmissions on air as heard by each device, after the
+    // weather and link losses. A transmission is heard once it travels the
+    // distance to the device, whatever the destination of its signal is. It
+    // is empty unless interference is enabled.
+    fn heard_transmissions(&self) -> HashMap<DeviceId, HeardTransmissions> {
+        if self.interference.is_none() {
+            return HashMap::new();
+        }
+
+        let gps_id = self.gps.device().id();
+        let transmitters: HashMap<DeviceId, &Device> = self.device_map
+            .values()
+            .chain(
+                self.attacker_devices
+                    .iter()
+                    .map(AttackerDevice::device)
+            )
+            .chain(std::iter::once(self.gps.device()))
+            .map(|device| (device.id(), device))
+            .collect();
+        // The queue is scanned once for all devices. Signals a source sends
+        // on a frequency at the same time, e.g. to several destinations, are
+        // a single transmission.
+        let transmissions: BTreeMap<(Millisecond, Frequency, DeviceId), _> =
+            self.signal_queue
+                .get_signals_created_until(self.current_time)
+                .filter_map(|(creation_time, signal)| {
+                    let transmitter = transmitters.get(&signal.source_id())?;
+
+                    Some((
+                        (creation_time, signal.frequency(), signal.source_id()),
+                        (*transmitter, *signal)
+                    ))
+                })
+                .collect();
+
+        self.device_map
+            .values()
+            .map(|receiver| {
+                let heard_transmissions = transmissions
+                    .iter()
+                    .filter(|&(
+                        &(creation_time, _, source_id),
+                        &(transmitter, _)
+                    )|
+                        source_id != receiver.id()
+                            && creation_time + delay_to(
+                                transmitter.distance_to(receiver),
+                                self.delay_multiplier
+                            ) == self.current_time
+                    )
+                    .filter_map(|(
+                        &(_, frequency, source_id), 
+                        &(transmitter, signal)
+                    )| {
+                        let strength = transmitter.tx_signal_strength_at(
+                            receiver.position(),
+                            frequency
+                        )?;
+                        let signal = attenuate_on_path(
+                            &self.weather,
+                            &self.link_loss,
+                            gps_id,
+                            signal.with_strength(strength),
+                            transmitter.position(),
+                            receiver.position()
+                        );
+
+                        Some(((frequency, source_id), *signal.strength()))
+                    })
+                    .collect();
+
+                (receiver.id(), heard_transmissions)
+            })
+            .collect()
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn handle_collisions(&mut self) {
+        if !self.collision_policy.is_enabled() {
+            return;
+        }
+
+        let positions: Vec<(DeviceId, Point3D)> = self.device_map
+            .active_devices()
+            .map(|device| (device.id(), *device.position()))
+            .collect();
+        let mut colliding_pairs = BTreeSet::new();
+
+        for (index, (device_id, position)) in positions.iter().enumerate() {
+            for (other_id, other_position) in &positions[index + 1..] {
+                if position.distance_to(other_position) 
+                    <= self.collision_policy.collision_distance()
+                {
+                    colliding_pairs.insert((
+                        (*device_id).min(*other_id),
+                        (*device_id).max(*other_id)
+                    ));
+                }
+            }
+        }
+
+        for (device_id, other_id) in &colliding_pairs {
+            if self.colliding_pairs.contains(&(*device_id, *other_id)) {
+                continue;
+            }
+
+            let (Some(position), Some(other_position)) = (
+                self.device_map.get(device_id).map(|device| *device.position()),
+                self.device_map.get(other_id).map(|device| *device.position())
+            ) else {
+                continue;
+            };
+            // Both devices suffer the same outcome.
+            let outcome = self.collision_policy.draw_outcome();
+
+            let away = Vector3D::new(other_position, position);
+            let other_away = Vector3D::new(position, other_position);
+
+            for (id, collided_id, away) in [
+                (*device_id, *other_id, away),
+                (*other_id, *device_id, other_away),
+            ] {
+                let Some(device) = self.device_map.get_mut(&id) else {
+                    continue;
+                };
+
+                device.collide(collided_id, outcome, away);
+                self.events.append(&mut device.take_events());
+                if self.changes.is_some() {
+                    self.changed_device_ids.insert(id);
+                }
+            }
+        }
+
+        self.colliding_pairs = colliding_pairs;
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn update_recognized_picture(&mut self) {
+        for event in &self.events {
+            if event.device_id() != self.command_device_id {
+                continue;
+            }
+
+            if let EventKind::ReconReceived(record) = event.kind() {
+                self.recognized_picture.add_record(record);
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn update_connections_graph(&mut self) {
+        self.connections.update(self.command_device_id, &self.device_map);
+        self.fault_injector.cut_edges(&mut self.connections, self.current_time);
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn remove_old_signals(&mut self) {
+        self.signal_queue.remove_old_signals(self.current_time);
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn queue_signals(&mut self) {
+        self.add_scenario_signals_to_queue();
+        self.add_firmware_update_signals_to_queue();
+        self.add_time_beacon_signals_to_queue();
+        self.add_formation_signals_to_queue();
+        self.add_gps_signals_to_queue();
+        self.add_recon_signals_to_queue();
+        self.add_recon_summary_signals_to_queue();
+        self.add_channel_switch_signals_to_queue();
+        self.add_gossip_signals_to_queue();
+    }
 
     fn add_scenario_signals_to_queue(&mut self) {
         let Some(command_device) = self.device_map.get(
@@ -280,14 +1273,25 @@ impl NetworkModel {
             return;
         };
 
+        // Delays to all devices are found with a single shortest path search
+        // instead of searching for every task signal separately.
+        let broadcast_delay_map = self.connections.delay_map(
+            command_device,
+            BROADCAST_ID, 
+            &self.device_map, 
+            self.delay_multiplier
+        );
+
         for (device_id, device) in &self.device_map {
-            if *device_id == self.command_device_id {
+            if device_id == self.command_device_id 
+                || self.follows_formation(device_id)
+            {
                 continue;
             }
 
             let Some(last_task) = self.scenario.get_last_task(
                 self.current_time, 
-                *device_id
+                device_id
             ) else {
                 continue;
             };
@@ -295,17 +1299,15 @@ impl NetworkModel {
             let Ok(task_signal) = command_device.create_signal_for(
                 device, 
                 Data::SetTask(*last_task), 
-                Frequency::Control,
+                command_device.control_frequency(),
             ) else {
                 continue;
             };
         
-            let delay_map = self.connections.delay_map(
-                command_device,
-                *device_id, 
-                &self.device_map, 
-                self.delay_multiplier
-            );
+            let delay_map = broadcast_delay_map
+                .get(&device_id)
+                .map(|delay| IdToDelayMap::from([(device_id, *delay)]))
+                .unwrap_or_default();
 
             self.signal_queue.add_entry(
                 self.current_time, 
@@ -315,18 +1317,1846 @@ impl NetworkModel {
         }
     }
    
-    fn add_gps_signals_to_queue(&mut self) {
-        self.gps.add_gps_signals_to_queue(
-            &mut self.signal_queue, 
+    // Beacons of the command device are relayed to every device it reaches,
+    // so drones far from it get them later and set their clocks behind.
+    // The update is pushed every iteration, so devices that were out of
+    // reach get it once they are back. Devices ignore updates they have.
+    fn add_firmware_update_signals_to_queue(&mut self) {
+        let Some(command_device) = self.device_map.get(
+            &self.command_device_id
+        ) else {
+            return;
+        };
+        let Some(update) = command_device.firmware_update() else {
+            return;
+        };
+
+        let broadcast_delay_map = self.connections.delay_map(
+            command_device,
+            BROADCAST_ID, 
             &self.device_map, 
-            self.current_time,
-            self.delay_multiplier,
+            self.delay_multiplier
         );
+
+        for (device_id, device) in &self.device_map {
+            if device_id == self.command_device_id {
+                continue;
+            }
+
+            let Some(delay) = broadcast_delay_map.get(&device_id) else {
+                continue;
+            };
+            let Ok(update_signal) = command_device.create_signal_for(
+                device, 
+                Data::FirmwareUpdate(update), 
+                command_device.control_frequency(),
+            ) else {
+                continue;
+            };
+
+            self.signal_queue.add_entry(
+                self.current_time, 
+                update_signal, 
+                IdToDelayMap::from([(device_id, *delay)])
+            );
+        }
     }
 
-    fn set_initial_state(&mut self) {
-        self.update_connections_graph();
-        self.add_gps_signals_to_queue();
-        self.add_scenario_signals_to_queue();
+    fn add_time_beacon_signals_to_queue(&mut self) {
+        let Some(command_device) = self.device_map.get(
+            &self.command_device_id
+        ) else {
+            return;
+        };
+        let Some(beacon_time) = command_device.time_beacon() else {
+            return;
+        };
+
+        let broadcast_delay_map = self.connections.delay_map(
+            command_device,
+            BROADCAST_ID, 
+            &self.device_map, 
+            self.delay_multiplier
+        );
+
+        for (device_id, device) in &self.device_map {
+            if device_id == self.command_device_id {
+                continue;
+            }
+
+            let Some(delay) = broadcast_delay_map.get(&device_id) else {
+                continue;
+            };
+            let Ok(beacon_signal) = command_device.create_signal_for(
+                device, 
+                Data::TimeBeacon(beacon_time), 
+                command_device.control_frequency(),
+            ) else {
+                continue;
+            };
+
+            self.signal_queue.add_entry(
+                self.current_time, 
+                beacon_signal, 
+                IdToDelayMap::from([(device_id, *delay)])
+            );
+        }
+    }
+
+    // Formation members do not get scenario tasks while their leader is
+    // active. They are steered by velocity commands instead.
+    fn follows_formation(&self, device_id: DeviceId) -> bool {
+        self.formations
+            .iter()
+            .any(|formation| 
+                formation.is_member(device_id) 
+                    && formation.is_active(&self.device_map)
+            )
+    }
+
+    fn add_formation_signals_to_queue(&mut self) {
+        if self.formations.is_empty() {
+            return;
+        }
+
+        let Some(command_device) = self.device_map.get(
+            &self.command_device_id
+        ) else {
+            return;
+        };
+
+        let broadcast_delay_map = self.connections.delay_map(
+            command_device,
+            BROADCAST_ID, 
+            &self.device_map, 
+            self.delay_multiplier
+        );
+
+        for formation in &mut self.formations {
+            let Some(leader) = self.device_map.get(&formation.leader_id())
+            else {
+                continue;
+            };
+
+            if leader.is_shut_down() {
+                continue;
+            }
+
+            formation.update_heading(leader);
+
+            for (member_id, velocity) in formation.velocity_commands(
+                &self.device_map
+            ) {
+                let Some(member) = self.device_map.get(&member_id) else {
+                    continue;
+                };
+                let Ok(task_signal) = command_device.create_signal_for(
+                    member, 
+                    Data::SetTask(Task::KeepFormation(velocity)), 
+                    command_device.control_frequency(),
+                ) else {
+                    continue;
+                };
+                let delay_map = broadcast_delay_map
+                    .get(&member_id)
+                    .map(|delay| IdToDelayMap::from([(member_id, *delay)]))
+                    .unwrap_or_default();
+
+                self.signal_queue.add_entry(
+                    self.current_time, 
+                    task_signal, 
+                    delay_map
+                );
+            }
+        }
+    }
+   
+    // Devices report sensor detections to the command device over the
+    // control network.
+    fn add_recon_signals_to_queue(&mut self) {
+        let targets: Vec<&Device> = self.device_map
+            .values()
+            .chain(self.attacker_devices.iter().map(AttackerDevice::device))
+            .collect();
+
+        for (device_id, device) in &self.device_map {
+            if device_id == self.command_device_id
+                || self.fault_injector.is_frozen(device_id, self.current_time)
+            {
+                continue;
+            }
+
+            let recon_records = device.scan(targets.iter().copied());
+
+            if recon_records.is_empty() {
+                continue;
+            }
+
+            if self.aggregator.is_enabled() {
+                let Some(relay_id) = self.connections
+                    .last_hop(device_id, self.command_device_id)
+                else {
+                    continue;
+                };
+
+                // Records are not transmitted to the relay, but reach it
+                // after the delay along the path to it.
+                let delay = if relay_id == device_id {
+                    Millisecond::ZERO
+                } else {
+                    self.connections
+                        .relay_route(
+                            device_id,
+                            relay_id,
+                            &self.device_map,
+                            self.delay_multiplier
+                        )
+                        .map_or(Millisecond::ZERO, |(delay, _)| delay)
+                };
+
+                for recon_record in recon_records {
+                    self.aggregator.add_record(
+                        relay_id,
+                        recon_record,
+                        self.current_time + delay
+                    );
+                }
+                continue;
+            }
+
+            let Some((delay, signal_strength)) = self.connections
+                .relay_route(
+                    device_id, 
+                    self.command_device_id,
+                    &self.device_map,
+                    self.delay_multiplier
+                )
+            else {
+                continue;
+            };
+            let delay_map = IdToDelayMap::from([(
+                self.command_device_id,
+                delay
+            )]);
+
+            for recon_record in recon_records {
+                let recon_signal = Signal::new(
+                    device_id,
+                    self.command_device_id,
+                    Data::Recon(recon_record),
+                    device.control_frequency(),
+                    signal_strength
+                );
+
+                self.signal_queue.add_entry(
+                    self.current_time,
+                    recon_signal,
+                    delay_map.clone()
+                );
+            }
+        }
+    }
+
+    // Relays send a summary per target of the records they aggregated once
+    // per aggregation interval.
+    fn add_recon_summary_signals_to_queue(&mut self) {
+        if !self.aggregator.flushes_at(self.current_time) {
+            return;
+        }
+
+        for (relay_id, recon_summary) in self.aggregator
+            .take_summaries(self.current_time)
+        {
+            let Some(relay) = self.device_map.get(&relay_id) else {
+                continue;
+            };
+            let Some((delay, signal_strength)) = self.connections
+                .relay_route(
+                    relay_id,
+                    self.command_device_id,
+                    &self.device_map,
+                    self.delay_multiplier
+                )
+            else {
+                continue;
+            };
+            let summary_signal = Signal::new(
+                relay_id,
+                self.command_device_id,
+                Data::ReconSummary(recon_summary),
+                relay.control_frequency(),
+                signal_strength
+            );
+
+            self.signal_queue.add_entry(
+                self.current_time,
+                summary_signal,
+                IdToDelayMap::from([(self.command_device_id, delay)])
+            );
+        }
+    }
+
+    // Devices that switched their control channel notify peers still on the
+    // previous frequency. The notice is sent on the previous frequency with
+    // the strength the retuned radio has.
+    fn add_channel_switch_signals_to_queue(&mut self) {
+        for event in &self.events {
+            let EventKind::ControlChannelSwitched { from, to } = *event.kind()
+            else {
+                continue;
+            };
+            let Some(device) = self.device_map.get(&event.device_id()) else {
+                continue;
+            };
+            let Some(tx_signal_strength) = device
+                .tx_signal_strength_map()
+                .get(&to)
+            else {
+                continue;
+            };
+
+            for peer in self.device_map.values() {
+                if peer.id() == device.id() || peer.control_frequency() != from 
+                {
+                    continue;
+                }
+
+                let distance = device.distance_to(peer);
+                let signal_strength = self.propagation.received_strength(
+                    *tx_signal_strength,
+                    from as Megahertz, 
+                    distance
+                );
+
+                if signal_strength.is_black() {
+                    continue;
+                }
+
+                let switch_signal = Signal::new(
+                    device.id(),
+                    peer.id(),
+                    Data::SwitchChannel(to),
+                    from,
+                    signal_strength
+                );
+                let delay_map = IdToDelayMap::from([(
+                    peer.id(),
+                    delay_to(distance, self.delay_multiplier)
+                        + peer.processing_delay()
+                )]);
+
+                self.signal_queue.add_entry(
+                    self.current_time,
+                    switch_signal,
+                    delay_map
+                );
+            }
+        }
+    }
+
+    // Rumors are sent to random gossiping peers in reach of the control
+    // signal of the device.
+    fn add_gossip_signals_to_queue(&mut self) {
+        for (device_id, device) in &self.device_map {
+            if self.fault_injector.is_frozen(device_id, self.current_time) {
+                continue;
+            }
+
+            let Some(rumor) = device.gossip() else {
+                continue;
+            };
+            let frequency = device.control_frequency();
+            let peers: Vec<(&Device, SignalStrength)> = self.device_map
+                .values()
+                .filter(|peer| 
+                    peer.id() != device_id
+                        && !peer.is_shut_down()
+                        && peer.gossip_system().is_enabled()
+                        && peer.control_frequency() == frequency
+                )
+                .filter_map(|peer| 
+                    device
+                        .tx_signal_strength_at(peer, frequency)
+                        .filter(|signal_strength| !signal_strength.is_black())
+                        .map(|signal_strength| (peer, signal_strength))
+                )
+                .collect();
+            let fanout = device.gossip_system().fanout();
+
+            for (peer, signal_strength) in random::with_rng(|rng| 
+                peers.choose_multiple(rng, fanout)
+            ) {
+                let gossip_signal = Signal::new(
+                    device_id,
+                    peer.id(),
+                    Data::Gossip(rumor),
+                    frequency,
+                    *signal_strength
+                );
+                let delay_map = IdToDelayMap::from([(
+                    peer.id(),
+                    delay_to(device.distance_to(*peer), self.delay_multiplier)
+                        + peer.processing_delay()
+                )]);
+
+                self.signal_queue.add_entry(
+                    self.current_time,
+                    gossip_signal,
+                    delay_map
+                );
+            }
+        }
+    }
+
+    fn record_transmissions(&mut self) {
+        let Some(ref mut packet_events) = self.packet_events else {
+            return;
+        };
+
+        packet_events.extend(
+            self.signal_queue
+                .take_transmissions()
+                .into_iter()
+                .map(|(time, signal)|
+                    PacketEvent::new(
+                        time,
+                        signal.source_id(),
+                        PacketEventKind::Transmitted,
+                        signal
+                    )
+                )
+        );
+    }
+
+    // Sources outside the model, e.g. GPS satellites, transmit for free.
+    fn draw_tx_energy(&mut self) {
+        for (source_id, signal_count) in self.signal_queue
+            .take_transmission_counts()
+        {
+            if let Some(device) = self.device_map.get_mut(&source_id) {
+                device.draw_tx_energy(signal_count);
+                self.mark_device_changed(source_id);
+            }
+        }
+    }
+
+    fn mark_device_changed(&mut self, device_id: DeviceId) {
+        if self.changes.is_some() {
+            self.changed_device_ids.insert(device_id);
+        }
+    }
+
+    fn mark_all_devices_changed(&mut self) {
+        if self.changes.is_some() {
+            self.changed_device_ids.extend(self.device_map.ids());
+        }
+    }
+
+    fn record_changes(&mut self, previous_time: Millisecond) {
+        if let Some(ref mut changes) = self.changes {
+            *changes = ModelChanges::new(
+                previous_time,
+                std::mem::take(&mut self.changed_device_ids),
+                self.signal_queue.take_changes()
+            );
+        }
+    }
+
+    fn add_gps_signals_to_queue(&mut self) {
+        self.gps.add_gps_signals_to_queue(
+            &mut self.signal_queue, 
+            &self.device_map, 
+            self.current_time,
+            self.delay_multiplier,
+        );
+    }
+
+    fn set_initial_state(&mut self) {
+        self.brief_devices();
+        self.update_connections_graph();
+        self.add_gps_signals_to_queue();
+        self.add_scenario_signals_to_queue();
+    }
+
+    // Devices without a mission are briefed on their scenario tasks before
+    // the start.
+    fn brief_devices(&mut self) {
+        for device in self.device_map.values_mut() {
+            if device.mission().is_empty() {
+                device.set_mission(self.scenario.mission_for(device.id()));
+            }
+        }
+    }
+}
+
+
+// Strength of the signal after the weather and link losses along its path.
+fn attenuate_on_path(
+    weather: &Weather,
+    link_loss: &LinkLoss,
+    gps_id: DeviceId,
+    signal: Signal,
+    source: &Point3D,
+    destination: &Point3D
+) -> Signal {
+    let signal = weather.attenuate(signal, source.distance_to(destination));
+
+    // Satellites are above any obstruction.
+    if signal.source_id() == gps_id {
+        signal
+    } else {
+        link_loss.attenuate(signal, source, destination)
+    }
+}
+
+// Devices that were already shut down fail to draw power on every update,
+// so only the failure that shut them down is recorded.
+fn record_device_error(
+    errors: &mut Vec<Arc<crate::Error>>,
+    device: &mut Device,
+    world_view: &WorldView
+) {
+    let was_shut_down = device.is_shut_down();
+    let Err(source) = device.update(world_view) else {
+        return;
+    };
+
+    if !was_shut_down {
+        errors.push(Arc::new(
+            crate::Error::Device { device_id: device.id(), source }
+        ));
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::device::{DeviceBuilder, device_map_from_slice};
+    use crate::backend::device::systems::{
+        ChannelSystem, ClockSystem, GossipSystem, MovementSystem, PowerSystem, 
+        RXModule, Rumor, SensorSystem, TRXSystem, TXModule
+    };
+    use crate::backend::malware::MalwareType;
+    use crate::backend::mathphysics::{
+        Float, Frequency, Meter, Point3D, Position, PowerUnit
+    };
+    use crate::backend::networkmodel::attack::AttackType;
+    use crate::backend::networkmodel::formation::FormationShape;
+    use crate::backend::signal::{
+        FreqToStrengthMap, LogDistance, SignalStrength, GREEN_SIGNAL_STRENGTH
+    };
+    use crate::backend::task::Task;
+
+    use super::*;
+
+
+    const DEVICE_MAX_POWER: PowerUnit       = PowerUnit::new(1000);
+    const LARGE_DEVICE_MAX_POWER: PowerUnit = PowerUnit::new(100_000);
+
+
+    fn network_model_builder() -> NetworkModelBuilder {
+        let command_center = DeviceBuilder::new().build();
+        let drone = DeviceBuilder::new()
+            .set_real_position(Point3D::new(10.0, 0.0, 5.0))
+            .build();
+        let attacker = DeviceBuilder::new().build();
+        let malware = Malware::new(
+            MalwareType::Indicator,
+            Millisecond::new(1000),
+            Some(Millisecond::new(500)),
+            None
+        );
+
+        NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_scenario(
+                Scenario::from([
+                    (
+                        Millisecond::ZERO,
+                        drone.id(),
+                        Task::Reposition(Point3D::default())
+                    )
+                ])
+            )
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .set_attacker_devices(vec![
+                AttackerDevice::new(
+                    attacker,
+                    AttackType::MalwareDistribution(malware)
+                )
+            ])
+    }
+
+    fn network_model() -> NetworkModel {
+        network_model_builder().build()
+    }
+
+
+    #[test]
+    fn json_schema_describes_serialized_fields() {
+        let schema = schemars::schema_for!(NetworkModel);
+        let schema_fields: BTreeSet<&String> = schema
+            .get("properties")
+            .and_then(serde_json::Value::as_object)
+            .map(|properties| properties.keys().collect())
+            .unwrap_or_default();
+
+        let model_value = network_model()
+            .to_json_value()
+            .unwrap_or_else(|error| panic!("{}", error));
+        let model_fields: BTreeSet<&String> = model_value
+            .as_object()
+            .map(|fields| fields.keys().collect())
+            .unwrap_or_default();
+
+        assert!(!model_fields.is_empty());
+        assert_eq!(schema_fields, model_fields);
+    }
+
+    #[test]
+    fn binary_roundtrip_matches_json() {
+        let network_model = network_model();
+
+        let bytes = network_model
+            .to_binary()
+            .unwrap_or_else(|error| panic!("{}", error));
+        let deserialized_model = NetworkModel::from_binary(&bytes)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        assert_eq!(
+            deserialized_model.to_json_value().ok(),
+            network_model.to_json_value().ok()
+        );
+    }
+
+    #[test]
+    fn injected_signal_is_received_on_next_update() {
+        let command_center = DeviceBuilder::new().build();
+        let rx_module = RXModule::new(
+            FreqToStrengthMap::from([
+                (Frequency::Control, GREEN_SIGNAL_STRENGTH)
+            ])
+        );
+        let drone = DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(DEVICE_MAX_POWER, DEVICE_MAX_POWER)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_trx_system(TRXSystem::new(TXModule::default(), rx_module))
+            .build();
+        let task = Task::Reposition(Point3D::new(10.0, 10.0, 5.0));
+        let signal = Signal::new(
+            command_center.id(),
+            drone.id(),
+            Data::SetTask(task),
+            Frequency::Control,
+            GREEN_SIGNAL_STRENGTH
+        );
+        let drone_id = drone.id();
+
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .build();
+
+        // Reception is random, so the generator is seeded to keep the test
+        // deterministic.
+        crate::backend::random::set_seed(0);
+        network_model.inject_signal(signal);
+        network_model.update();
+
+        assert_eq!(
+            network_model.device_map().get(&drone_id).map(Device::task),
+            Some(&task)
+        );
+    }
+
+    // The command center sends a task to the drone at the origin while the
+    // interferer transmits on the same frequency from `interferer_position`.
+    fn interfered_network_model(
+        interferer_position: Point3D,
+        delay_multiplier: f32
+    ) -> (NetworkModel, DeviceId, Task) {
+        let command_center = DeviceBuilder::new().build();
+        let rx_module = RXModule::new(
+            FreqToStrengthMap::from([
+                (Frequency::Control, GREEN_SIGNAL_STRENGTH)
+            ])
+        );
+        let drone = DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(DEVICE_MAX_POWER, DEVICE_MAX_POWER)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_trx_system(TRXSystem::new(TXModule::default(), rx_module))
+            .build();
+        let tx_module = TXModule::new(
+            FreqToStrengthMap::from([
+                (Frequency::Control, GREEN_SIGNAL_STRENGTH)
+            ])
+        );
+        let interferer = DeviceBuilder::new()
+            .set_real_position(interferer_position)
+            .set_trx_system(TRXSystem::new(tx_module, RXModule::default()))
+            .build();
+        let task = Task::Reposition(Point3D::new(10.0, 10.0, 5.0));
+        let task_signal = Signal::new(
+            command_center.id(),
+            drone.id(),
+            Data::SetTask(task),
+            Frequency::Control,
+            GREEN_SIGNAL_STRENGTH
+        );
+        let interfering_signal = Signal::new(
+            interferer.id(),
+            command_center.id(),
+            Data::Noise,
+            Frequency::Control,
+            GREEN_SIGNAL_STRENGTH
+        );
+        let drone_id = drone.id();
+
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(
+                device_map_from_slice(&[command_center, drone, interferer])
+            )
+            .set_delay_multiplier(delay_multiplier)
+            .build();
+
+        network_model.set_interference(Interference::default());
+        network_model.inject_signal(task_signal);
+        network_model.inject_signal(interfering_signal);
+
+        (network_model, drone_id, task)
+    }
+
+
+    #[test]
+    fn concurrent_transmission_nearby_blocks_reception() {
+        let (mut network_model, drone_id, task) = interfered_network_model(
+            Point3D::new(1.0, 0.0, 0.0),
+            0.0
+        );
+
+        network_model.update();
+
+        assert_ne!(
+            network_model.device_map().get(&drone_id).map(Device::task),
+            Some(&task)
+        );
+    }
+
+    #[test]
+    fn concurrent_transmission_far_away_does_not_interfere() {
+        let (mut network_model, drone_id, task) = interfered_network_model(
+            Point3D::new(1_000.0, 0.0, 0.0),
+            0.0
+        );
+
+        // Reception is random, so the generator is seeded to keep the test
+        // deterministic.
+        crate::backend::random::set_seed(0);
+        network_model.update();
+
+        assert_eq!(
+            network_model.device_map().get(&drone_id).map(Device::task),
+            Some(&task)
+        );
+    }
+
+    #[test]
+    fn attacker_noise_blocks_reception() {
+        let (network_model, drone_id, task) = interfered_network_model(
+            Point3D::new(1_000.0, 0.0, 0.0),
+            0.0
+        );
+        let tx_module = TXModule::new(
+            FreqToStrengthMap::from([
+                (Frequency::Control, GREEN_SIGNAL_STRENGTH)
+            ])
+        );
+        let attacker = DeviceBuilder::new()
+            .set_real_position(Point3D::new(1.0, 0.0, 0.0))
+            .set_power_system(
+                PowerSystem::build(DEVICE_MAX_POWER, DEVICE_MAX_POWER)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_trx_system(TRXSystem::new(tx_module, RXModule::default()))
+            .build();
+        let mut network_model = NetworkModel {
+            attacker_devices: vec![
+                AttackerDevice::new(attacker, AttackType::ElectronicWarfare)
+            ],
+            ..network_model
+        };
+
+        crate::backend::random::set_seed(0);
+        network_model.update();
+
+        assert_ne!(
+            network_model.device_map().get(&drone_id).map(Device::task),
+            Some(&task)
+        );
+    }
+
+    // The interfering signal is sent to the command center, but the drone
+    // hears it only after it travels the distance to the drone.
+    #[test]
+    fn concurrent_transmission_is_heard_after_delay_to_receiver() {
+        let (mut network_model, drone_id, task) = interfered_network_model(
+            Point3D::new(1.0, 0.0, 0.0),
+            1.0e8
+        );
+
+        crate::backend::random::set_seed(0);
+        network_model.update();
+
+        assert_eq!(
+            network_model.device_map().get(&drone_id).map(Device::task),
+            Some(&task)
+        );
+    }
+
+    #[test]
+    fn packet_tracing_records_transmission_and_reception() {
+        let command_center = DeviceBuilder::new().build();
+        let rx_module = RXModule::new(
+            FreqToStrengthMap::from([
+                (Frequency::Control, GREEN_SIGNAL_STRENGTH)
+            ])
+        );
+        let drone = DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(DEVICE_MAX_POWER, DEVICE_MAX_POWER)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_trx_system(TRXSystem::new(TXModule::default(), rx_module))
+            .build();
+        let signal = Signal::new(
+            command_center.id(),
+            drone.id(),
+            Data::SetTask(Task::Reposition(Point3D::new(10.0, 10.0, 5.0))),
+            Frequency::Control,
+            GREEN_SIGNAL_STRENGTH
+        );
+        let drone_id = drone.id();
+
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .build();
+
+        crate::backend::random::set_seed(0);
+        network_model.inject_signal(signal);
+        network_model.update();
+        assert!(network_model.packet_events().is_empty());
+
+        network_model.set_packet_tracing(true);
+        network_model.inject_signal(signal);
+        network_model.update();
+
+        let packet_events: Vec<(DeviceId, PacketEventKind)> = network_model
+            .packet_events()
+            .iter()
+            .filter(|packet_event| *packet_event.signal() == signal)
+            .map(|packet_event| (packet_event.device_id(), packet_event.kind()))
+            .collect();
+
+        assert_eq!(
+            packet_events,
+            vec![
+                (drone_id, PacketEventKind::Received),
+                (signal.source_id(), PacketEventKind::Transmitted),
+            ]
+        );
+    }
+
+    #[test]
+    fn latency_tracking_records_received_signals() {
+        let command_center = DeviceBuilder::new().build();
+        let rx_module = RXModule::new(
+            FreqToStrengthMap::from([
+                (Frequency::Control, GREEN_SIGNAL_STRENGTH)
+            ])
+        );
+        let drone = DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(DEVICE_MAX_POWER, DEVICE_MAX_POWER)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_trx_system(TRXSystem::new(TXModule::default(), rx_module))
+            .build();
+        let signal = Signal::new(
+            command_center.id(),
+            drone.id(),
+            Data::Noise,
+            Frequency::Control,
+            GREEN_SIGNAL_STRENGTH
+        );
+        let drone_id = drone.id();
+
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .build();
+
+        assert!(network_model.latency_stats().is_none());
+
+        crate::backend::random::set_seed(0);
+        network_model.set_latency_tracking(true);
+        network_model.inject_signal(signal);
+        network_model.update();
+
+        let latency_report = network_model
+            .latency_stats()
+            .and_then(LatencyStats::report)
+            .unwrap();
+
+        assert_eq!(latency_report.overall().count(), 1);
+        assert_eq!(latency_report.overall().max(), Millisecond::ZERO);
+        assert_eq!(latency_report.per_destination()[&drone_id].count(), 1);
+    }
+
+    #[test]
+    fn delivery_stats_ignore_noise() {
+        let command_center = DeviceBuilder::new().build();
+        let rx_module = RXModule::new(
+            FreqToStrengthMap::from([
+                (Frequency::Control, GREEN_SIGNAL_STRENGTH)
+            ])
+        );
+        let drone = DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(DEVICE_MAX_POWER, DEVICE_MAX_POWER)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_trx_system(TRXSystem::new(TXModule::default(), rx_module))
+            .build();
+        let signal = |data| Signal::new(
+            command_center.id(),
+            drone.id(),
+            data,
+            Frequency::Control,
+            GREEN_SIGNAL_STRENGTH
+        );
+        let task_signal = signal(Data::SetTask(Task::Undefined));
+        let noise_signal = signal(Data::Noise);
+
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .build();
+
+        crate::backend::random::set_seed(0);
+        network_model.inject_signal(task_signal);
+        network_model.inject_signal(noise_signal);
+        network_model.update();
+
+        assert_eq!(network_model.delivery_stats().received(), 1);
+        assert_eq!(network_model.delivery_stats().dropped(), 0);
+        assert_eq!(network_model.delivery_stats().delivery_rate(), Some(1.0));
+    }
+
+    #[test]
+    fn issued_task_is_recorded_and_sent() {
+        let power_system = PowerSystem::build(
+            DEVICE_MAX_POWER, 
+            DEVICE_MAX_POWER
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+        let command_center = DeviceBuilder::new()
+            .set_power_system(power_system.clone())
+            .set_trx_system(
+                TRXSystem::new(
+                    TXModule::new(
+                        FreqToStrengthMap::from([(
+                            Frequency::Control,
+                            SignalStrength::from_area_radius(
+                                Meter::new(100.0),
+                                Frequency::Control as Megahertz
+                            )
+                        )])
+                    ),
+                    RXModule::default()
+                )
+            )
+            .build();
+        let drone = DeviceBuilder::new()
+            .set_real_position(Point3D::new(1.0, 0.0, 0.0))
+            .set_power_system(power_system)
+            .set_trx_system(
+                TRXSystem::new(
+                    TXModule::default(),
+                    RXModule::new(
+                        FreqToStrengthMap::from([
+                            (Frequency::Control, SignalStrength::new(10_000.0))
+                        ])
+                    )
+                )
+            )
+            .build();
+        let drone_id = drone.id();
+        let task = Task::Reposition(Point3D::new(10.0, 0.0, 0.0));
+
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .build();
+
+        crate::backend::random::set_seed(0);
+        network_model.update();
+
+        assert!(!network_model.issue_task(drone_id + 1, task));
+        assert!(network_model.issue_task(drone_id, task));
+
+        for _ in 0..10 {
+            network_model.update();
+        }
+
+        let drone = network_model
+            .device_map()
+            .get(&drone_id)
+            .unwrap_or_else(|| panic!("Missing drone"));
+
+        assert_eq!(*drone.task(), task);
+        assert_eq!(network_model.scenario().iter().count(), 1);
+    }
+
+    #[test]
+    fn state_checksum_is_equal_for_runs_with_same_seed() {
+        let mut first_model = network_model();
+        let mut second_model = first_model.clone();
+
+        crate::backend::random::set_seed(1);
+        for _ in 0..10 {
+            first_model.update();
+        }
+        crate::backend::random::set_seed(1);
+        for _ in 0..10 {
+            second_model.update();
+        }
+
+        assert_eq!(
+            first_model.state_checksum(),
+            second_model.state_checksum()
+        );
+    }
+
+    #[test]
+    fn adaptive_update_of_settled_model_matches_regular_updates() {
+        let mut regular_model = network_model();
+        let mut adaptive_model = regular_model.clone();
+
+        assert!(adaptive_model.is_settled());
+
+        for _ in 0..10 {
+            regular_model.update();
+        }
+        let step = adaptive_model.update_adaptive(10 * ITERATION_TIME);
+
+        assert_eq!(step, 10 * ITERATION_TIME);
+        assert_eq!(
+            regular_model.state_checksum(),
+            adaptive_model.state_checksum()
+        );
+    }
+
+    #[test]
+    fn adaptive_update_advances_attackers_by_full_step() {
+        let attacker = DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(DEVICE_MAX_POWER, DEVICE_MAX_POWER)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .build();
+        let mut regular_model = network_model_builder()
+            .set_attacker_devices(vec![
+                AttackerDevice::new(attacker, AttackType::ElectronicWarfare)
+            ])
+            .build();
+        let mut adaptive_model = regular_model.clone();
+        let attacker_power_system = |network_model: &NetworkModel| 
+            network_model.attacker_devices[0]
+                .device()
+                .power_system()
+                .clone();
+
+        assert!(adaptive_model.is_settled());
+
+        for _ in 0..10 {
+            regular_model.update();
+        }
+        adaptive_model.update_adaptive(10 * ITERATION_TIME);
+
+        let power_system = attacker_power_system(&regular_model);
+
+        assert!(power_system.energy() < power_system.max_energy());
+        assert_eq!(
+            power_system.energy(),
+            attacker_power_system(&adaptive_model).energy()
+        );
+    }
+
+    // The drone sees the target beneath it on every iteration.
+    fn recon_network_model() -> (NetworkModel, DeviceId) {
+        let power_system = PowerSystem::build(
+            DEVICE_MAX_POWER,
+            DEVICE_MAX_POWER
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+        let command_center = DeviceBuilder::new()
+            .set_power_system(power_system.clone())
+            .set_trx_system(
+                TRXSystem::new(
+                    TXModule::default(),
+                    RXModule::new(
+                        FreqToStrengthMap::from([
+                            (Frequency::Control, GREEN_SIGNAL_STRENGTH)
+                        ])
+                    )
+                )
+            )
+            .build();
+        let drone = DeviceBuilder::new()
+            .set_real_position(Point3D::new(30.0, 0.0, 10.0))
+            .set_power_system(power_system)
+            .set_trx_system(
+                TRXSystem::new(
+                    TXModule::new(
+                        FreqToStrengthMap::from([(
+                            Frequency::Control,
+                            SignalStrength::from_area_radius(
+                                Meter::new(50.0), 
+                                Frequency::Control as Megahertz
+                            )
+                        )])
+                    ),
+                    RXModule::default()
+                )
+            )
+            .set_sensor_system(
+                SensorSystem::new(
+                    std::f32::consts::FRAC_PI_2, 
+                    Meter::new(20.0), 
+                    ITERATION_TIME
+                )
+            )
+            .build();
+        let target = DeviceBuilder::new()
+            .set_real_position(Point3D::new(30.0, 0.0, 0.0))
+            .build();
+        let target_id = target.id();
+
+        let network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(
+                device_map_from_slice(&[command_center, drone, target])
+            )
+            .build();
+
+        (network_model, target_id)
+    }
+
+
+    #[test]
+    fn sensor_detections_reach_command_device() {
+        let (mut network_model, target_id) = recon_network_model();
+
+        crate::backend::random::set_seed(0);
+        let mut recon_records = Vec::new();
+        for _ in 0..20 {
+            network_model.update();
+            recon_records.extend(
+                network_model
+                    .events()
+                    .iter()
+                    .filter_map(|event| match event.kind() {
+                        EventKind::ReconReceived(record) => Some(*record),
+                        _                                => None,
+                    })
+            );
+        }
+
+        assert!(!recon_records.is_empty());
+        assert!(
+            recon_records
+                .iter()
+                .all(|record| record.target_id() == target_id)
+        );
+        assert_eq!(
+            network_model.recognized_picture().contacts().len(),
+            1
+        );
+        assert!(
+            network_model.recognized_picture().contact(target_id).is_some()
+        );
+    }
+
+    #[test]
+    fn propagation_model_applies_to_device_transmitters() {
+        let (mut network_model, _) = recon_network_model();
+        let max_control_radius = |network_model: &NetworkModel| network_model
+            .drones()
+            .map(|drone| drone.area_radius_on(Frequency::Control))
+            .fold(Meter::ZERO, Meter::max);
+        let free_space_radius = max_control_radius(&network_model);
+
+        network_model.set_propagation(
+            Propagation::LogDistance(LogDistance::new(4.0, Meter::new(1.0)))
+        );
+
+        assert!(max_control_radius(&network_model) < free_space_radius);
+    }
+
+    #[test]
+    fn deserialized_propagation_model_applies_to_device_transmitters() {
+        let (network_model, _) = recon_network_model();
+        let log_distance = LogDistance::new(4.0, Meter::new(1.0));
+        let mut model_value = network_model
+            .to_json_value()
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        model_value["propagation"] = serde_json::json!({
+            "LogDistance": { "exponent": 4.0, "reference_distance": 1.0 }
+        });
+
+        let deserialized_model = NetworkModel::from_json_value(model_value)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        for drone in deserialized_model.drones() {
+            let tx_signal_strength = drone
+                .tx_signal_strength_on(&Frequency::Control)
+                .copied()
+                .unwrap_or_default();
+
+            assert_eq!(
+                drone.area_radius_on(Frequency::Control),
+                log_distance.area_radius(
+                    tx_signal_strength,
+                    Frequency::Control as Megahertz
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn devices_get_simulation_parameters_before_first_update() {
+        let parameters = SimulationParameters::default()
+            .set_destination_radius(Meter::new(50.0))
+            .unwrap_or_else(|error| panic!("{}", error));
+        let network_model = network_model_builder()
+            .set_parameters(parameters)
+            .build();
+        let mut model_value = network_model
+            .to_json_value()
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        for device in network_model.device_map().values() {
+            assert_eq!(device.parameters(), &parameters);
+        }
+
+        model_value["parameters"]["destination_radius"] = 80.0.into();
+
+        let deserialized_model = NetworkModel::from_json_value(
+            model_value.clone()
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        for device in deserialized_model.device_map().values() {
+            assert_eq!(
+                device.parameters().destination_radius(),
+                Meter::new(80.0)
+            );
+        }
+
+        model_value["parameters"]["drone_mass"] = 0.0.into();
+
+        assert!(NetworkModel::from_json_value(model_value).is_err());
+    }
+
+    #[test]
+    fn drones_fly_at_max_drone_speed_of_parameters() {
+        let mut network_model = colliding_network_model(
+            CollisionPolicy::default()
+        );
+        let parameters = SimulationParameters::default()
+            .set_max_drone_speed(30.0)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        network_model.set_parameters(parameters);
+
+        let command_device_id = network_model.command_device_id();
+
+        for device in network_model.device_map().values() {
+            let max_speed = if device.id() == command_device_id {
+                0.0
+            } else {
+                30.0
+            };
+
+            assert!((device.max_speed() - max_speed).abs() < Float::EPSILON);
+        }
+
+        let mut model_value = network_model
+            .to_json_value()
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        model_value["parameters"]["max_drone_speed"] = 40.0.into();
+
+        let deserialized_model = NetworkModel::from_json_value(model_value)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        assert!(
+            deserialized_model
+                .drones()
+                .all(|drone| (drone.max_speed() - 40.0).abs() < Float::EPSILON)
+        );
+    }
+
+    #[test]
+    fn relays_summarize_sensor_detections() {
+        let (mut network_model, target_id) = recon_network_model();
+
+        network_model.set_aggregator(Aggregator::new(ITERATION_TIME * 5));
+
+        crate::backend::random::set_seed(0);
+        for _ in 0..20 {
+            network_model.update();
+        }
+
+        assert!(
+            network_model.aggregator().ratio().is_some_and(|ratio| ratio > 1.0)
+        );
+        assert!(
+            network_model.recognized_picture().contact(target_id).is_some()
+        );
+    }
+
+    #[test]
+    fn formation_member_keeps_slot_next_to_leader() {
+        let power_system = PowerSystem::build(
+            LARGE_DEVICE_MAX_POWER,
+            LARGE_DEVICE_MAX_POWER
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+        let tx_module = |frequency| TXModule::new(
+            FreqToStrengthMap::from([(
+                frequency,
+                SignalStrength::from_area_radius(
+                    Meter::new(300.0), 
+                    Frequency::Control as Megahertz
+                )
+            )])
+        );
+        let rx_module = RXModule::new(
+            FreqToStrengthMap::from([
+                (Frequency::Control, SignalStrength::new(10_000.0)),
+                (Frequency::GPS, SignalStrength::new(10_000.0)),
+            ])
+        );
+        let drone = |position, max_speed| DeviceBuilder::new()
+            .set_real_position(position)
+            .set_power_system(power_system.clone())
+            .set_movement_system(
+                MovementSystem::build(max_speed)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_trx_system(
+                TRXSystem::new(TXModule::default(), rx_module.clone())
+            )
+            .build();
+
+        let command_center = DeviceBuilder::new()
+            .set_power_system(power_system.clone())
+            .set_trx_system(
+                TRXSystem::new(tx_module(Frequency::Control), rx_module.clone())
+            )
+            .build();
+        let gps = GPS::new(
+            DeviceBuilder::new()
+                .set_power_system(power_system.clone())
+                .set_trx_system(
+                    TRXSystem::new(
+                        tx_module(Frequency::GPS), 
+                        RXModule::default()
+                    )
+                )
+                .build()
+        );
+        let leader = drone(Point3D::new(0.0, 0.0, 10.0), 5.0);
+        let member = drone(Point3D::new(5.0, -5.0, 10.0), 10.0);
+        let (leader_id, member_id) = (leader.id(), member.id());
+
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(
+                device_map_from_slice(&[command_center, leader, member])
+            )
+            .set_gps(gps)
+            .set_scenario(
+                Scenario::from([(
+                    Millisecond::ZERO, 
+                    BROADCAST_ID, 
+                    Task::Reposition(Point3D::new(0.0, 100.0, 10.0))
+                )])
+            )
+            .set_formations(vec![
+                Formation::new(
+                    leader_id, 
+                    vec![member_id], 
+                    FormationShape::Line, 
+                    Meter::new(10.0)
+                )
+            ])
+            .build();
+
+        crate::backend::random::set_seed(0);
+        for _ in 0..100 {
+            network_model.update();
+        }
+
+        let leader = network_model
+            .device_map()
+            .get(&leader_id)
+            .unwrap_or_else(|| panic!("Missing leader"));
+        let member = network_model
+            .device_map()
+            .get(&member_id)
+            .unwrap_or_else(|| panic!("Missing member"));
+        // Leader flies along `y`, so its right side is along `x`.
+        let slot_position = *leader.position() + Point3D::new(10.0, 0.0, 0.0);
+
+        assert!(matches!(leader.task(), Task::Reposition(_)));
+        assert!(matches!(member.task(), Task::KeepFormation(_)));
+        assert!(leader.position().y > 10.0);
+        assert!(member.distance_to(&slot_position) < Meter::new(2.0));
+    }
+
+    fn colliding_network_model(
+        collision_policy: CollisionPolicy
+    ) -> NetworkModel {
+        let power_system = PowerSystem::build(
+            LARGE_DEVICE_MAX_POWER,
+            LARGE_DEVICE_MAX_POWER
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+        let drone = || DeviceBuilder::new()
+            .set_real_position(Point3D::new(10.0, 0.0, 5.0))
+            .set_power_system(power_system.clone())
+            .set_movement_system(
+                MovementSystem::build(10.0)
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .build();
+        let command_center = DeviceBuilder::new().build();
+
+        NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(
+                device_map_from_slice(&[command_center, drone(), drone()])
+            )
+            .set_collision_policy(collision_policy)
+            .build()
+    }
+
+    fn collision_count(network_model: &NetworkModel) -> usize {
+        network_model
+            .events()
+            .iter()
+            .filter(|event| matches!(event.kind(), EventKind::Collided { .. }))
+            .count()
+    }
+
+
+    #[test]
+    fn colliding_drones_are_destroyed() {
+        let mut network_model = colliding_network_model(
+            CollisionPolicy::new(Meter::new(1.0), 1.0, 0.0, 0.0)
+        );
+
+        network_model.update();
+
+        assert_eq!(collision_count(&network_model), 2);
+        assert!(
+            network_model
+                .device_map()
+                .values()
+                .filter(|device| 
+                    device.id() != network_model.command_device_id()
+                )
+                .all(|device| device.is_shut_down() && device.health() == 0.0)
+        );
+    }
+
+    #[test]
+    fn drones_in_contact_are_damaged_once() {
+        let mut network_model = colliding_network_model(
+            CollisionPolicy::new(Meter::new(1.0), 0.0, 1.0, 0.25)
+        );
+
+        network_model.update();
+        assert_eq!(collision_count(&network_model), 2);
+
+        network_model.update();
+        assert_eq!(collision_count(&network_model), 0);
+
+        assert!(
+            network_model
+                .device_map()
+                .values()
+                .filter(|device| 
+                    device.id() != network_model.command_device_id()
+                )
+                .all(|device| 
+                    !device.is_shut_down() && device.health() == 0.75
+                )
+        );
+    }
+
+    #[test]
+    fn try_build_accepts_consistent_parts() {
+        assert!(network_model_builder().try_build().is_ok());
+    }
+
+    #[test]
+    fn try_build_requires_command_center() {
+        let device_map = device_map_from_slice(&[DeviceBuilder::new().build()]);
+        let missing_id = device_map
+            .values()
+            .map(Device::id)
+            .max()
+            .unwrap_or_default() + 1_000_000;
+
+        assert_eq!(
+            NetworkModelBuilder::new()
+                .set_device_map(device_map.clone())
+                .try_build()
+                .err(),
+            Some(BuildError::MissingCommandCenterId)
+        );
+        assert_eq!(
+            NetworkModelBuilder::new()
+                .set_command_center_id(BROADCAST_ID)
+                .set_device_map(device_map.clone())
+                .try_build()
+                .err(),
+            Some(BuildError::BroadcastCommandCenterId)
+        );
+        assert_eq!(
+            NetworkModelBuilder::new()
+                .set_command_center_id(missing_id)
+                .try_build()
+                .err(),
+            Some(BuildError::MissingDeviceMap)
+        );
+        assert_eq!(
+            NetworkModelBuilder::new()
+                .set_command_center_id(missing_id)
+                .set_device_map(device_map)
+                .try_build()
+                .err(),
+            Some(BuildError::MissingCommandCenter(missing_id))
+        );
+    }
+
+    #[test]
+    fn try_build_rejects_dangling_and_duplicate_ids() {
+        let unknown_device = DeviceBuilder::new().build();
+        let scenario = Scenario::from([
+            (
+                Millisecond::new(100),
+                unknown_device.id(),
+                Task::Reposition(Point3D::default())
+            )
+        ]);
+
+        assert_eq!(
+            network_model_builder()
+                .set_scenario(scenario)
+                .try_build()
+                .err(),
+            Some(
+                BuildError::DanglingScenarioDevice(
+                    Millisecond::new(100),
+                    unknown_device.id()
+                )
+            )
+        );
+
+        let command_center = DeviceBuilder::new().build();
+        let drone = DeviceBuilder::new().build();
+
+        assert_eq!(
+            NetworkModelBuilder::new()
+                .set_command_center_id(command_center.id())
+                .set_device_map(
+                    device_map_from_slice(&[command_center, drone.clone()])
+                )
+                .set_attacker_devices(vec![
+                    AttackerDevice::new(
+                        drone.clone(),
+                        AttackType::ElectronicWarfare
+                    )
+                ])
+                .try_build()
+                .err(),
+            Some(BuildError::DuplicateAttackerId(drone.id()))
+        );
+    }
+
+    #[test]
+    fn jammed_drone_takes_command_center_to_backup_frequency() {
+        let tx_signal_strength = SignalStrength::from_area_radius(
+            Meter::new(100.0),
+            Frequency::Control as Megahertz
+        );
+        let device_builder = DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(
+                    LARGE_DEVICE_MAX_POWER, 
+                    LARGE_DEVICE_MAX_POWER
+                )
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_trx_system(
+                TRXSystem::new(
+                    TXModule::new(
+                        FreqToStrengthMap::from([
+                            (Frequency::Control, tx_signal_strength)
+                        ])
+                    ),
+                    RXModule::new(
+                        FreqToStrengthMap::from([
+                            (Frequency::Control, SignalStrength::new(10_000.0))
+                        ])
+                    )
+                )
+            )
+            .set_channel_system(
+                ChannelSystem::new(Frequency::Backup, Millisecond::new(100))
+            );
+        let command_center = device_builder.clone().build();
+        let drone = device_builder
+            .set_real_position(Point3D::new(10.0, 0.0, 0.0))
+            .build();
+        let command_center_id = command_center.id();
+        let drone_id = drone.id();
+        let noise = Signal::new(
+            command_center_id,
+            drone_id,
+            Data::Noise,
+            Frequency::Control,
+            GREEN_SIGNAL_STRENGTH
+        );
+
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center_id)
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .build();
+
+        crate::backend::random::set_seed(0);
+
+        let mut switched_ids = Vec::new();
+
+        for _ in 0..10 {
+            network_model.inject_signal(noise);
+            network_model.update();
+            switched_ids.extend(
+                network_model
+                    .events()
+                    .iter()
+                    .filter(|event| 
+                        matches!(
+                            event.kind(), 
+                            EventKind::ControlChannelSwitched { .. }
+                        )
+                    )
+                    .map(Event::device_id)
+            );
+        }
+
+        assert_eq!(switched_ids, vec![drone_id, command_center_id]);
+        assert!(
+            network_model
+                .device_map()
+                .values()
+                .all(|device| device.control_frequency() == Frequency::Backup)
+        );
+        assert!(
+            network_model
+                .connections()
+                .graph_map()
+                .contains_edge(command_center_id, drone_id)
+        );
+    }
+
+    // Devices that reach each other on the control frequency 100 m apart.
+    fn control_device_builder() -> DeviceBuilder {
+        let tx_signal_strength = SignalStrength::from_area_radius(
+            Meter::new(100.0),
+            Frequency::Control as Megahertz
+        );
+
+        DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(
+                    LARGE_DEVICE_MAX_POWER, 
+                    LARGE_DEVICE_MAX_POWER
+                )
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .set_trx_system(
+                TRXSystem::new(
+                    TXModule::new(
+                        FreqToStrengthMap::from([
+                            (Frequency::Control, tx_signal_strength)
+                        ])
+                    ),
+                    RXModule::new(
+                        FreqToStrengthMap::from([
+                            (Frequency::Control, SignalStrength::new(10_000.0))
+                        ])
+                    )
+                )
+            )
+    }
+
+    #[test]
+    fn gossip_spreads_infection_to_every_drone() {
+        let infection = Rumor::Infection { 
+            device_id: BROADCAST_ID, 
+            time: Millisecond::ZERO 
+        };
+        let mut informed_gossip_system = GossipSystem::new(ITERATION_TIME, 1);
+        informed_gossip_system.learn(infection);
+        let drone_builder = control_device_builder()
+            .set_gossip_system(GossipSystem::new(ITERATION_TIME, 1));
+        let command_center = DeviceBuilder::new().build();
+        let informed_drone = drone_builder
+            .clone()
+            .set_gossip_system(informed_gossip_system)
+            .build();
+        let near_drone = drone_builder
+            .clone()
+            .set_real_position(Point3D::new(10.0, 0.0, 0.0))
+            .build();
+        let far_drone = drone_builder
+            .set_real_position(Point3D::new(20.0, 0.0, 0.0))
+            .build();
+        let learned_ids = BTreeSet::from([near_drone.id(), far_drone.id()]);
+
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(
+                device_map_from_slice(
+                    &[command_center, informed_drone, near_drone, far_drone]
+                )
+            )
+            .build();
+
+        crate::backend::random::set_seed(0);
+
+        let mut rumor_learned_ids = BTreeSet::new();
+
+        for _ in 0..20 {
+            network_model.update();
+            rumor_learned_ids.extend(
+                network_model
+                    .events()
+                    .iter()
+                    .filter(|event| 
+                        *event.kind() == EventKind::RumorLearned(infection)
+                    )
+                    .map(Event::device_id)
+            );
+        }
+
+        assert_eq!(rumor_learned_ids, learned_ids);
+        assert!(
+            network_model
+                .drones()
+                .all(|drone| 
+                    drone
+                        .gossip_system()
+                        .infections()
+                        .contains_key(&BROADCAST_ID)
+                )
+        );
+    }
+
+    #[test]
+    fn command_center_beacons_keep_drifting_clock_close() {
+        let command_center = control_device_builder()
+            .set_clock_system(ClockSystem::new(0.0, Millisecond::new(500)))
+            .build();
+        let drone = control_device_builder()
+            .set_real_position(Point3D::new(10.0, 0.0, 0.0))
+            .set_clock_system(ClockSystem::new(100.0, Millisecond::ZERO))
+            .build();
+        let drone_id = drone.id();
+
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .build();
+
+        for _ in 0..40 {
+            network_model.update();
+        }
+
+        let drone = network_model
+            .device_map()
+            .get(&drone_id)
+            .expect("Drone is missing");
+        let drift = drone.clock_system().drift();
+        let offset = drone.local_time() - network_model.current_time();
+
+        // The last beacon was received half a second ago. Without beacons
+        // the offset would be two seconds of drift.
+        assert!(
+            (f64::from(offset.value()) - f64::from(drift) / 2.0).abs() <= 1.0
+        );
+    }
+
+    #[test]
+    fn drained_device_is_reported_once() {
+        let command_center = DeviceBuilder::new().build();
+        let drone = DeviceBuilder::new()
+            .set_power_system(
+                PowerSystem::build(DEVICE_MAX_POWER, PowerUnit::new(1))
+                    .unwrap_or_else(|error| panic!("{}", error))
+            )
+            .build();
+        let drone_id = drone.id();
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .build();
+
+        let mut failed_device_ids = Vec::new();
+
+        for _ in 0..100 {
+            network_model.update();
+
+            failed_device_ids.extend(
+                network_model
+                    .errors()
+                    .filter_map(|error| match error {
+                        crate::Error::Device { device_id, .. } =>
+                            Some(*device_id),
+                        _ => None,
+                    })
+            );
+        }
+
+        assert_eq!(failed_device_ids, vec![drone_id]);
+    }
+
+    #[test]
+    fn unreadable_model_file_is_io_error() {
+        let result = NetworkModel::from_json(Path::new("missing/model.json"));
+
+        assert!(matches!(result, Err(crate::Error::Io { .. })));
     }
 }
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -27,9 +28,14 @@ pub fn add_malware_signals_to_queue(
     current_time: Millisecond,
     delay_multiplier: f32,
 ) {
+    if !malware_list.iter().any(Malware::spreads) {
+        return;
+    }
+
+    let frequency = source_device.control_frequency();
     let Some(signal_strength) = source_device.tx_signal_strength_at(
         destination_device, 
-        Frequency::Control
+        frequency
     ) else {
         return;
     };
@@ -41,7 +47,7 @@ pub fn add_malware_signals_to_queue(
     let delay = delay_to(
         source_device.distance_to(destination_device), 
         delay_multiplier
-    );
+    ) + destination_device.processing_delay();
     let delay_map = IdToDelayMap::from([(destination_device.id(), delay)]);
 
 
@@ -54,7 +60,7 @@ pub fn add_malware_signals_to_queue(
             source_device.id(),
             destination_device.id(),
             Data::Malware(*malware), 
-            Frequency::Control, 
+            frequency, 
             signal_strength
         );
 
@@ -67,7 +73,7 @@ pub fn add_malware_signals_to_queue(
 }
 
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum AttackType {
     ElectronicWarfare,
     GPSSpoofing(Point3D),
@@ -75,7 +81,7 @@ pub enum AttackType {
 }
 
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
 pub struct AttackerDevice {
     device: Device,
     attack_type: AttackType
@@ -135,14 +141,11 @@ impl AttackerDevice {
         match self.attack_type {
             AttackType::ElectronicWarfare             => 
                 self.generate_noise_on_all_frequencies(target_device),
-            AttackType::GPSSpoofing(spoofed_position) => {
-                let spoofing_signal = self.generate_gps_spoofing_signal(
+            AttackType::GPSSpoofing(spoofed_position) => 
+                self.generate_gps_spoofing_signals(
                     target_device, 
                     spoofed_position,
-                )?;
-
-                Ok(vec![spoofing_signal])
-            },
+                ),
             AttackType::MalwareDistribution(malware)  => {
                 let malware_signal = self.generate_signal_with_malware(
                     target_device, 
@@ -177,16 +180,28 @@ impl AttackerDevice {
         Ok(signals_to_send)
     }
 
-    fn generate_gps_spoofing_signal(
+    // Every GPS band the attacker transmits on is spoofed.
+    fn generate_gps_spoofing_signals(
         &self,
         target_device: &Device,
         spoofed_position: Point3D,
-    ) -> Result<Signal, AttackError> {
-        self.device.create_signal_for(
-            target_device, 
-            Data::GPS(spoofed_position), 
-            Frequency::GPS,
-        ).map_err(|_| AttackError::TargetOutOfRange)
+    ) -> Result<Vec<Signal>, AttackError> {
+        let signals_to_send: Vec<Signal> = Frequency::GPS_BANDS
+            .into_iter()
+            .filter_map(|band| {
+                self.device.create_signal_for(
+                    target_device, 
+                    Data::GPS(spoofed_position), 
+                    band
+                ).ok()
+            })
+            .collect();
+
+        if signals_to_send.is_empty() {
+            return Err(AttackError::TargetOutOfRange);
+        }
+
+        Ok(signals_to_send)
     }
     
     fn generate_signal_with_malware(
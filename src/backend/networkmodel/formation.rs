@@ -0,0 +1,296 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::device::{Device, DeviceId, IdToDeviceMap};
+use crate::backend::mathphysics::{
+    Float, Meter, MeterPerSecond, Point3D, Radian, Vector3D
+};
+
+
+// Share of the distance to the slot that a member covers in a second on top
+// of the leader velocity.
+const FORMATION_GAIN: Float = 0.5;
+// Leader has to move faster than that for its heading to change.
+const MIN_HEADING_SPEED: MeterPerSecond = 0.1;
+
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize
+)]
+pub enum FormationShape {
+    // Members fly abreast of the leader on both sides.
+    #[default]
+    Line,
+    // Members fly behind the leader on both sides.
+    Wedge,
+    // Members fill the rows behind the leader. The leader takes the front
+    // left corner of the grid.
+    Grid,
+}
+
+
+#[derive(Clone, Debug, Default, JsonSchema, Serialize, Deserialize)]
+pub struct Formation {
+    leader_id: DeviceId,
+    member_ids: Vec<DeviceId>,
+    shape: FormationShape,
+    spacing: Meter,
+    // Angle of the leader movement direction in the horizontal plane. It is
+    // kept while the leader hovers, so the formation does not turn.
+    heading: Radian,
+}
+
+impl Formation {
+    #[must_use]
+    pub fn new(
+        leader_id: DeviceId,
+        member_ids: Vec<DeviceId>,
+        shape: FormationShape,
+        spacing: Meter,
+    ) -> Self {
+        Self {
+            leader_id,
+            member_ids,
+            shape,
+            spacing,
+            heading: 0.0,
+        }
+    }
+
+    #[must_use]
+    pub fn leader_id(&self) -> DeviceId {
+        self.leader_id
+    }
+
+    #[must_use]
+    pub fn member_ids(&self) -> &[DeviceId] {
+        self.member_ids.as_slice()
+    }
+
+    #[must_use]
+    pub fn shape(&self) -> FormationShape {
+        self.shape
+    }
+
+    #[must_use]
+    pub fn is_member(&self, device_id: DeviceId) -> bool {
+        self.member_ids.contains(&device_id)
+    }
+
+    // Formation falls apart once its leader is gone.
+    #[must_use]
+    pub fn is_active(&self, device_map: &IdToDeviceMap) -> bool {
+        device_map
+            .get(&self.leader_id)
+            .is_some_and(|leader| !leader.is_shut_down())
+    }
+
+    // Offset of the member slot from the leader, where `x` points forward
+    // and `y` points to the right of the leader.
+    #[must_use]
+    pub fn slot_offset(&self, member_index: usize) -> Point3D {
+        // Members are placed on both sides of the leader in turns.
+        let rank = (member_index / 2 + 1) as Float;
+        let side = if member_index.is_multiple_of(2) { 1.0 } else { -1.0 };
+        let spacing = self.spacing.value();
+
+        match self.shape {
+            FormationShape::Line  => Point3D::new(
+                0.0,
+                side * rank * spacing,
+                0.0
+            ),
+            FormationShape::Wedge => Point3D::new(
+                -rank * spacing,
+                side * rank * spacing,
+                0.0
+            ),
+            FormationShape::Grid  => {
+                let column_count = grid_column_count(self.member_ids.len());
+                let slot_index = member_index + 1;
+                let row = (slot_index / column_count) as Float;
+                let column = (slot_index % column_count) as Float;
+
+                Point3D::new(
+                    -row * spacing,
+                    column * spacing,
+                    0.0
+                )
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn slot_position(
+        &self,
+        leader_position: &Point3D,
+        member_index: usize
+    ) -> Point3D {
+        let offset = self.slot_offset(member_index);
+        let (sin, cos) = self.heading.sin_cos();
+
+        Point3D::new(
+            leader_position.x + offset.x * cos + offset.y * sin,
+            leader_position.y + offset.x * sin - offset.y * cos,
+            leader_position.z + offset.z,
+        )
+    }
+
+    pub fn update_heading(&mut self, leader: &Device) {
+        let velocity = leader.velocity().displacement();
+
+        if velocity.x.hypot(velocity.y) > MIN_HEADING_SPEED {
+            self.heading = velocity.y.atan2(velocity.x);
+        }
+    }
+
+    // Members follow the leader velocity and close the distance to their
+    // slots.
+    #[must_use]
+    pub fn velocity_commands(
+        &self,
+        device_map: &IdToDeviceMap
+    ) -> Vec<(DeviceId, Vector3D)> {
+        let Some(leader) = device_map.get(&self.leader_id) else {
+            return Vec::new();
+        };
+        let leader_velocity = leader.velocity().displacement();
+
+        self.member_ids
+            .iter()
+            .enumerate()
+            .filter_map(|(member_index, member_id)| {
+                let member = device_map.get(member_id)?;
+                let slot_position = self.slot_position(
+                    leader.gps_position(),
+                    member_index
+                );
+                let correction = (slot_position - *member.gps_position())
+                    * FORMATION_GAIN;
+                let velocity = Vector3D::new(
+                    Point3D::default(),
+                    leader_velocity + correction
+                );
+
+                Some((*member_id, velocity))
+            })
+            .collect()
+    }
+}
+
+
+fn grid_column_count(member_count: usize) -> usize {
+    let mut column_count = 1;
+
+    // The leader takes a slot too.
+    while column_count * column_count < member_count + 1 {
+        column_count += 1;
+    }
+
+    column_count
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::device::{DeviceBuilder, device_map_from_slice};
+    use crate::backend::device::systems::MovementSystem;
+    use crate::backend::mathphysics::Position;
+
+    use super::*;
+
+
+    const SPACING: Float = 10.0;
+
+
+    #[test]
+    fn members_are_placed_on_both_sides() {
+        let formation = Formation::new(
+            0,
+            vec![1, 2, 3],
+            FormationShape::Wedge,
+            Meter::new(SPACING)
+        );
+
+        assert_eq!(
+            formation.slot_offset(0),
+            Point3D::new(-SPACING, SPACING, 0.0)
+        );
+        assert_eq!(
+            formation.slot_offset(1),
+            Point3D::new(-SPACING, -SPACING, 0.0)
+        );
+        assert_eq!(
+            formation.slot_offset(2),
+            Point3D::new(-2.0 * SPACING, 2.0 * SPACING, 0.0)
+        );
+    }
+
+    #[test]
+    fn grid_is_filled_row_by_row() {
+        let formation = Formation::new(
+            0,
+            vec![1, 2, 3],
+            FormationShape::Grid,
+            Meter::new(SPACING)
+        );
+
+        assert_eq!(
+            formation.slot_offset(0),
+            Point3D::new(0.0, SPACING, 0.0)
+        );
+        assert_eq!(
+            formation.slot_offset(1),
+            Point3D::new(-SPACING, 0.0, 0.0)
+        );
+        assert_eq!(
+            formation.slot_offset(2),
+            Point3D::new(-SPACING, SPACING, 0.0)
+        );
+    }
+
+    #[test]
+    fn members_in_slots_match_leader_velocity() {
+        let leader_velocity = Vector3D::new(
+            Point3D::default(),
+            Point3D::new(0.0, 5.0, 0.0)
+        );
+        let mut leader_movement_system = MovementSystem::build(10.0)
+            .unwrap();
+        leader_movement_system.set_velocity(leader_velocity);
+        let leader = DeviceBuilder::new()
+            .set_movement_system(leader_movement_system)
+            .build();
+
+        // Leader flies along `y`, so its right side is along `x`.
+        let member_position = Point3D::new(SPACING, 0.0, 0.0);
+        let mut member_movement_system = MovementSystem::build(10.0)
+            .unwrap();
+        member_movement_system.set_position(member_position);
+        let member = DeviceBuilder::new()
+            .set_movement_system(member_movement_system)
+            .build();
+
+        let mut formation = Formation::new(
+            leader.id(),
+            vec![member.id()],
+            FormationShape::Line,
+            Meter::new(SPACING)
+        );
+        formation.update_heading(&leader);
+
+        let slot_position = formation.slot_position(leader.gps_position(), 0);
+        let device_map = device_map_from_slice(&[leader, member.clone()]);
+        let velocity_commands = formation.velocity_commands(&device_map);
+        let velocity_error = velocity_commands[0].1.displacement() 
+            - leader_velocity.displacement();
+
+        assert!(
+            slot_position.distance_to(&member_position) < Meter::new(1e-4)
+        );
+        assert_eq!(velocity_commands.len(), 1);
+        assert_eq!(velocity_commands[0].0, member.id());
+        assert!(velocity_error.x.abs() < 1e-4);
+        assert!(velocity_error.y.abs() < 1e-4);
+    }
+}
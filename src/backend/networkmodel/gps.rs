@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::backend::device::{Device, IdToDelayMap, IdToDeviceMap};
@@ -5,7 +6,7 @@ use crate::backend::mathphysics::{delay_to, Frequency, Millisecond, Position};
 use crate::backend::signal::{Data, SignalQueue};
 
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, JsonSchema, Serialize, Deserialize)]
 pub struct GPS(Device);
 
 impl GPS {
@@ -24,6 +25,8 @@ impl GPS {
         &mut self.0
     }
 
+    // Fixes are sent on every band the receiver listens on, so multi-band
+    // receivers get one fix per band.
     pub fn add_gps_signals_to_queue(
         &self,
         signal_queue: &mut SignalQueue,
@@ -32,24 +35,34 @@ impl GPS {
         delay_multiplier: f32,
     ) {
         for device in device_map.values() {
-            let Ok(gps_signal) = self.0.create_signal_for(
-                device,
-                Data::GPS(*device.position()), 
-                Frequency::GPS
-            ) else {
-                continue;
-            };
-
             let delay = delay_to(
                 self.0.distance_to(device), 
                 delay_multiplier
             );
-            
-            signal_queue.add_entry(
-                current_time, 
-                gps_signal,
-                IdToDelayMap::from([(device.id(), delay)])
-            );
+
+            for band in Frequency::GPS_BANDS
+                .into_iter()
+                .filter(|band|
+                    self.0.tx_signal_strength_map().contains_key(band)
+                        && device
+                            .rx_max_signal_strength_map()
+                            .contains_key(band)
+                )
+            {
+                let Ok(gps_signal) = self.0.create_signal_for(
+                    device,
+                    Data::GPS(*device.position()), 
+                    band
+                ) else {
+                    continue;
+                };
+                
+                signal_queue.add_entry(
+                    current_time, 
+                    gps_signal,
+                    IdToDelayMap::from([(device.id(), delay)])
+                );
+            }
         }    
     }
 }
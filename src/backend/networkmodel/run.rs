@@ -0,0 +1,197 @@
+use std::iter::FusedIterator;
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::backend::device::{Device, DeviceId};
+use crate::backend::event::Event;
+use crate::backend::mathphysics::{Millisecond, Point3D, Position};
+use crate::backend::metrics::Metrics;
+
+use super::NetworkModel;
+
+
+// The part of a device most sinks need on every iteration, so whole devices
+// do not have to be cloned.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct DeviceState {
+    id: DeviceId,
+    position: Point3D,
+    battery_level: f32,
+    infected: bool,
+    shut_down: bool,
+}
+
+impl From<&Device> for DeviceState {
+    fn from(device: &Device) -> Self {
+        Self {
+            id: device.id(),
+            position: *device.position(),
+            battery_level: device.power_system().level(),
+            infected: device.is_infected(),
+            shut_down: device.is_shut_down(),
+        }
+    }
+}
+
+impl DeviceState {
+    #[must_use]
+    pub fn id(&self) -> DeviceId {
+        self.id
+    }
+
+    #[must_use]
+    pub fn position(&self) -> &Point3D {
+        &self.position
+    }
+
+    #[must_use]
+    pub fn battery_level(&self) -> f32 {
+        self.battery_level
+    }
+
+    #[must_use]
+    pub fn is_infected(&self) -> bool {
+        self.infected
+    }
+
+    #[must_use]
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down
+    }
+}
+
+
+// State of a model after one update. It owns its data, so states can be
+// kept or sent to other threads while the model goes on.
+#[derive(Clone, Debug)]
+pub struct IterationState {
+    time: Millisecond,
+    metrics: Metrics,
+    devices: Vec<DeviceState>,
+    events: Vec<Event>,
+    errors: Vec<Arc<crate::Error>>,
+}
+
+impl IterationState {
+    // Devices are ordered by ID.
+    #[must_use]
+    pub fn from_model(network_model: &NetworkModel) -> Self {
+        let mut devices: Vec<DeviceState> = network_model
+            .device_map()
+            .values()
+            .map(DeviceState::from)
+            .collect();
+
+        devices.sort_by_key(DeviceState::id);
+
+        Self {
+            time: network_model.current_time(),
+            metrics: Metrics::from_model(network_model),
+            devices,
+            events: network_model.events().to_vec(),
+            errors: network_model.errors.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn time(&self) -> Millisecond {
+        self.time
+    }
+
+    #[must_use]
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    #[must_use]
+    pub fn devices(&self) -> &[DeviceState] {
+        &self.devices
+    }
+
+    #[must_use]
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &crate::Error> {
+        self.errors.iter().map(AsRef::as_ref)
+    }
+}
+
+
+// Updates the model each time it is advanced, until the model time reaches
+// the end time. Returned by `NetworkModel::run`.
+#[must_use = "the model is only updated while the iterator is advanced"]
+pub struct Run<'a> {
+    network_model: &'a mut NetworkModel,
+    end_time: Millisecond,
+}
+
+impl<'a> Run<'a> {
+    pub(super) fn new(
+        network_model: &'a mut NetworkModel,
+        end_time: Millisecond
+    ) -> Self {
+        Self { network_model, end_time }
+    }
+}
+
+impl Iterator for Run<'_> {
+    type Item = IterationState;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.network_model.current_time() >= self.end_time {
+            return None;
+        }
+
+        self.network_model.update();
+
+        Some(IterationState::from_model(self.network_model))
+    }
+}
+
+impl FusedIterator for Run<'_> {}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::ITERATION_TIME;
+    use crate::backend::device::{DeviceBuilder, device_map_from_slice};
+    use crate::backend::networkmodel::NetworkModelBuilder;
+
+    use super::*;
+
+
+    #[test]
+    fn run_yields_state_after_each_update() {
+        let command_center = DeviceBuilder::new().build();
+        let drone = DeviceBuilder::new().build();
+        let mut network_model = NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(device_map_from_slice(&[drone, command_center]))
+            .build();
+
+        let end_time = Millisecond::new(500);
+        let states: Vec<IterationState> = network_model
+            .run(end_time)
+            .collect();
+        let times: Vec<Millisecond> = states
+            .iter()
+            .map(IterationState::time)
+            .collect();
+        let expected_times: Vec<Millisecond> = (1..=10)
+            .map(|iteration| iteration * ITERATION_TIME)
+            .collect();
+
+        assert_eq!(times, expected_times);
+        assert_eq!(network_model.current_time(), end_time);
+        assert!(
+            states.iter().all(|state|
+                state.devices().len() == 2
+                    && state.devices()[0].id() < state.devices()[1].id()
+            )
+        );
+        assert_eq!(network_model.run(end_time).count(), 0);
+    }
+}
@@ -0,0 +1,587 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::backend::aggregation::Aggregator;
+use crate::backend::collision::CollisionPolicy;
+use crate::backend::connections::ConnectionGraph;
+use crate::backend::coverage::CoverageArea;
+use crate::backend::device::DeviceId;
+use crate::backend::fault::FaultInjector;
+use crate::backend::landing::LandingZone;
+use crate::backend::linkloss::LinkLoss;
+use crate::backend::mathphysics::Millisecond;
+use crate::backend::parameters::SimulationParameters;
+use crate::backend::provenance::Provenance;
+use crate::backend::recon::RecognizedPicture;
+use crate::backend::signal::{Interference, Propagation, QueueChange};
+use crate::backend::task::Scenario;
+use crate::backend::weather::Weather;
+
+use super::{AttackerDevice, Formation, NetworkModel, GPS};
+
+
+const DEVICE_MAP_FIELD: &str   = "device_map";
+const SIGNAL_QUEUE_FIELD: &str = "signal_queue";
+
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("Snapshot is not a JSON object")]
+    NotAnObject,
+    #[error("Snapshot field `{0}` has unexpected type")]
+    WrongFieldType(&'static str),
+    #[error("Signal queue index `{0}` is out of bounds")]
+    QueueIndexOutOfBounds(usize),
+    #[error("Failed to serialize snapshot: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+
+// Like `NetworkModel::to_json_value`, values are parsed from strings, so
+// `f32` fields are not widened to `f64`.
+fn to_json_value<T: Serialize>(value: &T) -> serde_json::Result<Value> {
+    serde_json::from_str(&serde_json::to_string(value)?)
+}
+
+
+// Changes of a network model made by its last update.
+// `since` - time of the model before the update.
+#[derive(Clone, Debug, Default)]
+pub struct ModelChanges {
+    pub(super) since: Millisecond,
+    device_ids: BTreeSet<DeviceId>,
+    queue_changes: Vec<QueueChange>,
+}
+
+impl ModelChanges {
+    #[must_use]
+    pub fn new(
+        since: Millisecond,
+        device_ids: BTreeSet<DeviceId>,
+        queue_changes: Vec<QueueChange>
+    ) -> Self {
+        Self { since, device_ids, queue_changes }
+    }
+
+    #[must_use]
+    pub fn since(&self) -> Millisecond {
+        self.since
+    }
+
+    #[must_use]
+    pub fn device_ids(&self) -> &BTreeSet<DeviceId> {
+        &self.device_ids
+    }
+
+    #[must_use]
+    pub fn queue_changes(&self) -> &[QueueChange] {
+        &self.queue_changes
+    }
+}
+
+
+// Serialized fields of the network model other than devices and the signal
+// queue, whose changes are tracked by the model itself.
+#[derive(Serialize)]
+struct ModelFields<'a> {
+    current_time: &'a Millisecond,
+    command_device_id: &'a DeviceId,
+    attacker_devices: &'a [AttackerDevice],
+    gps: &'a GPS,
+    connections: &'a ConnectionGraph,
+    delay_multiplier: &'a f32,
+    parameters: &'a SimulationParameters,
+    propagation: &'a Propagation,
+    scenario: &'a Scenario,
+    formations: &'a [Formation],
+    fault_injector: &'a FaultInjector,
+    recognized_picture: &'a RecognizedPicture,
+    collision_policy: &'a CollisionPolicy,
+    coverage_area: &'a Option<CoverageArea>,
+    weather: &'a Weather,
+    link_loss: &'a LinkLoss,
+    interference: &'a Option<Interference>,
+    landing_zones: &'a Arc<[LandingZone]>,
+    aggregator: &'a Aggregator,
+    colliding_pairs: &'a BTreeSet<(DeviceId, DeviceId)>,
+    provenance: &'a Option<Provenance>,
+}
+
+impl<'a> From<&'a NetworkModel> for ModelFields<'a> {
+    fn from(network_model: &'a NetworkModel) -> Self {
+        Self {
+            current_time: &network_model.current_time,
+            command_device_id: &network_model.command_device_id,
+            attacker_devices: &network_model.attacker_devices,
+            gps: &network_model.gps,
+            connections: &network_model.connections,
+            delay_multiplier: &network_model.delay_multiplier,
+            parameters: &network_model.parameters,
+            propagation: &network_model.propagation,
+            scenario: &network_model.scenario,
+            formations: &network_model.formations,
+            fault_injector: &network_model.fault_injector,
+            recognized_picture: &network_model.recognized_picture,
+            collision_policy: &network_model.collision_policy,
+            coverage_area: &network_model.coverage_area,
+            weather: &network_model.weather,
+            link_loss: &network_model.link_loss,
+            interference: &network_model.interference,
+            landing_zones: &network_model.landing_zones,
+            aggregator: &network_model.aggregator,
+            colliding_pairs: &network_model.colliding_pairs,
+            provenance: &network_model.provenance,
+        }
+    }
+}
+
+
+// Difference between two JSON values. Objects are compared field by field,
+// everything else is replaced whole.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ValueDelta {
+    Replace(Value),
+    Patch { changed: BTreeMap<String, ValueDelta>, removed: Vec<String> },
+}
+
+impl ValueDelta {
+    // Returns `None` if values are equal.
+    fn between(previous: &Value, current: &Value) -> Option<Self> {
+        if previous == current {
+            return None;
+        }
+
+        let (Value::Object(previous), Value::Object(current)) = (
+            previous,
+            current
+        ) else {
+            return Some(Self::Replace(current.clone()));
+        };
+
+        let changed = current
+            .iter()
+            .filter_map(|(key, current_value)| {
+                let delta = match previous.get(key) {
+                    Some(previous_value) =>
+                        Self::between(previous_value, current_value)?,
+                    None                 =>
+                        Self::Replace(current_value.clone()),
+                };
+
+                Some((key.clone(), delta))
+            })
+            .collect();
+        let removed = previous
+            .keys()
+            .filter(|key| !current.contains_key(*key))
+            .cloned()
+            .collect();
+
+        Some(Self::Patch { changed, removed })
+    }
+
+    fn apply_to(&self, value: &mut Value) {
+        match self {
+            Self::Replace(new_value)        => value.clone_from(new_value),
+            Self::Patch { changed, removed } => {
+                if !value.is_object() {
+                    *value = Value::Object(Map::new());
+                }
+
+                let Value::Object(object) = value else {
+                    return;
+                };
+
+                for key in removed {
+                    object.remove(key);
+                }
+                for (key, delta) in changed {
+                    delta.apply_to(
+                        object.entry(key.clone()).or_insert(Value::Null)
+                    );
+                }
+            },
+        }
+    }
+}
+
+
+// Difference between two states of a network model. Devices are keyed by
+// their IDs as in the serialized device map.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelDelta {
+    changed_fields: BTreeMap<String, ValueDelta>,
+    changed_devices: BTreeMap<String, ValueDelta>,
+    signal_queue: Vec<QueueChange>,
+}
+
+impl ModelDelta {
+    /// # Errors
+    ///
+    /// Will return `Err` if the snapshot is not a serialized network model or
+    /// the delta does not match it.
+    pub fn apply_to(&self, snapshot: &mut Value) -> Result<(), SnapshotError> {
+        let Value::Object(snapshot) = snapshot else {
+            return Err(SnapshotError::NotAnObject);
+        };
+
+        for (field, delta) in &self.changed_fields {
+            delta.apply_to(
+                snapshot.entry(field.clone()).or_insert(Value::Null)
+            );
+        }
+
+        let device_map = snapshot
+            .entry(DEVICE_MAP_FIELD)
+            .or_insert_with(|| Value::Object(Map::new()));
+        let Value::Object(device_map) = device_map else {
+            return Err(SnapshotError::WrongFieldType(DEVICE_MAP_FIELD));
+        };
+
+        for (device_id, delta) in &self.changed_devices {
+            delta.apply_to(
+                device_map.entry(device_id.clone()).or_insert(Value::Null)
+            );
+        }
+
+        let signal_queue = snapshot
+            .entry(SIGNAL_QUEUE_FIELD)
+            .or_insert_with(|| Value::Array(Vec::new()));
+        let Value::Array(signal_queue) = signal_queue else {
+            return Err(SnapshotError::WrongFieldType(SIGNAL_QUEUE_FIELD));
+        };
+
+        for queue_change in &self.signal_queue {
+            apply_queue_change(queue_change, signal_queue)?;
+        }
+
+        Ok(())
+    }
+}
+
+
+fn apply_queue_change(
+    queue_change: &QueueChange,
+    signal_queue: &mut Vec<Value>
+) -> Result<(), SnapshotError> {
+    match queue_change {
+        QueueChange::Insert(index, entry) => {
+            if *index > signal_queue.len() {
+                return Err(SnapshotError::QueueIndexOutOfBounds(*index));
+            }
+
+            signal_queue.insert(*index, to_json_value(entry)?);
+        },
+        QueueChange::Remove(indices)      => {
+            for index in indices.iter().rev() {
+                if *index >= signal_queue.len() {
+                    return Err(SnapshotError::QueueIndexOutOfBounds(*index));
+                }
+
+                signal_queue.remove(*index);
+            }
+        },
+    }
+
+    Ok(())
+}
+
+
+// Builds deltas from the changes a network model tracks. Only changed
+// devices and fields other than devices and the signal queue are serialized
+// and compared with their last encoded states.
+#[derive(Clone, Debug, Default)]
+pub struct DeltaEncoder {
+    // Time of the last encoded model state, `None` until a keyframe is set.
+    time: Option<Millisecond>,
+    fields: Map<String, Value>,
+    devices: HashMap<String, Value>,
+}
+
+impl DeltaEncoder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Following deltas are relative to the full snapshot of the model at
+    // `time`.
+    /// # Errors
+    ///
+    /// Will return `Err` if the snapshot is not a serialized network model.
+    pub fn set_keyframe(
+        &mut self,
+        snapshot: &Value,
+        time: Millisecond
+    ) -> Result<(), SnapshotError> {
+        self.reset();
+
+        let Value::Object(snapshot) = snapshot else {
+            return Err(SnapshotError::NotAnObject);
+        };
+        let Some(Value::Object(device_map)) = snapshot.get(DEVICE_MAP_FIELD)
+        else {
+            return Err(SnapshotError::WrongFieldType(DEVICE_MAP_FIELD));
+        };
+
+        self.fields = snapshot
+            .iter()
+            .filter(|(field, _)|
+                *field != DEVICE_MAP_FIELD && *field != SIGNAL_QUEUE_FIELD
+            )
+            .map(|(field, value)| (field.clone(), value.clone()))
+            .collect();
+        self.devices = device_map
+            .iter()
+            .map(|(device_id, device)| (device_id.clone(), device.clone()))
+            .collect();
+        self.time = Some(time);
+
+        Ok(())
+    }
+
+    // A keyframe is required before the first delta and when the changes
+    // tracked by the model do not follow the last encoded state.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    // Returns `None` if the model does not track changes or a keyframe is
+    // required. The encoder is reset on failure.
+    /// # Errors
+    ///
+    /// Will return `Err` if the model can not be serialized.
+    pub fn delta(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> Result<Option<ModelDelta>, SnapshotError> {
+        let Some(ref changes) = network_model.changes else {
+            return Ok(None);
+        };
+
+        if self.time != Some(changes.since()) {
+            return Ok(None);
+        }
+
+        let delta = self.encode(network_model, changes);
+
+        if delta.is_err() {
+            self.reset();
+        }
+
+        delta.map(Some)
+    }
+
+    fn encode(
+        &mut self,
+        network_model: &NetworkModel,
+        changes: &ModelChanges
+    ) -> Result<ModelDelta, SnapshotError> {
+        let Value::Object(fields) = to_json_value(
+            &ModelFields::from(network_model)
+        )? else {
+            return Err(SnapshotError::NotAnObject);
+        };
+
+        let mut changed_fields = BTreeMap::new();
+
+        for (field, value) in fields {
+            let delta = match self.fields.get(&field) {
+                Some(previous_value) =>
+                    ValueDelta::between(previous_value, &value),
+                None                 =>
+                    Some(ValueDelta::Replace(value.clone())),
+            };
+
+            if let Some(delta) = delta {
+                changed_fields.insert(field.clone(), delta);
+                self.fields.insert(field, value);
+            }
+        }
+
+        let mut changed_devices = BTreeMap::new();
+
+        for device_id in changes.device_ids() {
+            let Some(device) = network_model.device_map.get(device_id) else {
+                continue;
+            };
+            let device_id = device_id.to_string();
+            let device = to_json_value(device)?;
+            let delta = match self.devices.get(&device_id) {
+                Some(previous_device) =>
+                    ValueDelta::between(previous_device, &device),
+                None                  =>
+                    Some(ValueDelta::Replace(device.clone())),
+            };
+
+            if let Some(delta) = delta {
+                changed_devices.insert(device_id.clone(), delta);
+                self.devices.insert(device_id, device);
+            }
+        }
+
+        self.time = Some(network_model.current_time);
+
+        Ok(ModelDelta {
+            changed_fields,
+            changed_devices,
+            signal_queue: changes.queue_changes().to_vec(),
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::backend::device::{device_map_from_slice, DeviceBuilder};
+    use crate::backend::mathphysics::Point3D;
+    use crate::backend::networkmodel::NetworkModelBuilder;
+    use crate::backend::task::Task;
+
+    use super::*;
+
+
+    fn network_model() -> NetworkModel {
+        let command_center = DeviceBuilder::new().build();
+        let drone = DeviceBuilder::new()
+            .set_real_position(Point3D::new(10.0, 0.0, 5.0))
+            .build();
+        let scenario = Scenario::from([(
+            Millisecond::ZERO,
+            drone.id(),
+            Task::Reposition(Point3D::default())
+        )]);
+
+        NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_scenario(scenario)
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+            .build()
+    }
+
+    fn snapshot_of(network_model: &NetworkModel) -> Value {
+        network_model
+            .to_json_value()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+
+    #[test]
+    fn model_fields_and_tracked_parts_make_up_snapshot() {
+        let network_model = network_model();
+        let fields = serde_json::to_value(ModelFields::from(&network_model))
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        let mut field_names: BTreeSet<&str> = fields
+            .as_object()
+            .map(|fields| fields.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+        field_names.extend([DEVICE_MAP_FIELD, SIGNAL_QUEUE_FIELD]);
+
+        let snapshot = snapshot_of(&network_model);
+        let snapshot_field_names: BTreeSet<&str> = snapshot
+            .as_object()
+            .map(|fields| fields.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+
+        assert_eq!(field_names, snapshot_field_names);
+    }
+
+    #[test]
+    fn deltas_reconstruct_model_states() {
+        let mut network_model = network_model();
+        let mut delta_encoder = DeltaEncoder::new();
+
+        network_model.set_change_tracking(true);
+
+        let mut snapshot = snapshot_of(&network_model);
+
+        delta_encoder
+            .set_keyframe(&snapshot, network_model.current_time())
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        for _ in 0..10 {
+            network_model.update();
+
+            let delta = delta_encoder
+                .delta(&network_model)
+                .unwrap_or_else(|error| panic!("{}", error))
+                .unwrap_or_else(|| panic!("Keyframe is required"));
+
+            delta
+                .apply_to(&mut snapshot)
+                .unwrap_or_else(|error| panic!("{}", error));
+
+            assert_eq!(snapshot, snapshot_of(&network_model));
+        }
+    }
+
+    #[test]
+    fn skipped_update_requires_keyframe() {
+        let mut network_model = network_model();
+        let mut delta_encoder = DeltaEncoder::new();
+
+        network_model.set_change_tracking(true);
+        delta_encoder
+            .set_keyframe(
+                &snapshot_of(&network_model),
+                network_model.current_time()
+            )
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        network_model.update();
+        network_model.update();
+
+        assert!(
+            delta_encoder
+                .delta(&network_model)
+                .unwrap_or_else(|error| panic!("{}", error))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn delta_without_change_tracking_requires_keyframe() {
+        let mut network_model = network_model();
+        let mut delta_encoder = DeltaEncoder::new();
+
+        delta_encoder
+            .set_keyframe(
+                &snapshot_of(&network_model),
+                network_model.current_time()
+            )
+            .unwrap_or_else(|error| panic!("{}", error));
+        network_model.update();
+
+        assert!(
+            delta_encoder
+                .delta(&network_model)
+                .unwrap_or_else(|error| panic!("{}", error))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn object_delta_contains_only_changes() {
+        let previous = json!({ "1": { "power": 10 }, "2": { "power": 10 } });
+        let current = json!({ "1": { "power": 10 }, "3": { "power": 9 } });
+
+        assert_eq!(
+            ValueDelta::between(&previous, &current),
+            Some(ValueDelta::Patch {
+                changed: BTreeMap::from([(
+                    "3".to_string(),
+                    ValueDelta::Replace(json!({ "power": 9 }))
+                )]),
+                removed: vec!["2".to_string()],
+            })
+        );
+        assert_eq!(ValueDelta::between(&previous, &previous), None);
+    }
+}
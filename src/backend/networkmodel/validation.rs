@@ -0,0 +1,360 @@
+use std::iter;
+
+use derive_more::Display;
+use thiserror::Error;
+
+use crate::backend::device::{Device, DeviceId, BROADCAST_ID};
+use crate::backend::mathphysics::{Frequency, Meter, Millisecond};
+use crate::backend::signal::FreqToStrengthMap;
+
+use super::NetworkModel;
+
+
+#[derive(Clone, Copy, Debug, Display, PartialEq)]
+pub enum DeviceRole {
+    #[display("device {_0}")]
+    Device(DeviceId),
+    #[display("attacker device {_0}")]
+    Attacker(DeviceId),
+    #[display("GPS")]
+    GPS,
+}
+
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum ValidationIssue {
+    #[error("Command center {0} is missing from the device map")]
+    MissingCommandCenter(DeviceId),
+    #[error("Command center does not transmit on control frequency")]
+    CommandCenterWithoutControlTX,
+    #[error("Scenario entry at {0} ms refers to missing device {1}")]
+    DanglingScenarioDevice(Millisecond, DeviceId),
+    #[error("Connection refers to missing device {0}")]
+    DanglingConnectionDevice(DeviceId),
+    #[error("Formation refers to missing device {0}")]
+    DanglingFormationDevice(DeviceId),
+    #[error("TX signal strength of {0} on {1:?} frequency is invalid")]
+    InvalidTXSignalStrength(DeviceRole, Frequency),
+    #[error("Max RX signal strength of {0} on {1:?} frequency is invalid")]
+    InvalidRXSignalStrength(DeviceRole, Frequency),
+    #[error("Attacker device {0} has no attack area")]
+    AttackerWithoutArea(DeviceId),
+    #[error("Collision distance {0} is negative")]
+    NegativeCollisionDistance(Meter),
+}
+
+
+// Looks for inconsistencies that make the model behave unexpectedly instead
+// of failing, since a deserialized model is not checked otherwise.
+#[must_use]
+pub fn validate(network_model: &NetworkModel) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    validate_command_center(network_model, &mut issues);
+    validate_references(network_model, &mut issues);
+    validate_trx_systems(network_model, &mut issues);
+
+    let collision_distance = network_model
+        .collision_policy()
+        .collision_distance();
+
+    if collision_distance < Meter::ZERO {
+        issues.push(
+            ValidationIssue::NegativeCollisionDistance(collision_distance)
+        );
+    }
+
+    issues
+}
+
+fn validate_command_center(
+    network_model: &NetworkModel,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let Some(command_center) = network_model.command_device() else {
+        issues.push(
+            ValidationIssue::MissingCommandCenter(
+                network_model.command_device_id()
+            )
+        );
+        return;
+    };
+
+    if command_center.area_radius_on(command_center.control_frequency()) 
+        <= Meter::ZERO 
+    {
+        issues.push(ValidationIssue::CommandCenterWithoutControlTX);
+    }
+}
+
+fn validate_references(
+    network_model: &NetworkModel,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let device_map = network_model.device_map();
+
+    for (time, device_id, _) in network_model.scenario().iter() {
+        if *device_id != BROADCAST_ID && !device_map.contains_key(device_id) {
+            issues.push(
+                ValidationIssue::DanglingScenarioDevice(*time, *device_id)
+            );
+        }
+    }
+
+    for device_id in network_model.connections().graph_map().nodes() {
+        if !device_map.contains_key(&device_id) {
+            issues.push(ValidationIssue::DanglingConnectionDevice(device_id));
+        }
+    }
+
+    for formation in network_model.formations() {
+        let formation_ids = iter::once(formation.leader_id())
+            .chain(formation.member_ids().iter().copied());
+
+        for device_id in formation_ids {
+            if !device_map.contains_key(&device_id) {
+                issues.push(
+                    ValidationIssue::DanglingFormationDevice(device_id)
+                );
+            }
+        }
+    }
+}
+
+fn validate_trx_systems(
+    network_model: &NetworkModel,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    for device in network_model.device_map().values() {
+        validate_signal_strengths(
+            DeviceRole::Device(device.id()),
+            device,
+            issues
+        );
+    }
+
+    for attacker_device in network_model.attacker_devices() {
+        let device = attacker_device.device();
+
+        validate_signal_strengths(
+            DeviceRole::Attacker(device.id()),
+            device,
+            issues
+        );
+
+        let has_area = [Frequency::Control, Frequency::Backup]
+            .into_iter()
+            .chain(Frequency::GPS_BANDS)
+            .any(|frequency| device.area_radius_on(frequency) > Meter::ZERO);
+
+        if !has_area {
+            issues.push(ValidationIssue::AttackerWithoutArea(device.id()));
+        }
+    }
+
+    validate_signal_strengths(
+        DeviceRole::GPS,
+        network_model.gps().device(),
+        issues
+    );
+}
+
+fn validate_signal_strengths(
+    device_role: DeviceRole,
+    device: &Device,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    for frequency in invalid_frequencies(device.tx_signal_strength_map()) {
+        issues.push(
+            ValidationIssue::InvalidTXSignalStrength(device_role, frequency)
+        );
+    }
+
+    for frequency in invalid_frequencies(device.rx_max_signal_strength_map()) {
+        issues.push(
+            ValidationIssue::InvalidRXSignalStrength(device_role, frequency)
+        );
+    }
+}
+
+// Signal strengths must be finite and non-negative. Frequencies are sorted,
+// so issues are listed in the same order every time.
+fn invalid_frequencies(
+    signal_strength_map: &FreqToStrengthMap
+) -> Vec<Frequency> {
+    let mut frequencies: Vec<Frequency> = signal_strength_map
+        .iter()
+        .filter(|(_, signal_strength)| {
+            let value = signal_strength.value();
+
+            !value.is_finite() || value < 0.0
+        })
+        .map(|(frequency, _)| *frequency)
+        .collect();
+
+    frequencies.sort_by_key(|frequency| *frequency as u32);
+
+    frequencies
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::collision::CollisionPolicy;
+    use crate::backend::device::{DeviceBuilder, device_map_from_slice};
+    use crate::backend::device::systems::{RXModule, TRXSystem, TXModule};
+    use crate::backend::mathphysics::Point3D;
+    use crate::backend::networkmodel::NetworkModelBuilder;
+    use crate::backend::networkmodel::attack::{AttackType, AttackerDevice};
+    use crate::backend::signal::{SignalStrength, GREEN_SIGNAL_STRENGTH};
+    use crate::backend::task::{Scenario, Task};
+
+    use super::*;
+
+
+    const MISSING_DEVICE_ID: DeviceId = DeviceId::MAX;
+
+
+    fn trx_system(
+        frequency: Frequency,
+        tx_signal_strength: SignalStrength
+    ) -> TRXSystem {
+        TRXSystem::new(
+            TXModule::new(
+                FreqToStrengthMap::from([(frequency, tx_signal_strength)])
+            ),
+            RXModule::new(
+                FreqToStrengthMap::from([(frequency, GREEN_SIGNAL_STRENGTH)])
+            ),
+        )
+    }
+
+    fn command_center() -> Device {
+        DeviceBuilder::new()
+            .set_trx_system(
+                trx_system(Frequency::Control, GREEN_SIGNAL_STRENGTH)
+            )
+            .build()
+    }
+
+    fn drone() -> Device {
+        DeviceBuilder::new()
+            .set_real_position(Point3D::new(10.0, 0.0, 5.0))
+            .set_trx_system(
+                trx_system(Frequency::Control, GREEN_SIGNAL_STRENGTH)
+            )
+            .build()
+    }
+
+    fn network_model_builder(
+        command_center: Device,
+        drone: Device
+    ) -> NetworkModelBuilder {
+        NetworkModelBuilder::new()
+            .set_command_center_id(command_center.id())
+            .set_device_map(device_map_from_slice(&[command_center, drone]))
+    }
+
+
+    #[test]
+    fn valid_model_has_no_issues() {
+        let drone = drone();
+        let scenario = Scenario::from([
+            (
+                Millisecond::ZERO,
+                drone.id(),
+                Task::Reposition(Point3D::default())
+            )
+        ]);
+
+        let network_model = network_model_builder(command_center(), drone)
+            .set_scenario(scenario)
+            .build();
+
+        assert!(validate(&network_model).is_empty());
+    }
+
+    #[test]
+    fn dangling_scenario_device() {
+        let scenario = Scenario::from([
+            (
+                Millisecond::new(100),
+                MISSING_DEVICE_ID,
+                Task::Reposition(Point3D::default())
+            )
+        ]);
+
+        let network_model = network_model_builder(command_center(), drone())
+            .set_scenario(scenario)
+            .build();
+
+        assert_eq!(
+            validate(&network_model),
+            vec![
+                ValidationIssue::DanglingScenarioDevice(
+                    Millisecond::new(100),
+                    MISSING_DEVICE_ID
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_command_center() {
+        let network_model = NetworkModelBuilder::new()
+            .set_command_center_id(MISSING_DEVICE_ID)
+            .set_device_map(device_map_from_slice(&[drone()]))
+            .build();
+
+        assert_eq!(
+            validate(&network_model),
+            vec![ValidationIssue::MissingCommandCenter(MISSING_DEVICE_ID)]
+        );
+    }
+
+    #[test]
+    fn negative_tx_signal_strength() {
+        let drone = DeviceBuilder::new()
+            .set_trx_system(
+                trx_system(Frequency::Control, SignalStrength::new(-1.0))
+            )
+            .build();
+        let drone_id = drone.id();
+
+        let network_model = network_model_builder(command_center(), drone)
+            .build();
+
+        assert_eq!(
+            validate(&network_model),
+            vec![
+                ValidationIssue::InvalidTXSignalStrength(
+                    DeviceRole::Device(drone_id),
+                    Frequency::Control
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn attacker_without_area() {
+        let attacker = DeviceBuilder::new().build();
+        let attacker_id = attacker.id();
+
+        let network_model = network_model_builder(command_center(), drone())
+            .set_attacker_devices(vec![
+                AttackerDevice::new(attacker, AttackType::ElectronicWarfare)
+            ])
+            .set_collision_policy(
+                CollisionPolicy::new(Meter::new(-1.0), 0.0, 0.0, 0.0)
+            )
+            .build();
+
+        assert_eq!(
+            validate(&network_model),
+            vec![
+                ValidationIssue::AttackerWithoutArea(attacker_id),
+                ValidationIssue::NegativeCollisionDistance(Meter::new(-1.0)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,395 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error as _;
+use thiserror::Error;
+
+use super::DESTINATION_RADIUS;
+use super::device::MAX_DRONE_SPEED;
+use super::mathphysics::{
+    float_to_f64, Joule, Kilogram, Meter, MeterPerSecond, Watt
+};
+
+
+// Power drawn by device subsystems. Processing costs energy per received
+// signal and transmission per sent one.
+const HOVER_POWER_DRAW: Watt                = Watt::new(60.0);
+const PASSIVE_POWER_DRAW: Watt              = Watt::new(20.0);
+const PROCESSING_ENERGY_CONSUMPTION: Joule  = Joule::new(5.0);
+const TX_ENERGY_CONSUMPTION: Joule          = Joule::new(0.01);
+
+// Movement power draw coefficients. Level flight at max speed draws
+// `HOVER_POWER_DRAW + SPEED_POWER_DRAW` for a drone without payload.
+const CLIMB_POWER_DRAW: Watt = Watt::new(60.0);
+const DRONE_MASS: Kilogram   = 1.5;
+const SPEED_POWER_DRAW: Watt = Watt::new(40.0);
+
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum ParametersError {
+    #[error("Simulation parameter `{0}` must be positive, got {1}")]
+    NotPositive(&'static str, f64),
+    #[error("Simulation parameter `{0}` must not be negative, got {1}")]
+    Negative(&'static str, f64),
+}
+
+
+fn check_positive(
+    name: &'static str,
+    value: f64
+) -> Result<(), ParametersError> {
+    if value.is_nan() || value <= 0.0 {
+        return Err(ParametersError::NotPositive(name, value));
+    }
+
+    Ok(())
+}
+
+fn check_non_negative(
+    name: &'static str,
+    value: f64
+) -> Result<(), ParametersError> {
+    if value.is_nan() || value < 0.0 {
+        return Err(ParametersError::Negative(name, value));
+    }
+
+    Ok(())
+}
+
+
+// Physics shared by all devices of a network model. Missing fields of
+// a JSON model are defaulted, so experiments only list the ones they tune.
+// Deserialized and set parameters are validated, so devices never divide by
+// a zero mass or speed.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+#[serde(default, remote = "Self")]
+pub struct SimulationParameters {
+    // Devices within this distance of a destination have reached it.
+    destination_radius: Meter,
+    // Speed the movement power draw is scaled by.
+    max_drone_speed: MeterPerSecond,
+    drone_mass: Kilogram,
+    passive_power_draw: Watt,
+    hover_power_draw: Watt,
+    speed_power_draw: Watt,
+    climb_power_draw: Watt,
+    processing_energy_consumption: Joule,
+    tx_energy_consumption: Joule,
+}
+
+impl Default for SimulationParameters {
+    fn default() -> Self {
+        Self {
+            destination_radius: DESTINATION_RADIUS,
+            max_drone_speed: MAX_DRONE_SPEED,
+            drone_mass: DRONE_MASS,
+            passive_power_draw: PASSIVE_POWER_DRAW,
+            hover_power_draw: HOVER_POWER_DRAW,
+            speed_power_draw: SPEED_POWER_DRAW,
+            climb_power_draw: CLIMB_POWER_DRAW,
+            processing_energy_consumption: PROCESSING_ENERGY_CONSUMPTION,
+            tx_energy_consumption: TX_ENERGY_CONSUMPTION,
+        }
+    }
+}
+
+impl Serialize for SimulationParameters {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S
+    ) -> Result<S::Ok, S::Error> {
+        Self::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SimulationParameters {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Self, D::Error> {
+        let parameters = Self::deserialize(deserializer)?;
+
+        parameters.validate().map_err(D::Error::custom)?;
+
+        Ok(parameters)
+    }
+}
+
+impl SimulationParameters {
+    /// # Errors
+    ///
+    /// Will return `Err` if the max drone speed or the drone mass is not
+    /// positive, or any other parameter is negative.
+    pub fn validate(&self) -> Result<(), ParametersError> {
+        let positive = [
+            ("max_drone_speed", float_to_f64(self.max_drone_speed)),
+            ("drone_mass", f64::from(self.drone_mass)),
+        ];
+        let non_negative = [
+            (
+                "destination_radius",
+                float_to_f64(self.destination_radius.value())
+            ),
+            (
+                "passive_power_draw",
+                f64::from(self.passive_power_draw.value())
+            ),
+            ("hover_power_draw", f64::from(self.hover_power_draw.value())),
+            ("speed_power_draw", f64::from(self.speed_power_draw.value())),
+            ("climb_power_draw", f64::from(self.climb_power_draw.value())),
+            (
+                "processing_energy_consumption",
+                f64::from(self.processing_energy_consumption.value())
+            ),
+            (
+                "tx_energy_consumption",
+                f64::from(self.tx_energy_consumption.value())
+            ),
+        ];
+
+        for (name, value) in positive {
+            check_positive(name, value)?;
+        }
+        for (name, value) in non_negative {
+            check_non_negative(name, value)?;
+        }
+
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn destination_radius(&self) -> Meter {
+        self.destination_radius
+    }
+
+    #[must_use]
+    pub fn max_drone_speed(&self) -> MeterPerSecond {
+        self.max_drone_speed
+    }
+
+    #[must_use]
+    pub fn drone_mass(&self) -> Kilogram {
+        self.drone_mass
+    }
+
+    #[must_use]
+    pub fn passive_power_draw(&self) -> Watt {
+        self.passive_power_draw
+    }
+
+    #[must_use]
+    pub fn hover_power_draw(&self) -> Watt {
+        self.hover_power_draw
+    }
+
+    #[must_use]
+    pub fn speed_power_draw(&self) -> Watt {
+        self.speed_power_draw
+    }
+
+    #[must_use]
+    pub fn climb_power_draw(&self) -> Watt {
+        self.climb_power_draw
+    }
+
+    #[must_use]
+    pub fn processing_energy_consumption(&self) -> Joule {
+        self.processing_energy_consumption
+    }
+
+    #[must_use]
+    pub fn tx_energy_consumption(&self) -> Joule {
+        self.tx_energy_consumption
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the radius is negative.
+    pub fn set_destination_radius(
+        mut self,
+        destination_radius: Meter
+    ) -> Result<Self, ParametersError> {
+        check_non_negative(
+            "destination_radius",
+            float_to_f64(destination_radius.value())
+        )?;
+        self.destination_radius = destination_radius;
+
+        Ok(self)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the speed is not positive.
+    pub fn set_max_drone_speed(
+        mut self,
+        max_drone_speed: MeterPerSecond
+    ) -> Result<Self, ParametersError> {
+        check_positive("max_drone_speed", float_to_f64(max_drone_speed))?;
+        self.max_drone_speed = max_drone_speed;
+
+        Ok(self)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the mass is not positive.
+    pub fn set_drone_mass(
+        mut self,
+        drone_mass: Kilogram
+    ) -> Result<Self, ParametersError> {
+        check_positive("drone_mass", f64::from(drone_mass))?;
+        self.drone_mass = drone_mass;
+
+        Ok(self)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the power draw is negative.
+    pub fn set_passive_power_draw(
+        mut self,
+        passive_power_draw: Watt
+    ) -> Result<Self, ParametersError> {
+        check_non_negative(
+            "passive_power_draw",
+            f64::from(passive_power_draw.value())
+        )?;
+        self.passive_power_draw = passive_power_draw;
+
+        Ok(self)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the power draw is negative.
+    pub fn set_hover_power_draw(
+        mut self,
+        hover_power_draw: Watt
+    ) -> Result<Self, ParametersError> {
+        check_non_negative(
+            "hover_power_draw",
+            f64::from(hover_power_draw.value())
+        )?;
+        self.hover_power_draw = hover_power_draw;
+
+        Ok(self)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the power draw is negative.
+    pub fn set_speed_power_draw(
+        mut self,
+        speed_power_draw: Watt
+    ) -> Result<Self, ParametersError> {
+        check_non_negative(
+            "speed_power_draw",
+            f64::from(speed_power_draw.value())
+        )?;
+        self.speed_power_draw = speed_power_draw;
+
+        Ok(self)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the power draw is negative.
+    pub fn set_climb_power_draw(
+        mut self,
+        climb_power_draw: Watt
+    ) -> Result<Self, ParametersError> {
+        check_non_negative(
+            "climb_power_draw",
+            f64::from(climb_power_draw.value())
+        )?;
+        self.climb_power_draw = climb_power_draw;
+
+        Ok(self)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the energy is negative.
+    pub fn set_processing_energy_consumption(
+        mut self,
+        processing_energy_consumption: Joule
+    ) -> Result<Self, ParametersError> {
+        check_non_negative(
+            "processing_energy_consumption",
+            f64::from(processing_energy_consumption.value())
+        )?;
+        self.processing_energy_consumption = processing_energy_consumption;
+
+        Ok(self)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the energy is negative.
+    pub fn set_tx_energy_consumption(
+        mut self,
+        tx_energy_consumption: Joule
+    ) -> Result<Self, ParametersError> {
+        check_non_negative(
+            "tx_energy_consumption",
+            f64::from(tx_energy_consumption.value())
+        )?;
+        self.tx_energy_consumption = tx_energy_consumption;
+
+        Ok(self)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn missing_parameters_are_defaulted() {
+        let parameters: SimulationParameters = serde_json::from_str(
+            r#"{ "destination_radius": 10.0, "hover_power_draw": 80.0 }"#
+        )
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        assert_eq!(parameters.destination_radius(), Meter::new(10.0));
+        assert_eq!(parameters.hover_power_draw(), Watt::new(80.0));
+        assert_eq!(
+            parameters
+                .set_destination_radius(DESTINATION_RADIUS)
+                .and_then(|parameters|
+                    parameters.set_hover_power_draw(HOVER_POWER_DRAW)
+                ),
+            Ok(SimulationParameters::default())
+        );
+    }
+
+    #[test]
+    fn zero_drone_mass_is_rejected() {
+        let result = serde_json::from_str::<SimulationParameters>(
+            r#"{ "drone_mass": 0.0 }"#
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            SimulationParameters::default().set_drone_mass(0.0),
+            Err(ParametersError::NotPositive("drone_mass", 0.0))
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_not_set() {
+        let parameters = SimulationParameters::default();
+
+        assert!(
+            parameters.set_max_drone_speed(MeterPerSecond::NAN).is_err()
+        );
+        assert_eq!(
+            parameters.set_hover_power_draw(Watt::new(-1.0)),
+            Err(ParametersError::Negative("hover_power_draw", -1.0))
+        );
+        assert!(
+            parameters.set_tx_energy_consumption(Joule::ZERO).is_ok()
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+
+const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64        = 0x0000_0100_0000_01b3;
+
+
+// Run metadata that keeps snapshots, renders and summaries attributable to
+// the run that produced them. Parameters are experiment options as strings,
+// repeated options are joined with commas.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct Provenance {
+    crate_version: String,
+    experiment: String,
+    seed: u64,
+    // FNV-1a hash of the parameters as JSON with sorted keys, so runs with
+    // the same options have equal hashes across builds and Rust releases.
+    config_hash: String,
+    // RFC 3339 time of the run start.
+    started_at: String,
+    parameters: BTreeMap<String, String>,
+}
+
+impl Provenance {
+    #[must_use]
+    pub fn new(
+        experiment: &str,
+        seed: u64,
+        started_at: &str,
+        parameters: &BTreeMap<String, Value>
+    ) -> Self {
+        let parameters: BTreeMap<String, String> = parameters
+            .iter()
+            .map(|(name, value)| (name.clone(), parameter_string(value)))
+            .collect();
+        let config_hash = fnv1a(
+            serde_json::to_string(&parameters)
+                .unwrap_or_default()
+                .as_bytes()
+        );
+
+        Self {
+            crate_version: CRATE_VERSION.to_string(),
+            experiment: experiment.to_string(),
+            seed,
+            config_hash: format!("{config_hash:016x}"),
+            started_at: started_at.to_string(),
+            parameters,
+        }
+    }
+
+    #[must_use]
+    pub fn crate_version(&self) -> &str {
+        &self.crate_version
+    }
+
+    #[must_use]
+    pub fn experiment(&self) -> &str {
+        &self.experiment
+    }
+
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[must_use]
+    pub fn config_hash(&self) -> &str {
+        &self.config_hash
+    }
+
+    #[must_use]
+    pub fn started_at(&self) -> &str {
+        &self.started_at
+    }
+
+    #[must_use]
+    pub fn parameters(&self) -> &BTreeMap<String, String> {
+        &self.parameters
+    }
+
+    // The version, seed and a short config hash are inserted before the
+    // extension, e.g. `ewd_mesh.gif` becomes
+    // `ewd_mesh_v0.2.2_s42_1f2e3d4c.gif`.
+    #[must_use]
+    pub fn tag_filename(&self, filename: &str) -> String {
+        let tag = format!(
+            "v{}_s{}_{}",
+            self.crate_version,
+            self.seed,
+            &self.config_hash[..self.config_hash.len().min(8)]
+        );
+
+        match filename.rsplit_once('.') {
+            Some((stem, extension)) => format!("{stem}_{tag}.{extension}"),
+            None                    => format!("{filename}_{tag}"),
+        }
+    }
+}
+
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(FNV_OFFSET_BASIS, |hash, byte|
+            (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+        )
+}
+
+fn parameter_string(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        Value::Array(values)  => values
+            .iter()
+            .map(parameter_string)
+            .collect::<Vec<String>>()
+            .join(","),
+        _                     => value.to_string(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    fn parameters() -> BTreeMap<String, Value> {
+        BTreeMap::from([
+            ("topology".to_string(), Value::from("mesh")),
+            (
+                "fault".to_string(),
+                Value::Array(vec![Value::from("a"), Value::from("b")])
+            ),
+        ])
+    }
+
+
+    #[test]
+    fn config_hash_ignores_seed_and_start_time() {
+        let provenance = Provenance::new(
+            "move",
+            1,
+            "2025-01-01",
+            &parameters()
+        );
+        let other_provenance = Provenance::new(
+            "move",
+            2,
+            "2025-01-02",
+            &parameters()
+        );
+
+        assert_eq!(provenance.config_hash(), other_provenance.config_hash());
+        assert_eq!(provenance.parameters()["fault"], "a,b");
+    }
+
+    #[test]
+    fn config_hash_is_stable() {
+        let provenance = Provenance::new("move", 42, "", &parameters());
+
+        assert_eq!(fnv1a(b""), FNV_OFFSET_BASIS);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(provenance.config_hash(), "05cdc9fcd2e5fd96");
+    }
+
+    #[test]
+    fn tag_is_inserted_before_extension() {
+        let provenance = Provenance::new("move", 42, "", &parameters());
+        let tagged_filename = provenance.tag_filename("move_mesh.gif");
+        let expected_prefix = format!("move_mesh_v{CRATE_VERSION}_s42_");
+
+        assert!(tagged_filename.starts_with(&expected_prefix));
+        assert!(tagged_filename.ends_with(".gif"));
+        assert_eq!(
+            tagged_filename.len(),
+            expected_prefix.len() + 8 + ".gif".len()
+        );
+    }
+}
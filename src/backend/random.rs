@@ -0,0 +1,44 @@
+use std::cell::RefCell;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+
+// Every stochastic component draws from this generator, so runs with the same
+// seed are reproducible. The generator is thread local, because models are
+// never updated from several threads at once.
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_os_rng());
+}
+
+
+pub fn set_seed(seed: u64) {
+    RNG.with_borrow_mut(|rng| *rng = StdRng::seed_from_u64(seed));
+}
+
+pub fn with_rng<T>(f: impl FnOnce(&mut StdRng) -> T) -> T {
+    RNG.with_borrow_mut(f)
+}
+
+#[must_use]
+pub fn random_bool(probability: f64) -> bool {
+    with_rng(|rng| rng.random_bool(probability))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_same_draws() {
+        let draw = || with_rng(|rng| rng.random::<u64>());
+
+        set_seed(7);
+        let first_draws = [draw(), draw(), draw()];
+        set_seed(7);
+        let second_draws = [draw(), draw(), draw()];
+
+        assert_eq!(first_draws, second_draws);
+    }
+}
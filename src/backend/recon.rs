@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::device::DeviceId;
+use super::device::systems::ReconRecord;
+use super::mathphysics::{Millisecond, Point3D};
+
+
+// The latest report about a detected device.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct Contact {
+    position: Point3D,
+    last_seen: Millisecond,
+    reporter_id: DeviceId,
+}
+
+impl Contact {
+    #[must_use]
+    pub fn position(&self) -> &Point3D {
+        &self.position
+    }
+
+    #[must_use]
+    pub fn last_seen(&self) -> Millisecond {
+        self.last_seen
+    }
+
+    #[must_use]
+    pub fn reporter_id(&self) -> DeviceId {
+        self.reporter_id
+    }
+}
+
+
+// Recognized picture of the command center: contacts aggregated from the
+// reconnaissance records that reached it.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct RecognizedPicture {
+    contacts: BTreeMap<DeviceId, Contact>,
+}
+
+impl RecognizedPicture {
+    #[must_use]
+    pub fn contacts(&self) -> &BTreeMap<DeviceId, Contact> {
+        &self.contacts
+    }
+
+    #[must_use]
+    pub fn contact(&self, target_id: DeviceId) -> Option<&Contact> {
+        self.contacts.get(&target_id)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.contacts.is_empty()
+    }
+
+    // Records may arrive out of order, so older records do not overwrite
+    // newer contacts.
+    pub fn add_record(&mut self, record: &ReconRecord) {
+        let contact = Contact {
+            position: *record.target_position(),
+            last_seen: record.time(),
+            reporter_id: record.observer_id(),
+        };
+
+        self.contacts
+            .entry(record.target_id())
+            .and_modify(|current_contact| 
+                if current_contact.last_seen <= contact.last_seen {
+                    *current_contact = contact;
+                }
+            )
+            .or_insert(contact);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn older_records_do_not_overwrite_contacts() {
+        let mut recognized_picture = RecognizedPicture::default();
+        let new_position = Point3D::new(1.0, 0.0, 0.0);
+
+        recognized_picture.add_record(
+            &ReconRecord::new(Millisecond::new(100), 1, 3, new_position)
+        );
+        recognized_picture.add_record(
+            &ReconRecord::new(Millisecond::new(50), 2, 3, Point3D::default())
+        );
+
+        let contact = recognized_picture.contact(3);
+
+        assert_eq!(contact.map(Contact::position), Some(&new_position));
+        assert_eq!(contact.map(Contact::reporter_id), Some(1));
+    }
+}
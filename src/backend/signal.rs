@@ -1,36 +1,57 @@
 use std::collections::HashMap;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::device::DeviceId;
+use super::device::systems::{
+    FirmwareUpdate, ReconRecord, ReconSummary, Rumor
+};
 use super::malware::Malware;
-use super::mathphysics::{Frequency, Point3D};
+use super::mathphysics::{Frequency, Millisecond, Point3D};
 use super::task::Task;
 
 
 pub use strength::*;
+pub use interference::*;
+pub use latency::*;
+pub use propagation::*;
 pub use queue::*;
+pub use trace::*;
 
 
 pub mod strength;
+pub mod interference;
+pub mod latency;
+pub mod propagation;
 pub mod queue;
+pub mod trace;
 
 
 pub type FreqToStrengthMap = HashMap<Frequency, SignalStrength>;
 
 
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
 pub enum Data {
+    FirmwareUpdate(FirmwareUpdate),
     GPS(Point3D),
+    Gossip(Rumor),
     Malware(Malware),
+    Recon(ReconRecord),
+    // Records a relay combined on their way to the command center.
+    ReconSummary(ReconSummary),
     SetTask(Task),
+    // Notice that the sender moved its control channel to the frequency.
+    SwitchChannel(Frequency),
+    // Local time of the sender when the beacon was sent.
+    TimeBeacon(Millisecond),
     Noise,
 }
 
 
 // Using `source_id` and `destination_id` is not realistic for signal but it is
 // required for device communication to function. 
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
 pub struct Signal {
     source_id: DeviceId,
     destination_id: DeviceId,
@@ -61,6 +82,11 @@ impl Signal {
     pub fn to_noise(&self) -> Self {
         Self { data: Data::Noise, ..*self }
     }
+
+    #[must_use]
+    pub fn with_strength(&self, strength: SignalStrength) -> Self {
+        Self { strength, ..*self }
+    }
     
     #[must_use]
     pub fn source_id(&self) -> DeviceId {
@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::device::DeviceId;
+use crate::backend::mathphysics::{Float, Frequency};
+
+use super::Signal;
+use super::strength::{
+    SignalStrength, BLACK_SIGNAL_STRENGTH, MAX_BLACK_SIGNAL_STRENGTH
+};
+
+
+const DEFAULT_SINR_THRESHOLD: Float = 3.0;
+
+
+// Strengths of transmissions on air as heard by a receiver, by their
+// frequencies and sources.
+pub type HeardTransmissions = BTreeMap<(Frequency, DeviceId), SignalStrength>;
+
+
+// Concurrent transmissions on a frequency interfere with each other. A signal
+// is received only if its signal to interference plus noise ratio (SINR) is
+// at least `sinr_threshold` decibels, where interference is the sum of the
+// strengths of other transmissions heard on its frequency. By default the
+// noise floor is a tenth of the black signal strength, so a lone signal at
+// the edge of the transmitter area has SINR of 10 dB.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Interference {
+    noise_floor: SignalStrength,
+    sinr_threshold: Float,
+}
+
+impl Default for Interference {
+    fn default() -> Self {
+        Self {
+            noise_floor: MAX_BLACK_SIGNAL_STRENGTH * 0.1,
+            sinr_threshold: DEFAULT_SINR_THRESHOLD,
+        }
+    }
+}
+
+impl Interference {
+    // Negative noise floor is treated as zero.
+    #[must_use]
+    pub fn new(noise_floor: SignalStrength, sinr_threshold: Float) -> Self {
+        Self {
+            noise_floor: if noise_floor < BLACK_SIGNAL_STRENGTH {
+                BLACK_SIGNAL_STRENGTH
+            } else {
+                noise_floor
+            },
+            sinr_threshold,
+        }
+    }
+
+    #[must_use]
+    pub fn noise_floor(&self) -> SignalStrength {
+        self.noise_floor
+    }
+
+    #[must_use]
+    pub fn sinr_threshold(&self) -> Float {
+        self.sinr_threshold
+    }
+
+    // Sum of the strengths of transmissions heard on the frequency of the
+    // signal, except the one of its source.
+    #[must_use]
+    pub fn interference_for(
+        signal: &Signal,
+        heard_transmissions: &HeardTransmissions
+    ) -> SignalStrength {
+        heard_transmissions
+            .iter()
+            .filter(|((frequency, source_id), _)|
+                *frequency == signal.frequency()
+                    && *source_id != signal.source_id()
+            )
+            .fold(BLACK_SIGNAL_STRENGTH, |sum, (_, strength)| sum + *strength)
+    }
+
+    // SINR of the signal in decibels.
+    #[must_use]
+    pub fn sinr(
+        &self,
+        signal: &Signal,
+        heard_transmissions: &HeardTransmissions
+    ) -> Float {
+        let interference_plus_noise = self.noise_floor
+            + Self::interference_for(signal, heard_transmissions);
+
+        10.0 * (
+            signal.strength().value() / interference_plus_noise.value()
+        ).log10()
+    }
+
+    #[must_use]
+    pub fn allows(
+        &self,
+        signal: &Signal,
+        heard_transmissions: &HeardTransmissions
+    ) -> bool {
+        self.sinr(signal, heard_transmissions) >= self.sinr_threshold
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::signal::{Data, GREEN_SIGNAL_STRENGTH};
+
+    use super::*;
+
+
+    const SOURCE_ID: DeviceId      = 1;
+    const INTERFERER_ID: DeviceId  = 2;
+    const DESTINATION_ID: DeviceId = 3;
+
+
+    fn signal() -> Signal {
+        Signal::new(
+            SOURCE_ID,
+            DESTINATION_ID,
+            Data::Noise,
+            Frequency::Control,
+            MAX_BLACK_SIGNAL_STRENGTH * 10.0
+        )
+    }
+
+
+    #[test]
+    fn lone_signal_is_only_affected_by_noise() {
+        let interference = Interference::default();
+        // The source is heard too, but does not interfere with itself.
+        let heard_transmissions = HeardTransmissions::from([
+            ((Frequency::Control, SOURCE_ID), *signal().strength()),
+            ((Frequency::Backup, INTERFERER_ID), GREEN_SIGNAL_STRENGTH),
+        ]);
+
+        let sinr = interference.sinr(&signal(), &heard_transmissions);
+
+        assert!((sinr - 20.0).abs() < 1e-3);
+        assert!(interference.allows(&signal(), &heard_transmissions));
+    }
+
+    #[test]
+    fn concurrent_transmission_on_frequency_blocks_weaker_signal() {
+        let interference = Interference::default();
+        let heard_transmissions = HeardTransmissions::from([
+            ((Frequency::Control, SOURCE_ID), *signal().strength()),
+            ((Frequency::Control, INTERFERER_ID), GREEN_SIGNAL_STRENGTH),
+        ]);
+
+        assert!(interference.sinr(&signal(), &heard_transmissions) < 0.0);
+        assert!(!interference.allows(&signal(), &heard_transmissions));
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend::device::DeviceId;
+use crate::backend::mathphysics::{Frequency, Millisecond};
+
+use super::Signal;
+
+
+// Counts of delivered signals by their latency. Signals are delivered on
+// iterations, so there are few distinct latencies and percentiles are exact
+// without keeping every latency.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LatencyHistogram(BTreeMap<Millisecond, usize>);
+
+impl LatencyHistogram {
+    pub fn record(&mut self, latency: Millisecond) {
+        *self.0.entry(latency).or_default() += 1;
+    }
+
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.0.values().sum()
+    }
+
+    // Nearest-rank percentile, `None` if nothing was recorded.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn percentile(&self, percentile: f32) -> Option<Millisecond> {
+        let rank = (
+            percentile.clamp(0.0, 100.0) / 100.0 * self.count() as f32
+        ).ceil().max(1.0) as usize;
+        let mut cumulative_count = 0;
+
+        self.0
+            .iter()
+            .find(|(_, count)| {
+                cumulative_count += *count;
+                cumulative_count >= rank
+            })
+            .map(|(latency, _)| *latency)
+    }
+
+    #[must_use]
+    pub fn percentiles(&self) -> Option<LatencyPercentiles> {
+        Some(
+            LatencyPercentiles {
+                count: self.count(),
+                p50: self.percentile(50.0)?,
+                p90: self.percentile(90.0)?,
+                p99: self.percentile(99.0)?,
+                max: *self.0.keys().next_back()?,
+            }
+        )
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    count: usize,
+    p50: Millisecond,
+    p90: Millisecond,
+    p99: Millisecond,
+    max: Millisecond,
+}
+
+impl LatencyPercentiles {
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    #[must_use]
+    pub fn p50(&self) -> Millisecond {
+        self.p50
+    }
+
+    #[must_use]
+    pub fn p90(&self) -> Millisecond {
+        self.p90
+    }
+
+    #[must_use]
+    pub fn p99(&self) -> Millisecond {
+        self.p99
+    }
+
+    #[must_use]
+    pub fn max(&self) -> Millisecond {
+        self.max
+    }
+}
+
+
+// Creation-to-delivery latencies of received signals overall, per frequency
+// and per receiving device. Broadcast signals are counted for every device
+// that received them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencyStats {
+    overall: LatencyHistogram,
+    per_frequency: BTreeMap<Frequency, LatencyHistogram>,
+    per_destination: BTreeMap<DeviceId, LatencyHistogram>,
+}
+
+impl LatencyStats {
+    pub fn record(
+        &mut self,
+        destination_id: DeviceId,
+        signal: &Signal,
+        latency: Millisecond
+    ) {
+        self.overall.record(latency);
+        self.per_frequency
+            .entry(signal.frequency())
+            .or_default()
+            .record(latency);
+        self.per_destination
+            .entry(destination_id)
+            .or_default()
+            .record(latency);
+    }
+
+    #[must_use]
+    pub fn overall(&self) -> &LatencyHistogram {
+        &self.overall
+    }
+
+    #[must_use]
+    pub fn per_frequency(&self) -> &BTreeMap<Frequency, LatencyHistogram> {
+        &self.per_frequency
+    }
+
+    #[must_use]
+    pub fn per_destination(&self) -> &BTreeMap<DeviceId, LatencyHistogram> {
+        &self.per_destination
+    }
+
+    // `None` if no signal was delivered.
+    #[must_use]
+    pub fn report(&self) -> Option<LatencyReport> {
+        Some(
+            LatencyReport {
+                overall: self.overall.percentiles()?,
+                per_frequency: percentiles_by_key(&self.per_frequency),
+                per_destination: percentiles_by_key(&self.per_destination),
+            }
+        )
+    }
+}
+
+
+fn percentiles_by_key<K: Copy + Ord>(
+    histograms: &BTreeMap<K, LatencyHistogram>
+) -> BTreeMap<K, LatencyPercentiles> {
+    histograms
+        .iter()
+        .filter_map(|(key, histogram)|
+            histogram.percentiles().map(|percentiles| (*key, percentiles))
+        )
+        .collect()
+}
+
+
+// Percentiles of `LatencyStats`, written to run summaries.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LatencyReport {
+    overall: LatencyPercentiles,
+    per_frequency: BTreeMap<Frequency, LatencyPercentiles>,
+    per_destination: BTreeMap<DeviceId, LatencyPercentiles>,
+}
+
+impl LatencyReport {
+    #[must_use]
+    pub fn overall(&self) -> &LatencyPercentiles {
+        &self.overall
+    }
+
+    #[must_use]
+    pub fn per_frequency(&self) -> &BTreeMap<Frequency, LatencyPercentiles> {
+        &self.per_frequency
+    }
+
+    #[must_use]
+    pub fn per_destination(&self) -> &BTreeMap<DeviceId, LatencyPercentiles> {
+        &self.per_destination
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::signal::{Data, GREEN_SIGNAL_STRENGTH};
+
+    use super::*;
+
+
+    fn histogram(latencies: &[i32]) -> LatencyHistogram {
+        let mut histogram = LatencyHistogram::default();
+
+        for latency in latencies {
+            histogram.record(Millisecond::new(*latency));
+        }
+
+        histogram
+    }
+
+
+    #[test]
+    fn nearest_rank_percentiles() {
+        let histogram = histogram(&[0, 0, 0, 0, 0, 50, 50, 50, 100, 200]);
+        let percentiles = histogram.percentiles().unwrap();
+
+        assert_eq!(percentiles.count(), 10);
+        assert_eq!(percentiles.p50(), Millisecond::ZERO);
+        assert_eq!(percentiles.p90(), Millisecond::new(100));
+        assert_eq!(percentiles.p99(), Millisecond::new(200));
+        assert_eq!(percentiles.max(), Millisecond::new(200));
+        assert_eq!(LatencyHistogram::default().percentiles(), None);
+    }
+
+    #[test]
+    fn latencies_are_grouped_by_frequency_and_destination() {
+        let signal = |frequency| Signal::new(
+            1,
+            2,
+            Data::Noise,
+            frequency,
+            GREEN_SIGNAL_STRENGTH
+        );
+        let mut latency_stats = LatencyStats::default();
+
+        latency_stats.record(2, &signal(Frequency::Control), Millisecond::ZERO);
+        latency_stats.record(
+            3,
+            &signal(Frequency::Control),
+            Millisecond::new(100)
+        );
+        latency_stats.record(2, &signal(Frequency::GPS), Millisecond::new(50));
+
+        let latency_report = latency_stats.report().unwrap();
+
+        assert_eq!(latency_report.overall().count(), 3);
+        assert_eq!(
+            latency_report.per_frequency()[&Frequency::Control].max(),
+            Millisecond::new(100)
+        );
+        assert_eq!(latency_report.per_frequency()[&Frequency::GPS].count(), 1);
+        assert_eq!(latency_report.per_destination()[&2].count(), 2);
+        assert_eq!(
+            latency_report.per_destination()[&3].p50(),
+            Millisecond::new(100)
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::mathphysics::{
+    float_consts, wave_length_in_meters, Float, Megahertz, Meter
+};
+
+use super::strength::{SignalStrength, MAX_BLACK_SIGNAL_STRENGTH};
+
+
+// How a signal weakens with the distance from its transmitter.
+pub trait PropagationModel {
+    // Strength of the signal transmitted with `tx_signal_strength` at
+    // `distance` from the transmitter.
+    fn received_strength(
+        &self,
+        tx_signal_strength: SignalStrength,
+        frequency: Megahertz,
+        distance: Meter
+    ) -> SignalStrength;
+
+    // The closest distance at which the signal is black.
+    fn area_radius(
+        &self,
+        tx_signal_strength: SignalStrength,
+        frequency: Megahertz
+    ) -> Meter;
+}
+
+
+// Strength falls with the distance squared.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize
+)]
+pub struct FreeSpace;
+
+impl PropagationModel for FreeSpace {
+    fn received_strength(
+        &self,
+        tx_signal_strength: SignalStrength,
+        frequency: Megahertz,
+        distance: Meter
+    ) -> SignalStrength {
+        tx_signal_strength.at(frequency, distance)
+    }
+
+    fn area_radius(
+        &self,
+        tx_signal_strength: SignalStrength,
+        frequency: Megahertz
+    ) -> Meter {
+        tx_signal_strength.area_radius_on(frequency)
+    }
+}
+
+
+// Free space up to `reference_distance`, beyond it strength falls with the
+// distance to the power of `exponent`. Exponents above 2 describe cluttered
+// environments, e.g. 2.7-3.5 for urban areas.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct LogDistance {
+    exponent: Float,
+    reference_distance: Meter,
+}
+
+impl Default for LogDistance {
+    fn default() -> Self {
+        Self { exponent: 3.0, reference_distance: Meter::new(1.0) }
+    }
+}
+
+impl LogDistance {
+    // Exponents below 1 are treated as 1, reference distance below 1 m is
+    // treated as 1 m.
+    #[must_use]
+    pub fn new(exponent: Float, reference_distance: Meter) -> Self {
+        Self {
+            exponent: exponent.max(1.0),
+            reference_distance: reference_distance.max(Meter::new(1.0)),
+        }
+    }
+
+    #[must_use]
+    pub fn exponent(&self) -> Float {
+        self.exponent
+    }
+
+    #[must_use]
+    pub fn reference_distance(&self) -> Meter {
+        self.reference_distance
+    }
+}
+
+impl PropagationModel for LogDistance {
+    fn received_strength(
+        &self,
+        tx_signal_strength: SignalStrength,
+        frequency: Megahertz,
+        distance: Meter
+    ) -> SignalStrength {
+        if distance <= self.reference_distance {
+            return tx_signal_strength.at(frequency, distance);
+        }
+
+        let reference_strength = tx_signal_strength.at(
+            frequency,
+            self.reference_distance
+        );
+
+        reference_strength
+            * (self.reference_distance / distance).powf(self.exponent)
+    }
+
+    fn area_radius(
+        &self,
+        tx_signal_strength: SignalStrength,
+        frequency: Megahertz
+    ) -> Meter {
+        let reference_strength = tx_signal_strength.at(
+            frequency,
+            self.reference_distance
+        );
+
+        if reference_strength.is_black() {
+            return tx_signal_strength.area_radius_on(frequency);
+        }
+
+        let ratio = reference_strength.value()
+            / MAX_BLACK_SIGNAL_STRENGTH.value();
+
+        self.reference_distance * ratio.powf(self.exponent.recip())
+    }
+}
+
+
+// Interference of the direct ray with the one reflected from flat ground.
+// Up to the crossover distance strength falls as in free space, beyond it
+// with the distance to the power of 4. The crossover distance grows with
+// antenna heights, so low flying drones are affected first.
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct TwoRayGround {
+    tx_height: Meter,
+    rx_height: Meter,
+}
+
+impl Default for TwoRayGround {
+    fn default() -> Self {
+        Self { tx_height: Meter::new(2.0), rx_height: Meter::new(2.0) }
+    }
+}
+
+impl TwoRayGround {
+    // Negative heights are treated as zero.
+    #[must_use]
+    pub fn new(tx_height: Meter, rx_height: Meter) -> Self {
+        Self {
+            tx_height: tx_height.max(Meter::ZERO),
+            rx_height: rx_height.max(Meter::ZERO),
+        }
+    }
+
+    #[must_use]
+    pub fn tx_height(&self) -> Meter {
+        self.tx_height
+    }
+
+    #[must_use]
+    pub fn rx_height(&self) -> Meter {
+        self.rx_height
+    }
+
+    #[must_use]
+    pub fn crossover_distance(&self, frequency: Megahertz) -> Meter {
+        let wave_length = wave_length_in_meters(frequency);
+
+        Meter::new(
+            4.0 * float_consts::PI * self.tx_height.value()
+                * self.rx_height.value() / wave_length.value()
+        )
+    }
+
+    fn beyond_crossover(&self, frequency: Megahertz) -> LogDistance {
+        LogDistance::new(4.0, self.crossover_distance(frequency))
+    }
+}
+
+impl PropagationModel for TwoRayGround {
+    fn received_strength(
+        &self,
+        tx_signal_strength: SignalStrength,
+        frequency: Megahertz,
+        distance: Meter
+    ) -> SignalStrength {
+        self.beyond_crossover(frequency)
+            .received_strength(tx_signal_strength, frequency, distance)
+    }
+
+    fn area_radius(
+        &self,
+        tx_signal_strength: SignalStrength,
+        frequency: Megahertz
+    ) -> Meter {
+        self.beyond_crossover(frequency)
+            .area_radius(tx_signal_strength, frequency)
+    }
+}
+
+
+// Propagation model chosen for a network model. Free space by default.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize
+)]
+pub enum Propagation {
+    #[default]
+    FreeSpace,
+    LogDistance(LogDistance),
+    TwoRayGround(TwoRayGround),
+}
+
+impl PropagationModel for Propagation {
+    fn received_strength(
+        &self,
+        tx_signal_strength: SignalStrength,
+        frequency: Megahertz,
+        distance: Meter
+    ) -> SignalStrength {
+        match self {
+            Self::FreeSpace                   => FreeSpace
+                .received_strength(tx_signal_strength, frequency, distance),
+            Self::LogDistance(log_distance)   => log_distance
+                .received_strength(tx_signal_strength, frequency, distance),
+            Self::TwoRayGround(two_ray_ground) => two_ray_ground
+                .received_strength(tx_signal_strength, frequency, distance),
+        }
+    }
+
+    fn area_radius(
+        &self,
+        tx_signal_strength: SignalStrength,
+        frequency: Megahertz
+    ) -> Meter {
+        match self {
+            Self::FreeSpace                   => FreeSpace
+                .area_radius(tx_signal_strength, frequency),
+            Self::LogDistance(log_distance)   => log_distance
+                .area_radius(tx_signal_strength, frequency),
+            Self::TwoRayGround(two_ray_ground) => two_ray_ground
+                .area_radius(tx_signal_strength, frequency),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::signal::GREEN_SIGNAL_STRENGTH;
+
+    use super::*;
+
+
+    const FREQUENCY: Megahertz = 2_400;
+
+
+    #[test]
+    fn log_distance_with_exponent_2_is_free_space() {
+        let log_distance = LogDistance::new(2.0, Meter::new(1.0));
+
+        for distance in [0.5, 10.0, 100.0] {
+            let distance = Meter::new(distance);
+            let free_space_strength = FreeSpace.received_strength(
+                GREEN_SIGNAL_STRENGTH,
+                FREQUENCY,
+                distance
+            );
+            let log_distance_strength = log_distance.received_strength(
+                GREEN_SIGNAL_STRENGTH,
+                FREQUENCY,
+                distance
+            );
+
+            assert!(
+                (free_space_strength - log_distance_strength).value().abs()
+                    < 1e-3
+            );
+        }
+    }
+
+    #[test]
+    fn area_radius_is_where_signal_turns_black() {
+        let models = [
+            Propagation::FreeSpace,
+            Propagation::LogDistance(LogDistance::default()),
+            Propagation::TwoRayGround(TwoRayGround::default()),
+        ];
+
+        for model in models {
+            let area_radius = model.area_radius(
+                GREEN_SIGNAL_STRENGTH,
+                FREQUENCY
+            );
+
+            assert!(
+                !model
+                    .received_strength(
+                        GREEN_SIGNAL_STRENGTH,
+                        FREQUENCY,
+                        area_radius * 0.99
+                    )
+                    .is_black()
+            );
+            assert!(
+                model
+                    .received_strength(
+                        GREEN_SIGNAL_STRENGTH,
+                        FREQUENCY,
+                        area_radius * 1.01
+                    )
+                    .is_black()
+            );
+        }
+    }
+
+    #[test]
+    fn steeper_models_have_smaller_areas() {
+        let free_space_radius = FreeSpace.area_radius(
+            GREEN_SIGNAL_STRENGTH,
+            FREQUENCY
+        );
+        let log_distance_radius = LogDistance::default().area_radius(
+            GREEN_SIGNAL_STRENGTH,
+            FREQUENCY
+        );
+
+        assert!(log_distance_radius < free_space_radius);
+    }
+}
@@ -1,3 +1,6 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::backend::device::{DeviceId, IdToDelayMap, BROADCAST_ID}; 
@@ -9,7 +12,7 @@ use super::Signal;
 // The first element - time of signal creation.
 // The second element - the signal.
 // The third element - delays of sending the signal to devices.
-type SignalQueueEntry = (Millisecond, Signal, IdToDelayMap);
+pub type SignalQueueEntry = (Millisecond, Signal, IdToDelayMap);
 
 
 fn any_delay_for(
@@ -23,17 +26,70 @@ fn any_delay_for(
         return *broadcast_delay;
     }
 
-    0
+    Millisecond::ZERO
+}
+
+
+// Change of the signal queue, so it can be replayed on a serialized copy.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum QueueChange {
+    Insert(usize, SignalQueueEntry),
+    // Ascending indices in the queue before the removal.
+    Remove(Vec<usize>),
 }
 
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct SignalQueue(Vec<SignalQueueEntry>);
+// The second field - signals added since transmissions were last taken, it
+// is `None` unless tracing is enabled.
+// The third field - counts of signals added by each source since the counts
+// were last taken.
+// The fourth field - changes since they were last taken, it is `None` unless
+// change tracking is enabled.
+#[derive(Clone, Debug, Default, JsonSchema, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SignalQueue(
+    Vec<SignalQueueEntry>,
+    #[serde(skip)]
+    Option<Vec<(Millisecond, Signal)>>,
+    #[serde(skip)]
+    BTreeMap<DeviceId, usize>,
+    #[serde(skip)]
+    Option<Vec<QueueChange>>,
+);
 
 impl SignalQueue {
     #[must_use]
     pub fn new() -> Self {
-        Self(Vec::new())
+        Self(Vec::new(), None, BTreeMap::new(), None)
+    }
+
+    pub fn set_tracing(&mut self, tracing: bool) {
+        self.1 = tracing.then(Vec::new);
+    }
+
+    // Returns creation times and signals added since the last call.
+    pub fn take_transmissions(&mut self) -> Vec<(Millisecond, Signal)> {
+        self.1
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    pub fn set_change_tracking(&mut self, change_tracking: bool) {
+        self.3 = change_tracking.then(Vec::new);
+    }
+
+    // Returns changes made since the last call.
+    pub fn take_changes(&mut self) -> Vec<QueueChange> {
+        self.3
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    // Returns counts of signals each source added since the last call.
+    pub fn take_transmission_counts(&mut self) -> BTreeMap<DeviceId, usize> {
+        std::mem::take(&mut self.2)
     }
 
     #[must_use]
@@ -45,27 +101,50 @@ impl SignalQueue {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    pub fn destination_ids(&self) -> impl Iterator<Item = DeviceId> {
+        self.0
+            .iter()
+            .map(|(_, signal, _)| signal.destination_id())
+    }
     
-    #[must_use]
+    // Signals are returned with their creation times.
     pub fn get_current_signals_for(
         &self, 
         destination_id: DeviceId,
         current_time: Millisecond, 
-    ) -> Vec<&Signal> {
+    ) -> impl Iterator<Item = (Millisecond, &Signal)> {
         self.0
             .iter()
-            .filter_map(|(time, signal, delay_map)| {
+            .filter_map(move |(time, signal, delay_map)| {
+                if signal.destination_id() != destination_id {
+                    return None;
+                }
+
                 let delay = any_delay_for(destination_id, delay_map);
 
-                if current_time == time + delay 
-                    && signal.destination_id() == destination_id 
-                {
-                    Some(signal)
+                if current_time == *time + delay {
+                    Some((*time, signal))
                 } else {
                     None
                 }
             })
-            .collect()
+    }
+   
+    // Signals of any destination created until the current time, i.e. the
+    // ones that may be on air, with their creation times.
+    pub fn get_signals_created_until(
+        &self,
+        current_time: Millisecond,
+    ) -> impl Iterator<Item = (Millisecond, &Signal)> {
+        // The queue is sorted by creation time.
+        let created_count = self.0.partition_point(|(time, _, _)|
+            *time <= current_time
+        );
+
+        self.0[..created_count]
+            .iter()
+            .map(|(time, signal, _)| (*time, signal))
     }
    
     pub fn add_entry(
@@ -74,21 +153,52 @@ impl SignalQueue {
         signal: Signal, 
         delay_map: IdToDelayMap
     ) {
-        self.0.push((time, signal, delay_map));
-        self.0.sort_by_key(|(time, _, _)| *time);
+        // The queue is always sorted, so the entry is inserted after all 
+        // entries with the same time instead of resorting the whole queue.
+        let index = self.0.partition_point(|(entry_time, _, _)| 
+            *entry_time <= time
+        );
+
+        self.0.insert(index, (time, signal, delay_map));
+        *self.2.entry(signal.source_id()).or_default() += 1;
+
+        if let Some(ref mut transmissions) = self.1 {
+            transmissions.push((time, signal));
+        }
+        if let Some(ref mut changes) = self.3 {
+            changes.push(
+                QueueChange::Insert(index, self.0[index].clone())
+            );
+        }
     }
 
     pub fn remove_old_signals(&mut self, current_time: Millisecond) {
-        self.0.retain(|(time, _, delay_map)| {
+        let is_processed = |(time, _, delay_map): &SignalQueueEntry| {
             let longest_delay = delay_map
                 .values()
                 .max()
-                .unwrap_or(&0);
+                .copied()
+                .unwrap_or(Millisecond::ZERO);
 
             // We assume that the signal processing is finished if it was 
             // processed by a device with the longest delay. 
-            current_time < time + longest_delay
-        });
+            current_time >= *time + longest_delay
+        };
+
+        if let Some(ref mut changes) = self.3 {
+            let removed: Vec<usize> = self.0
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| is_processed(entry))
+                .map(|(index, _)| index)
+                .collect();
+
+            if !removed.is_empty() {
+                changes.push(QueueChange::Remove(removed));
+            }
+        }
+
+        self.0.retain(|entry| !is_processed(entry));
     }
 }
 
@@ -129,9 +239,9 @@ mod tests {
         );
 
         vec![
-            (25, signal1), 
-            (5, signal2), 
-            (10, signal3)
+            (Millisecond::new(25), signal1), 
+            (Millisecond::new(5), signal2), 
+            (Millisecond::new(10), signal3)
         ]
     }
 
@@ -144,10 +254,13 @@ mod tests {
             time_and_signals
                 .iter()
                 .map(|(time, signal)| (*time, *signal, IdToDelayMap::new()))
-                .collect()
+                .collect(),
+            None,
+            BTreeMap::new(),
+            None
         );
 
-        signal_queue.remove_old_signals(10);
+        signal_queue.remove_old_signals(Millisecond::new(10));
 
         assert_eq!(signal_queue.len(), 1);
         assert_eq!(signal_queue.0[0].1, time_and_signals[0].1);
@@ -177,4 +290,108 @@ mod tests {
             queue_iter.next().unwrap().0
         );
     }
+
+    #[test]
+    fn signals_are_on_air_once_created() {
+        let mut signal_queue = SignalQueue::new();
+
+        for (time, signal) in time_and_signals() {
+            signal_queue.add_entry(time, signal, IdToDelayMap::new());
+        }
+
+        let created_until = |current_time| signal_queue
+            .get_signals_created_until(current_time)
+            .map(|(time, _)| time)
+            .collect::<Vec<Millisecond>>();
+
+        assert!(created_until(Millisecond::new(4)).is_empty());
+        assert_eq!(
+            created_until(Millisecond::new(10)),
+            vec![Millisecond::new(5), Millisecond::new(10)]
+        );
+    }
+
+    #[test]
+    fn keep_insertion_order_of_signals_with_same_time() {
+        let time = Millisecond::new(10);
+        let mut signal_queue = SignalQueue::new();
+
+        for source_id in 1..=3 {
+            let signal = Signal::new(
+                source_id,
+                SOME_ID,
+                Data::Noise,
+                Frequency::Control,
+                BLACK_SIGNAL_STRENGTH,
+            );
+
+            signal_queue.add_entry(time, signal, IdToDelayMap::default());
+        }
+
+        let source_ids: Vec<DeviceId> = signal_queue.0
+            .iter()
+            .map(|(_, signal, _)| signal.source_id())
+            .collect();
+
+        assert_eq!(source_ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn record_transmissions_only_with_tracing() {
+        let time_and_signals = time_and_signals();
+        let mut signal_queue = SignalQueue::new();
+
+        signal_queue.add_entry(
+            Millisecond::ZERO,
+            time_and_signals[0].1,
+            IdToDelayMap::new()
+        );
+        assert!(signal_queue.take_transmissions().is_empty());
+
+        signal_queue.set_tracing(true);
+
+        for (time, signal) in &time_and_signals {
+            signal_queue.add_entry(*time, *signal, IdToDelayMap::new());
+        }
+
+        assert_eq!(signal_queue.take_transmissions(), time_and_signals);
+        assert!(signal_queue.take_transmissions().is_empty());
+        assert_eq!(signal_queue.len(), 4);
+    }
+
+    #[test]
+    fn record_changes_only_with_change_tracking() {
+        let time_and_signals = time_and_signals();
+        let mut signal_queue = SignalQueue::new();
+
+        signal_queue.add_entry(
+            Millisecond::new(40),
+            time_and_signals[0].1,
+            IdToDelayMap::new()
+        );
+        assert!(signal_queue.take_changes().is_empty());
+
+        signal_queue.set_change_tracking(true);
+
+        for (time, signal) in &time_and_signals {
+            signal_queue.add_entry(*time, *signal, IdToDelayMap::new());
+        }
+        signal_queue.remove_old_signals(Millisecond::new(10));
+
+        let insert_indices: Vec<usize> = signal_queue
+            .take_changes()
+            .into_iter()
+            .filter_map(|change| match change {
+                QueueChange::Insert(index, _) => Some(index),
+                QueueChange::Remove(indices)  => {
+                    assert_eq!(indices, vec![0, 1]);
+                    None
+                },
+            })
+            .collect();
+
+        assert_eq!(insert_indices, vec![0, 0, 1]);
+        assert!(signal_queue.take_changes().is_empty());
+        assert_eq!(signal_queue.len(), 2);
+    }
 }
@@ -1,7 +1,10 @@
 use derive_more::{Add, Div, Mul, Sub, Display};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::backend::mathphysics::{wave_length_in_meters, Megahertz, Meter};
+use crate::backend::mathphysics::{
+    wave_length_in_meters, Float, Megahertz, Meter
+};
 
 
 pub const GREEN_SIGNAL_STRENGTH_VALUE: StrengthValue = 100.0;
@@ -23,12 +26,12 @@ pub const GREEN_SIGNAL_STRENGTH: SignalStrength      = SignalStrength(
 const SIGNAL_STRENGTH_SCALING: StrengthValue = 2_500.0; 
 
 
-pub type StrengthValue = f32;
+pub type StrengthValue = Float;
 
 
 #[derive(
     Clone, Copy, Debug, Display, Default, Add, Sub, Mul, Div, PartialEq, 
-    PartialOrd, Serialize, Deserialize
+    PartialOrd, JsonSchema, Serialize, Deserialize
 )]
 #[display("{_0}")]
 pub struct SignalStrength(StrengthValue);
@@ -38,6 +41,11 @@ impl SignalStrength {
     pub fn new(value: StrengthValue) -> Self {
         Self(value)
     }
+
+    #[must_use]
+    pub fn value(&self) -> StrengthValue {
+        self.0
+    }
     
     #[must_use]
     pub fn from_area_radius(area_radius: Meter, frequency: Megahertz) -> Self {
@@ -70,7 +78,7 @@ impl SignalStrength {
         // length. However, in the future free-space path loss model may 
         // changed for this particular case.
         let signal_strength_at = if distance <= wave_length {
-            wave_length.powi(2)
+            wave_length.value().powi(2)
         } else {
             (wave_length / distance).powi(2)
         } * self.0 * SIGNAL_STRENGTH_SCALING; 
@@ -81,7 +89,7 @@ impl SignalStrength {
     #[must_use]
     pub fn area_radius_on(&self, frequency: Megahertz) -> Meter {
         if self.is_black() {
-            return 0.0;
+            return Meter::ZERO;
         }
        
         let wave_length = wave_length_in_meters(frequency);
@@ -113,10 +121,10 @@ mod tests {
     fn somewhat_realistic_area_radius() {
         let tx_signal_strength = GREEN_SIGNAL_STRENGTH;
         let frequency = 5_000;
-        let distance_outside_tx_area = 40.0;
-        let distance_far_from_tx     = 15.0;
-        let distance_close_to_tx     = 5.0;
-        let distance_next_to_tx      = 3.0;
+        let distance_outside_tx_area = Meter::new(40.0);
+        let distance_far_from_tx     = Meter::new(15.0);
+        let distance_close_to_tx     = Meter::new(5.0);
+        let distance_next_to_tx      = Meter::new(3.0);
         
         let black_signal_strength = tx_signal_strength.at(
             frequency, 
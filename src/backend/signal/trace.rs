@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+use crate::backend::device::DeviceId;
+use crate::backend::mathphysics::Millisecond;
+
+use super::Signal;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PacketEventKind {
+    // The signal was added to the signal queue by its source.
+    Transmitted,
+    Received,
+    // The signal reached the device but was not received, e.g. because of a
+    // stronger signal or an injected signal drop.
+    Dropped,
+}
+
+
+// What happened to a signal on a device, recorded when packet tracing is
+// enabled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PacketEvent {
+    time: Millisecond,
+    device_id: DeviceId,
+    kind: PacketEventKind,
+    signal: Signal,
+}
+
+impl PacketEvent {
+    #[must_use]
+    pub fn new(
+        time: Millisecond,
+        device_id: DeviceId,
+        kind: PacketEventKind,
+        signal: Signal
+    ) -> Self {
+        Self { time, device_id, kind, signal }
+    }
+
+    #[must_use]
+    pub fn time(&self) -> Millisecond {
+        self.time
+    }
+
+    #[must_use]
+    pub fn device_id(&self) -> DeviceId {
+        self.device_id
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> PacketEventKind {
+        self.kind
+    }
+
+    #[must_use]
+    pub fn signal(&self) -> &Signal {
+        &self.signal
+    }
+}
+
+
+// Counts of signals that reached devices. Noise is not counted, so jamming
+// shows up as dropped signals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeliveryStats {
+    received: usize,
+    dropped: usize,
+}
+
+impl DeliveryStats {
+    pub fn record(&mut self, received: bool) {
+        if received {
+            self.received += 1;
+        } else {
+            self.dropped += 1;
+        }
+    }
+
+    #[must_use]
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    #[must_use]
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    // Share of signals that were received, `None` if no signal reached a
+    // device.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn delivery_rate(&self) -> Option<f32> {
+        let total = self.received + self.dropped;
+
+        (total > 0).then(|| self.received as f32 / total as f32)
+    }
+}
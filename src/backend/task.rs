@@ -1,17 +1,36 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use super::mathphysics::Point3D;
+use super::mathphysics::{Point3D, Vector3D};
 
-pub use scenario::Scenario;
+pub use mission::Mission;
+pub use scenario::{Scenario, ScenarioError};
 
 
+pub mod mission;
 pub mod scenario;
 
 
-#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, JsonSchema, Serialize, Deserialize)]
 pub enum Task {
     Attack(Point3D),    
+    DropPayload(Point3D), // Moving to a point to release the payload
+    KeepFormation(Vector3D), // Velocity commanded by formation controller
     Reconnect(Point3D),  // Moving to a point to receive a control signal
     Reposition(Point3D),
     Undefined,
 }
+
+impl Task {
+    // Point the device moves to while doing the task.
+    #[must_use]
+    pub fn destination(&self) -> Option<Point3D> {
+        match self {
+            Task::Attack(destination)
+                | Task::DropPayload(destination)
+                | Task::Reconnect(destination)
+                | Task::Reposition(destination) => Some(*destination),
+            Task::KeepFormation(_) | Task::Undefined => None,
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::Task;
+
+
+// Tasks the device was briefed on, in the order they are executed. The
+// device follows them on its own once it has lost the command center.
+#[derive(Clone, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize)]
+pub struct Mission {
+    tasks: Vec<Task>,
+    // Index of the first task that is not completed yet.
+    next_task: usize,
+}
+
+impl Mission {
+    #[must_use]
+    pub fn new(tasks: Vec<Task>) -> Self {
+        Self { tasks, next_task: 0 }
+    }
+
+    #[must_use]
+    pub fn tasks(&self) -> &[Task] {
+        &self.tasks
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.next_task >= self.tasks.len()
+    }
+
+    #[must_use]
+    pub fn current_task(&self) -> Option<&Task> {
+        self.tasks.get(self.next_task)
+    }
+
+    // Tasks before a commanded task are skipped. Tasks out of the rest of
+    // the mission are ignored.
+    pub fn skip_to(&mut self, task: &Task) {
+        if let Some(index) = self.remaining_index_of(task) {
+            self.next_task += index;
+        }
+    }
+
+    // Marks the task and the tasks before it as completed.
+    pub fn complete(&mut self, task: &Task) {
+        if let Some(index) = self.remaining_index_of(task) {
+            self.next_task += index + 1;
+        }
+    }
+
+    fn remaining_index_of(&self, task: &Task) -> Option<usize> {
+        self.tasks
+            .get(self.next_task..)?
+            .iter()
+            .position(|mission_task| mission_task == task)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::mathphysics::Point3D;
+
+    use super::*;
+
+
+    fn mission() -> Mission {
+        Mission::new(vec![
+            Task::Reposition(Point3D::new(10.0, 0.0, 0.0)),
+            Task::DropPayload(Point3D::new(20.0, 0.0, 0.0)),
+            Task::Reposition(Point3D::new(0.0, 0.0, 0.0)),
+        ])
+    }
+
+
+    #[test]
+    fn completing_tasks_advances_mission() {
+        let mut mission = mission();
+        let tasks = mission.tasks().to_vec();
+
+        mission.complete(&tasks[0]);
+
+        assert_eq!(mission.current_task(), Some(&tasks[1]));
+
+        // Completing a task again does not skip the rest.
+        mission.complete(&tasks[0]);
+
+        assert_eq!(mission.current_task(), Some(&tasks[1]));
+
+        mission.complete(&tasks[1]);
+        mission.complete(&tasks[2]);
+
+        assert!(mission.is_complete());
+        assert_eq!(mission.current_task(), None);
+    }
+
+    #[test]
+    fn commanded_task_skips_previous_tasks() {
+        let mut mission = mission();
+        let tasks = mission.tasks().to_vec();
+
+        mission.skip_to(&tasks[2]);
+
+        assert_eq!(mission.current_task(), Some(&tasks[2]));
+
+        mission.skip_to(&Task::Undefined);
+
+        assert_eq!(mission.current_task(), Some(&tasks[2]));
+    }
+}
@@ -1,18 +1,66 @@
+use std::fs;
+use std::path::Path;
+
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::backend::device::{DeviceId, BROADCAST_ID};
 use crate::backend::mathphysics::Millisecond;
 
-use super::Task;
+use super::{Mission, Task};
 
 
 type ScenarioEntry = (Millisecond, DeviceId, Task);
 
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Error)]
+pub enum ScenarioError {
+    #[error("Failed to read scenario: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to deserialize scenario: {0}")]
+    Deserialization(#[from] serde_json::Error),
+}
+
+
+#[derive(Clone, Debug, Default, JsonSchema, Serialize, Deserialize)]
 pub struct Scenario(Vec<ScenarioEntry>);
 
 impl Scenario {
+    // Entries do not have to be sorted by time in the file.
+    /// # Errors
+    ///
+    /// Will return `Err` if the file can not be read or it does not contain
+    /// a JSON list of scenario entries.
+    pub fn from_json(scenario_path: &Path) -> Result<Self, ScenarioError> {
+        let json_string = fs::read_to_string(scenario_path)?;
+        let scenario_entries: Vec<ScenarioEntry> = serde_json::from_str(
+            &json_string
+        )?;
+
+        Ok(Self::from(scenario_entries.as_slice()))
+    }
+
+    // The entry is placed after the entries with the same time, so tasks
+    // given at once keep their order.
+    pub fn add_entry(
+        &mut self,
+        time: Millisecond,
+        device_id: DeviceId,
+        task: Task
+    ) {
+        let index = self.0.partition_point(|(entry_time, _, _)| 
+            *entry_time <= time
+        );
+
+        self.0.insert(index, (time, device_id, task));
+    }
+
+    // Entries are sorted by time.
+    pub fn iter(&self) -> impl Iterator<Item = &ScenarioEntry> {
+        self.0.iter()
+    }
+
     #[must_use]
     pub fn get_last_task(
         &self, 
@@ -32,6 +80,26 @@ impl Scenario {
                 }
             })
     }
+
+    // Tasks for the device in the order they are given. Formation and
+    // undefined tasks can not be executed without the command center, so
+    // they are left out.
+    #[must_use]
+    pub fn mission_for(&self, destination_id: DeviceId) -> Mission {
+        let tasks = self.0
+            .iter()
+            .filter(|(_, device_id, task)| 
+                (*device_id == destination_id || *device_id == BROADCAST_ID)
+                    && !matches!(
+                        task, 
+                        Task::KeepFormation(_) | Task::Undefined
+                    )
+            )
+            .map(|(_, _, task)| *task)
+            .collect();
+
+        Mission::new(tasks)
+    }
 }
 
 impl From<&[ScenarioEntry]> for Scenario {
@@ -57,6 +125,8 @@ impl<const N: usize> From<[ScenarioEntry; N]> for Scenario {
 
 #[cfg(test)]
 mod tests {
+    use crate::backend::mathphysics::Point3D;
+
     use super::*;
 
 
@@ -67,9 +137,9 @@ mod tests {
         let undefined_task = Task::Undefined;
 
         vec![
-            (25, SOME_DEVICE_ID, undefined_task),
-            (5, SOME_DEVICE_ID, undefined_task),
-            (10, SOME_DEVICE_ID, undefined_task),
+            (Millisecond::new(25), SOME_DEVICE_ID, undefined_task),
+            (Millisecond::new(5), SOME_DEVICE_ID, undefined_task),
+            (Millisecond::new(10), SOME_DEVICE_ID, undefined_task),
         ]
     }
 
@@ -80,7 +150,11 @@ mod tests {
 
         let scenario = Scenario::from(entries.as_slice());
 
-        assert!(scenario.get_last_task(0, SOME_DEVICE_ID).is_none());
+        assert!(
+            scenario
+                .get_last_task(Millisecond::ZERO, SOME_DEVICE_ID)
+                .is_none()
+        );
     }
 
     #[test]
@@ -90,7 +164,7 @@ mod tests {
         let scenario = Scenario::from(entries.as_slice());
 
         let last_task = *scenario
-            .get_last_task(7, SOME_DEVICE_ID)
+            .get_last_task(Millisecond::new(7), SOME_DEVICE_ID)
             .expect("Failed to get the last task");
 
         assert_eq!(last_task, entries[1].2);
@@ -109,6 +183,41 @@ mod tests {
         assert_eq!(last_task, entries[2].2);
     }
 
+    #[test]
+    fn mission_contains_tasks_for_device_in_time_order() {
+        let other_device_id = SOME_DEVICE_ID + 1;
+        let drop_task = Task::DropPayload(Point3D::new(2.0, 0.0, 0.0));
+        let reposition_task = Task::Reposition(Point3D::new(1.0, 0.0, 0.0));
+        let scenario = Scenario::from([
+            (Millisecond::new(20), BROADCAST_ID, reposition_task),
+            (Millisecond::new(15), SOME_DEVICE_ID, Task::Undefined),
+            (Millisecond::new(10), SOME_DEVICE_ID, drop_task),
+            (Millisecond::new(0), other_device_id, reposition_task),
+        ]);
+
+        let mission = scenario.mission_for(SOME_DEVICE_ID);
+
+        assert_eq!(mission.tasks(), [drop_task, reposition_task]);
+    }
+
+    #[test]
+    fn added_entry_keeps_time_order() {
+        let mut scenario = Scenario::from(entries().as_slice());
+
+        scenario.add_entry(Millisecond::new(10), BROADCAST_ID, Task::Undefined);
+
+        let entry_times: Vec<Millisecond> = scenario
+            .iter()
+            .map(|(time, _, _)| *time)
+            .collect();
+
+        assert_eq!(
+            entry_times,
+            [5, 10, 10, 25].map(Millisecond::new)
+        );
+        assert_eq!(scenario.0[2].1, BROADCAST_ID);
+    }
+
     #[test]
     fn sort_entries_on_creation() {
         let entries = entries();
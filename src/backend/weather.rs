@@ -0,0 +1,171 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::mathphysics::{Float, Frequency, Megahertz, Meter};
+use super::signal::{Signal, SignalStrength};
+
+
+// Power law coefficients of rain specific attenuation `k * rain_rate^alpha`,
+// where `k = K_SCALE * f^K_EXPONENT` and `alpha = ALPHA_SCALE *
+// f^ALPHA_EXPONENT` with frequency `f` in gigahertz.
+const RAIN_K_SCALE: Float        = 4.21e-5;
+const RAIN_K_EXPONENT: Float     = 2.42;
+const RAIN_ALPHA_SCALE: Float    = 1.41;
+const RAIN_ALPHA_EXPONENT: Float = -0.0779;
+// Fog specific attenuation in dB/km per g/m^3 of liquid water is
+// `FOG_SCALE * f^2` with frequency `f` in gigahertz.
+const FOG_SCALE: Float = 8.0e-4;
+
+const MEGAHERTZ_PER_GIGAHERTZ: Float = 1_000.0;
+const METERS_PER_KILOMETER: Float    = 1_000.0;
+
+
+// Rain and fog along signal paths. Losses grow with frequency and distance,
+// so the backup channel suffers the most. GPS signals come from above the
+// weather, so they are attenuated only if GPS degradation is enabled.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, JsonSchema, Serialize, Deserialize
+)]
+pub struct Weather {
+    // Millimeters per hour.
+    rain_rate: Float,
+    // Liquid water content in grams per cubic meter.
+    fog_density: Float,
+    degrades_gps: bool,
+}
+
+impl Weather {
+    // Negative values are treated as zero.
+    #[must_use]
+    pub fn new(rain_rate: Float, fog_density: Float) -> Self {
+        Self {
+            rain_rate: rain_rate.max(0.0),
+            fog_density: fog_density.max(0.0),
+            degrades_gps: false,
+        }
+    }
+
+    #[must_use]
+    pub fn set_degrades_gps(mut self, degrades_gps: bool) -> Self {
+        self.degrades_gps = degrades_gps;
+        self
+    }
+
+    #[must_use]
+    pub fn rain_rate(&self) -> Float {
+        self.rain_rate
+    }
+
+    #[must_use]
+    pub fn fog_density(&self) -> Float {
+        self.fog_density
+    }
+
+    #[must_use]
+    pub fn degrades_gps(&self) -> bool {
+        self.degrades_gps
+    }
+
+    #[must_use]
+    pub fn is_clear(&self) -> bool {
+        self.rain_rate <= 0.0 && self.fog_density <= 0.0
+    }
+
+    // Specific attenuation in dB/km.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn attenuation_on(&self, frequency: Megahertz) -> Float {
+        let frequency = frequency as Float / MEGAHERTZ_PER_GIGAHERTZ;
+        let rain_attenuation = if self.rain_rate > 0.0 {
+            let k = RAIN_K_SCALE * frequency.powf(RAIN_K_EXPONENT);
+            let alpha = RAIN_ALPHA_SCALE
+                * frequency.powf(RAIN_ALPHA_EXPONENT);
+
+            k * self.rain_rate.powf(alpha)
+        } else {
+            0.0
+        };
+        let fog_attenuation = FOG_SCALE
+            * frequency.powi(2)
+            * self.fog_density;
+
+        rain_attenuation + fog_attenuation
+    }
+
+    // Strength of the signal that travelled the distance through the
+    // weather.
+    #[must_use]
+    pub fn attenuate(&self, signal: Signal, distance: Meter) -> Signal {
+        if self.is_clear()
+            || (signal.frequency() == Frequency::GPS && !self.degrades_gps)
+        {
+            return signal;
+        }
+
+        let loss_in_db = self.attenuation_on(signal.frequency() as Megahertz)
+            * distance.value()
+            / METERS_PER_KILOMETER;
+        let strength_value = signal.strength().value()
+            * Float::powf(10.0, -loss_in_db / 10.0);
+
+        signal.with_strength(SignalStrength::new(strength_value))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::signal::{Data, GREEN_SIGNAL_STRENGTH};
+
+    use super::*;
+
+
+    fn signal(frequency: Frequency) -> Signal {
+        Signal::new(0, 1, Data::Noise, frequency, GREEN_SIGNAL_STRENGTH)
+    }
+
+
+    #[test]
+    fn clear_weather_keeps_signal() {
+        let signal = signal(Frequency::Control);
+
+        assert_eq!(
+            Weather::default().attenuate(signal, Meter::new(1_000.0)),
+            signal
+        );
+    }
+
+    #[test]
+    fn higher_frequencies_lose_more() {
+        let weather = Weather::new(50.0, 0.5);
+        let distance = Meter::new(1_000.0);
+
+        let control_signal = weather.attenuate(
+            signal(Frequency::Control),
+            distance
+        );
+        let backup_signal = weather.attenuate(
+            signal(Frequency::Backup),
+            distance
+        );
+
+        assert!(*control_signal.strength() < GREEN_SIGNAL_STRENGTH);
+        assert!(backup_signal.strength() < control_signal.strength());
+    }
+
+    #[test]
+    fn gps_is_attenuated_only_if_degraded() {
+        let weather = Weather::new(50.0, 0.0);
+        let distance = Meter::new(1_000.0);
+        let gps_signal = signal(Frequency::GPS);
+
+        assert_eq!(weather.attenuate(gps_signal, distance), gps_signal);
+        assert!(
+            *weather
+                .set_degrades_gps(true)
+                .attenuate(gps_signal, distance)
+                .strength()
+                < GREEN_SIGNAL_STRENGTH
+        );
+    }
+}
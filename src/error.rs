@@ -0,0 +1,73 @@
+use std::io;
+
+use thiserror::Error;
+
+use crate::backend::connections::ShortestPathError;
+use crate::backend::device::{DeviceError, DeviceId};
+use crate::backend::device::systems::TRXSystemError;
+use crate::backend::networkmodel::attack::AttackError;
+
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+
+// Failures of the library that callers may want to observe. Errors of the
+// backend systems are wrapped with the device they happened to, while IO
+// and serialization errors carry what was being done, e.g. the file path.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Device `{device_id}` failed: {source}")]
+    Device {
+        device_id: DeviceId,
+        #[source]
+        source: DeviceError,
+    },
+    #[error("Attack on device `{target_id}` failed: {source}")]
+    Attack {
+        target_id: DeviceId,
+        #[source]
+        source: AttackError,
+    },
+    #[error("TRX system failed with error `{0}`")]
+    TRXSystem(#[from] TRXSystemError),
+    #[error("Failed to find shortest path: {0}")]
+    ShortestPath(#[from] ShortestPathError),
+    #[error("{context}: {source}")]
+    Io {
+        context: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("{context}: {source}")]
+    Json {
+        context: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("{context}: {source}")]
+    Binary {
+        context: String,
+        #[source]
+        source: postcard::Error,
+    },
+}
+
+impl Error {
+    #[must_use]
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        Self::Io { context: context.into(), source }
+    }
+
+    #[must_use]
+    pub fn json(
+        context: impl Into<String>,
+        source: serde_json::Error
+    ) -> Self {
+        Self::Json { context: context.into(), source }
+    }
+
+    #[must_use]
+    pub fn binary(context: impl Into<String>, source: postcard::Error) -> Self {
+        Self::Binary { context: context.into(), source }
+    }
+}
@@ -0,0 +1,285 @@
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::backend::device::{Device, DeviceId};
+use crate::backend::mathphysics::{float_to_f32, Position};
+use crate::backend::networkmodel::NetworkModel;
+use crate::backend::random;
+
+
+// Returned by functions that can fail, the message is read with
+// `dn_last_error`.
+const DN_OK: i32    = 0;
+const DN_ERROR: i32 = -1;
+
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: &str) {
+    // Messages never contain nulls, but they are replaced just in case.
+    let message = CString::new(message.replace('\0', " "))
+        .unwrap_or_default();
+
+    LAST_ERROR.with_borrow_mut(|last_error| *last_error = Some(message));
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Simulation panicked")
+}
+
+
+// Layout is described by `DnDeviceState` in `include/drone_network.h`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DnDeviceState {
+    pub id: usize,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub yaw: f32,
+    pub battery_level: f32,
+    pub infected: bool,
+    pub shut_down: bool,
+}
+
+impl From<&Device> for DnDeviceState {
+    fn from(device: &Device) -> Self {
+        let position = device.position();
+
+        Self {
+            id: device.id(),
+            x: float_to_f32(position.x),
+            y: float_to_f32(position.y),
+            z: float_to_f32(position.z),
+            yaw: float_to_f32(device.orientation().yaw),
+            battery_level: device.power_system().level(),
+            infected: device.is_infected(),
+            shut_down: device.is_shut_down(),
+        }
+    }
+}
+
+
+/// Creates a model from the JSON written by `generate` or the `--jo` output.
+/// Returns null on failure.
+///
+/// # Safety
+///
+/// `json` must be a valid null-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dn_model_from_json(
+    json: *const c_char
+) -> *mut NetworkModel {
+    if json.is_null() {
+        set_last_error("Model JSON is null");
+        return ptr::null_mut();
+    }
+
+    // SAFETY: the caller passes a valid null-terminated string.
+    let json = unsafe { CStr::from_ptr(json) };
+    let network_model = json
+        .to_str()
+        .map_err(|error| error.to_string())
+        .and_then(|json|
+            serde_json::from_str::<NetworkModel>(json)
+                .map_err(|error| error.to_string())
+        );
+
+    match network_model {
+        Ok(network_model) => Box::into_raw(Box::new(network_model)),
+        Err(message)      => {
+            set_last_error(&message);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// # Safety
+///
+/// `model` must be returned by `dn_model_from_json` and not destroyed yet.
+/// Null is ignored.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dn_model_destroy(model: *mut NetworkModel) {
+    if !model.is_null() {
+        // SAFETY: the model was created by `Box::into_raw`.
+        drop(unsafe { Box::from_raw(model) });
+    }
+}
+
+/// Updates the model `iterations` times. Panics are caught, so they do not
+/// unwind into the host, and the model must not be used after a failure.
+///
+/// # Safety
+///
+/// `model` must be a valid model pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dn_model_update(
+    model: *mut NetworkModel,
+    iterations: u32
+) -> i32 {
+    // SAFETY: the caller passes a valid model pointer.
+    let Some(network_model) = (unsafe { model.as_mut() }) else {
+        set_last_error("Model is null");
+        return DN_ERROR;
+    };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        for _ in 0..iterations {
+            network_model.update();
+        }
+    }));
+
+    match result {
+        Ok(())       => DN_OK,
+        Err(payload) => {
+            set_last_error(panic_message(payload.as_ref()));
+            DN_ERROR
+        },
+    }
+}
+
+/// # Safety
+///
+/// `model` must be a valid model pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dn_model_current_time(
+    model: *const NetworkModel
+) -> i32 {
+    // SAFETY: the caller passes a valid model pointer.
+    unsafe { model.as_ref() }
+        .map_or(0, |model| model.current_time().value())
+}
+
+/// # Safety
+///
+/// `model` must be a valid model pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dn_model_command_device_id(
+    model: *const NetworkModel
+) -> DeviceId {
+    // SAFETY: the caller passes a valid model pointer.
+    unsafe { model.as_ref() }
+        .map_or(0, NetworkModel::command_device_id)
+}
+
+/// # Safety
+///
+/// `model` must be a valid model pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dn_model_device_count(
+    model: *const NetworkModel
+) -> usize {
+    // SAFETY: the caller passes a valid model pointer.
+    unsafe { model.as_ref() }
+        .map_or(0, |network_model| network_model.device_map().len())
+}
+
+/// Writes states of up to `capacity` devices ordered by ID to `states` and
+/// returns how many were written. The buffer can be sized with
+/// `dn_model_device_count`.
+///
+/// # Safety
+///
+/// `model` must be a valid model pointer and `states` must point to at
+/// least `capacity` writable elements.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dn_model_device_states(
+    model: *const NetworkModel,
+    states: *mut DnDeviceState,
+    capacity: usize
+) -> usize {
+    // SAFETY: the caller passes a valid model pointer.
+    let Some(network_model) = (unsafe { model.as_ref() }) else {
+        return 0;
+    };
+
+    if states.is_null() || capacity == 0 {
+        return 0;
+    }
+
+    let mut devices: Vec<&Device> = network_model
+        .device_map()
+        .values()
+        .collect();
+
+    devices.sort_by_key(|device| device.id());
+
+    // SAFETY: the caller passes a buffer of `capacity` elements.
+    let states = unsafe { std::slice::from_raw_parts_mut(states, capacity) };
+
+    states
+        .iter_mut()
+        .zip(devices)
+        .map(|(state, device)| *state = DnDeviceState::from(device))
+        .count()
+}
+
+/// Returns the model JSON, which is freed with `dn_string_free`, or null on
+/// failure.
+///
+/// # Safety
+///
+/// `model` must be a valid model pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dn_model_to_json(
+    model: *const NetworkModel
+) -> *mut c_char {
+    // SAFETY: the caller passes a valid model pointer.
+    let Some(network_model) = (unsafe { model.as_ref() }) else {
+        set_last_error("Model is null");
+        return ptr::null_mut();
+    };
+
+    let json = network_model
+        .to_json()
+        .map_err(|error| error.to_string())
+        .and_then(|json|
+            CString::new(json).map_err(|error| error.to_string())
+        );
+
+    match json {
+        Ok(json)     => json.into_raw(),
+        Err(message) => {
+            set_last_error(&message);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// # Safety
+///
+/// `string` must be returned by this library and not freed yet. Null is
+/// ignored.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dn_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: the string was created by `CString::into_raw`.
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Returns the message of the last failure on the calling thread or null.
+/// The pointer is valid until the next failing call on the thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn dn_last_error() -> *const c_char {
+    LAST_ERROR.with_borrow(|last_error|
+        last_error
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    )
+}
+
+/// The generator is thread-local, so the seed applies to models updated on
+/// the calling thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn dn_set_seed(seed: u64) {
+    random::set_seed(seed);
+}
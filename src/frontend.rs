@@ -2,11 +2,27 @@ use super::backend::mathphysics::Millisecond;
 
 
 pub mod cli;
+pub mod comparison;
 pub mod config;
 pub mod examples;
+pub mod inspect;
+#[cfg(feature = "otel")]
+pub mod otel;
 pub mod player;
+pub mod prometheus;
+#[cfg(feature = "python")]
+pub mod python;
 pub mod renderer;
+#[cfg(any(feature = "grpc", feature = "rest"))]
+pub mod server;
+pub mod summary;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "gui")]
+pub mod viewer;
 
 
-pub const MALWARE_INFECTION_DELAY: Millisecond      = 1000;
-pub const MALWARE_SPREAD_DELAY: Option<Millisecond> = Some(500);
+pub const MALWARE_INFECTION_DELAY: Millisecond      = Millisecond::new(1000);
+pub const MALWARE_SPREAD_DELAY: Option<Millisecond> = Some(
+    Millisecond::new(500)
+);
@@ -1,71 +1,429 @@
+use std::ffi::OsString;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
-use clap::{Arg, ArgAction, Command, value_parser};
+use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command, value_parser};
+use clap::error::{ContextKind, ContextValue, ErrorKind};
 
-use crate::backend::mathphysics::Millisecond;
+use crate::backend::coverage::CoverageArea;
+use crate::backend::landing::LandingZone;
+use crate::backend::linkloss::{AttenuationZone, Obstruction, Region};
+use crate::backend::device::systems::{
+    VersionRange, VersionRangeParseError
+};
+use crate::backend::mathphysics::{
+    Float, Kilogram, Meter, Millisecond, Point3D
+};
+use crate::frontend::config::{AttackerConfig, AttackerKind};
+use crate::frontend::examples::ExampleKind;
 use crate::frontend::renderer::{Pixel, PlottersUnit};
 
 use args::{
-    handle_arguments, ARG_CAMERA_PITCH, ARG_CAMERA_YAW, ARG_DELAY_MULTIPLIER, 
-    ARG_DRONE_COUNT, ARG_EXPERIMENT_TITLE, ARG_EW_FREQUENCY, 
-    ARG_ATTACKER_RADIUS, ARG_JSON_INPUT, ARG_MALWARE_TYPE, ARG_NO_PLOT, 
-    ARG_NETWORK_TOPOLOGY, ARG_JSON_OUTPUT, ARG_PLOT_CAPTION, ARG_PLOT_HEIGHT, 
-    ARG_PLOT_WIDTH, ARG_SIG_LOSS_RESP, ARG_SIM_TIME, ARG_VERBOSE, 
-    DEFAULT_CAMERA_PITCH, DEFAULT_CAMERA_YAW, DEFAULT_DELAY_MULTIPLIER, 
-    DEFAULT_DRONE_COUNT, DEFAULT_PLOT_CAPTION, DEFAULT_PLOT_HEIGHT, 
-    DEFAULT_PLOT_WIDTH, DEFAULT_SIM_TIME, EXP_CUSTOM, EXP_EWD, EXP_GPS_SPOOFING, 
-    EXP_MALWARE_INFECTION, EXP_MOVEMENT, EXP_SIGNAL_LOSS, EW_CONTROL, EW_GPS, 
-    MAL_DOS, MAL_INDICATOR, SLR_ASCEND, SLR_IGNORE, SLR_HOVER, SLR_RTH, 
-    SLR_SHUTDOWN, TOPOLOGY_MESH, TOPOLOGY_STAR,
+    handle_arguments, experiment_title, ARG_ADAPTIVE_STEPPING, 
+    ARG_AGGREGATION, ARG_ALTITUDE_LOSS, ARG_ATTACKED_BANDS, ARG_ATTACKER, ARG_ATTACKER_RADIUS, 
+    ARG_ATTENUATION_ZONE, ARG_BIT_ERRORS, ARG_CAMERA_PITCH, ARG_CAMERA_YAW, 
+    ARG_CHANNEL_SWITCH, ARG_CLOCK_DRIFT, ARG_COLLISIONS, ARG_COLLISION_DAMAGE, 
+    ARG_COLLISION_DESTROY, ARG_COLOR_BY_TASK, ARG_COMPARE_TOPOLOGIES, 
+    ARG_CONFIG_FILE, ARG_COVERAGE_AREA, ARG_CRITICAL_BATTERY, 
+    ARG_DELAY_MULTIPLIER, 
+    ARG_DRAW_CONNECTIONS, ARG_DRAW_DASHBOARD, ARG_DRAW_GPS, ARG_DRAW_LABELS, 
+    ARG_DRAW_LEGEND, ARG_DRAW_VELOCITIES, ARG_DRONE_COUNT, ARG_EW_FREQUENCY, 
+    ARG_EXPERIMENT_TITLE, ARG_FAULT_PLAN, ARG_FIRMWARE_UPDATE, ARG_FLOCKING, 
+    ARG_FOG_DENSITY, ARG_FORMATION, ARG_FRAME_FORMAT, ARG_FRAME_OUTPUT, 
+    ARG_GOSSIP, 
+    ARG_GPS_BANDS, ARG_GPS_CHECK, ARG_GPS_MTBF, ARG_HOME_POINT, 
+    ARG_INTEGRITY_CHECK, 
+    ARG_JSON_DELTA, ARG_JSON_INPUT, ARG_JSON_OUTPUT, ARG_LANDING_ZONE, 
+    ARG_LATENCY_STATS, 
+    ARG_LOG_FILE, ARG_MALWARE_TYPE, ARG_MALWARE_VERSIONS, ARG_MAX_CLIMB_RATE, 
+    ARG_MAX_DESCENT_RATE, ARG_MAX_TURN_RATE, ARG_METRICS_OUTPUT, 
+    ARG_MODEL_PATH, ARG_MOTORS_MTBF, ARG_NETWORK_ORIGIN, ARG_NETWORK_TOPOLOGY, 
+    ARG_NO_PLOT, ARG_NS3_OUTPUT, ARG_OBSTRUCTION, ARG_OUTPUT_COMPRESSION, 
+    ARG_OUTPUT_FORMAT, ARG_PATH_LOSS_EXPONENT, 
+    ARG_PAYLOAD_MASS, ARG_PLAYBACK_SPEED, ARG_PLOT_CAPTION, ARG_PLOT_HEIGHT, 
+    ARG_PLOT_WIDTH, ARG_PROCESSING_DELAY, ARG_PROMETHEUS_ADDRESS, 
+    ARG_PROPAGATION, 
+    ARG_RADIO_MTBF, ARG_RAIN_RATE, ARG_REAL_TIME, ARG_RENDER_INTERVAL, 
+    ARG_RTH_ALTITUDE, 
+    ARG_RUN_SUMMARY, ARG_SCENARIO, ARG_SCHEMA_FORMAT, ARG_SEED, ARG_SENSOR, 
+    ARG_SIG_LOSS_RESP, ARG_SIM_TIME, ARG_SINR_THRESHOLD, 
+    ARG_SITL_ADDRESS, ARG_SITL_DEVICE, 
+    ARG_SPAWN_SPREAD, ARG_STREAM_DELTA, ARG_STREAM_OUTPUT, ARG_SYNC_PERIOD, 
+    ARG_TEMPLATE_OUTPUT, ARG_THEME, ARG_THEME_FILE, ARG_THERMAL, ARG_VERBOSE, 
+    ARG_VERIFY, ARG_WEATHER_GPS, ATTACKER_CONTROL_JAMMING, 
+    ATTACKER_GPS_JAMMING, 
+    ATTACKER_GPS_SPOOFING, CMD_GENERATE, CMD_INSPECT, CMD_LIST_EXPERIMENTS, 
+    CMD_SCHEMA, COMPRESSION_GZIP, COMPRESSION_NONE, COMPRESSION_ZSTD, 
+    DEFAULT_CAMERA_PITCH, DEFAULT_CAMERA_YAW, DEFAULT_COLLISION_DAMAGE, 
+    DEFAULT_COLLISION_DESTROY, DEFAULT_DELAY_MULTIPLIER, DEFAULT_DRONE_COUNT, 
+    DEFAULT_FRAME_FORMAT, DEFAULT_GPS_BANDS, DEFAULT_OUTPUT_COMPRESSION, 
+    DEFAULT_OUTPUT_FORMAT, 
+    DEFAULT_PAYLOAD_MASS, DEFAULT_PLAYBACK_SPEED, DEFAULT_PLOT_CAPTION, 
+    DEFAULT_PLOT_HEIGHT, DEFAULT_PLOT_WIDTH, DEFAULT_RENDER_INTERVAL, 
+    DEFAULT_SIM_TIME, DEFAULT_THEME, ENV_JSON_OUTPUT, ENV_LOG_FILE, 
+    ENV_METRICS_OUTPUT, ENV_RUN_SUMMARY, ENV_SEED, ENV_SIM_TIME, EW_BACKUP, 
+    EW_CONTROL, EW_GPS, EXP_CUSTOM, EXP_DEFINITION, EXP_EWD, EXP_GPS_SPOOFING, 
+    EXP_MALWARE_INFECTION, EXP_MOVEMENT, EXP_REPLAY, EXP_SIGNAL_LOSS, 
+    FORMATION_GRID, FORMATION_LINE, FORMATION_WEDGE, FORMAT_BINARY, 
+    FORMAT_JSON, FRAME_PNG, FRAME_SVG, GPS_CHECK_DETECT, GPS_CHECK_REJECT, 
+    GPS_L1, GPS_L2, GPS_L5, GROUP_TOPOLOGY, 
+    MAL_DOS, MAL_INDICATOR, PROPAGATION_FREE_SPACE, PROPAGATION_LOG_DISTANCE, 
+    PROPAGATION_TWO_RAY, SCHEMA_DEFINITION, SCHEMA_FAULTS, SCHEMA_MODEL, 
+    SCHEMA_SCENARIO, SLR_ASCEND, SLR_HOVER, SLR_IGNORE, SLR_LAND, 
+    SLR_MISSION, SLR_RTH, SLR_SHUTDOWN, THEME_COLORBLIND, THEME_DARK, 
+    THEME_LIGHT, TOPOLOGY_MESH, TOPOLOGY_STAR,
 };
 
+#[cfg(any(feature = "kafka", feature = "mqtt"))]
+use args::ARG_EVENT_ENDPOINT;
+#[cfg(feature = "grpc")]
+use args::ARG_GRPC_ADDRESS;
+#[cfg(feature = "gui")]
+use args::ARG_LIVE_VIEWER;
+#[cfg(feature = "otel")]
+use args::ARG_OTEL_ENDPOINT;
+#[cfg(feature = "parquet")]
+use args::ARG_PARQUET_OUTPUT;
+#[cfg(feature = "rest")]
+use args::ARG_REST_ADDRESS;
+#[cfg(feature = "ros2")]
+use args::ARG_ROS2_ENDPOINT;
+#[cfg(feature = "tui")]
+use args::ARG_TUI_DASHBOARD;
+use configfile::{config_file_arguments, config_file_path};
+
 
 mod args;
+mod configfile;
+mod logfile;
 
 
 pub fn cli() {
-    let matches = Command::new("drone_network")
+    let command = Command::new("drone_network")
         .version("0.2.2")
         .about("Models drone networks.")
         .args([
+            arg_config_file(),
             arg_experiment_title(),
             arg_signal_loss_response(),
+            arg_home_point(),
+            arg_rth_altitude(),
             arg_topology(),
             arg_drone_count(),
+            arg_network_origin(),
+            arg_spawn_spread(),
             arg_delay_multiplier(),
+            arg_motors_mtbf(),
+            arg_radio_mtbf(),
+            arg_gps_mtbf(),
+            arg_thermal(),
+            arg_sensor(),
+            arg_gps_check(),
+            arg_gps_bands(),
+            arg_channel_switch(),
+            arg_gossip(),
+            arg_clock_drift(),
+            arg_sync_period(),
+            arg_firmware_update(),
+            arg_processing_delay(),
+            arg_aggregation(),
+            arg_bit_errors(),
+            arg_integrity_check(),
+            arg_flocking(),
+            arg_payload_mass(),
+            arg_critical_battery(),
+            arg_max_turn_rate(),
+            arg_max_climb_rate(),
+            arg_max_descent_rate(),
+            arg_formation(),
+            arg_collisions(),
+            arg_collision_destroy(),
+            arg_collision_damage(),
             arg_ew_frequency(),
             arg_attacker_radius(),
+            arg_attacker(),
+            arg_attacked_bands(),
             arg_malware_type(),
+            arg_malware_versions(),
             arg_json_input(),
             arg_json_output(),
+            arg_json_delta(),
+            arg_output_format(),
+            arg_output_compression(),
+            arg_metrics_output(),
+            arg_coverage_area(),
+            arg_rain_rate(),
+            arg_fog_density(),
+            arg_weather_gps(),
+            arg_obstruction(),
+            arg_attenuation_zone(),
+            arg_altitude_loss(),
+            arg_propagation(),
+            arg_path_loss_exponent(),
+            arg_sinr_threshold(),
+            arg_landing_zone(),
+            arg_ns3_output(),
+            arg_stream_output(),
+            arg_stream_delta(),
+            arg_prometheus_address(),
             arg_simulation_time(),
+            arg_seed(),
+            arg_adaptive_stepping(),
+            arg_real_time(),
+            arg_playback_speed(),
+            arg_sitl_address(),
+            arg_sitl_device(),
+            arg_scenario(),
+            arg_fault_plan(),
+            arg_verify(),
             arg_no_plot(),
+            arg_render_interval(),
+            arg_frame_output(),
+            arg_frame_format(),
+            arg_theme(),
+            arg_theme_file(),
+            arg_color_by_task(),
+            arg_draw_connections(),
+            arg_draw_labels(),
+            arg_draw_legend(),
+            arg_draw_velocities(),
+            arg_draw_gps(),
+            arg_draw_dashboard(),
             arg_plot_caption(),
             arg_plot_width(),
             arg_plot_height(),
             arg_camera_pitch(),
             arg_camera_yaw(),
+            arg_run_summary(),
+            arg_compare_topologies(),
+            arg_latency_stats(),
             arg_verbose(),
+            arg_log_file(),
         ])
-        .arg_required_else_help(true)
-        .get_matches();
+        // Comparing topologies runs every one of them, so a single one
+        // cannot be chosen too.
+        .group(
+            ArgGroup::new(GROUP_TOPOLOGY)
+                .args([ARG_NETWORK_TOPOLOGY, ARG_COMPARE_TOPOLOGIES])
+        )
+        .subcommand(cmd_generate())
+        .subcommand(cmd_inspect())
+        .subcommand(cmd_list_experiments())
+        .subcommand(cmd_schema())
+        .args_override_self(true)
+        .arg_required_else_help(true);
+
+    #[cfg(any(feature = "kafka", feature = "mqtt"))]
+    let command = command.arg(arg_event_endpoint());
+    #[cfg(feature = "grpc")]
+    let command = command.arg(arg_grpc_address());
+    #[cfg(feature = "gui")]
+    let command = command.arg(arg_live_viewer());
+    #[cfg(feature = "otel")]
+    let command = command.arg(arg_otel_endpoint());
+    #[cfg(feature = "parquet")]
+    let command = command.arg(arg_parquet_output());
+    #[cfg(feature = "rest")]
+    let command = command.arg(arg_rest_address());
+    #[cfg(feature = "ros2")]
+    let command = command.arg(arg_ros2_endpoint());
+    #[cfg(feature = "tui")]
+    let command = command.arg(arg_tui_dashboard());
+
+    let arguments = command_line_arguments(&command);
+    let matches = command.clone().get_matches_from(arguments);
+
+    check_json_delta_format(&command, &matches);
+
+    // Listing needs the command itself to find out the required arguments.
+    if matches.subcommand_matches(CMD_LIST_EXPERIMENTS).is_some() {
+        list_experiments(&command);
+
+        return;
+    }
 
     handle_arguments(&matches);
 }
 
+// Delta snapshots are written only in JSON, so `--jd` may be combined with
+// `--of json` but not with `--of binary`.
+fn check_json_delta_format(command: &Command, matches: &ArgMatches) {
+    let json_delta = matches
+        .get_one::<bool>(ARG_JSON_DELTA)
+        .is_some_and(|json_delta| *json_delta);
+    let output_format = matches
+        .get_one::<String>(ARG_OUTPUT_FORMAT)
+        .map(String::as_str);
+
+    if json_delta && output_format == Some(FORMAT_BINARY) {
+        command
+            .clone()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "the argument '--jd' cannot be used with '--of binary'"
+            )
+            .exit();
+    }
+}
+
+// Options from the config file are put before the command line ones, so the
+// latter override them.
+fn command_line_arguments(command: &Command) -> Vec<OsString> {
+    let mut arguments: Vec<OsString> = std::env::args_os().collect();
+
+    let Some(config_path) = config_file_path(&arguments) else {
+        return arguments;
+    };
+
+    let config_arguments = config_file_arguments(&config_path, command)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let program_name_count = arguments.len().min(1);
+
+    arguments.splice(
+        program_name_count..program_name_count, 
+        config_arguments
+    );
+
+    arguments
+}
+
+fn list_experiments(command: &Command) {
+    for example_kind in ExampleKind::ALL {
+        let title = experiment_title(example_kind);
+
+        println!("{title:<12}{}", example_kind.description());
+
+        let required_arguments = required_arguments(command, title);
+
+        if !required_arguments.is_empty() {
+            println!("{:<12}Requires: {}", "", required_arguments.join(" "));
+        }
+    }
+}
+
+// The experiment is chosen without any other argument, so clap reports all
+// the arguments it requires.
+fn required_arguments(
+    command: &Command,
+    experiment_title: &str
+) -> Vec<String> {
+    let Err(error) = command
+        .clone()
+        .try_get_matches_from([command.get_name(), "-x", experiment_title])
+    else {
+        return Vec::new();
+    };
+
+    if error.kind() != ErrorKind::MissingRequiredArgument {
+        return Vec::new();
+    }
+
+    match error.get(ContextKind::InvalidArg) {
+        Some(ContextValue::Strings(arguments)) => arguments.clone(),
+        _                                      => Vec::new(),
+    }
+}
+
+fn cmd_generate() -> Command {
+    Command::new(CMD_GENERATE)
+        .about(
+            format!(
+                "Generate template model file to start custom experiments \
+                from (\"{EXP_CUSTOM}\" experiment)"
+            )
+        )
+        .args([
+            Arg::new(ARG_DRONE_COUNT)
+                .short('n')
+                .value_parser(value_parser!(usize))
+                .default_value(DEFAULT_DRONE_COUNT)
+                .help("Set the number of drones in the model"),
+            Arg::new(ARG_NETWORK_TOPOLOGY)
+                .long("topology")
+                .value_parser([TOPOLOGY_MESH, TOPOLOGY_STAR])
+                .help("Choose network topology [default: star]"),
+            Arg::new(ARG_TEMPLATE_OUTPUT)
+                .short('o')
+                .value_parser(value_parser!(PathBuf))
+                .help("Write model to the file instead of stdout"),
+        ])
+}
+
+fn cmd_inspect() -> Command {
+    Command::new(CMD_INSPECT)
+        .about(
+            "Validate model file and print its summary without running the \
+            simulation"
+        )
+        .arg(
+            Arg::new(ARG_MODEL_PATH)
+                .value_parser(value_parser!(PathBuf))
+                .required(true)
+                .help("Model file path")
+        )
+}
+
+fn cmd_list_experiments() -> Command {
+    Command::new(CMD_LIST_EXPERIMENTS)
+        .about("List experiments with their descriptions and required options")
+}
+
+fn cmd_schema() -> Command {
+    Command::new(CMD_SCHEMA)
+        .about(
+            "Print JSON Schema of input file format, so editors and other \
+            tools can validate the files"
+        )
+        .arg(
+            Arg::new(ARG_SCHEMA_FORMAT)
+                .value_parser([
+                    SCHEMA_DEFINITION,
+                    SCHEMA_FAULTS,
+                    SCHEMA_MODEL,
+                    SCHEMA_SCENARIO,
+                ])
+                .required(true)
+                .help(
+                    format!(
+                        "Choose file format (\"{SCHEMA_MODEL}\" for \
+                        \"{EXP_CUSTOM}\" experiment input)"
+                    )
+                )
+        )
+}
+
+fn arg_config_file() -> Arg {
+    Arg::new(ARG_CONFIG_FILE)
+        .long("config")
+        .value_parser(value_parser!(PathBuf))
+        .help(
+            "Load options from `.toml` or `.yaml` file, options given on \
+            the command line override them"
+        )
+}
+
 fn arg_experiment_title() -> Arg {
     Arg::new(ARG_EXPERIMENT_TITLE)
         .short('x')
         .requires_ifs([
             (EXP_CUSTOM, ARG_JSON_INPUT),
+            (EXP_DEFINITION, ARG_JSON_INPUT),
+            (EXP_EWD, GROUP_TOPOLOGY),
+            (EXP_GPS_SPOOFING, GROUP_TOPOLOGY),
             (EXP_MALWARE_INFECTION, ARG_MALWARE_TYPE),
+            (EXP_MALWARE_INFECTION, GROUP_TOPOLOGY),
+            (EXP_MOVEMENT, GROUP_TOPOLOGY),
+            (EXP_REPLAY, ARG_JSON_INPUT),
+            (EXP_SIGNAL_LOSS, GROUP_TOPOLOGY),
         ])
         .value_parser([
             EXP_CUSTOM,
+            EXP_DEFINITION,
             EXP_EWD,
             EXP_GPS_SPOOFING,
             EXP_MALWARE_INFECTION,
             EXP_MOVEMENT,
+            EXP_REPLAY,
             EXP_SIGNAL_LOSS,
         ])
         .help("Choose experiment title")
@@ -75,10 +433,22 @@ fn arg_signal_loss_response() -> Arg {
     Arg::new(ARG_SIG_LOSS_RESP)
         .long("slr")
         .value_parser(
-            [SLR_ASCEND, SLR_IGNORE, SLR_HOVER, SLR_RTH, SLR_SHUTDOWN]
+            [
+                SLR_ASCEND, 
+                SLR_IGNORE, 
+                SLR_HOVER, 
+                SLR_RTH, 
+                SLR_SHUTDOWN, 
+                SLR_MISSION,
+                SLR_LAND
+            ]
         )
-        .required(true)
-        .conflicts_with(EXP_SIGNAL_LOSS)
+        .required_if_eq_any([
+            (ARG_EXPERIMENT_TITLE, EXP_EWD),
+            (ARG_EXPERIMENT_TITLE, EXP_GPS_SPOOFING),
+            (ARG_EXPERIMENT_TITLE, EXP_MALWARE_INFECTION),
+            (ARG_EXPERIMENT_TITLE, EXP_MOVEMENT),
+        ])
         .help(
             format!(
                 "Choose control signal loss response \
@@ -87,17 +457,58 @@ fn arg_signal_loss_response() -> Arg {
         )
 }
 
+fn arg_home_point() -> Arg {
+    Arg::new(ARG_HOME_POINT)
+        .long("home")
+        .value_parser(point)
+        .help(
+            format!(
+                "Set home point of \"{SLR_RTH}\" signal loss response as \
+                `x,y,z` (command center position by default)"
+            )
+        )
+}
+
+fn arg_rth_altitude() -> Arg {
+    Arg::new(ARG_RTH_ALTITUDE)
+        .long("rth-altitude")
+        .value_parser(value_parser!(f32))
+        .help(
+            format!(
+                "Set altitude drones return home at with \"{SLR_RTH}\" \
+                signal loss response (home point altitude by default)"
+            )
+        )
+}
+
+fn point(value: &str) -> Result<Point3D, String> {
+    let parts: Vec<&str> = value.split(',').collect();
+    let [x, y, z] = parts[..] else {
+        return Err("expected `x,y,z`".to_string());
+    };
+
+    let number = |value: &str| value
+        .trim()
+        .parse::<Float>()
+        .map_err(|error| error.to_string());
+
+    Ok(Point3D::new(number(x)?, number(y)?, number(z)?))
+}
+
+fn non_negative_point(value: &str) -> Result<Point3D, String> {
+    let point = point(value)?;
+
+    if point.x >= 0.0 && point.y >= 0.0 && point.z >= 0.0 {
+        Ok(point)
+    } else {
+        Err("coordinates must not be negative".to_string())
+    }
+}
+
 fn arg_topology() -> Arg {
     Arg::new(ARG_NETWORK_TOPOLOGY)
         .long("topology")
         .value_parser([TOPOLOGY_MESH, TOPOLOGY_STAR])
-        .required_if_eq_any([
-            (ARG_EXPERIMENT_TITLE, EXP_EWD),
-            (ARG_EXPERIMENT_TITLE, EXP_GPS_SPOOFING),
-            (ARG_EXPERIMENT_TITLE, EXP_MALWARE_INFECTION),
-            (ARG_EXPERIMENT_TITLE, EXP_MOVEMENT),
-            (ARG_EXPERIMENT_TITLE, EXP_SIGNAL_LOSS),
-        ])
         .help("Choose network topology")
 }
 
@@ -112,6 +523,7 @@ fn arg_drone_count() -> Arg {
 fn arg_simulation_time() -> Arg {
     Arg::new(ARG_SIM_TIME)
         .long("time")
+        .env(ENV_SIM_TIME)
         .value_parser(value_parser!(Millisecond))
         .default_value(DEFAULT_SIM_TIME)
         .help("Set the simulation time (non-negative integer, in millis)")
@@ -120,7 +532,7 @@ fn arg_simulation_time() -> Arg {
 fn arg_ew_frequency() -> Arg {
     Arg::new(ARG_EW_FREQUENCY)
         .long("ewf")
-        .value_parser([EW_CONTROL, EW_GPS])
+        .value_parser([EW_CONTROL, EW_GPS, EW_BACKUP])
         .required_if_eq(ARG_EXPERIMENT_TITLE, EXP_EWD)
         .help(format!("Choose EW frequency (\"{EXP_EWD}\" experiment)"))
 }
@@ -128,7 +540,7 @@ fn arg_ew_frequency() -> Arg {
 fn arg_attacker_radius() -> Arg {
     Arg::new(ARG_ATTACKER_RADIUS)
         .long("ar")
-        .value_parser(value_parser!(f32))
+        .value_parser(value_parser!(Meter))
         .required_if_eq_any([
             (ARG_EXPERIMENT_TITLE, EXP_EWD),
             (ARG_EXPERIMENT_TITLE, EXP_GPS_SPOOFING),
@@ -143,6 +555,29 @@ fn arg_attacker_radius() -> Arg {
         )
 }
 
+fn arg_network_origin() -> Arg {
+    Arg::new(ARG_NETWORK_ORIGIN)
+        .long("origin")
+        .value_parser(point)
+        .conflicts_with(ARG_JSON_INPUT)
+        .help(
+            "Set center of the area drones are spawned in as `x,y,z` \
+            (experiment one by default)"
+        )
+}
+
+fn arg_spawn_spread() -> Arg {
+    Arg::new(ARG_SPAWN_SPREAD)
+        .long("spread")
+        .value_parser(non_negative_point)
+        .conflicts_with(ARG_JSON_INPUT)
+        .help(
+            "Set how far drones are spawned from the network origin along \
+            each axis as `x,y,z`, zero keeps them at the origin coordinate \
+            [default: 40,40,20]"
+        )
+}
+
 fn arg_delay_multiplier() -> Arg {
     Arg::new(ARG_DELAY_MULTIPLIER)
         .long("dm")
@@ -154,6 +589,321 @@ fn arg_delay_multiplier() -> Arg {
         )
 }
 
+fn arg_motors_mtbf() -> Arg {
+    Arg::new(ARG_MOTORS_MTBF)
+        .long("motors-mtbf")
+        .value_parser(value_parser!(Millisecond))
+        .help(
+            "Set mean time between failures of drone motors \
+            (in milliseconds, motors never fail by default)"
+        )
+}
+
+fn arg_radio_mtbf() -> Arg {
+    Arg::new(ARG_RADIO_MTBF)
+        .long("radio-mtbf")
+        .value_parser(value_parser!(Millisecond))
+        .help(
+            "Set mean time between failures of drone radios \
+            (in milliseconds, radios never fail by default)"
+        )
+}
+
+fn arg_gps_mtbf() -> Arg {
+    Arg::new(ARG_GPS_MTBF)
+        .long("gps-mtbf")
+        .value_parser(value_parser!(Millisecond))
+        .help(
+            "Set mean time between failures of drone GPS receivers \
+            (in milliseconds, receivers never fail by default)"
+        )
+}
+
+fn arg_thermal() -> Arg {
+    Arg::new(ARG_THERMAL)
+        .long("thermal")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Enable drone heating, so sustained max speed flight throttles \
+            max speed and TX strength"
+        )
+}
+
+fn arg_sensor() -> Arg {
+    Arg::new(ARG_SENSOR)
+        .long("sensor")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Equip drones with downward sensors that report detected devices \
+            to the command center"
+        )
+}
+
+fn arg_gps_check() -> Arg {
+    Arg::new(ARG_GPS_CHECK)
+        .long("gps-check")
+        .value_parser([GPS_CHECK_DETECT, GPS_CHECK_REJECT])
+        .help(
+            "Check drone GPS fixes against dead reckoning and ranges to \
+            neighbors and report spoofed ones, rejecting them with `reject`"
+        )
+}
+
+fn arg_gps_bands() -> Arg {
+    Arg::new(ARG_GPS_BANDS)
+        .long("gps-bands")
+        .value_parser([GPS_L1, GPS_L2, GPS_L5])
+        .value_delimiter(',')
+        .default_value(DEFAULT_GPS_BANDS)
+        .help(
+            "Choose GPS bands drones listen on, comma separated; drones lose \
+            their fix only when all of them are denied"
+        )
+}
+
+fn arg_channel_switch() -> Arg {
+    Arg::new(ARG_CHANNEL_SWITCH)
+        .long("channel-switch")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Switch drones and the command center to the backup frequency \
+            after sustained control jamming, notifying their peers"
+        )
+}
+
+fn arg_gossip() -> Arg {
+    Arg::new(ARG_GOSSIP)
+        .long("gossip")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Make drones periodically share known infections, the leader and \
+            the last task with random neighbors"
+        )
+}
+
+fn arg_clock_drift() -> Arg {
+    Arg::new(ARG_CLOCK_DRIFT)
+        .long("clock-drift")
+        .value_parser(value_parser!(f32))
+        .help(
+            "Make drone clocks drift by a random rate up to the given one \
+            (in milliseconds per second, clocks keep model time by default)"
+        )
+}
+
+fn arg_sync_period() -> Arg {
+    Arg::new(ARG_SYNC_PERIOD)
+        .long("sync-period")
+        .value_parser(value_parser!(Millisecond))
+        .help(
+            "Make the command center broadcast time beacons drones set their \
+            clocks to (in milliseconds, beacons are not sent by default)"
+        )
+}
+
+fn arg_firmware_update() -> Arg {
+    Arg::new(ARG_FIRMWARE_UPDATE)
+        .long("firmware-update")
+        .value_parser(value_parser!(Millisecond))
+        .help(
+            "Make the command center push a firmware update to drones from \
+            the given time (in milliseconds), drones draw power while \
+            installing it"
+        )
+}
+
+fn arg_processing_delay() -> Arg {
+    Arg::new(ARG_PROCESSING_DELAY)
+        .long("processing-delay")
+        .value_parser(value_parser!(Millisecond))
+        .help(
+            "Set time drones take to act on or relay a received signal (in \
+            milliseconds, rounded down to 50 ms iterations, the command \
+            center acts immediately)"
+        )
+}
+
+fn arg_aggregation() -> Arg {
+    Arg::new(ARG_AGGREGATION)
+        .long("aggregation")
+        .value_parser(value_parser!(Millisecond))
+        .help(
+            "Make relays combine recon records heading to the command center \
+            into one summary per interval (in milliseconds, records are not \
+            aggregated by default)"
+        )
+}
+
+fn arg_bit_errors() -> Arg {
+    Arg::new(ARG_BIT_ERRORS)
+        .long("bit-errors")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Corrupt received data with a probability growing as the signal \
+            weakens, so positions in tasks, GPS fixes and recon records are \
+            subtly wrong and other data is discarded"
+        )
+}
+
+fn arg_integrity_check() -> Arg {
+    Arg::new(ARG_INTEGRITY_CHECK)
+        .long("integrity-check")
+        .requires(ARG_BIT_ERRORS)
+        .action(ArgAction::SetTrue)
+        .help(
+            "Make devices check received data integrity and discard all \
+            corrupted data"
+        )
+}
+
+fn arg_max_turn_rate() -> Arg {
+    Arg::new(ARG_MAX_TURN_RATE)
+        .long("max-turn-rate")
+        .value_parser(positive_f32)
+        .help(
+            "Limit how fast drones change heading \
+            (in degrees per second, unlimited by default)"
+        )
+}
+
+fn arg_max_climb_rate() -> Arg {
+    Arg::new(ARG_MAX_CLIMB_RATE)
+        .long("max-climb-rate")
+        .value_parser(positive_f32)
+        .help("Limit drone climb rate (in meters per second)")
+}
+
+fn arg_max_descent_rate() -> Arg {
+    Arg::new(ARG_MAX_DESCENT_RATE)
+        .long("max-descent-rate")
+        .value_parser(positive_f32)
+        .help("Limit drone descent rate (in meters per second)")
+}
+
+fn arg_flocking() -> Arg {
+    Arg::new(ARG_FLOCKING)
+        .long("flocking")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Steer drones away from close neighbors and along with the swarm \
+            (separation, alignment and cohesion)"
+        )
+}
+
+fn arg_payload_mass() -> Arg {
+    Arg::new(ARG_PAYLOAD_MASS)
+        .long("payload")
+        .value_parser(value_parser!(Kilogram))
+        .default_value(DEFAULT_PAYLOAD_MASS)
+        .help(
+            "Set payload mass carried by each drone \
+            (in kilograms, heavier drones consume more power)"
+        )
+}
+
+fn arg_critical_battery() -> Arg {
+    Arg::new(ARG_CRITICAL_BATTERY)
+        .long("critical-battery")
+        .value_parser(value_parser!(f32))
+        .help(
+            "Land drones in an emergency once their battery level drops to \
+            the fraction of max energy (0.0-1.0, never by default)"
+        )
+}
+
+fn arg_formation() -> Arg {
+    Arg::new(ARG_FORMATION)
+        .long("formation")
+        .value_parser([FORMATION_LINE, FORMATION_WEDGE, FORMATION_GRID])
+        .help(
+            "Keep drones in formation led by the first drone \
+            (members follow velocity commands instead of scenario tasks)"
+        )
+}
+
+fn arg_collisions() -> Arg {
+    Arg::new(ARG_COLLISIONS)
+        .long("collisions")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Detect drone collisions, which destroy, damage or deflect \
+            colliding drones"
+        )
+}
+
+fn arg_collision_destroy() -> Arg {
+    Arg::new(ARG_COLLISION_DESTROY)
+        .long("collision-destroy")
+        .requires(ARG_COLLISIONS)
+        .value_parser(probability)
+        .default_value(DEFAULT_COLLISION_DESTROY)
+        .help("Set probability of a collision destroying both drones")
+}
+
+fn arg_collision_damage() -> Arg {
+    Arg::new(ARG_COLLISION_DAMAGE)
+        .long("collision-damage")
+        .requires(ARG_COLLISIONS)
+        .value_parser(probability)
+        .default_value(DEFAULT_COLLISION_DAMAGE)
+        .help(
+            "Set probability of a collision damaging both drones (damaged \
+            drones lose health and max speed, the rest are deflected)"
+        )
+}
+
+fn arg_attacker() -> Arg {
+    Arg::new(ARG_ATTACKER)
+        .long("attacker")
+        .value_parser(attacker)
+        .action(ArgAction::Append)
+        .conflicts_with(ARG_JSON_INPUT)
+        .help(
+            format!(
+                "Add attacker device to the experiment ones as \
+                `kind,x,y,z,radius`, can be repeated \
+                [possible kinds: {ATTACKER_CONTROL_JAMMING}, \
+                {ATTACKER_GPS_JAMMING}, {ATTACKER_GPS_SPOOFING}]"
+            )
+        )
+}
+
+fn attacker(value: &str) -> Result<AttackerConfig, String> {
+    let parts: Vec<&str> = value.split(',').collect();
+    let [kind, x, y, z, area_radius] = parts[..] else {
+        return Err("expected `kind,x,y,z,radius`".to_string());
+    };
+
+    let kind = match kind.trim() {
+        ATTACKER_CONTROL_JAMMING => AttackerKind::ControlJamming,
+        ATTACKER_GPS_JAMMING     => AttackerKind::GPSJamming,
+        ATTACKER_GPS_SPOOFING    => AttackerKind::GPSSpoofing,
+        _                        => 
+            return Err(format!("unknown attacker kind `{kind}`")),
+    };
+    let number = |value: &str| value
+        .trim()
+        .parse::<Float>()
+        .map_err(|error| error.to_string());
+    let position = Point3D::new(number(x)?, number(y)?, number(z)?);
+    let area_radius = Meter::new(number(area_radius)?);
+
+    if area_radius < Meter::ZERO {
+        return Err("radius must be non-negative".to_string());
+    }
+
+    Ok(AttackerConfig::new(kind, position, area_radius))
+}
+
+fn arg_attacked_bands() -> Arg {
+    Arg::new(ARG_ATTACKED_BANDS)
+        .long("attacked-bands")
+        .value_parser([GPS_L1, GPS_L2, GPS_L5])
+        .value_delimiter(',')
+        .default_value(DEFAULT_GPS_BANDS)
+        .help("Choose GPS bands jammed or spoofed by attackers, comma separated")
+}
+
 fn arg_malware_type() -> Arg {
     Arg::new(ARG_MALWARE_TYPE)
         .long("mt")
@@ -165,6 +915,23 @@ fn arg_malware_type() -> Arg {
         )
 }
 
+fn arg_malware_versions() -> Arg {
+    Arg::new(ARG_MALWARE_VERSIONS)
+        .long("malware-versions")
+        .value_parser(version_range)
+        .help(
+            "Make malware infect only drones with firmware versions from the \
+            given range (`<first>..<last>`, drones run version 1 and are \
+            updated to version 2)"
+        )
+}
+
+fn version_range(value: &str) -> Result<VersionRange, String> {
+    value
+        .parse()
+        .map_err(|error: VersionRangeParseError| error.to_string())
+}
+
 fn arg_json_input() -> Arg {
     Arg::new(ARG_JSON_INPUT)
         .long("ji")
@@ -178,8 +945,11 @@ fn arg_json_input() -> Arg {
         ])
         .help(
             format!(
-                "Deserialize network model from `.json` file and use it \
-                (\"{EXP_CUSTOM}\" experiment)"
+                "Deserialize network model from `.json` or `.bin` file and \
+                use it (\"{EXP_CUSTOM}\" experiment), build it from `.json` \
+                experiment definition (\"{EXP_DEFINITION}\" experiment), or \
+                replay iteration files from directory (\"{EXP_REPLAY}\" \
+                experiment)"
             )
         )
 }
@@ -187,10 +957,487 @@ fn arg_json_input() -> Arg {
 fn arg_json_output() -> Arg {
     Arg::new(ARG_JSON_OUTPUT)
         .long("jo")
+        .env(ENV_JSON_OUTPUT)
         .value_parser(value_parser!(PathBuf))
         .help(
-            "Serialize network model data on each iteration to `.json` files \
-            in specified directory"
+            "Serialize network model data on each iteration to files in \
+            specified directory"
+        )
+}
+
+fn arg_json_delta() -> Arg {
+    Arg::new(ARG_JSON_DELTA)
+        .long("jd")
+        .requires(ARG_JSON_OUTPUT)
+        .action(ArgAction::SetTrue)
+        .help(
+            "Serialize only changes since the previous iteration, with a full \
+            snapshot every 100 iterations (JSON format only)"
+        )
+}
+
+fn arg_output_format() -> Arg {
+    Arg::new(ARG_OUTPUT_FORMAT)
+        .long("of")
+        .requires(ARG_JSON_OUTPUT)
+        .value_parser([FORMAT_JSON, FORMAT_BINARY])
+        .default_value(DEFAULT_OUTPUT_FORMAT)
+        .help("Choose iteration output file format")
+}
+
+fn arg_output_compression() -> Arg {
+    Arg::new(ARG_OUTPUT_COMPRESSION)
+        .long("oc")
+        .requires(ARG_JSON_OUTPUT)
+        .value_parser([COMPRESSION_NONE, COMPRESSION_GZIP, COMPRESSION_ZSTD])
+        .default_value(DEFAULT_OUTPUT_COMPRESSION)
+        .help("Choose iteration output file compression")
+}
+
+fn arg_metrics_output() -> Arg {
+    Arg::new(ARG_METRICS_OUTPUT)
+        .long("mo")
+        .env(ENV_METRICS_OUTPUT)
+        .value_parser(value_parser!(PathBuf))
+        .help(
+            "Write network metrics on each iteration to specified `.csv` file"
+        )
+}
+
+fn arg_coverage_area() -> Arg {
+    Arg::new(ARG_COVERAGE_AREA)
+        .long("coverage-area")
+        .value_parser(area)
+        .help(
+            "Set mission area as opposite ground corners `x1,y1,x2,y2` to \
+            add its sensor and control coverage to metrics"
+        )
+}
+
+fn area(value: &str) -> Result<CoverageArea, String> {
+    let parts: Vec<&str> = value.split(',').collect();
+    let [x1, y1, x2, y2] = parts[..] else {
+        return Err("expected `x1,y1,x2,y2`".to_string());
+    };
+
+    let number = |value: &str| value
+        .trim()
+        .parse::<Float>()
+        .map_err(|error| error.to_string());
+    let corner = (number(x1)?, number(y1)?);
+    let opposite_corner = (number(x2)?, number(y2)?);
+
+    if corner.0 == opposite_corner.0 || corner.1 == opposite_corner.1 {
+        return Err("area must not be empty".to_string());
+    }
+
+    Ok(CoverageArea::new(corner, opposite_corner))
+}
+
+fn arg_rain_rate() -> Arg {
+    Arg::new(ARG_RAIN_RATE)
+        .long("rain")
+        .value_parser(non_negative_float)
+        .help("Attenuate signals by rain (in millimeters per hour)")
+}
+
+fn arg_fog_density() -> Arg {
+    Arg::new(ARG_FOG_DENSITY)
+        .long("fog")
+        .value_parser(non_negative_float)
+        .help(
+            "Attenuate signals by fog (liquid water content in grams per \
+            cubic meter)"
+        )
+}
+
+fn arg_weather_gps() -> Arg {
+    Arg::new(ARG_WEATHER_GPS)
+        .long("weather-gps")
+        .action(ArgAction::SetTrue)
+        .help("Attenuate GPS signals by weather too")
+}
+
+fn arg_obstruction() -> Arg {
+    Arg::new(ARG_OBSTRUCTION)
+        .long("obstruction")
+        .value_parser(obstruction)
+        .action(ArgAction::Append)
+        .help(
+            "Add loss to links crossing a box as opposite corners and loss \
+            `x1,y1,z1,x2,y2,z2,dB`, can be repeated"
+        )
+}
+
+fn obstruction(value: &str) -> Result<Obstruction, String> {
+    let (region, loss) = region_with_value(value)?;
+
+    Ok(Obstruction::new(region, loss))
+}
+
+fn arg_attenuation_zone() -> Arg {
+    Arg::new(ARG_ATTENUATION_ZONE)
+        .long("attenuation-zone")
+        .value_parser(attenuation_zone)
+        .action(ArgAction::Append)
+        .help(
+            "Attenuate links inside a box as opposite corners and \
+            attenuation `x1,y1,z1,x2,y2,z2,dB/km`, can be repeated"
+        )
+}
+
+fn attenuation_zone(value: &str) -> Result<AttenuationZone, String> {
+    let (region, attenuation) = region_with_value(value)?;
+
+    Ok(AttenuationZone::new(region, attenuation))
+}
+
+fn region_with_value(value: &str) -> Result<(Region, Float), String> {
+    let numbers = value
+        .split(',')
+        .map(|number| number
+            .trim()
+            .parse::<Float>()
+            .map_err(|error| error.to_string())
+        )
+        .collect::<Result<Vec<Float>, String>>()?;
+    let [x1, y1, z1, x2, y2, z2, value] = numbers[..] else {
+        return Err("expected `x1,y1,z1,x2,y2,z2,value`".to_string());
+    };
+
+    if value < 0.0 {
+        return Err("value must not be negative".to_string());
+    }
+
+    Ok((
+        Region::new(Point3D::new(x1, y1, z1), Point3D::new(x2, y2, z2)),
+        value
+    ))
+}
+
+fn arg_altitude_loss() -> Arg {
+    Arg::new(ARG_ALTITUDE_LOSS)
+        .long("altitude-loss")
+        .value_parser(non_negative_float)
+        .help(
+            "Add loss to links by altitude difference of their ends \
+            (in dB per meter)"
+        )
+}
+
+fn arg_propagation() -> Arg {
+    Arg::new(ARG_PROPAGATION)
+        .long("propagation")
+        .value_parser([
+            PROPAGATION_FREE_SPACE, 
+            PROPAGATION_LOG_DISTANCE, 
+            PROPAGATION_TWO_RAY
+        ])
+        .help(
+            "Choose how signals weaken with distance (free space by default, \
+            two-ray ground reflection assumes 2 m high antennas)"
+        )
+}
+
+fn arg_path_loss_exponent() -> Arg {
+    Arg::new(ARG_PATH_LOSS_EXPONENT)
+        .long("path-loss-exponent")
+        .value_parser(non_negative_float)
+        .help(
+            "Set the path loss exponent of the log-distance propagation \
+            model [default: 3.0]"
+        )
+}
+
+fn arg_sinr_threshold() -> Arg {
+    Arg::new(ARG_SINR_THRESHOLD)
+        .long("sinr-threshold")
+        .value_parser(value_parser!(Float))
+        .allow_negative_numbers(true)
+        .help(
+            "Make concurrent transmissions on a frequency interfere and \
+            receive only signals with SINR above threshold (in dB)"
+        )
+}
+
+fn arg_landing_zone() -> Arg {
+    Arg::new(ARG_LANDING_ZONE)
+        .long("landing-zone")
+        .value_parser(landing_zone)
+        .action(ArgAction::Append)
+        .help(
+            "Add a zone drones land at in an emergency as ground center and \
+            radius `x,y,z,radius`, can be repeated"
+        )
+}
+
+fn landing_zone(value: &str) -> Result<LandingZone, String> {
+    let numbers = value
+        .split(',')
+        .map(|number| number
+            .trim()
+            .parse::<Float>()
+            .map_err(|error| error.to_string())
+        )
+        .collect::<Result<Vec<Float>, String>>()?;
+    let [x, y, z, radius] = numbers[..] else {
+        return Err("expected `x,y,z,radius`".to_string());
+    };
+
+    if radius < 0.0 {
+        return Err("radius must not be negative".to_string());
+    }
+
+    Ok(LandingZone::new(Point3D::new(x, y, z), Meter::new(radius)))
+}
+
+fn arg_ns3_output() -> Arg {
+    Arg::new(ARG_NS3_OUTPUT)
+        .long("ns3")
+        .value_parser(value_parser!(PathBuf))
+        .help(
+            "Write ns-2 mobility and ns-3 ASCII packet traces to specified \
+            directory"
+        )
+}
+
+#[cfg(feature = "parquet")]
+fn arg_parquet_output() -> Arg {
+    Arg::new(ARG_PARQUET_OUTPUT)
+        .long("po")
+        .value_parser(value_parser!(PathBuf))
+        .help(
+            "Write metric and event time series to `.parquet` files in \
+            specified directory"
+        )
+}
+
+fn arg_stream_output() -> Arg {
+    Arg::new(ARG_STREAM_OUTPUT)
+        .long("so")
+        .help(
+            "Stream iteration data as JSON to specified `tcp://host:port` or \
+            `ws://host:port/path` endpoint"
+        )
+}
+
+fn arg_stream_delta() -> Arg {
+    Arg::new(ARG_STREAM_DELTA)
+        .long("sd")
+        .requires(ARG_STREAM_OUTPUT)
+        .action(ArgAction::SetTrue)
+        .help(
+            "Stream only changes since the previous iteration, with a full \
+            snapshot every 100 iterations"
+        )
+}
+
+fn arg_prometheus_address() -> Arg {
+    Arg::new(ARG_PROMETHEUS_ADDRESS)
+        .long("prometheus")
+        .value_parser(value_parser!(SocketAddr))
+        .help(
+            "Serve simulation metrics for Prometheus on `/metrics` of \
+            specified address while the experiment runs"
+        )
+}
+
+#[cfg(feature = "otel")]
+fn arg_otel_endpoint() -> Arg {
+    Arg::new(ARG_OTEL_ENDPOINT)
+        .long("otel")
+        .help(
+            "Export spans of model update phases to specified OTLP HTTP \
+            endpoint, e.g. `http://localhost:4318/v1/traces`"
+        )
+}
+
+#[cfg(any(feature = "kafka", feature = "mqtt"))]
+fn arg_event_endpoint() -> Arg {
+    Arg::new(ARG_EVENT_ENDPOINT)
+        .long("ep")
+        .help(
+            "Publish network events as JSON to specified \
+            `mqtt://host:port/topic` or `kafka://host:port/topic` endpoint"
+        )
+}
+
+#[cfg(feature = "gui")]
+fn arg_live_viewer() -> Arg {
+    Arg::new(ARG_LIVE_VIEWER)
+        .long("gui")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Play the experiment in an interactive window with rotation, \
+            zoom, playback controls and device inspection instead of \
+            writing outputs"
+        )
+}
+
+#[cfg(feature = "tui")]
+fn arg_tui_dashboard() -> Arg {
+    Arg::new(ARG_TUI_DASHBOARD)
+        .long("tui")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Show progress, counters and top infected devices in the \
+            terminal instead of logs (press `q` to stop the simulation)"
+        )
+}
+
+#[cfg(feature = "grpc")]
+fn arg_grpc_address() -> Arg {
+    Arg::new(ARG_GRPC_ADDRESS)
+        .long("grpc")
+        .value_parser(value_parser!(SocketAddr))
+        .conflicts_with(ARG_EXPERIMENT_TITLE)
+        .help(
+            "Serve the gRPC control API on specified address instead of \
+            running an experiment"
+        )
+}
+
+#[cfg(feature = "ros2")]
+fn arg_ros2_endpoint() -> Arg {
+    Arg::new(ARG_ROS2_ENDPOINT)
+        .long("ros2")
+        .help(
+            "Publish device poses, links and events as ROS 2 topics through \
+            Zenoh router or `zenoh-bridge-ros2dds` on specified endpoint \
+            (e.g. `tcp/127.0.0.1:7447`)"
+        )
+}
+
+#[cfg(feature = "rest")]
+fn arg_rest_address() -> Arg {
+    Arg::new(ARG_REST_ADDRESS)
+        .long("rest")
+        .value_parser(value_parser!(SocketAddr))
+        .conflicts_with(ARG_EXPERIMENT_TITLE)
+        .help(
+            "Serve the REST API on specified address instead of running an \
+            experiment"
+        )
+}
+
+fn arg_adaptive_stepping() -> Arg {
+    Arg::new(ARG_ADAPTIVE_STEPPING)
+        .long("adaptive")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Take steps of up to a second once all network devices are shut \
+            down and nothing else moves, which does not change results"
+        )
+}
+
+fn arg_real_time() -> Arg {
+    Arg::new(ARG_REAL_TIME)
+        .long("realtime")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Keep simulation time aligned with wall-clock time scaled by the \
+            playback speed"
+        )
+}
+
+fn arg_playback_speed() -> Arg {
+    Arg::new(ARG_PLAYBACK_SPEED)
+        .long("speed")
+        .value_parser(positive_f32)
+        .default_value(DEFAULT_PLAYBACK_SPEED)
+        .help(
+            "Set playback speed multiplier of real-time pacing and GIF \
+            frames (positive float)"
+        )
+}
+
+fn arg_sitl_address() -> Arg {
+    Arg::new(ARG_SITL_ADDRESS)
+        .long("sitl")
+        .value_parser(value_parser!(SocketAddr))
+        .requires(ARG_SITL_DEVICE)
+        .help(
+            "Receive positions of SITL-driven devices as JSON UDP datagrams \
+            `{\"device_id\":N,\"x\":..,\"y\":..,\"z\":..}` on specified \
+            address"
+        )
+}
+
+fn arg_sitl_device() -> Arg {
+    Arg::new(ARG_SITL_DEVICE)
+        .long("sitl-device")
+        .value_parser(value_parser!(usize))
+        .action(ArgAction::Append)
+        .requires(ARG_SITL_ADDRESS)
+        .help(
+            "Drive device with specified ID by positions received from SITL \
+            instead of its movement, can be repeated"
+        )
+}
+
+fn arg_scenario() -> Arg {
+    Arg::new(ARG_SCENARIO)
+        .long("scenario")
+        .value_parser(value_parser!(PathBuf))
+        .conflicts_with(ARG_JSON_INPUT)
+        .help(
+            "Give drones tasks listed in `.json` file instead of the \
+            experiment scenario"
+        )
+}
+
+fn arg_fault_plan() -> Arg {
+    Arg::new(ARG_FAULT_PLAN)
+        .long("faults")
+        .value_parser(value_parser!(PathBuf))
+        .help(
+            "Inject faults listed in `.json` file into the network model \
+            (signal drops, device freezes and edge cuts at scheduled times)"
+        )
+}
+
+fn positive_f32(value: &str) -> Result<f32, String> {
+    match value.parse::<f32>() {
+        Ok(number) if number > 0.0 => Ok(number),
+        Ok(_)                      => Err("must be positive".to_string()),
+        Err(error)                 => Err(error.to_string()),
+    }
+}
+
+fn non_negative_float(value: &str) -> Result<Float, String> {
+    match value.parse::<Float>() {
+        Ok(number) if number >= 0.0 => Ok(number),
+        Ok(_)      => Err("must not be negative".to_string()),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+fn probability(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(number) if (0.0..=1.0).contains(&number) => Ok(number),
+        Ok(_)      => Err("must be between 0.0 and 1.0".to_string()),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+fn arg_seed() -> Arg {
+    Arg::new(ARG_SEED)
+        .long("seed")
+        .env(ENV_SEED)
+        .value_parser(value_parser!(u64))
+        .help(
+            "Seed the random number generator, so the run can be reproduced \
+            (non-negative integer, random by default)"
+        )
+}
+
+fn arg_verify() -> Arg {
+    Arg::new(ARG_VERIFY)
+        .long("verify")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Replay the run from the same seed in advance and check that \
+            state checksums match on each iteration"
         )
 }
 
@@ -201,6 +1448,116 @@ fn arg_no_plot() -> Arg {
         .help("Do not render a GIF plot")
 }
 
+fn arg_render_interval() -> Arg {
+    Arg::new(ARG_RENDER_INTERVAL)
+        .long("render-every")
+        .value_parser(value_parser!(u32).range(1..))
+        .default_value(DEFAULT_RENDER_INTERVAL)
+        .help(
+            "Render only every N-th iteration into the GIF plot (positive \
+            integer)"
+        )
+}
+
+fn arg_frame_output() -> Arg {
+    Arg::new(ARG_FRAME_OUTPUT)
+        .long("frames")
+        .value_parser(value_parser!(PathBuf))
+        .help(
+            "Write rendered frames to separate files in specified directory \
+            with an index file instead of a GIF plot"
+        )
+}
+
+fn arg_frame_format() -> Arg {
+    Arg::new(ARG_FRAME_FORMAT)
+        .long("frame-format")
+        .requires(ARG_FRAME_OUTPUT)
+        .value_parser([FRAME_PNG, FRAME_SVG])
+        .default_value(DEFAULT_FRAME_FORMAT)
+        .help("Choose rendered frame file format")
+}
+
+fn arg_theme() -> Arg {
+    Arg::new(ARG_THEME)
+        .long("theme")
+        .value_parser([THEME_LIGHT, THEME_DARK, THEME_COLORBLIND])
+        .default_value(DEFAULT_THEME)
+        .help("Choose plot color theme")
+}
+
+fn arg_theme_file() -> Arg {
+    Arg::new(ARG_THEME_FILE)
+        .long("theme-file")
+        .conflicts_with(ARG_THEME)
+        .value_parser(value_parser!(PathBuf))
+        .help(
+            "Load plot colors from `.json` file (colors missing from the \
+            file are taken from \"light\" theme)"
+        )
+}
+
+fn arg_color_by_task() -> Arg {
+    Arg::new(ARG_COLOR_BY_TASK)
+        .long("color-by-task")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Color devices by their current task instead of a single color \
+            (except \"malware\" experiment)"
+        )
+}
+
+fn arg_draw_connections() -> Arg {
+    Arg::new(ARG_DRAW_CONNECTIONS)
+        .long("draw-connections")
+        .action(ArgAction::SetTrue)
+        .help("Draw control links between devices colored by signal level")
+}
+
+fn arg_draw_labels() -> Arg {
+    Arg::new(ARG_DRAW_LABELS)
+        .long("labels")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Annotate devices with their IDs, tasks, infection marks and \
+            battery levels (up to 50 devices)"
+        )
+}
+
+fn arg_draw_legend() -> Arg {
+    Arg::new(ARG_DRAW_LEGEND)
+        .long("legend")
+        .action(ArgAction::SetTrue)
+        .help("Describe device colors, attacker areas and markers in a legend")
+}
+
+fn arg_draw_velocities() -> Arg {
+    Arg::new(ARG_DRAW_VELOCITIES)
+        .long("velocities")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Draw velocity arrows as long as the distance devices cover in \
+            a second"
+        )
+}
+
+fn arg_draw_gps() -> Arg {
+    Arg::new(ARG_DRAW_GPS)
+        .long("gps")
+        .action(ArgAction::SetTrue)
+        .help("Draw GPS source and its coverage area")
+}
+
+fn arg_draw_dashboard() -> Arg {
+    Arg::new(ARG_DRAW_DASHBOARD)
+        .long("dashboard")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Draw infected drone count, mean battery level and reachable \
+            drone share over time next to the 3D scene"
+        )
+}
+
 fn arg_plot_caption() -> Arg {
     Arg::new(ARG_PLOT_CAPTION)
         .short('c')
@@ -245,6 +1602,54 @@ fn arg_camera_yaw() -> Arg {
         .help("Set camera yaw (in radians)")
 }
 
+fn arg_run_summary() -> Arg {
+    Arg::new(ARG_RUN_SUMMARY)
+        .long("summary")
+        .env(ENV_RUN_SUMMARY)
+        .value_parser(value_parser!(PathBuf))
+        .help(
+            "Write JSON summary of the run with its status, seed, crate \
+            version, config hash, start time, options, final metrics and \
+            output paths to specified file. The exit code is 0 for completed \
+            runs, 3 for stopped and 1 for failed ones"
+        )
+}
+
+fn arg_compare_topologies() -> Arg {
+    Arg::new(ARG_COMPARE_TOPOLOGIES)
+        .long("compare-topologies")
+        .value_parser(value_parser!(PathBuf))
+        .conflicts_with(ARG_JSON_INPUT)
+        .help(
+            "Run the experiment with every network topology and the same \
+            seed, without rendering and outputs, and write signal delivery, \
+            infection speed and drone survival of the runs to specified \
+            `.json` report"
+        )
+}
+
+fn arg_latency_stats() -> Arg {
+    Arg::new(ARG_LATENCY_STATS)
+        .long("latency-stats")
+        .action(ArgAction::SetTrue)
+        .help(
+            "Track creation-to-delivery latency of received signals and \
+            report its percentiles overall, per frequency and per device in \
+            the log and the run summary"
+        )
+}
+
+fn arg_log_file() -> Arg {
+    Arg::new(ARG_LOG_FILE)
+        .long("log-file")
+        .env(ENV_LOG_FILE)
+        .value_parser(value_parser!(PathBuf))
+        .help(
+            "Also write logs, including trace-level device events, to \
+            specified file rotated once it grows over 10 MiB"
+        )
+}
+
 fn arg_verbose() -> Arg {
     Arg::new(ARG_VERBOSE)
         .short('v')
@@ -1,77 +1,346 @@
+use std::collections::BTreeMap;
+use std::fs;
 use std::io::Write;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
+use std::process;
 
 use clap::ArgMatches;
+use schemars::schema_for;
+use serde_json::Value;
 use env_logger::{Builder, Target};
-use log::LevelFilter;
+use log::{error, info, warn, LevelFilter};
 
+use crate::backend::collision::CollisionPolicy;
 use crate::backend::connections::Topology;
-use crate::backend::device::SignalLossResponse;
+use crate::backend::coverage::CoverageArea;
+use crate::backend::device::{DeviceId, SignalLossResponse};
+use crate::backend::device::systems::{
+    ChannelSystem, ClockSystem, FlockingSystem, GossipSystem, IntegritySystem, 
+    MovementLimits, NavigationSystem, ReliabilitySystem, SensorSystem, 
+    ThermalSystem, VersionRange
+};
+use crate::backend::fault::{Fault, FaultInjector};
+use crate::backend::landing::LandingZone;
+use crate::backend::linkloss::{AttenuationZone, LinkLoss, Obstruction};
 use crate::backend::malware::{Malware, MalwareType};
-use crate::backend::mathphysics::{Frequency, Millisecond, Point3D};
+use crate::backend::mathphysics::{
+    Float, Frequency, Kilogram, Meter, Millisecond, Point3D
+};
+use crate::backend::networkmodel::NetworkModel;
+use crate::backend::networkmodel::formation::FormationShape;
+use crate::backend::provenance::Provenance;
+use crate::backend::random;
+use crate::backend::signal::{
+    Interference, LogDistance, Propagation, TwoRayGround
+};
+use crate::backend::task::Scenario;
+use crate::backend::weather::Weather;
 use crate::frontend::{MALWARE_INFECTION_DELAY, MALWARE_SPREAD_DELAY};
+use crate::frontend::comparison::{ComparisonReport, TopologyRun};
 use crate::frontend::config::{
-    GeneralConfig, ModelConfig, ModelPlayerConfig, RenderConfig
+    AttackerConfig, GeneralConfig, ModelConfig, ModelPlayerConfig, 
+    OutputConfig, RenderConfig, SitlConfig
+};
+use crate::frontend::examples::{
+    collision_policy, definition_schema, device_channel_system, 
+    drone_flocking_system, drone_gossip_system, drone_navigation_system, 
+    drone_sensor_system, drone_thermal_system, land_signal_loss_response, 
+    mission_signal_loss_response, template, Example, ExampleKind, 
+    CC_POSITION, DEVICE_MAX_POWER
 };
-use crate::frontend::examples::{Example, DEVICE_MAX_POWER};
+use crate::frontend::inspect::inspect;
+use crate::frontend::player::{
+    request_stop, OutputCompression, OutputFormat, SnapshotMode
+};
+#[cfg(feature = "otel")]
+use crate::frontend::otel::OtelExporter;
+#[cfg(feature = "grpc")]
+use crate::frontend::server::serve_grpc;
+#[cfg(feature = "rest")]
+use crate::frontend::server::serve_rest;
 use crate::frontend::renderer::{
-    CameraAngle, Pixel, PlottersUnit, PlotResolution, DEFAULT_AXES_RANGE, 
-    DEFAULT_DEVICE_COLORING
+    CameraAngle, DeviceColoring, FrameFormat, Pixel, PlottersUnit, 
+    PlotResolution, RenderTheme, DEFAULT_AXES_RANGE, DEFAULT_DEVICE_COLORING
+};
+use crate::frontend::summary::{
+    OutputPaths, PlayOutcome, RunStatus, RunSummary
 };
 
+use super::logfile::{RotatingLogFile, TeeLogger};
+
 
-pub const ARG_ATTACKER_RADIUS: &str  = "attacker device area radius";
-pub const ARG_CAMERA_PITCH: &str     = "camera pitch";
-pub const ARG_CAMERA_YAW: &str       = "camera yaw";
-pub const ARG_DELAY_MULTIPLIER: &str = "delay multiplier";
-pub const ARG_DRONE_COUNT: &str      = "drone count";
-pub const ARG_EXPERIMENT_TITLE: &str = "experiment title";
-pub const ARG_EW_FREQUENCY: &str     = "electronic warfare frequency";
-pub const ARG_JSON_INPUT: &str       = "json input path";
-pub const ARG_JSON_OUTPUT: &str      = "json directory output path";
-pub const ARG_MALWARE_TYPE: &str     = "malware type";
-pub const ARG_NETWORK_TOPOLOGY: &str = "network topology";
-pub const ARG_NO_PLOT: &str          = "no GIF rendering";
-pub const ARG_PLOT_CAPTION: &str     = "plot caption";
-pub const ARG_PLOT_HEIGHT: &str      = "plot height";
-pub const ARG_PLOT_WIDTH: &str       = "plot width";
-pub const ARG_SIG_LOSS_RESP: &str    = "control signal loss response"; 
-pub const ARG_SIM_TIME: &str         = "simulation time";
-pub const ARG_VERBOSE: &str          = "verbose logs";
+pub const ARG_ADAPTIVE_STEPPING: &str  = "adaptive stepping";
+pub const ARG_AGGREGATION: &str        = "aggregation interval";
+pub const ARG_ALTITUDE_LOSS: &str      = "altitude loss";
+pub const ARG_ATTACKED_BANDS: &str     = "attacked gps bands";
+pub const ARG_ATTACKER: &str           = "attacker device";
+pub const ARG_ATTACKER_RADIUS: &str    = "attacker device area radius";
+pub const ARG_ATTENUATION_ZONE: &str   = "attenuation zone";
+pub const ARG_BIT_ERRORS: &str         = "bit errors";
+pub const ARG_CAMERA_PITCH: &str       = "camera pitch";
+pub const ARG_CAMERA_YAW: &str         = "camera yaw";
+pub const ARG_CHANNEL_SWITCH: &str     = "control channel switching";
+pub const ARG_CLOCK_DRIFT: &str        = "clock drift";
+pub const ARG_COLLISION_DAMAGE: &str   = "collision damage probability";
+pub const ARG_COLLISION_DESTROY: &str  = "collision destroy probability";
+pub const ARG_COLLISIONS: &str         = "collisions";
+pub const ARG_COLOR_BY_TASK: &str      = "color devices by task";
+pub const ARG_COMPARE_TOPOLOGIES: &str = "topology comparison report path";
+pub const ARG_CONFIG_FILE: &str        = "config file path";
+pub const ARG_COVERAGE_AREA: &str      = "coverage area";
+pub const ARG_CRITICAL_BATTERY: &str   = "critical battery level";
+pub const ARG_DELAY_MULTIPLIER: &str   = "delay multiplier";
+pub const ARG_DRAW_CONNECTIONS: &str   = "draw connections";
+pub const ARG_DRAW_DASHBOARD: &str     = "draw dashboard";
+pub const ARG_DRAW_GPS: &str           = "draw gps";
+pub const ARG_DRAW_LABELS: &str        = "draw device labels";
+pub const ARG_DRAW_LEGEND: &str        = "draw legend";
+pub const ARG_DRAW_VELOCITIES: &str    = "draw velocities";
+pub const ARG_DRONE_COUNT: &str        = "drone count";
+pub const ARG_EXPERIMENT_TITLE: &str   = "experiment title";
+pub const ARG_EVENT_ENDPOINT: &str     = "event broker endpoint";
+pub const ARG_EW_FREQUENCY: &str       = "electronic warfare frequency";
+pub const ARG_FAULT_PLAN: &str         = "fault plan path";
+pub const ARG_FIRMWARE_UPDATE: &str    = "firmware update time";
+pub const ARG_FLOCKING: &str           = "drone flocking";
+pub const ARG_FOG_DENSITY: &str        = "fog density";
+pub const ARG_FORMATION: &str          = "formation shape";
+pub const ARG_FRAME_FORMAT: &str       = "frame format";
+pub const ARG_FRAME_OUTPUT: &str       = "frame directory output path";
+pub const ARG_GOSSIP: &str             = "drone gossip";
+pub const ARG_GPS_BANDS: &str          = "gps bands";
+pub const ARG_GPS_CHECK: &str          = "gps spoofing check";
+pub const ARG_GPS_MTBF: &str           = "gps receiver mtbf";
+#[cfg(feature = "grpc")]
+pub const ARG_GRPC_ADDRESS: &str       = "grpc server address";
+pub const ARG_HOME_POINT: &str         = "home point";
+pub const ARG_INTEGRITY_CHECK: &str    = "integrity check";
+pub const ARG_JSON_DELTA: &str         = "json delta output";
+pub const ARG_JSON_INPUT: &str         = "json input path";
+pub const ARG_JSON_OUTPUT: &str        = "json directory output path";
+pub const ARG_LANDING_ZONE: &str       = "landing zone";
+pub const ARG_LATENCY_STATS: &str      = "latency statistics";
+pub const ARG_LIVE_VIEWER: &str        = "live viewer";
+pub const ARG_LOG_FILE: &str           = "log file";
+pub const ARG_MALWARE_TYPE: &str       = "malware type";
+pub const ARG_MALWARE_VERSIONS: &str   = "malware target versions";
+pub const ARG_MAX_CLIMB_RATE: &str     = "max climb rate";
+pub const ARG_MODEL_PATH: &str         = "model path";
+pub const ARG_MAX_DESCENT_RATE: &str   = "max descent rate";
+pub const ARG_MAX_TURN_RATE: &str      = "max turn rate";
+pub const ARG_MOTORS_MTBF: &str        = "motors mtbf";
+pub const ARG_METRICS_OUTPUT: &str     = "metrics output path";
+pub const ARG_NETWORK_ORIGIN: &str     = "network origin";
+pub const ARG_NETWORK_TOPOLOGY: &str   = "network topology";
+pub const ARG_NO_PLOT: &str            = "no GIF rendering";
+pub const ARG_NS3_OUTPUT: &str         = "ns-3 trace directory output path";
+pub const ARG_OBSTRUCTION: &str        = "obstruction";
+#[cfg(feature = "otel")]
+pub const ARG_OTEL_ENDPOINT: &str      = "otel endpoint";
+pub const ARG_OUTPUT_COMPRESSION: &str = "output compression";
+pub const ARG_OUTPUT_FORMAT: &str      = "output format";
+pub const ARG_PAYLOAD_MASS: &str       = "payload mass";
+pub const ARG_PARQUET_OUTPUT: &str     = "parquet directory output path";
+pub const ARG_PATH_LOSS_EXPONENT: &str = "path loss exponent";
+pub const ARG_PLAYBACK_SPEED: &str     = "playback speed";
+pub const ARG_PLOT_CAPTION: &str       = "plot caption";
+pub const ARG_PLOT_HEIGHT: &str        = "plot height";
+pub const ARG_PLOT_WIDTH: &str         = "plot width";
+pub const ARG_PROCESSING_DELAY: &str   = "drone processing delay";
+pub const ARG_PROMETHEUS_ADDRESS: &str = "prometheus address";
+pub const ARG_PROPAGATION: &str        = "propagation model";
+pub const ARG_RADIO_MTBF: &str         = "radio mtbf";
+pub const ARG_REAL_TIME: &str          = "real time pacing";
+pub const ARG_RENDER_INTERVAL: &str    = "render interval";
+#[cfg(feature = "rest")]
+pub const ARG_REST_ADDRESS: &str       = "rest server address";
+pub const ARG_RAIN_RATE: &str          = "rain rate";
+pub const ARG_ROS2_ENDPOINT: &str      = "ros2 zenoh endpoint";
+pub const ARG_RTH_ALTITUDE: &str       = "return to home altitude";
+pub const ARG_RUN_SUMMARY: &str        = "run summary";
+pub const ARG_SCHEMA_FORMAT: &str      = "schema file format";
+pub const ARG_SCENARIO: &str           = "scenario path";
+pub const ARG_SEED: &str               = "random seed";
+pub const ARG_SENSOR: &str             = "drone sensor";
+pub const ARG_SIG_LOSS_RESP: &str      = "control signal loss response"; 
+pub const ARG_SIM_TIME: &str           = "simulation time";
+pub const ARG_SINR_THRESHOLD: &str     = "sinr threshold";
+pub const ARG_SITL_ADDRESS: &str       = "sitl address";
+pub const ARG_SITL_DEVICE: &str        = "sitl device";
+pub const ARG_SPAWN_SPREAD: &str       = "spawn spread";
+pub const ARG_STREAM_DELTA: &str       = "stream delta output";
+pub const ARG_STREAM_OUTPUT: &str      = "stream endpoint";
+pub const ARG_SYNC_PERIOD: &str        = "time sync period";
+pub const ARG_TEMPLATE_OUTPUT: &str    = "template output path";
+pub const ARG_THEME: &str              = "render theme";
+pub const ARG_THEME_FILE: &str         = "render theme path";
+pub const ARG_THERMAL: &str            = "thermal throttling";
+pub const ARG_TUI_DASHBOARD: &str      = "tui dashboard";
+pub const ARG_VERBOSE: &str            = "verbose logs";
+pub const ARG_VERIFY: &str             = "verify determinism";
+pub const ARG_WEATHER_GPS: &str        = "weather degrades gps";
+
+// Premade experiments run with the chosen topology or compare all of them.
+pub const GROUP_TOPOLOGY: &str = "topology choice";
 
 pub const EXP_CUSTOM: &str            = "custom";
+pub const EXP_DEFINITION: &str        = "definition";
 pub const EXP_EWD: &str               = "ewd";
 pub const EXP_GPS_SPOOFING: &str      = "gpsspoof";
 pub const EXP_MALWARE_INFECTION: &str = "malware";
 pub const EXP_MOVEMENT: &str          = "move";
+pub const EXP_REPLAY: &str            = "replay";
 pub const EXP_SIGNAL_LOSS: &str       = "signalloss";
 
+pub const ENV_JSON_OUTPUT: &str    = "DRONE_NETWORK_OUTPUT_DIR";
+pub const ENV_LOG_FILE: &str       = "DRONE_NETWORK_LOG_FILE";
+pub const ENV_METRICS_OUTPUT: &str = "DRONE_NETWORK_METRICS_OUTPUT";
+pub const ENV_RUN_SUMMARY: &str    = "DRONE_NETWORK_SUMMARY";
+pub const ENV_SEED: &str           = "DRONE_NETWORK_SEED";
+pub const ENV_SIM_TIME: &str       = "DRONE_NETWORK_SIM_TIME";
+
+pub const CMD_GENERATE: &str         = "generate";
+pub const CMD_INSPECT: &str          = "inspect";
+pub const CMD_LIST_EXPERIMENTS: &str = "list-experiments";
+pub const CMD_SCHEMA: &str           = "schema";
+
+pub const SCHEMA_DEFINITION: &str = "definition";
+pub const SCHEMA_FAULTS: &str     = "faults";
+pub const SCHEMA_MODEL: &str      = "model";
+pub const SCHEMA_SCENARIO: &str   = "scenario";
+
+pub const ATTACKER_CONTROL_JAMMING: &str = "control";
+pub const ATTACKER_GPS_JAMMING: &str     = "gps";
+pub const ATTACKER_GPS_SPOOFING: &str    = "spoof";
+
 pub const EW_CONTROL: &str = "control";
 pub const EW_GPS: &str     = "gps";
+pub const EW_BACKUP: &str  = "backup";
+
+pub const COMPRESSION_GZIP: &str = "gzip";
+pub const COMPRESSION_NONE: &str = "none";
+pub const COMPRESSION_ZSTD: &str = "zstd";
+
+pub const FORMAT_BINARY: &str = "binary";
+pub const FORMAT_JSON: &str   = "json";
+
+pub const FORMATION_GRID: &str  = "grid";
+pub const FORMATION_LINE: &str  = "line";
+pub const FORMATION_WEDGE: &str = "wedge";
+
+pub const FRAME_PNG: &str = "png";
+pub const FRAME_SVG: &str = "svg";
+
+pub const GPS_CHECK_DETECT: &str = "detect";
+pub const GPS_CHECK_REJECT: &str = "reject";
+
+pub const GPS_L1: &str = "l1";
+pub const GPS_L2: &str = "l2";
+pub const GPS_L5: &str = "l5";
 
 pub const MAL_DOS: &str       = "dos";
 pub const MAL_INDICATOR: &str = "indicator";
 
+pub const PROPAGATION_FREE_SPACE: &str   = "free-space";
+pub const PROPAGATION_LOG_DISTANCE: &str = "log-distance";
+pub const PROPAGATION_TWO_RAY: &str      = "two-ray";
+
 pub const SLR_ASCEND: &str   = "ascend";
 pub const SLR_IGNORE: &str   = "ignore";
 pub const SLR_HOVER: &str    = "hover";
-pub const SLR_RTH: &str      = "rth"; // Return to command center.
+pub const SLR_RTH: &str      = "rth"; // Return to home point.
 pub const SLR_SHUTDOWN: &str = "shutdown"; 
+pub const SLR_MISSION: &str  = "mission"; // Continue mission on its own.
+pub const SLR_LAND: &str     = "land"; // Land at the nearest landing zone.
+
+pub const THEME_COLORBLIND: &str = "colorblind";
+pub const THEME_DARK: &str       = "dark";
+pub const THEME_LIGHT: &str      = "light";
 
 pub const TOPOLOGY_MESH: &str = "mesh";
 pub const TOPOLOGY_STAR: &str = "star";
 
-pub const DEFAULT_CAMERA_PITCH: &str     = "0.15";
-pub const DEFAULT_CAMERA_YAW: &str       = "0.5";
-pub const DEFAULT_DELAY_MULTIPLIER: &str = "0.0";
-pub const DEFAULT_DRONE_COUNT: &str      = "100";
-pub const DEFAULT_PLOT_CAPTION: &str     = "";
-pub const DEFAULT_PLOT_HEIGHT: &str      = "300";
-pub const DEFAULT_PLOT_WIDTH: &str       = "400";
-pub const DEFAULT_SIM_TIME: &str         = "15000";
+pub const DEFAULT_CAMERA_PITCH: &str       = "0.15";
+pub const DEFAULT_CAMERA_YAW: &str         = "0.5";
+pub const DEFAULT_COLLISION_DAMAGE: &str   = "0.5";
+pub const DEFAULT_COLLISION_DESTROY: &str  = "0.2";
+pub const DEFAULT_DELAY_MULTIPLIER: &str   = "0.0";
+pub const DEFAULT_DRONE_COUNT: &str        = "100";
+pub const DEFAULT_FRAME_FORMAT: &str       = FRAME_PNG;
+pub const DEFAULT_GPS_BANDS: &str          = GPS_L1;
+pub const DEFAULT_OUTPUT_COMPRESSION: &str = COMPRESSION_NONE;
+pub const DEFAULT_OUTPUT_FORMAT: &str      = FORMAT_JSON;
+pub const DEFAULT_PAYLOAD_MASS: &str       = "0.0";
+pub const DEFAULT_PLAYBACK_SPEED: &str     = "1.0";
+pub const DEFAULT_PLOT_CAPTION: &str       = "";
+pub const DEFAULT_PLOT_HEIGHT: &str        = "300";
+pub const DEFAULT_PLOT_WIDTH: &str         = "400";
+pub const DEFAULT_RENDER_INTERVAL: &str    = "1";
+pub const DEFAULT_SIM_TIME: &str           = "15000";
+pub const DEFAULT_THEME: &str              = THEME_LIGHT;
 
 
 pub fn handle_arguments(matches: &ArgMatches) {
+    if let Some(generate_matches) = matches.subcommand_matches(CMD_GENERATE) {
+        if let Some(seed) = seed(matches) {
+            random::set_seed(seed);
+        }
+
+        generate(generate_matches);
+
+        return;
+    }
+
+    if let Some(schema_matches) = matches.subcommand_matches(CMD_SCHEMA) {
+        print_schema(schema_matches);
+
+        return;
+    }
+
+    if let Some(inspect_matches) = matches.subcommand_matches(CMD_INSPECT) {
+        let model_path = model_path(inspect_matches);
+
+        // Invalid models fail the command, so it can be used in scripts.
+        match inspect(&model_path) {
+            Ok(true)   => (),
+            Ok(false)  => process::exit(1),
+            Err(error) => {
+                eprintln!(
+                    "Failed to read model `{}`: {error}",
+                    model_path.display()
+                );
+                process::exit(1);
+            },
+        }
+
+        return;
+    }
+
+    #[cfg(feature = "grpc")]
+    if let Some(grpc_address) = grpc_address(matches) {
+        configure_logging(verbosity_level(matches), log_file_path(matches));
+        info!("Serving gRPC API on {grpc_address}");
+
+        if let Err(error) = serve_grpc(grpc_address) {
+            error!("{error}");
+        }
+
+        return;
+    }
+
+    #[cfg(feature = "rest")]
+    if let Some(rest_address) = rest_address(matches) {
+        configure_logging(verbosity_level(matches), log_file_path(matches));
+        info!("Serving REST API on {rest_address}");
+
+        if let Err(error) = serve_rest(rest_address, render_config(matches)) {
+            error!("{error}");
+        }
+
+        return;
+    }
+
     let Some(experiment_title) = matches.get_one::<String>(
         ARG_EXPERIMENT_TITLE
     ) else {
@@ -81,6 +350,8 @@ pub fn handle_arguments(matches: &ArgMatches) {
     let example = match experiment_title.as_str() {
         EXP_CUSTOM            =>
             Example::Custom(input_model_path(matches)),
+        EXP_DEFINITION        =>
+            Example::Definition(input_model_path(matches)),
         EXP_EWD               => 
             Example::EWD {
                 ew_frequency: ew_frequency(matches), 
@@ -96,23 +367,216 @@ pub fn handle_arguments(matches: &ArgMatches) {
                 attacker_area_radius: attacker_radius(matches),
             }, 
         EXP_MOVEMENT          => Example::Movement,
+        EXP_REPLAY            =>
+            Example::Replay(input_model_path(matches)),
         EXP_SIGNAL_LOSS       => Example::SignalLossResponse,
         _                     => return
     };
 
+    if let Some(report_path) = comparison_report_path(matches) {
+        configure_logging(verbosity_level(matches), log_file_path(matches));
+        install_stop_handler();
+        compare_topologies(matches, &example, experiment_title, report_path);
+
+        return;
+    }
+
     let model_config = match example {
-        Example::Custom(_) => ModelConfig::default(),
-        _                  => model_config(matches),
+        Example::Custom(_) | Example::Replay(_) => ModelConfig::default(),
+        _                                       => model_config(matches),
     };
     
-    configure_logging(verbosity_level(matches));
-    
-    example.execute(
-        &GeneralConfig::new(
-            model_config,
-            model_player_config(matches),
+    configure_logging(verbosity_level(matches), log_file_path(matches));
+    install_stop_handler();
+
+    // A random seed is logged too, so any run can be reproduced.
+    let seed = seed(matches).unwrap_or_else(rand::random);
+
+    random::set_seed(seed);
+    info!("Random seed: {seed}");
+
+    #[cfg(feature = "otel")]
+    let otel_exporter = install_otel_exporter(matches);
+
+    let provenance = Provenance::new(
+        experiment_title,
+        seed,
+        &chrono::Local::now().to_rfc3339(),
+        &provenance_parameters(matches)
+    );
+    let general_config = GeneralConfig::new(
+        model_config,
+        model_player_config(matches).set_provenance(Some(provenance.clone())),
+    );
+
+    // Failed runs are summarized too, with the values that are known.
+    let play_outcome = example
+        .execute(&general_config)
+        .inspect_err(|error| error!("{error}"))
+        .ok();
+    let status = play_outcome
+        .as_ref()
+        .map_or(RunStatus::Failed, PlayOutcome::status);
+
+    #[cfg(feature = "otel")]
+    if let Some(otel_exporter) = otel_exporter {
+        otel_exporter.shutdown();
+    }
+
+    if let Some(summary_path) = run_summary_path(matches) {
+        let run_summary = RunSummary::new(
+            &provenance,
+            option_values(matches),
+            status
         )
+            .set_metrics(
+                play_outcome
+                    .as_ref()
+                    .and_then(PlayOutcome::final_metrics)
+                    .copied()
+            )
+            .set_epidemic(
+                play_outcome
+                    .as_ref()
+                    .and_then(PlayOutcome::epidemic_stats)
+                    .cloned()
+            )
+            .set_latency(
+                play_outcome
+                    .as_ref()
+                    .and_then(PlayOutcome::latency_report)
+                    .cloned()
+            )
+            .set_energy(
+                play_outcome
+                    .as_ref()
+                    .and_then(PlayOutcome::energy_report)
+                    .cloned()
+            )
+            .set_delivery(
+                play_outcome
+                    .as_ref()
+                    .and_then(PlayOutcome::delivery_stats)
+                    .copied()
+            )
+            .set_outputs(
+                output_paths(
+                    &general_config,
+                    play_outcome
+                        .as_ref()
+                        .and_then(PlayOutcome::render_filename),
+                    log_file_path(matches)
+                )
+            );
+
+        if let Err(error) = run_summary.write(summary_path) {
+            error!("{error}");
+        }
+    }
+
+    if status != RunStatus::Completed {
+        process::exit(status.exit_code());
+    }
+}
+
+// The first Ctrl-C stops the run after the current iteration, so its outputs
+// and summary are still written. The second one exits at once.
+fn install_stop_handler() {
+    let result = ctrlc::set_handler(|| {
+        if request_stop() {
+            process::exit(RunStatus::Stopped.exit_code());
+        }
+    });
+
+    if let Err(error) = result {
+        warn!("Failed to set Ctrl-C handler: {error}");
+    }
+}
+
+// Every topology is run with the same seed and options. Rendering and
+// outputs are disabled, since the runs would overwrite each other's files.
+fn compare_topologies(
+    matches: &ArgMatches,
+    example: &Example,
+    experiment_title: &str,
+    report_path: &Path
+) {
+    let seed = seed(matches).unwrap_or_else(rand::random);
+    let mut report = ComparisonReport::new(experiment_title, seed);
+
+    info!("Random seed: {seed}");
+
+    for topology in Topology::ALL {
+        info!("Running with {topology:?} topology");
+        random::set_seed(seed);
+
+        let general_config = GeneralConfig::new(
+            model_config(matches).set_topology(topology),
+            comparison_player_config(matches),
+        );
+        let play_outcome = example
+            .execute(&general_config)
+            .inspect_err(|error| error!("{error}"))
+            .ok();
+
+        report.add_run(
+            TopologyRun::new(
+                topology,
+                play_outcome.as_ref(),
+                drone_count(matches)
+            )
+        );
+    }
+
+    if let Err(error) = report.write(report_path) {
+        error!("{error}");
+        process::exit(RunStatus::Failed.exit_code());
+    }
+
+    if let Some(run) = report
+        .runs()
+        .iter()
+        .find(|run| run.status() != RunStatus::Completed)
+    {
+        process::exit(run.status().exit_code());
+    }
+}
+
+// Writes the template model to the output file or to stdout if it is not
+// given.
+fn generate(matches: &ArgMatches) {
+    let model_config = ModelConfig::new(
+        SignalLossResponse::default(),
+        topology(matches),
+        drone_count(matches),
+        0.0,
     );
+    let model_json = serde_json::to_string_pretty(&template(&model_config))
+        .unwrap_or_else(|error| panic!("{}", error));
+
+    match matches.get_one::<PathBuf>(ARG_TEMPLATE_OUTPUT) {
+        Some(output_path) => fs::write(output_path, model_json)
+            .unwrap_or_else(|error| panic!("{}", error)),
+        None              => println!("{model_json}"),
+    }
+}
+
+fn print_schema(matches: &ArgMatches) {
+    let schema = match matches
+        .get_one::<String>(ARG_SCHEMA_FORMAT)
+        .unwrap()
+        .as_str()
+    {
+        SCHEMA_DEFINITION => definition_schema(),
+        SCHEMA_FAULTS     => schema_for!(Vec<Fault>),
+        SCHEMA_MODEL      => schema_for!(NetworkModel),
+        SCHEMA_SCENARIO   => schema_for!(Scenario),
+        _                 => panic!("Wrong schema file format")
+    };
+    let schema_json = serde_json::to_string_pretty(&schema)
+        .unwrap_or_else(|error| panic!("{}", error));
+
+    println!("{schema_json}");
 }
 
 fn model_config(matches: &ArgMatches) -> ModelConfig {
@@ -122,6 +586,29 @@ fn model_config(matches: &ArgMatches) -> ModelConfig {
         drone_count(matches),
         delay_multiplier(matches),
     )
+        .set_reliability_system(reliability_system(matches))
+        .set_thermal_system(thermal_system(matches))
+        .set_sensor_system(sensor_system(matches))
+        .set_flocking_system(flocking_system(matches))
+        .set_navigation_system(navigation_system(matches))
+        .set_channel_system(channel_system(matches))
+        .set_gossip_system(gossip_system(matches))
+        .set_clock_system(clock_system(matches))
+        .set_sync_period(sync_period(matches))
+        .set_firmware_update_time(firmware_update_time(matches))
+        .set_processing_delay(processing_delay(matches))
+        .set_integrity_system(integrity_system(matches))
+        .set_payload_mass(payload_mass(matches))
+        .set_critical_battery_level(critical_battery_level(matches))
+        .set_formation_shape(formation_shape(matches))
+        .set_collision_policy(drone_collision_policy(matches))
+        .set_movement_limits(movement_limits(matches))
+        .set_scenario(scenario(matches))
+        .set_attackers(attackers(matches))
+        .set_network_origin(network_origin(matches))
+        .set_spawn_spread(spawn_spread(matches))
+        .set_gps_bands(gps_bands(matches, ARG_GPS_BANDS))
+        .set_attacked_gps_bands(gps_bands(matches, ARG_ATTACKED_BANDS))
 }
 
 fn model_player_config(matches: &ArgMatches) -> ModelPlayerConfig {
@@ -132,10 +619,56 @@ fn model_player_config(matches: &ArgMatches) -> ModelPlayerConfig {
     };
 
     ModelPlayerConfig::new(
-        json_output_directory(matches), 
+        output_config(matches),
         render_config,
         simulation_time(matches),
     )
+        .set_adaptive_stepping(adaptive_stepping(matches))
+        .set_real_time_pacing(real_time_pacing(matches))
+        .set_playback_speed(playback_speed(matches))
+        .set_render_interval(render_interval(matches))
+        .set_fault_injector(fault_injector(matches))
+        .set_coverage_area(coverage_area(matches))
+        .set_weather(weather(matches))
+        .set_link_loss(link_loss(matches))
+        .set_landing_zones(landing_zones(matches))
+        .set_aggregation_interval(aggregation_interval(matches))
+        .set_propagation(propagation(matches))
+        .set_interference(interference(matches))
+        .set_verify_determinism(verify_determinism(matches))
+        .set_latency_tracking(latency_tracking(matches))
+        .set_live_viewer(live_viewer(matches))
+        .set_tui_dashboard(tui_dashboard(matches))
+        .set_sitl_config(sitl_config(matches))
+}
+
+fn comparison_player_config(matches: &ArgMatches) -> ModelPlayerConfig {
+    ModelPlayerConfig::new(OutputConfig::new(), None, simulation_time(matches))
+        .set_adaptive_stepping(adaptive_stepping(matches))
+        .set_fault_injector(fault_injector(matches))
+        .set_coverage_area(coverage_area(matches))
+        .set_weather(weather(matches))
+        .set_link_loss(link_loss(matches))
+        .set_aggregation_interval(aggregation_interval(matches))
+        .set_propagation(propagation(matches))
+        .set_interference(interference(matches))
+        .set_latency_tracking(latency_tracking(matches))
+}
+
+fn output_config(matches: &ArgMatches) -> OutputConfig {
+    OutputConfig::new()
+        .set_json_output_directory(json_output_directory(matches))
+        .set_output_format(output_format(matches))
+        .set_output_compression(output_compression(matches))
+        .set_snapshot_mode(snapshot_mode(matches))
+        .set_metrics_output_path(metrics_output_path(matches))
+        .set_parquet_output_directory(parquet_output_directory(matches))
+        .set_ns3_trace_directory(ns3_trace_directory(matches))
+        .set_stream_endpoint(stream_endpoint(matches))
+        .set_stream_snapshot_mode(stream_snapshot_mode(matches))
+        .set_event_endpoint(event_endpoint(matches))
+        .set_ros2_endpoint(ros2_endpoint(matches))
+        .set_prometheus_address(prometheus_address(matches))
 }
 
 fn render_config(matches: &ArgMatches) -> RenderConfig {
@@ -144,8 +677,137 @@ fn render_config(matches: &ArgMatches) -> RenderConfig {
         plot_resolution(matches), 
         DEFAULT_AXES_RANGE,
         camera_angle(matches), 
-        DEFAULT_DEVICE_COLORING,
+        device_coloring(matches),
     )
+        .set_theme(render_theme(matches))
+        .set_draw_connections(draw_connections(matches))
+        .set_draw_labels(draw_labels(matches))
+        .set_draw_legend(draw_legend(matches))
+        .set_draw_velocities(draw_velocities(matches))
+        .set_draw_gps(draw_gps(matches))
+        .set_draw_dashboard(draw_dashboard(matches))
+        .set_frame_output(
+            frame_output_directory(matches), 
+            frame_format(matches)
+        )
+}
+
+#[must_use]
+pub fn experiment_title(example_kind: ExampleKind) -> &'static str {
+    match example_kind {
+        ExampleKind::Custom             => EXP_CUSTOM,
+        ExampleKind::Definition         => EXP_DEFINITION,
+        ExampleKind::EWD                => EXP_EWD,
+        ExampleKind::GPSSpoofing        => EXP_GPS_SPOOFING,
+        ExampleKind::MalwareInfection   => EXP_MALWARE_INFECTION,
+        ExampleKind::Movement           => EXP_MOVEMENT,
+        ExampleKind::Replay             => EXP_REPLAY,
+        ExampleKind::SignalLossResponse => EXP_SIGNAL_LOSS,
+    }
+}
+
+fn run_summary_path(matches: &ArgMatches) -> Option<&Path> {
+    matches
+        .get_one::<PathBuf>(ARG_RUN_SUMMARY)
+        .map(PathBuf::as_path)
+}
+
+// Values of all options, including the default ones. Repeated options get
+// arrays of values.
+fn option_values(matches: &ArgMatches) -> BTreeMap<String, Value> {
+    matches
+        .ids()
+        .filter_map(|id| {
+            let mut values: Vec<Value> = matches
+                .try_get_raw(id.as_str())
+                .ok()??
+                .map(|raw_value| raw_value.to_string_lossy().into())
+                .collect();
+
+            let value = if values.len() == 1 {
+                values.remove(0)
+            } else {
+                Value::Array(values)
+            };
+
+            Some((id.to_string(), value))
+        })
+        .collect()
+}
+
+// Options that only tell where results go or how they are logged.
+const OUTPUT_OPTIONS: [&str; 13] = [
+    ARG_COMPARE_TOPOLOGIES,
+    ARG_FRAME_FORMAT,
+    ARG_FRAME_OUTPUT,
+    ARG_JSON_DELTA,
+    ARG_JSON_OUTPUT,
+    ARG_LOG_FILE,
+    ARG_METRICS_OUTPUT,
+    ARG_NS3_OUTPUT,
+    ARG_OUTPUT_COMPRESSION,
+    ARG_OUTPUT_FORMAT,
+    ARG_PARQUET_OUTPUT,
+    ARG_RUN_SUMMARY,
+    ARG_VERBOSE,
+];
+
+// The seed is kept separately in the provenance and output options do not
+// change results, so runs that differ only in them get the same config
+// hash.
+fn provenance_parameters(matches: &ArgMatches) -> BTreeMap<String, Value> {
+    let mut parameters = option_values(matches);
+
+    parameters.remove(ARG_SEED);
+    for option in OUTPUT_OPTIONS {
+        parameters.remove(option);
+    }
+
+    parameters
+}
+
+fn output_paths(
+    general_config: &GeneralConfig,
+    render_filename: Option<&str>,
+    log_file_path: Option<PathBuf>,
+) -> OutputPaths {
+    let output_config = general_config
+        .model_player_config()
+        .output_config();
+    let mut output_paths = OutputPaths::new();
+
+    if let Some(directory) = output_config.json_output_directory() {
+        output_paths.insert("iterations", directory.to_path_buf());
+    }
+    if let Some(path) = output_config.metrics_output_path() {
+        output_paths.insert("metrics", path.to_path_buf());
+    }
+    if let Some(directory) = output_config.parquet_output_directory() {
+        output_paths.insert("time_series", directory.to_path_buf());
+    }
+    if let Some(directory) = output_config.ns3_trace_directory() {
+        output_paths.insert("ns3_traces", directory.to_path_buf());
+    }
+    // Frame directory if frames are written separately.
+    if let Some(render_filename) = render_filename {
+        output_paths.insert("render", PathBuf::from(render_filename));
+    }
+    if let Some(path) = log_file_path {
+        output_paths.insert("log", path);
+    }
+
+    output_paths
+}
+
+fn seed(matches: &ArgMatches) -> Option<u64> {
+    matches.get_one::<u64>(ARG_SEED).copied()
+}
+
+fn model_path(matches: &ArgMatches) -> PathBuf {
+    matches
+        .get_one::<PathBuf>(ARG_MODEL_PATH)
+        .unwrap()
+        .clone()
 }
 
 fn input_model_path(matches: &ArgMatches) -> PathBuf {
@@ -163,37 +825,71 @@ fn ew_frequency(matches: &ArgMatches) -> Frequency {
     {
         EW_CONTROL  => Frequency::Control,
         EW_GPS      => Frequency::GPS,
+        EW_BACKUP   => Frequency::Backup,
         _           => panic!("Wrong EW frequency")
     }
 }
 
-fn attacker_radius(matches: &ArgMatches) -> f32 {
+fn attacker_radius(matches: &ArgMatches) -> Meter {
     *matches
-        .get_one::<f32>(ARG_ATTACKER_RADIUS)
+        .get_one::<Meter>(ARG_ATTACKER_RADIUS)
         .unwrap()
 }
 
 fn signal_loss_response(matches: &ArgMatches) -> SignalLossResponse {
-    match matches
-        .get_one::<String>(ARG_SIG_LOSS_RESP) 
-        .unwrap()
-        .as_str() 
-    {   
+    // Not required for the signal loss experiment, which sets responses
+    // itself, and for experiments with a model from JSON input.
+    let Some(signal_loss_response) = matches.get_one::<String>(
+        ARG_SIG_LOSS_RESP
+    ) else {
+        return SignalLossResponse::default();
+    };
+
+    match signal_loss_response.as_str() {   
         SLR_ASCEND   => SignalLossResponse::Ascend,
         SLR_IGNORE   => SignalLossResponse::Ignore,
         SLR_HOVER    => SignalLossResponse::Hover,
-        SLR_RTH      => SignalLossResponse::ReturnToHome(Point3D::default()),
+        SLR_RTH      => SignalLossResponse::ReturnToHome(
+            return_to_home_point(matches)
+        ),
         SLR_SHUTDOWN => SignalLossResponse::Shutdown,
+        SLR_MISSION  => mission_signal_loss_response(),
+        SLR_LAND     => land_signal_loss_response(),
         _            => panic!("Wrong signal loss response")
     }
 }
 
+// Home point is the command center by default. Drones fly back at the return
+// altitude if it is set, otherwise at the home point altitude.
+fn return_to_home_point(matches: &ArgMatches) -> Point3D {
+    let mut home_point = matches
+        .get_one::<Point3D>(ARG_HOME_POINT)
+        .copied()
+        .unwrap_or(CC_POSITION);
+
+    if let Some(altitude) = matches.get_one::<f32>(ARG_RTH_ALTITUDE) {
+        home_point.z = Float::from(*altitude);
+    }
+
+    home_point
+}
+
+fn network_origin(matches: &ArgMatches) -> Option<Point3D> {
+    matches.get_one::<Point3D>(ARG_NETWORK_ORIGIN).copied()
+}
+
+fn spawn_spread(matches: &ArgMatches) -> Option<Point3D> {
+    matches.get_one::<Point3D>(ARG_SPAWN_SPREAD).copied()
+}
+
 fn topology(matches: &ArgMatches) -> Topology {
-    match matches
-        .get_one::<String>(ARG_NETWORK_TOPOLOGY)
-        .unwrap()
-        .as_str()
-    {
+    // Not allowed for the definition experiment, which may set the topology
+    // in its file.
+    let Some(topology) = matches.get_one::<String>(ARG_NETWORK_TOPOLOGY) else {
+        return Topology::default();
+    };
+
+    match topology.as_str() {
         TOPOLOGY_STAR => Topology::Star,
         TOPOLOGY_MESH => Topology::Mesh,
         _             => panic!("Wrong topology")
@@ -212,6 +908,177 @@ fn delay_multiplier(matches: &ArgMatches) -> f32 {
         .unwrap()
 }
 
+fn reliability_system(matches: &ArgMatches) -> ReliabilitySystem {
+    ReliabilitySystem::new(
+        matches.get_one::<Millisecond>(ARG_MOTORS_MTBF).copied(),
+        matches.get_one::<Millisecond>(ARG_RADIO_MTBF).copied(),
+        matches.get_one::<Millisecond>(ARG_GPS_MTBF).copied(),
+    )
+}
+
+fn payload_mass(matches: &ArgMatches) -> Kilogram {
+    *matches
+        .get_one::<Kilogram>(ARG_PAYLOAD_MASS)
+        .unwrap()
+}
+
+fn critical_battery_level(matches: &ArgMatches) -> f32 {
+    matches
+        .get_one::<f32>(ARG_CRITICAL_BATTERY)
+        .copied()
+        .unwrap_or_default()
+}
+
+fn formation_shape(matches: &ArgMatches) -> Option<FormationShape> {
+    let formation_shape = match matches
+        .get_one::<String>(ARG_FORMATION)?
+        .as_str()
+    {
+        FORMATION_GRID  => FormationShape::Grid,
+        FORMATION_LINE  => FormationShape::Line,
+        FORMATION_WEDGE => FormationShape::Wedge,
+        _               => panic!("Wrong formation shape"),
+    };
+
+    Some(formation_shape)
+}
+
+fn navigation_system(matches: &ArgMatches) -> NavigationSystem {
+    match matches
+        .get_one::<String>(ARG_GPS_CHECK)
+        .map(String::as_str)
+    {
+        Some(GPS_CHECK_DETECT) => drone_navigation_system(false),
+        Some(GPS_CHECK_REJECT) => drone_navigation_system(true),
+        Some(_)                => panic!("Wrong GPS spoofing check"),
+        None                   => NavigationSystem::default(),
+    }
+}
+
+fn gps_bands(matches: &ArgMatches, arg: &str) -> Vec<Frequency> {
+    let mut bands: Vec<Frequency> = matches
+        .get_many::<String>(arg)
+        .unwrap()
+        .map(|band| match band.as_str() {
+            GPS_L1 => Frequency::GPS,
+            GPS_L2 => Frequency::GPSL2,
+            GPS_L5 => Frequency::GPSL5,
+            _      => panic!("Wrong GPS band"),
+        })
+        .collect();
+
+    bands.sort_unstable();
+    bands.dedup();
+
+    bands
+}
+
+fn thermal_system(matches: &ArgMatches) -> ThermalSystem {
+    if *matches.get_one::<bool>(ARG_THERMAL).unwrap() {
+        drone_thermal_system()
+    } else {
+        ThermalSystem::default()
+    }
+}
+
+fn sensor_system(matches: &ArgMatches) -> SensorSystem {
+    if *matches.get_one::<bool>(ARG_SENSOR).unwrap() {
+        drone_sensor_system()
+    } else {
+        SensorSystem::default()
+    }
+}
+
+fn flocking_system(matches: &ArgMatches) -> FlockingSystem {
+    if *matches.get_one::<bool>(ARG_FLOCKING).unwrap() {
+        drone_flocking_system()
+    } else {
+        FlockingSystem::default()
+    }
+}
+
+fn channel_system(matches: &ArgMatches) -> ChannelSystem {
+    if *matches.get_one::<bool>(ARG_CHANNEL_SWITCH).unwrap() {
+        device_channel_system()
+    } else {
+        ChannelSystem::default()
+    }
+}
+
+fn gossip_system(matches: &ArgMatches) -> GossipSystem {
+    if *matches.get_one::<bool>(ARG_GOSSIP).unwrap() {
+        drone_gossip_system()
+    } else {
+        GossipSystem::default()
+    }
+}
+
+fn clock_system(matches: &ArgMatches) -> ClockSystem {
+    match matches.get_one::<f32>(ARG_CLOCK_DRIFT) {
+        Some(max_drift) => ClockSystem::new(*max_drift, Millisecond::ZERO),
+        None            => ClockSystem::default(),
+    }
+}
+
+fn sync_period(matches: &ArgMatches) -> Millisecond {
+    matches
+        .get_one::<Millisecond>(ARG_SYNC_PERIOD)
+        .copied()
+        .unwrap_or(Millisecond::ZERO)
+}
+
+fn firmware_update_time(matches: &ArgMatches) -> Option<Millisecond> {
+    matches.get_one::<Millisecond>(ARG_FIRMWARE_UPDATE).copied()
+}
+
+fn processing_delay(matches: &ArgMatches) -> Millisecond {
+    matches
+        .get_one::<Millisecond>(ARG_PROCESSING_DELAY)
+        .copied()
+        .unwrap_or(Millisecond::ZERO)
+}
+
+fn aggregation_interval(matches: &ArgMatches) -> Millisecond {
+    matches
+        .get_one::<Millisecond>(ARG_AGGREGATION)
+        .copied()
+        .unwrap_or(Millisecond::ZERO)
+}
+
+fn integrity_system(matches: &ArgMatches) -> IntegritySystem {
+    IntegritySystem::new(
+        *matches.get_one::<bool>(ARG_BIT_ERRORS).unwrap(),
+        *matches.get_one::<bool>(ARG_INTEGRITY_CHECK).unwrap()
+    )
+}
+
+fn drone_collision_policy(matches: &ArgMatches) -> CollisionPolicy {
+    if !*matches.get_one::<bool>(ARG_COLLISIONS).unwrap() {
+        return CollisionPolicy::default();
+    }
+
+    collision_policy(
+        *matches.get_one::<f64>(ARG_COLLISION_DESTROY).unwrap(),
+        *matches.get_one::<f64>(ARG_COLLISION_DAMAGE).unwrap(),
+    )
+}
+
+// Missing limits are not applied.
+fn movement_limits(matches: &ArgMatches) -> MovementLimits {
+    let limit = |arg| Float::from(
+        matches
+            .get_one::<f32>(arg)
+            .copied()
+            .unwrap_or_default()
+    );
+
+    MovementLimits::new(
+        limit(ARG_MAX_TURN_RATE).to_radians(),
+        limit(ARG_MAX_CLIMB_RATE),
+        limit(ARG_MAX_DESCENT_RATE),
+    )
+}
+
 fn malware(matches: &ArgMatches) -> Malware {
     let malware_type = match matches
         .get_one::<String>(ARG_MALWARE_TYPE)
@@ -226,7 +1093,8 @@ fn malware(matches: &ArgMatches) -> Malware {
     Malware::new(
         malware_type, 
         MALWARE_INFECTION_DELAY,
-        MALWARE_SPREAD_DELAY
+        MALWARE_SPREAD_DELAY,
+        matches.get_one::<VersionRange>(ARG_MALWARE_VERSIONS).copied()
     )
 }
 
@@ -236,6 +1104,140 @@ fn json_output_directory(matches: &ArgMatches) -> Option<&Path> {
         .map(|p| &**p)
 }
 
+fn output_format(matches: &ArgMatches) -> OutputFormat {
+    match matches
+        .get_one::<String>(ARG_OUTPUT_FORMAT)
+        .unwrap()
+        .as_str()
+    {
+        FORMAT_BINARY => OutputFormat::Binary,
+        FORMAT_JSON   => OutputFormat::Json,
+        _             => panic!("Wrong output format"),
+    }
+}
+
+fn output_compression(matches: &ArgMatches) -> OutputCompression {
+    match matches
+        .get_one::<String>(ARG_OUTPUT_COMPRESSION)
+        .unwrap()
+        .as_str()
+    {
+        COMPRESSION_GZIP => OutputCompression::Gzip,
+        COMPRESSION_NONE => OutputCompression::None,
+        COMPRESSION_ZSTD => OutputCompression::Zstd,
+        _                => panic!("Wrong output compression"),
+    }
+}
+
+fn snapshot_mode(matches: &ArgMatches) -> SnapshotMode {
+    if *matches.get_one::<bool>(ARG_JSON_DELTA).unwrap() {
+        SnapshotMode::Delta
+    } else {
+        SnapshotMode::Full
+    }
+}
+
+fn metrics_output_path(matches: &ArgMatches) -> Option<&Path> {
+    matches
+        .get_one::<PathBuf>(ARG_METRICS_OUTPUT)
+        .map(|p| &**p)
+}
+
+fn ns3_trace_directory(matches: &ArgMatches) -> Option<&Path> {
+    matches
+        .get_one::<PathBuf>(ARG_NS3_OUTPUT)
+        .map(|p| &**p)
+}
+
+// The argument exists only with the `parquet` feature.
+fn parquet_output_directory(matches: &ArgMatches) -> Option<&Path> {
+    matches
+        .try_get_one::<PathBuf>(ARG_PARQUET_OUTPUT)
+        .ok()
+        .flatten()
+        .map(|p| &**p)
+}
+
+fn stream_endpoint(matches: &ArgMatches) -> Option<&str> {
+    matches
+        .get_one::<String>(ARG_STREAM_OUTPUT)
+        .map(String::as_str)
+}
+
+fn stream_snapshot_mode(matches: &ArgMatches) -> SnapshotMode {
+    if *matches.get_one::<bool>(ARG_STREAM_DELTA).unwrap() {
+        SnapshotMode::Delta
+    } else {
+        SnapshotMode::Full
+    }
+}
+
+fn prometheus_address(matches: &ArgMatches) -> Option<SocketAddr> {
+    matches
+        .get_one::<SocketAddr>(ARG_PROMETHEUS_ADDRESS)
+        .copied()
+}
+
+#[cfg(feature = "otel")]
+fn install_otel_exporter(matches: &ArgMatches) -> Option<OtelExporter> {
+    let endpoint = matches.get_one::<String>(ARG_OTEL_ENDPOINT)?;
+
+    OtelExporter::install(endpoint)
+        .inspect(|_| info!("Exporting update spans to {endpoint}"))
+        .inspect_err(|error| error!("{error}"))
+        .ok()
+}
+
+#[cfg(feature = "grpc")]
+fn grpc_address(matches: &ArgMatches) -> Option<SocketAddr> {
+    matches
+        .get_one::<SocketAddr>(ARG_GRPC_ADDRESS)
+        .copied()
+}
+
+#[cfg(feature = "rest")]
+fn rest_address(matches: &ArgMatches) -> Option<SocketAddr> {
+    matches
+        .get_one::<SocketAddr>(ARG_REST_ADDRESS)
+        .copied()
+}
+
+// The argument exists only with the `gui` feature.
+fn live_viewer(matches: &ArgMatches) -> bool {
+    matches
+        .try_get_one::<bool>(ARG_LIVE_VIEWER)
+        .ok()
+        .flatten()
+        .is_some_and(|live_viewer| *live_viewer)
+}
+
+// The argument exists only with the `tui` feature.
+fn tui_dashboard(matches: &ArgMatches) -> bool {
+    matches
+        .try_get_one::<bool>(ARG_TUI_DASHBOARD)
+        .ok()
+        .flatten()
+        .is_some_and(|tui_dashboard| *tui_dashboard)
+}
+
+// The argument exists only with the `kafka` or `mqtt` feature.
+fn event_endpoint(matches: &ArgMatches) -> Option<&str> {
+    matches
+        .try_get_one::<String>(ARG_EVENT_ENDPOINT)
+        .ok()
+        .flatten()
+        .map(String::as_str)
+}
+
+// The argument exists only with the `ros2` feature.
+fn ros2_endpoint(matches: &ArgMatches) -> Option<&str> {
+    matches
+        .try_get_one::<String>(ARG_ROS2_ENDPOINT)
+        .ok()
+        .flatten()
+        .map(String::as_str)
+}
+
 fn simulation_time(matches: &ArgMatches) -> Millisecond {
     *matches
         .get_one::<Millisecond>(ARG_SIM_TIME)
@@ -248,6 +1250,248 @@ fn no_rendering(matches: &ArgMatches) -> bool {
         .unwrap()
 }
 
+fn device_coloring(matches: &ArgMatches) -> DeviceColoring {
+    if *matches.get_one::<bool>(ARG_COLOR_BY_TASK).unwrap() {
+        DeviceColoring::Task
+    } else {
+        DEFAULT_DEVICE_COLORING
+    }
+}
+
+fn render_theme(matches: &ArgMatches) -> RenderTheme {
+    if let Some(theme_path) = matches.get_one::<PathBuf>(ARG_THEME_FILE) {
+        return RenderTheme::from_json(theme_path)
+            .unwrap_or_else(|error| panic!("{}", error));
+    }
+
+    match matches
+        .get_one::<String>(ARG_THEME)
+        .unwrap()
+        .as_str()
+    {
+        THEME_COLORBLIND => RenderTheme::colorblind(),
+        THEME_DARK       => RenderTheme::dark(),
+        THEME_LIGHT      => RenderTheme::light(),
+        _                => panic!("Wrong render theme"),
+    }
+}
+
+fn draw_connections(matches: &ArgMatches) -> bool {
+    *matches
+        .get_one::<bool>(ARG_DRAW_CONNECTIONS)
+        .unwrap()
+}
+
+fn draw_labels(matches: &ArgMatches) -> bool {
+    *matches
+        .get_one::<bool>(ARG_DRAW_LABELS)
+        .unwrap()
+}
+
+fn draw_legend(matches: &ArgMatches) -> bool {
+    *matches
+        .get_one::<bool>(ARG_DRAW_LEGEND)
+        .unwrap()
+}
+
+fn draw_velocities(matches: &ArgMatches) -> bool {
+    *matches
+        .get_one::<bool>(ARG_DRAW_VELOCITIES)
+        .unwrap()
+}
+
+fn draw_gps(matches: &ArgMatches) -> bool {
+    *matches
+        .get_one::<bool>(ARG_DRAW_GPS)
+        .unwrap()
+}
+
+fn draw_dashboard(matches: &ArgMatches) -> bool {
+    *matches
+        .get_one::<bool>(ARG_DRAW_DASHBOARD)
+        .unwrap()
+}
+
+fn frame_output_directory(matches: &ArgMatches) -> Option<&Path> {
+    matches
+        .get_one::<PathBuf>(ARG_FRAME_OUTPUT)
+        .map(|p| &**p)
+}
+
+fn frame_format(matches: &ArgMatches) -> FrameFormat {
+    match matches
+        .get_one::<String>(ARG_FRAME_FORMAT)
+        .unwrap()
+        .as_str()
+    {
+        FRAME_PNG => FrameFormat::Png,
+        FRAME_SVG => FrameFormat::Svg,
+        _         => panic!("Wrong frame format"),
+    }
+}
+
+fn adaptive_stepping(matches: &ArgMatches) -> bool {
+    *matches
+        .get_one::<bool>(ARG_ADAPTIVE_STEPPING)
+        .unwrap()
+}
+
+fn attackers(matches: &ArgMatches) -> Vec<AttackerConfig> {
+    matches
+        .get_many::<AttackerConfig>(ARG_ATTACKER)
+        .map(|attackers| attackers.copied().collect())
+        .unwrap_or_default()
+}
+
+fn sitl_config(matches: &ArgMatches) -> Option<SitlConfig> {
+    let address = *matches.get_one::<SocketAddr>(ARG_SITL_ADDRESS)?;
+    let device_ids = matches
+        .get_many::<DeviceId>(ARG_SITL_DEVICE)
+        .map(|device_ids| device_ids.copied().collect())
+        .unwrap_or_default();
+
+    Some(SitlConfig::new(address, device_ids))
+}
+
+fn scenario(matches: &ArgMatches) -> Option<Scenario> {
+    matches
+        .get_one::<PathBuf>(ARG_SCENARIO)
+        .map(|scenario_path|
+            Scenario::from_json(scenario_path)
+                .unwrap_or_else(|error| panic!("{}", error))
+        )
+}
+
+fn fault_injector(matches: &ArgMatches) -> FaultInjector {
+    matches
+        .get_one::<PathBuf>(ARG_FAULT_PLAN)
+        .map(|fault_plan_path|
+            FaultInjector::from_json(fault_plan_path)
+                .unwrap_or_else(|error| panic!("{}", error))
+        )
+        .unwrap_or_default()
+}
+
+fn comparison_report_path(matches: &ArgMatches) -> Option<&Path> {
+    matches
+        .get_one::<PathBuf>(ARG_COMPARE_TOPOLOGIES)
+        .map(PathBuf::as_path)
+}
+
+fn coverage_area(matches: &ArgMatches) -> Option<CoverageArea> {
+    matches.get_one::<CoverageArea>(ARG_COVERAGE_AREA).copied()
+}
+
+fn weather(matches: &ArgMatches) -> Weather {
+    let rain_rate = matches
+        .get_one::<Float>(ARG_RAIN_RATE)
+        .copied()
+        .unwrap_or_default();
+    let fog_density = matches
+        .get_one::<Float>(ARG_FOG_DENSITY)
+        .copied()
+        .unwrap_or_default();
+
+    Weather::new(rain_rate, fog_density)
+        .set_degrades_gps(*matches.get_one::<bool>(ARG_WEATHER_GPS).unwrap())
+}
+
+// The exponent only applies to the log-distance model, whose reference
+// distance is 1 m.
+fn propagation(matches: &ArgMatches) -> Option<Propagation> {
+    let propagation = match matches
+        .get_one::<String>(ARG_PROPAGATION)?
+        .as_str()
+    {
+        PROPAGATION_FREE_SPACE   => Propagation::FreeSpace,
+        PROPAGATION_LOG_DISTANCE => {
+            let exponent = matches
+                .get_one::<Float>(ARG_PATH_LOSS_EXPONENT)
+                .copied()
+                .unwrap_or_else(|| LogDistance::default().exponent());
+
+            Propagation::LogDistance(
+                LogDistance::new(exponent, Meter::new(1.0))
+            )
+        },
+        PROPAGATION_TWO_RAY      => 
+            Propagation::TwoRayGround(TwoRayGround::default()),
+        _                        => panic!("Wrong propagation model"),
+    };
+
+    Some(propagation)
+}
+
+// Interference is enabled by the SINR threshold, the noise floor is
+// defaulted.
+fn interference(matches: &ArgMatches) -> Option<Interference> {
+    matches
+        .get_one::<Float>(ARG_SINR_THRESHOLD)
+        .map(|sinr_threshold|
+            Interference::new(
+                Interference::default().noise_floor(),
+                *sinr_threshold
+            )
+        )
+}
+
+fn link_loss(matches: &ArgMatches) -> LinkLoss {
+    let obstructions = matches
+        .get_many::<Obstruction>(ARG_OBSTRUCTION)
+        .map(|obstructions| obstructions.copied().collect())
+        .unwrap_or_default();
+    let attenuation_zones = matches
+        .get_many::<AttenuationZone>(ARG_ATTENUATION_ZONE)
+        .map(|attenuation_zones| attenuation_zones.copied().collect())
+        .unwrap_or_default();
+    let altitude_loss = matches
+        .get_one::<Float>(ARG_ALTITUDE_LOSS)
+        .copied()
+        .unwrap_or_default();
+
+    LinkLoss::new()
+        .set_obstructions(obstructions)
+        .set_attenuation_zones(attenuation_zones)
+        .set_altitude_loss(altitude_loss)
+}
+
+fn landing_zones(matches: &ArgMatches) -> Vec<LandingZone> {
+    matches
+        .get_many::<LandingZone>(ARG_LANDING_ZONE)
+        .map(|landing_zones| landing_zones.copied().collect())
+        .unwrap_or_default()
+}
+
+fn real_time_pacing(matches: &ArgMatches) -> bool {
+    *matches
+        .get_one::<bool>(ARG_REAL_TIME)
+        .unwrap()
+}
+
+fn playback_speed(matches: &ArgMatches) -> f32 {
+    *matches
+        .get_one::<f32>(ARG_PLAYBACK_SPEED)
+        .unwrap()
+}
+
+fn render_interval(matches: &ArgMatches) -> u32 {
+    *matches
+        .get_one::<u32>(ARG_RENDER_INTERVAL)
+        .unwrap()
+}
+
+fn verify_determinism(matches: &ArgMatches) -> bool {
+    *matches
+        .get_one::<bool>(ARG_VERIFY)
+        .unwrap()
+}
+
+fn latency_tracking(matches: &ArgMatches) -> bool {
+    *matches
+        .get_one::<bool>(ARG_LATENCY_STATS)
+        .unwrap()
+}
+
 fn plot_caption(matches: &ArgMatches) -> &str {
     matches
         .get_one::<String>(ARG_PLOT_CAPTION)
@@ -276,26 +1520,64 @@ fn camera_angle(matches: &ArgMatches) -> CameraAngle {
     CameraAngle::new(camera_pitch, camera_yaw)
 }
 
+// Logs would overwrite the dashboard, so they are disabled with it.
 fn verbosity_level(matches: &ArgMatches) -> LevelFilter {
-    if *matches.get_one::<bool>(ARG_VERBOSE).unwrap() {
+    if tui_dashboard(matches) {
+        LevelFilter::Off
+    } else if *matches.get_one::<bool>(ARG_VERBOSE).unwrap() {
         LevelFilter::Trace
     } else {
         LevelFilter::Info
     }
 }
 
-fn configure_logging(filter: LevelFilter) {
-    Builder::new()
-        .format(|buf, record| 
-            writeln!(
-                buf,
-                "{} {} - {}", 
-                chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
-                record.level(), 
-                record.args()
-            )
+fn log_file_path(matches: &ArgMatches) -> Option<PathBuf> {
+    matches.get_one::<PathBuf>(ARG_LOG_FILE).cloned()
+}
+
+fn log_builder() -> Builder {
+    let mut builder = Builder::new();
+
+    builder.format(|buf, record| 
+        writeln!(
+            buf,
+            "{} {} - {}", 
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            record.level(), 
+            record.args()
         )
+    );
+
+    builder
+}
+
+// The log file gets every record regardless of the console level, so a
+// verbose run can be studied later without flooding the console.
+fn configure_logging(filter: LevelFilter, log_file_path: Option<PathBuf>) {
+    let console_logger = log_builder()
         .filter(None, filter)
         .target(Target::Stdout)
-        .init();
+        .build();
+
+    let Some(log_file_path) = log_file_path else {
+        log::set_max_level(console_logger.filter());
+        log::set_boxed_logger(Box::new(console_logger))
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        return;
+    };
+
+    let log_file = RotatingLogFile::open(&log_file_path)
+        .unwrap_or_else(|error| panic!("Failed to open log file: {}", error));
+    let file_logger = log_builder()
+        .filter(None, LevelFilter::Info)
+        .filter(Some(env!("CARGO_CRATE_NAME")), LevelFilter::Trace)
+        .target(Target::Pipe(Box::new(log_file)))
+        .build();
+
+    log::set_max_level(LevelFilter::Trace);
+    log::set_boxed_logger(
+        Box::new(TeeLogger::new(console_logger, file_logger))
+    )
+        .unwrap_or_else(|error| panic!("{}", error));
 }
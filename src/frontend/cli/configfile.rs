@@ -0,0 +1,176 @@
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{Arg, Command};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use super::args::ARG_CONFIG_FILE;
+
+
+const CONFIG_FILE_OPTION: &str = "--config";
+
+
+#[derive(Debug, Error)]
+pub enum ConfigFileError {
+    #[error("Failed to read config file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to deserialize TOML config file: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Failed to deserialize YAML config file: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("Unsupported config file extension `{0}`, expected toml or yaml")]
+    UnsupportedFormat(String),
+    #[error("Unknown config file option `{0}`")]
+    UnknownOption(String),
+    #[error("Invalid value of config file option `{0}`")]
+    InvalidValue(String),
+}
+
+
+// The config file path is looked up before parsing, since the file provides
+// values for the other arguments.
+#[must_use]
+pub fn config_file_path(arguments: &[OsString]) -> Option<PathBuf> {
+    arguments
+        .iter()
+        .enumerate()
+        .find_map(|(index, argument)| {
+            let argument = argument.to_str()?;
+
+            if argument == CONFIG_FILE_OPTION {
+                return arguments.get(index + 1).map(PathBuf::from);
+            }
+
+            argument
+                .strip_prefix(CONFIG_FILE_OPTION)?
+                .strip_prefix('=')
+                .map(PathBuf::from)
+        })
+}
+
+// Converts config file options into command line arguments of `command`, so
+// they are validated the same way. Keys are long option names (or short ones
+// if there is no long name) without dashes. Tables only group options, their
+// names are ignored.
+/// # Errors
+///
+/// Will return `Err` if the file can not be read or deserialized, or if it
+/// contains an option `command` does not have.
+pub fn config_file_arguments(
+    config_path: &Path,
+    command: &Command
+) -> Result<Vec<OsString>, ConfigFileError> {
+    let config_string = fs::read_to_string(config_path)?;
+    let extension = config_path
+        .extension()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default();
+
+    let options: Value = match extension {
+        "toml"         => toml::from_str(&config_string)?,
+        "yaml" | "yml" => serde_yaml::from_str(&config_string)?,
+        _              => return Err(
+            ConfigFileError::UnsupportedFormat(extension.to_string())
+        ),
+    };
+
+    let mut arguments = Vec::new();
+
+    match options {
+        Value::Object(options) => push_arguments(
+            &options,
+            command,
+            &mut arguments
+        )?,
+        // Empty YAML file.
+        Value::Null            => (),
+        _                      => return Err(
+            ConfigFileError::InvalidValue(config_path.display().to_string())
+        ),
+    }
+
+    Ok(arguments)
+}
+
+fn push_arguments(
+    options: &Map<String, Value>,
+    command: &Command,
+    arguments: &mut Vec<OsString>,
+) -> Result<(), ConfigFileError> {
+    for (key, value) in options {
+        if let Value::Object(group) = value {
+            push_arguments(group, command, arguments)?;
+            continue;
+        }
+
+        let (arg, option) = find_option(command, key)
+            .ok_or_else(|| ConfigFileError::UnknownOption(key.clone()))?;
+        let takes_value = arg.get_action().takes_values();
+        let invalid_value = || ConfigFileError::InvalidValue(key.clone());
+
+        // Environment variables override the config file, but not the
+        // command line.
+        if arg.get_env().is_some_and(|env| env::var_os(env).is_some()) {
+            continue;
+        }
+
+        match value {
+            // Flags can only be set, so `false` keeps the default.
+            Value::Bool(flag) if !takes_value => if *flag {
+                arguments.push(option.into());
+            },
+            // Repeated options.
+            Value::Array(values) if takes_value => for value in values {
+                push_option_value(&option, value, arguments)
+                    .ok_or_else(invalid_value)?;
+            },
+            _ if takes_value => push_option_value(&option, value, arguments)
+                .ok_or_else(invalid_value)?,
+            _ => return Err(invalid_value()),
+        }
+    }
+
+    Ok(())
+}
+
+fn push_option_value(
+    option: &str,
+    value: &Value,
+    arguments: &mut Vec<OsString>,
+) -> Option<()> {
+    let value = match value {
+        Value::String(string) => string.clone(),
+        Value::Number(number) => number.to_string(),
+        _                     => return None,
+    };
+
+    arguments.push(option.into());
+    arguments.push(value.into());
+
+    Some(())
+}
+
+// Returns the argument with its option with dashes.
+fn find_option<'a>(
+    command: &'a Command,
+    key: &str
+) -> Option<(&'a Arg, String)> {
+    command
+        .get_arguments()
+        .filter(|arg| arg.get_id() != ARG_CONFIG_FILE)
+        .find_map(|arg| {
+            if arg.get_long() == Some(key) {
+                Some((arg, format!("--{key}")))
+            } else if arg
+                .get_short()
+                .is_some_and(|short| key == short.to_string())
+            {
+                Some((arg, format!("-{key}")))
+            } else {
+                None
+            }
+        })
+}
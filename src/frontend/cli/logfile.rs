@@ -0,0 +1,111 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use env_logger::Logger;
+use log::{Log, Metadata, Record};
+
+
+// The file is rotated once it grows over `MAX_LOG_FILE_SIZE` bytes. Rotated
+// files get numeric suffixes, the oldest ones above `MAX_ROTATED_LOG_FILES`
+// are removed.
+const MAX_LOG_FILE_SIZE: u64     = 10 * 1024 * 1024;
+const MAX_ROTATED_LOG_FILES: u32 = 5;
+
+
+pub struct RotatingLogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingLogFile {
+    /// # Errors
+    ///
+    /// Will return `Err` if the file can not be opened.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self { path: path.to_path_buf(), file, size })
+    }
+
+    fn rotated_path(&self, index: u32) -> PathBuf {
+        let mut rotated_path = self.path.clone().into_os_string();
+        rotated_path.push(format!(".{index}"));
+
+        PathBuf::from(rotated_path)
+    }
+
+    // `log` becomes `log.1`, `log.1` becomes `log.2` and so on.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        for index in (1..MAX_ROTATED_LOG_FILES).rev() {
+            let rotated_path = self.rotated_path(index);
+
+            if rotated_path.exists() {
+                fs::rename(&rotated_path, self.rotated_path(index + 1))?;
+            }
+        }
+
+        fs::rename(&self.path, self.rotated_path(1))?;
+
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > MAX_LOG_FILE_SIZE {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+
+// Passes records to both loggers, so the console and the log file can have
+// different levels.
+pub struct TeeLogger {
+    console_logger: Logger,
+    file_logger: Logger,
+}
+
+impl TeeLogger {
+    #[must_use]
+    pub fn new(console_logger: Logger, file_logger: Logger) -> Self {
+        Self { console_logger, file_logger }
+    }
+}
+
+impl Log for TeeLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.console_logger.enabled(metadata)
+            || self.file_logger.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        self.console_logger.log(record);
+        self.file_logger.log(record);
+    }
+
+    fn flush(&self) {
+        self.console_logger.flush();
+        self.file_logger.flush();
+    }
+}
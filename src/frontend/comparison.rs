@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::backend::connections::Topology;
+use crate::backend::mathphysics::{millis_to_secs, Millisecond};
+
+use super::summary::{PlayOutcome, RunStatus};
+
+
+#[derive(Debug, Error)]
+pub enum ComparisonReportError {
+    #[error("Failed to write comparison report: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to serialize comparison report: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+
+// Outcome of the experiment with one topology. Values are missing if the run
+// failed or they are not defined, e.g. infection values for runs without
+// infected drones.
+#[derive(Clone, Debug, Serialize)]
+pub struct TopologyRun {
+    topology: Topology,
+    status: RunStatus,
+    // Share of signals other than noise that reached devices and were
+    // received.
+    delivery_rate: Option<f32>,
+    // Share of drones that are not shut down at the end of the run.
+    survival_rate: Option<f32>,
+    infected_count: Option<usize>,
+    // Drones infected per second until the last infection.
+    infection_rate: Option<f32>,
+    time_to_full_compromise: Option<Millisecond>,
+    reproduction_number: Option<f32>,
+}
+
+impl TopologyRun {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(
+        topology: Topology,
+        play_outcome: Option<&PlayOutcome>,
+        drone_count: usize
+    ) -> Self {
+        let status = play_outcome
+            .map_or(RunStatus::Failed, PlayOutcome::status);
+        let epidemic_stats = play_outcome
+            .and_then(PlayOutcome::epidemic_stats);
+        let infection_rate = epidemic_stats
+            .and_then(|epidemic_stats| epidemic_stats.infection_curve().last())
+            .filter(|(time, _)| *time > Millisecond::ZERO)
+            .map(|(time, infected_count)|
+                *infected_count as f32 / millis_to_secs(*time)
+            );
+
+        Self {
+            topology,
+            status,
+            delivery_rate: play_outcome
+                .and_then(PlayOutcome::delivery_stats)
+                .and_then(|delivery_stats| delivery_stats.delivery_rate()),
+            survival_rate: play_outcome
+                .and_then(PlayOutcome::final_metrics)
+                .filter(|_| drone_count > 0)
+                .map(|metrics|
+                    metrics.alive_count() as f32 / drone_count as f32
+                ),
+            infected_count: epidemic_stats.map(|epidemic_stats|
+                epidemic_stats.infected_count()
+            ),
+            infection_rate,
+            time_to_full_compromise: epidemic_stats.and_then(|epidemic_stats|
+                epidemic_stats.time_to_full_compromise()
+            ),
+            reproduction_number: epidemic_stats.and_then(|epidemic_stats|
+                epidemic_stats.reproduction_number()
+            ),
+        }
+    }
+
+    #[must_use]
+    pub fn status(&self) -> RunStatus {
+        self.status
+    }
+}
+
+
+// Runs of the same experiment with every topology and the same seed.
+#[derive(Clone, Debug, Serialize)]
+pub struct ComparisonReport {
+    experiment: String,
+    seed: u64,
+    runs: Vec<TopologyRun>,
+}
+
+impl ComparisonReport {
+    #[must_use]
+    pub fn new(experiment: &str, seed: u64) -> Self {
+        Self {
+            experiment: experiment.to_string(),
+            seed,
+            runs: Vec::new(),
+        }
+    }
+
+    pub fn add_run(&mut self, run: TopologyRun) {
+        self.runs.push(run);
+    }
+
+    #[must_use]
+    pub fn runs(&self) -> &[TopologyRun] {
+        &self.runs
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the report can not be serialized or written.
+    pub fn write(
+        &self,
+        report_path: &Path
+    ) -> Result<(), ComparisonReportError> {
+        if let Some(parent) = report_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(report_path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+}
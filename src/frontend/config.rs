@@ -1,14 +1,39 @@
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 
+use crate::backend::ITERATION_TIME;
+use crate::backend::collision::CollisionPolicy;
 use crate::backend::connections::Topology;
-use crate::backend::device::SignalLossResponse;
-use crate::backend::mathphysics::Millisecond;
+use crate::backend::coverage::CoverageArea;
+use crate::backend::device::{DeviceId, SignalLossResponse};
+use crate::backend::device::systems::{
+    ChannelSystem, ClockSystem, FlockingSystem, GossipSystem, IntegritySystem, 
+    MovementLimits, NavigationSystem, ReliabilitySystem, SensorSystem, 
+    ThermalSystem
+};
+use crate::backend::fault::FaultInjector;
+use crate::backend::landing::LandingZone;
+use crate::backend::linkloss::LinkLoss;
+use crate::backend::mathphysics::{
+    Frequency, Kilogram, Meter, Millisecond, Point3D
+};
+use crate::backend::networkmodel::formation::FormationShape;
+use crate::backend::provenance::Provenance;
+use crate::backend::signal::{Interference, Propagation};
+use crate::backend::task::Scenario;
+use crate::backend::weather::Weather;
 
+use crate::frontend::player::{OutputCompression, OutputFormat, SnapshotMode};
 use crate::frontend::renderer::{
-    Axes3DRanges, CameraAngle, DeviceColoring, PlotResolution
+    Axes3DRanges, CameraAngle, DeviceColoring, FrameFormat, PlotResolution,
+    RenderTheme
 };
 
 
+// Milliseconds, a hundredth of a second.
+const MIN_FRAME_DELAY: u32 = 10;
+
+
 pub struct GeneralConfig {
     model_config: ModelConfig,
     model_player_config: ModelPlayerConfig,
@@ -38,12 +63,44 @@ impl GeneralConfig {
 }
 
 
-#[derive(Default)]
 pub struct ModelConfig {
     signal_loss_response: SignalLossResponse,
     topology: Topology,
     drone_count: usize,
     delay_multiplier: f32,
+    reliability_system: ReliabilitySystem,
+    thermal_system: ThermalSystem,
+    sensor_system: SensorSystem,
+    flocking_system: FlockingSystem,
+    navigation_system: NavigationSystem,
+    channel_system: ChannelSystem,
+    gossip_system: GossipSystem,
+    clock_system: ClockSystem,
+    // Period of command center time beacons, 0 if they are not sent.
+    sync_period: Millisecond,
+    firmware_update_time: Option<Millisecond>,
+    processing_delay: Millisecond,
+    integrity_system: IntegritySystem,
+    payload_mass: Kilogram,
+    // Battery level drones land at, 0.0 if they do not.
+    critical_battery_level: f32,
+    formation_shape: Option<FormationShape>,
+    collision_policy: CollisionPolicy,
+    movement_limits: MovementLimits,
+    scenario: Option<Scenario>,
+    attackers: Vec<AttackerConfig>,
+    network_origin: Option<Point3D>,
+    spawn_spread: Option<Point3D>,
+    // GPS bands drone receivers listen on.
+    gps_bands: Vec<Frequency>,
+    // GPS bands jammed or spoofed by GPS attackers.
+    attacked_gps_bands: Vec<Frequency>,
+}
+
+impl Default for ModelConfig {
+    fn default() -> Self {
+        Self::new(SignalLossResponse::default(), Topology::default(), 0, 0.0)
+    }
 }
 
 impl ModelConfig {
@@ -59,9 +116,223 @@ impl ModelConfig {
             topology,
             drone_count,
             delay_multiplier,
+            reliability_system: ReliabilitySystem::default(),
+            thermal_system: ThermalSystem::default(),
+            sensor_system: SensorSystem::default(),
+            flocking_system: FlockingSystem::default(),
+            navigation_system: NavigationSystem::default(),
+            channel_system: ChannelSystem::default(),
+            gossip_system: GossipSystem::default(),
+            clock_system: ClockSystem::default(),
+            sync_period: Millisecond::ZERO,
+            firmware_update_time: None,
+            processing_delay: Millisecond::ZERO,
+            integrity_system: IntegritySystem::default(),
+            payload_mass: 0.0,
+            critical_battery_level: 0.0,
+            formation_shape: None,
+            collision_policy: CollisionPolicy::default(),
+            movement_limits: MovementLimits::default(),
+            scenario: None,
+            attackers: Vec::new(),
+            network_origin: None,
+            spawn_spread: None,
+            gps_bands: vec![Frequency::GPS],
+            attacked_gps_bands: vec![Frequency::GPS],
         }
     }
 
+    #[must_use]
+    pub fn set_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    // Drones get the reliability, thermal, sensor, flocking and navigation 
+    // systems and carry the payload.
+    #[must_use]
+    pub fn set_reliability_system(
+        mut self,
+        reliability_system: ReliabilitySystem
+    ) -> Self {
+        self.reliability_system = reliability_system;
+        self
+    }
+
+    #[must_use]
+    pub fn set_thermal_system(mut self, thermal_system: ThermalSystem) -> Self {
+        self.thermal_system = thermal_system;
+        self
+    }
+
+    #[must_use]
+    pub fn set_sensor_system(mut self, sensor_system: SensorSystem) -> Self {
+        self.sensor_system = sensor_system;
+        self
+    }
+
+    #[must_use]
+    pub fn set_flocking_system(
+        mut self, 
+        flocking_system: FlockingSystem
+    ) -> Self {
+        self.flocking_system = flocking_system;
+        self
+    }
+
+    #[must_use]
+    pub fn set_navigation_system(
+        mut self, 
+        navigation_system: NavigationSystem
+    ) -> Self {
+        self.navigation_system = navigation_system;
+        self
+    }
+
+    // The command center gets the channel system too, so it can follow
+    // drones to the backup frequency.
+    #[must_use]
+    pub fn set_channel_system(mut self, channel_system: ChannelSystem) -> Self {
+        self.channel_system = channel_system;
+        self
+    }
+
+    #[must_use]
+    pub fn set_gossip_system(mut self, gossip_system: GossipSystem) -> Self {
+        self.gossip_system = gossip_system;
+        self
+    }
+
+    #[must_use]
+    pub fn set_clock_system(mut self, clock_system: ClockSystem) -> Self {
+        self.clock_system = clock_system;
+        self
+    }
+
+    #[must_use]
+    pub fn set_sync_period(mut self, sync_period: Millisecond) -> Self {
+        self.sync_period = sync_period;
+        self
+    }
+
+    #[must_use]
+    pub fn set_firmware_update_time(
+        mut self,
+        firmware_update_time: Option<Millisecond>
+    ) -> Self {
+        self.firmware_update_time = firmware_update_time;
+        self
+    }
+
+    #[must_use]
+    pub fn set_processing_delay(
+        mut self,
+        processing_delay: Millisecond
+    ) -> Self {
+        self.processing_delay = processing_delay;
+        self
+    }
+
+    // The command center gets the integrity system too, as it receives
+    // recon records from drones.
+    #[must_use]
+    pub fn set_integrity_system(
+        mut self,
+        integrity_system: IntegritySystem
+    ) -> Self {
+        self.integrity_system = integrity_system;
+        self
+    }
+
+    #[must_use]
+    pub fn set_payload_mass(mut self, payload_mass: Kilogram) -> Self {
+        self.payload_mass = payload_mass;
+        self
+    }
+
+    #[must_use]
+    pub fn set_critical_battery_level(
+        mut self,
+        critical_battery_level: f32
+    ) -> Self {
+        self.critical_battery_level = critical_battery_level;
+        self
+    }
+
+    #[must_use]
+    pub fn set_formation_shape(
+        mut self, 
+        formation_shape: Option<FormationShape>
+    ) -> Self {
+        self.formation_shape = formation_shape;
+        self
+    }
+
+    #[must_use]
+    pub fn set_collision_policy(
+        mut self, 
+        collision_policy: CollisionPolicy
+    ) -> Self {
+        self.collision_policy = collision_policy;
+        self
+    }
+
+    #[must_use]
+    pub fn set_movement_limits(
+        mut self, 
+        movement_limits: MovementLimits
+    ) -> Self {
+        self.movement_limits = movement_limits;
+        self
+    }
+
+    // Replaces the task timeline of premade experiments.
+    #[must_use]
+    pub fn set_scenario(mut self, scenario: Option<Scenario>) -> Self {
+        self.scenario = scenario;
+        self
+    }
+
+    // Adds attacker devices to the ones of premade experiments.
+    #[must_use]
+    pub fn set_attackers(mut self, attackers: Vec<AttackerConfig>) -> Self {
+        self.attackers = attackers;
+        self
+    }
+
+    // Drones of premade experiments are placed randomly in a box around the
+    // network origin that extends by the spawn spread along each axis in
+    // both directions.
+    #[must_use]
+    pub fn set_network_origin(
+        mut self,
+        network_origin: Option<Point3D>
+    ) -> Self {
+        self.network_origin = network_origin;
+        self
+    }
+
+    #[must_use]
+    pub fn set_spawn_spread(mut self, spawn_spread: Option<Point3D>) -> Self {
+        self.spawn_spread = spawn_spread;
+        self
+    }
+
+    #[must_use]
+    pub fn set_gps_bands(mut self, gps_bands: Vec<Frequency>) -> Self {
+        self.gps_bands = gps_bands;
+        self
+    }
+
+    #[must_use]
+    pub fn set_attacked_gps_bands(
+        mut self,
+        attacked_gps_bands: Vec<Frequency>
+    ) -> Self {
+        self.attacked_gps_bands = attacked_gps_bands;
+        self
+    }
+
     #[must_use]
     pub fn signal_loss_response(&self) -> SignalLossResponse {
         self.signal_loss_response
@@ -81,32 +352,396 @@ impl ModelConfig {
     pub fn delay_multiplier(&self) -> f32 {
         self.delay_multiplier
     }
+
+    #[must_use]
+    pub fn reliability_system(&self) -> &ReliabilitySystem {
+        &self.reliability_system
+    }
+
+    #[must_use]
+    pub fn thermal_system(&self) -> &ThermalSystem {
+        &self.thermal_system
+    }
+
+    #[must_use]
+    pub fn sensor_system(&self) -> &SensorSystem {
+        &self.sensor_system
+    }
+
+    #[must_use]
+    pub fn flocking_system(&self) -> &FlockingSystem {
+        &self.flocking_system
+    }
+
+    #[must_use]
+    pub fn navigation_system(&self) -> &NavigationSystem {
+        &self.navigation_system
+    }
+
+    #[must_use]
+    pub fn channel_system(&self) -> &ChannelSystem {
+        &self.channel_system
+    }
+
+    #[must_use]
+    pub fn gossip_system(&self) -> &GossipSystem {
+        &self.gossip_system
+    }
+
+    #[must_use]
+    pub fn clock_system(&self) -> &ClockSystem {
+        &self.clock_system
+    }
+
+    #[must_use]
+    pub fn sync_period(&self) -> Millisecond {
+        self.sync_period
+    }
+
+    #[must_use]
+    pub fn firmware_update_time(&self) -> Option<Millisecond> {
+        self.firmware_update_time
+    }
+
+    #[must_use]
+    pub fn processing_delay(&self) -> Millisecond {
+        self.processing_delay
+    }
+
+    #[must_use]
+    pub fn integrity_system(&self) -> &IntegritySystem {
+        &self.integrity_system
+    }
+
+    #[must_use]
+    pub fn payload_mass(&self) -> Kilogram {
+        self.payload_mass
+    }
+
+    #[must_use]
+    pub fn critical_battery_level(&self) -> f32 {
+        self.critical_battery_level
+    }
+
+    #[must_use]
+    pub fn formation_shape(&self) -> Option<FormationShape> {
+        self.formation_shape
+    }
+
+    #[must_use]
+    pub fn collision_policy(&self) -> &CollisionPolicy {
+        &self.collision_policy
+    }
+
+    #[must_use]
+    pub fn movement_limits(&self) -> MovementLimits {
+        self.movement_limits
+    }
+
+    #[must_use]
+    pub fn scenario(&self) -> Option<&Scenario> {
+        self.scenario.as_ref()
+    }
+
+    #[must_use]
+    pub fn attackers(&self) -> &[AttackerConfig] {
+        &self.attackers
+    }
+
+    #[must_use]
+    pub fn network_origin(&self) -> Option<Point3D> {
+        self.network_origin
+    }
+
+    #[must_use]
+    pub fn spawn_spread(&self) -> Option<Point3D> {
+        self.spawn_spread
+    }
+
+    #[must_use]
+    pub fn gps_bands(&self) -> &[Frequency] {
+        &self.gps_bands
+    }
+
+    #[must_use]
+    pub fn attacked_gps_bands(&self) -> &[Frequency] {
+        &self.attacked_gps_bands
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AttackerKind {
+    ControlJamming,
+    GPSJamming,
+    GPSSpoofing,
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AttackerConfig {
+    kind: AttackerKind,
+    position: Point3D,
+    area_radius: Meter,
+}
+
+impl AttackerConfig {
+    #[must_use]
+    pub fn new(
+        kind: AttackerKind,
+        position: Point3D,
+        area_radius: Meter
+    ) -> Self {
+        Self { kind, position, area_radius }
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> AttackerKind {
+        self.kind
+    }
+
+    #[must_use]
+    pub fn position(&self) -> Point3D {
+        self.position
+    }
+
+    #[must_use]
+    pub fn area_radius(&self) -> Meter {
+        self.area_radius
+    }
+}
+
+
+// Devices whose positions come from another simulator over UDP instead of
+// their movement systems.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SitlConfig {
+    address: SocketAddr,
+    device_ids: Vec<DeviceId>,
+}
+
+impl SitlConfig {
+    #[must_use]
+    pub fn new(address: SocketAddr, device_ids: Vec<DeviceId>) -> Self {
+        Self { address, device_ids }
+    }
+
+    #[must_use]
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    #[must_use]
+    pub fn device_ids(&self) -> &[DeviceId] {
+        &self.device_ids
+    }
 }
 
 
 pub struct ModelPlayerConfig {
-    json_output_directory: Option<PathBuf>,
+    output_config: OutputConfig,
     render_config: Option<RenderConfig>,
     simulation_time: Millisecond,
+    verify_determinism: bool,
+    latency_tracking: bool,
+    adaptive_stepping: bool,
+    real_time_pacing: bool,
+    playback_speed: f32,
+    render_interval: u32,
+    live_viewer: bool,
+    tui_dashboard: bool,
+    fault_injector: FaultInjector,
+    coverage_area: Option<CoverageArea>,
+    weather: Weather,
+    link_loss: LinkLoss,
+    landing_zones: Vec<LandingZone>,
+    aggregation_interval: Millisecond,
+    propagation: Option<Propagation>,
+    interference: Option<Interference>,
+    sitl_config: Option<SitlConfig>,
+    provenance: Option<Provenance>,
 }
 
 impl ModelPlayerConfig {
     #[must_use]
     pub fn new(
-        json_output_directory: Option<&Path>,
+        output_config: OutputConfig,
         render_config: Option<RenderConfig>,
         simulation_time: Millisecond,
     ) -> Self {
         Self {
-            json_output_directory: json_output_directory.map(Path::to_path_buf),
+            output_config,
             render_config,
             simulation_time,
+            verify_determinism: false,
+            latency_tracking: false,
+            adaptive_stepping: false,
+            real_time_pacing: false,
+            playback_speed: 1.0,
+            render_interval: 1,
+            live_viewer: false,
+            tui_dashboard: false,
+            fault_injector: FaultInjector::default(),
+            coverage_area: None,
+            weather: Weather::default(),
+            link_loss: LinkLoss::default(),
+            landing_zones: Vec::new(),
+            aggregation_interval: Millisecond::ZERO,
+            propagation: None,
+            interference: None,
+            sitl_config: None,
+            provenance: None,
         }
     }
+
+    #[must_use]
+    pub fn set_adaptive_stepping(mut self, adaptive_stepping: bool) -> Self {
+        self.adaptive_stepping = adaptive_stepping;
+        self
+    }
+
+    // Simulation time is kept aligned with wall-clock time scaled by the
+    // playback speed.
+    #[must_use]
+    pub fn set_real_time_pacing(mut self, real_time_pacing: bool) -> Self {
+        self.real_time_pacing = real_time_pacing;
+        self
+    }
+
+    #[must_use]
+    pub fn set_playback_speed(mut self, playback_speed: f32) -> Self {
+        self.playback_speed = playback_speed;
+        self
+    }
+
+    // Only every `render_interval`-th iteration is rendered. Zero is treated
+    // as one.
+    #[must_use]
+    pub fn set_render_interval(mut self, render_interval: u32) -> Self {
+        self.render_interval = render_interval.max(1);
+        self
+    }
+
+    // The network model is played in an interactive window instead of 
+    // writing outputs. It has an effect only with the `gui` feature.
+    #[must_use]
+    pub fn set_live_viewer(mut self, live_viewer: bool) -> Self {
+        self.live_viewer = live_viewer;
+        self
+    }
+
+    // Progress, counters and top infected devices are shown in the 
+    // terminal instead of logs. It has an effect only with the `tui` 
+    // feature.
+    #[must_use]
+    pub fn set_tui_dashboard(mut self, tui_dashboard: bool) -> Self {
+        self.tui_dashboard = tui_dashboard;
+        self
+    }
+
+    // Faults are injected into the played network model.
+    #[must_use]
+    pub fn set_fault_injector(mut self, fault_injector: FaultInjector) -> Self {
+        self.fault_injector = fault_injector;
+        self
+    }
+
+    // Overrides the coverage area of the played network model.
+    #[must_use]
+    pub fn set_coverage_area(
+        mut self,
+        coverage_area: Option<CoverageArea>
+    ) -> Self {
+        self.coverage_area = coverage_area;
+        self
+    }
+
+    // Overrides the weather of the played network model unless it is
+    // clear.
+    #[must_use]
+    pub fn set_weather(mut self, weather: Weather) -> Self {
+        self.weather = weather;
+        self
+    }
+
+    // Overrides the link losses of the played network model unless there
+    // are none.
+    #[must_use]
+    pub fn set_link_loss(mut self, link_loss: LinkLoss) -> Self {
+        self.link_loss = link_loss;
+        self
+    }
+
+    // Overrides the landing zones of the played network model unless there
+    // are none.
+    #[must_use]
+    pub fn set_landing_zones(
+        mut self,
+        landing_zones: Vec<LandingZone>
+    ) -> Self {
+        self.landing_zones = landing_zones;
+        self
+    }
+
+    // Relays of the played network model aggregate reconnaissance records
+    // unless the interval is 0.
+    #[must_use]
+    pub fn set_aggregation_interval(
+        mut self,
+        aggregation_interval: Millisecond
+    ) -> Self {
+        self.aggregation_interval = aggregation_interval;
+        self
+    }
+
+    // Overrides the propagation model of the played network model if it is
+    // set.
+    #[must_use]
+    pub fn set_propagation(mut self, propagation: Option<Propagation>) -> Self {
+        self.propagation = propagation;
+        self
+    }
+
+    // Enables interference of concurrent transmissions in the played network
+    // model if it is set.
+    #[must_use]
+    pub fn set_interference(
+        mut self,
+        interference: Option<Interference>
+    ) -> Self {
+        self.interference = interference;
+        self
+    }
+
+    #[must_use]
+    pub fn set_verify_determinism(mut self, verify_determinism: bool) -> Self {
+        self.verify_determinism = verify_determinism;
+        self
+    }
+
+    #[must_use]
+    pub fn set_latency_tracking(mut self, latency_tracking: bool) -> Self {
+        self.latency_tracking = latency_tracking;
+        self
+    }
+
+    #[must_use]
+    pub fn set_sitl_config(mut self, sitl_config: Option<SitlConfig>) -> Self {
+        self.sitl_config = sitl_config;
+        self
+    }
+
+    // The played network model gets the provenance, so it is in every
+    // snapshot, and render filenames are tagged with it.
+    #[must_use]
+    pub fn set_provenance(mut self, provenance: Option<Provenance>) -> Self {
+        self.provenance = provenance;
+        self
+    }
     
     #[must_use]
-    pub fn json_output_directory(&self) -> Option<&Path> {
-        self.json_output_directory.as_deref()
+    pub fn output_config(&self) -> &OutputConfig {
+        &self.output_config
     }
 
     #[must_use]
@@ -118,6 +753,313 @@ impl ModelPlayerConfig {
     pub fn simulation_time(&self) -> Millisecond {
         self.simulation_time
     }
+
+    #[must_use]
+    pub fn verify_determinism(&self) -> bool {
+        self.verify_determinism
+    }
+
+    #[must_use]
+    pub fn latency_tracking(&self) -> bool {
+        self.latency_tracking
+    }
+
+    #[must_use]
+    pub fn adaptive_stepping(&self) -> bool {
+        self.adaptive_stepping
+    }
+
+    #[must_use]
+    pub fn real_time_pacing(&self) -> bool {
+        self.real_time_pacing
+    }
+
+    #[must_use]
+    pub fn playback_speed(&self) -> f32 {
+        self.playback_speed
+    }
+
+    #[must_use]
+    pub fn render_interval(&self) -> u32 {
+        self.render_interval
+    }
+
+    #[must_use]
+    pub fn live_viewer(&self) -> bool {
+        self.live_viewer
+    }
+
+    #[must_use]
+    pub fn tui_dashboard(&self) -> bool {
+        self.tui_dashboard
+    }
+
+    #[must_use]
+    pub fn fault_injector(&self) -> &FaultInjector {
+        &self.fault_injector
+    }
+
+    #[must_use]
+    pub fn coverage_area(&self) -> Option<&CoverageArea> {
+        self.coverage_area.as_ref()
+    }
+
+    #[must_use]
+    pub fn weather(&self) -> &Weather {
+        &self.weather
+    }
+
+    #[must_use]
+    pub fn link_loss(&self) -> &LinkLoss {
+        &self.link_loss
+    }
+
+    #[must_use]
+    pub fn landing_zones(&self) -> &[LandingZone] {
+        &self.landing_zones
+    }
+
+    #[must_use]
+    pub fn aggregation_interval(&self) -> Millisecond {
+        self.aggregation_interval
+    }
+
+    #[must_use]
+    pub fn propagation(&self) -> Option<&Propagation> {
+        self.propagation.as_ref()
+    }
+
+    #[must_use]
+    pub fn interference(&self) -> Option<&Interference> {
+        self.interference.as_ref()
+    }
+
+    #[must_use]
+    pub fn sitl_config(&self) -> Option<&SitlConfig> {
+        self.sitl_config.as_ref()
+    }
+
+    #[must_use]
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    // Filename of a render output, which is tagged with the provenance if
+    // it is set.
+    #[must_use]
+    pub fn output_filename(&self, filename: &str) -> String {
+        self.provenance
+            .as_ref()
+            .map_or_else(
+                || filename.to_string(),
+                |provenance| provenance.tag_filename(filename)
+            )
+    }
+
+    // GIF frame delay that plays the iterations between rendered frames in
+    // `ITERATION_TIME` each scaled by the playback speed. GIF delays are 
+    // stored in hundredths of a second, so shorter delays are rounded up to 
+    // it.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn frame_delay(&self) -> u32 {
+        let frame_time = ITERATION_TIME.value() as f32
+            * self.render_interval as f32;
+        let frame_delay = (frame_time / self.playback_speed).round();
+
+        (frame_delay as u32).max(MIN_FRAME_DELAY)
+    }
+}
+
+
+// Files and other sinks the model player writes simulation data to. Every
+// sink is disabled by default.
+#[derive(Clone, Debug, Default)]
+pub struct OutputConfig {
+    json_output_directory: Option<PathBuf>,
+    output_format: OutputFormat,
+    output_compression: OutputCompression,
+    snapshot_mode: SnapshotMode,
+    metrics_output_path: Option<PathBuf>,
+    parquet_output_directory: Option<PathBuf>,
+    ns3_trace_directory: Option<PathBuf>,
+    stream_endpoint: Option<String>,
+    stream_snapshot_mode: SnapshotMode,
+    event_endpoint: Option<String>,
+    ros2_endpoint: Option<String>,
+    prometheus_address: Option<SocketAddr>,
+}
+
+impl OutputConfig {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn set_json_output_directory(
+        mut self,
+        json_output_directory: Option<&Path>
+    ) -> Self {
+        self.json_output_directory = json_output_directory
+            .map(Path::to_path_buf);
+        self
+    }
+
+    #[must_use]
+    pub fn set_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    #[must_use]
+    pub fn set_output_compression(
+        mut self,
+        output_compression: OutputCompression
+    ) -> Self {
+        self.output_compression = output_compression;
+        self
+    }
+
+    #[must_use]
+    pub fn set_snapshot_mode(mut self, snapshot_mode: SnapshotMode) -> Self {
+        self.snapshot_mode = snapshot_mode;
+        self
+    }
+
+    #[must_use]
+    pub fn set_metrics_output_path(
+        mut self,
+        metrics_output_path: Option<&Path>
+    ) -> Self {
+        self.metrics_output_path = metrics_output_path.map(Path::to_path_buf);
+        self
+    }
+
+    #[must_use]
+    pub fn set_parquet_output_directory(
+        mut self,
+        parquet_output_directory: Option<&Path>
+    ) -> Self {
+        self.parquet_output_directory = parquet_output_directory
+            .map(Path::to_path_buf);
+        self
+    }
+
+    // ns-2 mobility and ns-3 ASCII packet traces are written to the
+    // directory.
+    #[must_use]
+    pub fn set_ns3_trace_directory(
+        mut self,
+        ns3_trace_directory: Option<&Path>
+    ) -> Self {
+        self.ns3_trace_directory = ns3_trace_directory.map(Path::to_path_buf);
+        self
+    }
+
+    #[must_use]
+    pub fn set_stream_endpoint(
+        mut self,
+        stream_endpoint: Option<&str>
+    ) -> Self {
+        self.stream_endpoint = stream_endpoint.map(str::to_string);
+        self
+    }
+
+    #[must_use]
+    pub fn set_stream_snapshot_mode(
+        mut self,
+        stream_snapshot_mode: SnapshotMode
+    ) -> Self {
+        self.stream_snapshot_mode = stream_snapshot_mode;
+        self
+    }
+
+    #[must_use]
+    pub fn set_event_endpoint(mut self, event_endpoint: Option<&str>) -> Self {
+        self.event_endpoint = event_endpoint.map(str::to_string);
+        self
+    }
+
+    #[must_use]
+    pub fn set_ros2_endpoint(mut self, ros2_endpoint: Option<&str>) -> Self {
+        self.ros2_endpoint = ros2_endpoint.map(str::to_string);
+        self
+    }
+
+    // Metrics of the played model are served on `/metrics` of the address.
+    #[must_use]
+    pub fn set_prometheus_address(
+        mut self,
+        prometheus_address: Option<SocketAddr>
+    ) -> Self {
+        self.prometheus_address = prometheus_address;
+        self
+    }
+
+    #[must_use]
+    pub fn json_output_directory(&self) -> Option<&Path> {
+        self.json_output_directory.as_deref()
+    }
+
+    #[must_use]
+    pub fn output_format(&self) -> OutputFormat {
+        self.output_format
+    }
+
+    #[must_use]
+    pub fn output_compression(&self) -> OutputCompression {
+        self.output_compression
+    }
+
+    #[must_use]
+    pub fn snapshot_mode(&self) -> SnapshotMode {
+        self.snapshot_mode
+    }
+
+    #[must_use]
+    pub fn metrics_output_path(&self) -> Option<&Path> {
+        self.metrics_output_path.as_deref()
+    }
+
+    #[must_use]
+    pub fn parquet_output_directory(&self) -> Option<&Path> {
+        self.parquet_output_directory.as_deref()
+    }
+
+    #[must_use]
+    pub fn ns3_trace_directory(&self) -> Option<&Path> {
+        self.ns3_trace_directory.as_deref()
+    }
+
+    #[must_use]
+    pub fn stream_endpoint(&self) -> Option<&str> {
+        self.stream_endpoint.as_deref()
+    }
+
+    #[must_use]
+    pub fn stream_snapshot_mode(&self) -> SnapshotMode {
+        self.stream_snapshot_mode
+    }
+
+    #[must_use]
+    pub fn event_endpoint(&self) -> Option<&str> {
+        self.event_endpoint.as_deref()
+    }
+
+    #[must_use]
+    pub fn ros2_endpoint(&self) -> Option<&str> {
+        self.ros2_endpoint.as_deref()
+    }
+
+    #[must_use]
+    pub fn prometheus_address(&self) -> Option<SocketAddr> {
+        self.prometheus_address
+    }
 }
 
 
@@ -127,6 +1069,15 @@ pub struct RenderConfig {
     axes_ranges: Axes3DRanges,
     camera_angle: CameraAngle,
     device_coloring: DeviceColoring,
+    theme: RenderTheme,
+    draw_connections: bool,
+    draw_labels: bool,
+    draw_legend: bool,
+    draw_velocities: bool,
+    draw_gps: bool,
+    draw_dashboard: bool,
+    frame_directory: Option<PathBuf>,
+    frame_format: FrameFormat,
 }
 
 impl RenderConfig {
@@ -144,8 +1095,72 @@ impl RenderConfig {
             axes_ranges,
             camera_angle,
             device_coloring,
+            theme: RenderTheme::default(),
+            draw_connections: false,
+            draw_labels: false,
+            draw_legend: false,
+            draw_velocities: false,
+            draw_gps: false,
+            draw_dashboard: false,
+            frame_directory: None,
+            frame_format: FrameFormat::default(),
         }
     }
+
+    #[must_use]
+    pub fn set_theme(mut self, theme: RenderTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    #[must_use]
+    pub fn set_draw_connections(mut self, draw_connections: bool) -> Self {
+        self.draw_connections = draw_connections;
+        self
+    }
+
+    #[must_use]
+    pub fn set_draw_labels(mut self, draw_labels: bool) -> Self {
+        self.draw_labels = draw_labels;
+        self
+    }
+
+    #[must_use]
+    pub fn set_draw_legend(mut self, draw_legend: bool) -> Self {
+        self.draw_legend = draw_legend;
+        self
+    }
+
+    #[must_use]
+    pub fn set_draw_velocities(mut self, draw_velocities: bool) -> Self {
+        self.draw_velocities = draw_velocities;
+        self
+    }
+
+    #[must_use]
+    pub fn set_draw_gps(mut self, draw_gps: bool) -> Self {
+        self.draw_gps = draw_gps;
+        self
+    }
+
+    #[must_use]
+    pub fn set_draw_dashboard(mut self, draw_dashboard: bool) -> Self {
+        self.draw_dashboard = draw_dashboard;
+        self
+    }
+
+    // Frames are written into separate files in `frame_directory` instead 
+    // of a GIF file.
+    #[must_use]
+    pub fn set_frame_output(
+        mut self,
+        frame_directory: Option<&Path>,
+        frame_format: FrameFormat,
+    ) -> Self {
+        self.frame_directory = frame_directory.map(Path::to_path_buf);
+        self.frame_format = frame_format;
+        self
+    }
     
     #[must_use]
     pub fn plot_caption(&self) -> &str {
@@ -171,4 +1186,49 @@ impl RenderConfig {
     pub fn device_coloring(&self) -> DeviceColoring {
         self.device_coloring
     }
+    
+    #[must_use]
+    pub fn theme(&self) -> &RenderTheme {
+        &self.theme
+    }
+    
+    #[must_use]
+    pub fn draw_connections(&self) -> bool {
+        self.draw_connections
+    }
+    
+    #[must_use]
+    pub fn draw_labels(&self) -> bool {
+        self.draw_labels
+    }
+    
+    #[must_use]
+    pub fn draw_legend(&self) -> bool {
+        self.draw_legend
+    }
+    
+    #[must_use]
+    pub fn draw_velocities(&self) -> bool {
+        self.draw_velocities
+    }
+    
+    #[must_use]
+    pub fn draw_gps(&self) -> bool {
+        self.draw_gps
+    }
+    
+    #[must_use]
+    pub fn draw_dashboard(&self) -> bool {
+        self.draw_dashboard
+    }
+    
+    #[must_use]
+    pub fn frame_directory(&self) -> Option<&Path> {
+        self.frame_directory.as_deref()
+    }
+    
+    #[must_use]
+    pub fn frame_format(&self) -> FrameFormat {
+        self.frame_format
+    }
 }
@@ -1,27 +1,57 @@
 use std::path::PathBuf;
 
+use thiserror::Error;
+
 use crate::backend::malware::Malware;
 use crate::backend::mathphysics::{Frequency, Meter};
 
 use super::config::GeneralConfig;
+use super::player::{OutputError, PlayError};
+use super::summary::PlayOutcome;
 
 
-pub use premade::DEVICE_MAX_POWER;
+pub use definition::{definition_schema, ExperimentDefinitionError};
+pub use premade::{
+    collision_policy, device_channel_system, drone_flocking_system, 
+    drone_gossip_system, drone_navigation_system, drone_sensor_system, 
+    drone_thermal_system, land_signal_loss_response, 
+    mission_signal_loss_response, template, CC_POSITION, DEVICE_MAX_POWER
+};
 
 
 use custom::custom;
+use definition::definition;
 use premade::{
     ewd, gps_spoofing, malware_infection, movement, signal_loss_response
 };
+use replay::replay;
 
 
 mod custom;
+mod definition;
 mod premade;
+mod replay;
+
+
+#[derive(Debug, Error)]
+pub enum ExampleError {
+    #[error("Failed to read `{}`: {source}", path.display())]
+    Input {
+        path: PathBuf,
+        #[source]
+        source: OutputError,
+    },
+    #[error(transparent)]
+    Definition(#[from] ExperimentDefinitionError),
+    #[error(transparent)]
+    Play(#[from] PlayError),
+}
 
 
 #[derive(Clone)]
 pub enum Example {
     Custom(PathBuf),
+    Definition(PathBuf),
     EWD { 
         ew_frequency: Frequency, 
         ewd_area_radius: Meter
@@ -34,26 +64,109 @@ pub enum Example {
         attacker_area_radius: Meter, 
     },
     Movement,
+    Replay(PathBuf),
     SignalLossResponse,
 }
 
 impl Example {
-    pub fn execute(&self, general_config: &GeneralConfig) {
+    #[must_use]
+    pub fn kind(&self) -> ExampleKind {
         match self {
+            Self::Custom(_)               => ExampleKind::Custom,
+            Self::Definition(_)           => ExampleKind::Definition,
+            Self::EWD { .. }              => ExampleKind::EWD,
+            Self::GPSSpoofing { .. }      => ExampleKind::GPSSpoofing,
+            Self::MalwareInfection { .. } => ExampleKind::MalwareInfection,
+            Self::Movement                => ExampleKind::Movement,
+            Self::Replay(_)               => ExampleKind::Replay,
+            Self::SignalLossResponse      => ExampleKind::SignalLossResponse,
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the input files can not be read, the network
+    /// model can not be built or the run fails.
+    pub fn execute(
+        &self,
+        general_config: &GeneralConfig
+    ) -> Result<PlayOutcome, ExampleError> {
+        let play_outcome = match self {
             Self::Custom(json_path)                                   => 
-                custom(json_path, general_config.model_player_config()),
+                custom(json_path, general_config.model_player_config())?,
+            Self::Definition(definition_path)                         =>
+                definition(definition_path, general_config)?,
             Self::EWD { ew_frequency, ewd_area_radius }               => 
-                ewd(general_config, *ew_frequency, *ewd_area_radius),
+                ewd(general_config, *ew_frequency, *ewd_area_radius)?,
             Self::GPSSpoofing { spoofer_area_radius }                 => 
-                gps_spoofing(general_config, *spoofer_area_radius),
+                gps_spoofing(general_config, *spoofer_area_radius)?,
             Self::MalwareInfection { malware, attacker_area_radius, } => 
                 malware_infection(
                     general_config, 
                     *malware,
                     *attacker_area_radius,
-                ),
-            Self::Movement           => movement(general_config),
-            Self::SignalLossResponse => signal_loss_response(general_config),
+                )?,
+            Self::Movement                                            => 
+                movement(general_config)?,
+            Self::Replay(iteration_directory)                         =>
+                replay(
+                    iteration_directory,
+                    general_config.model_player_config()
+                )?,
+            Self::SignalLossResponse => signal_loss_response(general_config)?,
+        };
+
+        Ok(play_outcome)
+    }
+}
+
+
+// `Example` variants without their parameters, so the experiments can be
+// listed before any of them is configured.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExampleKind {
+    Custom,
+    Definition,
+    EWD,
+    GPSSpoofing,
+    MalwareInfection,
+    Movement,
+    Replay,
+    SignalLossResponse,
+}
+
+impl ExampleKind {
+    pub const ALL: [Self; 8] = [
+        Self::Custom,
+        Self::Definition,
+        Self::EWD,
+        Self::GPSSpoofing,
+        Self::MalwareInfection,
+        Self::Movement,
+        Self::Replay,
+        Self::SignalLossResponse,
+    ];
+
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Custom             =>
+                "Play network model deserialized from file",
+            Self::Definition         =>
+                "Build network model from experiment definition and play it",
+            Self::EWD                =>
+                "Drones fly through electronic warfare device area",
+            Self::GPSSpoofing        =>
+                "Drones fly through GPS spoofer area",
+            Self::MalwareInfection   =>
+                "Malware spreads through the network from attacker device",
+            Self::Movement           =>
+                "Drones fly to destination without attackers",
+            Self::Replay             =>
+                "Replay iteration files of previous run",
+            Self::SignalLossResponse =>
+                "Drone groups with different control signal loss responses \
+                lose connection to command center",
         }
     }
 }
@@ -1,37 +1,53 @@
 use std::path::Path;
 
-use crate::backend::networkmodel::NetworkModel;
 use crate::frontend::config::ModelPlayerConfig;
-use crate::frontend::player::ModelPlayer;
+use crate::frontend::player::{read_network_model, ModelPlayer};
 use crate::frontend::renderer::PlottersRenderer;
+use crate::frontend::summary::PlayOutcome;
+
+use super::ExampleError;
 
 
 pub fn custom(
     network_model_path: &Path,
     model_player_config: &ModelPlayerConfig,
-) {
-    let network_model = NetworkModel::from_json(network_model_path)
-        .expect("Failed to deserialize network model");
+) -> Result<PlayOutcome, ExampleError> {
+    let network_model = read_network_model(network_model_path)
+        .map_err(|source| ExampleError::Input {
+            path: network_model_path.to_path_buf(),
+            source
+        })?;
 
     let renderer = model_player_config
         .render_config() 
         .map(|render_config|
             PlottersRenderer::new(
-                "custom.gif",
+                &model_player_config.output_filename("custom.gif"),
                 render_config.plot_caption(),
                 render_config.plot_resolution(),
                 render_config.axes_ranges(),
                 render_config.device_coloring(),
-                render_config.camera_angle()
+                render_config.camera_angle(),
+                model_player_config.frame_delay()
             )
+                .set_theme(render_config.theme().clone())
+                .set_draw_connections(render_config.draw_connections())
+                .set_draw_labels(render_config.draw_labels())
+                .set_draw_legend(render_config.draw_legend())
+                .set_draw_velocities(render_config.draw_velocities())
+                .set_draw_gps(render_config.draw_gps())
+                .set_draw_dashboard(render_config.draw_dashboard())
+                .set_frame_output(
+                    render_config.frame_directory(), 
+                    render_config.frame_format()
+                )
         );
 
     let mut model_player = ModelPlayer::new(
-        model_player_config.json_output_directory(),
+        model_player_config,
         network_model,
         renderer,
-        model_player_config.simulation_time(),
-    );
+    )?;
 
-    model_player.play();
+    Ok(model_player.play()?)
 }
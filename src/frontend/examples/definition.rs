@@ -0,0 +1,514 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use schemars::{schema_for, JsonSchema, Schema};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::backend::connections::Topology;
+use crate::backend::device::{
+    Device, DeviceBuilder, DeviceTemplates, SignalLossResponse, TemplateError,
+    BROADCAST_ID, device_map_from_slice
+};
+use crate::backend::device::systems::{
+    FirmwareVersion, IntegritySystem, SecuritySystem
+};
+use crate::backend::malware::Malware;
+use crate::backend::mathphysics::{Frequency, Meter, Millisecond, Point3D};
+use crate::backend::networkmodel::{NetworkModel, NetworkModelBuilder};
+use crate::backend::networkmodel::attack::{AttackType, AttackerDevice};
+use crate::backend::parameters::SimulationParameters;
+use crate::backend::signal::{SignalStrength, StrengthValue};
+use crate::backend::task::{Scenario, Task};
+use crate::frontend::config::{GeneralConfig, ModelConfig};
+use crate::frontend::player::ModelPlayer;
+use crate::frontend::renderer::PlottersRenderer;
+use crate::frontend::summary::PlayOutcome;
+
+use super::ExampleError;
+use super::premade::{
+    cc_builder, default_gps, device_channel_system, device_power_system,
+    drone_builder, drone_firmware_system, drone_flocking_system,
+    drone_formations, drone_gossip_system, drone_navigation_system,
+    drone_sensor_system, drone_thermal_system, ewd_trx_system,
+    generate_drone_position_in_rect_prism, gps, NetworkPosition
+};
+
+
+const DEFAULT_CC_TX_CONTROL_AREA_RADIUS: Meter            = Meter::new(300.0);
+const DEFAULT_DRONE_TX_CONTROL_AREA_RADIUS: Meter         = Meter::new(50.0);
+const DEFAULT_DRONE_GPS_RX_SIGNAL_STRENGTH: StrengthValue = 10_000.0;
+
+
+#[derive(Debug, Error)]
+pub enum ExperimentDefinitionError {
+    #[error("Failed to read experiment definition: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to deserialize experiment definition: {0}")]
+    Deserialization(#[from] serde_json::Error),
+    #[error("Drone group spread must not be negative along any axis")]
+    InvalidSpread,
+    #[error("Scenario refers to missing drone {0}")]
+    MissingDrone(usize),
+    #[error("Failed to place drone: {0}")]
+    MissingTemplate(#[from] TemplateError),
+}
+
+
+fn default_cc_tx_control_area_radius() -> Meter {
+    DEFAULT_CC_TX_CONTROL_AREA_RADIUS
+}
+
+fn default_drone_tx_control_area_radius() -> Meter {
+    DEFAULT_DRONE_TX_CONTROL_AREA_RADIUS
+}
+
+fn default_drone_gps_rx_signal_strength() -> StrengthValue {
+    DEFAULT_DRONE_GPS_RX_SIGNAL_STRENGTH
+}
+
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+struct CommandCenterDefinition {
+    position: Point3D,
+    #[serde(default = "default_cc_tx_control_area_radius")]
+    tx_control_area_radius: Meter,
+    #[serde(default)]
+    processing_delay: Millisecond,
+}
+
+
+// Systems not listed in the definition are taken from the command line.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+struct DroneDefinition {
+    #[serde(default = "default_drone_tx_control_area_radius")]
+    tx_control_area_radius: Meter,
+    #[serde(default = "default_drone_gps_rx_signal_strength")]
+    max_gps_rx_signal_strength: StrengthValue,
+    #[serde(default)]
+    signal_loss_response: Option<SignalLossResponse>,
+    #[serde(default)]
+    thermal: bool,
+    #[serde(default)]
+    sensor: bool,
+    #[serde(default)]
+    flocking: bool,
+    #[serde(default)]
+    channel_switch: bool,
+    #[serde(default)]
+    gossip: bool,
+    #[serde(default)]
+    gps_check: bool,
+    // Only used with `gps_check`.
+    #[serde(default)]
+    reject_spoofed_fixes: bool,
+    #[serde(default)]
+    firmware_version: Option<FirmwareVersion>,
+    #[serde(default)]
+    processing_delay: Option<Millisecond>,
+    // Only matters with bit errors enabled on the command line.
+    #[serde(default)]
+    integrity_check: bool,
+    // Malware the drone is protected from.
+    #[serde(default)]
+    patches: Vec<Malware>,
+}
+
+impl DroneDefinition {
+    fn builder(
+        &self,
+        model_config: &ModelConfig,
+        parameters: &SimulationParameters
+    ) -> DeviceBuilder {
+        let mut drone_builder = drone_builder(
+            model_config,
+            parameters,
+            self.tx_control_area_radius,
+            SignalStrength::new(self.max_gps_rx_signal_strength)
+        )
+            .set_security_system(SecuritySystem::new(self.patches.clone()));
+
+        if let Some(signal_loss_response) = self.signal_loss_response {
+            drone_builder = drone_builder
+                .set_signal_loss_response(signal_loss_response);
+        }
+        if self.thermal {
+            drone_builder = drone_builder
+                .set_thermal_system(drone_thermal_system());
+        }
+        if self.sensor {
+            drone_builder = drone_builder
+                .set_sensor_system(drone_sensor_system());
+        }
+        if self.flocking {
+            drone_builder = drone_builder
+                .set_flocking_system(drone_flocking_system());
+        }
+        if self.channel_switch {
+            drone_builder = drone_builder
+                .set_channel_system(device_channel_system());
+        }
+        if self.gossip {
+            drone_builder = drone_builder
+                .set_gossip_system(drone_gossip_system());
+        }
+        if self.gps_check {
+            drone_builder = drone_builder.set_navigation_system(
+                drone_navigation_system(self.reject_spoofed_fixes)
+            );
+        }
+        if let Some(firmware_version) = self.firmware_version {
+            drone_builder = drone_builder
+                .set_firmware_system(drone_firmware_system(firmware_version));
+        }
+        if let Some(processing_delay) = self.processing_delay {
+            drone_builder = drone_builder
+                .set_processing_delay(processing_delay);
+        }
+        if self.integrity_check {
+            let bit_errors = model_config.integrity_system().has_bit_errors();
+
+            drone_builder = drone_builder
+                .set_integrity_system(IntegritySystem::new(bit_errors, true));
+        }
+
+        drone_builder
+    }
+}
+
+
+// Drones are described inline or by the name of one of `templates`, in
+// which case the inline systems are ignored.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+struct PositionedDroneDefinition {
+    position: Point3D,
+    #[serde(default)]
+    template: Option<String>,
+    #[serde(flatten)]
+    drone: DroneDefinition,
+}
+
+
+// Drones placed randomly in a box around `origin` that extends by `spread`
+// along each axis in both directions.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+struct DroneGroupDefinition {
+    count: usize,
+    origin: Point3D,
+    spread: Point3D,
+    #[serde(default)]
+    template: Option<String>,
+    #[serde(flatten)]
+    drone: DroneDefinition,
+}
+
+
+// Electronic warfare suppresses `frequency`, which is control by default.
+// GPS spoofing and malware distribution always use GPS and control
+// frequencies respectively.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+struct AttackerDefinition {
+    position: Point3D,
+    area_radius: Meter,
+    attack_type: AttackType,
+    #[serde(default)]
+    frequency: Option<Frequency>,
+}
+
+impl AttackerDefinition {
+    fn frequency(&self) -> Frequency {
+        match self.attack_type {
+            AttackType::ElectronicWarfare      => self.frequency
+                .unwrap_or(Frequency::Control),
+            AttackType::GPSSpoofing(_)         => Frequency::GPS,
+            AttackType::MalwareDistribution(_) => Frequency::Control,
+        }
+    }
+
+    fn attacker_device(&self) -> AttackerDevice {
+        let device = DeviceBuilder::new()
+            .set_real_position(self.position)
+            .set_power_system(device_power_system())
+            .set_trx_system(
+                ewd_trx_system(self.frequency(), self.area_radius)
+            )
+            .build();
+
+        AttackerDevice::new(device, self.attack_type)
+    }
+}
+
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+struct GPSDefinition {
+    position: Point3D,
+    tx_area_radius: Meter,
+}
+
+
+// `drone` is the index of a drone in the list of `drones` followed by the
+// drones of `drone_groups`. If it is missing, the task is broadcast.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+struct ScenarioEntryDefinition {
+    time: Millisecond,
+    #[serde(default)]
+    drone: Option<usize>,
+    task: Task,
+}
+
+
+// Everything the network model consists of. The default topology is used if
+// it is missing.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+struct ExperimentDefinition {
+    #[serde(default)]
+    topology: Option<Topology>,
+    command_center: CommandCenterDefinition,
+    // Drones defined once and placed by `drones` and `drone_groups`.
+    #[serde(default)]
+    templates: BTreeMap<String, DroneDefinition>,
+    #[serde(default)]
+    drones: Vec<PositionedDroneDefinition>,
+    #[serde(default)]
+    drone_groups: Vec<DroneGroupDefinition>,
+    #[serde(default)]
+    attackers: Vec<AttackerDefinition>,
+    #[serde(default)]
+    gps: Option<GPSDefinition>,
+    #[serde(default)]
+    scenario: Vec<ScenarioEntryDefinition>,
+    #[serde(default)]
+    parameters: SimulationParameters,
+}
+
+impl ExperimentDefinition {
+    /// # Errors
+    ///
+    /// Will return `Err` if the file can not be read or it does not contain
+    /// a JSON experiment definition.
+    fn from_json(
+        definition_path: &Path
+    ) -> Result<Self, ExperimentDefinitionError> {
+        let json_string = fs::read_to_string(definition_path)?;
+
+        Ok(serde_json::from_str(&json_string)?)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if a drone group spread is negative or the
+    /// scenario refers to a missing drone.
+    fn build_network_model(
+        &self,
+        model_config: &ModelConfig
+    ) -> Result<NetworkModel, ExperimentDefinitionError> {
+        let command_center = cc_builder(
+            model_config,
+            self.command_center.tx_control_area_radius
+        )
+            .set_real_position(self.command_center.position)
+            .set_processing_delay(self.command_center.processing_delay)
+            .build();
+        let command_center_id = command_center.id();
+
+        let drones = self.drones(model_config)?;
+        let scenario = self.scenario(&drones)?;
+        let formations = drone_formations(model_config, &drones);
+
+        let mut devices = drones;
+        devices.insert(0, command_center);
+
+        let gps = self.gps
+            .as_ref()
+            .map_or_else(
+                default_gps,
+                |gps_definition| gps(
+                    gps_definition.position,
+                    gps_definition.tx_area_radius
+                )
+            );
+
+        Ok(
+            NetworkModelBuilder::new()
+                .set_command_center_id(command_center_id)
+                .set_device_map(device_map_from_slice(devices.as_slice()))
+                .set_attacker_devices(
+                    self.attackers
+                        .iter()
+                        .map(AttackerDefinition::attacker_device)
+                        .collect()
+                )
+                .set_gps(gps)
+                .set_topology(
+                    self.topology.unwrap_or_else(|| model_config.topology())
+                )
+                .set_scenario(scenario)
+                .set_formations(formations)
+                .set_collision_policy(model_config.collision_policy().clone())
+                .set_delay_multiplier(model_config.delay_multiplier())
+                .set_parameters(self.parameters)
+                .build()
+        )
+    }
+
+    fn device_templates(&self, model_config: &ModelConfig) -> DeviceTemplates {
+        self.templates
+            .iter()
+            .fold(DeviceTemplates::new(), |device_templates, (name, drone)|
+                device_templates.set_template(
+                    name.as_str(),
+                    drone.builder(model_config, &self.parameters)
+                )
+            )
+    }
+
+    fn drones(
+        &self,
+        model_config: &ModelConfig
+    ) -> Result<Vec<Device>, ExperimentDefinitionError> {
+        let device_templates = self.device_templates(model_config);
+        let drone_builder = |template: Option<&str>, drone: &DroneDefinition|
+            match template {
+                Some(name) => device_templates
+                    .get(name)
+                    .cloned()
+                    .ok_or_else(||
+                        TemplateError::UnknownTemplate(name.to_string())
+                    ),
+                None       => Ok(
+                    drone.builder(model_config, &self.parameters)
+                ),
+            };
+
+        let mut drones = Vec::with_capacity(self.drones.len());
+
+        for positioned_drone in &self.drones {
+            drones.push(
+                drone_builder(
+                    positioned_drone.template.as_deref(),
+                    &positioned_drone.drone
+                )?
+                    .set_real_position(positioned_drone.position)
+                    .build()
+            );
+        }
+
+        for drone_group in &self.drone_groups {
+            let spread = drone_group.spread;
+
+            if spread.x < 0.0 || spread.y < 0.0 || spread.z < 0.0 {
+                return Err(ExperimentDefinitionError::InvalidSpread);
+            }
+
+            let network_position = NetworkPosition::new(
+                drone_group.origin,
+                -spread.x..spread.x,
+                -spread.y..spread.y,
+                -spread.z..spread.z,
+            );
+            let drone_builder = drone_builder(
+                drone_group.template.as_deref(),
+                &drone_group.drone
+            )?;
+
+            drones.extend(
+                (0..drone_group.count).map(|_|
+                    drone_builder
+                        .clone()
+                        .set_real_position(
+                            generate_drone_position_in_rect_prism(
+                                &network_position
+                            )
+                        )
+                        .build()
+                )
+            );
+        }
+
+        Ok(drones)
+    }
+
+    fn scenario(
+        &self,
+        drones: &[Device]
+    ) -> Result<Scenario, ExperimentDefinitionError> {
+        let mut scenario_entries = Vec::with_capacity(self.scenario.len());
+
+        for entry in &self.scenario {
+            let destination_id = match entry.drone {
+                Some(index) => drones
+                    .get(index)
+                    .map(Device::id)
+                    .ok_or(ExperimentDefinitionError::MissingDrone(index))?,
+                None        => BROADCAST_ID,
+            };
+
+            scenario_entries.push((entry.time, destination_id, entry.task));
+        }
+
+        Ok(Scenario::from(scenario_entries.as_slice()))
+    }
+}
+
+
+#[must_use]
+pub fn definition_schema() -> Schema {
+    schema_for!(ExperimentDefinition)
+}
+
+pub fn definition(
+    definition_path: &Path,
+    general_config: &GeneralConfig
+) -> Result<PlayOutcome, ExampleError> {
+    let network_model = ExperimentDefinition::from_json(definition_path)
+        .and_then(|experiment_definition|
+            experiment_definition.build_network_model(
+                general_config.model_config()
+            )
+        )?;
+
+    let renderer = general_config
+        .model_player_config()
+        .render_config()
+        .map(|render_config| {
+            let output_filename = format!(
+                "{}.gif",
+                definition_path
+                    .file_stem()
+                    .unwrap_or_default()
+                    .display()
+            );
+
+            PlottersRenderer::new(
+                &general_config
+                    .model_player_config()
+                    .output_filename(&output_filename),
+                render_config.plot_caption(),
+                render_config.plot_resolution(),
+                render_config.axes_ranges(),
+                render_config.device_coloring(),
+                render_config.camera_angle(),
+                general_config.model_player_config().frame_delay(),
+            )
+                .set_theme(render_config.theme().clone())
+                .set_draw_connections(render_config.draw_connections())
+                .set_draw_labels(render_config.draw_labels())
+                .set_draw_legend(render_config.draw_legend())
+                .set_draw_velocities(render_config.draw_velocities())
+                .set_draw_gps(render_config.draw_gps())
+                .set_draw_dashboard(render_config.draw_dashboard())
+                .set_frame_output(
+                    render_config.frame_directory(),
+                    render_config.frame_format()
+                )
+        });
+
+    let mut model_player = ModelPlayer::new(
+        general_config.model_player_config(),
+        network_model,
+        renderer,
+    )?;
+
+    Ok(model_player.play()?)
+}
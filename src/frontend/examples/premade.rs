@@ -4,39 +4,52 @@ use crate::backend::device::{
 };
 use crate::backend::malware::{Malware, MalwareType};
 use crate::backend::mathphysics::{Frequency, Meter, Point3D};
-use crate::backend::networkmodel::NetworkModelBuilder; 
+use crate::backend::networkmodel::{NetworkModel, NetworkModelBuilder};
 use crate::backend::networkmodel::attack::{AttackType, AttackerDevice};
+use crate::backend::parameters::SimulationParameters;
 use crate::backend::signal::{
     SignalStrength, GREEN_SIGNAL_STRENGTH, MAX_RED_SIGNAL_STRENGTH
 };
-use crate::frontend::config::GeneralConfig;
-use crate::frontend::player::ModelPlayer;
+use crate::backend::task::Scenario;
+use crate::frontend::config::{GeneralConfig, ModelConfig};
+use crate::frontend::player::{ModelPlayer, PlayError};
 use crate::frontend::renderer::{
     Axes3DRanges, CameraAngle, DeviceColoring, PlottersRenderer, 
-    DEFAULT_AXES_RANGE, DEFAULT_DEVICE_COLORING
+    DEFAULT_AXES_RANGE
 };
+use crate::frontend::summary::PlayOutcome;
 
 use devsetup::{
-    attack_scenario, cc_trx_system, create_drone_vec, default_gps, 
-    default_network_position, device_movement_system, device_power_system, 
-    drone_trx_system, ewd_trx_system, reposition_scenario, CC_POSITION, 
-    NETWORK_ORIGIN
+    attack_scenario, attacker_trx_system, create_drone_vec, device_templates, 
+    experiment_attacker_devices, experiment_network_position, 
+    experiment_scenario, instantiate, reposition_scenario, CC_TEMPLATE, 
+    NETWORK_ORIGIN, SPOOFED_GPS_POSITION
 };
 
 
-pub use devsetup::DEVICE_MAX_POWER;
+pub use devsetup::{
+    cc_builder, collision_policy, default_gps, device_channel_system, 
+    device_power_system, drone_builder, drone_firmware_system, 
+    drone_flocking_system, drone_formations, drone_gossip_system, 
+    drone_navigation_system, drone_sensor_system, drone_thermal_system, 
+    ewd_trx_system, generate_drone_position_in_rect_prism, gps, 
+    land_signal_loss_response, mission_signal_loss_response, 
+    NetworkPosition, CC_POSITION, DEVICE_MAX_POWER
+};
 
 
 mod devsetup;
 
 
-fn derive_filename(topology: Topology, text: &str) -> String {
-    let topology_part = match topology {
+fn derive_filename(general_config: &GeneralConfig, text: &str) -> String {
+    let topology_part = match general_config.model_config().topology() {
         Topology::Mesh => "mesh",
         Topology::Star => "star",
     };
 
-    format!("{text}_{topology_part}.gif")
+    general_config
+        .model_player_config()
+        .output_filename(&format!("{text}_{topology_part}.gif"))
 }
 
 
@@ -44,33 +57,48 @@ pub fn ewd(
     general_config: &GeneralConfig, 
     ew_frequency: Frequency,
     ewd_area_radius: Meter,
-) {
-    let cc_tx_control_area_radius    = 300.0;
-    let drone_tx_control_area_radius = 50.0;
+) -> Result<PlayOutcome, PlayError> {
+    let cc_tx_control_area_radius    = Meter::new(300.0);
+    let drone_tx_control_area_radius = Meter::new(50.0);
     let drone_gps_rx_signal_strength = MAX_RED_SIGNAL_STRENGTH; 
         
-    let command_center = DeviceBuilder::new()
-        .set_real_position(CC_POSITION)
-        .set_power_system(device_power_system())
-        .set_trx_system(cc_trx_system(cc_tx_control_area_radius))
-        .set_signal_loss_response(SignalLossResponse::Ignore)
-        .build();
+    let device_templates = device_templates(
+        general_config.model_config(),
+        cc_tx_control_area_radius,
+        drone_tx_control_area_radius, 
+        drone_gps_rx_signal_strength, 
+        None
+    );
+
+    let command_center = instantiate(
+        &device_templates,
+        CC_TEMPLATE,
+        CC_POSITION
+    );
     let command_center_id = command_center.id();
 
     let mut devices = create_drone_vec(
         general_config.model_config().drone_count(),
-        &default_network_position(NETWORK_ORIGIN),
-        None,
-        general_config.model_config().signal_loss_response(),
-        drone_tx_control_area_radius, 
-        drone_gps_rx_signal_strength, 
+        &experiment_network_position(
+            general_config.model_config(),
+            NETWORK_ORIGIN
+        ),
+        &device_templates,
+    );
+    let formations = drone_formations(
+        general_config.model_config(), 
+        devices.as_slice()
     );
     devices.insert(0, command_center);
  
     let ewd = DeviceBuilder::new()
         .set_real_position(Point3D::new(0.0, 5.0, 2.0))
         .set_power_system(device_power_system())
-        .set_trx_system(ewd_trx_system(ew_frequency, ewd_area_radius))
+        .set_trx_system(attacker_trx_system(
+            general_config.model_config(),
+            ew_frequency,
+            ewd_area_radius
+        ))
         .build();
     let attacker_devices = vec![
         AttackerDevice::new(ewd, AttackType::ElectronicWarfare)
@@ -79,10 +107,20 @@ pub fn ewd(
     let drone_network = NetworkModelBuilder::new()
         .set_command_center_id(command_center_id)
         .set_device_map(device_map_from_slice(devices.as_slice()))
-        .set_attacker_devices(attacker_devices)
+        .set_attacker_devices(experiment_attacker_devices(
+            general_config.model_config(),
+            attacker_devices
+        ))
         .set_gps(default_gps())
         .set_topology(general_config.model_config().topology())
-        .set_scenario(attack_scenario())
+        .set_scenario(experiment_scenario(
+            general_config.model_config(),
+            attack_scenario
+        ))
+        .set_formations(formations)
+        .set_collision_policy(
+            general_config.model_config().collision_policy().clone()
+        )
         .set_delay_multiplier(general_config.model_config().delay_multiplier())
         .build();
 
@@ -91,7 +129,7 @@ pub fn ewd(
         .render_config()
         .map(|render_config| { 
             let output_filename = derive_filename(
-                general_config.model_config().topology(), 
+                general_config,
                 "ewd"
             );
             
@@ -100,50 +138,85 @@ pub fn ewd(
                 render_config.plot_caption(),
                 render_config.plot_resolution(),
                 DEFAULT_AXES_RANGE,
-                DEFAULT_DEVICE_COLORING,
+                render_config.device_coloring(),
                 render_config.camera_angle(),
+                general_config.model_player_config().frame_delay(),
             )
+                .set_theme(render_config.theme().clone())
+                .set_draw_connections(render_config.draw_connections())
+                .set_draw_labels(render_config.draw_labels())
+                .set_draw_legend(render_config.draw_legend())
+                .set_draw_velocities(render_config.draw_velocities())
+                .set_draw_gps(render_config.draw_gps())
+                .set_draw_dashboard(render_config.draw_dashboard())
+                .set_frame_output(
+                    render_config.frame_directory(), 
+                    render_config.frame_format()
+                )
         });
 
     let mut model_player = ModelPlayer::new(
-        general_config.model_player_config().json_output_directory(),
+        general_config.model_player_config(),
         drone_network,
         renderer,
-        general_config.model_player_config().simulation_time(),
-    );
+    )?;
 
-    model_player.play();
+    model_player.play()
 }
 
-pub fn movement(general_config: &GeneralConfig) {
-    let cc_tx_control_area_radius    = 300.0;
-    let drone_tx_control_area_radius = 50.0;
+pub fn movement(
+    general_config: &GeneralConfig
+) -> Result<PlayOutcome, PlayError> {
+    let cc_tx_control_area_radius    = Meter::new(300.0);
+    let drone_tx_control_area_radius = Meter::new(50.0);
     let drone_gps_rx_signal_strength  = SignalStrength::new(10_000.0); 
 
-    let command_center = DeviceBuilder::new()
-        .set_real_position(CC_POSITION)
-        .set_power_system(device_power_system())
-        .set_trx_system(cc_trx_system(cc_tx_control_area_radius))
-        .set_signal_loss_response(SignalLossResponse::Ignore)
-        .build();
+    let device_templates = device_templates(
+        general_config.model_config(),
+        cc_tx_control_area_radius,
+        drone_tx_control_area_radius, 
+        drone_gps_rx_signal_strength, 
+        None
+    );
+
+    let command_center = instantiate(
+        &device_templates,
+        CC_TEMPLATE,
+        CC_POSITION
+    );
     let command_center_id = command_center.id();
 
     let mut devices = create_drone_vec(
         general_config.model_config().drone_count(),
-        &default_network_position(NETWORK_ORIGIN),
-        None,
-        general_config.model_config().signal_loss_response(),
-        drone_tx_control_area_radius, 
-        drone_gps_rx_signal_strength, 
+        &experiment_network_position(
+            general_config.model_config(),
+            NETWORK_ORIGIN
+        ),
+        &device_templates,
+    );
+    let formations = drone_formations(
+        general_config.model_config(), 
+        devices.as_slice()
     );
     devices.insert(0, command_center);
     
     let drone_network = NetworkModelBuilder::new()
         .set_command_center_id(command_center_id)
         .set_device_map(device_map_from_slice(devices.as_slice()))
+        .set_attacker_devices(experiment_attacker_devices(
+            general_config.model_config(),
+            Vec::new()
+        ))
         .set_gps(default_gps())
         .set_topology(general_config.model_config().topology())
-        .set_scenario(reposition_scenario())
+        .set_scenario(experiment_scenario(
+            general_config.model_config(),
+            reposition_scenario
+        ))
+        .set_formations(formations)
+        .set_collision_policy(
+            general_config.model_config().collision_policy().clone()
+        )
         .set_delay_multiplier(general_config.model_config().delay_multiplier())
         .build();
 
@@ -152,7 +225,7 @@ pub fn movement(general_config: &GeneralConfig) {
         .render_config()
         .map(|render_config| { 
             let output_filename = derive_filename(
-                general_config.model_config().topology(), 
+                general_config,
                 "movement"
             );
                     
@@ -161,64 +234,102 @@ pub fn movement(general_config: &GeneralConfig) {
                 render_config.plot_caption(),
                 render_config.plot_resolution(),
                 DEFAULT_AXES_RANGE,
-                DEFAULT_DEVICE_COLORING,
+                render_config.device_coloring(),
                 render_config.camera_angle(),
+                general_config.model_player_config().frame_delay(),
             )
+                .set_theme(render_config.theme().clone())
+                .set_draw_connections(render_config.draw_connections())
+                .set_draw_labels(render_config.draw_labels())
+                .set_draw_legend(render_config.draw_legend())
+                .set_draw_velocities(render_config.draw_velocities())
+                .set_draw_gps(render_config.draw_gps())
+                .set_draw_dashboard(render_config.draw_dashboard())
+                .set_frame_output(
+                    render_config.frame_directory(), 
+                    render_config.frame_format()
+                )
         });
 
     let mut model_player = ModelPlayer::new(
-        general_config.model_player_config().json_output_directory(),
+        general_config.model_player_config(),
         drone_network,
         renderer,
-        general_config.model_player_config().simulation_time(),
-    );
+    )?;
 
-    model_player.play();
+    model_player.play()
 }
 
 pub fn gps_spoofing(
     general_config: &GeneralConfig,
     spoofer_area_radius: Meter
-) {
-    let cc_tx_control_area_radius    = 300.0;
-    let drone_tx_control_area_radius = 50.0;
+) -> Result<PlayOutcome, PlayError> {
+    let cc_tx_control_area_radius    = Meter::new(300.0);
+    let drone_tx_control_area_radius = Meter::new(50.0);
     let drone_gps_rx_signal_strength = MAX_RED_SIGNAL_STRENGTH; 
         
-    let command_center = DeviceBuilder::new()
-        .set_real_position(CC_POSITION)
-        .set_power_system(device_power_system())
-        .set_trx_system(cc_trx_system(cc_tx_control_area_radius))
-        .set_signal_loss_response(SignalLossResponse::Ignore)
-        .build();
+    let device_templates = device_templates(
+        general_config.model_config(),
+        cc_tx_control_area_radius,
+        drone_tx_control_area_radius, 
+        drone_gps_rx_signal_strength, 
+        None
+    );
+
+    let command_center = instantiate(
+        &device_templates,
+        CC_TEMPLATE,
+        CC_POSITION
+    );
     let command_center_id = command_center.id();
 
     let mut devices = create_drone_vec(
         general_config.model_config().drone_count(),
-        &default_network_position(NETWORK_ORIGIN),
-        None,
-        general_config.model_config().signal_loss_response(),
-        drone_tx_control_area_radius, 
-        drone_gps_rx_signal_strength, 
+        &experiment_network_position(
+            general_config.model_config(),
+            NETWORK_ORIGIN
+        ),
+        &device_templates,
+    );
+    let formations = drone_formations(
+        general_config.model_config(), 
+        devices.as_slice()
     );
     devices.insert(0, command_center);
 
     let spoofer = DeviceBuilder::new()
         .set_real_position(Point3D::new(0.0, 5.0, 2.0))
         .set_power_system(device_power_system())
-        .set_trx_system(ewd_trx_system(Frequency::GPS, spoofer_area_radius))
+        .set_trx_system(attacker_trx_system(
+            general_config.model_config(),
+            Frequency::GPS,
+            spoofer_area_radius
+        ))
         .build();
-    let spoofed_position = Point3D::new(-200.0, -100.0, -200.0);
     let attacker_devices = vec![
-        AttackerDevice::new(spoofer, AttackType::GPSSpoofing(spoofed_position))
+        AttackerDevice::new(
+            spoofer, 
+            AttackType::GPSSpoofing(SPOOFED_GPS_POSITION)
+        )
     ];
 
     let drone_network = NetworkModelBuilder::new()
         .set_command_center_id(command_center_id)
         .set_device_map(device_map_from_slice(devices.as_slice()))
-        .set_attacker_devices(attacker_devices)
+        .set_attacker_devices(experiment_attacker_devices(
+            general_config.model_config(),
+            attacker_devices
+        ))
         .set_gps(default_gps())
         .set_topology(general_config.model_config().topology())
-        .set_scenario(attack_scenario())
+        .set_scenario(experiment_scenario(
+            general_config.model_config(),
+            attack_scenario
+        ))
+        .set_formations(formations)
+        .set_collision_policy(
+            general_config.model_config().collision_policy().clone()
+        )
         .set_delay_multiplier(general_config.model_config().delay_multiplier())
         .build();
 
@@ -227,7 +338,7 @@ pub fn gps_spoofing(
         .render_config()
         .map(|render_config| { 
             let output_filename = derive_filename(
-                general_config.model_config().topology(), 
+                general_config,
                 "gps_spoofing"
             );
             let axes_ranges = Axes3DRanges::new(
@@ -242,45 +353,63 @@ pub fn gps_spoofing(
                 render_config.plot_caption(),
                 render_config.plot_resolution(),
                 axes_ranges,
-                DEFAULT_DEVICE_COLORING,
+                render_config.device_coloring(),
                 camera_angle,
+                general_config.model_player_config().frame_delay(),
             )
+                .set_theme(render_config.theme().clone())
+                .set_draw_connections(render_config.draw_connections())
+                .set_draw_labels(render_config.draw_labels())
+                .set_draw_legend(render_config.draw_legend())
+                .set_draw_velocities(render_config.draw_velocities())
+                .set_draw_gps(render_config.draw_gps())
+                .set_draw_dashboard(render_config.draw_dashboard())
+                .set_frame_output(
+                    render_config.frame_directory(), 
+                    render_config.frame_format()
+                )
         });
 
     let mut model_player = ModelPlayer::new(
-        general_config.model_player_config().json_output_directory(),
+        general_config.model_player_config(),
         drone_network,
         renderer,
-        general_config.model_player_config().simulation_time(),
-    );
+    )?;
 
-    model_player.play();
+    model_player.play()
 }
 
 pub fn malware_infection(
     general_config: &GeneralConfig,
     malware: Malware,
     attacker_area_radius: Meter,
-) {
-    let cc_tx_control_area_radius    = 200.0;
-    let drone_tx_control_area_radius = 30.0;
+) -> Result<PlayOutcome, PlayError> {
+    let cc_tx_control_area_radius    = Meter::new(200.0);
+    let drone_tx_control_area_radius = Meter::new(30.0);
     let drone_gps_rx_signal_strength  = GREEN_SIGNAL_STRENGTH; 
 
-    let command_center = DeviceBuilder::new()
-        .set_real_position(Point3D::new(100.0, 50.0, 0.0))
-        .set_power_system(device_power_system())
-        .set_trx_system(cc_trx_system(cc_tx_control_area_radius))
-        .set_signal_loss_response(SignalLossResponse::Ignore)
-        .build();
+    let device_templates = device_templates(
+        general_config.model_config(),
+        cc_tx_control_area_radius,
+        drone_tx_control_area_radius, 
+        drone_gps_rx_signal_strength, 
+        Some(malware)
+    );
+
+    let command_center = instantiate(
+        &device_templates,
+        CC_TEMPLATE,
+        Point3D::new(100.0, 50.0, 0.0)
+    );
     let command_center_id = command_center.id();
 
     let mut devices = create_drone_vec(
         general_config.model_config().drone_count(),
-        &default_network_position(Point3D::new(50.0, 50.0, 0.0)),
-        Some(malware),
-        general_config.model_config().signal_loss_response(),
-        drone_tx_control_area_radius, 
-        drone_gps_rx_signal_strength, 
+        &experiment_network_position(
+            general_config.model_config(),
+            Point3D::new(50.0, 50.0, 0.0)
+        ),
+        &device_templates,
     );
     devices.insert(0, command_center);
     
@@ -303,10 +432,20 @@ pub fn malware_infection(
         .set_device_map(device_map_from_slice(devices.as_slice()))
         .set_gps(default_gps())
         .set_topology(general_config.model_config().topology())
+        .set_scenario(experiment_scenario(
+            general_config.model_config(),
+            Scenario::default
+        ))
+        .set_collision_policy(
+            general_config.model_config().collision_policy().clone()
+        )
         .set_delay_multiplier(general_config.model_config().delay_multiplier());
     
     let drone_network = drone_network_builder
-        .set_attacker_devices(attacker_devices)
+        .set_attacker_devices(experiment_attacker_devices(
+            general_config.model_config(),
+            attacker_devices
+        ))
         .build();
 
     let renderer = general_config
@@ -318,7 +457,7 @@ pub fn malware_infection(
                 MalwareType::Indicator  => "mal_indicator",
             };
             let output_filename = derive_filename(
-                general_config.model_config().topology(), 
+                general_config,
                 text,
             );
             let drone_coloring = match malware.malware_type() {
@@ -338,45 +477,62 @@ pub fn malware_infection(
                 render_config.plot_resolution(),
                 axes_ranges,
                 drone_coloring,
-                camera_angle
+                camera_angle,
+                general_config.model_player_config().frame_delay()
             )
+                .set_theme(render_config.theme().clone())
+                .set_draw_connections(render_config.draw_connections())
+                .set_draw_labels(render_config.draw_labels())
+                .set_draw_legend(render_config.draw_legend())
+                .set_draw_velocities(render_config.draw_velocities())
+                .set_draw_gps(render_config.draw_gps())
+                .set_draw_dashboard(render_config.draw_dashboard())
+                .set_frame_output(
+                    render_config.frame_directory(), 
+                    render_config.frame_format()
+                )
         });
 
     let mut model_player = ModelPlayer::new(
-        general_config.model_player_config().json_output_directory(),
+        general_config.model_player_config(),
         drone_network,
         renderer,
-        general_config.model_player_config().simulation_time(),
-    );
+    )?;
 
-    model_player.play();
+    model_player.play()
 }
 
-pub fn signal_loss_response(general_config: &GeneralConfig) {
-    let cc_tx_control_area_radius    = 200.0;
-    let drone_tx_control_area_radius = 50.0;
+pub fn signal_loss_response(
+    general_config: &GeneralConfig
+) -> Result<PlayOutcome, PlayError> {
+    let cc_tx_control_area_radius    = Meter::new(200.0);
+    let drone_tx_control_area_radius = Meter::new(50.0);
     let drone_gps_rx_signal_strength  = GREEN_SIGNAL_STRENGTH; 
-    let control_ewd_suppression_area_radius = 25.0;
+    let control_ewd_suppression_area_radius = Meter::new(25.0);
     let command_center_position      = Point3D::new(100.0, 50.0, 0.0);
 
-    let command_center = DeviceBuilder::new()
-        .set_real_position(command_center_position)
-        .set_power_system(device_power_system())
-        .set_trx_system(cc_trx_system(cc_tx_control_area_radius))
-        .set_signal_loss_response(SignalLossResponse::Ignore)
-        .build();
+    let device_templates = device_templates(
+        general_config.model_config(),
+        cc_tx_control_area_radius,
+        drone_tx_control_area_radius, 
+        drone_gps_rx_signal_strength, 
+        None
+    );
+
+    let command_center = instantiate(
+        &device_templates,
+        CC_TEMPLATE,
+        command_center_position
+    );
     let command_center_id = command_center.id();
    
-    let drone_builder = DeviceBuilder::new()
-        .set_real_position(Point3D::new(70.0, 50.0, 30.0))
-        .set_power_system(device_power_system())
-        .set_movement_system(device_movement_system())
-        .set_trx_system(
-            drone_trx_system(
-                drone_tx_control_area_radius, 
-                drone_gps_rx_signal_strength
-            )
-        );
+    let drone_builder = drone_builder(
+        general_config.model_config(),
+        &SimulationParameters::default(),
+        drone_tx_control_area_radius, 
+        drone_gps_rx_signal_strength
+    )
+        .set_real_position(Point3D::new(70.0, 50.0, 30.0));
 
     let ascend_drone = drone_builder
         .clone()
@@ -397,15 +553,25 @@ pub fn signal_loss_response(general_config: &GeneralConfig) {
         )
         .build();
     let shutdown_drone = drone_builder
+        .clone()
         .set_signal_loss_response(SignalLossResponse::Shutdown)
         .build();
+    let mission_drone = drone_builder
+        .clone()
+        .set_signal_loss_response(mission_signal_loss_response())
+        .build();
+    let land_drone = drone_builder
+        .set_signal_loss_response(land_signal_loss_response())
+        .build();
     let devices = [
         command_center, 
         ascend_drone, 
         hover_drone, 
         ignore_drone,
         rth_drone, 
-        shutdown_drone
+        shutdown_drone,
+        mission_drone,
+        land_drone
     ]; 
     
     let ewd_control = DeviceBuilder::new()
@@ -425,10 +591,19 @@ pub fn signal_loss_response(general_config: &GeneralConfig) {
     let drone_network = NetworkModelBuilder::new()
         .set_command_center_id(command_center_id)
         .set_device_map(device_map_from_slice(devices.as_slice()))
-        .set_attacker_devices(attacker_devices)
+        .set_attacker_devices(experiment_attacker_devices(
+            general_config.model_config(),
+            attacker_devices
+        ))
         .set_gps(default_gps())
         .set_topology(general_config.model_config().topology())
-        .set_scenario(attack_scenario())
+        .set_scenario(experiment_scenario(
+            general_config.model_config(),
+            attack_scenario
+        ))
+        .set_collision_policy(
+            general_config.model_config().collision_policy().clone()
+        )
         .set_delay_multiplier(general_config.model_config().delay_multiplier())
         .build();
  
@@ -437,7 +612,7 @@ pub fn signal_loss_response(general_config: &GeneralConfig) {
         .render_config()
         .map(|render_config| { 
             let output_filename = derive_filename(
-                general_config.model_config().topology(),
+                general_config,
                 "signal_loss_response"
             ); 
             let axes_ranges = Axes3DRanges::new(
@@ -451,17 +626,78 @@ pub fn signal_loss_response(general_config: &GeneralConfig) {
                 render_config.plot_caption(),
                 render_config.plot_resolution(),
                 axes_ranges,
-                DEFAULT_DEVICE_COLORING,
+                render_config.device_coloring(),
                 render_config.camera_angle(),
+                general_config.model_player_config().frame_delay(),
             )
+                .set_theme(render_config.theme().clone())
+                .set_draw_connections(render_config.draw_connections())
+                .set_draw_labels(render_config.draw_labels())
+                .set_draw_legend(render_config.draw_legend())
+                .set_draw_velocities(render_config.draw_velocities())
+                .set_draw_gps(render_config.draw_gps())
+                .set_draw_dashboard(render_config.draw_dashboard())
+                .set_frame_output(
+                    render_config.frame_directory(), 
+                    render_config.frame_format()
+                )
         });
     
     let mut model_player = ModelPlayer::new(
-        general_config.model_player_config().json_output_directory(),
+        general_config.model_player_config(),
         drone_network,
         renderer,
-        general_config.model_player_config().simulation_time(),
+    )?;
+
+    model_player.play()
+}
+
+// Starting point of custom experiments. It has every kind of device, so the
+// serialized model shows how each of them is described.
+#[must_use]
+pub fn template(model_config: &ModelConfig) -> NetworkModel {
+    let cc_tx_control_area_radius    = Meter::new(300.0);
+    let drone_tx_control_area_radius = Meter::new(50.0);
+    let drone_gps_rx_signal_strength = SignalStrength::new(10_000.0);
+    let ewd_area_radius              = Meter::new(30.0);
+
+    let device_templates = device_templates(
+        model_config,
+        cc_tx_control_area_radius,
+        drone_tx_control_area_radius,
+        drone_gps_rx_signal_strength,
+        None
+    );
+
+    let command_center = instantiate(
+        &device_templates,
+        CC_TEMPLATE,
+        CC_POSITION
     );
+    let command_center_id = command_center.id();
 
-    model_player.play();
+    let mut devices = create_drone_vec(
+        model_config.drone_count(),
+        &experiment_network_position(model_config, NETWORK_ORIGIN),
+        &device_templates,
+    );
+    devices.insert(0, command_center);
+
+    let ewd = DeviceBuilder::new()
+        .set_real_position(Point3D::new(0.0, 5.0, 2.0))
+        .set_power_system(device_power_system())
+        .set_trx_system(ewd_trx_system(Frequency::Control, ewd_area_radius))
+        .build();
+
+    NetworkModelBuilder::new()
+        .set_command_center_id(command_center_id)
+        .set_device_map(device_map_from_slice(devices.as_slice()))
+        .set_attacker_devices(vec![
+            AttackerDevice::new(ewd, AttackType::ElectronicWarfare)
+        ])
+        .set_gps(default_gps())
+        .set_topology(model_config.topology())
+        .set_scenario(reposition_scenario())
+        .set_delay_multiplier(model_config.delay_multiplier())
+        .build()
 }
@@ -1,27 +1,50 @@
+use std::f32::consts::FRAC_PI_3;
 use std::ops::Range;
 
-use rand::prelude::*;
+use rand::Rng;
 
+use crate::backend::collision::CollisionPolicy;
 use crate::backend::device::{
-    Device, DeviceBuilder, SignalLossResponse, BROADCAST_ID, MAX_DRONE_SPEED 
+    Device, DeviceBuilder, DeviceTemplates, Payload, SignalLossResponse, 
+    BROADCAST_ID
 };
 use crate::backend::device::systems::{
-    MovementSystem, PowerSystem, RXModule, SecuritySystem, TRXSystem, TXModule, 
+    ChannelSystem, ClockSystem, FirmwareSystem, FirmwareUpdate, 
+    FirmwareVersion, FlockingSystem, GossipSystem, MovementSystem, 
+    NavigationSystem, PowerSystem, RXModule, SecuritySystem, SensorSystem, 
+    ThermalSystem, TRXSystem, TXModule, 
 };
 use crate::backend::malware::Malware;
 use crate::backend::mathphysics::{
-    Frequency, Megahertz, Meter, Point3D, PowerUnit
+    Celsius, Float, Frequency, Megahertz, Meter, MeterPerSecond, Millisecond, 
+    Point3D, PowerUnit, Watt
 };
+use crate::backend::networkmodel::formation::Formation;
 use crate::backend::networkmodel::gps::GPS;
+use crate::backend::parameters::SimulationParameters;
+use crate::backend::random;
 use crate::backend::signal::{
     FreqToStrengthMap, SignalStrength, GREEN_SIGNAL_STRENGTH
 };
 use crate::backend::task::{Scenario, Task};
+use crate::backend::networkmodel::attack::{AttackType, AttackerDevice};
+use crate::frontend::config::{AttackerConfig, AttackerKind, ModelConfig};
 
 
-pub const DEVICE_MAX_POWER: PowerUnit = 100_000;
+pub const DEVICE_MAX_POWER: PowerUnit = PowerUnit::new(100_000);
+// Templates every premade experiment registers. Hardened drones are patched
+// against the malware of the experiment.
+pub const CC_TEMPLATE: &str             = "cc";
+pub const STANDARD_DRONE_TEMPLATE: &str = "standard-drone";
+pub const HARDENED_DRONE_TEMPLATE: &str = "hardened-drone";
 pub const NETWORK_ORIGIN: Point3D     = Point3D { x: 150.0, y: 90.0, z: 25.0 };
 pub const CC_POSITION: Point3D        = Point3D { x: 200.0, y: 100.0, z: 0.0 };
+// Position GPS spoofers send to receivers in their areas.
+pub const SPOOFED_GPS_POSITION: Point3D = Point3D { 
+    x: -200.0, 
+    y: -100.0, 
+    z: -200.0 
+};
 
 const DEFAULT_GPS_POSITION_IN_METERS: Point3D = Point3D { 
     x: NETWORK_ORIGIN.x, 
@@ -29,64 +52,205 @@ const DEFAULT_GPS_POSITION_IN_METERS: Point3D = Point3D {
     z: 200.0
 };
 const DRONE_DESTINATION: Point3D  = Point3D { x: 0.0, y: 0.0, z: 0.0 };
-const GPS_TX_RADIUS: Meter = 350.0;
+const DEFAULT_SPAWN_SPREAD: Point3D = Point3D { x: 40.0, y: 40.0, z: 20.0 };
+const GPS_TX_RADIUS: Meter = Meter::new(350.0);
+const DRONE_PAYLOAD_ITEM: &str = "cargo";
 const PATCH_PROBABILITY: f64 = 0.0;
 
+// Drones flying at max speed overheat in about a minute, while hovering
+// drones stay below the throttle temperature.
+const DRONE_AMBIENT_TEMPERATURE: Celsius  = 20.0;
+const DRONE_HEATING_RATE: Celsius         = 2.0;
+const DRONE_COOLING_RATE: f32             = 0.02;
+const DRONE_THROTTLE_TEMPERATURE: Celsius = 90.0;
+const DRONE_THROTTLE_FACTOR: f32          = 0.6;
+
+// Drones sample a 60 degree cone beneath them every second.
+const DRONE_SENSOR_FIELD_OF_VIEW: f32           = FRAC_PI_3;
+const DRONE_SENSOR_RANGE: Meter                 = Meter::new(100.0);
+const DRONE_SENSOR_SAMPLING_PERIOD: Millisecond = Millisecond::new(1000);
+
+// Separation dominates close to neighbors, so drones spread out around a
+// shared destination instead of stacking onto it.
+const DRONE_FLOCKING_NEIGHBOR_RADIUS: Meter = Meter::new(15.0);
+const DRONE_FLOCKING_SEPARATION: f32        = 20.0;
+const DRONE_FLOCKING_ALIGNMENT: f32         = 0.05;
+const DRONE_FLOCKING_COHESION: f32          = 0.05;
+
+// Drones cover about a meter per iteration at max speed, so honest fixes
+// stay well within the limits even when they are delayed.
+const DRONE_NAVIGATION_MAX_JUMP: Meter        = Meter::new(15.0);
+const DRONE_NAVIGATION_MAX_RANGE_ERROR: Meter = Meter::new(10.0);
+
+// Control jamming is noise for about half a second, so a single strong
+// signal does not move the network to the backup frequency.
+const DEVICE_NOISE_TOLERANCE: Millisecond = Millisecond::new(500);
+
+// Drones share an entry of what they know with two random neighbors every
+// second.
+const DRONE_GOSSIP_PERIOD: Millisecond = Millisecond::new(1000);
+const DRONE_GOSSIP_FANOUT: usize       = 2;
+
+// Drones run the first firmware version. The command center pushes the
+// next one, which takes drones five seconds to install.
+const DEVICE_FIRMWARE_VERSION: FirmwareVersion = 1;
+const FIRMWARE_UPDATE_INSTALL_TIME: Millisecond = Millisecond::new(5000);
+const FIRMWARE_UPDATE_INSTALL_POWER: Watt       = Watt::new(10.0);
+
+// Drones hover waiting for the command center for a few seconds before they
+// continue their mission on their own.
+const DRONE_MISSION_PATIENCE: Millisecond = Millisecond::new(3000);
+// The same wait before drones give the command center up and land.
+const DRONE_LANDING_PATIENCE: Millisecond = Millisecond::new(3000);
+
+const FORMATION_SPACING: Meter = Meter::new(10.0);
+
+// Drones collide when their centers are closer than that and lose half of
+// their health when damaged.
+const COLLISION_DISTANCE: Meter = Meter::new(1.0);
+const COLLISION_DAMAGE: f32     = 0.5;
+
+
+pub fn device_templates(
+    model_config: &ModelConfig,
+    cc_tx_control_area_radius: Meter,
+    drone_tx_control_area_radius: Meter,
+    max_gps_rx_signal_strength: SignalStrength,
+    malware: Option<Malware>,
+) -> DeviceTemplates {
+    let patches: Vec<Malware> = malware.into_iter().collect();
+    // Premade experiments run with default simulation parameters.
+    let standard_drone_builder = drone_builder(
+        model_config,
+        &SimulationParameters::default(),
+        drone_tx_control_area_radius,
+        max_gps_rx_signal_strength
+    );
+    let hardened_drone_builder = standard_drone_builder
+        .clone()
+        .set_security_system(SecuritySystem::new(patches));
+
+    DeviceTemplates::new()
+        .set_template(
+            CC_TEMPLATE, 
+            cc_builder(model_config, cc_tx_control_area_radius)
+        )
+        .set_template(STANDARD_DRONE_TEMPLATE, standard_drone_builder)
+        .set_template(HARDENED_DRONE_TEMPLATE, hardened_drone_builder)
+}
+
+// Templates of `device_templates` are always registered, so instantiation
+// can not fail.
+pub fn instantiate(
+    device_templates: &DeviceTemplates,
+    name: &str,
+    position: Point3D
+) -> Device {
+    device_templates
+        .instantiate(name, position)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
 
 pub fn create_drone_vec(
     drone_count: usize, 
     network_position: &NetworkPosition,
-    malware: Option<Malware>,
-    signal_loss_response: SignalLossResponse,
+    device_templates: &DeviceTemplates,
+) -> Vec<Device> {
+    (0..drone_count)
+        .map(|_| {
+            let name = if random::random_bool(PATCH_PROBABILITY) {
+                HARDENED_DRONE_TEMPLATE
+            } else { 
+                STANDARD_DRONE_TEMPLATE
+            };
+
+            instantiate(
+                device_templates,
+                name,
+                generate_drone_position_in_rect_prism(network_position)
+            )
+        })  
+        .collect()
+}
+
+pub fn cc_builder(
+    model_config: &ModelConfig,
+    tx_control_area_radius: Meter
+) -> DeviceBuilder {
+    DeviceBuilder::new()
+        .set_power_system(device_power_system())
+        .set_trx_system(cc_trx_system(tx_control_area_radius))
+        .set_channel_system(model_config.channel_system().clone())
+        .set_clock_system(
+            ClockSystem::new(0.0, model_config.sync_period())
+        )
+        .set_firmware_system(cc_firmware_system(model_config))
+        .set_integrity_system(model_config.integrity_system().clone())
+        .set_signal_loss_response(SignalLossResponse::Ignore)
+}
+
+// Drone without a position with systems enabled in `model_config`.
+pub fn drone_builder(
+    model_config: &ModelConfig,
+    parameters: &SimulationParameters,
     tx_control_area_radius: Meter,
     max_gps_rx_signal_strength: SignalStrength,
-) -> Vec<Device> {
-    let power_system    = device_power_system();
-    let movement_system = device_movement_system();
+) -> DeviceBuilder {
+    let power_system        = device_power_system()
+        .set_critical_level(model_config.critical_battery_level());
+    let mut movement_system = device_movement_system(
+        parameters.max_drone_speed()
+    );
     let trx_system      = drone_trx_system(
         tx_control_area_radius,
-        max_gps_rx_signal_strength
+        max_gps_rx_signal_strength,
+        model_config.gps_bands()
     );
-    let patches = match malware {
-        Some(malware) => vec![malware],
-        None          => Vec::new(),
-    };
-    let security_system = SecuritySystem::new(patches);
+
+    movement_system.set_limits(model_config.movement_limits());
 
     let drone_builder = DeviceBuilder::new()
         .set_power_system(power_system)
         .set_movement_system(movement_system)
         .set_trx_system(trx_system)
-        .set_signal_loss_response(signal_loss_response);
+        .set_reliability_system(model_config.reliability_system().clone())
+        .set_thermal_system(model_config.thermal_system().clone())
+        .set_sensor_system(model_config.sensor_system().clone())
+        .set_flocking_system(model_config.flocking_system().clone())
+        .set_navigation_system(model_config.navigation_system().clone())
+        .set_channel_system(model_config.channel_system().clone())
+        .set_gossip_system(model_config.gossip_system().clone())
+        .set_clock_system(model_config.clock_system().clone())
+        .set_firmware_system(drone_firmware_system(DEVICE_FIRMWARE_VERSION))
+        .set_processing_delay(model_config.processing_delay())
+        .set_integrity_system(model_config.integrity_system().clone())
+        .set_signal_loss_response(model_config.signal_loss_response());
 
-    (0..drone_count)
-        .map(|_| {
-            let drone_builder = if rand::random_bool(PATCH_PROBABILITY) {
-                drone_builder
-                    .clone()
-                    .set_security_system(security_system.clone())
-            } else { 
-                drone_builder.clone()
-            };
+    match drone_payload(model_config) {
+        Some(payload) => drone_builder.set_payload(payload),
+        None          => drone_builder,
+    }
+}
 
-            drone_builder
-                .set_real_position(
-                    generate_drone_position_in_rect_prism(network_position)
-                )
-                .build()
-        })  
-        .collect()
+// The range of a zero spread is empty, so drones keep the origin coordinate
+// along its axis.
+fn random_offset_in<R: Rng>(rng: &mut R, range: &Range<Float>) -> Float {
+    if range.is_empty() {
+        return range.start;
+    }
+
+    rng.random_range(range.clone())
 }
 
-fn generate_drone_position_in_rect_prism(
+pub fn generate_drone_position_in_rect_prism(
     network_position: &NetworkPosition
 ) -> Point3D {
-    let mut rng = rand::rng();
-
-    let random_offset = Point3D::new(
-        rng.random_range(network_position.x_offset_range.clone()),
-        rng.random_range(network_position.y_offset_range.clone()),
-        rng.random_range(network_position.z_offset_range.clone())
+    let random_offset = random::with_rng(|rng|
+        Point3D::new(
+            random_offset_in(rng, &network_position.x_offset_range),
+            random_offset_in(rng, &network_position.y_offset_range),
+            random_offset_in(rng, &network_position.z_offset_range)
+        )
     );
     
     network_position.origin + random_offset
@@ -103,11 +267,12 @@ pub fn cc_trx_system(
 
 pub fn drone_trx_system(
     tx_control_area_radius: Meter,
-    max_gps_rx_signal_strength: SignalStrength
+    max_gps_rx_signal_strength: SignalStrength,
+    gps_bands: &[Frequency],
 ) -> TRXSystem {
     TRXSystem::new( 
         tx_module(Frequency::Control, tx_control_area_radius), 
-        rx_module(max_gps_rx_signal_strength),
+        multi_band_rx_module(max_gps_rx_signal_strength, gps_bands),
     )
 }
  
@@ -121,9 +286,26 @@ pub fn ewd_trx_system(
     )
 }
 
-fn gps_trx_system() -> TRXSystem {
+// GPS attackers transmit on every attacked GPS band, so they deny only
+// receivers that listen on no other band.
+pub fn attacker_trx_system(
+    model_config: &ModelConfig,
+    frequency: Frequency,
+    area_radius: Meter
+) -> TRXSystem {
+    if !frequency.is_gps() {
+        return ewd_trx_system(frequency, area_radius);
+    }
+
+    TRXSystem::new(
+        multi_band_tx_module(model_config.attacked_gps_bands(), area_radius),
+        RXModule::default()
+    )
+}
+
+fn gps_trx_system(tx_area_radius: Meter) -> TRXSystem {
     TRXSystem::new( 
-        tx_module(Frequency::GPS, GPS_TX_RADIUS), 
+        multi_band_tx_module(&Frequency::GPS_BANDS, tx_area_radius), 
         RXModule::default()
     )
 }
@@ -131,23 +313,39 @@ fn gps_trx_system() -> TRXSystem {
 pub fn tx_module(
     frequency: Frequency, 
     tx_area_radius: Meter
+) -> TXModule {
+    multi_band_tx_module(&[frequency], tx_area_radius)
+}
+
+pub fn multi_band_tx_module(
+    frequencies: &[Frequency], 
+    tx_area_radius: Meter
 ) -> TXModule {
     let tx_signal_strength = SignalStrength::from_area_radius(
         tx_area_radius, 
         Frequency::Control as Megahertz
     );
-    let tx_signal_strengths = FreqToStrengthMap::from([
-        (frequency, tx_signal_strength)
-    ]);
+    let tx_signal_strengths: FreqToStrengthMap = frequencies
+        .iter()
+        .map(|frequency| (*frequency, tx_signal_strength))
+        .collect();
 
     TXModule::new(tx_signal_strengths)
 }
 
 pub fn rx_module(max_gps_rx_signal_strength: SignalStrength) -> RXModule {
-    let max_rx_signal_strengths = FreqToStrengthMap::from([
-        (Frequency::Control, SignalStrength::new(10_000.0)),
-        (Frequency::GPS, max_gps_rx_signal_strength)
-    ]);
+    multi_band_rx_module(max_gps_rx_signal_strength, &[Frequency::GPS])
+}
+
+pub fn multi_band_rx_module(
+    max_gps_rx_signal_strength: SignalStrength,
+    gps_bands: &[Frequency]
+) -> RXModule {
+    let max_rx_signal_strengths: FreqToStrengthMap = gps_bands
+        .iter()
+        .map(|band| (*band, max_gps_rx_signal_strength))
+        .chain([(Frequency::Control, SignalStrength::new(10_000.0))])
+        .collect();
 
     RXModule::new(max_rx_signal_strengths)
 }
@@ -157,33 +355,216 @@ pub fn device_power_system() -> PowerSystem {
         .unwrap_or_else(|error| panic!("{}", error))
 }
 
-pub fn device_movement_system() -> MovementSystem {
-    MovementSystem::build(MAX_DRONE_SPEED)
+pub fn device_movement_system(max_speed: MeterPerSecond) -> MovementSystem {
+    MovementSystem::build(max_speed)
         .unwrap_or_else(|error| panic!("{}", error))
 }
 
-pub fn default_network_position(network_origin: Point3D) -> NetworkPosition {
+pub fn drone_payload(model_config: &ModelConfig) -> Option<Payload> {
+    (model_config.payload_mass() > 0.0)
+        .then(|| Payload::new(DRONE_PAYLOAD_ITEM, model_config.payload_mass()))
+}
+
+pub fn drone_thermal_system() -> ThermalSystem {
+    ThermalSystem::new(
+        DRONE_AMBIENT_TEMPERATURE,
+        DRONE_HEATING_RATE,
+        DRONE_COOLING_RATE,
+        DRONE_THROTTLE_TEMPERATURE,
+        DRONE_THROTTLE_FACTOR,
+    )
+}
+
+pub fn drone_sensor_system() -> SensorSystem {
+    SensorSystem::new(
+        DRONE_SENSOR_FIELD_OF_VIEW,
+        DRONE_SENSOR_RANGE,
+        DRONE_SENSOR_SAMPLING_PERIOD,
+    )
+}
+
+pub fn drone_flocking_system() -> FlockingSystem {
+    FlockingSystem::new(
+        DRONE_FLOCKING_NEIGHBOR_RADIUS,
+        DRONE_FLOCKING_SEPARATION,
+        DRONE_FLOCKING_ALIGNMENT,
+        DRONE_FLOCKING_COHESION,
+    )
+}
+
+pub fn drone_navigation_system(
+    rejects_spoofed_fixes: bool
+) -> NavigationSystem {
+    NavigationSystem::new(
+        DRONE_NAVIGATION_MAX_JUMP,
+        DRONE_NAVIGATION_MAX_RANGE_ERROR,
+        rejects_spoofed_fixes,
+    )
+}
+
+pub fn device_channel_system() -> ChannelSystem {
+    ChannelSystem::new(Frequency::Backup, DEVICE_NOISE_TOLERANCE)
+}
+
+pub fn drone_gossip_system() -> GossipSystem {
+    GossipSystem::new(DRONE_GOSSIP_PERIOD, DRONE_GOSSIP_FANOUT)
+}
+
+pub fn drone_firmware_system(version: FirmwareVersion) -> FirmwareSystem {
+    FirmwareSystem::new(version, None)
+}
+
+fn cc_firmware_system(model_config: &ModelConfig) -> FirmwareSystem {
+    let rollout = model_config
+        .firmware_update_time()
+        .map(|time| {
+            let update = FirmwareUpdate::new(
+                DEVICE_FIRMWARE_VERSION + 1,
+                FIRMWARE_UPDATE_INSTALL_TIME,
+                FIRMWARE_UPDATE_INSTALL_POWER
+            );
+
+            (time, update)
+        });
+
+    FirmwareSystem::new(DEVICE_FIRMWARE_VERSION, rollout)
+}
+
+pub fn mission_signal_loss_response() -> SignalLossResponse {
+    SignalLossResponse::ContinueMission(DRONE_MISSION_PATIENCE)
+}
+
+pub fn land_signal_loss_response() -> SignalLossResponse {
+    SignalLossResponse::Land(DRONE_LANDING_PATIENCE)
+}
+
+pub fn collision_policy(
+    destroy_probability: f64,
+    damage_probability: f64
+) -> CollisionPolicy {
+    CollisionPolicy::new(
+        COLLISION_DISTANCE,
+        destroy_probability,
+        damage_probability,
+        COLLISION_DAMAGE,
+    )
+}
+
+// The first drone leads the rest of them.
+pub fn drone_formations(
+    model_config: &ModelConfig,
+    drones: &[Device]
+) -> Vec<Formation> {
+    let Some(formation_shape) = model_config.formation_shape() else {
+        return Vec::new();
+    };
+    let Some((leader, members)) = drones.split_first() else {
+        return Vec::new();
+    };
+
+    vec![
+        Formation::new(
+            leader.id(),
+            members.iter().map(Device::id).collect(),
+            formation_shape,
+            FORMATION_SPACING,
+        )
+    ]
+}
+
+// Network origin and spawn spread given in `model_config` take precedence
+// over the experiment ones.
+pub fn experiment_network_position(
+    model_config: &ModelConfig,
+    default_network_origin: Point3D
+) -> NetworkPosition {
+    let origin = model_config
+        .network_origin()
+        .unwrap_or(default_network_origin);
+    let spread = model_config
+        .spawn_spread()
+        .unwrap_or(DEFAULT_SPAWN_SPREAD);
+
     NetworkPosition::new(
-        network_origin,
-        -40.0..40.0,
-        -40.0..40.0,
-        -20.0..20.0,
+        origin,
+        -spread.x..spread.x,
+        -spread.y..spread.y,
+        -spread.z..spread.z,
     )
 }
 
 pub fn default_gps() -> GPS {
+    gps(DEFAULT_GPS_POSITION_IN_METERS, GPS_TX_RADIUS)
+}
+
+pub fn gps(position: Point3D, tx_area_radius: Meter) -> GPS {
     let device = DeviceBuilder::new()
-        .set_real_position(DEFAULT_GPS_POSITION_IN_METERS)
+        .set_real_position(position)
         .set_signal_loss_response(SignalLossResponse::Ignore)
         .set_power_system(device_power_system())
-        .set_trx_system(gps_trx_system())
+        .set_trx_system(gps_trx_system(tx_area_radius))
         .build();
 
     GPS::new(device)
 }
 
+// Attackers given in `model_config` are added to the experiment ones.
+pub fn experiment_attacker_devices(
+    model_config: &ModelConfig,
+    mut attacker_devices: Vec<AttackerDevice>
+) -> Vec<AttackerDevice> {
+    attacker_devices.extend(
+        model_config
+            .attackers()
+            .iter()
+            .map(|attacker_config|
+                attacker_device(model_config, attacker_config)
+            )
+    );
+
+    attacker_devices
+}
+
+fn attacker_device(
+    model_config: &ModelConfig,
+    attacker_config: &AttackerConfig
+) -> AttackerDevice {
+    let (frequency, attack_type) = match attacker_config.kind() {
+        AttackerKind::ControlJamming => 
+            (Frequency::Control, AttackType::ElectronicWarfare),
+        AttackerKind::GPSJamming     => 
+            (Frequency::GPS, AttackType::ElectronicWarfare),
+        AttackerKind::GPSSpoofing    => 
+            (Frequency::GPS, AttackType::GPSSpoofing(SPOOFED_GPS_POSITION)),
+    };
+    let device = DeviceBuilder::new()
+        .set_real_position(attacker_config.position())
+        .set_power_system(device_power_system())
+        .set_trx_system(attacker_trx_system(
+            model_config,
+            frequency,
+            attacker_config.area_radius()
+        ))
+        .build();
+
+    AttackerDevice::new(device, attack_type)
+}
+
+// Scenario given in `model_config` takes precedence over the experiment one.
+pub fn experiment_scenario(
+    model_config: &ModelConfig,
+    default_scenario: fn() -> Scenario
+) -> Scenario {
+    model_config
+        .scenario()
+        .cloned()
+        .unwrap_or_else(default_scenario)
+}
+
 pub fn attack_scenario() -> Scenario {
-    Scenario::from([(0, BROADCAST_ID, Task::Attack(DRONE_DESTINATION))])
+    Scenario::from([
+        (Millisecond::ZERO, BROADCAST_ID, Task::Attack(DRONE_DESTINATION))
+    ])
 }
 
 pub fn reposition_scenario() -> Scenario {
@@ -193,28 +574,28 @@ pub fn reposition_scenario() -> Scenario {
     let task4 = task1;
 
     Scenario::from([
-        (0, BROADCAST_ID, task1),
-        (250, BROADCAST_ID, task2),
-        (4000, BROADCAST_ID, task3),
-        (6000, BROADCAST_ID, task4),
+        (Millisecond::ZERO, BROADCAST_ID, task1),
+        (Millisecond::new(250), BROADCAST_ID, task2),
+        (Millisecond::new(4000), BROADCAST_ID, task3),
+        (Millisecond::new(6000), BROADCAST_ID, task4),
     ])
 }
 
 
 pub struct NetworkPosition {
     origin: Point3D,
-    x_offset_range: Range<f32>,
-    y_offset_range: Range<f32>,
-    z_offset_range: Range<f32>,
+    x_offset_range: Range<Float>,
+    y_offset_range: Range<Float>,
+    z_offset_range: Range<Float>,
 }
 
 impl NetworkPosition {
     #[must_use]
     pub fn new(
         origin: Point3D,
-        x_offset_range: Range<f32>,
-        y_offset_range: Range<f32>,
-        z_offset_range: Range<f32>,
+        x_offset_range: Range<Float>,
+        y_offset_range: Range<Float>,
+        z_offset_range: Range<Float>,
     ) -> Self {
         Self { 
             origin, 
@@ -0,0 +1,53 @@
+use std::path::Path;
+
+use crate::frontend::config::ModelPlayerConfig;
+use crate::frontend::player::{IterationReader, ReplayPlayer};
+use crate::frontend::renderer::PlottersRenderer;
+use crate::frontend::summary::PlayOutcome;
+
+use super::ExampleError;
+
+
+pub fn replay(
+    iteration_directory: &Path,
+    model_player_config: &ModelPlayerConfig,
+) -> Result<PlayOutcome, ExampleError> {
+    let iteration_reader = IterationReader::new(iteration_directory)
+        .map_err(|source| ExampleError::Input {
+            path: iteration_directory.to_path_buf(),
+            source
+        })?;
+
+    let renderer = model_player_config
+        .render_config() 
+        .map(|render_config|
+            PlottersRenderer::new(
+                &model_player_config.output_filename("replay.gif"),
+                render_config.plot_caption(),
+                render_config.plot_resolution(),
+                render_config.axes_ranges(),
+                render_config.device_coloring(),
+                render_config.camera_angle(),
+                model_player_config.frame_delay()
+            )
+                .set_theme(render_config.theme().clone())
+                .set_draw_connections(render_config.draw_connections())
+                .set_draw_labels(render_config.draw_labels())
+                .set_draw_legend(render_config.draw_legend())
+                .set_draw_velocities(render_config.draw_velocities())
+                .set_draw_gps(render_config.draw_gps())
+                .set_draw_dashboard(render_config.draw_dashboard())
+                .set_frame_output(
+                    render_config.frame_directory(), 
+                    render_config.frame_format()
+                )
+        );
+
+    let mut replay_player = ReplayPlayer::new(
+        model_player_config,
+        iteration_reader,
+        renderer,
+    )?;
+
+    Ok(replay_player.play()?)
+}
@@ -0,0 +1,117 @@
+use std::path::Path;
+
+use crate::backend::device::Device;
+use crate::backend::mathphysics::{Frequency, Point3D, Position};
+use crate::backend::networkmodel::NetworkModel;
+use crate::backend::networkmodel::attack::AttackType;
+use crate::backend::networkmodel::validation::validate;
+use crate::frontend::player::{read_network_model, OutputError};
+
+
+// Prints the summary of the model and the issues found in it. Returns
+// whether the model is valid.
+/// # Errors
+///
+/// Will return `Err` if the model can not be read.
+pub fn inspect(model_path: &Path) -> Result<bool, OutputError> {
+    let network_model = read_network_model(model_path)?;
+
+    print_summary(model_path, &network_model);
+
+    let issues = validate(&network_model);
+
+    if issues.is_empty() {
+        println!("Issues: none");
+    } else {
+        println!("Issues:");
+
+        for issue in &issues {
+            println!("  {issue}");
+        }
+    }
+
+    Ok(issues.is_empty())
+}
+
+
+fn print_summary(model_path: &Path, network_model: &NetworkModel) {
+    let drones: Vec<&Device> = network_model.drones().collect();
+    let infected_count = drones
+        .iter()
+        .filter(|drone| drone.is_infected())
+        .count();
+    let shut_down_count = drones
+        .iter()
+        .filter(|drone| drone.is_shut_down())
+        .count();
+
+    println!("Model: {}", model_path.display());
+    println!("Time: {} ms", network_model.current_time());
+    println!("Topology: {:?}", network_model.connections().topology());
+
+    match network_model.command_device() {
+        Some(command_center) => println!(
+            "Command center: device {} at {}, {}",
+            command_center.id(),
+            point(command_center.position()),
+            area_radii(command_center)
+        ),
+        None                 => println!("Command center: missing"),
+    }
+
+    println!(
+        "Drones: {} ({infected_count} infected, {shut_down_count} shut down)",
+        drones.len()
+    );
+
+    if network_model.attacker_devices().is_empty() {
+        println!("Attackers: none");
+    } else {
+        println!("Attackers:");
+
+        for attacker_device in network_model.attacker_devices() {
+            let device = attacker_device.device();
+
+            println!(
+                "  device {}: {} at {}, {}",
+                device.id(),
+                attack_type_name(attacker_device.attack_type()),
+                point(device.position()),
+                area_radii(device)
+            );
+        }
+    }
+
+    let gps = network_model.gps().device();
+
+    println!("GPS: at {}, {}", point(gps.position()), area_radii(gps));
+    println!("Scenario entries: {}", network_model.scenario().iter().count());
+    println!("Formations: {}", network_model.formations().len());
+    println!(
+        "Connections: {}",
+        network_model.connections().graph_map().edge_count()
+    );
+}
+
+fn point(point: &Point3D) -> String {
+    format!("({:.1}, {:.1}, {:.1})", point.x, point.y, point.z)
+}
+
+fn area_radii(device: &Device) -> String {
+    format!(
+        "control area radius {:.1} m, GPS area radius {:.1} m",
+        device.area_radius_on(device.control_frequency()),
+        device.area_radius_on(Frequency::GPS)
+    )
+}
+
+fn attack_type_name(attack_type: AttackType) -> String {
+    match attack_type {
+        AttackType::ElectronicWarfare             =>
+            "electronic warfare".to_string(),
+        AttackType::GPSSpoofing(spoofed_position) =>
+            format!("GPS spoofing to {}", point(&spoofed_position)),
+        AttackType::MalwareDistribution(malware)  =>
+            format!("{} malware distribution", malware.malware_type()),
+    }
+}
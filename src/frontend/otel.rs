@@ -0,0 +1,63 @@
+use log::warn;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::{
+    ExporterBuildError, SpanExporter, WithExportConfig
+};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use thiserror::Error;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::{SubscriberInitExt, TryInitError};
+
+
+const SERVICE_NAME: &str = env!("CARGO_PKG_NAME");
+
+
+#[derive(Debug, Error)]
+pub enum OtelError {
+    #[error("Failed to build OTLP exporter: {0}")]
+    Exporter(#[from] ExporterBuildError),
+    #[error("Failed to install tracing subscriber: {0}")]
+    Subscriber(#[from] TryInitError),
+}
+
+
+// Exports spans of model updates to an OpenTelemetry collector over OTLP
+// HTTP. Spans are sent in batches by a background thread, so the exporter
+// has to be shut down to send the last ones.
+pub struct OtelExporter(SdkTracerProvider);
+
+impl OtelExporter {
+    /// # Errors
+    ///
+    /// Will return `Err` if the exporter can not be built or another
+    /// subscriber is already installed.
+    pub fn install(endpoint: &str) -> Result<Self, OtelError> {
+        let span_exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(endpoint)
+            .build()?;
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_batch_exporter(span_exporter)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(SERVICE_NAME)
+                    .build()
+            )
+            .build();
+        let tracer = tracer_provider.tracer(SERVICE_NAME);
+
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(tracer))
+            .try_init()?;
+
+        Ok(Self(tracer_provider))
+    }
+
+    // Sends spans that are not exported yet.
+    pub fn shutdown(self) {
+        if let Err(error) = self.0.shutdown() {
+            warn!("Failed to export remaining spans: {error}");
+        }
+    }
+}
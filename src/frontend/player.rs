@@ -1,84 +1,671 @@
-use std::path::{Path, PathBuf};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
-use log::info;
+use log::{debug, info, warn};
+use thiserror::Error;
 
 use crate::backend::ITERATION_TIME;
+use crate::backend::aggregation::Aggregator;
+use crate::backend::device::{Device, DeviceId};
+use crate::backend::device::systems::{EnergyConsumer, EnergyReport};
+use crate::backend::epidemic::EpidemicStats;
+use crate::backend::event::{Event, EventKind};
+use crate::backend::metrics::Metrics;
 use crate::backend::networkmodel::NetworkModel;
+use crate::backend::signal::{DeliveryStats, LatencyReport, LatencyStats};
 use crate::backend::mathphysics::Millisecond;
 
-use super::renderer::PlottersRenderer;
+use super::config::{ModelPlayerConfig, OutputConfig, SitlConfig};
+use super::prometheus::PrometheusExporter;
+use super::renderer::{RenderError, Renderer};
+use super::summary::{PlayOutcome, RunStatus};
+#[cfg(feature = "tui")]
+use super::tui::TuiDashboard;
+#[cfg(feature = "gui")]
+use super::viewer::run_live_viewer;
 
-use output::write_iteration_data;
+use progress::ProgressReporter;
 
 
+pub use metrics::MetricsWriter;
+pub use ns3::Ns3TraceWriter;
+#[cfg(any(feature = "kafka", feature = "mqtt"))]
+pub use publisher::{EventPublisher, PublisherError};
+#[cfg(feature = "parquet")]
+pub use parquet::{TimeSeriesError, TimeSeriesWriter};
+pub use output::{
+    read_network_model, IterationReader, IterationWriter, OutputCompression, 
+    OutputError, OutputFormat, SnapshotMode
+};
+pub use replay::ReplayPlayer;
+#[cfg(feature = "ros2")]
+pub use ros2::{Ros2Bridge, Ros2BridgeError};
+pub use sitl::SitlReceiver;
+pub use stream::{StreamError, StreamSink};
+pub use verify::DeterminismVerifier;
+
+
+mod metrics;
+mod ns3;
 mod output;
+#[cfg(feature = "parquet")]
+mod parquet;
+mod progress;
+#[cfg(any(feature = "kafka", feature = "mqtt"))]
+mod publisher;
+mod replay;
+#[cfg(feature = "ros2")]
+mod ros2;
+mod sitl;
+mod stream;
+mod verify;
+
+
+// The longest step the model takes with adaptive stepping, so outputs are
+// still written regularly.
+const MAX_ADAPTIVE_STEP: Millisecond = Millisecond::new(1000);
+
+
+// Set on Ctrl-C, so headless runs stop after the current iteration like from
+// the TUI dashboard, and their outputs are still completed.
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+
+// Errors of file outputs and rendering fail the run. Network sinks, like
+// the stream endpoint or the event broker, are dropped after the first
+// failure instead, so the run goes on without them.
+#[derive(Debug, Error)]
+pub enum PlayError {
+    #[error("Failed to create `{}`: {source}", path.display())]
+    OutputDirectory {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    IterationData(#[from] crate::Error),
+    #[error("Failed to read iteration {0}: {1}")]
+    Replay(Millisecond, #[source] OutputError),
+    #[error("Failed to write metrics: {0}")]
+    Metrics(#[source] std::io::Error),
+    #[error("Failed to write ns-3 traces: {0}")]
+    Ns3Trace(#[source] std::io::Error),
+    #[cfg(feature = "parquet")]
+    #[error(transparent)]
+    TimeSeries(#[from] TimeSeriesError),
+    #[error(transparent)]
+    Render(#[from] RenderError),
+}
+
+
+// Returns whether a stop was already requested.
+pub fn request_stop() -> bool {
+    STOP_REQUESTED.swap(true, Ordering::Relaxed)
+}
+
+fn stop_requested() -> bool {
+    STOP_REQUESTED.load(Ordering::Relaxed)
+}
+
+fn create_metrics_writer(
+    output_config: &OutputConfig
+) -> Result<Option<MetricsWriter>, PlayError> {
+    output_config
+        .metrics_output_path()
+        .map(MetricsWriter::new)
+        .transpose()
+        .map_err(PlayError::Metrics)
+}
+
+// The listed devices are marked as externally driven before the receiver is
+// bound, so positions are not dropped.
+fn create_sitl_receiver(
+    sitl_config: &SitlConfig,
+    network_model: &mut NetworkModel
+) -> Option<SitlReceiver> {
+    for &device_id in sitl_config.device_ids() {
+        if !network_model.set_externally_driven(device_id) {
+            warn!("Device {device_id} to drive from SITL does not exist");
+        }
+    }
+
+    SitlReceiver::bind(sitl_config.address())
+        .inspect_err(|error| warn!("Failed to bind SITL socket: {error}"))
+        .ok()
+}
+
+fn create_prometheus_exporter(
+    output_config: &OutputConfig
+) -> Option<PrometheusExporter> {
+    output_config
+        .prometheus_address()
+        .and_then(|prometheus_address|
+            PrometheusExporter::bind(prometheus_address)
+                .inspect_err(|error|
+                    warn!("Failed to bind Prometheus endpoint: {error}")
+                )
+                .ok()
+        )
+}
 
 
-pub struct ModelPlayer<'a> {
-    json_output_directory: Option<PathBuf>,
+pub struct ModelPlayer<R: Renderer> {
+    iteration_writer: Option<IterationWriter>,
+    metrics_writer: Option<MetricsWriter>,
+    ns3_trace_writer: Option<Ns3TraceWriter>,
+    #[cfg(feature = "parquet")]
+    time_series_writer: Option<TimeSeriesWriter>,
+    stream_sink: Option<StreamSink>,
+    #[cfg(any(feature = "kafka", feature = "mqtt"))]
+    event_publisher: Option<EventPublisher>,
+    #[cfg(feature = "ros2")]
+    ros2_bridge: Option<Ros2Bridge>,
+    sitl_receiver: Option<SitlReceiver>,
+    prometheus_exporter: Option<PrometheusExporter>,
     network_model: NetworkModel,
-    renderer: Option<PlottersRenderer<'a>>,
+    // Infection events of the whole run, the rest of the events are only
+    // kept for one iteration.
+    infection_events: Vec<Event>,
+    renderer: Option<R>,
+    render_interval: u32,
     current_time: Millisecond,
     end_time: Millisecond,
+    verify_determinism: bool,
+    adaptive_stepping: bool,
+    real_time_speed: Option<f32>,
+    #[cfg(feature = "gui")]
+    live_viewer: bool,
+    #[cfg(feature = "tui")]
+    tui_dashboard: bool,
 }
 
-impl<'a> ModelPlayer<'a> {
-    #[must_use]
+impl<R: Renderer> ModelPlayer<R> {
+    /// # Errors
+    ///
+    /// Will return `Err` if the output directory or an output file can not be
+    /// created.
     pub fn new(
-        json_output_directory: Option<&Path>,
-        network_model: NetworkModel,
-        renderer: Option<PlottersRenderer<'a>>,
-        end_time: Millisecond,
-    ) -> Self {
-        Self {
-            json_output_directory: json_output_directory.map(Path::to_path_buf),
+        model_player_config: &ModelPlayerConfig,
+        mut network_model: NetworkModel,
+        renderer: Option<R>,
+    ) -> Result<Self, PlayError> {
+        let output_config = model_player_config.output_config();
+
+        network_model.set_latency_tracking(
+            model_player_config.latency_tracking()
+        );
+
+        if !model_player_config.fault_injector().is_empty() {
+            network_model.set_fault_injector(
+                model_player_config.fault_injector().clone()
+            );
+        }
+        if let Some(coverage_area) = model_player_config.coverage_area() {
+            network_model.set_coverage_area(*coverage_area);
+        }
+        if !model_player_config.weather().is_clear() {
+            network_model.set_weather(*model_player_config.weather());
+        }
+        if !model_player_config.link_loss().is_empty() {
+            network_model.set_link_loss(
+                model_player_config.link_loss().clone()
+            );
+        }
+        if !model_player_config.landing_zones().is_empty() {
+            network_model.set_landing_zones(
+                model_player_config.landing_zones().to_vec()
+            );
+        }
+        if let Some(propagation) = model_player_config.propagation() {
+            network_model.set_propagation(*propagation);
+        }
+        if let Some(interference) = model_player_config.interference() {
+            network_model.set_interference(*interference);
+        }
+        if model_player_config.aggregation_interval() > Millisecond::ZERO {
+            network_model.set_aggregator(
+                Aggregator::new(model_player_config.aggregation_interval())
+            );
+        }
+        if let Some(provenance) = model_player_config.provenance() {
+            network_model.set_provenance(provenance.clone());
+        }
+
+        // Deltas are built from the changes of each update.
+        let writes_deltas = output_config.json_output_directory().is_some()
+            && output_config.output_format() == OutputFormat::Json
+            && output_config.snapshot_mode() == SnapshotMode::Delta;
+        let streams_deltas = output_config.stream_endpoint().is_some()
+            && output_config.stream_snapshot_mode() == SnapshotMode::Delta;
+
+        network_model.set_change_tracking(writes_deltas || streams_deltas);
+
+        let iteration_writer = output_config
+            .json_output_directory()
+            .map(|json_output_directory|
+                std::fs::create_dir_all(json_output_directory)
+                    .map(|()| IterationWriter::new(
+                        json_output_directory,
+                        output_config.output_format(),
+                        output_config.output_compression(),
+                        output_config.snapshot_mode()
+                    ))
+                    .map_err(|source| PlayError::OutputDirectory {
+                        path: json_output_directory.to_path_buf(),
+                        source
+                    })
+            )
+            .transpose()?;
+
+        let ns3_trace_writer = output_config
+            .ns3_trace_directory()
+            .map(|ns3_trace_directory| {
+                network_model.set_packet_tracing(true);
+
+                Ns3TraceWriter::new(ns3_trace_directory, &network_model)
+                    .map_err(PlayError::Ns3Trace)
+            })
+            .transpose()?;
+
+        let sitl_receiver = model_player_config
+            .sitl_config()
+            .and_then(|sitl_config|
+                create_sitl_receiver(sitl_config, &mut network_model)
+            );
+
+        let stream_sink = output_config
+            .stream_endpoint()
+            .and_then(|stream_endpoint|
+                StreamSink::connect(
+                    stream_endpoint,
+                    output_config.stream_snapshot_mode()
+                )
+                    .inspect_err(|error|
+                        warn!("Failed to connect to stream endpoint: {error}")
+                    )
+                    .ok()
+            );
+
+        Ok(Self {
+            iteration_writer,
+            metrics_writer: create_metrics_writer(output_config)?,
+            ns3_trace_writer,
+            #[cfg(feature = "parquet")]
+            time_series_writer: output_config
+                .parquet_output_directory()
+                .map(TimeSeriesWriter::new),
+            stream_sink,
+            #[cfg(any(feature = "kafka", feature = "mqtt"))]
+            event_publisher: output_config
+                .event_endpoint()
+                .and_then(|event_endpoint|
+                    EventPublisher::connect(event_endpoint)
+                        .inspect_err(|error|
+                            warn!("Failed to connect to event broker: {error}")
+                        )
+                        .ok()
+                ),
+            #[cfg(feature = "ros2")]
+            ros2_bridge: output_config
+                .ros2_endpoint()
+                .and_then(|ros2_endpoint|
+                    Ros2Bridge::connect(ros2_endpoint)
+                        .inspect_err(|error|
+                            warn!("Failed to connect to ROS 2: {error}")
+                        )
+                        .ok()
+                ),
+            sitl_receiver,
+            prometheus_exporter: create_prometheus_exporter(output_config),
             network_model,
+            infection_events: Vec::new(),
             renderer,
-            current_time: 0,
-            end_time,
-        }
+            render_interval: model_player_config.render_interval(),
+            current_time: Millisecond::ZERO,
+            end_time: model_player_config.simulation_time(),
+            verify_determinism: model_player_config.verify_determinism(),
+            adaptive_stepping: model_player_config.adaptive_stepping(),
+            real_time_speed: model_player_config
+                .real_time_pacing()
+                .then(|| model_player_config.playback_speed())
+                .filter(|real_time_speed| *real_time_speed > 0.0),
+            #[cfg(feature = "gui")]
+            live_viewer: model_player_config.live_viewer(),
+            #[cfg(feature = "tui")]
+            tui_dashboard: model_player_config.tui_dashboard(),
+        })
     }
 
-    /// # Panics
+    /// # Errors
     ///
-    /// Will panic if an error occurs during rendering. 
-    pub fn play(&mut self) {
+    /// Will return `Err` if an output file can not be written or an error
+    /// occurs during rendering.
+    pub fn play(&mut self) -> Result<PlayOutcome, PlayError> {
+        #[cfg(feature = "gui")]
+        if self.live_viewer {
+            self.play_in_live_viewer();
+            return Ok(PlayOutcome::new(RunStatus::Completed, None, None));
+        }
+
         self.start_info();
 
-        if let Some(json_output_directory) = &self.json_output_directory {
-            let _ = std::fs::create_dir_all(json_output_directory);
-        }
+        let mut determinism_verifier = self.verify_determinism.then(||
+            DeterminismVerifier::record_reference(
+                &self.network_model,
+                self.end_time
+            )
+        );
+
+        #[cfg(feature = "tui")]
+        let mut tui_dashboard = self.tui_dashboard
+            .then(|| 
+                TuiDashboard::new()
+                    .inspect_err(|error| 
+                        warn!("Failed to start TUI dashboard: {error}")
+                    )
+                    .ok()
+            )
+            .flatten();
 
-        for _ in (0..self.end_time).step_by(ITERATION_TIME as usize) {
-            info!("Current time: {}", self.current_time);
+        let started_at = Instant::now();
+        let mut iteration: u32 = 0;
+        let mut progress_reporter = ProgressReporter::new(self.end_time);
 
-            if let Some(
-                ref json_output_directory
-            ) = self.json_output_directory {
-                write_iteration_data(
-                    json_output_directory,
+        while self.current_time < self.end_time {
+            debug!("Current time: {}", self.current_time);
+
+            if let Some(ref mut determinism_verifier) = determinism_verifier {
+                determinism_verifier.check_iteration(
                     &self.network_model,
                     self.current_time
                 );
             }
 
-            self.network_model.update();
+            self.write_iteration_data()?;
+
+            if let Some(ref mut metrics_writer) = self.metrics_writer {
+                metrics_writer
+                    .write_iteration_metrics(&self.network_model)
+                    .map_err(PlayError::Metrics)?;
+            }
+
+            #[cfg(feature = "parquet")]
+            if let Some(ref mut time_series_writer) = self.time_series_writer {
+                time_series_writer.record_iteration(&self.network_model);
+            }
+
+            self.stream_iteration_data();
+            self.receive_sitl_positions();
+
+            let step = if self.adaptive_stepping {
+                self.network_model.update_adaptive(
+                    (self.end_time - self.current_time).min(MAX_ADAPTIVE_STEP)
+                )
+            } else {
+                self.network_model.update();
+
+                ITERATION_TIME
+            };
 
-            if let Some(ref mut renderer) = self.renderer {
-                renderer.render(&self.network_model);
+            for error in self.network_model.errors() {
+                warn!("{error}");
             }
+
+            self.record_infection_events();
+
+            if let Some(ref mut ns3_trace_writer) = self.ns3_trace_writer {
+                ns3_trace_writer
+                    .record_update(&self.network_model)
+                    .map_err(PlayError::Ns3Trace)?;
+            }
+
+            self.export_metrics();
+
+            #[cfg(any(feature = "kafka", feature = "mqtt"))]
+            self.publish_events();
+
+            #[cfg(feature = "ros2")]
+            self.publish_to_ros2();
+
+            self.render_iteration(iteration)?;
                         
-            self.current_time += ITERATION_TIME;
+            self.current_time += step;
+            iteration = iteration.wrapping_add(1);
+            progress_reporter.report(self.current_time);
+
+            #[cfg(feature = "tui")]
+            if self.stop_requested_in(&mut tui_dashboard) {
+                break;
+            }
+
+            if stop_requested() {
+                info!("Stopped at {}", self.current_time);
+                break;
+            }
+
+            self.wait_for_wall_clock(started_at);
+        }
+
+        if let Some(ref mut metrics_writer) = self.metrics_writer {
+            metrics_writer.flush().map_err(PlayError::Metrics)?;
+        }
+
+        if let Some(ref mut ns3_trace_writer) = self.ns3_trace_writer {
+            ns3_trace_writer.flush().map_err(PlayError::Ns3Trace)?;
+        }
+
+        #[cfg(feature = "parquet")]
+        if let Some(ref time_series_writer) = self.time_series_writer {
+            time_series_writer.finish()?;
+        }
+
+        if let Some(ref mut stream_sink) = self.stream_sink {
+            stream_sink.close();
+        }
+
+        #[cfg(any(feature = "kafka", feature = "mqtt"))]
+        if let Some(event_publisher) = self.event_publisher.take() {
+            event_publisher.close();
+        }
+
+        #[cfg(feature = "ros2")]
+        if let Some(ros2_bridge) = self.ros2_bridge.take() {
+            ros2_bridge.close();
+        }
+
+        if let Some(ref determinism_verifier) = determinism_verifier {
+            determinism_verifier.report();
+        }
+
+        if let Some(ref mut renderer) = self.renderer {
+            renderer.finish();
+        }
+
+        let epidemic_stats = self.epidemic_stats();
+        let latency_report = self.network_model
+            .latency_stats()
+            .and_then(LatencyStats::report);
+        let energy_report = self.energy_report();
+        let delivery_stats = *self.network_model.delivery_stats();
+
+        self.end_info(
+            epidemic_stats.as_ref(),
+            latency_report.as_ref(),
+            &energy_report,
+            &delivery_stats
+        );
+
+        // The loop is only left early when the user stops the run from the
+        // TUI dashboard or with Ctrl-C.
+        let status = if self.current_time < self.end_time {
+            RunStatus::Stopped
+        } else {
+            RunStatus::Completed
+        };
+
+        let play_outcome = PlayOutcome::new(
+            status,
+            Some(Metrics::from_model(&self.network_model)),
+            self.renderer
+                .as_ref()
+                .and_then(Renderer::output_filename)
+        )
+            .set_epidemic_stats(epidemic_stats)
+            .set_latency_report(latency_report)
+            .set_energy_report(Some(energy_report))
+            .set_delivery_stats(Some(delivery_stats));
+
+        Ok(play_outcome)
+    }
+
+    // Outputs are not written, since the user controls the simulation time
+    // in the viewer.
+    #[cfg(feature = "gui")]
+    fn play_in_live_viewer(&self) {
+        info!("Playing in live viewer");
+
+        let _ = run_live_viewer(self.network_model.clone(), self.end_time)
+            .inspect_err(|error| warn!("Live viewer failed: {error}"));
+    }
+
+    // The dashboard is closed after the first failure, so the simulation 
+    // goes on without it.
+    #[cfg(feature = "tui")]
+    fn stop_requested_in(
+        &self,
+        tui_dashboard: &mut Option<TuiDashboard>
+    ) -> bool {
+        let Some(dashboard) = tui_dashboard else {
+            return false;
+        };
+
+        dashboard.record_iteration(&self.network_model);
+
+        match dashboard
+            .draw(&self.network_model, self.end_time)
+            .and_then(|()| dashboard.quit_requested())
+        {
+            Ok(quit_requested) => quit_requested,
+            Err(error)         => {
+                *tui_dashboard = None;
+                warn!("Closed TUI dashboard: {error}");
+                false
+            }
+        }
+    }
+
+    fn render_iteration(&mut self, iteration: u32) -> Result<(), RenderError> {
+        if !iteration.is_multiple_of(self.render_interval) {
+            return Ok(());
+        }
+
+        if let Some(ref mut renderer) = self.renderer {
+            renderer.render(&self.network_model)?;
+        }
+
+        Ok(())
+    }
+
+    // Sleeps until the wall-clock time since `started_at` catches up with the
+    // simulation time. If an iteration takes longer, the next one is not
+    // delayed.
+    fn wait_for_wall_clock(&self, started_at: Instant) {
+        let Some(real_time_speed) = self.real_time_speed else {
+            return;
+        };
+
+        let simulation_time = Duration::from_millis(
+            u64::try_from(self.current_time.value()).unwrap_or_default()
+        );
+        let wall_clock_time = simulation_time.div_f32(real_time_speed);
+
+        if let Some(remaining_time) = wall_clock_time.checked_sub(
+            started_at.elapsed()
+        ) {
+            std::thread::sleep(remaining_time);
         }
+    }
 
-        self.end_info();
+    // Streaming stops after the first failure, so a disconnected dashboard
+    // does not slow down the simulation.
+    fn stream_iteration_data(&mut self) {
+        let Some(ref mut stream_sink) = self.stream_sink else {
+            return;
+        };
+
+        if let Err(error) = stream_sink.send_iteration_data(
+            &self.network_model,
+            self.current_time
+        ) {
+            warn!("Stopped streaming iteration data: {error}");
+            self.stream_sink = None;
+        }
+    }
+
+    #[cfg(any(feature = "kafka", feature = "mqtt"))]
+    fn publish_events(&mut self) {
+        let Some(ref mut event_publisher) = self.event_publisher else {
+            return;
+        };
+
+        if let Err(error) = event_publisher.publish_events(
+            self.network_model.events()
+        ) {
+            warn!("Stopped publishing events: {error}");
+            self.event_publisher = None;
+        }
+    }
+
+    fn receive_sitl_positions(&mut self) {
+        let Some(ref mut sitl_receiver) = self.sitl_receiver else {
+            return;
+        };
+
+        if let Err(error) = sitl_receiver
+            .receive_positions(&mut self.network_model)
+        {
+            warn!("Stopped receiving SITL positions: {error}");
+            self.sitl_receiver = None;
+        }
+    }
+
+    fn write_iteration_data(&mut self) -> crate::Result<()> {
+        let Some(ref mut iteration_writer) = self.iteration_writer else {
+            return Ok(());
+        };
+
+        iteration_writer.write_iteration_data(
+            &self.network_model,
+            self.current_time
+        )
+    }
+
+    fn export_metrics(&mut self) {
+        if let Some(ref mut prometheus_exporter) = self.prometheus_exporter {
+            prometheus_exporter.record_update(&self.network_model);
+        }
+    }
+
+    #[cfg(feature = "ros2")]
+    fn publish_to_ros2(&mut self) {
+        let Some(ref mut ros2_bridge) = self.ros2_bridge else {
+            return;
+        };
+
+        if let Err(error) = ros2_bridge.publish_iteration(&self.network_model) {
+            warn!("Stopped publishing to ROS 2: {error}");
+            self.ros2_bridge = None;
+        }
     }
 
     fn start_info(&self) {
         self.renderer
             .as_ref()
-            .inspect(|renderer| {
-                info!("Rendering in {}", renderer.output_filename());
+            .and_then(Renderer::output_filename)
+            .inspect(|output_filename| {
+                info!("Rendering in {output_filename}");
             });
         info!(
             "Initial device count: {}", 
@@ -86,16 +673,107 @@ impl<'a> ModelPlayer<'a> {
         );
     }
 
-    fn end_info(&self) {
+    fn record_infection_events(&mut self) {
+        self.infection_events.extend(
+            self.network_model
+                .events()
+                .iter()
+                .filter(|event|
+                    matches!(event.kind(), EventKind::Infected { .. })
+                )
+        );
+    }
+
+    // Runs without infections have no epidemic to describe.
+    fn epidemic_stats(&self) -> Option<EpidemicStats> {
+        if self.infection_events.is_empty() {
+            return None;
+        }
+
+        let drone_ids: BTreeSet<DeviceId> = self.network_model
+            .drones()
+            .map(Device::id)
+            .collect();
+
+        Some(EpidemicStats::from_events(&self.infection_events, &drone_ids))
+    }
+
+    fn energy_report(&self) -> EnergyReport {
+        EnergyReport::new(
+            self.network_model
+                .device_map()
+                .iter()
+                .map(|(device_id, device)| (device_id, *device.energy_usage()))
+                .collect()
+        )
+    }
+
+    fn end_info(
+        &self,
+        epidemic_stats: Option<&EpidemicStats>,
+        latency_report: Option<&LatencyReport>,
+        energy_report: &EnergyReport,
+        delivery_stats: &DeliveryStats
+    ) {
         info!("Simulation finished at {}", self.current_time);
         info!(
             "Conclusive device count: {}", 
             self.network_model.device_map().len()
         );
+
+        if let Some(epidemic_stats) = epidemic_stats {
+            info!(
+                "Infected drones: {} of {}",
+                epidemic_stats.infected_count(),
+                epidemic_stats.population()
+            );
+            if let Some(time) = epidemic_stats.time_to_full_compromise() {
+                info!("Time to full compromise: {time}");
+            }
+            if let Some(r0) = epidemic_stats.reproduction_number() {
+                info!("Estimated R0: {r0:.2}");
+            }
+        }
+
+        if let Some(latency_report) = latency_report {
+            let overall = latency_report.overall();
+
+            info!(
+                "Signal latency: p50 {}, p90 {}, p99 {}, max {} ({} signals)",
+                overall.p50(),
+                overall.p90(),
+                overall.p99(),
+                overall.max(),
+                overall.count()
+            );
+        }
+
+        if let Some(delivery_rate) = delivery_stats.delivery_rate() {
+            info!(
+                "Signal delivery rate: {:.1}% ({} received, {} dropped)",
+                delivery_rate * 100.0,
+                delivery_stats.received(),
+                delivery_stats.dropped()
+            );
+        }
+
+        let energy_usage = energy_report.total();
+
+        info!(
+            "Energy used: {:.1} J (movement {:.1} J, processing {:.1} J, \
+            TX {:.1} J, passive {:.1} J)",
+            energy_usage.total(),
+            energy_usage.of(EnergyConsumer::Movement),
+            energy_usage.of(EnergyConsumer::Processing),
+            energy_usage.of(EnergyConsumer::TX),
+            energy_usage.of(EnergyConsumer::Passive)
+        );
+
         self.renderer
             .as_ref()
-            .inspect(|renderer| {
-                info!("Render filename: {}", renderer.output_filename());
+            .and_then(Renderer::output_filename)
+            .inspect(|output_filename| {
+                info!("Render filename: {output_filename}");
             });
     }
 }
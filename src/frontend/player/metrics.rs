@@ -0,0 +1,47 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::backend::metrics::{Metrics, CSV_HEADER};
+use crate::backend::networkmodel::NetworkModel;
+
+
+pub struct MetricsWriter {
+    writer: BufWriter<File>,
+}
+
+impl MetricsWriter {
+    /// # Errors
+    ///
+    /// Will return `Err` if the file can not be created.
+    pub fn new(metrics_output_path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = metrics_output_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut writer = BufWriter::new(File::create(metrics_output_path)?);
+        writeln!(writer, "{CSV_HEADER}")?;
+
+        Ok(Self { writer })
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the record can not be written.
+    pub fn write_iteration_metrics(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> std::io::Result<()> {
+        let metrics = Metrics::from_model(network_model);
+
+        writeln!(self.writer, "{}", metrics.to_csv_record())
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if buffered records can not be written.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::backend::device::{Device, DeviceId, BROADCAST_ID};
+use crate::backend::mathphysics::{Millisecond, Point3D, Position};
+use crate::backend::networkmodel::NetworkModel;
+use crate::backend::signal::{Data, PacketEvent, PacketEventKind};
+
+
+const MOBILITY_FILE_NAME: &str = "mobility.tcl";
+const PACKETS_FILE_NAME: &str  = "packets.tr";
+
+
+// ns-3 prints times in seconds.
+fn seconds(time: Millisecond) -> String {
+    format!("{}.{:03}", time.value() / 1000, time.value() % 1000)
+}
+
+fn data_name(data: &Data) -> &'static str {
+    match data {
+        Data::FirmwareUpdate(_) => "firmware_update",
+        Data::GPS(_)            => "gps",
+        Data::Gossip(_)         => "gossip",
+        Data::Malware(_)        => "malware",
+        Data::Recon(_)          => "recon",
+        Data::ReconSummary(_)   => "recon_summary",
+        Data::SetTask(_)        => "set_task",
+        Data::SwitchChannel(_)  => "switch_channel",
+        Data::TimeBeacon(_)     => "time_beacon",
+        Data::Noise             => "noise",
+    }
+}
+
+// Trace operations and trace sources follow `AsciiTraceHelper` output.
+fn operation_and_context(kind: PacketEventKind) -> (char, &'static str) {
+    match kind {
+        PacketEventKind::Transmitted => ('t', "Phy/Tx"),
+        PacketEventKind::Received    => ('r', "Phy/Rx"),
+        PacketEventKind::Dropped     => ('d', "Phy/RxDrop"),
+    }
+}
+
+fn network_devices(network_model: &NetworkModel) -> Vec<&Device> {
+    network_model
+        .device_map()
+        .values()
+        .chain(
+            network_model
+                .attacker_devices()
+                .iter()
+                .map(|attacker_device| attacker_device.device())
+        )
+        .chain([network_model.gps().device()])
+        .collect()
+}
+
+
+// Writes the run as ns-3 traces, so the radio and signal queue models can be
+// compared with an ns-3 simulation of the same scenario:
+// * `mobility.tcl` - ns-2 mobility trace read by `Ns2MobilityHelper`. Node
+//   positions are set every iteration they change;
+// * `packets.tr` - ASCII packet trace with transmissions, receptions and
+//   drops of signals.
+// ns-3 nodes are numbered from zero, so devices, attackers and the GPS
+// device get node indices in order of their IDs. The mapping is listed in
+// comments at the start of the mobility trace.
+pub struct Ns3TraceWriter {
+    mobility_writer: BufWriter<File>,
+    packets_writer: BufWriter<File>,
+    node_indices: BTreeMap<DeviceId, usize>,
+    positions: Vec<Point3D>,
+}
+
+impl Ns3TraceWriter {
+    /// # Errors
+    ///
+    /// Will return `Err` if the trace files can not be created or written.
+    pub fn new(
+        output_directory: &Path,
+        network_model: &NetworkModel
+    ) -> std::io::Result<Self> {
+        fs::create_dir_all(output_directory)?;
+
+        let mut devices = network_devices(network_model);
+        devices.sort_by_key(|device| device.id());
+
+        let node_indices: BTreeMap<DeviceId, usize> = devices
+            .iter()
+            .enumerate()
+            .map(|(node_index, device)| (device.id(), node_index))
+            .collect();
+        let positions: Vec<Point3D> = devices
+            .iter()
+            .map(|device| *device.position())
+            .collect();
+
+        let mut mobility_writer = BufWriter::new(
+            File::create(output_directory.join(MOBILITY_FILE_NAME))?
+        );
+
+        for (device_id, node_index) in &node_indices {
+            writeln!(
+                mobility_writer,
+                "# $node_({node_index}) device {device_id}"
+            )?;
+        }
+        for (node_index, position) in positions.iter().enumerate() {
+            for (axis, coordinate) in [
+                ('X', position.x),
+                ('Y', position.y),
+                ('Z', position.z),
+            ] {
+                writeln!(
+                    mobility_writer,
+                    "$node_({node_index}) set {axis}_ {coordinate}"
+                )?;
+            }
+        }
+
+        let packets_writer = BufWriter::new(
+            File::create(output_directory.join(PACKETS_FILE_NAME))?
+        );
+
+        Ok(Self { mobility_writer, packets_writer, node_indices, positions })
+    }
+
+    // Called after each update with packet events of the update and
+    // positions at the new model time.
+    /// # Errors
+    ///
+    /// Will return `Err` if writing to a trace file fails.
+    pub fn record_update(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> std::io::Result<()> {
+        let mut packet_events = network_model.packet_events().to_vec();
+
+        // Signals created for the next iteration are recorded last, so
+        // events are sorted to keep the trace in time order.
+        packet_events.sort_by_key(PacketEvent::time);
+
+        for packet_event in &packet_events {
+            self.write_packet_event(packet_event)?;
+        }
+
+        let time = network_model.current_time();
+
+        for device in network_devices(network_model) {
+            self.write_position(time, device)?;
+        }
+
+        Ok(())
+    }
+
+    fn node_label(&self, device_id: DeviceId) -> String {
+        if device_id == BROADCAST_ID {
+            return "broadcast".to_string();
+        }
+
+        self.node_indices
+            .get(&device_id)
+            .map_or_else(
+                || format!("device_{device_id}"),
+                ToString::to_string
+            )
+    }
+
+    fn write_packet_event(
+        &mut self,
+        packet_event: &PacketEvent
+    ) -> std::io::Result<()> {
+        let Some(&node_index) = self.node_indices
+            .get(&packet_event.device_id())
+        else {
+            return Ok(());
+        };
+        let (operation, context) = operation_and_context(packet_event.kind());
+        let signal = packet_event.signal();
+        let source = self.node_label(signal.source_id());
+        let destination = self.node_label(signal.destination_id());
+
+        writeln!(
+            self.packets_writer,
+            "{operation} {} /NodeList/{node_index}/DeviceList/0/{context} \
+            drone_network::Signal (source={source} destination={destination} \
+            frequency={:?} strength={} data={})",
+            seconds(packet_event.time()),
+            signal.frequency(),
+            signal.strength().value(),
+            data_name(signal.data())
+        )
+    }
+
+    fn write_position(
+        &mut self,
+        time: Millisecond,
+        device: &Device
+    ) -> std::io::Result<()> {
+        let Some(&node_index) = self.node_indices.get(&device.id()) else {
+            return Ok(());
+        };
+        let position = *device.position();
+        let previous_position = self.positions[node_index];
+
+        for (axis, coordinate, previous_coordinate) in [
+            ('X', position.x, previous_position.x),
+            ('Y', position.y, previous_position.y),
+            ('Z', position.z, previous_position.z),
+        ] {
+            if coordinate.to_bits() != previous_coordinate.to_bits() {
+                writeln!(
+                    self.mobility_writer,
+                    "$ns_ at {} \"$node_({node_index}) set {axis}_ \
+                    {coordinate}\"",
+                    seconds(time)
+                )?;
+            }
+        }
+
+        self.positions[node_index] = position;
+
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if writing to a trace file fails.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.mobility_writer.flush()?;
+        self.packets_writer.flush()
+    }
+}
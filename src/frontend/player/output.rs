@@ -1,28 +1,421 @@
-use std::path::Path;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use serde_json::Value;
+use thiserror::Error;
 
 use crate::backend::mathphysics::Millisecond;
 use crate::backend::networkmodel::NetworkModel;
+use crate::backend::networkmodel::snapshot::{
+    DeltaEncoder, ModelDelta, SnapshotError
+};
 
 
+const BINARY_EXTENSION: &str  = "bin";
+const DELTA_EXTENSION: &str   = "delta";
+const GZIP_EXTENSION: &str    = "gz";
+const ZSTD_EXTENSION: &str    = "zst";
 const ERR_SERIALIZATION: &str = "Failed to serialize";
+const ZSTD_LEVEL: i32         = 3;
+// A full snapshot is written every `KEYFRAME_INTERVAL` iterations in delta
+// mode, so a single corrupted file does not break the rest of the output.
+pub(super) const KEYFRAME_INTERVAL: usize = 100;
+
+
+#[derive(Debug, Error)]
+pub enum OutputError {
+    #[error("Failed to access output files: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to (de)serialize output file: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Failed to (de)serialize binary output file: {0}")]
+    BinarySerialization(#[from] postcard::Error),
+    #[error("Failed to apply delta: {0}")]
+    Snapshot(#[from] SnapshotError),
+    #[error("Delta file `{0}` has no preceding full snapshot")]
+    MissingKeyframe(PathBuf),
+}
+
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    Binary,
+}
+
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputCompression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl OutputCompression {
+    fn extension(self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Gzip => Some(GZIP_EXTENSION),
+            Self::Zstd => Some(ZSTD_EXTENSION),
+        }
+    }
+
+    // Splits the compression extension from the file name.
+    fn from_file_name(file_name: &str) -> (Self, &str) {
+        if let Some(file_name) = file_name.strip_suffix(
+            &format!(".{GZIP_EXTENSION}")
+        ) {
+            (Self::Gzip, file_name)
+        } else if let Some(file_name) = file_name.strip_suffix(
+            &format!(".{ZSTD_EXTENSION}")
+        ) {
+            (Self::Zstd, file_name)
+        } else {
+            (Self::None, file_name)
+        }
+    }
+
+    fn compress(self, data: Vec<u8>) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(data),
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(
+                    Vec::new(),
+                    flate2::Compression::default()
+                );
+                encoder.write_all(&data)?;
+
+                encoder.finish()
+            },
+            Self::Zstd => zstd::encode_all(data.as_slice(), ZSTD_LEVEL),
+        }
+    }
 
+    fn decompress(self, data: Vec<u8>) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(data),
+            Self::Gzip => {
+                let mut decompressed_data = Vec::new();
+                GzDecoder::new(data.as_slice())
+                    .read_to_end(&mut decompressed_data)?;
+
+                Ok(decompressed_data)
+            },
+            Self::Zstd => zstd::decode_all(data.as_slice()),
+        }
+    }
+}
+
+
+// Deltas are stored as JSON only, because they contain arbitrary JSON values
+// that a non self-describing binary format can not deserialize.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SnapshotMode {
+    #[default]
+    Full,
+    Delta,
+}
 
-pub fn write_iteration_data(
-    json_output_directory: &Path,
-    network_model: &NetworkModel,
-    current_iteration_time: Millisecond
-) {
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum IterationFileKind {
+    Json,
+    JsonDelta,
+    Binary,
+}
+
+impl IterationFileKind {
+    fn extension(self) -> Option<&'static str> {
+        match self {
+            Self::Json      => None,
+            Self::JsonDelta => Some(DELTA_EXTENSION),
+            Self::Binary    => Some(BINARY_EXTENSION),
+        }
+    }
+
+    // Splits the file kind extension from the file name without
+    // compression extension.
+    fn from_file_name(file_name: &str) -> (Self, &str) {
+        if let Some(file_name) = file_name.strip_suffix(
+            &format!(".{DELTA_EXTENSION}")
+        ) {
+            (Self::JsonDelta, file_name)
+        } else if let Some(file_name) = file_name.strip_suffix(
+            &format!(".{BINARY_EXTENSION}")
+        ) {
+            (Self::Binary, file_name)
+        } else {
+            (Self::Json, file_name)
+        }
+    }
+}
+
+
+fn iteration_file_name(
+    current_iteration_time: Millisecond,
+    file_kind: IterationFileKind,
+    compression: OutputCompression
+) -> String {
     let local_time = chrono::Local::now()
         .format("%YY-%mm-%dd_%HH-%MM-%SS-%3ff");
 
-    let file_name = format!("{local_time}_{current_iteration_time}");
-    let file_path = json_output_directory.join(file_name);
+    let mut file_name = format!("{local_time}_{current_iteration_time}");
+
+    for extension in [file_kind.extension(), compression.extension()]
+        .into_iter()
+        .flatten()
+    {
+        file_name.push('.');
+        file_name.push_str(extension);
+    }
+
+    file_name
+}
+
+fn parse_file_kind(
+    file_path: &Path
+) -> Option<(IterationFileKind, OutputCompression, &str)> {
+    let file_name = file_path.file_name()?.to_str()?;
+    let (compression, file_name) = OutputCompression::from_file_name(
+        file_name
+    );
+    let (file_kind, file_name) = IterationFileKind::from_file_name(file_name);
+
+    Some((file_kind, compression, file_name))
+}
+
+fn parse_iteration_file_name(
+    file_path: &Path
+) -> Option<(Millisecond, IterationFileKind)> {
+    let (file_kind, _, file_name) = parse_file_kind(file_path)?;
+    let (_, iteration_time) = file_name.rsplit_once('_')?;
+
+    Some((iteration_time.parse().ok()?, file_kind))
+}
+
+// Reads the file and decompresses it according to its extension.
+fn read_decompressed(file_path: &Path) -> std::io::Result<Vec<u8>> {
+    let compression = parse_file_kind(file_path)
+        .map(|(_, compression, _)| compression)
+        .unwrap_or_default();
+
+    compression.decompress(fs::read(file_path)?)
+}
+
+
+/// # Errors
+///
+/// Will return `Err` if the file can not be read, decompressed or
+/// deserialized.
+pub fn read_network_model(
+    model_path: &Path
+) -> Result<NetworkModel, OutputError> {
+    let data = read_decompressed(model_path)?;
+
+    match parse_file_kind(model_path) {
+        Some((IterationFileKind::Binary, ..)) =>
+            Ok(NetworkModel::from_binary(&data)?),
+        _                                     =>
+            Ok(serde_json::from_slice(&data)?),
+    }
+}
+
+
+pub struct IterationWriter {
+    json_output_directory: PathBuf,
+    output_format: OutputFormat,
+    compression: OutputCompression,
+    snapshot_mode: SnapshotMode,
+    delta_encoder: DeltaEncoder,
+    written_count: usize,
+}
+
+impl IterationWriter {
+    #[must_use]
+    pub fn new(
+        json_output_directory: &Path,
+        output_format: OutputFormat,
+        compression: OutputCompression,
+        snapshot_mode: SnapshotMode
+    ) -> Self {
+        Self {
+            json_output_directory: json_output_directory.to_path_buf(),
+            output_format,
+            compression,
+            snapshot_mode,
+            delta_encoder: DeltaEncoder::new(),
+            written_count: 0,
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the file can not be compressed or written. Data
+    /// that can not be serialized is replaced with an error message instead.
+    pub fn write_iteration_data(
+        &mut self,
+        network_model: &NetworkModel,
+        current_iteration_time: Millisecond
+    ) -> crate::Result<()> {
+        let (data, file_kind) = match (self.output_format, self.snapshot_mode) {
+            (OutputFormat::Binary, _)                  => (
+                network_model.to_binary().ok(),
+                IterationFileKind::Binary
+            ),
+            (OutputFormat::Json, SnapshotMode::Full)  => (
+                network_model.to_json().ok().map(String::into_bytes),
+                IterationFileKind::Json
+            ),
+            (OutputFormat::Json, SnapshotMode::Delta) =>
+                self.delta_json(network_model),
+        };
+
+        let file_path = self.json_output_directory.join(
+            iteration_file_name(
+                current_iteration_time,
+                file_kind,
+                self.compression
+            )
+        );
+        let data = data
+            .unwrap_or_else(|| ERR_SERIALIZATION.as_bytes().to_vec());
+
+        let context = || format!("Failed to write `{}`", file_path.display());
+
+        self.written_count += 1;
+
+        let data = self.compression
+            .compress(data)
+            .map_err(|error| crate::Error::io(context(), error))?;
+
+        fs::write(&file_path, data)
+            .map_err(|error| crate::Error::io(context(), error))
+    }
+
+    // Deltas are built from the changes tracked by the model, a full
+    // snapshot is written if the model does not track them.
+    fn delta_json(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> (Option<Vec<u8>>, IterationFileKind) {
+        let is_keyframe = self.written_count.is_multiple_of(KEYFRAME_INTERVAL);
+        let delta = if is_keyframe {
+            None
+        } else {
+            self.delta_encoder.delta(network_model).ok().flatten()
+        };
+
+        if let Some(delta) = delta {
+            let json_data = serde_json::to_vec(&delta).ok();
+
+            if json_data.is_none() {
+                self.delta_encoder.reset();
+            }
+
+            return (json_data, IterationFileKind::JsonDelta);
+        }
+
+        let Ok(snapshot) = network_model.to_json_value() else {
+            self.delta_encoder.reset();
+
+            return (None, IterationFileKind::Json);
+        };
+
+        if self.delta_encoder
+            .set_keyframe(&snapshot, network_model.current_time())
+            .is_err()
+        {
+            self.delta_encoder.reset();
+        }
+
+        (serde_json::to_vec(&snapshot).ok(), IterationFileKind::Json)
+    }
+}
+
+
+// Reconstructs full network model states from the files written by
+// `IterationWriter` in any format and snapshot mode.
+pub struct IterationReader {
+    file_paths: std::vec::IntoIter<(Millisecond, IterationFileKind, PathBuf)>,
+    current_snapshot: Option<Value>,
+}
+
+impl IterationReader {
+    /// # Errors
+    ///
+    /// Will return `Err` if the directory can not be read.
+    pub fn new(json_output_directory: &Path) -> Result<Self, OutputError> {
+        let mut file_paths = Vec::new();
+
+        for entry in fs::read_dir(json_output_directory)? {
+            let file_path = entry?.path();
+
+            if let Some((iteration_time, file_kind)) = parse_iteration_file_name(
+                &file_path
+            ) {
+                file_paths.push((iteration_time, file_kind, file_path));
+            }
+        }
+
+        file_paths.sort_by_key(|(iteration_time, ..)| *iteration_time);
+
+        Ok(Self {
+            file_paths: file_paths.into_iter(),
+            current_snapshot: None,
+        })
+    }
+
+    fn read_next(
+        &mut self,
+        file_kind: IterationFileKind,
+        file_path: &Path
+    ) -> Result<NetworkModel, OutputError> {
+        match file_kind {
+            IterationFileKind::Binary    => {
+                self.current_snapshot = None;
+
+                return read_network_model(file_path);
+            },
+            IterationFileKind::JsonDelta => {
+                let json_data = read_decompressed(file_path)?;
+                let delta: ModelDelta = serde_json::from_slice(&json_data)?;
+                let Some(ref mut snapshot) = self.current_snapshot else {
+                    return Err(
+                        OutputError::MissingKeyframe(file_path.to_path_buf())
+                    );
+                };
+
+                delta.apply_to(snapshot)?;
+            },
+            IterationFileKind::Json      => {
+                let json_data = read_decompressed(file_path)?;
+
+                self.current_snapshot = Some(
+                    serde_json::from_slice(&json_data)?
+                );
+            },
+        }
+
+        let snapshot = self.current_snapshot
+            .clone()
+            .unwrap_or_default();
+
+        Ok(NetworkModel::from_json_value(snapshot)?)
+    }
+}
+
+impl Iterator for IterationReader {
+    type Item = (Millisecond, Result<NetworkModel, OutputError>);
 
-    let json_data = if let Ok(data) = network_model.to_json() {
-        data
-    } else {
-        ERR_SERIALIZATION.to_string()
-    };
+    fn next(&mut self) -> Option<Self::Item> {
+        let (iteration_time, file_kind, file_path) = self.file_paths.next()?;
 
-    let _ = std::fs::write(file_path, json_data);
+        Some((iteration_time, self.read_next(file_kind, &file_path)))
+    }
 }
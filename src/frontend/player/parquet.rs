@@ -0,0 +1,184 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, Float32Array, Int32Array, RecordBatch, StringArray, UInt64Array
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+use thiserror::Error;
+
+use crate::backend::event::Event;
+use crate::backend::metrics::Metrics;
+use crate::backend::networkmodel::NetworkModel;
+
+
+const EVENTS_FILE_NAME: &str  = "events.parquet";
+const METRICS_FILE_NAME: &str = "metrics.parquet";
+
+
+#[derive(Debug, Error)]
+pub enum TimeSeriesError {
+    #[error("Failed to access time series file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to build Arrow batch: {0}")]
+    Arrow(#[from] ArrowError),
+    #[error("Failed to write Parquet file: {0}")]
+    Parquet(#[from] ParquetError),
+}
+
+
+fn write_batch(
+    file_path: &Path,
+    batch: &RecordBatch
+) -> Result<(), TimeSeriesError> {
+    let file = File::create(file_path)?;
+    let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+
+    writer.write(batch)?;
+    writer.close()?;
+
+    Ok(())
+}
+
+// `usize` columns are stored as `UInt64`, which is lossless on supported
+// targets.
+fn usize_column(values: impl Iterator<Item = usize>) -> ArrayRef {
+    Arc::new(
+        values
+            .map(|value| value as u64)
+            .collect::<UInt64Array>()
+    )
+}
+
+
+// Collects metric and event time series during a run and writes them to
+// `metrics.parquet` and `events.parquet` when the run is finished.
+pub struct TimeSeriesWriter {
+    output_directory: PathBuf,
+    metrics: Vec<Metrics>,
+    events: Vec<Event>,
+}
+
+impl TimeSeriesWriter {
+    #[must_use]
+    pub fn new(output_directory: &Path) -> Self {
+        Self {
+            output_directory: output_directory.to_path_buf(),
+            metrics: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    pub fn record_iteration(&mut self, network_model: &NetworkModel) {
+        self.metrics.push(Metrics::from_model(network_model));
+        self.events.extend_from_slice(network_model.events());
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if any of the files can not be written.
+    pub fn finish(&self) -> Result<(), TimeSeriesError> {
+        std::fs::create_dir_all(&self.output_directory)?;
+
+        write_batch(
+            &self.output_directory.join(METRICS_FILE_NAME),
+            &self.metrics_batch()?
+        )?;
+        write_batch(
+            &self.output_directory.join(EVENTS_FILE_NAME),
+            &self.events_batch()?
+        )
+    }
+
+    fn metrics_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let schema = Schema::new(vec![
+            Field::new("time", DataType::Int32, false),
+            Field::new("alive_count", DataType::UInt64, false),
+            Field::new("infected_count", DataType::UInt64, false),
+            Field::new("mean_battery_level", DataType::Float32, false),
+            Field::new("reachable_fraction", DataType::Float32, false),
+            Field::new("sensor_coverage", DataType::Float32, true),
+            Field::new("control_coverage", DataType::Float32, true),
+            Field::new("aggregation_ratio", DataType::Float32, true),
+        ]);
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                self.metrics
+                    .iter()
+                    .map(|metrics| metrics.time().value())
+                    .collect::<Int32Array>()
+            ),
+            usize_column(self.metrics.iter().map(Metrics::alive_count)),
+            usize_column(self.metrics.iter().map(Metrics::infected_count)),
+            Arc::new(
+                self.metrics
+                    .iter()
+                    .map(Metrics::mean_battery_level)
+                    .collect::<Float32Array>()
+            ),
+            Arc::new(
+                self.metrics
+                    .iter()
+                    .map(Metrics::reachable_fraction)
+                    .collect::<Float32Array>()
+            ),
+            Arc::new(
+                self.metrics
+                    .iter()
+                    .map(Metrics::sensor_coverage)
+                    .collect::<Float32Array>()
+            ),
+            Arc::new(
+                self.metrics
+                    .iter()
+                    .map(Metrics::control_coverage)
+                    .collect::<Float32Array>()
+            ),
+            Arc::new(
+                self.metrics
+                    .iter()
+                    .map(Metrics::aggregation_ratio)
+                    .collect::<Float32Array>()
+            ),
+        ];
+
+        RecordBatch::try_new(Arc::new(schema), columns)
+    }
+
+    fn events_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let schema = Schema::new(vec![
+            Field::new("time", DataType::Int32, false),
+            Field::new("device_id", DataType::UInt64, false),
+            Field::new("kind", DataType::Utf8, false),
+            Field::new("details", DataType::Utf8, true),
+        ]);
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                self.events
+                    .iter()
+                    .map(|event| event.time().value())
+                    .collect::<Int32Array>()
+            ),
+            usize_column(self.events.iter().map(Event::device_id)),
+            Arc::new(
+                self.events
+                    .iter()
+                    .map(|event| Some(event.kind().name()))
+                    .collect::<StringArray>()
+            ),
+            Arc::new(
+                self.events
+                    .iter()
+                    .map(|event| event.kind().details())
+                    .collect::<StringArray>()
+            ),
+        ];
+
+        RecordBatch::try_new(Arc::new(schema), columns)
+    }
+}
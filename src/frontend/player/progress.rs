@@ -0,0 +1,90 @@
+use std::time::{Duration, Instant};
+
+use log::info;
+
+use crate::backend::mathphysics::Millisecond;
+
+
+// Progress is reported each time it grows by that many percent, but not
+// less often than once per `PROGRESS_INTERVAL`.
+const PROGRESS_STEP_PERCENT: u32 = 10;
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+
+// Logs the share of simulation time played so far with the estimated time
+// left. The estimate assumes the remaining iterations cost as much as the
+// already played ones.
+pub struct ProgressReporter {
+    end_time: Millisecond,
+    started_at: Instant,
+    reported_at: Instant,
+    reported_percent: u32,
+}
+
+impl ProgressReporter {
+    #[must_use]
+    pub fn new(end_time: Millisecond) -> Self {
+        let now = Instant::now();
+
+        Self {
+            end_time,
+            started_at: now,
+            reported_at: now,
+            reported_percent: 0,
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn report(&mut self, current_time: Millisecond) {
+        if self.end_time <= Millisecond::ZERO {
+            return;
+        }
+
+        let percent = (
+            f64::from(current_time.value())
+                / f64::from(self.end_time.value())
+                * 100.0
+        ).clamp(0.0, 100.0) as u32;
+
+        if percent < self.reported_percent + PROGRESS_STEP_PERCENT
+            && self.reported_at.elapsed() < PROGRESS_INTERVAL
+        {
+            return;
+        }
+
+        self.reported_at = Instant::now();
+        self.reported_percent = percent;
+
+        let remaining_time = remaining_wall_clock_time(
+            self.started_at.elapsed(),
+            current_time,
+            self.end_time
+        );
+
+        info!(
+            "Progress: {percent}% ({current_time}/{} ms), ETA {:.1} s",
+            self.end_time,
+            remaining_time.as_secs_f64()
+        );
+    }
+}
+
+
+fn remaining_wall_clock_time(
+    elapsed: Duration,
+    current_time: Millisecond,
+    end_time: Millisecond
+) -> Duration {
+    if current_time <= Millisecond::ZERO {
+        return Duration::ZERO;
+    }
+
+    let remaining_simulation_time = (end_time - current_time)
+        .max(Millisecond::ZERO);
+
+    elapsed.mul_f64(
+        f64::from(remaining_simulation_time.value())
+            / f64::from(current_time.value())
+    )
+}
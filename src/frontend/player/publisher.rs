@@ -0,0 +1,252 @@
+#[cfg(feature = "mqtt")]
+use std::time::Duration;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::backend::device::DeviceId;
+use crate::backend::event::Event;
+use crate::backend::mathphysics::Millisecond;
+
+
+#[cfg(feature = "kafka")]
+const KAFKA_SCHEME: &str           = "kafka://";
+#[cfg(feature = "mqtt")]
+const MQTT_CLIENT_ID: &str         = "drone_network";
+#[cfg(feature = "mqtt")]
+const MQTT_FLUSH_TIMEOUT: Duration = Duration::from_millis(500);
+#[cfg(feature = "mqtt")]
+const MQTT_QUEUE_SIZE: usize       = 64;
+#[cfg(feature = "mqtt")]
+const MQTT_SCHEME: &str            = "mqtt://";
+
+
+#[derive(Debug, Error)]
+pub enum PublisherError {
+    #[error(
+        "Unsupported event endpoint `{0}`, expected \
+        `mqtt://host:port/topic` or `kafka://host:port/topic`"
+    )]
+    UnsupportedEndpoint(String),
+    #[error("Failed to serialize event: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[cfg(feature = "mqtt")]
+    #[error("MQTT client error: {0}")]
+    Mqtt(#[from] rumqttc::ClientError),
+    #[cfg(feature = "kafka")]
+    #[error("Kafka producer error: {0}")]
+    Kafka(#[from] kafka::Error),
+}
+
+
+#[derive(Serialize)]
+struct EventMessage<'a> {
+    time: Millisecond,
+    device_id: DeviceId,
+    kind: &'a str,
+    details: Option<String>,
+}
+
+impl<'a> From<&'a Event> for EventMessage<'a> {
+    fn from(event: &'a Event) -> Self {
+        Self {
+            time: event.time(),
+            device_id: event.device_id(),
+            kind: event.kind().name(),
+            details: event.kind().details(),
+        }
+    }
+}
+
+
+// Splits `host:port/topic` into the broker address and topic.
+fn split_endpoint(endpoint: &str) -> Option<(&str, &str)> {
+    endpoint
+        .split_once('/')
+        .filter(|(address, topic)| !address.is_empty() && !topic.is_empty())
+}
+
+
+// Polls the connection until it fails or is closed. After the disconnect
+// request is sent, the connection is polled until the remaining messages are
+// flushed, because some brokers do not close the connection themselves.
+#[cfg(feature = "mqtt")]
+fn poll_mqtt_connection(connection: &mut rumqttc::Connection) {
+    use rumqttc::{Event, Outgoing};
+
+    let mut is_disconnecting = false;
+
+    loop {
+        let notification = if is_disconnecting {
+            match connection.recv_timeout(MQTT_FLUSH_TIMEOUT) {
+                Ok(notification) => notification,
+                Err(_)           => break,
+            }
+        } else {
+            match connection.recv() {
+                Ok(notification) => notification,
+                Err(_)           => break,
+            }
+        };
+
+        match notification {
+            Ok(Event::Outgoing(Outgoing::Disconnect)) =>
+                is_disconnecting = true,
+            Ok(_)                                     => (),
+            Err(_) if is_disconnecting                => break,
+            Err(error)                                => {
+                log::warn!("MQTT connection failed: {error}");
+                break;
+            },
+        }
+    }
+}
+
+
+#[cfg(feature = "mqtt")]
+struct MqttConnection {
+    client: rumqttc::Client,
+    topic: String,
+    event_loop: std::thread::JoinHandle<()>,
+}
+
+#[cfg(feature = "mqtt")]
+impl MqttConnection {
+    fn connect(address: &str, topic: &str) -> Option<Self> {
+        let (host, port) = address.rsplit_once(':')?;
+        let options = rumqttc::MqttOptions::new(
+            MQTT_CLIENT_ID,
+            host,
+            port.parse().ok()?
+        );
+        let (client, mut connection) = rumqttc::Client::new(
+            options,
+            MQTT_QUEUE_SIZE
+        );
+
+        // The event loop has to be polled for requests to be sent.
+        let event_loop = std::thread::spawn(move || {
+            poll_mqtt_connection(&mut connection);
+        });
+
+        Some(Self { client, topic: topic.to_string(), event_loop })
+    }
+
+    // Waits until queued messages are sent, so they are not lost on exit.
+    fn close(self) {
+        if self.client.disconnect().is_ok() {
+            let _ = self.event_loop.join();
+        }
+    }
+}
+
+
+enum PublisherConnection {
+    #[cfg(feature = "mqtt")]
+    Mqtt(MqttConnection),
+    #[cfg(feature = "kafka")]
+    Kafka {
+        producer: Box<kafka::producer::Producer>,
+        topic: String,
+    },
+}
+
+impl PublisherConnection {
+    fn connect(endpoint: &str) -> Result<Self, PublisherError> {
+        let unsupported_endpoint = || {
+            PublisherError::UnsupportedEndpoint(endpoint.to_string())
+        };
+
+        #[cfg(feature = "mqtt")]
+        if let Some(endpoint) = endpoint.strip_prefix(MQTT_SCHEME) {
+            return split_endpoint(endpoint)
+                .and_then(|(address, topic)|
+                    MqttConnection::connect(address, topic)
+                )
+                .map(Self::Mqtt)
+                .ok_or_else(unsupported_endpoint);
+        }
+
+        #[cfg(feature = "kafka")]
+        if let Some(endpoint) = endpoint.strip_prefix(KAFKA_SCHEME) {
+            let (address, topic) = split_endpoint(endpoint)
+                .ok_or_else(unsupported_endpoint)?;
+            let producer = kafka::producer::Producer::from_hosts(
+                vec![address.to_string()]
+            )
+                .with_required_acks(kafka::producer::RequiredAcks::One)
+                .create()?;
+
+            return Ok(Self::Kafka {
+                producer: Box::new(producer),
+                topic: topic.to_string(),
+            });
+        }
+
+        Err(unsupported_endpoint())
+    }
+
+    fn publish(&mut self, payload: Vec<u8>) -> Result<(), PublisherError> {
+        match self {
+            #[cfg(feature = "mqtt")]
+            Self::Mqtt(connection)         => connection.client.publish(
+                connection.topic.as_str(),
+                rumqttc::QoS::AtLeastOnce,
+                false,
+                payload
+            )?,
+            #[cfg(feature = "kafka")]
+            Self::Kafka { producer, topic } => producer.send(
+                &kafka::producer::Record::from_value(topic, payload)
+            )?,
+        }
+
+        Ok(())
+    }
+
+    fn close(self) {
+        match self {
+            #[cfg(feature = "mqtt")]
+            Self::Mqtt(connection) => connection.close(),
+            #[cfg(feature = "kafka")]
+            Self::Kafka { .. }     => (),
+        }
+    }
+}
+
+
+// Publishes network model events to an MQTT or Kafka topic as JSON, one
+// message per event.
+pub struct EventPublisher {
+    connection: PublisherConnection,
+}
+
+impl EventPublisher {
+    /// # Errors
+    ///
+    /// Will return `Err` if the endpoint is not supported or the connection
+    /// can not be established.
+    pub fn connect(endpoint: &str) -> Result<Self, PublisherError> {
+        Ok(Self { connection: PublisherConnection::connect(endpoint)? })
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if an event can not be serialized or published.
+    pub fn publish_events(
+        &mut self,
+        events: &[Event]
+    ) -> Result<(), PublisherError> {
+        for event in events {
+            let payload = serde_json::to_vec(&EventMessage::from(event))?;
+
+            self.connection.publish(payload)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn close(self) {
+        self.connection.close();
+    }
+}
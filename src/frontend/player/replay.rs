@@ -0,0 +1,122 @@
+use log::{debug, info};
+
+use crate::backend::metrics::Metrics;
+use crate::backend::mathphysics::Millisecond;
+use crate::frontend::config::ModelPlayerConfig;
+use crate::frontend::renderer::Renderer;
+use crate::frontend::summary::{PlayOutcome, RunStatus};
+
+#[cfg(feature = "parquet")]
+use super::TimeSeriesWriter;
+use super::{
+    create_metrics_writer, stop_requested, IterationReader, MetricsWriter,
+    PlayError
+};
+
+
+// Renders and analyzes network model states from the files written by
+// `IterationWriter` instead of simulating them again. Events are not
+// serialized, so replayed time series contain metrics only.
+pub struct ReplayPlayer<R: Renderer> {
+    iteration_reader: IterationReader,
+    metrics_writer: Option<MetricsWriter>,
+    #[cfg(feature = "parquet")]
+    time_series_writer: Option<TimeSeriesWriter>,
+    renderer: Option<R>,
+    end_time: Millisecond,
+}
+
+impl<R: Renderer> ReplayPlayer<R> {
+    /// # Errors
+    ///
+    /// Will return `Err` if the metrics file can not be created.
+    pub fn new(
+        model_player_config: &ModelPlayerConfig,
+        iteration_reader: IterationReader,
+        renderer: Option<R>,
+    ) -> Result<Self, PlayError> {
+        let output_config = model_player_config.output_config();
+
+        Ok(Self {
+            iteration_reader,
+            metrics_writer: create_metrics_writer(output_config)?,
+            #[cfg(feature = "parquet")]
+            time_series_writer: output_config
+                .parquet_output_directory()
+                .map(TimeSeriesWriter::new),
+            renderer,
+            end_time: model_player_config.simulation_time(),
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if an iteration file can not be read, an output
+    /// file can not be written or an error occurs during rendering.
+    pub fn play(&mut self) -> Result<PlayOutcome, PlayError> {
+        let mut replayed_count = 0;
+        let mut status = RunStatus::Completed;
+        let mut final_metrics = None;
+
+        for (iteration_time, network_model) in self.iteration_reader
+            .by_ref()
+            .take_while(|(iteration_time, _)| *iteration_time < self.end_time)
+        {
+            if stop_requested() {
+                info!("Stopped at {iteration_time}");
+                status = RunStatus::Stopped;
+                break;
+            }
+
+            // Deltas after a file that can not be read can not be applied
+            // either, so the replay fails then.
+            let network_model = network_model.map_err(|error|
+                PlayError::Replay(iteration_time, error)
+            )?;
+
+            debug!("Current time: {iteration_time}");
+
+            if let Some(ref mut metrics_writer) = self.metrics_writer {
+                metrics_writer
+                    .write_iteration_metrics(&network_model)
+                    .map_err(PlayError::Metrics)?;
+            }
+
+            #[cfg(feature = "parquet")]
+            if let Some(ref mut time_series_writer) = self.time_series_writer {
+                time_series_writer.record_iteration(&network_model);
+            }
+
+            if let Some(ref mut renderer) = self.renderer {
+                renderer.render(&network_model)?;
+            }
+
+            final_metrics = Some(Metrics::from_model(&network_model));
+            replayed_count += 1;
+        }
+
+        if let Some(ref mut metrics_writer) = self.metrics_writer {
+            metrics_writer.flush().map_err(PlayError::Metrics)?;
+        }
+
+        #[cfg(feature = "parquet")]
+        if let Some(ref time_series_writer) = self.time_series_writer {
+            time_series_writer.finish()?;
+        }
+
+        if let Some(ref mut renderer) = self.renderer {
+            renderer.finish();
+        }
+
+        info!("Replayed iteration count: {replayed_count}");
+
+        let render_filename = self.renderer
+            .as_ref()
+            .and_then(Renderer::output_filename)
+            .inspect(|output_filename| {
+                info!("Render filename: {output_filename}");
+            });
+
+        Ok(PlayOutcome::new(status, final_metrics, render_filename))
+    }
+}
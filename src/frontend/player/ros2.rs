@@ -0,0 +1,306 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use thiserror::Error;
+use zenoh::Wait;
+use zenoh::pubsub::Publisher;
+use zenoh::Session;
+
+use crate::backend::device::{Device, DeviceId};
+use crate::backend::event::Event;
+use crate::backend::mathphysics::{
+    float_to_f64, Meter, Millisecond, Orientation, Position
+};
+use crate::backend::networkmodel::NetworkModel;
+use crate::backend::signal::StrengthValue;
+
+
+// Keys are ROS 2 topic names without the leading slash, the way
+// `zenoh-bridge-ros2dds` maps them.
+const CLOCK_KEY: &str  = "clock";
+const EVENTS_KEY: &str = "drone_network/events";
+const LINKS_KEY: &str  = "drone_network/links";
+const FRAME_ID: &str   = "map";
+// Little-endian CDR encapsulation header.
+const CDR_HEADER: [u8; 4] = [0x00, 0x01, 0x00, 0x00];
+
+
+#[derive(Debug, Error)]
+pub enum Ros2BridgeError {
+    #[error("Zenoh error: {0}")]
+    Zenoh(zenoh::Error),
+    #[error("Failed to serialize ROS 2 message: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+
+fn pose_key(device_id: DeviceId) -> String {
+    format!("drone_network/device_{device_id}/pose")
+}
+
+
+// Serializes messages the way ROS 2 middleware does. Alignment is counted
+// from the end of the encapsulation header.
+struct CdrWriter {
+    buffer: Vec<u8>,
+}
+
+impl CdrWriter {
+    fn new() -> Self {
+        Self { buffer: CDR_HEADER.to_vec() }
+    }
+
+    fn align(&mut self, alignment: usize) {
+        let offset = self.buffer.len() - CDR_HEADER.len();
+
+        self.buffer.resize(
+            self.buffer.len() + (alignment - offset % alignment) % alignment,
+            0
+        );
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        self.align(4);
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.align(4);
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_f64(&mut self, value: f64) {
+        self.align(8);
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    // Strings are null-terminated and their length includes the null.
+    fn write_string(&mut self, value: &str) {
+        self.write_u32(u32::try_from(value.len() + 1).unwrap_or(u32::MAX));
+        self.buffer.extend_from_slice(value.as_bytes());
+        self.buffer.push(0);
+    }
+
+    // builtin_interfaces/msg/Time
+    fn write_time(&mut self, time: Millisecond) {
+        let time = time.max(Millisecond::ZERO).value();
+
+        self.write_i32(time / 1000);
+        self.write_u32(u32::try_from(time % 1000 * 1_000_000).unwrap_or(0));
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+
+// rosgraph_msgs/msg/Clock
+fn clock_message(time: Millisecond) -> Vec<u8> {
+    let mut cdr_writer = CdrWriter::new();
+
+    cdr_writer.write_time(time);
+
+    cdr_writer.finish()
+}
+
+// std_msgs/msg/String
+fn string_message(data: &str) -> Vec<u8> {
+    let mut cdr_writer = CdrWriter::new();
+
+    cdr_writer.write_string(data);
+
+    cdr_writer.finish()
+}
+
+// geometry_msgs/msg/PoseStamped
+fn pose_message(time: Millisecond, device: &Device) -> Vec<u8> {
+    let mut cdr_writer = CdrWriter::new();
+    let position = device.position();
+
+    cdr_writer.write_time(time);
+    cdr_writer.write_string(FRAME_ID);
+
+    for coordinate in [position.x, position.y, position.z] {
+        cdr_writer.write_f64(float_to_f64(coordinate));
+    }
+    for component in quaternion(device.orientation()) {
+        cdr_writer.write_f64(component);
+    }
+
+    cdr_writer.finish()
+}
+
+// Returns `[x, y, z, w]`. ROS 2 body frames point `y` left and `z` up, so
+// climbing and banking into a counterclockwise turn are negative pitch and
+// roll there.
+fn quaternion(orientation: &Orientation) -> [f64; 4] {
+    let half_yaw = float_to_f64(orientation.yaw) / 2.0;
+    let half_pitch = -float_to_f64(orientation.pitch) / 2.0;
+    let half_roll = -float_to_f64(orientation.roll) / 2.0;
+
+    let (sin_yaw, cos_yaw) = half_yaw.sin_cos();
+    let (sin_pitch, cos_pitch) = half_pitch.sin_cos();
+    let (sin_roll, cos_roll) = half_roll.sin_cos();
+
+    [
+        sin_roll * cos_pitch * cos_yaw - cos_roll * sin_pitch * sin_yaw,
+        cos_roll * sin_pitch * cos_yaw + sin_roll * cos_pitch * sin_yaw,
+        cos_roll * cos_pitch * sin_yaw - sin_roll * sin_pitch * cos_yaw,
+        cos_roll * cos_pitch * cos_yaw + sin_roll * sin_pitch * sin_yaw,
+    ]
+}
+
+
+#[derive(Serialize)]
+struct LinkState {
+    source: DeviceId,
+    destination: DeviceId,
+    distance: Meter,
+    signal_strength: StrengthValue,
+}
+
+
+// Publishes the simulation to ROS 2 through Zenoh, e.g. to
+// `zenoh-bridge-ros2dds` that forwards the topics to DDS:
+// * `/clock` - simulation time (`rosgraph_msgs/msg/Clock`);
+// * `/drone_network/device_<id>/pose` - device poses in `map` frame
+//   (`geometry_msgs/msg/PoseStamped`);
+// * `/drone_network/links` - connections as JSON (`std_msgs/msg/String`);
+// * `/drone_network/events` - events as JSON, one message per event
+//   (`std_msgs/msg/String`).
+pub struct Ros2Bridge {
+    session: Session,
+    clock_publisher: Publisher<'static>,
+    links_publisher: Publisher<'static>,
+    events_publisher: Publisher<'static>,
+    pose_publishers: HashMap<DeviceId, Publisher<'static>>,
+}
+
+impl Ros2Bridge {
+    /// # Errors
+    ///
+    /// Will return `Err` if the Zenoh session can not be opened on
+    /// `endpoint` (e.g. `tcp/127.0.0.1:7447`).
+    pub fn connect(endpoint: &str) -> Result<Self, Ros2BridgeError> {
+        let endpoints = serde_json::to_string(&[endpoint])?;
+        let mut config = zenoh::Config::default();
+
+        config
+            .insert_json5("mode", r#""client""#)
+            .and_then(|()|
+                config.insert_json5("connect/endpoints", &endpoints)
+            )
+            .map_err(Ros2BridgeError::Zenoh)?;
+
+        let session = zenoh::open(config)
+            .wait()
+            .map_err(Ros2BridgeError::Zenoh)?;
+        let clock_publisher = declare_publisher(&session, CLOCK_KEY)?;
+        let links_publisher = declare_publisher(&session, LINKS_KEY)?;
+        let events_publisher = declare_publisher(&session, EVENTS_KEY)?;
+
+        Ok(Self {
+            session,
+            clock_publisher,
+            links_publisher,
+            events_publisher,
+            pose_publishers: HashMap::new(),
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if a message can not be serialized or published.
+    pub fn publish_iteration(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> Result<(), Ros2BridgeError> {
+        let time = network_model.current_time();
+
+        put(&self.clock_publisher, clock_message(time))?;
+
+        let devices = network_model
+            .device_map()
+            .values()
+            .chain(
+                network_model
+                    .attacker_devices()
+                    .iter()
+                    .map(|attacker_device| attacker_device.device())
+            );
+
+        for device in devices {
+            if !self.pose_publishers.contains_key(&device.id()) {
+                let publisher = declare_publisher(
+                    &self.session,
+                    &pose_key(device.id())
+                )?;
+
+                self.pose_publishers.insert(device.id(), publisher);
+            }
+
+            put(
+                &self.pose_publishers[&device.id()],
+                pose_message(time, device)
+            )?;
+        }
+
+        let link_states: Vec<LinkState> = network_model
+            .connections()
+            .graph_map()
+            .all_edges()
+            .map(|(source, destination, (distance, signal_strength))|
+                LinkState {
+                    source,
+                    destination,
+                    distance: *distance,
+                    signal_strength: signal_strength.value(),
+                }
+            )
+            .collect();
+
+        put(
+            &self.links_publisher,
+            string_message(&serde_json::to_string(&link_states)?)
+        )?;
+
+        self.publish_events(network_model.events())
+    }
+
+    fn publish_events(&self, events: &[Event]) -> Result<(), Ros2BridgeError> {
+        for event in events {
+            put(
+                &self.events_publisher,
+                string_message(&serde_json::to_string(event)?)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn close(self) {
+        let _ = self.session.close().wait();
+    }
+}
+
+
+fn declare_publisher(
+    session: &Session,
+    key: &str
+) -> Result<Publisher<'static>, Ros2BridgeError> {
+    session
+        .declare_publisher(key.to_string())
+        .wait()
+        .map_err(Ros2BridgeError::Zenoh)
+}
+
+fn put(
+    publisher: &Publisher<'static>,
+    payload: Vec<u8>
+) -> Result<(), Ros2BridgeError> {
+    publisher
+        .put(payload)
+        .wait()
+        .map_err(Ros2BridgeError::Zenoh)
+}
@@ -0,0 +1,88 @@
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+
+use log::warn;
+use serde::Deserialize;
+
+use crate::backend::device::DeviceId;
+use crate::backend::mathphysics::{Float, Point3D};
+use crate::backend::networkmodel::NetworkModel;
+
+
+// Positions are small JSON objects, so a datagram never gets close to it.
+const MAX_DATAGRAM_SIZE: usize = 1024;
+
+
+// Position of a device in the model frame (`z` up, meters), e.g. converted
+// from PX4 local NED position by the script forwarding it.
+#[derive(Deserialize)]
+struct PositionMessage {
+    device_id: DeviceId,
+    x: Float,
+    y: Float,
+    z: Float,
+}
+
+
+// Moves externally driven devices to the positions another simulator (e.g.
+// PX4 SITL with Gazebo) sends over UDP as JSON objects like
+// `{"device_id": 2, "x": 10.0, "y": 5.0, "z": 20.0}`. Only the latest
+// position of a device received before an iteration is used.
+pub struct SitlReceiver {
+    socket: UdpSocket,
+    buffer: [u8; MAX_DATAGRAM_SIZE],
+}
+
+impl SitlReceiver {
+    /// # Errors
+    ///
+    /// Will return `Err` if the socket can not be bound to the address.
+    pub fn bind(address: SocketAddr) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(address)?;
+
+        socket.set_nonblocking(true)?;
+
+        Ok(Self { socket, buffer: [0; MAX_DATAGRAM_SIZE] })
+    }
+
+    // Malformed datagrams and positions of devices that are not externally
+    // driven are skipped, so one bad sender does not stop the run.
+    /// # Errors
+    ///
+    /// Will return `Err` if the socket fails.
+    pub fn receive_positions(
+        &mut self,
+        network_model: &mut NetworkModel
+    ) -> std::io::Result<()> {
+        loop {
+            let size = match self.socket.recv(&mut self.buffer) {
+                Ok(size)                                           => size,
+                Err(error) if error.kind() == ErrorKind::WouldBlock =>
+                    return Ok(()),
+                Err(error)                                         =>
+                    return Err(error),
+            };
+
+            let message = match serde_json::from_slice::<PositionMessage>(
+                &self.buffer[..size]
+            ) {
+                Ok(message) => message,
+                Err(error)  => {
+                    warn!("Skipped malformed SITL position: {error}");
+                    continue;
+                },
+            };
+
+            if !network_model.set_external_position(
+                message.device_id,
+                Point3D::new(message.x, message.y, message.z)
+            ) {
+                warn!(
+                    "Skipped SITL position of device {} that is not \
+                    externally driven",
+                    message.device_id
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::io::{BufWriter, Write};
+use std::net::TcpStream;
+
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+use crate::backend::mathphysics::Millisecond;
+use crate::backend::networkmodel::NetworkModel;
+use crate::backend::networkmodel::snapshot::{DeltaEncoder, ModelDelta};
+
+use super::output::{KEYFRAME_INTERVAL, SnapshotMode};
+
+
+const TCP_SCHEME: &str       = "tcp://";
+const WEBSOCKET_SCHEME: &str = "ws://";
+
+
+#[derive(Debug, Error)]
+pub enum StreamError {
+    #[error("Unsupported stream endpoint `{0}`, expected `tcp://` or `ws://`")]
+    UnsupportedEndpoint(String),
+    #[error("Failed to write to TCP stream: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to write to WebSocket: {0}")]
+    WebSocket(#[from] tungstenite::Error),
+    #[error("Failed to serialize stream message: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+
+// Every message carries either a full snapshot or a delta to the previous
+// message.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StreamPayload<'a> {
+    Snapshot(&'a Value),
+    Delta(&'a ModelDelta),
+}
+
+#[derive(Serialize)]
+struct StreamMessage<'a> {
+    time: Millisecond,
+    #[serde(flatten)]
+    payload: StreamPayload<'a>,
+}
+
+
+enum StreamConnection {
+    // Messages are separated by newlines.
+    Tcp(BufWriter<TcpStream>),
+    WebSocket(Box<WebSocket<MaybeTlsStream<TcpStream>>>),
+}
+
+impl StreamConnection {
+    fn connect(endpoint: &str) -> Result<Self, StreamError> {
+        if let Some(address) = endpoint.strip_prefix(TCP_SCHEME) {
+            let stream = TcpStream::connect(address)?;
+            stream.set_nodelay(true)?;
+
+            Ok(Self::Tcp(BufWriter::new(stream)))
+        } else if endpoint.starts_with(WEBSOCKET_SCHEME) {
+            let (socket, _) = tungstenite::connect(endpoint)?;
+
+            Ok(Self::WebSocket(Box::new(socket)))
+        } else {
+            Err(StreamError::UnsupportedEndpoint(endpoint.to_string()))
+        }
+    }
+
+    fn send(&mut self, message: String) -> Result<(), StreamError> {
+        match self {
+            Self::Tcp(writer)       => {
+                writer.write_all(message.as_bytes())?;
+                writer.write_all(b"\n")?;
+                writer.flush()?;
+            },
+            Self::WebSocket(socket) => socket.send(Message::text(message))?,
+        }
+
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        match self {
+            Self::Tcp(writer)       => {
+                let _ = writer.flush();
+            },
+            Self::WebSocket(socket) => {
+                let _ = socket.close(None);
+                let _ = socket.flush();
+            },
+        }
+    }
+}
+
+
+// Pushes network model state on each iteration to a TCP or WebSocket
+// endpoint as JSON.
+pub struct StreamSink {
+    connection: StreamConnection,
+    snapshot_mode: SnapshotMode,
+    delta_encoder: DeltaEncoder,
+    sent_count: usize,
+}
+
+impl StreamSink {
+    /// # Errors
+    ///
+    /// Will return `Err` if the endpoint is not supported or the connection
+    /// can not be established.
+    pub fn connect(
+        endpoint: &str,
+        snapshot_mode: SnapshotMode
+    ) -> Result<Self, StreamError> {
+        Ok(Self {
+            connection: StreamConnection::connect(endpoint)?,
+            snapshot_mode,
+            delta_encoder: DeltaEncoder::new(),
+            sent_count: 0,
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the model can not be serialized or the message
+    /// can not be sent.
+    pub fn send_iteration_data(
+        &mut self,
+        network_model: &NetworkModel,
+        current_iteration_time: Millisecond
+    ) -> Result<(), StreamError> {
+        let is_keyframe = self.snapshot_mode == SnapshotMode::Full
+            || self.sent_count.is_multiple_of(KEYFRAME_INTERVAL);
+        let delta = if is_keyframe {
+            None
+        } else {
+            self.delta_encoder.delta(network_model).ok().flatten()
+        };
+
+        let message = if let Some(ref delta) = delta {
+            serde_json::to_string(&StreamMessage {
+                time: current_iteration_time,
+                payload: StreamPayload::Delta(delta),
+            })
+        } else {
+            let snapshot = network_model.to_json_value()?;
+
+            if self.snapshot_mode == SnapshotMode::Delta
+                && self.delta_encoder
+                    .set_keyframe(&snapshot, network_model.current_time())
+                    .is_err()
+            {
+                self.delta_encoder.reset();
+            }
+
+            serde_json::to_string(&StreamMessage {
+                time: current_iteration_time,
+                payload: StreamPayload::Snapshot(&snapshot),
+            })
+        };
+
+        // A failed message ends streaming, so the encoder is not reset.
+        self.connection.send(message?)?;
+        self.sent_count += 1;
+
+        Ok(())
+    }
+
+    pub fn close(&mut self) {
+        self.connection.close();
+    }
+}
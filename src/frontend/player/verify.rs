@@ -0,0 +1,98 @@
+use log::{info, warn};
+use rand::Rng;
+
+use crate::backend::ITERATION_TIME;
+use crate::backend::mathphysics::Millisecond;
+use crate::backend::networkmodel::NetworkModel;
+use crate::backend::random;
+
+
+// Checks that a run is deterministic. A reference run of a model copy is done
+// in advance with regular updates, then the actual run is started from the
+// same seed and its state checksums are compared with the reference ones at
+// the same time.
+pub struct DeterminismVerifier {
+    seed: u64,
+    reference_checksums: Vec<u64>,
+    checked_count: usize,
+    mismatch_time: Option<Millisecond>,
+}
+
+impl DeterminismVerifier {
+    // The generator is seeded again afterwards, so the actual run has to
+    // start right after this call.
+    #[must_use]
+    pub fn record_reference(
+        network_model: &NetworkModel,
+        end_time: Millisecond
+    ) -> Self {
+        let seed = random::with_rng(|rng| rng.random());
+        let mut reference_model = network_model.clone();
+
+        random::set_seed(seed);
+
+        let reference_checksums = (0..end_time.value())
+            .step_by(ITERATION_TIME.value() as usize)
+            .map(|_| {
+                let checksum = reference_model.state_checksum();
+                reference_model.update();
+
+                checksum
+            })
+            .collect();
+
+        random::set_seed(seed);
+
+        Self {
+            seed,
+            reference_checksums,
+            checked_count: 0,
+            mismatch_time: None,
+        }
+    }
+
+    // Only the first mismatch is reported, because the states diverge from
+    // then on.
+    pub fn check_iteration(
+        &mut self,
+        network_model: &NetworkModel,
+        current_iteration_time: Millisecond
+    ) {
+        if self.mismatch_time.is_some() {
+            return;
+        }
+
+        let iteration = (current_iteration_time / ITERATION_TIME) as usize;
+        let Some(&expected) = self.reference_checksums.get(iteration) else {
+            return;
+        };
+        let actual = network_model.state_checksum();
+
+        if actual == expected {
+            self.checked_count += 1;
+        } else {
+            warn!(
+                "State checksum mismatch at {current_iteration_time}: \
+                expected {expected:016x}, got {actual:016x}"
+            );
+            self.mismatch_time = Some(current_iteration_time);
+        }
+    }
+
+    #[must_use]
+    pub fn is_deterministic(&self) -> bool {
+        self.mismatch_time.is_none()
+    }
+
+    pub fn report(&self) {
+        if self.is_deterministic() {
+            info!(
+                "Deterministic replay verified for {} iterations (seed {})",
+                self.checked_count,
+                self.seed
+            );
+        } else {
+            warn!("Deterministic replay failed (seed {})", self.seed);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Instant;
+
+use crate::backend::device::Device;
+use crate::backend::mathphysics::Millisecond;
+use crate::backend::networkmodel::NetworkModel;
+
+
+pub const METRICS_PATH: &str = "/metrics";
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+// Name, type and help of each exported metric in the order of
+// `PrometheusSample::values`.
+const METRIC_FAMILIES: [(&str, &str, &str); 6] = [
+    (
+        "drone_network_iterations_total",
+        "counter",
+        "Number of model updates"
+    ),
+    (
+        "drone_network_iterations_per_second",
+        "gauge",
+        "Mean number of model updates per wall-clock second"
+    ),
+    (
+        "drone_network_simulation_time_milliseconds",
+        "gauge",
+        "Current simulation time"
+    ),
+    (
+        "drone_network_devices_alive",
+        "gauge",
+        "Number of drones that are not shut down"
+    ),
+    (
+        "drone_network_devices_infected",
+        "gauge",
+        "Number of infected drones"
+    ),
+    (
+        "drone_network_signal_queue_depth",
+        "gauge",
+        "Number of signals in the signal queue"
+    ),
+];
+
+
+// Counts model updates since creation, so the update rate of long runs can
+// be exported.
+#[derive(Clone, Copy, Debug)]
+pub struct IterationCounter {
+    started_at: Instant,
+    iterations: u64,
+}
+
+impl Default for IterationCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IterationCounter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { started_at: Instant::now(), iterations: 0 }
+    }
+
+    pub fn record(&mut self, iterations: u64) {
+        self.iterations += iterations;
+    }
+
+    #[must_use]
+    pub fn iterations(&self) -> u64 {
+        self.iterations
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn iterations_per_second(&self) -> f64 {
+        let elapsed = self.started_at.elapsed().as_secs_f64();
+
+        if elapsed > 0.0 {
+            self.iterations as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+}
+
+
+// Values of the exported metrics for one model. Drone counts exclude the
+// command device like `Metrics`, but the reachable fraction is not computed,
+// so a sample can be taken on every iteration.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PrometheusSample {
+    iterations: u64,
+    iterations_per_second: f64,
+    simulation_time: Millisecond,
+    alive_count: usize,
+    infected_count: usize,
+    queue_depth: usize,
+}
+
+impl PrometheusSample {
+    #[must_use]
+    pub fn from_model(
+        network_model: &NetworkModel,
+        iteration_counter: &IterationCounter
+    ) -> Self {
+        let drones: Vec<&Device> = network_model.drones().collect();
+
+        Self {
+            iterations: iteration_counter.iterations(),
+            iterations_per_second: iteration_counter.iterations_per_second(),
+            simulation_time: network_model.current_time(),
+            alive_count: drones
+                .iter()
+                .filter(|drone| !drone.is_shut_down())
+                .count(),
+            infected_count: drones
+                .iter()
+                .filter(|drone| drone.is_infected())
+                .count(),
+            queue_depth: network_model.signal_queue().len(),
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn values(&self) -> [f64; 6] {
+        [
+            self.iterations as f64,
+            self.iterations_per_second,
+            f64::from(self.simulation_time.value()),
+            self.alive_count as f64,
+            self.infected_count as f64,
+            self.queue_depth as f64,
+        ]
+    }
+}
+
+
+// Writes samples in the Prometheus text exposition format. Each sample is
+// given with its label set, e.g. `model="1"`, which is empty if only one
+// model is exported.
+#[must_use]
+pub fn encode_samples(samples: &[(String, PrometheusSample)]) -> String {
+    let sample_values: Vec<(&str, [f64; 6])> = samples
+        .iter()
+        .map(|(labels, sample)| (labels.as_str(), sample.values()))
+        .collect();
+    let mut text = String::new();
+
+    for (index, (name, kind, help)) in METRIC_FAMILIES.iter().enumerate() {
+        let _ = writeln!(text, "# HELP {name} {help}");
+        let _ = writeln!(text, "# TYPE {name} {kind}");
+
+        for (labels, values) in &sample_values {
+            if labels.is_empty() {
+                let _ = writeln!(text, "{name} {}", values[index]);
+            } else {
+                let _ = writeln!(text, "{name}{{{labels}}} {}", values[index]);
+            }
+        }
+    }
+
+    text
+}
+
+
+// Serves the latest sample of a played model on `/metrics`. Requests are
+// answered by a background thread, so scrapes do not slow down the
+// simulation.
+pub struct PrometheusExporter {
+    sample: Arc<Mutex<PrometheusSample>>,
+    iteration_counter: IterationCounter,
+}
+
+impl PrometheusExporter {
+    /// # Errors
+    ///
+    /// Will return `Err` if the address can not be bound.
+    pub fn bind(address: SocketAddr) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let sample = Arc::new(Mutex::new(PrometheusSample::default()));
+        let served_sample = Arc::clone(&sample);
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = respond(stream, &served_sample);
+            }
+        });
+
+        Ok(Self { sample, iteration_counter: IterationCounter::new() })
+    }
+
+    // Called after each update.
+    pub fn record_update(&mut self, network_model: &NetworkModel) {
+        self.iteration_counter.record(1);
+
+        let sample = PrometheusSample::from_model(
+            network_model,
+            &self.iteration_counter
+        );
+
+        *self.sample
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = sample;
+    }
+}
+
+fn respond(
+    mut stream: TcpStream,
+    sample: &Mutex<PrometheusSample>
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+
+    reader.read_line(&mut request_line)?;
+
+    // Headers are read, so the connection is not reset before the client
+    // reads the response.
+    let mut header = String::new();
+
+    while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+
+    let path = request_line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default();
+    let (status, body) = if path == METRICS_PATH {
+        let sample = *sample
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        ("200 OK", encode_samples(&[(String::new(), sample)]))
+    } else {
+        ("404 Not Found", String::new())
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {CONTENT_TYPE}\r\n\
+        Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
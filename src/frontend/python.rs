@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::backend::ITERATION_TIME;
+use crate::backend::connections::Topology;
+use crate::backend::device::{
+    Device, DeviceId, SignalLossResponse, device_map_from_slice
+};
+use crate::backend::mathphysics::{Float, Millisecond, Position};
+use crate::backend::metrics::Metrics;
+use crate::backend::networkmodel;
+use crate::backend::random;
+use crate::frontend::config::ModelConfig;
+use crate::frontend::examples::template as model_template;
+
+
+// Values cross the boundary as JSON, so Python gets plain dictionaries and
+// lists shaped like the `--jo` output.
+fn to_python<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<Py<PyAny>> {
+    let json = serde_json::to_string(value)
+        .map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+    py.import("json")?
+        .call_method1("loads", (json,))
+        .map(Bound::unbind)
+}
+
+fn from_python<T: DeserializeOwned>(value: &Bound<'_, PyAny>) -> PyResult<T> {
+    let json: String = value
+        .py()
+        .import("json")?
+        .call_method1("dumps", (value,))?
+        .extract()?;
+
+    serde_json::from_str(&json)
+        .map_err(|error| PyValueError::new_err(error.to_string()))
+}
+
+fn topology(name: &str) -> PyResult<Topology> {
+    match name {
+        "mesh" => Ok(Topology::Mesh),
+        "star" => Ok(Topology::Star),
+        _      => Err(
+            PyValueError::new_err(format!("Unknown topology `{name}`"))
+        ),
+    }
+}
+
+
+// Setters return the builder, so calls can be chained like in Rust.
+#[pyclass(name = "NetworkModelBuilder")]
+#[derive(Default)]
+struct PyNetworkModelBuilder(networkmodel::NetworkModelBuilder);
+
+#[pymethods]
+impl PyNetworkModelBuilder {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn set_command_center_id(
+        mut slf: PyRefMut<'_, Self>,
+        command_center_id: DeviceId
+    ) -> PyRefMut<'_, Self> {
+        slf.0 = std::mem::take(&mut slf.0)
+            .set_command_center_id(command_center_id);
+        slf
+    }
+
+    // Devices are given as a list of device dictionaries.
+    fn set_devices<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        devices: &Bound<'py, PyAny>
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let devices: Vec<Device> = from_python(devices)?;
+
+        slf.0 = std::mem::take(&mut slf.0)
+            .set_device_map(device_map_from_slice(&devices));
+        Ok(slf)
+    }
+
+    fn set_attacker_devices<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        attacker_devices: &Bound<'py, PyAny>
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let attacker_devices = from_python(attacker_devices)?;
+
+        slf.0 = std::mem::take(&mut slf.0)
+            .set_attacker_devices(attacker_devices);
+        Ok(slf)
+    }
+
+    fn set_gps<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        gps: &Bound<'py, PyAny>
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let gps = from_python(gps)?;
+
+        slf.0 = std::mem::take(&mut slf.0).set_gps(gps);
+        Ok(slf)
+    }
+
+    fn set_scenario<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        scenario: &Bound<'py, PyAny>
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let scenario = from_python(scenario)?;
+
+        slf.0 = std::mem::take(&mut slf.0).set_scenario(scenario);
+        Ok(slf)
+    }
+
+    fn set_topology<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        name: &str
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let topology = topology(name)?;
+
+        slf.0 = std::mem::take(&mut slf.0).set_topology(topology);
+        Ok(slf)
+    }
+
+    fn set_delay_multiplier(
+        mut slf: PyRefMut<'_, Self>,
+        delay_multiplier: f32
+    ) -> PyRefMut<'_, Self> {
+        slf.0 = std::mem::take(&mut slf.0)
+            .set_delay_multiplier(delay_multiplier);
+        slf
+    }
+
+    fn build(&self) -> PyNetworkModel {
+        PyNetworkModel(self.0.clone().build())
+    }
+}
+
+
+#[pyclass(name = "NetworkModel", skip_from_py_object)]
+#[derive(Clone)]
+struct PyNetworkModel(networkmodel::NetworkModel);
+
+#[pymethods]
+impl PyNetworkModel {
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        serde_json::from_str(json)
+            .map(Self)
+            .map_err(|error| PyValueError::new_err(error.to_string()))
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        self.0
+            .to_json()
+            .map_err(|error| PyValueError::new_err(error.to_string()))
+    }
+
+    // Releases the interpreter, so other Python threads can run while the
+    // model is updated.
+    #[pyo3(signature = (iterations = 1))]
+    fn update(&mut self, py: Python<'_>, iterations: u32) {
+        py.detach(|| {
+            for _ in 0..iterations {
+                self.0.update();
+            }
+        });
+    }
+
+    // Updates the model until its time advances by `duration` milliseconds.
+    fn run_for(&mut self, py: Python<'_>, duration: i32) {
+        let end_time = self.0.current_time() + Millisecond::new(duration);
+
+        py.detach(|| {
+            while self.0.current_time() < end_time {
+                self.0.update();
+            }
+        });
+    }
+
+    #[getter]
+    fn current_time(&self) -> i32 {
+        self.0.current_time().value()
+    }
+
+    #[getter]
+    fn iteration_time(&self) -> i32 {
+        ITERATION_TIME.value()
+    }
+
+    #[getter]
+    fn command_device_id(&self) -> DeviceId {
+        self.0.command_device_id()
+    }
+
+    fn device_ids(&self) -> Vec<DeviceId> {
+        let mut device_ids: Vec<DeviceId> = self.0
+            .device_map()
+            .values()
+            .map(Device::id)
+            .collect();
+
+        device_ids.sort_unstable();
+
+        device_ids
+    }
+
+    fn device(
+        &self,
+        py: Python<'_>,
+        device_id: DeviceId
+    ) -> PyResult<Py<PyAny>> {
+        let device = self.0
+            .device_map()
+            .get(&device_id)
+            .ok_or_else(||
+                PyKeyError::new_err(
+                    format!("Device {device_id} does not exist")
+                )
+            )?;
+
+        to_python(py, device)
+    }
+
+    // Real positions of all devices as `{id: (x, y, z)}`, which is cheaper
+    // than reading whole devices.
+    fn positions(&self) -> BTreeMap<DeviceId, (Float, Float, Float)> {
+        self.0
+            .device_map()
+            .values()
+            .map(|device| {
+                let position = device.position();
+
+                (device.id(), (position.x, position.y, position.z))
+            })
+            .collect()
+    }
+
+    fn events(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        to_python(py, &self.0.events())
+    }
+
+    fn metrics(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        to_python(py, &Metrics::from_model(&self.0))
+    }
+
+    fn is_settled(&self) -> bool {
+        self.0.is_settled()
+    }
+
+    fn state_checksum(&self) -> u64 {
+        self.0.state_checksum()
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+}
+
+
+// The model written by the `generate` subcommand.
+#[pyfunction]
+#[pyo3(signature = (drone_count = 10, topology = "star"))]
+fn template(drone_count: usize, topology: &str) -> PyResult<PyNetworkModel> {
+    let model_config = ModelConfig::new(
+        SignalLossResponse::default(),
+        self::topology(topology)?,
+        drone_count,
+        0.0,
+    );
+
+    Ok(PyNetworkModel(model_template(&model_config)))
+}
+
+// The generator is thread-local, so the seed applies to models updated on
+// the calling thread.
+#[pyfunction]
+fn set_seed(seed: u64) {
+    random::set_seed(seed);
+}
+
+
+#[pymodule]
+fn drone_network(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyNetworkModelBuilder>()?;
+    module.add_class::<PyNetworkModel>()?;
+    module.add_function(wrap_pyfunction!(template, module)?)?;
+    module.add_function(wrap_pyfunction!(set_seed, module)?)?;
+
+    Ok(())
+}
@@ -1,20 +1,30 @@
-use full_palette::GREY;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
 use plotters::coord::Shift;
 use plotters::coord::ranged3d::Cartesian3d;
 use plotters::coord::types::RangedCoordf64;
 use plotters::prelude::*;
+use thiserror::Error;
 
-use crate::backend::ITERATION_TIME;
 use crate::backend::device::{IdToDeviceMap, IdToTaskMap};
 use crate::backend::mathphysics::Point3D;
+use crate::backend::metrics::Metrics;
 use crate::backend::networkmodel::NetworkModel;
 use crate::backend::task::Task;
 
+use dashboard::draw_metric_panels;
+use frames::FrameWriter;
 use primitives::{
     attacker_device_primitive_on_all_frequencies, command_device_primitive, 
-    destination_primitive, device_primitive
+    connection_primitives, contact_primitive, destination_primitive,
+    gps_area_primitive, gps_primitive,
+    device_label_primitive, device_primitive, legend_entries, 
+    velocity_arrow_primitive, LegendMarker
 };
 
+pub use frames::FrameFormat;
+pub use theme::{RenderTheme, ThemeColor, ThemeError};
 pub use plotcfg::{
     Axes3DRanges, CameraAngle, DeviceColoring, Pixel, PlottersUnit, 
     PlottersPoint3D, PlotResolution, meters_to_pixels, DEFAULT_AXES_RANGE,
@@ -24,24 +34,49 @@ pub use plotcfg::{
 use plotcfg::{font_size, PLOT_MARGIN};
 
 
+mod dashboard;
+mod frames;
 mod plotcfg;
 mod primitives;
+mod theme;
 
 
-type PlottersChartContext<'a> = ChartContext<
+type PlottersChartContext<'a, DB> = ChartContext<
     'a, 
-    BitMapBackend<'a>, 
+    DB, 
     Cartesian3d<RangedCoordf64, RangedCoordf64, RangedCoordf64>
 >;
 
 
 const FONT: &str = "sans-serif";
+// Labels of larger fleets overlap each other too much to be readable.
+const MAX_LABELED_DEVICE_COUNT: usize = 50;
+// Share of the frame width taken by the 3D scene in the dashboard layout.
+const DASHBOARD_SCENE_WIDTH_PERCENT: f64 = 60.0;
+
+
+#[derive(Debug, Error)]
+pub enum RenderError {
+    #[error("{0}: {1}")]
+    Drawing(&'static str, String),
+    #[error("Failed to write frame files: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+
+// Drawing errors are generic over the backend, so only their messages are
+// kept.
+fn drawing_error<E: Display>(
+    context: &'static str
+) -> impl FnOnce(E) -> RenderError {
+    move |error| RenderError::Drawing(context, error.to_string())
+}
 
 
 fn task_map(device_map: &IdToDeviceMap) -> IdToTaskMap {
     device_map
         .iter()
-        .map(|(device_id, device)| (*device_id, *device.task()))
+        .map(|(device_id, device)| (device_id, *device.task()))
         .collect()
 }
 
@@ -56,9 +91,10 @@ fn network_model_destinations(network_model: &NetworkModel) -> Vec<Point3D> {
     for task in task_vec {
         let destination = match task {
             Task::Attack(point) 
+                | Task::DropPayload(point) 
                 | Task::Reconnect(point) 
                 | Task::Reposition(point) => point,
-            Task::Undefined => continue,
+            Task::KeepFormation(_) | Task::Undefined => continue,
         };
 
         destinations.push(destination);
@@ -68,6 +104,48 @@ fn network_model_destinations(network_model: &NetworkModel) -> Vec<Point3D> {
 }
 
 
+// Output backend of the network model states played by `ModelPlayer` and
+// `ReplayPlayer`. Each rendered state goes through `new_frame` and `draw`,
+// and `finish` is called once after the last one.
+pub trait Renderer {
+    /// # Errors
+    ///
+    /// Will return `Err` if the output of the frame can not be created.
+    fn new_frame(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> Result<(), RenderError>;
+
+    /// # Errors
+    ///
+    /// Will return `Err` if an error occurs during drawing.
+    fn draw(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> Result<(), RenderError>;
+
+    // Completes the output, for example finalizes a file. No frames are
+    // expected after it.
+    fn finish(&mut self) {}
+
+    // File or directory the frames are written to, if there is one.
+    fn output_filename(&self) -> Option<String> {
+        None
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the frame can not be created or drawn.
+    fn render(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> Result<(), RenderError> {
+        self.new_frame(network_model)?;
+        self.draw(network_model)
+    }
+}
+
+
 pub struct PlottersRenderer<'a> {
     output_filename: String,
     caption: String,
@@ -76,13 +154,26 @@ pub struct PlottersRenderer<'a> {
     axes_ranges: Axes3DRanges,
     camera_angle: CameraAngle,
     device_coloring: DeviceColoring,
-    area: DrawingArea<BitMapBackend<'a>, Shift>, 
+    theme: RenderTheme,
+    draw_connections: bool,
+    draw_labels: bool,
+    draw_legend: bool,
+    draw_velocities: bool,
+    draw_gps: bool,
+    draw_dashboard: bool,
+    // Metrics of the rendered frames shown in the dashboard panels.
+    metrics_history: Vec<Metrics>,
+    frame_delay: u32,
+    // GIF area is created on the first rendered frame, so no GIF file is
+    // left behind when frames are written separately.
+    area: Option<DrawingArea<BitMapBackend<'a>, Shift>>, 
+    // Frame directory and format, until the frame writer is created on the
+    // first rendered frame, so failures are reported by rendering.
+    frame_output: Option<(PathBuf, FrameFormat)>,
+    frame_writer: Option<FrameWriter>,
 }
 
 impl<'a> PlottersRenderer<'a> {
-    /// # Panics
-    ///
-    /// Will panic if an error occurs during bitmap backend creation. 
     #[must_use]
     pub fn new(
         output_filename: &str,
@@ -91,63 +182,231 @@ impl<'a> PlottersRenderer<'a> {
         axes_ranges: Axes3DRanges,
         device_coloring: DeviceColoring,
         camera_angle: CameraAngle,
+        frame_delay: u32,
     ) -> Self {
-        let font_size = font_size(plot_resolution);
-        let area      = BitMapBackend::gif(
-            output_filename, 
-            plot_resolution.into(),
-            ITERATION_TIME
-                .try_into()
-                .expect("Failed to convert i32 to u32")
-        )
-            .expect("Failed to create `BitMapBackend`")
+        Self {
+            output_filename: output_filename.to_string(),
+            frame_delay,
+            ..Self::with_area(
+                None,
+                caption,
+                plot_resolution,
+                axes_ranges,
+                device_coloring,
+                camera_angle
+            )
+        }
+    }
+
+    // Renders a single frame into an RGB `buffer` of
+    // `plot_resolution.width() * plot_resolution.height() * 3` bytes instead
+    // of a GIF file.
+    #[must_use]
+    pub fn with_buffer(
+        buffer: &'a mut [u8],
+        caption: &str,
+        plot_resolution: PlotResolution,
+        axes_ranges: Axes3DRanges,
+        device_coloring: DeviceColoring,
+        camera_angle: CameraAngle,
+    ) -> Self {
+        let area = BitMapBackend::with_buffer(buffer, plot_resolution.into())
             .into_drawing_area();
 
+        Self::with_area(
+            Some(area),
+            caption,
+            plot_resolution,
+            axes_ranges,
+            device_coloring,
+            camera_angle
+        )
+    }
+
+    fn with_area(
+        area: Option<DrawingArea<BitMapBackend<'a>, Shift>>,
+        caption: &str,
+        plot_resolution: PlotResolution,
+        axes_ranges: Axes3DRanges,
+        device_coloring: DeviceColoring,
+        camera_angle: CameraAngle,
+    ) -> Self {
         Self {
-            output_filename: output_filename.to_string(),
+            output_filename: String::new(),
             caption: caption.to_string(),
             plot_resolution,
-            font_size,
+            font_size: font_size(plot_resolution),
             axes_ranges,
             camera_angle,
             device_coloring,
+            theme: RenderTheme::default(),
+            draw_connections: false,
+            draw_labels: false,
+            draw_legend: false,
+            draw_velocities: false,
+            draw_gps: false,
+            draw_dashboard: false,
+            metrics_history: Vec::new(),
+            frame_delay: 0,
             area,
+            frame_output: None,
+            frame_writer: None,
         }
     }
 
     #[must_use]
-    pub fn output_filename(&self) -> String {
-        self.output_filename.clone()
+    pub fn set_theme(mut self, theme: RenderTheme) -> Self {
+        self.theme = theme;
+        self
     }
 
-    /// # Panics
-    ///
-    /// Will panic if an error occurs during drawing.
-    pub fn render(
-        &mut self, 
+    // Draws control links between devices under the devices, so topology
+    // changes and partitions are visible in each frame.
+    #[must_use]
+    pub fn set_draw_connections(mut self, draw_connections: bool) -> Self {
+        self.draw_connections = draw_connections;
+        self
+    }
+
+    // Annotates devices with their IDs and statuses. Fleets larger than 
+    // `MAX_LABELED_DEVICE_COUNT` are left unlabeled.
+    #[must_use]
+    pub fn set_draw_labels(mut self, draw_labels: bool) -> Self {
+        self.draw_labels = draw_labels;
+        self
+    }
+
+    // Describes device colors, attacker areas and markers in the upper left
+    // corner of each frame.
+    #[must_use]
+    pub fn set_draw_legend(mut self, draw_legend: bool) -> Self {
+        self.draw_legend = draw_legend;
+        self
+    }
+
+    // Draws an arrow from each device along its velocity, as long as the
+    // distance the device covers in a second.
+    #[must_use]
+    pub fn set_draw_velocities(mut self, draw_velocities: bool) -> Self {
+        self.draw_velocities = draw_velocities;
+        self
+    }
+
+    // Draws the GPS source with its coverage area, so GPS denial is visible
+    // next to the jamming and spoofing areas.
+    #[must_use]
+    pub fn set_draw_gps(mut self, draw_gps: bool) -> Self {
+        self.draw_gps = draw_gps;
+        self
+    }
+
+    // Draws the 3D scene on the left side of each frame and time series of
+    // infected drones, mean battery level and reachable drones on the right.
+    #[must_use]
+    pub fn set_draw_dashboard(mut self, draw_dashboard: bool) -> Self {
+        self.draw_dashboard = draw_dashboard;
+        self
+    }
+
+    // Writes every frame into a separate PNG or SVG file in 
+    // `frame_directory` instead of a GIF file.
+    #[must_use]
+    pub fn set_frame_output(
+        mut self,
+        frame_directory: Option<&Path>,
+        frame_format: FrameFormat,
+    ) -> Self {
+        let Some(frame_directory) = frame_directory else {
+            return self;
+        };
+
+        self.output_filename = frame_directory.display().to_string();
+        self.frame_output = Some((frame_directory.to_path_buf(), frame_format));
+        self
+    }
+
+    fn render_frame_file(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> Result<(), RenderError> {
+        let Some(frame_writer) = &self.frame_writer else {
+            return Ok(());
+        };
+        let frame_path = frame_writer.next_frame_path();
+
+        match frame_writer.format() {
+            FrameFormat::Png => self.render_on(
+                &BitMapBackend::new(&frame_path, self.plot_resolution.into())
+                    .into_drawing_area(),
+                network_model
+            ),
+            FrameFormat::Svg => self.render_on(
+                &SVGBackend::new(&frame_path, self.plot_resolution.into())
+                    .into_drawing_area(),
+                network_model
+            ),
+        }?;
+
+        if let Some(frame_writer) = &mut self.frame_writer {
+            frame_writer.record_frame(network_model.current_time())?;
+        }
+
+        Ok(())
+    }
+
+    fn render_on<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
         network_model: &NetworkModel
-    ) {
-        self.area
-            .fill(&WHITE)
-            .expect("Failed to fill an area");
-        
-        let mut chart_context = self.chart_context();
+    ) -> Result<(), RenderError> {
+        area
+            .fill(&self.theme.background())
+            .map_err(drawing_error("Failed to fill an area"))?;
+
+        if self.draw_dashboard {
+            let (scene_area, panel_area) = area.split_horizontally(
+                DASHBOARD_SCENE_WIDTH_PERCENT.percent_width()
+            );
 
-        self.draw_chart(&mut chart_context);
-        self.draw_network_model(network_model, &mut chart_context);
+            draw_metric_panels(
+                &panel_area, 
+                &self.metrics_history, 
+                self.font_size / 3,
+                &self.theme
+            )?;
+            self.draw_scene(&scene_area, network_model)?;
+        } else {
+            self.draw_scene(area, network_model)?;
+        }
 
-        self.area
+        area
             .present()
-            .expect("Failed to finalize drawing");
+            .map_err(drawing_error("Failed to finalize drawing"))
+    }
+
+    fn draw_scene<DB: DrawingBackend>(
+        &self,
+        area: &DrawingArea<DB, Shift>,
+        network_model: &NetworkModel
+    ) -> Result<(), RenderError> {
+        let mut chart_context = self.chart_context(area)?;
+
+        self.draw_chart(&mut chart_context)?;
+        self.draw_network_model(network_model, &mut chart_context)
     }
     
-    fn chart_context(&self) -> PlottersChartContext<'a> {
-        let mut chart_builder = ChartBuilder::on(&self.area);
+    fn chart_context<'b, DB: DrawingBackend>(
+        &self,
+        area: &'b DrawingArea<DB, Shift>
+    ) -> Result<PlottersChartContext<'b, DB>, RenderError> {
+        let mut chart_builder = ChartBuilder::on(area);
 
         if !self.caption.is_empty() {
             chart_builder.caption(
                 &self.caption, 
                 (FONT, self.font_size)
+                    .into_font()
+                    .color(&self.theme.foreground())
             );
         }
 
@@ -158,21 +417,30 @@ impl<'a> PlottersRenderer<'a> {
                 self.axes_ranges.y(),
                 self.axes_ranges.z(),
             )
-            .expect("Failed to create a chart")
+            .map_err(drawing_error("Failed to create a chart"))
     }
 
-    fn draw_network_model(
+    fn draw_network_model<'b, DB: DrawingBackend + 'b>(
         &self,
         network_model: &NetworkModel,
-        chart_context: &mut PlottersChartContext<'a>
-    ) {
-        self.draw_destinations(network_model, chart_context);
-        self.draw_command_device(network_model, chart_context);
-        self.draw_devices(network_model, chart_context);
-        self.draw_attacker_devices(network_model, chart_context);
+        chart_context: &mut PlottersChartContext<'b, DB>
+    ) -> Result<(), RenderError> {
+        self.draw_destinations(network_model, chart_context)?;
+        self.draw_gps(network_model, chart_context)?;
+        self.draw_connections(network_model, chart_context)?;
+        self.draw_command_device(network_model, chart_context)?;
+        self.draw_velocities(network_model, chart_context)?;
+        self.draw_devices(network_model, chart_context)?;
+        self.draw_attacker_devices(network_model, chart_context)?;
+        self.draw_contacts(network_model, chart_context)?;
+        self.draw_device_labels(network_model, chart_context)?;
+        self.draw_legend(network_model, chart_context)
     }
 
-    fn draw_chart(&self, chart_context: &mut PlottersChartContext<'a>) {
+    fn draw_chart<DB: DrawingBackend>(
+        &self,
+        chart_context: &mut PlottersChartContext<'_, DB>
+    ) -> Result<(), RenderError> {
         chart_context 
             .with_projection(|mut p| {
                 p.pitch = self.camera_angle.pitch();
@@ -180,93 +448,349 @@ impl<'a> PlottersRenderer<'a> {
                 p.into_matrix()
             })
             .configure_axes()
-            .axis_panel_style(GREY.mix(0.1))
-            .label_style((FONT, self.font_size / 2))
+            .axis_panel_style(self.theme.axis_panel().mix(0.1))
+            .bold_grid_style(self.theme.foreground().mix(0.2))
+            .label_style(
+                (FONT, self.font_size / 2)
+                    .into_font()
+                    .color(&self.theme.foreground())
+            )
             .draw()
-            .expect("Failed to draw a chart");
+            .map_err(drawing_error("Failed to draw a chart"))
     }
     
-    fn draw_destinations(
+    fn draw_destinations<DB: DrawingBackend>(
         &self, 
         network_model: &NetworkModel,
-        chart_context: &mut PlottersChartContext<'a>
-    ) {
+        chart_context: &mut PlottersChartContext<'_, DB>
+    ) -> Result<(), RenderError> {
         let destinations = network_model_destinations(network_model);
+        let destination_radius = network_model
+            .parameters()
+            .destination_radius();
         let destination_primitives = destinations
             .iter()
             .map(|destination| 
                 destination_primitive(
                     destination, 
-                    self.plot_resolution
+                    destination_radius,
+                    self.plot_resolution,
+                    &self.theme
                 )
             );
 
         chart_context
             .draw_series(destination_primitives)
-            .expect("Failed to draw destination points");
+            .map_err(drawing_error("Failed to draw destination points"))?;
+
+        Ok(())
+    }
+    
+    fn draw_gps<DB: DrawingBackend>(
+        &self, 
+        network_model: &NetworkModel,
+        chart_context: &mut PlottersChartContext<'_, DB>
+    ) -> Result<(), RenderError> {
+        if !self.draw_gps {
+            return Ok(());
+        }
+
+        let gps = network_model.gps();
+
+        chart_context
+            .draw_series(
+                gps_area_primitive(gps, self.plot_resolution, &self.theme)
+            )
+            .map_err(drawing_error("Failed to draw GPS coverage"))?;
+        chart_context
+            .draw_series(gps_primitive(gps, self.plot_resolution, &self.theme))
+            .map_err(drawing_error("Failed to draw GPS source"))?;
+
+        Ok(())
+    }
+    
+    fn draw_connections<DB: DrawingBackend>(
+        &self, 
+        network_model: &NetworkModel,
+        chart_context: &mut PlottersChartContext<'_, DB>
+    ) -> Result<(), RenderError> {
+        if !self.draw_connections {
+            return Ok(());
+        }
+
+        chart_context
+            .draw_series(connection_primitives(network_model, &self.theme))
+            .map_err(drawing_error("Failed to draw connections"))?;
+
+        Ok(())
     }
     
-    fn draw_command_device(
+    fn draw_command_device<DB: DrawingBackend>(
         &self, 
         network_model: &NetworkModel,
-        chart_context: &mut PlottersChartContext<'a>
-    ) {
+        chart_context: &mut PlottersChartContext<'_, DB>
+    ) -> Result<(), RenderError> {
         let Some(command_device) = network_model.command_device() else {
-            return;
+            return Ok(());
         };
         let primitive = command_device_primitive(
             command_device, 
-            self.plot_resolution
+            self.plot_resolution,
+            &self.theme
         );
 
         chart_context
             .draw_series([primitive])
-            .expect("Failed to draw command device");
+            .map_err(drawing_error("Failed to draw command device"))?;
+
+        Ok(())
     }
 
-    fn draw_devices(
+    fn draw_velocities<DB: DrawingBackend>(
         &self, 
         network_model: &NetworkModel,
-        chart_context: &mut PlottersChartContext<'a>
-    ) {
+        chart_context: &mut PlottersChartContext<'_, DB>
+    ) -> Result<(), RenderError> {
+        if !self.draw_velocities {
+            return Ok(());
+        }
+
+        let velocity_primitives = network_model
+            .device_map()
+            .active_devices()
+            .filter_map(|device| 
+                velocity_arrow_primitive(
+                    network_model, 
+                    device, 
+                    self.device_coloring,
+                    &self.theme
+                )
+            );
+
+        chart_context
+            .draw_series(velocity_primitives)
+            .map_err(drawing_error("Failed to draw velocities"))?;
+
+        Ok(())
+    }
+
+    fn draw_devices<DB: DrawingBackend>(
+        &self, 
+        network_model: &NetworkModel,
+        chart_context: &mut PlottersChartContext<'_, DB>
+    ) -> Result<(), RenderError> {
         let device_primitives = network_model
             .device_map()
-            .values()
-            .filter_map(|device|
-                if device.is_shut_down() {
-                    None
-                } else {
-                    Some(device_primitive(
-                        network_model,
-                        device, 
-                        self.device_coloring, 
-                        self.plot_resolution
-                    ))
-                }
+            .active_devices()
+            .map(|device|
+                device_primitive(
+                    network_model,
+                    device, 
+                    self.device_coloring, 
+                    self.plot_resolution,
+                    &self.theme
+                )
             );
 
         chart_context
             .draw_series(device_primitives)
-            .expect("Failed to draw devices");
+            .map_err(drawing_error("Failed to draw devices"))?;
+
+        Ok(())
     }
 
-    fn draw_attacker_devices(
+    fn draw_device_labels<DB: DrawingBackend>(
         &self, 
         network_model: &NetworkModel,
-        chart_context: &mut PlottersChartContext<'a>
-    ) {
+        chart_context: &mut PlottersChartContext<'_, DB>
+    ) -> Result<(), RenderError> {
+        if !self.draw_labels
+            || network_model.device_map().len() > MAX_LABELED_DEVICE_COUNT
+        {
+            return Ok(());
+        }
+
+        let label_primitives = network_model
+            .device_map()
+            .active_devices()
+            .map(|device| 
+                device_label_primitive(
+                    device, 
+                    self.font_size / 3, 
+                    &self.theme
+                )
+            );
+
+        chart_context
+            .draw_series(label_primitives)
+            .map_err(drawing_error("Failed to draw device labels"))?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn draw_legend<'b, DB: DrawingBackend + 'b>(
+        &self, 
+        network_model: &NetworkModel,
+        chart_context: &mut PlottersChartContext<'b, DB>
+    ) -> Result<(), RenderError> {
+        if !self.draw_legend {
+            return Ok(());
+        }
+
+        let marker_size = (self.font_size / 8).max(1) as i32;
+        let entries = legend_entries(
+            network_model, 
+            self.device_coloring, 
+            self.draw_connections,
+            self.draw_gps,
+            &self.theme
+        );
+
+        for (label, color, marker) in entries {
+            let annotation = chart_context
+                .draw_series(std::iter::empty::<EmptyElement<_, _>>())
+                .map_err(drawing_error("Failed to draw a legend entry"))?;
+
+            annotation.label(label);
+
+            match marker {
+                LegendMarker::Area   => annotation.legend(move |point| 
+                    Circle::new(point, marker_size, color)
+                ),
+                LegendMarker::Cross  => annotation.legend(move |point| 
+                    Cross::new(point, marker_size, color)
+                ),
+                LegendMarker::Device => annotation.legend(move |point| 
+                    Circle::new(point, marker_size, color.filled())
+                ),
+                LegendMarker::Link   => annotation.legend(move |(x, y)| 
+                    PathElement::new(
+                        vec![(x - marker_size, y), (x + marker_size, y)], 
+                        color
+                    )
+                ),
+                LegendMarker::Triangle => annotation.legend(move |point| 
+                    TriangleMarker::new(point, marker_size, color.filled())
+                ),
+            };
+        }
+
+        chart_context
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .background_style(self.theme.background().mix(0.8))
+            .border_style(self.theme.foreground())
+            .label_font(
+                (FONT, self.font_size / 3)
+                    .into_font()
+                    .color(&self.theme.foreground())
+            )
+            .draw()
+            .map_err(drawing_error("Failed to draw a legend"))
+    }
+
+    fn draw_attacker_devices<DB: DrawingBackend>(
+        &self, 
+        network_model: &NetworkModel,
+        chart_context: &mut PlottersChartContext<'_, DB>
+    ) -> Result<(), RenderError> {
         let attacker_device_primitives = network_model
             .attacker_devices()
             .iter()
             .flat_map(|attacker_device| {
                 attacker_device_primitive_on_all_frequencies(
                     attacker_device, 
-                    self.plot_resolution
+                    self.plot_resolution,
+                    &self.theme
                 )
             });
 
         chart_context
             .draw_series(attacker_device_primitives)
-            .expect("Failed to draw attacker devices");
+            .map_err(drawing_error("Failed to draw attacker devices"))?;
+
+        Ok(())
+    }
+
+    fn draw_contacts<DB: DrawingBackend>(
+        &self, 
+        network_model: &NetworkModel,
+        chart_context: &mut PlottersChartContext<'_, DB>
+    ) -> Result<(), RenderError> {
+        let contact_primitives = network_model
+            .recognized_picture()
+            .contacts()
+            .values()
+            .map(|contact| 
+                contact_primitive(
+                    contact, 
+                    self.plot_resolution, 
+                    &self.theme
+                )
+            );
+
+        chart_context
+            .draw_series(contact_primitives)
+            .map_err(drawing_error("Failed to draw contacts"))?;
+
+        Ok(())
+    }
+}
+
+
+impl Renderer for PlottersRenderer<'_> {
+    fn new_frame(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> Result<(), RenderError> {
+        if self.draw_dashboard {
+            self.metrics_history.push(Metrics::from_model(network_model));
+        }
+
+        if let Some((frame_directory, frame_format)) = self.frame_output.take()
+        {
+            self.frame_writer = Some(
+                FrameWriter::new(&frame_directory, frame_format)?
+            );
+        }
+
+        if self.frame_writer.is_some() || self.area.is_some() {
+            return Ok(());
+        }
+
+        let area = BitMapBackend::gif(
+            &self.output_filename, 
+            self.plot_resolution.into(),
+            self.frame_delay
+        )
+            .map_err(drawing_error("Failed to create `BitMapBackend`"))?
+            .into_drawing_area();
+
+        self.area = Some(area);
+
+        Ok(())
+    }
+
+    fn draw(
+        &mut self,
+        network_model: &NetworkModel
+    ) -> Result<(), RenderError> {
+        if self.frame_writer.is_some() {
+            self.render_frame_file(network_model)
+        } else if let Some(area) = &self.area {
+            self.render_on(area, network_model)
+        } else {
+            Ok(())
+        }
+    }
+
+    // The GIF file is completed when its backend is dropped.
+    fn finish(&mut self) {
+        self.area = None;
+    }
+
+    fn output_filename(&self) -> Option<String> {
+        Some(self.output_filename.clone())
     }
 }
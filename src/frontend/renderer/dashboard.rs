@@ -0,0 +1,145 @@
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::RGBColor;
+
+use crate::backend::mathphysics::Millisecond;
+use crate::backend::metrics::Metrics;
+
+use super::{drawing_error, Pixel, RenderError, RenderTheme, FONT};
+
+
+const PANEL_MARGIN: Pixel   = 10;
+const PANEL_X_LABELS: usize = 5;
+const PANEL_Y_LABELS: usize = 3;
+// Panels show at least that many seconds, so the first frames are not
+// stretched over the whole time axis.
+const MIN_TIME_RANGE: f64 = 1.0;
+
+
+type MetricValue = fn(&Metrics) -> f64;
+// Title, line color, upper bound and value of a panel metric.
+type MetricPanel = (&'static str, RGBColor, f64, MetricValue);
+
+
+fn seconds(time: Millisecond) -> f64 {
+    f64::from(time.value()) / 1000.0
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn infected_count(metrics: &Metrics) -> f64 {
+    metrics.infected_count() as f64
+}
+
+fn mean_battery_percent(metrics: &Metrics) -> f64 {
+    f64::from(metrics.mean_battery_level()) * 100.0
+}
+
+fn reachable_percent(metrics: &Metrics) -> f64 {
+    f64::from(metrics.reachable_fraction()) * 100.0
+}
+
+
+// Draws infected drone count, mean battery level and reachable drone share
+// over the simulation time in panels stacked on top of each other.
+#[allow(clippy::cast_precision_loss)]
+pub fn draw_metric_panels<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    metrics_history: &[Metrics],
+    font_size: Pixel,
+    theme: &RenderTheme,
+) -> Result<(), RenderError> {
+    // Infected count is bound by the number of drones at the start.
+    let max_infected_count = metrics_history
+        .iter()
+        .map(Metrics::alive_count)
+        .max()
+        .unwrap_or_default()
+        .max(1) as f64;
+    let panels: [MetricPanel; 3] = [
+        (
+            "Infected drones", 
+            theme.infected_metric(), 
+            max_infected_count, 
+            infected_count
+        ),
+        (
+            "Mean battery, %", 
+            theme.battery_metric(), 
+            100.0, 
+            mean_battery_percent
+        ),
+        (
+            "Reachable drones, %", 
+            theme.reachable_metric(), 
+            100.0, 
+            reachable_percent
+        ),
+    ];
+
+    for (panel_area, panel) in area
+        .split_evenly((panels.len(), 1))
+        .iter()
+        .zip(panels)
+    {
+        draw_metric_panel(
+            panel_area,
+            metrics_history,
+            panel,
+            font_size,
+            theme.foreground()
+        )?;
+    }
+
+    Ok(())
+}
+
+fn draw_metric_panel<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    metrics_history: &[Metrics],
+    (title, color, max_value, metric_value): MetricPanel,
+    font_size: Pixel,
+    text_color: RGBColor,
+) -> Result<(), RenderError> {
+    let text_style = (FONT, font_size).into_font().color(&text_color);
+
+    let end_time = metrics_history
+        .last()
+        .map_or(0.0, |metrics| seconds(metrics.time()))
+        .max(MIN_TIME_RANGE);
+
+    let mut chart_context = ChartBuilder::on(area)
+        .caption(title, text_style.clone())
+        .margin(PANEL_MARGIN)
+        // Leaves room for the last time label.
+        .margin_right(PANEL_MARGIN + 2 * font_size)
+        .x_label_area_size(2 * font_size)
+        .y_label_area_size(3 * font_size)
+        .build_cartesian_2d(0.0..end_time, 0.0..max_value)
+        .map_err(drawing_error("Failed to create a metric panel"))?;
+
+    chart_context
+        .configure_mesh()
+        .x_labels(PANEL_X_LABELS)
+        .y_labels(PANEL_Y_LABELS)
+        .max_light_lines(0)
+        .bold_line_style(text_color.mix(0.2))
+        .axis_style(text_color)
+        .label_style(text_style.clone())
+        .axis_desc_style(text_style)
+        .x_desc("Time, s")
+        .draw()
+        .map_err(drawing_error("Failed to draw a metric panel mesh"))?;
+
+    chart_context
+        .draw_series(LineSeries::new(
+            metrics_history
+                .iter()
+                .map(|metrics| 
+                    (seconds(metrics.time()), metric_value(metrics))
+                ),
+            color.stroke_width(2)
+        ))
+        .map_err(drawing_error("Failed to draw a metric series"))?;
+
+    Ok(())
+}
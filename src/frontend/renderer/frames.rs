@@ -0,0 +1,98 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::backend::mathphysics::Millisecond;
+
+
+const INDEX_FILENAME: &str = "index.csv";
+const INDEX_HEADER: &str   = "frame,time,file";
+
+
+#[derive(Clone, Copy, Debug, Default)]
+pub enum FrameFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl FrameFormat {
+    #[must_use]
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+
+// Writes every rendered frame into a separate file and lists the frames with
+// their simulation times in an index file of the same directory.
+pub struct FrameWriter {
+    directory: PathBuf,
+    format: FrameFormat,
+    index_writer: BufWriter<File>,
+    frame_count: usize,
+}
+
+impl FrameWriter {
+    /// # Errors
+    ///
+    /// Will return `Err` if it fails to create the directory or the index
+    /// file.
+    pub fn new(directory: &Path, format: FrameFormat) -> std::io::Result<Self> {
+        fs::create_dir_all(directory)?;
+
+        let index_file = File::create(directory.join(INDEX_FILENAME))?;
+        let mut index_writer = BufWriter::new(index_file);
+
+        writeln!(index_writer, "{INDEX_HEADER}")?;
+
+        Ok(Self {
+            directory: directory.to_path_buf(),
+            format,
+            index_writer,
+            frame_count: 0,
+        })
+    }
+
+    #[must_use]
+    pub fn format(&self) -> FrameFormat {
+        self.format
+    }
+
+    #[must_use]
+    pub fn next_frame_path(&self) -> PathBuf {
+        self.directory.join(self.frame_filename(self.frame_count))
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if it fails to write the index file.
+    pub fn record_frame(
+        &mut self,
+        current_time: Millisecond
+    ) -> std::io::Result<()> {
+        let frame_filename = self.frame_filename(self.frame_count);
+
+        // The index is flushed on each frame, so it stays usable even if the
+        // simulation is interrupted.
+        writeln!(
+            self.index_writer,
+            "{},{},{}",
+            self.frame_count,
+            current_time,
+            frame_filename
+        )
+            .and_then(|()| self.index_writer.flush())?;
+
+        self.frame_count += 1;
+
+        Ok(())
+    }
+
+    fn frame_filename(&self, frame: usize) -> String {
+        format!("frame_{frame:06}.{}", self.format.extension())
+    }
+}
@@ -9,9 +9,7 @@ pub const DEFAULT_AXES_RANGE: Axes3DRanges = Axes3DRanges {
     y: 0.0..200.0,
     z: 0.0..200.0,
 };
-pub const DEFAULT_DEVICE_COLORING: DeviceColoring = DeviceColoring::SingleColor(
-    0, 0, 0
-);
+pub const DEFAULT_DEVICE_COLORING: DeviceColoring = DeviceColoring::Uniform;
 
 const METERS_TO_PIXELS_SCALE_COEF: PlottersUnit = 400.0;
 
@@ -37,7 +35,7 @@ pub fn meters_to_pixels(
     let coef = PlottersUnit::from(plot_resolution.height()) 
         / METERS_TO_PIXELS_SCALE_COEF;
 
-    (PlottersUnit::from(value_in_meters) * coef).round() as Pixel 
+    (PlottersUnit::from(value_in_meters.value()) * coef).round() as Pixel 
 }
 
 
@@ -147,6 +145,9 @@ pub enum DeviceColoring {
     Infection,
     ControlConnection,
     SingleColor(u8, u8, u8),
+    Task,
+    // All devices have the device color of the render theme.
+    Uniform,
 }
 
 
@@ -1,32 +1,64 @@
-use full_palette::{GREEN_400, ORANGE, PINK_300, PINK_200, RED_400, YELLOW_700};
 use plotters::prelude::*;
 use plotters::style::RGBColor;
 
-use crate::backend::DESTINATION_RADIUS;
 use crate::backend::device::Device;
-use crate::backend::mathphysics::{Frequency, Meter, Point3D, Position};
+use crate::backend::mathphysics::{
+    Float, Frequency, Meter, MeterPerSecond, Point3D, Position, Second, 
+    Vector3D
+};
 use crate::backend::networkmodel::NetworkModel;
 use crate::backend::networkmodel::attack::{AttackerDevice, AttackType};
+use crate::backend::networkmodel::gps::GPS;
+use crate::backend::recon::Contact;
 use crate::backend::signal::{
-    SignalStrength, BLACK_SIGNAL_STRENGTH, MAX_BLACK_SIGNAL_STRENGTH, 
+    SignalStrength, BLACK_SIGNAL_STRENGTH, GREEN_SIGNAL_STRENGTH, 
     MAX_RED_SIGNAL_STRENGTH, MAX_YELLOW_SIGNAL_STRENGTH
 };
+use crate::backend::task::Task;
 
 use super::{
     DeviceColoring, Pixel, PlottersUnit, PlottersPoint3D, PlotResolution, 
-    meters_to_pixels, 
+    RenderTheme, meters_to_pixels, FONT
 };
 
 
-const COMMAND_CENTER_RADIUS: Meter = 5.0;
+const COMMAND_CENTER_RADIUS: Meter = Meter::new(5.0);
 
-const CIRCLE_SIZE_COEF: Pixel = 400;
+// Velocity arrows are as long as the distance a device covers in that time.
+const VELOCITY_ARROW_TIME: Second         = 1.0;
+const VELOCITY_ARROW_HEAD_FRACTION: Float = 0.3;
+const MIN_VELOCITY_ARROW_SPEED: MeterPerSecond = 0.1;
 
-const PLOTTERS_DESTINATION_COLOR: RGBColor    = YELLOW;
-const PLOTTERS_COMMAND_CENTER_COLOR: RGBColor = GREEN;
+const CIRCLE_SIZE_COEF: Pixel = 400;
 
 
 type PlottersCircle = Circle<(PlottersUnit, PlottersUnit, PlottersUnit), Pixel>; 
+type PlottersCross = Cross<(PlottersUnit, PlottersUnit, PlottersUnit), Pixel>; 
+type PlottersPath = PathElement<(PlottersUnit, PlottersUnit, PlottersUnit)>;
+type PlottersTriangle = TriangleMarker<
+    (PlottersUnit, PlottersUnit, PlottersUnit), 
+    Pixel
+>;
+type PlottersText = Text<
+    'static, 
+    (PlottersUnit, PlottersUnit, PlottersUnit), 
+    String
+>;
+
+
+// Label, color and marker of a legend entry.
+pub type LegendEntry = (&'static str, RGBColor, LegendMarker);
+
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LegendMarker {
+    // Outline of an area, e.g. an attacker device coverage.
+    Area,
+    Cross,
+    Device,
+    Link,
+    Triangle,
+}
 
 
 fn min_signal_strength(
@@ -41,24 +73,156 @@ fn min_signal_strength(
 }
 
 
+// Only entries that can be seen on the current frame are listed.
+#[must_use]
+pub fn legend_entries(
+    network_model: &NetworkModel,
+    coloring: DeviceColoring,
+    draw_connections: bool,
+    draw_gps: bool,
+    theme: &RenderTheme,
+) -> Vec<LegendEntry> {
+    let mut entries = device_legend_entries(coloring, theme);
+
+    entries.push((
+        "Command center", 
+        theme.command_center(), 
+        LegendMarker::Area
+    ));
+    entries.push(("Destination", theme.destination(), LegendMarker::Area));
+
+    if draw_gps && transmits_gps(network_model.gps()) {
+        entries.push(("GPS source", theme.gps(), LegendMarker::Triangle));
+        entries.push(("GPS coverage", theme.gps(), LegendMarker::Area));
+    }
+
+    if !network_model.recognized_picture().contacts().is_empty() {
+        entries.push(("Contact", theme.contact(), LegendMarker::Cross));
+    }
+
+    if draw_connections {
+        entries.extend(
+            signal_level_legend_entries(
+                &["Strong link", "Medium link", "Weak link"],
+                theme
+            )
+                .into_iter()
+                .map(|(label, color, _)| (label, color, LegendMarker::Link))
+        );
+    }
+
+    for attacker_device in network_model.attacker_devices() {
+        let frequencies = attacker_device
+            .device()
+            .tx_signal_strength_map()
+            .keys();
+
+        for frequency in frequencies {
+            let entry = (
+                attacker_device_area_label(attacker_device, *frequency),
+                attacker_device_area_color(
+                    attacker_device, 
+                    *frequency, 
+                    theme
+                ),
+                LegendMarker::Area
+            );
+
+            if !entries.contains(&entry) {
+                entries.push(entry);
+            }
+        }
+    }
+
+    entries
+}
+
+fn device_legend_entries(
+    coloring: DeviceColoring,
+    theme: &RenderTheme
+) -> Vec<LegendEntry> {
+    match coloring {
+        DeviceColoring::Infection            => vec![
+            (
+                "Infected device", 
+                theme.infection_color(true), 
+                LegendMarker::Device
+            ),
+            ("Device", theme.infection_color(false), LegendMarker::Device),
+        ],
+        DeviceColoring::ControlConnection    => signal_level_legend_entries(
+            &[
+                "Strong control signal", 
+                "Medium control signal", 
+                "Weak control signal", 
+                "No control signal"
+            ],
+            theme
+        ),
+        DeviceColoring::SingleColor(r, g, b) => vec![
+            ("Device", RGBColor(r, g, b), LegendMarker::Device)
+        ],
+        DeviceColoring::Task                 => [
+            ("Attack", Task::Attack(Point3D::default())),
+            ("Drop payload", Task::DropPayload(Point3D::default())),
+            ("Keep formation", Task::KeepFormation(Vector3D::default())),
+            ("Reconnect", Task::Reconnect(Point3D::default())),
+            ("Reposition", Task::Reposition(Point3D::default())),
+            ("No task", Task::Undefined),
+        ]
+            .into_iter()
+            .map(|(label, task)| 
+                (label, theme.task_color(&task), LegendMarker::Device)
+            )
+            .collect(),
+        DeviceColoring::Uniform              => vec![
+            ("Device", theme.device(), LegendMarker::Device)
+        ],
+    }
+}
+
+// Labels go from the strongest signal level to the weakest one.
+fn signal_level_legend_entries(
+    labels: &[&'static str],
+    theme: &RenderTheme
+) -> Vec<LegendEntry> {
+    [
+        GREEN_SIGNAL_STRENGTH, 
+        MAX_YELLOW_SIGNAL_STRENGTH, 
+        MAX_RED_SIGNAL_STRENGTH, 
+        BLACK_SIGNAL_STRENGTH
+    ]
+        .into_iter()
+        .zip(labels)
+        .map(|(signal_strength, label)| (
+            *label, 
+            theme.signal_strength_color(signal_strength), 
+            LegendMarker::Device
+        ))
+        .collect()
+}
+
 #[must_use]
 pub fn destination_primitive( 
     destination: &Point3D,
-    plot_resolution: PlotResolution
+    destination_radius: Meter,
+    plot_resolution: PlotResolution,
+    theme: &RenderTheme
 ) -> PlottersCircle {
     let point  = PlottersPoint3D::from(destination);
     let radius = meters_to_pixels(
-        DESTINATION_RADIUS,
+        destination_radius,
         plot_resolution
     );
 
-    Circle::new(point.into(), radius, PLOTTERS_DESTINATION_COLOR)
+    Circle::new(point.into(), radius, theme.destination())
 }
 
 #[must_use]
 pub fn command_device_primitive(
     command_device: &Device,
-    plot_resolution: PlotResolution
+    plot_resolution: PlotResolution,
+    theme: &RenderTheme
 ) -> PlottersCircle {
     let point  = PlottersPoint3D::from(command_device.position());
     let radius = meters_to_pixels(
@@ -66,7 +230,93 @@ pub fn command_device_primitive(
         plot_resolution
     );  
     
-    Circle::new(point.into(), radius, PLOTTERS_COMMAND_CENTER_COLOR)
+    Circle::new(point.into(), radius, theme.command_center())
+}
+
+// Models without a GPS source have a default one that does not transmit.
+fn transmits_gps(gps: &GPS) -> bool {
+    gps.device().area_radius_on(Frequency::GPS) > Meter::ZERO
+}
+
+#[must_use]
+pub fn gps_primitive(
+    gps: &GPS,
+    plot_resolution: PlotResolution,
+    theme: &RenderTheme
+) -> Option<PlottersTriangle> {
+    if !transmits_gps(gps) {
+        return None;
+    }
+
+    let point = PlottersPoint3D::from(gps.device().position());
+    let size  = 3 * device_size(plot_resolution);
+
+    Some(TriangleMarker::new(point.into(), size, theme.gps().filled()))
+}
+
+#[must_use]
+pub fn gps_area_primitive(
+    gps: &GPS,
+    plot_resolution: PlotResolution,
+    theme: &RenderTheme
+) -> Option<PlottersCircle> {
+    if !transmits_gps(gps) {
+        return None;
+    }
+
+    let point  = PlottersPoint3D::from(gps.device().position());
+    let radius = meters_to_pixels(
+        gps.device().area_radius_on(Frequency::GPS),
+        plot_resolution
+    );
+
+    Some(Circle::new(point.into(), radius, theme.gps()))
+}
+
+#[must_use]
+pub fn contact_primitive(
+    contact: &Contact,
+    plot_resolution: PlotResolution,
+    theme: &RenderTheme
+) -> PlottersCross {
+    let point = PlottersPoint3D::from(contact.position());
+    let size  = 2 * device_size(plot_resolution);
+
+    Cross::new(point.into(), size, theme.contact())
+}
+
+// Both directions of a link are drawn as one line colored by the weaker
+// of them.
+#[must_use]
+pub fn connection_primitives(
+    network_model: &NetworkModel,
+    theme: &RenderTheme
+) -> Vec<PlottersPath> {
+    let graph_map = network_model.connections().graph_map();
+
+    graph_map
+        .all_edges()
+        .filter(|(tx_id, rx_id, _)| tx_id < rx_id)
+        .filter_map(|(tx_id, rx_id, (_, signal_strength))| {
+            let tx = network_model.device_map().get(&tx_id)?;
+            let rx = network_model.device_map().get(&rx_id)?;
+            let signal_strength = graph_map
+                .edge_weight(rx_id, tx_id)
+                .map_or(*signal_strength, |(_, reverse_signal_strength)|
+                    min_signal_strength(
+                        *signal_strength, 
+                        *reverse_signal_strength
+                    )
+                );
+            let tx_point = PlottersPoint3D::from(tx.position());
+            let rx_point = PlottersPoint3D::from(rx.position());
+
+            Some(PathElement::new(
+                vec![tx_point.into(), rx_point.into()],
+                theme.signal_strength_color(signal_strength)
+            ))
+        })
+        .collect()
 }
 
 #[must_use]
@@ -74,37 +324,111 @@ pub fn device_primitive(
     network_model: &NetworkModel,
     device: &Device,
     coloring: DeviceColoring,
-    plot_resolution: PlotResolution
+    plot_resolution: PlotResolution,
+    theme: &RenderTheme
 ) -> PlottersCircle {
     let point = PlottersPoint3D::from(device.position());
-    let color = device_color(network_model, device, coloring);
+    let color = device_color(network_model, device, coloring, theme);
     let size  = device_size(plot_resolution); 
     let style = Into::<ShapeStyle>::into(color).filled();
 
     Circle::new(point.into(), size, style)
 }
 
+// The arrow starts at the device and points along its velocity. Its head is
+// drawn in the horizontal plane, or along `x` axis for vertical motion.
+#[must_use]
+pub fn velocity_arrow_primitive(
+    network_model: &NetworkModel,
+    device: &Device,
+    coloring: DeviceColoring,
+    theme: &RenderTheme
+) -> Option<PlottersPath> {
+    let velocity = device.velocity();
+
+    if velocity.size() < MIN_VELOCITY_ARROW_SPEED {
+        return None;
+    }
+
+    let direction = velocity.displacement() / velocity.size();
+    let arrow_length = velocity.size() * Float::from(VELOCITY_ARROW_TIME);
+    let head_length = arrow_length * VELOCITY_ARROW_HEAD_FRACTION;
+    let horizontal_size = direction.x.hypot(direction.y);
+    let side = if horizontal_size > 0.0 {
+        Point3D::new(-direction.y, direction.x, 0.0) / horizontal_size
+    } else {
+        Point3D::new(1.0, 0.0, 0.0)
+    };
+
+    let start = *device.position();
+    let tip = start + direction * arrow_length;
+    let head_base = tip - direction * head_length;
+    let left_barb = head_base + side * (head_length / 2.0);
+    let right_barb = head_base - side * (head_length / 2.0);
+    let points = [start, tip, left_barb, tip, right_barb]
+        .into_iter()
+        .map(|point| PlottersPoint3D::from(point).into())
+        .collect::<Vec<_>>();
+
+    Some(PathElement::new(
+        points, 
+        device_color(network_model, device, coloring, theme)
+    ))
+}
+
+// The label is the device ID followed by its task, an infection mark and its 
+// battery level, e.g. "12 ATK I 85%".
+#[must_use]
+pub fn device_label_primitive(
+    device: &Device,
+    font_size: Pixel,
+    theme: &RenderTheme
+) -> PlottersText {
+    let point = PlottersPoint3D::from(device.position());
+    let infection_mark = if device.is_infected() { " I" } else { "" };
+    let label = format!(
+        "{} {}{} {:.0}%",
+        device.id(),
+        task_abbreviation(device.task()),
+        infection_mark,
+        device.power_system().level() * 100.0
+    );
+
+    Text::new(
+        label, 
+        point.into(), 
+        (FONT, font_size).into_font().color(&theme.foreground())
+    )
+}
+
+fn task_abbreviation(task: &Task) -> &'static str {
+    match task {
+        Task::Attack(_)        => "ATK",
+        Task::DropPayload(_)   => "DRP",
+        Task::KeepFormation(_) => "FRM",
+        Task::Reconnect(_)     => "RCN",
+        Task::Reposition(_)    => "RPS",
+        Task::Undefined        => "UND",
+    }
+}
+
 fn device_color(
     network_model: &NetworkModel, 
     device: &Device,
-    coloring: DeviceColoring
+    coloring: DeviceColoring,
+    theme: &RenderTheme
 ) -> RGBColor {
     match coloring {
         DeviceColoring::Infection            => 
-            color_by_infection(device.is_infected()),
+            theme.infection_color(device.is_infected()),
         DeviceColoring::ControlConnection    => 
-            color_by_signal_strength(
+            theme.signal_strength_color(
                 device_control_signal_strength(network_model, device)
             ),
         DeviceColoring::SingleColor(r, g, b) => RGBColor(r, g, b),
-    }
-}
-
-fn color_by_infection(infected: bool) -> RGBColor {
-    if infected {
-        PINK_200
-    } else {
-        BLACK
+        DeviceColoring::Task                 => 
+            theme.task_color(device.task()),
+        DeviceColoring::Uniform              => theme.device(),
     }
 }
 
@@ -152,18 +476,6 @@ fn device_control_signal_strength(
     min_control_signal_strength
 }
 
-fn color_by_signal_strength(signal_strength: SignalStrength) -> RGBColor {
-    if signal_strength > MAX_YELLOW_SIGNAL_STRENGTH {
-        GREEN_400 
-    } else if signal_strength > MAX_RED_SIGNAL_STRENGTH {
-        YELLOW_700
-    } else if signal_strength > MAX_BLACK_SIGNAL_STRENGTH {
-        RED_400
-    } else {
-        BLACK
-    }
-}
-
 fn device_size(plot_resolution: PlotResolution) -> Pixel {
     if plot_resolution.width() < CIRCLE_SIZE_COEF {
         return 1;  
@@ -176,6 +488,7 @@ fn device_size(plot_resolution: PlotResolution) -> Pixel {
 pub fn attacker_device_primitive_on_all_frequencies(
     attacker_device: &AttackerDevice,
     plot_resolution: PlotResolution,
+    theme: &RenderTheme,
 ) -> Vec<PlottersCircle> {
     attacker_device
         .device()
@@ -185,7 +498,8 @@ pub fn attacker_device_primitive_on_all_frequencies(
             attacker_device_primitive(
                 attacker_device, 
                 *frequency, 
-                plot_resolution
+                plot_resolution,
+                theme
             )
         )
         .collect()
@@ -195,7 +509,8 @@ pub fn attacker_device_primitive_on_all_frequencies(
 pub fn attacker_device_primitive(
     attacker_device: &AttackerDevice,
     frequency: Frequency,
-    plot_resolution: PlotResolution
+    plot_resolution: PlotResolution,
+    theme: &RenderTheme
 ) -> PlottersCircle {
     let point = PlottersPoint3D::from(
         attacker_device.device().position()
@@ -204,14 +519,41 @@ pub fn attacker_device_primitive(
         .device()
         .area_radius_on(frequency);
     let attacker_device_coverage = meters_to_pixels(radius, plot_resolution);
-    let area_color = attacker_device_area_color(attacker_device, frequency);
+    let area_color = attacker_device_area_color(
+        attacker_device, 
+        frequency, 
+        theme
+    );
 
     Circle::new(point.into(), attacker_device_coverage, area_color)
 }
 
-fn attacker_device_area_color(
+fn attacker_device_area_label(
     attacker_device: &AttackerDevice,
     frequency: Frequency
+) -> &'static str {
+    let spoofs_gps = matches!(
+        attacker_device.attack_type(), 
+        AttackType::GPSSpoofing(_)
+    );
+    let spreads_malware = matches!(
+        attacker_device.attack_type(), 
+        AttackType::MalwareDistribution(_)
+    );
+    
+    match frequency {
+        _ if frequency.is_gps() && spoofs_gps => "GPS spoofing area",
+        _ if frequency.is_gps()               => "GPS jamming area",
+        _ if spreads_malware                  => "Malware area",
+        Frequency::Backup                     => "Backup jamming area",
+        _                                     => "Control jamming area",
+    }
+}
+
+fn attacker_device_area_color(
+    attacker_device: &AttackerDevice,
+    frequency: Frequency,
+    theme: &RenderTheme
 ) -> RGBColor {
     let spoofs_gps = matches!(
         attacker_device.attack_type(), 
@@ -223,9 +565,9 @@ fn attacker_device_area_color(
     );
     
     match frequency {
-        Frequency::GPS if spoofs_gps          => ORANGE,
-        Frequency::GPS                        => RED,
-        Frequency::Control if spreads_malware => PINK_300,
-        Frequency::Control                    => BLUE,
+        _ if frequency.is_gps() && spoofs_gps => theme.gps_spoofing_area(),
+        _ if frequency.is_gps()               => theme.gps_jamming_area(),
+        _ if spreads_malware                  => theme.malware_area(),
+        _                                     => theme.control_jamming_area(),
     }
 }
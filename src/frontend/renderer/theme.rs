@@ -0,0 +1,329 @@
+use std::fs;
+use std::path::Path;
+
+use plotters::style::RGBColor;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::backend::signal::{
+    SignalStrength, MAX_BLACK_SIGNAL_STRENGTH, MAX_RED_SIGNAL_STRENGTH,
+    MAX_YELLOW_SIGNAL_STRENGTH
+};
+use crate::backend::task::Task;
+
+
+#[derive(Debug, Error)]
+pub enum ThemeError {
+    #[error("Failed to read theme: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to deserialize theme: {0}")]
+    Deserialization(#[from] serde_json::Error),
+}
+
+
+#[derive(Debug, Error)]
+#[error("Invalid color `{0}`, expected \"#rrggbb\"")]
+pub struct InvalidThemeColor(String);
+
+
+// Colors are (de)serialized as "#rrggbb" strings.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ThemeColor(pub u8, pub u8, pub u8);
+
+impl TryFrom<String> for ThemeColor {
+    type Error = InvalidThemeColor;
+
+    fn try_from(hex: String) -> Result<Self, Self::Error> {
+        let invalid_color = || InvalidThemeColor(hex.clone());
+        let digits = hex
+            .strip_prefix('#')
+            .filter(|digits| digits.len() == 6 && digits.is_ascii())
+            .ok_or_else(invalid_color)?;
+        let channel = |index: usize|
+            u8::from_str_radix(&digits[index..index + 2], 16)
+                .map_err(|_| invalid_color());
+
+        Ok(Self(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl From<ThemeColor> for String {
+    fn from(color: ThemeColor) -> Self {
+        format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+    }
+}
+
+impl From<ThemeColor> for RGBColor {
+    fn from(color: ThemeColor) -> Self {
+        RGBColor(color.0, color.1, color.2)
+    }
+}
+
+
+// Palette of every rendered element. Colors missing from a theme file are
+// taken from the light theme.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RenderTheme {
+    background: ThemeColor,
+    // Text, axes, grid lines and legend border.
+    foreground: ThemeColor,
+    axis_panel: ThemeColor,
+    device: ThemeColor,
+    infected_device: ThemeColor,
+    command_center: ThemeColor,
+    destination: ThemeColor,
+    contact: ThemeColor,
+    gps: ThemeColor,
+    strong_signal: ThemeColor,
+    medium_signal: ThemeColor,
+    weak_signal: ThemeColor,
+    no_signal: ThemeColor,
+    attack_task: ThemeColor,
+    drop_payload_task: ThemeColor,
+    keep_formation_task: ThemeColor,
+    reconnect_task: ThemeColor,
+    reposition_task: ThemeColor,
+    gps_spoofing_area: ThemeColor,
+    gps_jamming_area: ThemeColor,
+    malware_area: ThemeColor,
+    control_jamming_area: ThemeColor,
+    infected_metric: ThemeColor,
+    battery_metric: ThemeColor,
+    reachable_metric: ThemeColor,
+}
+
+impl Default for RenderTheme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl RenderTheme {
+    #[must_use]
+    pub fn light() -> Self {
+        Self {
+            background: ThemeColor(255, 255, 255),
+            foreground: ThemeColor(0, 0, 0),
+            axis_panel: ThemeColor(158, 158, 158),
+            device: ThemeColor(0, 0, 0),
+            infected_device: ThemeColor(244, 143, 177),
+            command_center: ThemeColor(0, 255, 0),
+            destination: ThemeColor(255, 255, 0),
+            contact: ThemeColor(255, 0, 255),
+            gps: ThemeColor(0, 150, 136),
+            strong_signal: ThemeColor(102, 187, 106),
+            medium_signal: ThemeColor(251, 192, 45),
+            weak_signal: ThemeColor(239, 83, 80),
+            no_signal: ThemeColor(0, 0, 0),
+            attack_task: ThemeColor(171, 71, 188),
+            drop_payload_task: ThemeColor(255, 167, 38),
+            keep_formation_task: ThemeColor(38, 198, 218),
+            reconnect_task: ThemeColor(66, 165, 245),
+            reposition_task: ThemeColor(102, 187, 106),
+            gps_spoofing_area: ThemeColor(255, 152, 0),
+            gps_jamming_area: ThemeColor(255, 0, 0),
+            malware_area: ThemeColor(240, 98, 146),
+            control_jamming_area: ThemeColor(0, 0, 255),
+            infected_metric: ThemeColor(236, 64, 122),
+            battery_metric: ThemeColor(255, 167, 38),
+            reachable_metric: ThemeColor(66, 165, 245),
+        }
+    }
+
+    #[must_use]
+    pub fn dark() -> Self {
+        Self {
+            background: ThemeColor(30, 30, 30),
+            foreground: ThemeColor(230, 230, 230),
+            axis_panel: ThemeColor(120, 120, 120),
+            device: ThemeColor(230, 230, 230),
+            infected_device: ThemeColor(244, 143, 177),
+            command_center: ThemeColor(0, 230, 118),
+            destination: ThemeColor(255, 235, 59),
+            contact: ThemeColor(234, 128, 252),
+            gps: ThemeColor(77, 182, 172),
+            strong_signal: ThemeColor(102, 187, 106),
+            medium_signal: ThemeColor(253, 216, 53),
+            weak_signal: ThemeColor(239, 83, 80),
+            no_signal: ThemeColor(117, 117, 117),
+            attack_task: ThemeColor(206, 147, 216),
+            drop_payload_task: ThemeColor(255, 183, 77),
+            keep_formation_task: ThemeColor(77, 208, 225),
+            reconnect_task: ThemeColor(100, 181, 246),
+            reposition_task: ThemeColor(129, 199, 132),
+            gps_spoofing_area: ThemeColor(255, 167, 38),
+            gps_jamming_area: ThemeColor(239, 83, 80),
+            malware_area: ThemeColor(240, 98, 146),
+            control_jamming_area: ThemeColor(100, 181, 246),
+            infected_metric: ThemeColor(240, 98, 146),
+            battery_metric: ThemeColor(255, 183, 77),
+            reachable_metric: ThemeColor(100, 181, 246),
+        }
+    }
+
+    // Okabe-Ito palette, which stays distinguishable with the common color
+    // vision deficiencies.
+    #[must_use]
+    pub fn colorblind() -> Self {
+        let orange = ThemeColor(230, 159, 0);
+        let sky_blue = ThemeColor(86, 180, 233);
+        let bluish_green = ThemeColor(0, 158, 115);
+        let yellow = ThemeColor(240, 228, 66);
+        let blue = ThemeColor(0, 114, 178);
+        let vermillion = ThemeColor(213, 94, 0);
+        let reddish_purple = ThemeColor(204, 121, 167);
+        let black = ThemeColor(0, 0, 0);
+
+        Self {
+            background: ThemeColor(255, 255, 255),
+            foreground: black,
+            axis_panel: ThemeColor(158, 158, 158),
+            device: black,
+            infected_device: vermillion,
+            command_center: bluish_green,
+            destination: yellow,
+            contact: reddish_purple,
+            gps: sky_blue,
+            strong_signal: blue,
+            medium_signal: orange,
+            weak_signal: reddish_purple,
+            no_signal: black,
+            attack_task: vermillion,
+            drop_payload_task: orange,
+            keep_formation_task: sky_blue,
+            reconnect_task: blue,
+            reposition_task: bluish_green,
+            gps_spoofing_area: orange,
+            gps_jamming_area: vermillion,
+            malware_area: reddish_purple,
+            control_jamming_area: blue,
+            infected_metric: vermillion,
+            battery_metric: orange,
+            reachable_metric: blue,
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the file can not be read or it does not contain
+    /// a JSON theme.
+    pub fn from_json(theme_path: &Path) -> Result<Self, ThemeError> {
+        let json_string = fs::read_to_string(theme_path)?;
+
+        Ok(serde_json::from_str(&json_string)?)
+    }
+
+    #[must_use]
+    pub fn background(&self) -> RGBColor {
+        self.background.into()
+    }
+
+    #[must_use]
+    pub fn foreground(&self) -> RGBColor {
+        self.foreground.into()
+    }
+
+    #[must_use]
+    pub fn axis_panel(&self) -> RGBColor {
+        self.axis_panel.into()
+    }
+
+    #[must_use]
+    pub fn device(&self) -> RGBColor {
+        self.device.into()
+    }
+
+    #[must_use]
+    pub fn command_center(&self) -> RGBColor {
+        self.command_center.into()
+    }
+
+    #[must_use]
+    pub fn destination(&self) -> RGBColor {
+        self.destination.into()
+    }
+
+    #[must_use]
+    pub fn contact(&self) -> RGBColor {
+        self.contact.into()
+    }
+
+    #[must_use]
+    pub fn gps(&self) -> RGBColor {
+        self.gps.into()
+    }
+
+    #[must_use]
+    pub fn infection_color(&self, infected: bool) -> RGBColor {
+        if infected {
+            self.infected_device.into()
+        } else {
+            self.device.into()
+        }
+    }
+
+    #[must_use]
+    pub fn task_color(&self, task: &Task) -> RGBColor {
+        match task {
+            Task::Attack(_)        => self.attack_task.into(),
+            Task::DropPayload(_)   => self.drop_payload_task.into(),
+            Task::KeepFormation(_) => self.keep_formation_task.into(),
+            Task::Reconnect(_)     => self.reconnect_task.into(),
+            Task::Reposition(_)    => self.reposition_task.into(),
+            Task::Undefined        => self.device.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn signal_strength_color(
+        &self,
+        signal_strength: SignalStrength
+    ) -> RGBColor {
+        if signal_strength > MAX_YELLOW_SIGNAL_STRENGTH {
+            self.strong_signal.into()
+        } else if signal_strength > MAX_RED_SIGNAL_STRENGTH {
+            self.medium_signal.into()
+        } else if signal_strength > MAX_BLACK_SIGNAL_STRENGTH {
+            self.weak_signal.into()
+        } else {
+            self.no_signal.into()
+        }
+    }
+
+    #[must_use]
+    pub fn gps_spoofing_area(&self) -> RGBColor {
+        self.gps_spoofing_area.into()
+    }
+
+    #[must_use]
+    pub fn gps_jamming_area(&self) -> RGBColor {
+        self.gps_jamming_area.into()
+    }
+
+    #[must_use]
+    pub fn malware_area(&self) -> RGBColor {
+        self.malware_area.into()
+    }
+
+    #[must_use]
+    pub fn control_jamming_area(&self) -> RGBColor {
+        self.control_jamming_area.into()
+    }
+
+    #[must_use]
+    pub fn infected_metric(&self) -> RGBColor {
+        self.infected_metric.into()
+    }
+
+    #[must_use]
+    pub fn battery_metric(&self) -> RGBColor {
+        self.battery_metric.into()
+    }
+
+    #[must_use]
+    pub fn reachable_metric(&self) -> RGBColor {
+        self.reachable_metric.into()
+    }
+}
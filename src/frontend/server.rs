@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+
+#[cfg(feature = "grpc")]
+pub use grpc::{serve_grpc, SimulationService};
+#[cfg(feature = "rest")]
+pub use rest::serve_rest;
+
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "rest")]
+pub mod rest;
+
+
+#[derive(Debug, Error)]
+pub enum ServerError {
+    #[error("Failed to start server: {0}")]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "grpc")]
+    #[error("gRPC server failed: {0}")]
+    Grpc(#[from] tonic::transport::Error),
+}
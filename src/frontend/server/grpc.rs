@@ -0,0 +1,222 @@
+// The generated service trait returns `tonic::Status` as error, so it is used
+// by all helpers as well.
+#![allow(clippy::result_large_err)]
+
+use std::net::SocketAddr;
+use std::sync::{Mutex, MutexGuard};
+
+use tonic::transport::Server;
+use tonic::{Request, Response, Status};
+
+use crate::backend::metrics::Metrics;
+use crate::backend::networkmodel::NetworkModel;
+use crate::backend::signal::Signal;
+use crate::backend::task::Task;
+
+use super::ServerError;
+
+use proto::simulation_server::{Simulation, SimulationServer};
+use proto::{
+    InjectSignalRequest, InspectRequest, InspectResponse, IssueTaskRequest,
+    ModelState, ScenarioRequest, ScenarioResponse, StartRequest, StepRequest
+};
+
+
+#[allow(clippy::all, clippy::pedantic)]
+pub mod proto {
+    tonic::include_proto!("drone_network");
+}
+
+
+impl From<Metrics> for ModelState {
+    fn from(metrics: Metrics) -> Self {
+        Self {
+            current_time: metrics.time().value(),
+            alive_count: metrics.alive_count() as u64,
+            infected_count: metrics.infected_count() as u64,
+            mean_battery_level: metrics.mean_battery_level(),
+            reachable_fraction: metrics.reachable_fraction(),
+            sensor_coverage: metrics.sensor_coverage(),
+            control_coverage: metrics.control_coverage(),
+            aggregation_ratio: metrics.aggregation_ratio(),
+        }
+    }
+}
+
+
+fn model_state(network_model: &NetworkModel) -> ModelState {
+    Metrics::from_model(network_model).into()
+}
+
+fn json_error(error: &serde_json::Error) -> Status {
+    Status::invalid_argument(error.to_string())
+}
+
+
+// Holds the network model that is driven by RPCs. Only one model runs at a
+// time, `Start` replaces it.
+#[derive(Default)]
+pub struct SimulationService {
+    network_model: Mutex<Option<NetworkModel>>,
+}
+
+impl SimulationService {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Option<NetworkModel>>, Status> {
+        self.network_model
+            .lock()
+            .map_err(|_| Status::internal("Network model lock is poisoned"))
+    }
+
+    // Runs `f` on the running network model.
+    fn with_model<T>(
+        &self,
+        f: impl FnOnce(&mut NetworkModel) -> Result<T, Status>
+    ) -> Result<T, Status> {
+        let mut network_model = self.lock()?;
+        let network_model = network_model
+            .as_mut()
+            .ok_or_else(||
+                Status::failed_precondition("Network model is not started")
+            )?;
+
+        f(network_model)
+    }
+}
+
+#[tonic::async_trait]
+impl Simulation for SimulationService {
+    async fn start(
+        &self,
+        request: Request<StartRequest>
+    ) -> Result<Response<ModelState>, Status> {
+        let network_model: NetworkModel = serde_json::from_str(
+            &request.get_ref().model_json
+        ).map_err(|error| json_error(&error))?;
+        let state = model_state(&network_model);
+
+        *self.lock()? = Some(network_model);
+
+        Ok(Response::new(state))
+    }
+
+    async fn step(
+        &self,
+        request: Request<StepRequest>
+    ) -> Result<Response<ModelState>, Status> {
+        let iterations = request.get_ref().iterations;
+
+        self.with_model(|network_model| {
+            for _ in 0..iterations {
+                network_model.update();
+            }
+
+            Ok(Response::new(model_state(network_model)))
+        })
+    }
+
+    async fn inspect(
+        &self,
+        request: Request<InspectRequest>
+    ) -> Result<Response<InspectResponse>, Status> {
+        let device_id = request.get_ref().device_id;
+
+        self.with_model(|network_model| {
+            let json = match device_id {
+                Some(device_id) => {
+                    let device_id = usize::try_from(device_id)
+                        .map_err(|error|
+                            Status::invalid_argument(error.to_string())
+                        )?;
+                    let device = network_model
+                        .device_map()
+                        .get(&device_id)
+                        .ok_or_else(||
+                            Status::not_found(
+                                format!("Device {device_id} does not exist")
+                            )
+                        )?;
+
+                    serde_json::to_string(device)
+                },
+                None            => network_model.to_json(),
+            }.map_err(|error| Status::internal(error.to_string()))?;
+
+            Ok(Response::new(InspectResponse {
+                state: Some(model_state(network_model)),
+                json,
+            }))
+        })
+    }
+
+    async fn inject_signal(
+        &self,
+        request: Request<InjectSignalRequest>
+    ) -> Result<Response<ModelState>, Status> {
+        let signal: Signal = serde_json::from_str(
+            &request.get_ref().signal_json
+        ).map_err(|error| json_error(&error))?;
+
+        self.with_model(|network_model| {
+            network_model.inject_signal(signal);
+
+            Ok(Response::new(model_state(network_model)))
+        })
+    }
+
+    async fn issue_task(
+        &self,
+        request: Request<IssueTaskRequest>
+    ) -> Result<Response<ModelState>, Status> {
+        let request = request.get_ref();
+        let task: Task = serde_json::from_str(&request.task_json)
+            .map_err(|error| json_error(&error))?;
+        let device_id = usize::try_from(request.device_id)
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+
+        self.with_model(|network_model| {
+            if !network_model.issue_task(device_id, task) {
+                return Err(
+                    Status::not_found(
+                        format!("Device {device_id} does not exist")
+                    )
+                );
+            }
+
+            Ok(Response::new(model_state(network_model)))
+        })
+    }
+
+    async fn get_scenario(
+        &self,
+        _request: Request<ScenarioRequest>
+    ) -> Result<Response<ScenarioResponse>, Status> {
+        self.with_model(|network_model| {
+            let scenario_json = serde_json::to_string(
+                network_model.scenario()
+            ).map_err(|error| Status::internal(error.to_string()))?;
+
+            Ok(Response::new(ScenarioResponse { scenario_json }))
+        })
+    }
+}
+
+
+/// # Errors
+///
+/// Will return `Err` if the runtime can not be started or the server fails.
+pub fn serve_grpc(address: SocketAddr) -> Result<(), ServerError> {
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    runtime.block_on(
+        Server::builder()
+            .add_service(SimulationServer::new(SimulationService::new()))
+            .serve(address)
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use axum::Router;
+use axum::extract::{Json, Path, Query, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use image::ImageEncoder;
+use image::codecs::png::PngEncoder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::backend::device::DeviceId;
+use crate::backend::metrics::Metrics;
+use crate::backend::networkmodel::NetworkModel;
+use crate::backend::task::{Scenario, Task};
+use crate::frontend::config::RenderConfig;
+use crate::frontend::prometheus::{
+    encode_samples, IterationCounter, PrometheusSample, CONTENT_TYPE,
+    METRICS_PATH
+};
+use crate::frontend::renderer::{
+    PlottersRenderer, RenderError, Renderer, DEFAULT_AXES_RANGE
+};
+
+use super::ServerError;
+
+
+type ModelId = u64;
+
+
+#[derive(Debug)]
+enum ApiError {
+    ModelNotFound(ModelId),
+    DeviceNotFound(DeviceId),
+    Serialization(serde_json::Error),
+    Encoding(image::ImageError),
+    Rendering(RenderError),
+    PoisonedLock,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            Self::ModelNotFound(model_id)   => (
+                StatusCode::NOT_FOUND,
+                format!("Model {model_id} does not exist")
+            ),
+            Self::DeviceNotFound(device_id) => (
+                StatusCode::NOT_FOUND,
+                format!("Device {device_id} does not exist")
+            ),
+            Self::Serialization(error)      => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to serialize: {error}")
+            ),
+            Self::Encoding(error)           => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to encode frame: {error}")
+            ),
+            Self::Rendering(error)          => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to render frame: {error}")
+            ),
+            Self::PoisonedLock              => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Model storage lock is poisoned".to_string()
+            ),
+        };
+
+        (status, message).into_response()
+    }
+}
+
+
+#[derive(Serialize)]
+struct ModelCreated {
+    id: ModelId,
+    state: Metrics,
+}
+
+#[derive(Deserialize)]
+struct StepQuery {
+    #[serde(default = "default_iterations")]
+    iterations: u32,
+}
+
+fn default_iterations() -> u32 {
+    1
+}
+
+#[derive(Deserialize)]
+struct TaskRequest {
+    device_id: DeviceId,
+    task: Task,
+}
+
+
+#[derive(Default)]
+struct ModelStorage {
+    models: HashMap<ModelId, NetworkModel>,
+    iteration_counters: HashMap<ModelId, IterationCounter>,
+    next_id: ModelId,
+}
+
+struct ServerState {
+    storage: Mutex<ModelStorage>,
+    render_config: RenderConfig,
+}
+
+impl ServerState {
+    fn storage(&self) -> Result<MutexGuard<'_, ModelStorage>, ApiError> {
+        self.storage
+            .lock()
+            .map_err(|_| ApiError::PoisonedLock)
+    }
+
+    // Runs `f` on the model with `model_id`.
+    fn with_model<T>(
+        &self,
+        model_id: ModelId,
+        f: impl FnOnce(&mut NetworkModel) -> Result<T, ApiError>
+    ) -> Result<T, ApiError> {
+        let mut storage = self.storage()?;
+        let network_model = storage.models
+            .get_mut(&model_id)
+            .ok_or(ApiError::ModelNotFound(model_id))?;
+
+        f(network_model)
+    }
+}
+
+
+fn render_png(
+    network_model: &NetworkModel,
+    render_config: &RenderConfig
+) -> Result<Vec<u8>, ApiError> {
+    let plot_resolution = render_config.plot_resolution();
+    let (width, height) = plot_resolution.into();
+    let mut buffer = vec![0; width as usize * height as usize * 3];
+
+    PlottersRenderer::with_buffer(
+        &mut buffer,
+        render_config.plot_caption(),
+        plot_resolution,
+        DEFAULT_AXES_RANGE,
+        render_config.device_coloring(),
+        render_config.camera_angle(),
+    )
+        .set_theme(render_config.theme().clone())
+        .set_draw_connections(render_config.draw_connections())
+        .set_draw_labels(render_config.draw_labels())
+        .set_draw_legend(render_config.draw_legend())
+        .set_draw_velocities(render_config.draw_velocities())
+        .set_draw_gps(render_config.draw_gps())
+        .set_draw_dashboard(render_config.draw_dashboard())
+        .render(network_model)
+        .map_err(ApiError::Rendering)?;
+
+    let mut png = Vec::new();
+
+    PngEncoder::new(&mut png)
+        .write_image(&buffer, width, height, image::ColorType::Rgb8)
+        .map_err(ApiError::Encoding)?;
+
+    Ok(png)
+}
+
+
+async fn create_model(
+    State(state): State<Arc<ServerState>>,
+    Json(network_model): Json<NetworkModel>
+) -> Result<(StatusCode, Json<ModelCreated>), ApiError> {
+    let mut storage = state.storage()?;
+    let id = storage.next_id;
+    let model_state = Metrics::from_model(&network_model);
+
+    storage.next_id += 1;
+    storage.models.insert(id, network_model);
+    storage.iteration_counters.insert(id, IterationCounter::new());
+
+    Ok((StatusCode::CREATED, Json(ModelCreated { id, state: model_state })))
+}
+
+async fn get_model(
+    State(state): State<Arc<ServerState>>,
+    Path(model_id): Path<ModelId>
+) -> Result<Json<Value>, ApiError> {
+    state.with_model(model_id, |network_model| {
+        network_model
+            .to_json_value()
+            .map(Json)
+            .map_err(ApiError::Serialization)
+    })
+}
+
+async fn delete_model(
+    State(state): State<Arc<ServerState>>,
+    Path(model_id): Path<ModelId>
+) -> Result<StatusCode, ApiError> {
+    let mut storage = state.storage()?;
+
+    storage.iteration_counters.remove(&model_id);
+    storage
+        .models
+        .remove(&model_id)
+        .map(|_| StatusCode::NO_CONTENT)
+        .ok_or(ApiError::ModelNotFound(model_id))
+}
+
+async fn get_state(
+    State(state): State<Arc<ServerState>>,
+    Path(model_id): Path<ModelId>
+) -> Result<Json<Metrics>, ApiError> {
+    state.with_model(model_id, |network_model|
+        Ok(Json(Metrics::from_model(network_model)))
+    )
+}
+
+async fn step_model(
+    State(state): State<Arc<ServerState>>,
+    Path(model_id): Path<ModelId>,
+    Query(step_query): Query<StepQuery>
+) -> Result<Json<Metrics>, ApiError> {
+    let mut storage = state.storage()?;
+    let network_model = storage.models
+        .get_mut(&model_id)
+        .ok_or(ApiError::ModelNotFound(model_id))?;
+
+    for _ in 0..step_query.iterations {
+        network_model.update();
+    }
+
+    let model_state = Metrics::from_model(network_model);
+
+    storage.iteration_counters
+        .entry(model_id)
+        .or_default()
+        .record(u64::from(step_query.iterations));
+
+    Ok(Json(model_state))
+}
+
+// The task is recorded into the model scenario, which is returned by
+// `get_scenario` for later replay.
+async fn issue_task(
+    State(state): State<Arc<ServerState>>,
+    Path(model_id): Path<ModelId>,
+    Json(TaskRequest { device_id, task }): Json<TaskRequest>
+) -> Result<Json<Metrics>, ApiError> {
+    state.with_model(model_id, |network_model| {
+        if !network_model.issue_task(device_id, task) {
+            return Err(ApiError::DeviceNotFound(device_id));
+        }
+
+        Ok(Json(Metrics::from_model(network_model)))
+    })
+}
+
+async fn get_scenario(
+    State(state): State<Arc<ServerState>>,
+    Path(model_id): Path<ModelId>
+) -> Result<Json<Scenario>, ApiError> {
+    state.with_model(model_id, |network_model|
+        Ok(Json(network_model.scenario().clone()))
+    )
+}
+
+async fn get_device(
+    State(state): State<Arc<ServerState>>,
+    Path((model_id, device_id)): Path<(ModelId, DeviceId)>
+) -> Result<Json<Value>, ApiError> {
+    state.with_model(model_id, |network_model| {
+        let device = network_model
+            .device_map()
+            .get(&device_id)
+            .ok_or(ApiError::DeviceNotFound(device_id))?;
+
+        serde_json::to_value(device)
+            .map(Json)
+            .map_err(ApiError::Serialization)
+    })
+}
+
+async fn get_frame(
+    State(state): State<Arc<ServerState>>,
+    Path(model_id): Path<ModelId>
+) -> Result<impl IntoResponse, ApiError> {
+    let png = state.with_model(model_id, |network_model|
+        render_png(network_model, &state.render_config)
+    )?;
+
+    Ok(([(header::CONTENT_TYPE, "image/png")], png))
+}
+
+
+// Samples of all stored models labeled by model ID.
+async fn get_metrics(
+    State(state): State<Arc<ServerState>>
+) -> Result<impl IntoResponse, ApiError> {
+    let storage = state.storage()?;
+    let mut model_ids: Vec<ModelId> = storage.models
+        .keys()
+        .copied()
+        .collect();
+
+    model_ids.sort_unstable();
+
+    let samples: Vec<(String, PrometheusSample)> = model_ids
+        .into_iter()
+        .map(|model_id| {
+            let iteration_counter = storage.iteration_counters
+                .get(&model_id)
+                .copied()
+                .unwrap_or_default();
+            let sample = PrometheusSample::from_model(
+                &storage.models[&model_id],
+                &iteration_counter
+            );
+
+            (format!("model=\"{model_id}\""), sample)
+        })
+        .collect();
+
+    Ok(([(header::CONTENT_TYPE, CONTENT_TYPE)], encode_samples(&samples)))
+}
+
+
+fn router(render_config: RenderConfig) -> Router {
+    let state = ServerState {
+        storage: Mutex::new(ModelStorage::default()),
+        render_config,
+    };
+
+    Router::new()
+        .route("/models", post(create_model))
+        .route("/models/{model_id}", get(get_model).delete(delete_model))
+        .route("/models/{model_id}/state", get(get_state))
+        .route("/models/{model_id}/step", post(step_model))
+        .route("/models/{model_id}/tasks", post(issue_task))
+        .route("/models/{model_id}/scenario", get(get_scenario))
+        .route("/models/{model_id}/devices/{device_id}", get(get_device))
+        .route("/models/{model_id}/frame", get(get_frame))
+        .route(METRICS_PATH, get(get_metrics))
+        .with_state(Arc::new(state))
+}
+
+/// # Errors
+///
+/// Will return `Err` if the runtime can not be started or the address can
+/// not be bound.
+pub fn serve_rest(
+    address: SocketAddr,
+    render_config: RenderConfig
+) -> Result<(), ServerError> {
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(address).await?;
+
+        axum::serve(listener, router(render_config)).await
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::backend::device::systems::EnergyReport;
+use crate::backend::epidemic::EpidemicStats;
+use crate::backend::metrics::Metrics;
+use crate::backend::provenance::Provenance;
+use crate::backend::signal::{DeliveryStats, LatencyReport};
+
+
+// Clap exits with 2 on wrong arguments, so the codes do not overlap.
+const EXIT_CODE_COMPLETED: i32 = 0;
+const EXIT_CODE_FAILED: i32    = 1;
+const EXIT_CODE_STOPPED: i32   = 3;
+
+
+#[derive(Debug, Error)]
+pub enum RunSummaryError {
+    #[error("Failed to write run summary: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to serialize run summary: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    // The simulation time has run out.
+    Completed,
+    // The run ended early on request, from the TUI dashboard or with Ctrl-C.
+    Stopped,
+    Failed,
+}
+
+impl RunStatus {
+    #[must_use]
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Completed => EXIT_CODE_COMPLETED,
+            Self::Stopped   => EXIT_CODE_STOPPED,
+            Self::Failed    => EXIT_CODE_FAILED,
+        }
+    }
+}
+
+
+// What a player reports about the run once it is over.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayOutcome {
+    status: RunStatus,
+    final_metrics: Option<Metrics>,
+    render_filename: Option<String>,
+    epidemic_stats: Option<EpidemicStats>,
+    latency_report: Option<LatencyReport>,
+    energy_report: Option<EnergyReport>,
+    delivery_stats: Option<DeliveryStats>,
+}
+
+impl PlayOutcome {
+    #[must_use]
+    pub fn new(
+        status: RunStatus,
+        final_metrics: Option<Metrics>,
+        render_filename: Option<String>,
+    ) -> Self {
+        Self {
+            status,
+            final_metrics,
+            render_filename,
+            epidemic_stats: None,
+            latency_report: None,
+            energy_report: None,
+            delivery_stats: None,
+        }
+    }
+
+    #[must_use]
+    pub fn set_epidemic_stats(
+        mut self,
+        epidemic_stats: Option<EpidemicStats>
+    ) -> Self {
+        self.epidemic_stats = epidemic_stats;
+        self
+    }
+
+    #[must_use]
+    pub fn set_latency_report(
+        mut self,
+        latency_report: Option<LatencyReport>
+    ) -> Self {
+        self.latency_report = latency_report;
+        self
+    }
+
+    #[must_use]
+    pub fn set_energy_report(
+        mut self,
+        energy_report: Option<EnergyReport>
+    ) -> Self {
+        self.energy_report = energy_report;
+        self
+    }
+
+    #[must_use]
+    pub fn set_delivery_stats(
+        mut self,
+        delivery_stats: Option<DeliveryStats>
+    ) -> Self {
+        self.delivery_stats = delivery_stats;
+        self
+    }
+
+    #[must_use]
+    pub fn status(&self) -> RunStatus {
+        self.status
+    }
+
+    #[must_use]
+    pub fn final_metrics(&self) -> Option<&Metrics> {
+        self.final_metrics.as_ref()
+    }
+
+    #[must_use]
+    pub fn render_filename(&self) -> Option<&str> {
+        self.render_filename.as_deref()
+    }
+
+    #[must_use]
+    pub fn epidemic_stats(&self) -> Option<&EpidemicStats> {
+        self.epidemic_stats.as_ref()
+    }
+
+    #[must_use]
+    pub fn latency_report(&self) -> Option<&LatencyReport> {
+        self.latency_report.as_ref()
+    }
+
+    #[must_use]
+    pub fn energy_report(&self) -> Option<&EnergyReport> {
+        self.energy_report.as_ref()
+    }
+
+    #[must_use]
+    pub fn delivery_stats(&self) -> Option<&DeliveryStats> {
+        self.delivery_stats.as_ref()
+    }
+}
+
+
+// Files written by the run, keyed by their kind.
+pub type OutputPaths = BTreeMap<&'static str, PathBuf>;
+
+
+// Configuration is stored as option values, so the run can be repeated with
+// the same options and seed.
+#[derive(Clone, Debug, Serialize)]
+pub struct RunSummary {
+    status: RunStatus,
+    exit_code: i32,
+    experiment: String,
+    seed: u64,
+    crate_version: String,
+    config_hash: String,
+    started_at: String,
+    config: BTreeMap<String, Value>,
+    metrics: Option<Metrics>,
+    // Only present if drones were infected.
+    epidemic: Option<EpidemicStats>,
+    // Only present if latency tracking was enabled.
+    latency: Option<LatencyReport>,
+    // Only present if the run was played.
+    energy: Option<EnergyReport>,
+    // Only present if the run was played.
+    delivery: Option<DeliveryStats>,
+    outputs: OutputPaths,
+}
+
+impl RunSummary {
+    #[must_use]
+    pub fn new(
+        provenance: &Provenance,
+        config: BTreeMap<String, Value>,
+        status: RunStatus,
+    ) -> Self {
+        Self {
+            status,
+            exit_code: status.exit_code(),
+            experiment: provenance.experiment().to_string(),
+            seed: provenance.seed(),
+            crate_version: provenance.crate_version().to_string(),
+            config_hash: provenance.config_hash().to_string(),
+            started_at: provenance.started_at().to_string(),
+            config,
+            metrics: None,
+            epidemic: None,
+            latency: None,
+            energy: None,
+            delivery: None,
+            outputs: OutputPaths::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn set_metrics(mut self, metrics: Option<Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    #[must_use]
+    pub fn set_epidemic(mut self, epidemic: Option<EpidemicStats>) -> Self {
+        self.epidemic = epidemic;
+        self
+    }
+
+    #[must_use]
+    pub fn set_latency(mut self, latency: Option<LatencyReport>) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    #[must_use]
+    pub fn set_energy(mut self, energy: Option<EnergyReport>) -> Self {
+        self.energy = energy;
+        self
+    }
+
+    #[must_use]
+    pub fn set_delivery(mut self, delivery: Option<DeliveryStats>) -> Self {
+        self.delivery = delivery;
+        self
+    }
+
+    #[must_use]
+    pub fn set_outputs(mut self, outputs: OutputPaths) -> Self {
+        self.outputs = outputs;
+        self
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the summary can not be serialized or written.
+    pub fn write(&self, summary_path: &Path) -> Result<(), RunSummaryError> {
+        if let Some(parent) = summary_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(summary_path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+}
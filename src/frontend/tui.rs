@@ -0,0 +1,297 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::time::{Duration, Instant};
+
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::widgets::{Block, Gauge, Row, Table};
+use ratatui::Frame;
+
+use crate::backend::device::DeviceId;
+use crate::backend::event::EventKind;
+use crate::backend::mathphysics::Millisecond;
+use crate::backend::metrics::Metrics;
+use crate::backend::networkmodel::NetworkModel;
+
+
+// Drawing every iteration would slow down short iterations, so the
+// dashboard is redrawn at most that often.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+const TOP_SPREADER_COUNT: usize  = 10;
+const QUIT_KEY: char             = 'q';
+
+
+// Live text dashboard that replaces logs in the terminal while the model is
+// played. The terminal is restored when the dashboard is dropped.
+pub struct TuiDashboard {
+    terminal: DefaultTerminal,
+    started_at: Instant,
+    drawn_at: Option<Instant>,
+    iteration: u32,
+    event_counts: BTreeMap<&'static str, usize>,
+    // Number of devices each device has infected.
+    spread_counts: HashMap<DeviceId, usize>,
+}
+
+impl TuiDashboard {
+    /// # Errors
+    ///
+    /// Will return `Err` if the terminal cannot be switched to the
+    /// dashboard screen.
+    pub fn new() -> io::Result<Self> {
+        Ok(Self {
+            terminal: ratatui::try_init()?,
+            started_at: Instant::now(),
+            drawn_at: None,
+            iteration: 0,
+            event_counts: BTreeMap::new(),
+            spread_counts: HashMap::new(),
+        })
+    }
+
+    pub fn record_iteration(&mut self, network_model: &NetworkModel) {
+        self.iteration = self.iteration.wrapping_add(1);
+
+        for event in network_model.events() {
+            *self.event_counts
+                .entry(event.kind().name())
+                .or_default() += 1;
+
+            if let EventKind::Infected { source_id, .. } = event.kind() {
+                *self.spread_counts.entry(*source_id).or_default() += 1;
+            }
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if drawing to the terminal fails.
+    pub fn draw(
+        &mut self,
+        network_model: &NetworkModel,
+        end_time: Millisecond,
+    ) -> io::Result<()> {
+        // The last iteration is always drawn, so the final state stays on
+        // the screen.
+        if network_model.current_time() < end_time && !self.refresh_due() {
+            return Ok(());
+        }
+
+        self.drawn_at = Some(Instant::now());
+
+        let metrics = Metrics::from_model(network_model);
+        let counters = self.counter_rows(&metrics);
+        let top_spreaders = self.top_spreader_rows(network_model);
+        let progress = progress(network_model.current_time(), end_time);
+
+        self.terminal.draw(|frame|
+            draw_frame(frame, progress, counters, top_spreaders)
+        )?;
+
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if terminal events cannot be read.
+    pub fn quit_requested(&self) -> io::Result<bool> {
+        while event::poll(Duration::ZERO)? {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+
+            if key.kind == KeyEventKind::Press
+                && key.code == KeyCode::Char(QUIT_KEY)
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn refresh_due(&self) -> bool {
+        self.drawn_at
+            .is_none_or(|drawn_at| drawn_at.elapsed() >= REFRESH_INTERVAL)
+    }
+
+    fn counter_rows(&self, metrics: &Metrics) -> Vec<Row<'static>> {
+        let elapsed = self.started_at.elapsed();
+        let iteration_cost = if self.iteration == 0 {
+            Duration::ZERO
+        } else {
+            elapsed / self.iteration
+        };
+
+        let mut rows = vec![
+            row("Time, ms", metrics.time().to_string()),
+            row("Iteration", self.iteration.to_string()),
+            row("Elapsed, s", format!("{:.1}", elapsed.as_secs_f32())),
+            row(
+                "Iteration cost, ms",
+                format!("{:.2}", iteration_cost.as_secs_f64() * 1000.0)
+            ),
+            row("Alive drones", metrics.alive_count().to_string()),
+            row("Infected drones", metrics.infected_count().to_string()),
+            row(
+                "Mean battery, %",
+                format!("{:.1}", metrics.mean_battery_level() * 100.0)
+            ),
+            row(
+                "Reachable drones, %",
+                format!("{:.1}", metrics.reachable_fraction() * 100.0)
+            ),
+        ];
+
+        if let Some(sensor_coverage) = metrics.sensor_coverage() {
+            rows.push(
+                row(
+                    "Sensor coverage, %",
+                    format!("{:.1}", sensor_coverage * 100.0)
+                )
+            );
+        }
+        if let Some(control_coverage) = metrics.control_coverage() {
+            rows.push(
+                row(
+                    "Control coverage, %",
+                    format!("{:.1}", control_coverage * 100.0)
+                )
+            );
+        }
+        if let Some(aggregation_ratio) = metrics.aggregation_ratio() {
+            rows.push(
+                row(
+                    "Records per summary",
+                    format!("{aggregation_ratio:.1}")
+                )
+            );
+        }
+
+        rows.extend(
+            self.event_counts
+                .iter()
+                .map(|(name, count)| row(name, count.to_string()))
+        );
+
+        rows
+    }
+
+    // Infected devices that have infected the most other devices.
+    fn top_spreader_rows(
+        &self,
+        network_model: &NetworkModel
+    ) -> Vec<Row<'static>> {
+        let mut spreaders: Vec<(DeviceId, usize, f32)> = network_model
+            .device_map()
+            .infected_devices()
+            .map(|device| (
+                device.id(),
+                self.spread_counts
+                    .get(&device.id())
+                    .copied()
+                    .unwrap_or_default(),
+                device.power_system().level()
+            ))
+            .collect();
+
+        spreaders.sort_by(|(id1, count1, _), (id2, count2, _)|
+            count2.cmp(count1).then(id1.cmp(id2))
+        );
+
+        spreaders
+            .into_iter()
+            .take(TOP_SPREADER_COUNT)
+            .map(|(device_id, count, battery_level)| Row::new([
+                device_id.to_string(),
+                count.to_string(),
+                format!("{:.0}", battery_level * 100.0),
+            ]))
+            .collect()
+    }
+}
+
+impl Drop for TuiDashboard {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}
+
+
+fn row(name: &str, value: String) -> Row<'static> {
+    Row::new([name.to_string(), value])
+}
+
+fn progress(current_time: Millisecond, end_time: Millisecond) -> f64 {
+    if end_time == Millisecond::ZERO {
+        return 1.0;
+    }
+
+    (
+        f64::from(current_time.value()) / f64::from(end_time.value())
+    ).clamp(0.0, 1.0)
+}
+
+fn draw_frame(
+    frame: &mut Frame,
+    progress: f64,
+    counters: Vec<Row<'static>>,
+    top_spreaders: Vec<Row<'static>>,
+) {
+    let [progress_area, tables_area, help_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+        .areas(frame.area());
+    let [counters_area, top_spreaders_area] = Layout::horizontal([
+        Constraint::Percentage(50),
+        Constraint::Percentage(50),
+    ])
+        .areas(tables_area);
+
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::bordered().title("Progress"))
+            .gauge_style(Style::default().fg(Color::Green))
+            .ratio(progress),
+        progress_area
+    );
+    draw_table(
+        frame,
+        counters_area,
+        "Counters",
+        ["Counter", "Value"],
+        counters
+    );
+    draw_table(
+        frame,
+        top_spreaders_area,
+        "Top infected devices",
+        ["Device", "Infected devices", "Battery, %"],
+        top_spreaders
+    );
+    frame.render_widget(
+        format!("Press `{QUIT_KEY}` to stop the simulation").dim(),
+        help_area
+    );
+}
+
+fn draw_table<const N: usize>(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    header: [&str; N],
+    rows: Vec<Row<'static>>,
+) {
+    let widths = [Constraint::Fill(1); N];
+
+    frame.render_widget(
+        Table::new(rows, widths)
+            .header(Row::new(header).bold())
+            .block(Block::bordered().title(title.to_string())),
+        area
+    );
+}
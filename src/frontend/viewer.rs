@@ -0,0 +1,397 @@
+use std::f32::consts::FRAC_PI_2;
+
+use eframe::egui::{
+    self, Color32, Painter, Pos2, Rect, Sense, Stroke, Ui, Vec2
+};
+
+use crate::backend::device::{Device, DeviceId};
+use crate::backend::mathphysics::{
+    float_to_f32, Float, Frequency, Meter, Millisecond, Point3D, Position
+};
+use crate::backend::networkmodel::NetworkModel;
+
+
+const WINDOW_TITLE: &str = "Drone network";
+
+// The scene is a cube with the same side as the default plot axes.
+const SCENE_SIZE: Float = 200.0;
+const SCENE_CENTER: Point3D = Point3D {
+    x: SCENE_SIZE / 2.0,
+    y: SCENE_SIZE / 2.0,
+    z: SCENE_SIZE / 2.0
+};
+
+const DEFAULT_YAW: f32   = 0.5;
+const DEFAULT_PITCH: f32 = 0.3;
+// Radians per dragged pixel.
+const ROTATION_SPEED: f32 = 0.01;
+// Zoom change per scrolled pixel.
+const ZOOM_SPEED: f32 = 0.002;
+const MIN_ZOOM: f32   = 0.2;
+const MAX_ZOOM: f32   = 10.0;
+
+const DEVICE_RADIUS: f32         = 3.0;
+const COMMAND_DEVICE_RADIUS: f32 = 6.0;
+// Clicks farther than that from any device clear the selection.
+const PICK_RADIUS: f32           = 10.0;
+
+const BACKGROUND_COLOR: Color32      = Color32::WHITE;
+const GROUND_COLOR: Color32          = Color32::GRAY;
+const LINK_COLOR: Color32            = Color32::from_gray(220);
+const DEVICE_COLOR: Color32          = Color32::BLACK;
+const INFECTED_DEVICE_COLOR: Color32 = Color32::from_rgb(244, 143, 177);
+const COMMAND_DEVICE_COLOR: Color32  = Color32::GREEN;
+const ATTACKER_DEVICE_COLOR: Color32 = Color32::RED;
+const SELECTION_COLOR: Color32       = Color32::BLUE;
+
+
+/// # Errors
+///
+/// Will return `Err` if the window cannot be created.
+pub fn run_live_viewer(
+    network_model: NetworkModel,
+    end_time: Millisecond,
+) -> eframe::Result {
+    eframe::run_native(
+        WINDOW_TITLE,
+        eframe::NativeOptions::default(),
+        Box::new(move |_| 
+            Ok(Box::new(LiveViewer::new(network_model, end_time)))
+        )
+    )
+}
+
+
+#[derive(Clone, Copy)]
+struct ViewerCamera {
+    yaw: f32,
+    pitch: f32,
+    zoom: f32,
+}
+
+impl Default for ViewerCamera {
+    fn default() -> Self {
+        Self {
+            yaw: DEFAULT_YAW,
+            pitch: DEFAULT_PITCH,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl ViewerCamera {
+    fn rotate(&mut self, drag_delta: Vec2) {
+        self.yaw -= drag_delta.x * ROTATION_SPEED;
+        self.pitch = (self.pitch + drag_delta.y * ROTATION_SPEED)
+            .clamp(-FRAC_PI_2, FRAC_PI_2);
+    }
+
+    fn zoom(&mut self, scroll_delta: f32) {
+        self.zoom = (self.zoom * (1.0 + scroll_delta * ZOOM_SPEED))
+            .clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    fn scale(&self, rect: Rect) -> f32 {
+        self.zoom * rect.width().min(rect.height()) / float_to_f32(SCENE_SIZE)
+    }
+
+    // Orthographic projection of the scene rotated around its vertical axis
+    // by yaw and tilted towards the viewer by pitch.
+    fn project(&self, point: &Point3D, rect: Rect) -> Pos2 {
+        let offset = *point - SCENE_CENTER;
+        let (x, y, z) = (
+            float_to_f32(offset.x),
+            float_to_f32(offset.y),
+            float_to_f32(offset.z)
+        );
+        let (yaw_sin, yaw_cos) = self.yaw.sin_cos();
+        let (pitch_sin, pitch_cos) = self.pitch.sin_cos();
+
+        let right = x * yaw_cos - y * yaw_sin;
+        let depth = x * yaw_sin + y * yaw_cos;
+        let up = z * pitch_cos + depth * pitch_sin;
+
+        rect.center() + Vec2::new(right, -up) * self.scale(rect)
+    }
+}
+
+
+struct LiveViewer {
+    network_model: NetworkModel,
+    end_time: Millisecond,
+    playing: bool,
+    camera: ViewerCamera,
+    selected_device_id: Option<DeviceId>,
+}
+
+impl LiveViewer {
+    fn new(network_model: NetworkModel, end_time: Millisecond) -> Self {
+        Self {
+            network_model,
+            end_time,
+            playing: false,
+            camera: ViewerCamera::default(),
+            selected_device_id: None,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.network_model.current_time() >= self.end_time
+    }
+
+    fn step(&mut self) {
+        if !self.is_finished() {
+            self.network_model.update();
+        }
+    }
+
+    fn show_controls(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let play_label = if self.playing { "Pause" } else { "Play" };
+
+            if ui.button(play_label).clicked() {
+                self.playing = !self.playing;
+            }
+            if ui.add_enabled(!self.playing, egui::Button::new("Step"))
+                .clicked()
+            {
+                self.step();
+            }
+            if ui.button("Reset view").clicked() {
+                self.camera = ViewerCamera::default();
+            }
+
+            ui.separator();
+            ui.label(format!(
+                "Time: {} / {} ms",
+                self.network_model.current_time(),
+                self.end_time
+            ));
+        });
+    }
+
+    fn show_inspector(&self, ui: &mut Ui) {
+        ui.heading("Device");
+
+        let Some(device) = self.selected_device_id
+            .and_then(|device_id|
+                self.network_model.device_map().get(&device_id)
+            )
+        else {
+            ui.label("Click a device to inspect it");
+            return;
+        };
+
+        egui::Grid::new("device inspector")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (name, value) in device_properties(device) {
+                    ui.label(name);
+                    ui.label(value);
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn show_scene(&mut self, ui: &mut Ui) {
+        let (response, painter) = ui.allocate_painter(
+            ui.available_size(),
+            Sense::click_and_drag()
+        );
+        let rect = response.rect;
+
+        if response.dragged() {
+            self.camera.rotate(response.drag_delta());
+        }
+        if response.hovered() {
+            let scroll_delta = ui.input(|input| input.smooth_scroll_delta.y);
+
+            self.camera.zoom(scroll_delta);
+        }
+        if response.clicked() {
+            self.selected_device_id = response
+                .interact_pointer_pos()
+                .and_then(|pointer_position|
+                    self.device_at(pointer_position, rect)
+                );
+        }
+
+        painter.rect_filled(rect, 0.0, BACKGROUND_COLOR);
+        self.draw_ground(&painter, rect);
+        self.draw_links(&painter, rect);
+        self.draw_attacker_devices(&painter, rect);
+        self.draw_devices(&painter, rect);
+    }
+
+    fn device_at(
+        &self,
+        pointer_position: Pos2,
+        rect: Rect
+    ) -> Option<DeviceId> {
+        self.network_model
+            .device_map()
+            .active_devices()
+            .map(|device| {
+                let distance = self.camera
+                    .project(device.position(), rect)
+                    .distance(pointer_position);
+
+                (device.id(), distance)
+            })
+            .filter(|(_, distance)| *distance <= PICK_RADIUS)
+            .min_by(|(_, distance1), (_, distance2)|
+                distance1.total_cmp(distance2)
+            )
+            .map(|(device_id, _)| device_id)
+    }
+
+    fn draw_ground(&self, painter: &Painter, rect: Rect) {
+        let corners = [
+            Point3D::new(0.0, 0.0, 0.0),
+            Point3D::new(SCENE_SIZE, 0.0, 0.0),
+            Point3D::new(SCENE_SIZE, SCENE_SIZE, 0.0),
+            Point3D::new(0.0, SCENE_SIZE, 0.0),
+        ];
+        let stroke = Stroke::new(1.0, GROUND_COLOR);
+
+        for (index, corner) in corners.iter().enumerate() {
+            let next_corner = &corners[(index + 1) % corners.len()];
+
+            painter.line_segment(
+                [
+                    self.camera.project(corner, rect),
+                    self.camera.project(next_corner, rect)
+                ],
+                stroke
+            );
+        }
+
+        painter.line_segment(
+            [
+                self.camera.project(&corners[0], rect),
+                self.camera.project(&Point3D::new(0.0, 0.0, SCENE_SIZE), rect)
+            ],
+            stroke
+        );
+    }
+
+    fn draw_links(&self, painter: &Painter, rect: Rect) {
+        let device_map = self.network_model.device_map();
+        let stroke = Stroke::new(1.0, LINK_COLOR);
+
+        for (tx_id, rx_id, _) in self.network_model
+            .connections()
+            .graph_map()
+            .all_edges()
+            .filter(|(tx_id, rx_id, _)| tx_id < rx_id)
+        {
+            let (Some(tx), Some(rx)) = (
+                device_map.get(&tx_id),
+                device_map.get(&rx_id)
+            ) else {
+                continue;
+            };
+
+            painter.line_segment(
+                [
+                    self.camera.project(tx.position(), rect),
+                    self.camera.project(rx.position(), rect)
+                ],
+                stroke
+            );
+        }
+    }
+
+    fn draw_attacker_devices(&self, painter: &Painter, rect: Rect) {
+        let scale = self.camera.scale(rect);
+
+        for attacker_device in self.network_model.attacker_devices() {
+            let device = attacker_device.device();
+            let center = self.camera.project(device.position(), rect);
+
+            painter.circle_filled(center, DEVICE_RADIUS, ATTACKER_DEVICE_COLOR);
+
+            for frequency in [Frequency::Control, Frequency::Backup]
+                .into_iter()
+                .chain(Frequency::GPS_BANDS)
+            {
+                let radius = device.area_radius_on(frequency);
+
+                if radius > Meter::ZERO {
+                    painter.circle_stroke(
+                        center,
+                        float_to_f32(radius.value()) * scale,
+                        Stroke::new(1.0, ATTACKER_DEVICE_COLOR)
+                    );
+                }
+            }
+        }
+    }
+
+    fn draw_devices(&self, painter: &Painter, rect: Rect) {
+        let command_device_id = self.network_model.command_device_id();
+
+        for (device_id, device) in self.network_model.device_map().iter() {
+            if device.is_shut_down() {
+                continue;
+            }
+
+            let center = self.camera.project(device.position(), rect);
+            let (radius, color) = if device_id == command_device_id {
+                (COMMAND_DEVICE_RADIUS, COMMAND_DEVICE_COLOR)
+            } else if device.is_infected() {
+                (DEVICE_RADIUS, INFECTED_DEVICE_COLOR)
+            } else {
+                (DEVICE_RADIUS, DEVICE_COLOR)
+            };
+
+            painter.circle_filled(center, radius, color);
+
+            if self.selected_device_id == Some(device_id) {
+                painter.circle_stroke(
+                    center,
+                    2.0 * radius,
+                    Stroke::new(2.0, SELECTION_COLOR)
+                );
+            }
+        }
+    }
+}
+
+impl eframe::App for LiveViewer {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if self.playing {
+            self.step();
+            self.playing = !self.is_finished();
+            ctx.request_repaint();
+        }
+
+        egui::TopBottomPanel::top("controls")
+            .show(ctx, |ui| self.show_controls(ui));
+        egui::SidePanel::right("inspector")
+            .show(ctx, |ui| self.show_inspector(ui));
+        egui::CentralPanel::default()
+            .show(ctx, |ui| self.show_scene(ui));
+    }
+}
+
+
+fn device_properties(device: &Device) -> Vec<(&'static str, String)> {
+    let position = device.position();
+
+    vec![
+        ("ID", device.id().to_string()),
+        (
+            "Position",
+            format!("{:.1}, {:.1}, {:.1}", position.x, position.y, position.z)
+        ),
+        ("Speed", format!("{:.1} m/s", device.velocity().size())),
+        ("Task", format!("{:?}", device.task())),
+        (
+            "Battery",
+            format!("{:.0}%", device.power_system().level() * 100.0)
+        ),
+        ("Health", format!("{:.0}%", device.health() * 100.0)),
+        ("Infected", device.is_infected().to_string()),
+    ]
+}
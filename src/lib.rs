@@ -0,0 +1,20 @@
+//! Models drone networks under electronic warfare and malware attacks.
+//!
+//! The stable API is [`prelude`] and the items re-exported here, which
+//! follow semantic versioning. Other modules are public for the frontends
+//! and may change between minor versions.
+
+pub mod backend;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "native")]
+pub mod frontend;
+pub mod prelude;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+
+pub use backend::metrics::Metrics;
+pub use error::{Error, Result};
+pub use backend::networkmodel::{NetworkModel, NetworkModelBuilder};
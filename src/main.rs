@@ -1,8 +1,4 @@
-use crate::frontend::cli::cli;
-
-
-pub mod backend;
-pub mod frontend;
+use drone_network::frontend::cli::cli;
 
 
 fn main() {
@@ -0,0 +1,31 @@
+//! Types most models are built and inspected with, so external crates can
+//! depend on `use drone_network::prelude::*` instead of module paths.
+//! Removing or changing an item here is a breaking change, while modules
+//! under `backend` may still be reorganized in minor versions.
+
+pub use crate::backend::ITERATION_TIME;
+pub use crate::backend::connections::Topology;
+pub use crate::backend::device::{
+    device_map_from_slice, Device, DeviceBuilder, DeviceId, DeviceTemplates,
+    IdToDeviceMap, NeighborView, SignalLossResponse, TemplateError, WorldView,
+    BROADCAST_ID
+};
+pub use crate::backend::device::systems::{
+    MovementSystem, PowerSystem, RXModule, SecuritySystem, TRXSystem, TXModule
+};
+pub use crate::backend::event::{Event, EventKind};
+pub use crate::backend::malware::{Malware, MalwareType};
+pub use crate::backend::mathphysics::{
+    Float, Frequency, Megahertz, Meter, Millisecond, Point3D, Position,
+    PowerUnit, Vector3D
+};
+pub use crate::backend::metrics::Metrics;
+pub use crate::backend::networkmodel::{
+    BuildError, NetworkModel, NetworkModelBuilder
+};
+pub use crate::backend::networkmodel::attack::{AttackType, AttackerDevice};
+pub use crate::backend::networkmodel::gps::GPS;
+pub use crate::backend::networkmodel::run::{DeviceState, IterationState};
+pub use crate::backend::signal::{FreqToStrengthMap, SignalStrength};
+pub use crate::backend::task::{Scenario, Task};
+pub use crate::error::Error;
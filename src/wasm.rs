@@ -0,0 +1,179 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::backend::device::{Device, DeviceId};
+use crate::backend::mathphysics::{Float, Millisecond, Position, Radian};
+use crate::backend::metrics::Metrics;
+use crate::backend::networkmodel::NetworkModel;
+use crate::backend::random;
+
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(value)
+        .map_err(|error| JsError::new(&error.to_string()))
+}
+
+
+// The part of a device a viewer needs on every frame, so whole devices do
+// not have to be converted.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceState {
+    id: DeviceId,
+    position: [Float; 3],
+    yaw: Radian,
+    battery_level: f32,
+    infected: bool,
+    shut_down: bool,
+}
+
+impl From<&Device> for DeviceState {
+    fn from(device: &Device) -> Self {
+        let position = device.position();
+
+        Self {
+            id: device.id(),
+            position: [position.x, position.y, position.z],
+            yaw: device.orientation().yaw,
+            battery_level: device.power_system().level(),
+            infected: device.is_infected(),
+            shut_down: device.is_shut_down(),
+        }
+    }
+}
+
+
+// Network model stepped from JavaScript. Models are created from the JSON
+// written by `drone_network generate` or the `--jo` output.
+#[wasm_bindgen(js_name = NetworkModel)]
+pub struct WasmNetworkModel(NetworkModel);
+
+#[wasm_bindgen(js_class = NetworkModel)]
+impl WasmNetworkModel {
+    /// # Errors
+    ///
+    /// Will return `Err` if the JSON does not describe a network model.
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<WasmNetworkModel, JsError> {
+        serde_json::from_str(json)
+            .map(Self)
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the model can not be serialized.
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> Result<String, JsError> {
+        self.0
+            .to_json()
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+
+    pub fn update(&mut self, iterations: u32) {
+        for _ in 0..iterations {
+            self.0.update();
+        }
+    }
+
+    // Updates the model until its time advances by `duration` milliseconds,
+    // e.g. the time between animation frames.
+    #[wasm_bindgen(js_name = runFor)]
+    pub fn run_for(&mut self, duration: i32) {
+        let end_time = self.0.current_time() + Millisecond::new(duration);
+
+        while self.0.current_time() < end_time {
+            self.0.update();
+        }
+    }
+
+    #[must_use]
+    #[wasm_bindgen(getter, js_name = currentTime)]
+    pub fn current_time(&self) -> i32 {
+        self.0.current_time().value()
+    }
+
+    #[must_use]
+    #[wasm_bindgen(getter, js_name = commandDeviceId)]
+    pub fn command_device_id(&self) -> DeviceId {
+        self.0.command_device_id()
+    }
+
+    #[must_use]
+    #[wasm_bindgen(js_name = deviceIds)]
+    pub fn device_ids(&self) -> Vec<DeviceId> {
+        let mut device_ids: Vec<DeviceId> = self.0
+            .device_map()
+            .values()
+            .map(Device::id)
+            .collect();
+
+        device_ids.sort_unstable();
+
+        device_ids
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the device does not exist.
+    pub fn device(&self, device_id: DeviceId) -> Result<JsValue, JsError> {
+        let device = self.0
+            .device_map()
+            .get(&device_id)
+            .ok_or_else(||
+                JsError::new(&format!("Device {device_id} does not exist"))
+            )?;
+
+        to_js(device)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the states can not be converted.
+    #[wasm_bindgen(js_name = deviceStates)]
+    pub fn device_states(&self) -> Result<JsValue, JsError> {
+        let device_states: Vec<DeviceState> = self.0
+            .device_map()
+            .values()
+            .map(DeviceState::from)
+            .collect();
+
+        to_js(&device_states)
+    }
+
+    // Connected device pairs as `[source, destination]` arrays.
+    /// # Errors
+    ///
+    /// Will return `Err` if the connections can not be converted.
+    pub fn connections(&self) -> Result<JsValue, JsError> {
+        let connections: Vec<(DeviceId, DeviceId)> = self.0
+            .connections()
+            .graph_map()
+            .all_edges()
+            .map(|(source, destination, _)| (source, destination))
+            .collect();
+
+        to_js(&connections)
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the events can not be converted.
+    pub fn events(&self) -> Result<JsValue, JsError> {
+        to_js(&self.0.events())
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the metrics can not be converted.
+    pub fn metrics(&self) -> Result<JsValue, JsError> {
+        to_js(&Metrics::from_model(&self.0))
+    }
+}
+
+
+// The seed is a `BigInt` in JavaScript.
+#[wasm_bindgen(js_name = setSeed)]
+pub fn set_seed(seed: u64) {
+    random::set_seed(seed);
+}